@@ -1,6 +1,6 @@
 //! Long-polling update loop and Channel trait implementation.
 
-use super::types::{TgFile, TgResponse, TgUpdate};
+use super::types::{TgCallbackQuery, TgFile, TgResponse, TgUpdate, TgUser};
 use super::TelegramChannel;
 use async_trait::async_trait;
 use omega_core::{
@@ -89,6 +89,18 @@ impl Channel for TelegramChannel {
                 }
 
                 for update in updates {
+                    if let Some(query) = update.callback_query {
+                        answer_callback_query(&client, &base_url, &query.id).await;
+                        let Some(incoming) = callback_incoming(query, &allowed_users) else {
+                            continue;
+                        };
+                        if tx.send(incoming).await.is_err() {
+                            info!("telegram channel receiver dropped, stopping poll");
+                            return;
+                        }
+                        continue;
+                    }
+
                     let msg = match update.message {
                         Some(m) => m,
                         None => continue,
@@ -199,19 +211,11 @@ impl Channel for TelegramChannel {
                         continue;
                     }
 
-                    let sender_name = if let Some(ref un) = user.username {
-                        format!("@{un}")
-                    } else if let Some(ref ln) = user.last_name {
-                        format!("{} {ln}", user.first_name)
-                    } else {
-                        user.first_name.clone()
-                    };
-
                     let incoming = IncomingMessage {
                         id: Uuid::new_v4(),
                         channel: "telegram".to_string(),
                         sender_id: user.id.to_string(),
-                        sender_name: Some(sender_name),
+                        sender_name: Some(display_name(&user)),
                         text,
                         timestamp: chrono::Utc::now(),
                         reply_to: None,
@@ -252,6 +256,20 @@ impl Channel for TelegramChannel {
         self.send_photo_bytes(chat_id, image, caption).await
     }
 
+    async fn send_document(
+        &self,
+        target: &str,
+        file_name: &str,
+        bytes: &[u8],
+        caption: &str,
+    ) -> Result<(), OmegaError> {
+        let chat_id: i64 = target.parse().map_err(|e| {
+            OmegaError::Channel(format!("invalid telegram chat_id '{target}': {e}"))
+        })?;
+        self.send_document_bytes(chat_id, file_name, bytes, caption)
+            .await
+    }
+
     async fn delete_message(&self, target: &str, message_id: &str) -> Result<(), OmegaError> {
         let chat_id: i64 = target.parse().map_err(|e| {
             OmegaError::Channel(format!("invalid telegram chat_id '{target}': {e}"))
@@ -273,7 +291,10 @@ impl Channel for TelegramChannel {
             OmegaError::Channel(format!("invalid telegram chat_id '{chat_id_str}': {e}"))
         })?;
 
-        if message.plain_text {
+        if !message.buttons.is_empty() {
+            self.send_text_with_buttons(chat_id, &message.text, &message.buttons)
+                .await
+        } else if message.plain_text {
             self.send_text_plain(chat_id, &message.text).await
         } else {
             self.send_text(chat_id, &message.text).await
@@ -290,6 +311,61 @@ impl Channel for TelegramChannel {
     }
 }
 
+/// `@username`, or the full name when the user has none.
+fn display_name(user: &TgUser) -> String {
+    if let Some(ref un) = user.username {
+        format!("@{un}")
+    } else if let Some(ref ln) = user.last_name {
+        format!("{} {ln}", user.first_name)
+    } else {
+        user.first_name.clone()
+    }
+}
+
+/// Turn an inline button press into an incoming message carrying the
+/// button's command, under the same auth and no-groups rules as messages.
+pub(super) fn callback_incoming(
+    query: TgCallbackQuery,
+    allowed_users: &[i64],
+) -> Option<IncomingMessage> {
+    let text = query.data?;
+    let chat = query.message?.chat;
+    if !allowed_users.is_empty() && !allowed_users.contains(&query.from.id) {
+        warn!(
+            "ignoring button press from unauthorized user {}",
+            query.from.id
+        );
+        return None;
+    }
+    if matches!(chat.chat_type.as_str(), "group" | "supergroup") {
+        return None;
+    }
+    Some(IncomingMessage {
+        id: Uuid::new_v4(),
+        channel: "telegram".to_string(),
+        sender_id: query.from.id.to_string(),
+        sender_name: Some(display_name(&query.from)),
+        text,
+        timestamp: chrono::Utc::now(),
+        reply_to: None,
+        attachments: Vec::new(),
+        reply_target: Some(chat.id.to_string()),
+        is_group: false,
+        source: None,
+        platform_message_id: None,
+    })
+}
+
+/// Acknowledge a button press so the client stops its loading spinner.
+/// Best-effort: a failure only leaves the spinner running.
+async fn answer_callback_query(client: &reqwest::Client, base_url: &str, query_id: &str) {
+    let url = format!("{base_url}/answerCallbackQuery");
+    let body = serde_json::json!({ "callback_query_id": query_id });
+    if let Err(e) = client.post(&url).json(&body).send().await {
+        warn!("telegram answerCallbackQuery failed: {e}");
+    }
+}
+
 /// Download a file from Telegram servers by file_id.
 async fn download_telegram_file(
     client: &reqwest::Client,
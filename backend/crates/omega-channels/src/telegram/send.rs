@@ -2,7 +2,7 @@
 
 use super::TelegramChannel;
 use crate::utils::split_message;
-use omega_core::error::OmegaError;
+use omega_core::{error::OmegaError, message::ReplyButton};
 use tracing::{info, warn};
 
 impl TelegramChannel {
@@ -95,6 +95,45 @@ impl TelegramChannel {
         Ok(())
     }
 
+    /// Send plain text with an inline keyboard under its last chunk.
+    pub(crate) async fn send_text_with_buttons(
+        &self,
+        chat_id: i64,
+        text: &str,
+        buttons: &[ReplyButton],
+    ) -> Result<(), OmegaError> {
+        let chunks = split_message(text, 4096);
+        let Some((last, rest)) = chunks.split_last() else {
+            return Ok(());
+        };
+        for chunk in rest {
+            self.send_text_plain(chat_id, chunk).await?;
+        }
+
+        let url = format!("{}/sendMessage", self.base_url);
+        let body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": last,
+            "reply_markup": inline_keyboard(buttons),
+        });
+        let resp = self
+            .client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| OmegaError::Channel(format!("telegram send failed: {e}")))?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(OmegaError::Channel(format!(
+                "telegram send (buttons) failed ({status}): {error_text}"
+            )));
+        }
+        Ok(())
+    }
+
     /// Send a photo (PNG bytes) with a caption to a chat.
     pub(crate) async fn send_photo_bytes(
         &self,
@@ -133,6 +172,42 @@ impl TelegramChannel {
         Ok(())
     }
 
+    /// Send a file as a document with a caption to a chat.
+    pub(crate) async fn send_document_bytes(
+        &self,
+        chat_id: i64,
+        file_name: &str,
+        bytes: &[u8],
+        caption: &str,
+    ) -> Result<(), OmegaError> {
+        let url = format!("{}/sendDocument", self.base_url);
+
+        let part = reqwest::multipart::Part::bytes(bytes.to_vec()).file_name(file_name.to_string());
+
+        let form = reqwest::multipart::Form::new()
+            .text("chat_id", chat_id.to_string())
+            .text("caption", caption.to_string())
+            .part("document", part);
+
+        let resp = self
+            .client
+            .post(&url)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| OmegaError::Channel(format!("telegram sendDocument failed: {e}")))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_text = resp.text().await.unwrap_or_default();
+            return Err(OmegaError::Channel(format!(
+                "telegram sendDocument failed ({status}): {error_text}"
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Register bot commands with Telegram so users see an autocomplete menu.
     /// Best-effort: logs failures but does not propagate errors.
     pub(crate) async fn register_commands(&self) {
@@ -144,8 +219,14 @@ impl TelegramChannel {
                 { "command": "history", "description": "Last 5 conversation summaries" },
                 { "command": "facts", "description": "List known facts about you" },
                 { "command": "forget", "description": "Clear current conversation" },
+                { "command": "retry", "description": "Answer your last message again" },
+                { "command": "undo", "description": "Remove the last exchange" },
+                { "command": "snapshot", "description": "Save or list conversation snapshots" },
+                { "command": "branch", "description": "Continue from a saved snapshot" },
                 { "command": "tasks", "description": "List your scheduled tasks" },
                 { "command": "cancel", "description": "Cancel a task by ID" },
+                { "command": "stop", "description": "Stop what I'm working on right now" },
+                { "command": "queue", "description": "How messages sent while I'm busy are handled" },
                 { "command": "language", "description": "Show or set your language" },
                 { "command": "personality", "description": "Show or set how I behave" },
                 { "command": "skills", "description": "List available skills" },
@@ -153,10 +234,15 @@ impl TelegramChannel {
                 { "command": "project", "description": "Show, activate, or deactivate a project" },
                 { "command": "purge", "description": "Delete all learned facts (clean slate)" },
                 { "command": "whatsapp", "description": "Connect WhatsApp via QR code" },
-                { "command": "heartbeat", "description": "Heartbeat status and watchlist" },
+                { "command": "heartbeat", "description": "Your heartbeat: status, schedule, watchlist" },
+                { "command": "link", "description": "Link your Telegram and WhatsApp accounts" },
+                { "command": "unlink", "description": "Detach a linked account" },
+                { "command": "export", "description": "Download everything I remember about you" },
                 { "command": "learning", "description": "Show what I've learned from you" },
                 { "command": "google", "description": "Connect your Google account" },
                 { "command": "setup", "description": "Configure OMEGA \u{03a9} as a domain expert" },
+                { "command": "build", "description": "Build status, or resume an interrupted build" },
+                { "command": "topologies", "description": "List installed build topologies" },
             ]
         });
 
@@ -220,3 +306,13 @@ impl TelegramChannel {
         Ok(())
     }
 }
+
+/// `reply_markup` for `buttons`, laid out in a single row. Each button's
+/// command comes back as the callback query's `data`.
+pub(crate) fn inline_keyboard(buttons: &[ReplyButton]) -> serde_json::Value {
+    let row: Vec<serde_json::Value> = buttons
+        .iter()
+        .map(|b| serde_json::json!({ "text": b.label, "callback_data": b.command }))
+        .collect();
+    serde_json::json!({ "inline_keyboard": [row] })
+}
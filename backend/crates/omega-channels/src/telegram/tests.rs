@@ -1,7 +1,10 @@
 //! Tests for the Telegram channel module.
 
+use super::polling::callback_incoming;
+use super::send::inline_keyboard;
 use super::types::*;
 use crate::utils::split_message;
+use omega_core::message::ReplyButton;
 
 #[test]
 fn test_split_short_message() {
@@ -150,3 +153,49 @@ fn test_split_message_emoji_boundary() {
     let reassembled: String = chunks.iter().copied().collect();
     assert_eq!(reassembled, text);
 }
+
+fn button_press(from: i64, chat_type: &str) -> TgUpdate {
+    let json = format!(
+        r#"{{
+        "update_id": 7,
+        "callback_query": {{
+            "id": "q1",
+            "from": {{"id": {from}, "first_name": "Ana", "username": "ana"}},
+            "message": {{"message_id": 3, "chat": {{"id": 100, "type": "{chat_type}"}}}},
+            "data": "/stop"
+        }}
+    }}"#
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn test_callback_query_becomes_command_message() {
+    let update = button_press(42, "private");
+    assert!(update.message.is_none());
+    let incoming = callback_incoming(update.callback_query.unwrap(), &[]).unwrap();
+    assert_eq!(incoming.text, "/stop");
+    assert_eq!(incoming.sender_id, "42");
+    assert_eq!(incoming.sender_name.as_deref(), Some("@ana"));
+    assert_eq!(incoming.reply_target.as_deref(), Some("100"));
+}
+
+#[test]
+fn test_callback_query_respects_auth_and_groups() {
+    let query = button_press(42, "private").callback_query.unwrap();
+    assert!(callback_incoming(query, &[7]).is_none());
+    let query = button_press(42, "group").callback_query.unwrap();
+    assert!(callback_incoming(query, &[]).is_none());
+}
+
+#[test]
+fn test_inline_keyboard_layout() {
+    let markup = inline_keyboard(&[ReplyButton {
+        label: "Stop".to_string(),
+        command: "/stop".to_string(),
+    }]);
+    assert_eq!(
+        markup,
+        serde_json::json!({"inline_keyboard": [[{"text": "Stop", "callback_data": "/stop"}]]})
+    );
+}
@@ -13,6 +13,18 @@ pub(crate) struct TgResponse<T> {
 pub(crate) struct TgUpdate {
     pub update_id: i64,
     pub message: Option<TgMessage>,
+    pub callback_query: Option<TgCallbackQuery>,
+}
+
+/// A press on an inline keyboard button.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TgCallbackQuery {
+    pub id: String,
+    pub from: TgUser,
+    /// The message the button is attached to.
+    pub message: Option<TgMessage>,
+    /// `callback_data` of the pressed button.
+    pub data: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -70,6 +70,61 @@ impl WhatsAppChannel {
         Ok(())
     }
 
+    /// Send a file as a document message with a caption to a JID.
+    async fn send_document_impl(
+        &self,
+        jid_str: &str,
+        file_name: &str,
+        bytes: &[u8],
+        caption: &str,
+    ) -> Result<(), OmegaError> {
+        let client = {
+            let guard = self.client.lock().await;
+            guard
+                .as_ref()
+                .ok_or_else(|| OmegaError::Channel("whatsapp client not connected".into()))?
+                .clone()
+        };
+
+        let jid: Jid = jid_str
+            .parse()
+            .map_err(|e| OmegaError::Channel(format!("invalid whatsapp JID '{jid_str}': {e}")))?;
+
+        let upload = client
+            .upload(bytes.to_vec(), whatsapp_rust::download::MediaType::Document)
+            .await
+            .map_err(|e| OmegaError::Channel(format!("whatsapp document upload failed: {e}")))?;
+
+        let msg = waproto::whatsapp::Message {
+            document_message: Some(Box::new(waproto::whatsapp::message::DocumentMessage {
+                mimetype: Some(document_mime(file_name).to_string()),
+                file_name: Some(file_name.to_string()),
+                title: Some(file_name.to_string()),
+                caption: Some(caption.to_string()),
+                url: Some(upload.url),
+                direct_path: Some(upload.direct_path),
+                media_key: Some(upload.media_key),
+                file_enc_sha256: Some(upload.file_enc_sha256),
+                file_sha256: Some(upload.file_sha256),
+                file_length: Some(upload.file_length),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let msg_id = retry_send(&client, &jid, msg).await?;
+        {
+            let mut ids = self.sent_ids.lock().await;
+            if ids.len() >= MAX_SENT_IDS {
+                warn!("whatsapp: sent_ids reached {MAX_SENT_IDS}, clearing stale entries");
+                ids.clear();
+            }
+            ids.insert(msg_id);
+        }
+
+        Ok(())
+    }
+
     /// Send a text message to a JID string (phone@s.whatsapp.net).
     async fn send_text(&self, jid_str: &str, text: &str) -> Result<(), OmegaError> {
         let client = {
@@ -144,6 +199,17 @@ impl Channel for WhatsAppChannel {
         self.send_photo_impl(target, image, caption).await
     }
 
+    async fn send_document(
+        &self,
+        target: &str,
+        file_name: &str,
+        bytes: &[u8],
+        caption: &str,
+    ) -> Result<(), OmegaError> {
+        self.send_document_impl(target, file_name, bytes, caption)
+            .await
+    }
+
     async fn send(&self, message: OutgoingMessage) -> Result<(), OmegaError> {
         let target = message
             .reply_target
@@ -165,3 +231,15 @@ impl Channel for WhatsAppChannel {
 }
 
 use tokio::sync::mpsc;
+
+/// MIME type for a document by file extension (WhatsApp requires one).
+fn document_mime(file_name: &str) -> &'static str {
+    match file_name.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("zip") => "application/zip",
+        Some("xml") => "application/xml",
+        Some("json") => "application/json",
+        Some("html") => "text/html",
+        Some("txt" | "log" | "md" | "info") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
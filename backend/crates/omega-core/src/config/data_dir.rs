@@ -0,0 +1,123 @@
+//! Data directory layout — the single place where `data_dir` becomes paths.
+//!
+//! Every file-backed subsystem (prompts, heartbeat checklists, suppress lists,
+//! projects, skills, setup sessions, inbox, media) resolves its location through
+//! [`DataDir`], so two instances with different `[omega] data_dir` values never
+//! share files.
+
+use std::path::{Path, PathBuf};
+
+use super::shellexpand;
+
+/// Resolved `[omega] data_dir` with accessors for each subsystem's location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    /// Resolve a configured data dir (tilde-expanded).
+    pub fn new(data_dir: &str) -> Self {
+        Self {
+            root: PathBuf::from(shellexpand(data_dir)),
+        }
+    }
+
+    /// The data dir itself (e.g. `~/.omega`).
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// `{data_dir}/prompts/`.
+    pub fn prompts(&self) -> PathBuf {
+        self.root.join("prompts")
+    }
+
+    /// `{data_dir}/prompts/HEARTBEAT.md` — the owner's seed checklist.
+    pub fn heartbeat_file(&self) -> PathBuf {
+        self.prompts().join("HEARTBEAT.md")
+    }
+
+    /// `{data_dir}/projects/`.
+    pub fn projects(&self) -> PathBuf {
+        self.root.join("projects")
+    }
+
+    /// `{data_dir}/projects/<name>/`.
+    pub fn project(&self, name: &str) -> PathBuf {
+        self.projects().join(name)
+    }
+
+    /// `{data_dir}/projects/<name>/HEARTBEAT.md`.
+    pub fn project_heartbeat_file(&self, name: &str) -> PathBuf {
+        self.project(name).join("HEARTBEAT.md")
+    }
+
+    /// Suppress list for a sender's personal (`None`) or a project heartbeat.
+    ///
+    /// Personal: `{data_dir}/prompts/suppress/<sender_id>.suppress`.
+    /// Project: `{data_dir}/projects/<name>/HEARTBEAT.suppress`.
+    pub fn heartbeat_suppress_file(&self, sender_id: &str, project: Option<&str>) -> PathBuf {
+        match project {
+            Some(name) if !name.is_empty() => self.project(name).join("HEARTBEAT.suppress"),
+            _ => self
+                .prompts()
+                .join("suppress")
+                .join(format!("{sender_id}.suppress")),
+        }
+    }
+
+    /// `{data_dir}/skills/`.
+    pub fn skills(&self) -> PathBuf {
+        self.root.join("skills")
+    }
+
+    /// `{data_dir}/workspace/` — the AI subprocess working directory.
+    pub fn workspace(&self) -> PathBuf {
+        self.root.join("workspace")
+    }
+
+    /// `{data_dir}/workspace/inbox/` — incoming attachments.
+    pub fn inbox(&self) -> PathBuf {
+        self.workspace().join("inbox")
+    }
+
+    /// `{data_dir}/workspace/media/` — attachments kept for follow-up questions.
+    pub fn media(&self) -> PathBuf {
+        self.workspace().join("media")
+    }
+
+    /// `{data_dir}/tenants/<name>/` as a data dir of its own.
+    ///
+    /// A tenant's `workspace()`, `projects()` and `skills()` resolve inside it.
+    pub fn tenant(&self, name: &str) -> DataDir {
+        Self {
+            root: self.root.join("tenants").join(name),
+        }
+    }
+
+    /// `{data_dir}/backups/` — scheduled database snapshots.
+    pub fn backups(&self) -> PathBuf {
+        self.root.join("backups")
+    }
+
+    /// `{data_dir}/exports/` — `/export` archives.
+    pub fn exports(&self) -> PathBuf {
+        self.root.join("exports")
+    }
+
+    /// `{data_dir}/master.key` — default encryption key file.
+    pub fn master_key(&self) -> PathBuf {
+        self.root.join("master.key")
+    }
+
+    /// `{data_dir}/secrets.enc` — the encrypted secrets store.
+    pub fn secrets(&self) -> PathBuf {
+        self.root.join("secrets.enc")
+    }
+
+    /// `{data_dir}/setup/<sender_id>.md` — a `/setup` session's context file.
+    pub fn setup_context(&self, sender_id: &str) -> PathBuf {
+        self.root.join("setup").join(format!("{sender_id}.md"))
+    }
+}
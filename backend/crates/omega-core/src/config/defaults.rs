@@ -68,6 +68,26 @@ pub fn default_max_context() -> usize {
     50
 }
 
+pub fn default_context_tokens() -> usize {
+    32_000
+}
+
+pub fn default_maintenance_interval() -> u64 {
+    24
+}
+
+pub fn default_keep_backups() -> usize {
+    7
+}
+
+pub fn default_audit_retention() -> u32 {
+    90
+}
+
+pub fn default_outcome_retention() -> u32 {
+    90
+}
+
 pub fn default_heartbeat_interval() -> u64 {
     30
 }
@@ -99,3 +119,22 @@ pub fn default_model() -> String {
 pub fn default_model_complex() -> String {
     "claude-opus-4-6".to_string()
 }
+
+pub fn default_approval_bash_patterns() -> Vec<String> {
+    ["rm -rf", "rm -fr", "git push", "sudo ", "mkfs", "dd if="]
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+pub fn default_approval_timeout_secs() -> u64 {
+    120
+}
+
+pub fn default_allow_all() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+pub fn default_key_source() -> String {
+    "file".to_string()
+}
@@ -2,7 +2,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use tracing::warn;
 
-use super::shellexpand;
+use super::DataDir;
 
 /// Externalized prompts and welcome messages, loaded from `~/.omega/` at startup.
 ///
@@ -157,8 +157,7 @@ pub fn bundled_workspace_claude() -> &'static str {
 ///
 /// Never overwrites existing files so user edits are preserved.
 pub fn install_bundled_prompts(data_dir: &str) {
-    let expanded = shellexpand(data_dir);
-    let dir = std::path::Path::new(&expanded).join("prompts");
+    let dir = DataDir::new(data_dir).prompts();
     if let Err(e) = std::fs::create_dir_all(&dir) {
         warn!("prompts: failed to create {}: {e}", dir.display());
         return;
@@ -189,10 +188,10 @@ impl Prompts {
     /// Missing files or sections fall back to defaults.
     pub fn load(data_dir: &str) -> Self {
         let mut prompts = Self::default();
-        let dir = shellexpand(data_dir);
+        let dir = DataDir::new(data_dir).prompts();
 
         // Load SYSTEM_PROMPT.md
-        let prompt_path = format!("{dir}/prompts/SYSTEM_PROMPT.md");
+        let prompt_path = dir.join("SYSTEM_PROMPT.md").display().to_string();
         if let Ok(content) = std::fs::read_to_string(&prompt_path) {
             let all = parse_markdown_sections(&content);
 
@@ -213,7 +212,7 @@ impl Prompts {
         }
 
         // Load WELCOME.toml
-        let welcome_path = format!("{dir}/prompts/WELCOME.toml");
+        let welcome_path = dir.join("WELCOME.toml").display().to_string();
         if let Ok(content) = std::fs::read_to_string(&welcome_path) {
             match toml::from_str::<WelcomeFile>(&content) {
                 Ok(w) => {
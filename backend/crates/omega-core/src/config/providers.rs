@@ -69,6 +69,26 @@ pub struct AnthropicConfig {
     pub model: String,
     #[serde(default = "default_anthropic_max_tokens")]
     pub max_tokens: u32,
+    /// Model for multi-step work such as builds (empty = same as `model`).
+    #[serde(default)]
+    pub model_complex: String,
+    /// Put `cache_control` breakpoints on the stable prompt prefix.
+    #[serde(default = "default_true")]
+    pub prompt_caching: bool,
+    /// Extended thinking budget in tokens for `model_complex` calls (0 = off).
+    #[serde(default)]
+    pub thinking_budget: u32,
+}
+
+impl AnthropicConfig {
+    /// The complex model, falling back to `model`.
+    pub fn model_complex(&self) -> &str {
+        if self.model_complex.is_empty() {
+            &self.model
+        } else {
+            &self.model_complex
+        }
+    }
 }
 
 fn default_anthropic_max_tokens() -> u32 {
@@ -0,0 +1,160 @@
+//! Named roles — per-user permissions beyond the channel allow-lists.
+//!
+//! ```toml
+//! [roles]
+//! default = "member"         # role of users not listed anywhere ("" = unrestricted)
+//!
+//! [roles.role.member]
+//! commands = ["help", "status", "project", "projects"]
+//! projects = ["real-estate"]
+//! models = ["claude-sonnet-4-6"]
+//! builds = false
+//! tools = ["Read", "Grep"]   # omit = provider default, [] = no tools
+//! users = ["123456789", "5511999887766"]
+//! ```
+//!
+//! Allow-lists take names or `"*"`; an empty list allows nothing. Users are
+//! matched by platform id or canonical (cross-channel) id.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use super::defaults::*;
+
+/// What the members of one role may use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleConfig {
+    /// Command names without the slash (`"status"`, `"purge"`).
+    #[serde(default = "default_allow_all")]
+    pub commands: Vec<String>,
+    /// Skill names whose MCP servers and instructions are offered.
+    #[serde(default = "default_allow_all")]
+    pub skills: Vec<String>,
+    /// Project names that are listed and can be activated.
+    #[serde(default = "default_allow_all")]
+    pub projects: Vec<String>,
+    /// Provider names (`"claude-code"`, `"anthropic"`, ...).
+    #[serde(default = "default_allow_all")]
+    pub providers: Vec<String>,
+    /// Models; when the configured model is not listed, the first entry is used.
+    #[serde(default = "default_allow_all")]
+    pub models: Vec<String>,
+    /// Whether the role may run builds.
+    #[serde(default = "default_true")]
+    pub builds: bool,
+    /// Tool allow-list passed to the provider (`None` = provider default).
+    #[serde(default)]
+    pub tools: Option<Vec<String>>,
+    /// Sender ids (platform ids or canonical ids) that have this role.
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+impl Default for RoleConfig {
+    /// Unrestricted — the behavior without `[roles]`.
+    fn default() -> Self {
+        Self {
+            commands: default_allow_all(),
+            skills: default_allow_all(),
+            projects: default_allow_all(),
+            providers: default_allow_all(),
+            models: default_allow_all(),
+            builds: true,
+            tools: None,
+            users: Vec::new(),
+        }
+    }
+}
+
+/// Whether an allow-list permits `name` (`"*"` permits everything).
+fn allows(list: &[String], name: &str) -> bool {
+    list.iter().any(|entry| entry == "*" || entry == name)
+}
+
+impl RoleConfig {
+    pub fn allows_command(&self, name: &str) -> bool {
+        allows(&self.commands, name)
+    }
+
+    pub fn allows_skill(&self, name: &str) -> bool {
+        allows(&self.skills, name)
+    }
+
+    pub fn allows_project(&self, name: &str) -> bool {
+        allows(&self.projects, name)
+    }
+
+    pub fn allows_provider(&self, name: &str) -> bool {
+        allows(&self.providers, name)
+    }
+
+    /// The model to use instead of `model`: `model` itself when allowed,
+    /// otherwise the first listed model (`None` when the list is empty).
+    pub fn model_for(&self, model: &str) -> Option<String> {
+        if allows(&self.models, model) {
+            Some(model.to_string())
+        } else {
+            self.models.first().cloned()
+        }
+    }
+}
+
+/// `[roles]` section. No roles defined = everyone is unrestricted.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RolesConfig {
+    /// Role of users not listed in any role (empty = unrestricted).
+    #[serde(default)]
+    pub default: String,
+    /// Roles keyed by name.
+    #[serde(default)]
+    pub role: BTreeMap<String, RoleConfig>,
+}
+
+impl RolesConfig {
+    /// Reject an undefined `default` role and users listed in several roles.
+    ///
+    /// Checked at config load, so [`Self::role_for`] never has to guess.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.default.is_empty() && !self.role.contains_key(&self.default) {
+            return Err(format!("default role '{}' is not defined", self.default));
+        }
+        let mut seen: BTreeMap<&str, &str> = BTreeMap::new();
+        for (name, role) in &self.role {
+            for user in &role.users {
+                if let Some(other) = seen.insert(user, name) {
+                    return Err(format!(
+                        "user '{user}' is listed in both role '{other}' and role '{name}'"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The role of any of `sender_ids`, as `(name, config)`.
+    ///
+    /// Pass both the platform id and the canonical id with its aliases.
+    /// Unlisted senders get the `default` role; `None` means unrestricted.
+    /// [`Self::validate`] keeps each id in one role; if linked ids sit in
+    /// different roles, the first role by name wins.
+    pub fn role_for(&self, sender_ids: &[&str]) -> Option<(&str, &RoleConfig)> {
+        if let Some((name, role)) = self
+            .role
+            .iter()
+            .find(|(_, r)| r.users.iter().any(|u| sender_ids.contains(&u.as_str())))
+        {
+            return Some((name.as_str(), role));
+        }
+        if self.default.is_empty() {
+            return None;
+        }
+        match self.role.get_key_value(&self.default) {
+            Some((name, role)) => Some((name.as_str(), role)),
+            None => {
+                warn!("roles: default role '{}' is not defined", self.default);
+                None
+            }
+        }
+    }
+}
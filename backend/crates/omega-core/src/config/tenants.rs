@@ -0,0 +1,121 @@
+//! Multi-tenant mode — which senders share a workspace, projects and skills.
+//!
+//! ```toml
+//! [tenants]
+//! enabled = true
+//! default_role = "guest"   # senders not listed in any tenant
+//!
+//! [tenants.team.ops]
+//! role = "admin"
+//! users = ["123456789", "5511999887766"]
+//! ```
+//!
+//! Each tenant's files live under `{data_dir}/tenants/<name>/` (see
+//! [`DataDir::tenant`](super::DataDir::tenant)).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+/// Access level of a tenant's members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TenantRole {
+    /// Everything, including commands that change shared state
+    /// (`/setup`, `/google`, `/whatsapp`, builds).
+    Admin,
+    /// Conversations, projects and `/purge` of their own data.
+    #[default]
+    User,
+    /// Conversations only.
+    Guest,
+}
+
+impl TenantRole {
+    /// Whether this role may change its own stored data (`/purge`).
+    pub fn can_modify(self) -> bool {
+        self != Self::Guest
+    }
+
+    /// Whether this role may run commands that write to the shared data dir
+    /// or instance-wide integrations (`/setup`, `/google`, builds).
+    pub fn is_admin(self) -> bool {
+        self == Self::Admin
+    }
+
+    /// Lowercase name as written in config.toml.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::User => "user",
+            Self::Guest => "guest",
+        }
+    }
+}
+
+/// One tenant: its members and their role.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TenantConfig {
+    #[serde(default)]
+    pub role: TenantRole,
+    /// Sender ids (platform ids or canonical ids) that belong to this tenant.
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// `[tenants]` section. Disabled = single-tenant: everyone shares the
+/// data dir and has the admin role (the pre-tenant behavior).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TenantsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Role of senders that are not listed in any tenant. They use the
+    /// shared workspace, projects and skills.
+    #[serde(default)]
+    pub default_role: TenantRole,
+    /// Tenants keyed by name (also their directory name).
+    #[serde(default)]
+    pub team: BTreeMap<String, TenantConfig>,
+}
+
+/// Tenant names become directory names: ASCII letters, digits, `-` and `_`.
+pub fn is_valid_tenant_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl TenantsConfig {
+    /// The tenant any of `sender_ids` belongs to, as `(name, config)`.
+    ///
+    /// Pass both the platform id and the canonical (cross-channel) id. Returns
+    /// `None` when tenants are disabled or no tenant lists the sender.
+    pub fn tenant_for(&self, sender_ids: &[&str]) -> Option<(&str, &TenantConfig)> {
+        if !self.enabled {
+            return None;
+        }
+        self.team
+            .iter()
+            .filter(|(name, _)| {
+                let valid = is_valid_tenant_name(name);
+                if !valid {
+                    warn!("tenants: ignoring invalid tenant name '{name}'");
+                }
+                valid
+            })
+            .find(|(_, t)| t.users.iter().any(|u| sender_ids.contains(&u.as_str())))
+            .map(|(name, t)| (name.as_str(), t))
+    }
+
+    /// Role of a sender: their tenant's role, `default_role` when unlisted,
+    /// or admin when tenants are disabled.
+    pub fn role_for(&self, sender_ids: &[&str]) -> TenantRole {
+        if !self.enabled {
+            return TenantRole::Admin;
+        }
+        self.tenant_for(sender_ids)
+            .map(|(_, t)| t.role)
+            .unwrap_or(self.default_role)
+    }
+}
@@ -182,6 +182,33 @@ fn test_gemini_config_defaults() {
     assert_eq!(cfg.model, "gemini-2.0-flash");
 }
 
+#[test]
+fn test_anthropic_config_caching_and_thinking() {
+    let cfg: AnthropicConfig = toml::from_str(
+        r#"
+        api_key = "sk-ant-test"
+        model = "claude-sonnet-4-20250514"
+    "#,
+    )
+    .unwrap();
+    assert!(cfg.prompt_caching);
+    assert_eq!(cfg.thinking_budget, 0);
+    assert_eq!(cfg.model_complex(), "claude-sonnet-4-20250514");
+
+    let cfg: AnthropicConfig = toml::from_str(
+        r#"
+        api_key = "sk-ant-test"
+        model_complex = "claude-opus-4-20250514"
+        prompt_caching = false
+        thinking_budget = 8000
+    "#,
+    )
+    .unwrap();
+    assert!(!cfg.prompt_caching);
+    assert_eq!(cfg.thinking_budget, 8000);
+    assert_eq!(cfg.model_complex(), "claude-opus-4-20250514");
+}
+
 #[test]
 fn test_whatsapp_config_with_whisper() {
     let toml_str = r#"
@@ -688,3 +715,290 @@ fn test_patch_whatsapp_enabled_preserves_other_sections() {
 
     let _ = std::fs::remove_dir_all(&tmp);
 }
+
+#[test]
+fn test_approval_config_defaults() {
+    let cfg: Config = toml::from_str("").unwrap();
+    assert!(!cfg.approval.enabled);
+    assert!(cfg.approval.writes_outside_workspace);
+    assert_eq!(cfg.approval.timeout_secs, 120);
+    assert!(cfg.approval.bash_patterns.iter().any(|p| p == "rm -rf"));
+    assert!(cfg.approval.bash_patterns.iter().any(|p| p == "git push"));
+}
+
+#[test]
+fn test_approval_config_from_toml() {
+    let toml_str = r#"
+        [approval]
+        enabled = true
+        bash_patterns = ["terraform apply"]
+        writes_outside_workspace = false
+        sensitive_tools = ["send_email"]
+        timeout_secs = 30
+    "#;
+    let cfg: Config = toml::from_str(toml_str).unwrap();
+    assert!(cfg.approval.enabled);
+    assert_eq!(cfg.approval.bash_patterns, vec!["terraform apply"]);
+    assert!(!cfg.approval.writes_outside_workspace);
+    assert_eq!(cfg.approval.sensitive_tools, vec!["send_email"]);
+    assert_eq!(cfg.approval.timeout_secs, 30);
+}
+
+#[test]
+fn test_trust_config() {
+    let cfg: Config = toml::from_str("").unwrap();
+    assert!(!cfg.trust.block_actions);
+    let cfg: Config = toml::from_str("[trust]\nblock_actions = true").unwrap();
+    assert!(cfg.trust.block_actions);
+}
+
+#[test]
+fn test_context_budget_per_model() {
+    let cfg: Config = toml::from_str("").unwrap();
+    assert_eq!(
+        cfg.memory.context_budget.tokens_for("claude-sonnet-4-6"),
+        32_000
+    );
+
+    let toml_str = r#"
+        [memory.context_budget]
+        default_tokens = 0
+        models = { "llama3" = 6000, "qwen2.5:14b" = 12000 }
+    "#;
+    let cfg: Config = toml::from_str(toml_str).unwrap();
+    let budget = &cfg.memory.context_budget;
+    assert_eq!(budget.tokens_for("llama3"), 6000);
+    assert_eq!(budget.tokens_for("llama3:8b"), 6000);
+    assert_eq!(budget.tokens_for("qwen2.5:14b"), 12000);
+    assert_eq!(budget.tokens_for("qwen2.5:7b"), 0);
+    assert_eq!(budget.tokens_for("gpt-4o"), 0);
+}
+
+#[test]
+fn test_data_dir_layout() {
+    let dd = DataDir::new("/srv/omega-staging");
+    assert_eq!(dd.root(), Path::new("/srv/omega-staging"));
+    assert_eq!(
+        dd.heartbeat_file(),
+        Path::new("/srv/omega-staging/prompts/HEARTBEAT.md")
+    );
+    assert_eq!(
+        dd.project_heartbeat_file("garden"),
+        Path::new("/srv/omega-staging/projects/garden/HEARTBEAT.md")
+    );
+    assert_eq!(dd.skills(), Path::new("/srv/omega-staging/skills"));
+    assert_eq!(dd.inbox(), Path::new("/srv/omega-staging/workspace/inbox"));
+    assert_eq!(dd.media(), Path::new("/srv/omega-staging/workspace/media"));
+    assert_eq!(dd.exports(), Path::new("/srv/omega-staging/exports"));
+    assert_eq!(dd.backups(), Path::new("/srv/omega-staging/backups"));
+    assert_eq!(
+        dd.setup_context("42"),
+        Path::new("/srv/omega-staging/setup/42.md")
+    );
+}
+
+#[test]
+fn test_data_dir_suppress_file_scopes() {
+    let dd = DataDir::new("/srv/omega");
+    assert_eq!(
+        dd.heartbeat_suppress_file("42", None),
+        Path::new("/srv/omega/prompts/suppress/42.suppress")
+    );
+    assert_eq!(
+        dd.heartbeat_suppress_file("42", Some("")),
+        Path::new("/srv/omega/prompts/suppress/42.suppress")
+    );
+    assert_ne!(
+        dd.heartbeat_suppress_file("42", None),
+        dd.heartbeat_suppress_file("43", None)
+    );
+    assert_eq!(
+        dd.heartbeat_suppress_file("42", Some("garden")),
+        Path::new("/srv/omega/projects/garden/HEARTBEAT.suppress")
+    );
+}
+
+#[test]
+fn test_data_dir_instances_do_not_overlap() {
+    let staging = DataDir::new("/srv/omega-staging");
+    let prod = DataDir::new("/srv/omega-prod");
+    assert_ne!(staging.heartbeat_file(), prod.heartbeat_file());
+    assert_ne!(
+        staging.heartbeat_suppress_file("42", Some("garden")),
+        prod.heartbeat_suppress_file("42", Some("garden"))
+    );
+}
+
+#[test]
+fn test_data_dir_tenant_layout() {
+    let tenant = DataDir::new("/srv/omega").tenant("ops");
+    assert_eq!(tenant.root(), Path::new("/srv/omega/tenants/ops"));
+    assert_eq!(
+        tenant.workspace(),
+        Path::new("/srv/omega/tenants/ops/workspace")
+    );
+    assert_eq!(
+        tenant.projects(),
+        Path::new("/srv/omega/tenants/ops/projects")
+    );
+    assert_eq!(tenant.skills(), Path::new("/srv/omega/tenants/ops/skills"));
+}
+
+#[test]
+fn test_tenants_config_parse_and_resolve() {
+    let toml_str = r#"
+[tenants]
+enabled = true
+default_role = "guest"
+
+[tenants.team.ops]
+role = "admin"
+users = ["111", "5511999887766"]
+
+[tenants.team.sales]
+users = ["222"]
+"#;
+    let cfg: Config = toml::from_str(toml_str).unwrap();
+    let t = &cfg.tenants;
+    assert_eq!(t.tenant_for(&["5511999887766"]).unwrap().0, "ops");
+    assert_eq!(t.tenant_for(&["whatsapp-id", "222"]).unwrap().0, "sales");
+    assert!(t.tenant_for(&["333"]).is_none());
+    assert_eq!(t.role_for(&["111"]), TenantRole::Admin);
+    assert_eq!(t.role_for(&["222"]), TenantRole::User);
+    assert_eq!(t.role_for(&["333"]), TenantRole::Guest);
+    assert!(!TenantRole::Guest.can_modify());
+    assert!(TenantRole::User.can_modify() && !TenantRole::User.is_admin());
+}
+
+#[test]
+fn test_tenants_disabled_is_single_tenant_admin() {
+    let cfg: Config = toml::from_str("").unwrap();
+    assert!(!cfg.tenants.enabled);
+    assert!(cfg.tenants.tenant_for(&["111"]).is_none());
+    assert_eq!(cfg.tenants.role_for(&["111"]), TenantRole::Admin);
+}
+
+#[test]
+fn test_tenants_reject_unsafe_names() {
+    let mut t = TenantsConfig {
+        enabled: true,
+        ..Default::default()
+    };
+    t.team.insert(
+        "../escape".to_string(),
+        TenantConfig {
+            role: TenantRole::Admin,
+            users: vec!["111".to_string()],
+        },
+    );
+    assert!(t.tenant_for(&["111"]).is_none());
+    assert!(is_valid_tenant_name("team-a_1"));
+    assert!(!is_valid_tenant_name("a/b"));
+    assert!(!is_valid_tenant_name(""));
+}
+
+#[test]
+fn test_roles_config_parse_and_resolve() {
+    let toml_str = r#"
+[roles]
+default = "guest"
+
+[roles.role.guest]
+commands = ["help", "status"]
+projects = []
+models = ["claude-haiku-4-5"]
+builds = false
+tools = []
+
+[roles.role.ops]
+users = ["111", "whatsapp:222"]
+"#;
+    let cfg: Config = toml::from_str(toml_str).unwrap();
+
+    let (name, ops) = cfg.roles.role_for(&["999", "whatsapp:222"]).unwrap();
+    assert_eq!(name, "ops");
+    assert!(ops.allows_command("purge"));
+    assert!(ops.builds);
+    assert!(ops.tools.is_none());
+    assert_eq!(
+        ops.model_for("claude-sonnet-4-6").as_deref(),
+        Some("claude-sonnet-4-6")
+    );
+
+    let (name, guest) = cfg.roles.role_for(&["333"]).unwrap();
+    assert_eq!(name, "guest");
+    assert!(guest.allows_command("help"));
+    assert!(!guest.allows_command("purge"));
+    assert!(!guest.allows_project("trading"));
+    assert!(guest.allows_skill("google-workspace"));
+    assert!(!guest.builds);
+    assert_eq!(guest.tools.as_deref(), Some(&[][..]));
+    assert_eq!(
+        guest.model_for("claude-sonnet-4-6").as_deref(),
+        Some("claude-haiku-4-5")
+    );
+}
+
+#[test]
+fn test_roles_validate() {
+    let toml_str = r#"
+[roles]
+default = "guest"
+
+[roles.role.guest]
+users = ["333"]
+
+[roles.role.ops]
+users = ["111", "whatsapp:222"]
+"#;
+    let cfg: Config = toml::from_str(toml_str).unwrap();
+    assert!(cfg.roles.validate().is_ok());
+    assert!(RolesConfig::default().validate().is_ok());
+
+    let mut undefined = cfg.roles.clone();
+    undefined.default = "missing".to_string();
+    let err = undefined.validate().unwrap_err();
+    assert!(err.contains("'missing'"), "{err}");
+
+    let mut twice = cfg.roles.clone();
+    twice
+        .role
+        .get_mut("guest")
+        .unwrap()
+        .users
+        .push("111".to_string());
+    let err = twice.validate().unwrap_err();
+    assert!(
+        err.contains("'111'") && err.contains("'guest'") && err.contains("'ops'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_load_rejects_invalid_roles() {
+    let path = std::env::temp_dir().join("__omega_test_invalid_roles__.toml");
+    std::fs::write(&path, "[roles]\ndefault = \"nobody\"\n").unwrap();
+    let err = load(path.to_str().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("invalid [roles]"), "{err}");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_roles_absent_is_unrestricted() {
+    let roles = RolesConfig::default();
+    assert!(roles.role_for(&["111"]).is_none());
+
+    let unknown_default = RolesConfig {
+        default: "missing".to_string(),
+        ..Default::default()
+    };
+    assert!(unknown_default.role_for(&["111"]).is_none());
+
+    let open = RoleConfig::default();
+    assert!(open.allows_command("setup") && open.allows_provider("ollama"));
+    let no_models = RoleConfig {
+        models: Vec::new(),
+        ..Default::default()
+    };
+    assert!(no_models.model_for("claude-sonnet-4-6").is_none());
+}
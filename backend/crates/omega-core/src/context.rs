@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::config::ApprovalConfig;
+use crate::structured::ResponseSchema;
+use crate::traits::{ProgressReporter, ToolApprover};
 
 /// Controls which optional context blocks are loaded and injected.
 ///
@@ -15,6 +21,10 @@ pub struct ContextNeeds {
     pub summaries: bool,
     /// Load and inject recent reward outcomes.
     pub outcomes: bool,
+    /// Estimated token budget for the whole context (system prompt, history
+    /// and current message). Lower-priority sections and older turns are
+    /// trimmed to fit. `0` = unlimited.
+    pub token_budget: usize,
 }
 
 impl Default for ContextNeeds {
@@ -25,10 +35,59 @@ impl Default for ContextNeeds {
             profile: true,
             summaries: true,
             outcomes: true,
+            token_budget: 0,
         }
     }
 }
 
+/// Estimate how many tokens `text` costs a typical BPE tokenizer.
+///
+/// Counted per word rather than a flat `len / 4`: runs of ASCII letters and
+/// digits cost about one token per four characters, while punctuation and
+/// each non-ASCII character (accents, Cyrillic, CJK, emoji) cost one token
+/// apiece. Errs on the high side so a budget is not overrun.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            let mut tokens = 0;
+            let mut run = 0usize;
+            for c in word.chars() {
+                if c.is_ascii_alphanumeric() {
+                    run += 1;
+                } else {
+                    tokens += run.div_ceil(4) + 1;
+                    run = 0;
+                }
+            }
+            tokens + run.div_ceil(4)
+        })
+        .sum()
+}
+
+/// One part of a multimodal message.
+///
+/// Binary data is base64-encoded. API providers serialize blocks to their
+/// native format; text-only providers see just the message text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        /// `image/jpeg`, `image/png`, `image/gif` or `image/webp`.
+        media_type: String,
+        data: String,
+    },
+    Document {
+        /// `application/pdf`.
+        media_type: String,
+        data: String,
+        /// File name shown to the model.
+        name: String,
+    },
+}
+
 /// A single entry in the conversation history.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextEntry {
@@ -36,6 +95,9 @@ pub struct ContextEntry {
     pub role: String,
     /// The message content.
     pub content: String,
+    /// Images and documents attached to the message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<ContentBlock>,
 }
 
 /// An MCP server declared by a skill.
@@ -47,6 +109,49 @@ pub struct McpServer {
     pub command: String,
     /// Command-line arguments.
     pub args: Vec<String>,
+    /// Every tool on this server requires human approval before it runs.
+    #[serde(default)]
+    pub sensitive: bool,
+}
+
+/// A tool call waiting on a human decision.
+#[derive(Debug, Clone)]
+pub struct ApprovalRequest {
+    /// Tool name as called by the model (e.g. "bash").
+    pub tool: String,
+    /// Short human-readable summary of the call (command, path, arguments).
+    pub summary: String,
+    /// Why the policy flagged this call.
+    pub reason: String,
+}
+
+/// Approval policy plus the approver that answers for the current request.
+///
+/// Attached to a [`Context`] by the gateway. When `approver` is `None`
+/// (background tasks with nobody to ask), flagged calls are denied.
+#[derive(Clone)]
+pub struct ApprovalGate {
+    pub policy: ApprovalConfig,
+    pub approver: Option<Arc<dyn ToolApprover>>,
+}
+
+impl std::fmt::Debug for ApprovalGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApprovalGate")
+            .field("policy", &self.policy)
+            .field("approver", &self.approver.is_some())
+            .finish()
+    }
+}
+
+/// Receiver for live status updates while a provider runs.
+#[derive(Clone)]
+pub struct ProgressSink(pub Arc<dyn ProgressReporter>);
+
+impl std::fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressSink")
+    }
 }
 
 /// Conversation context passed to an AI provider.
@@ -58,6 +163,9 @@ pub struct Context {
     pub history: Vec<ContextEntry>,
     /// The current user message.
     pub current_message: String,
+    /// Images and documents attached to the current message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub current_media: Vec<ContentBlock>,
     /// MCP servers to activate for this request.
     #[serde(default)]
     pub mcp_servers: Vec<McpServer>,
@@ -69,6 +177,10 @@ pub struct Context {
     /// uses this list instead of its configured default. `Some(vec![])` = no tools.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowed_tools: Option<Vec<String>>,
+    /// Tools the provider must neither offer nor run, whatever `allowed_tools`
+    /// says (`"Bash"` for tenant members whose role doesn't grant it).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_tools: Vec<String>,
     /// Override the provider's default model. When `Some`, the provider passes
     /// `--model` with this value instead of its configured default.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -84,6 +196,31 @@ pub struct Context {
     /// `to_prompt_string()` emits only the current_message.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent_name: Option<String>,
+    /// Human-in-the-loop gate for dangerous tool calls (HTTP providers only).
+    /// `None` = no gating.
+    #[serde(skip)]
+    pub approval: Option<ApprovalGate>,
+    /// Override the provider's working directory (a tenant's workspace).
+    /// When `None`, the provider uses its configured workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<PathBuf>,
+    /// Offer gateway actions (SCHEDULE, CANCEL_TASK, ...) as native tools.
+    /// Providers without function calling ignore it and keep text markers.
+    #[serde(default)]
+    pub action_tools: bool,
+    /// Length in bytes of the `system_prompt` prefix that is the same on
+    /// every request (the SYSTEM_PROMPT.md sections). Providers with prompt
+    /// caching cache it. `0` means unknown.
+    #[serde(default)]
+    pub stable_prefix_len: usize,
+    /// Request schema-constrained JSON output instead of free text.
+    /// Providers map it to their native structured-output feature and
+    /// disable tools for the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
+    /// Live status updates (tool use) while the request runs. `None` = silent.
+    #[serde(skip)]
+    pub progress: Option<ProgressSink>,
 }
 
 /// A structured message for API-based providers (OpenAI, Anthropic, etc.).
@@ -93,6 +230,22 @@ pub struct ApiMessage {
     pub role: String,
     /// The message content.
     pub content: String,
+    /// Images and documents attached to the message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<ContentBlock>,
+}
+
+impl ApiMessage {
+    /// The message as content blocks: attachments first, then the text.
+    pub fn content_blocks(&self) -> Vec<ContentBlock> {
+        let mut blocks = self.media.clone();
+        if !self.content.is_empty() || blocks.is_empty() {
+            blocks.push(ContentBlock::Text {
+                text: self.content.clone(),
+            });
+        }
+        blocks
+    }
 }
 
 impl Context {
@@ -102,12 +255,20 @@ impl Context {
             system_prompt: default_system_prompt(),
             history: Vec::new(),
             current_message: message.to_string(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         }
     }
 
@@ -155,6 +316,11 @@ impl Context {
             }
         }
 
+        // No native structured output on a text CLI: ask for it in the prompt.
+        if let Some(ref schema) = self.response_schema {
+            parts.push(schema.instructions());
+        }
+
         parts.join("\n\n")
     }
 
@@ -169,12 +335,14 @@ impl Context {
             messages.push(ApiMessage {
                 role: entry.role.clone(),
                 content: entry.content.clone(),
+                media: entry.media.clone(),
             });
         }
 
         messages.push(ApiMessage {
             role: "user".to_string(),
             content: self.current_message.clone(),
+            media: self.current_media.clone(),
         });
 
         (self.system_prompt.clone(), messages)
@@ -192,12 +360,27 @@ fn default_system_prompt() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("   \n\t"), 0);
+        assert_eq!(estimate_tokens("hello world"), 4);
+        assert_eq!(estimate_tokens("a b c"), 3);
+        // Punctuation and non-ASCII characters are a token each.
+        assert_eq!(estimate_tokens("Hi, there!"), 5);
+        assert_eq!(estimate_tokens("\u{4f60}\u{597d}"), 2);
+        // Long pasted text scales with its length.
+        let pasted = "lorem ipsum dolor sit amet ".repeat(1000);
+        assert_eq!(estimate_tokens(&pasted), 8000);
+    }
+
     #[test]
     fn test_mcp_server_serde_round_trip() {
         let server = McpServer {
             name: "playwright".into(),
             command: "npx".into(),
             args: vec!["@playwright/mcp".into(), "--headless".into()],
+            sensitive: false,
         };
         let json = serde_json::to_string(&server).unwrap();
         let deserialized: McpServer = serde_json::from_str(&json).unwrap();
@@ -218,16 +401,25 @@ mod tests {
             system_prompt: "test".into(),
             history: Vec::new(),
             current_message: "browse google.com".into(),
+            current_media: Vec::new(),
             mcp_servers: vec![McpServer {
                 name: "playwright".into(),
                 command: "npx".into(),
                 args: vec!["@playwright/mcp".into()],
+                sensitive: false,
             }],
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let json = serde_json::to_string(&ctx).unwrap();
         let deserialized: Context = serde_json::from_str(&json).unwrap();
@@ -261,19 +453,29 @@ mod tests {
                 ContextEntry {
                     role: "user".into(),
                     content: "Hi".into(),
+                    media: Vec::new(),
                 },
                 ContextEntry {
                     role: "assistant".into(),
                     content: "Hello!".into(),
+                    media: Vec::new(),
                 },
             ],
             current_message: "How are you?".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let (system, messages) = ctx.to_api_messages();
         assert_eq!(system, "Be helpful.");
@@ -293,14 +495,23 @@ mod tests {
             history: vec![ContextEntry {
                 role: "user".into(),
                 content: "Hi".into(),
+                media: Vec::new(),
             }],
             current_message: "How are you?".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         assert!(prompt.contains("[System]\nBe helpful."));
@@ -315,14 +526,23 @@ mod tests {
             history: vec![ContextEntry {
                 role: "user".into(),
                 content: "Hi".into(),
+                media: Vec::new(),
             }],
             current_message: "How are you?".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: Some("sess-abc".into()),
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         // Should NOT contain [System] block or history.
@@ -338,29 +558,57 @@ mod tests {
             system_prompt: String::new(),
             history: Vec::new(),
             current_message: "hello".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: Some("sess-xyz".into()),
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         assert_eq!(prompt, "[User]\nhello");
     }
 
+    #[test]
+    fn test_to_prompt_string_appends_schema_instructions() {
+        let mut ctx = Context::new("Summarize.");
+        ctx.response_schema = Some(ResponseSchema::new(
+            "summary",
+            serde_json::json!({"type": "object"}),
+        ));
+        let prompt = ctx.to_prompt_string();
+        assert!(prompt.contains("[User]\nSummarize."));
+        assert!(prompt.ends_with("{\"type\":\"object\"}"));
+    }
+
     #[test]
     fn test_session_id_serde_round_trip() {
         let ctx = Context {
             system_prompt: "test".into(),
             history: Vec::new(),
             current_message: "hi".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: Some("sess-123".into()),
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let json = serde_json::to_string(&ctx).unwrap();
         let deserialized: Context = serde_json::from_str(&json).unwrap();
@@ -425,12 +673,20 @@ mod tests {
             system_prompt: "test".into(),
             history: Vec::new(),
             current_message: "hi".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: Some("build-analyst".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let json = serde_json::to_string(&ctx).unwrap();
         assert!(
@@ -451,14 +707,23 @@ mod tests {
             history: vec![ContextEntry {
                 role: "user".into(),
                 content: "previous message".into(),
+                media: Vec::new(),
             }],
             current_message: "Build me a task tracker.".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: Some("build-analyst".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         // When agent_name is set, the agent file provides the system prompt,
@@ -490,12 +755,20 @@ mod tests {
             system_prompt: "system prompt here".into(),
             history: Vec::new(),
             current_message: "Build something.".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: Some("sess-456".into()),
             agent_name: Some("build-architect".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         // agent_name should win — just return current_message
@@ -513,12 +786,20 @@ mod tests {
             system_prompt: "system".into(),
             history: Vec::new(),
             current_message: String::new(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: Some("build-qa".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         assert_eq!(
@@ -535,12 +816,20 @@ mod tests {
             system_prompt: "test".into(),
             history: Vec::new(),
             current_message: "hi".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: Some("build-test-writer".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let json = serde_json::to_string(&ctx).unwrap();
         let deserialized: Context = serde_json::from_str(&json).unwrap();
@@ -558,14 +847,23 @@ mod tests {
             history: vec![ContextEntry {
                 role: "user".into(),
                 content: "Hi".into(),
+                media: Vec::new(),
             }],
             current_message: "How are you?".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: None,
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let prompt = ctx.to_prompt_string();
         assert!(
@@ -591,12 +889,20 @@ mod tests {
             system_prompt: "sys".into(),
             history: Vec::new(),
             current_message: "msg".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: Some(50),
             allowed_tools: Some(vec!["Bash".into()]),
+            denied_tools: Vec::new(),
             model: Some("claude-sonnet-4-6".into()),
             session_id: Some("sess-1".into()),
             agent_name: Some("build-analyst".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         assert_eq!(ctx.agent_name, Some("build-analyst".into()));
         assert_eq!(ctx.session_id, Some("sess-1".into()));
@@ -611,12 +917,20 @@ mod tests {
             system_prompt: "test".into(),
             history: Vec::new(),
             current_message: "hi".into(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: Some("build-\u{03a9}mega".into()),
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
         };
         let json = serde_json::to_string(&ctx).unwrap();
         let deserialized: Context = serde_json::from_str(&json).unwrap();
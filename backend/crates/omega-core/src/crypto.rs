@@ -0,0 +1,467 @@
+//! Encryption at rest — the master key, the value cipher and the secrets store.
+//!
+//! Values are sealed with AES-256-GCM and stored as `enc:v1:<base64>`. The
+//! nonce is derived from the plaintext (HMAC-SHA256 with a separate key), so
+//! equal plaintexts give equal ciphertexts and SQL equality and `UNIQUE`
+//! constraints keep working; the price is that equality itself is visible.
+//! Values without the prefix are plaintext written before encryption was on
+//! and are read as-is.
+//!
+//! The 32-byte master key comes from `OMEGA_MASTER_KEY`, a key file or the OS
+//! keyring (`security` on macOS, `secret-tool` on Linux), base64-encoded.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::config::{shellexpand, Config, DataDir, EncryptionConfig};
+use crate::error::OmegaError;
+
+/// Prefix of every encrypted value.
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Environment variable read by `key_source = "env"`.
+pub const KEY_ENV: &str = "OMEGA_MASTER_KEY";
+
+/// Keyring service and account the master key is stored under.
+const KEYRING_SERVICE: &str = "omega";
+const KEYRING_ACCOUNT: &str = "master-key";
+
+/// Secret holding the Google OAuth credentials (the `google.json` document).
+pub const GOOGLE_SECRET: &str = "google";
+
+/// Prefix that makes a config string a reference into the secrets store.
+pub const SECRET_REF_PREFIX: &str = "secret:";
+
+fn crypto_err(msg: impl std::fmt::Display) -> OmegaError {
+    OmegaError::Config(format!("encryption: {msg}"))
+}
+
+/// A fresh random master key, base64-encoded.
+pub fn generate_key() -> Result<String, OmegaError> {
+    let mut key = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| crypto_err("system RNG unavailable"))?;
+    Ok(STANDARD.encode(key))
+}
+
+/// Decode a base64 master key; it must be exactly 32 bytes.
+pub fn parse_key(encoded: &str) -> Result<[u8; 32], OmegaError> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| crypto_err(format!("master key is not base64: {e}")))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| crypto_err(format!("master key is {} bytes, need 32", b.len())))
+}
+
+/// Seals and opens individual values with a key derived from the master key.
+pub struct Cipher {
+    key: LessSafeKey,
+    nonce_key: hmac::Key,
+}
+
+impl std::fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Cipher(..)")
+    }
+}
+
+impl Cipher {
+    /// Derive the encryption and nonce keys from a base64 master key.
+    pub fn new(master_key: &str) -> Result<Self, OmegaError> {
+        let master = hmac::Key::new(hmac::HMAC_SHA256, &parse_key(master_key)?);
+        let derive = |label: &[u8]| hmac::sign(&master, label);
+        let key = UnboundKey::new(&AES_256_GCM, derive(b"omega-encryption-v1").as_ref())
+            .map_err(|_| crypto_err("invalid key"))?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            nonce_key: hmac::Key::new(hmac::HMAC_SHA256, derive(b"omega-nonce-v1").as_ref()),
+        })
+    }
+
+    /// Whether `value` was written by [`Cipher::encrypt`].
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENCRYPTED_PREFIX)
+    }
+
+    /// Encrypt `plaintext`. Empty strings stay empty.
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        if plaintext.is_empty() {
+            return String::new();
+        }
+        let tag = hmac::sign(&self.nonce_key, plaintext.as_bytes());
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&tag.as_ref()[..NONCE_LEN]);
+
+        let mut sealed = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut sealed,
+            )
+            .expect("AES-GCM seal cannot fail for in-memory buffers");
+        let mut out = nonce.to_vec();
+        out.extend_from_slice(&sealed);
+        format!("{ENCRYPTED_PREFIX}{}", STANDARD.encode(out))
+    }
+
+    /// Decrypt a value; plaintext values (no prefix) are returned unchanged.
+    pub fn decrypt(&self, value: &str) -> Result<String, OmegaError> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let raw = STANDARD
+            .decode(encoded)
+            .map_err(|e| crypto_err(format!("corrupt ciphertext: {e}")))?;
+        if raw.len() < NONCE_LEN {
+            return Err(crypto_err("corrupt ciphertext: too short"));
+        }
+        let (nonce, sealed) = raw.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| crypto_err("corrupt ciphertext: bad nonce"))?;
+        let mut buf = sealed.to_vec();
+        let plain = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut buf)
+            .map_err(|_| crypto_err("cannot decrypt value (wrong master key?)"))?;
+        String::from_utf8(plain.to_vec()).map_err(|e| crypto_err(format!("not UTF-8: {e}")))
+    }
+}
+
+/// The key file used by `key_source = "file"`.
+pub fn key_file_path(cfg: &EncryptionConfig, data_dir: &str) -> PathBuf {
+    if cfg.key_file.is_empty() {
+        DataDir::new(data_dir).master_key()
+    } else {
+        PathBuf::from(shellexpand(&cfg.key_file))
+    }
+}
+
+/// Read the base64 master key from the configured source.
+pub fn load_key(cfg: &EncryptionConfig, data_dir: &str) -> Result<String, OmegaError> {
+    let key = match cfg.key_source.as_str() {
+        "env" => std::env::var(KEY_ENV).map_err(|_| crypto_err(format!("{KEY_ENV} is not set")))?,
+        "file" => {
+            let path = key_file_path(cfg, data_dir);
+            std::fs::read_to_string(&path)
+                .map_err(|e| crypto_err(format!("cannot read {}: {e}", path.display())))?
+        }
+        "keyring" => keyring_get()?,
+        other => return Err(crypto_err(format!("unknown key_source \"{other}\""))),
+    };
+    parse_key(&key)?;
+    Ok(key.trim().to_string())
+}
+
+/// Persist a new master key to the configured source.
+///
+/// `env` cannot be written; the caller has to show the key to the user.
+pub fn save_key(cfg: &EncryptionConfig, data_dir: &str, key: &str) -> Result<(), OmegaError> {
+    match cfg.key_source.as_str() {
+        "env" => Err(crypto_err(format!(
+            "key_source is \"env\"; set {KEY_ENV} yourself"
+        ))),
+        "file" => write_private(&key_file_path(cfg, data_dir), key.as_bytes()),
+        "keyring" => keyring_set(key),
+        other => Err(crypto_err(format!("unknown key_source \"{other}\""))),
+    }
+}
+
+/// The value cipher for a loaded config, or `None` when encryption is off.
+pub fn cipher_for(cfg: &Config) -> Result<Option<Arc<Cipher>>, OmegaError> {
+    if !cfg.encryption.enabled {
+        return Ok(None);
+    }
+    let key = load_key(&cfg.encryption, &cfg.omega.data_dir)?;
+    Ok(Some(Arc::new(Cipher::new(&key)?)))
+}
+
+fn keyring_get() -> Result<String, OmegaError> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", KEYRING_SERVICE])
+            .args(["-a", KEYRING_ACCOUNT, "-w"])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", KEYRING_SERVICE])
+            .args(["account", KEYRING_ACCOUNT])
+            .output()
+    }
+    .map_err(|e| crypto_err(format!("keyring unavailable: {e}")))?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(crypto_err("no master key in the OS keyring"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn keyring_set(key: &str) -> Result<(), OmegaError> {
+    let status = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["add-generic-password", "-U", "-s", KEYRING_SERVICE])
+            .args(["-a", KEYRING_ACCOUNT, "-w", key])
+            .status()
+    } else {
+        // secret-tool reads the secret from stdin, keeping it out of argv.
+        Command::new("secret-tool")
+            .args([
+                "store",
+                "--label=Omega master key",
+                "service",
+                KEYRING_SERVICE,
+            ])
+            .args(["account", KEYRING_ACCOUNT])
+            .stdin(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(key.as_bytes())?;
+                }
+                child.wait()
+            })
+    }
+    .map_err(|e| crypto_err(format!("keyring unavailable: {e}")))?;
+    if !status.success() {
+        return Err(crypto_err(
+            "could not store the master key in the OS keyring",
+        ));
+    }
+    Ok(())
+}
+
+/// Write `bytes` to `path` with 0600 permissions, creating parent dirs.
+pub fn write_private(path: &Path, bytes: &[u8]) -> Result<(), OmegaError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Encrypted name → value store for channel tokens, API keys and credentials.
+///
+/// The whole map is one sealed JSON document in `{data_dir}/secrets.enc`.
+/// Config strings of the form `secret:<name>` are resolved against it.
+#[derive(Debug)]
+pub struct SecretStore {
+    path: PathBuf,
+    cipher: Arc<Cipher>,
+}
+
+impl SecretStore {
+    pub fn new(path: PathBuf, cipher: Arc<Cipher>) -> Self {
+        Self { path, cipher }
+    }
+
+    /// The store for a loaded config, or `None` when encryption is off.
+    pub fn for_config(cfg: &Config) -> Result<Option<Self>, OmegaError> {
+        Ok(cipher_for(cfg)?
+            .map(|cipher| Self::new(DataDir::new(&cfg.omega.data_dir).secrets(), cipher)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All secrets. A missing file is an empty store.
+    pub fn load(&self) -> Result<BTreeMap<String, String>, OmegaError> {
+        let sealed = match std::fs::read_to_string(&self.path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(serde_json::from_str(&self.cipher.decrypt(sealed.trim())?)?)
+    }
+
+    /// Replace the whole store.
+    pub fn save(&self, secrets: &BTreeMap<String, String>) -> Result<(), OmegaError> {
+        let json = serde_json::to_string(secrets)?;
+        write_private(&self.path, self.cipher.encrypt(&json).as_bytes())
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<String>, OmegaError> {
+        Ok(self.load()?.remove(name))
+    }
+
+    pub fn set(&self, name: &str, value: &str) -> Result<(), OmegaError> {
+        let mut secrets = self.load()?;
+        secrets.insert(name.to_string(), value.to_string());
+        self.save(&secrets)
+    }
+
+    /// Remove a secret; returns whether it existed.
+    pub fn remove(&self, name: &str) -> Result<bool, OmegaError> {
+        let mut secrets = self.load()?;
+        let existed = secrets.remove(name).is_some();
+        if existed {
+            self.save(&secrets)?;
+        }
+        Ok(existed)
+    }
+
+    /// Write secret `name` to `path` (0600) for a tool that can only read a
+    /// plaintext file. The file is removed when the last guard for `path` drops.
+    ///
+    /// Returns `None` when the secret does not exist.
+    pub fn materialize(&self, name: &str, path: &Path) -> Option<MaterializedSecret> {
+        let value = match self.get(name) {
+            Ok(Some(value)) => value,
+            Ok(None) => return None,
+            Err(e) => {
+                tracing::warn!("secrets: cannot read {name}: {e}");
+                return None;
+            }
+        };
+        let mut active = ACTIVE_FILES.lock().unwrap_or_else(|e| e.into_inner());
+        let count = active.entry(path.to_path_buf()).or_insert(0);
+        if let Err(e) = write_private(path, value.as_bytes()) {
+            tracing::warn!("secrets: cannot write {}: {e}", path.display());
+            if *count == 0 {
+                active.remove(path);
+            }
+            return None;
+        }
+        *count += 1;
+        Some(MaterializedSecret {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+/// Reference counts of files written by [`SecretStore::materialize`].
+static ACTIVE_FILES: Mutex<BTreeMap<PathBuf, usize>> = Mutex::new(BTreeMap::new());
+
+/// Keeps a materialized secret file on disk; see [`SecretStore::materialize`].
+#[derive(Debug)]
+pub struct MaterializedSecret {
+    path: PathBuf,
+}
+
+impl Drop for MaterializedSecret {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_FILES.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(count) = active.get_mut(&self.path) {
+            *count -= 1;
+            if *count == 0 {
+                active.remove(&self.path);
+                let _ = std::fs::remove_file(&self.path);
+            }
+        }
+    }
+}
+
+/// Replace every `secret:<name>` string in a parsed config with the stored value.
+///
+/// Returns the names that were referenced but not found.
+pub fn resolve_secret_refs(
+    value: &mut toml::Value,
+    secrets: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut missing = Vec::new();
+    resolve_into(value, secrets, &mut missing);
+    missing
+}
+
+fn resolve_into(
+    value: &mut toml::Value,
+    secrets: &BTreeMap<String, String>,
+    missing: &mut Vec<String>,
+) {
+    match value {
+        toml::Value::String(s) => {
+            if let Some(name) = s.strip_prefix(SECRET_REF_PREFIX) {
+                match secrets.get(name) {
+                    Some(secret) => *s = secret.clone(),
+                    None => missing.push(name.to_string()),
+                }
+            }
+        }
+        toml::Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| resolve_into(v, secrets, missing)),
+        toml::Value::Table(table) => table
+            .iter_mut()
+            .for_each(|(_, v)| resolve_into(v, secrets, missing)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> Cipher {
+        Cipher::new(&generate_key().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_and_determinism() {
+        let c = cipher();
+        let sealed = c.encrypt("lives in Madrid");
+        assert!(Cipher::is_encrypted(&sealed));
+        assert!(!sealed.contains("Madrid"));
+        assert_eq!(sealed, c.encrypt("lives in Madrid"));
+        assert_ne!(sealed, c.encrypt("lives in Lisbon"));
+        assert_eq!(c.decrypt(&sealed).unwrap(), "lives in Madrid");
+        assert_eq!(c.encrypt(""), "");
+        assert_eq!(c.decrypt("plain old value").unwrap(), "plain old value");
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let sealed = cipher().encrypt("secret");
+        assert!(cipher().decrypt(&sealed).is_err());
+        assert!(parse_key("c2hvcnQ=").is_err());
+    }
+
+    #[test]
+    fn test_secret_store_and_refs() {
+        let dir = std::env::temp_dir().join(format!("omega-secrets-{}", uuid::Uuid::new_v4()));
+        let store = SecretStore::new(dir.join("secrets.enc"), Arc::new(cipher()));
+        assert!(store.load().unwrap().is_empty());
+        store.set("telegram", "123:abc").unwrap();
+        assert_eq!(store.get("telegram").unwrap().as_deref(), Some("123:abc"));
+        let raw = std::fs::read_to_string(store.path()).unwrap();
+        assert!(!raw.contains("123:abc"));
+
+        let mut value: toml::Value = toml::from_str(
+            "[channel.telegram]\nbot_token = \"secret:telegram\"\nkey = \"secret:nope\"",
+        )
+        .unwrap();
+        let missing = resolve_secret_refs(&mut value, &store.load().unwrap());
+        assert_eq!(
+            value["channel"]["telegram"]["bot_token"].as_str(),
+            Some("123:abc")
+        );
+        assert_eq!(missing, vec!["nope".to_string()]);
+
+        let target = dir.join("stores").join("tool.json");
+        let first = store.materialize("telegram", &target).unwrap();
+        let second = store.materialize("telegram", &target).unwrap();
+        drop(first);
+        assert!(target.exists());
+        drop(second);
+        assert!(!target.exists());
+        assert!(store.materialize("missing", &target).is_none());
+
+        assert!(store.remove("telegram").unwrap());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -4,9 +4,12 @@
 
 pub mod config;
 pub mod context;
+pub mod crypto;
 pub mod error;
+pub mod media;
 pub mod message;
 pub mod sanitize;
+pub mod structured;
 pub mod traits;
 
 pub use config::shellexpand;
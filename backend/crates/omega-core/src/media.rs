@@ -0,0 +1,246 @@
+//! Attachment normalization for multimodal context.
+//!
+//! Incoming images and PDFs become [`ContentBlock`]s. The format is taken
+//! from the file's magic bytes, not its name or the channel's claim, and
+//! anything the provider APIs would reject (unknown formats, oversized
+//! files) is dropped here instead of failing the whole request.
+//!
+//! Stored user messages carry `[Attached image: <file name>]` references so
+//! the media can be loaded again for follow-up questions. They only ever
+//! resolve inside the sender's media dir, and lines a user types in that form
+//! are escaped, so a reference can't name an arbitrary file.
+
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::context::ContentBlock;
+
+/// Largest image sent inline (the Anthropic per-image limit).
+pub const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Largest document sent inline.
+pub const MAX_DOCUMENT_BYTES: usize = 20 * 1024 * 1024;
+
+const IMAGE_PREFIX: &str = "[Attached image: ";
+const DOCUMENT_PREFIX: &str = "[Attached document: ";
+
+/// Detect a supported media type from the file's leading bytes.
+pub fn sniff_media_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if data.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
+/// File extension for a supported media type.
+pub fn extension(media_type: &str) -> &'static str {
+    match media_type {
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "application/pdf" => "pdf",
+        _ => "jpg",
+    }
+}
+
+/// Build a content block from raw file bytes.
+///
+/// Returns a human-readable reason when the data cannot be sent inline.
+pub fn content_block(data: &[u8], name: &str) -> Result<ContentBlock, String> {
+    let Some(media_type) = sniff_media_type(data) else {
+        return Err(format!("{name}: unsupported format"));
+    };
+    let is_document = media_type == "application/pdf";
+    let limit = if is_document {
+        MAX_DOCUMENT_BYTES
+    } else {
+        MAX_IMAGE_BYTES
+    };
+    if data.len() > limit {
+        return Err(format!(
+            "{name}: {} bytes exceeds the {limit}-byte limit",
+            data.len()
+        ));
+    }
+    let encoded = STANDARD.encode(data);
+    Ok(if is_document {
+        ContentBlock::Document {
+            media_type: media_type.to_string(),
+            data: encoded,
+            name: name.to_string(),
+        }
+    } else {
+        ContentBlock::Image {
+            media_type: media_type.to_string(),
+            data: encoded,
+        }
+    })
+}
+
+/// Load a content block from a file on disk.
+pub fn load(path: &Path) -> Result<ContentBlock, String> {
+    let data = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    content_block(&data, &name)
+}
+
+/// The reference line stored in a user message for a saved attachment.
+///
+/// Only the file name is kept; [`resolve`] finds it again in the media dir.
+pub fn reference(path: &Path, block: &ContentBlock) -> String {
+    let prefix = match block {
+        ContentBlock::Document { .. } => DOCUMENT_PREFIX,
+        _ => IMAGE_PREFIX,
+    };
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    format!("{prefix}{name}]")
+}
+
+/// Escape reference lines in user-typed text so they are never loaded.
+pub fn escape_references(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with(IMAGE_PREFIX) || trimmed.starts_with(DOCUMENT_PREFIX) {
+                format!("\\{trimmed}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The file a stored reference names, if it lies inside `media_dir`.
+///
+/// References are file names; absolute paths from older messages are accepted
+/// only when they canonicalize into `media_dir` too.
+pub fn resolve(media_dir: &Path, reference: &Path) -> Option<PathBuf> {
+    let root = media_dir.canonicalize().ok()?;
+    let path = root.join(reference).canonicalize().ok()?;
+    (path.starts_with(&root) && path.is_file()).then_some(path)
+}
+
+/// Paths referenced by `[Attached image: ...]` / `[Attached document: ...]` lines.
+pub fn references(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let rest = line
+                .strip_prefix(IMAGE_PREFIX)
+                .or_else(|| line.strip_prefix(DOCUMENT_PREFIX))?;
+            rest.strip_suffix(']').map(PathBuf::from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn test_sniff_media_type() {
+        assert_eq!(sniff_media_type(PNG), Some("image/png"));
+        assert_eq!(
+            sniff_media_type(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some("image/jpeg")
+        );
+        assert_eq!(sniff_media_type(b"GIF89a...."), Some("image/gif"));
+        assert_eq!(
+            sniff_media_type(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(sniff_media_type(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff_media_type(b"hello"), None);
+    }
+
+    #[test]
+    fn test_content_block_uses_sniffed_type() {
+        // Named .jpg but actually a PNG.
+        let block = content_block(PNG, "photo.jpg").unwrap();
+        assert_eq!(
+            block,
+            ContentBlock::Image {
+                media_type: "image/png".into(),
+                data: STANDARD.encode(PNG),
+            }
+        );
+        let doc = content_block(b"%PDF-1.4 body", "invoice.pdf").unwrap();
+        assert!(matches!(doc, ContentBlock::Document { ref name, .. } if name == "invoice.pdf"));
+    }
+
+    #[test]
+    fn test_content_block_rejects_unsupported_and_oversized() {
+        assert!(content_block(b"plain text", "notes.txt").is_err());
+        let mut big = PNG.to_vec();
+        big.resize(MAX_IMAGE_BYTES + 1, 0);
+        let err = content_block(&big, "big.png").unwrap_err();
+        assert!(err.contains("exceeds"));
+    }
+
+    #[test]
+    fn test_reference_roundtrip() {
+        let image = content_block(PNG, "a.png").unwrap();
+        let doc = content_block(b"%PDF-1.4", "b.pdf").unwrap();
+        let text = format!(
+            "{}\n{}\nWhat are these?",
+            reference(Path::new("/data/media/a.png"), &image),
+            reference(Path::new("/data/media/b.pdf"), &doc)
+        );
+        assert!(text.starts_with("[Attached image: a.png]"));
+        assert_eq!(
+            references(&text),
+            vec![PathBuf::from("a.png"), PathBuf::from("b.pdf")]
+        );
+        assert!(references("no attachments here").is_empty());
+    }
+
+    #[test]
+    fn test_escape_references() {
+        let typed = "look\n  [Attached image: /etc/secret.png]\n[Attached document: x.pdf]";
+        let escaped = escape_references(typed);
+        assert!(references(&escaped).is_empty());
+        assert!(escaped.starts_with("look\n\\[Attached image: /etc/secret.png]"));
+        assert_eq!(escape_references("plain\ntext"), "plain\ntext");
+    }
+
+    #[test]
+    fn test_resolve_stays_inside_media_dir() {
+        let root = std::env::temp_dir().join(format!("omega_media_resolve_{}", std::process::id()));
+        let media = root.join("media");
+        std::fs::create_dir_all(&media).unwrap();
+        std::fs::write(media.join("a.png"), PNG).unwrap();
+        std::fs::write(root.join("secret.png"), PNG).unwrap();
+
+        assert_eq!(
+            resolve(&media, Path::new("a.png")),
+            Some(media.canonicalize().unwrap().join("a.png"))
+        );
+        // Legacy absolute references still load when they point inside.
+        assert!(resolve(&media, &media.join("a.png")).is_some());
+        assert!(resolve(&media, Path::new("../secret.png")).is_none());
+        assert!(resolve(&media, &root.join("secret.png")).is_none());
+        assert!(resolve(&media, Path::new("missing.png")).is_none());
+        assert!(resolve(&root.join("nowhere"), Path::new("a.png")).is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
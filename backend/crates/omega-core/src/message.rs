@@ -46,6 +46,32 @@ pub struct OutgoingMessage {
     /// containing URLs with underscores that Telegram's Markdown would mangle.
     #[serde(default)]
     pub plain_text: bool,
+    /// Gateway actions the model requested through native tool calls.
+    /// `Some` (even empty) means the provider offered the action tools, so
+    /// text markers in `text` are not acted on. `None` keeps text markers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<ActionCall>>,
+    /// Inline buttons shown under the message by channels that support them
+    /// (Telegram). Other channels send the text alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buttons: Vec<ReplyButton>,
+}
+
+/// An inline button. Pressing it arrives as an incoming message whose text
+/// is `command`, so it takes the same path as typing the command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplyButton {
+    pub label: String,
+    pub command: String,
+}
+
+/// A gateway action (schedule a task, store a lesson, ...) requested as a tool call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionCall {
+    /// Tool name as called by the model (e.g. "omega_schedule").
+    pub name: String,
+    /// Arguments as sent by the model, already checked against the tool schema.
+    pub args: serde_json::Value,
 }
 
 /// Metadata about how a message was generated.
@@ -62,6 +88,19 @@ pub struct MessageMetadata {
     /// Session ID returned by the provider (Claude Code CLI only).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// Prompt tokens served from the provider's cache (Anthropic only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_tokens: Option<u64>,
+    /// Prompt tokens written to the provider's cache (Anthropic only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_tokens: Option<u64>,
+    /// Ids of scheduled tasks created by markers in this turn (cancelled by `/undo`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub created_tasks: Vec<String>,
+    /// Where untrusted content consumed this turn came from (`mcp:...`,
+    /// `read:...`); see `sanitize::label_untrusted`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub untrusted_sources: Vec<String>,
 }
 
 /// A file attachment on a message.
@@ -127,6 +166,10 @@ mod tests {
                 processing_time_ms: 150,
                 model: Some("sonnet".to_string()),
                 session_id: None,
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+                created_tasks: Vec::new(),
+                untrusted_sources: Vec::new(),
             },
             reply_target: Some("chat_123".to_string()),
             ..Default::default()
@@ -148,5 +191,7 @@ mod tests {
         assert_eq!(meta.processing_time_ms, 0);
         assert!(meta.model.is_none());
         assert!(meta.session_id.is_none());
+        assert!(meta.cache_read_tokens.is_none());
+        assert!(meta.cache_write_tokens.is_none());
     }
 }
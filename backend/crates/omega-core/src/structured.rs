@@ -0,0 +1,177 @@
+//! Schema-constrained JSON output for internal calls.
+//!
+//! Summaries, heartbeat grouping and similar internal calls set
+//! [`Context::response_schema`]. Each provider maps it to its native
+//! structured-output feature; [`complete_structured`] parses the reply into
+//! a typed struct and retries once with the parse error when the model drifts.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{context::Context, error::OmegaError, traits::Provider};
+
+/// The JSON object an internal call expects back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Short identifier (e.g. `conversation_summary`), used as the schema
+    /// or forced-tool name.
+    pub name: String,
+    /// JSON Schema of the expected object.
+    pub schema: serde_json::Value,
+}
+
+impl ResponseSchema {
+    pub fn new(name: &str, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.to_string(),
+            schema,
+        }
+    }
+
+    /// Prompt instruction for providers without native structured output.
+    pub fn instructions(&self) -> String {
+        format!(
+            "Respond with only a JSON object matching this JSON Schema — \
+             no prose, no code fences:\n{}",
+            self.schema
+        )
+    }
+}
+
+/// Extract the JSON object from a model reply.
+///
+/// Tolerates code fences and surrounding prose by taking the span from the
+/// first `{` to the last `}`.
+pub fn extract_json(text: &str) -> Option<&str> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    (end > start).then(|| &text[start..=end])
+}
+
+/// Parse a model reply into `T`.
+pub fn parse<T: DeserializeOwned>(text: &str) -> Result<T, String> {
+    let json = extract_json(text).ok_or_else(|| "no JSON object in reply".to_string())?;
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+/// Run a structured call and parse the reply, retrying once on invalid output.
+///
+/// The retry repeats the request with the parse error appended so the model
+/// can correct itself.
+pub async fn complete_structured<T: DeserializeOwned>(
+    provider: &dyn Provider,
+    context: &Context,
+) -> Result<T, OmegaError> {
+    let first = provider.complete(context).await?;
+    let err = match parse(&first.text) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+    tracing::debug!("structured output invalid ({err}), retrying once");
+
+    let mut retry = context.clone();
+    retry.current_message = format!(
+        "{}\n\nYour previous reply was not valid ({err}). \
+         Reply again with only the JSON object.",
+        context.current_message
+    );
+    let second = provider.complete(&retry).await?;
+    parse(&second.text).map_err(|e| OmegaError::Provider(format!("invalid structured output: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::OutgoingMessage;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Reply {
+        answer: String,
+    }
+
+    struct Scripted {
+        replies: Mutex<Vec<&'static str>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl Scripted {
+        fn new(replies: &[&'static str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().copied().collect()),
+                prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for Scripted {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn requires_api_key(&self) -> bool {
+            false
+        }
+
+        async fn complete(&self, context: &Context) -> Result<OutgoingMessage, OmegaError> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(context.current_message.clone());
+            let text = self.replies.lock().unwrap().pop().unwrap_or_default();
+            Ok(OutgoingMessage {
+                text: text.to_string(),
+                ..Default::default()
+            })
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_extract_json_strips_fences_and_prose() {
+        assert_eq!(
+            extract_json("Sure:\n```json\n{\"a\": {\"b\": 1}}\n```"),
+            Some("{\"a\": {\"b\": 1}}")
+        );
+        assert_eq!(extract_json("no json"), None);
+        assert_eq!(extract_json("} backwards {"), None);
+    }
+
+    #[test]
+    fn test_parse_typed() {
+        let reply: Reply = parse("{\"answer\": \"42\"}").unwrap();
+        assert_eq!(reply.answer, "42");
+        assert!(parse::<Reply>("{\"other\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_instructions_include_schema() {
+        let schema = ResponseSchema::new("reply", serde_json::json!({"type": "object"}));
+        assert!(schema.instructions().contains("{\"type\":\"object\"}"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_retries_once() {
+        let provider = Scripted::new(&["not json", "{\"answer\": \"ok\"}"]);
+        let reply: Reply = complete_structured(&provider, &Context::new("question"))
+            .await
+            .unwrap();
+        assert_eq!(reply.answer, "ok");
+        let prompts = provider.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[1].starts_with("question"));
+        assert!(prompts[1].contains("not valid"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_gives_up_after_retry() {
+        let provider = Scripted::new(&["nope", "{\"wrong\": true}"]);
+        let result = complete_structured::<Reply>(&provider, &Context::new("q")).await;
+        assert!(result.is_err());
+        assert_eq!(provider.prompts.lock().unwrap().len(), 2);
+    }
+}
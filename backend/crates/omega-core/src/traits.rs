@@ -1,5 +1,5 @@
 use crate::{
-    context::Context,
+    context::{ApprovalRequest, Context},
     error::OmegaError,
     message::{IncomingMessage, OutgoingMessage},
 };
@@ -25,6 +25,28 @@ pub trait Provider: Send + Sync {
     async fn is_available(&self) -> bool;
 }
 
+/// Tool approval trait — the human in the loop.
+///
+/// Implemented by the gateway to ask the user, on their channel, whether a
+/// flagged tool call may run. Providers call it from inside the agentic loop.
+#[async_trait]
+pub trait ToolApprover: Send + Sync {
+    /// Ask for a decision. Returns `true` only on explicit approval —
+    /// timeouts and delivery failures must return `false`.
+    async fn request_approval(&self, request: &ApprovalRequest) -> bool;
+}
+
+/// Progress reporting trait — the heartbeat of a long request.
+///
+/// Implemented by the gateway to relay short status lines ("Editing
+/// src/main.rs") to the user's channel while a provider works. Providers
+/// throttle before calling it.
+#[async_trait]
+pub trait ProgressReporter: Send + Sync {
+    /// Report one status line. Delivery failures are ignored.
+    async fn report(&self, status: &str);
+}
+
 /// Messaging Channel trait — the nervous system.
 ///
 /// Every messaging platform (Telegram, WhatsApp, etc.) implements this
@@ -56,6 +78,17 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Send a file (arbitrary bytes) as a document with an optional caption.
+    async fn send_document(
+        &self,
+        _target: &str,
+        _file_name: &str,
+        _bytes: &[u8],
+        _caption: &str,
+    ) -> Result<(), OmegaError> {
+        Ok(())
+    }
+
     /// Delete a message by its platform-specific ID.
     /// Best-effort: implementations should log failures but not propagate errors.
     async fn delete_message(&self, _target: &str, _message_id: &str) -> Result<(), OmegaError> {
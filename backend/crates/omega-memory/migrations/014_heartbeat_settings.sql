@@ -0,0 +1,15 @@
+-- Per-user heartbeat: checklist, pulse, active hours and delivery target.
+CREATE TABLE IF NOT EXISTS heartbeat_settings (
+    sender_id        TEXT PRIMARY KEY,
+    channel          TEXT NOT NULL,
+    reply_target     TEXT NOT NULL,
+    enabled          INTEGER NOT NULL DEFAULT 1,
+    interval_minutes INTEGER NOT NULL DEFAULT 30,
+    active_start     TEXT NOT NULL DEFAULT '',
+    active_end       TEXT NOT NULL DEFAULT '',
+    checklist        TEXT NOT NULL DEFAULT '',
+    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_heartbeat_settings_enabled
+    ON heartbeat_settings(enabled);
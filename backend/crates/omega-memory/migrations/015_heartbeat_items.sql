@@ -0,0 +1,16 @@
+-- Structured heartbeat checklist: one row per monitored item.
+-- cadence: '' (every pulse), 'hourly', 'daily HH:MM', 'weekly <day> HH:MM'.
+CREATE TABLE IF NOT EXISTS heartbeat_items (
+    id              TEXT PRIMARY KEY,
+    sender_id       TEXT NOT NULL,
+    project         TEXT NOT NULL DEFAULT '',
+    description     TEXT NOT NULL,
+    cadence         TEXT NOT NULL DEFAULT '',
+    alert_on_change INTEGER NOT NULL DEFAULT 0,
+    last_checked_at TEXT,
+    last_result     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_heartbeat_items_sender
+    ON heartbeat_items(sender_id, project);
@@ -0,0 +1,14 @@
+-- Database maintenance history: one row per snapshot/prune/compact/check run.
+-- integrity: 'ok' or the problems reported by PRAGMA quick_check.
+CREATE TABLE IF NOT EXISTS maintenance_runs (
+    id               TEXT PRIMARY KEY,
+    finished_at      TEXT NOT NULL DEFAULT (datetime('now')),
+    snapshot         TEXT,
+    pruned_audit     INTEGER NOT NULL DEFAULT 0,
+    pruned_outcomes  INTEGER NOT NULL DEFAULT 0,
+    pruned_messages  INTEGER NOT NULL DEFAULT 0,
+    reclaimed_bytes  INTEGER NOT NULL DEFAULT 0,
+    integrity        TEXT NOT NULL DEFAULT 'ok'
+);
+CREATE INDEX IF NOT EXISTS idx_maintenance_runs_finished
+    ON maintenance_runs(finished_at);
@@ -0,0 +1,13 @@
+-- Named copies of a conversation's messages (/snapshot, /branch).
+-- messages: JSON array of [role, content] pairs, sealed like message content.
+CREATE TABLE IF NOT EXISTS conversation_snapshots (
+    id            TEXT PRIMARY KEY,
+    channel       TEXT NOT NULL,
+    sender_id     TEXT NOT NULL,
+    project       TEXT NOT NULL DEFAULT '',
+    name          TEXT NOT NULL,
+    messages      TEXT NOT NULL,
+    message_count INTEGER NOT NULL DEFAULT 0,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(channel, sender_id, project, name)
+);
@@ -0,0 +1,28 @@
+-- Allow status = 'cancelled' for requests stopped with /stop.
+-- SQLite cannot alter a CHECK constraint, so the table is rebuilt.
+
+CREATE TABLE audit_log_v2 (
+    id              TEXT PRIMARY KEY,
+    timestamp       TEXT NOT NULL DEFAULT (datetime('now')),
+    channel         TEXT NOT NULL,
+    sender_id       TEXT NOT NULL,
+    sender_name     TEXT,
+    input_text      TEXT NOT NULL,
+    output_text     TEXT,
+    provider_used   TEXT,
+    model           TEXT,
+    processing_ms   INTEGER,
+    status          TEXT NOT NULL DEFAULT 'ok' CHECK (status IN ('ok', 'error', 'denied', 'cancelled')),
+    denial_reason   TEXT
+);
+
+INSERT INTO audit_log_v2 (id, timestamp, channel, sender_id, sender_name, input_text,
+    output_text, provider_used, model, processing_ms, status, denial_reason)
+SELECT id, timestamp, channel, sender_id, sender_name, input_text,
+    output_text, provider_used, model, processing_ms, status, denial_reason
+FROM audit_log;
+DROP TABLE audit_log;
+ALTER TABLE audit_log_v2 RENAME TO audit_log;
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
+CREATE INDEX IF NOT EXISTS idx_audit_log_sender ON audit_log(channel, sender_id);
@@ -0,0 +1,179 @@
+-- PostgreSQL schema for the memory store: the SQLite schema after
+-- migrations 001-016, in one step.
+--
+-- Timestamps are TIMESTAMPTZ and read back through omega_ts(), which
+-- formats them like SQLite's datetime('now'). due_at stays TEXT: it holds
+-- whatever the model scheduled and is parsed with omega_try_ts().
+
+-- 'YYYY-MM-DD HH:MM:SS' in UTC.
+CREATE OR REPLACE FUNCTION omega_ts(t TIMESTAMPTZ) RETURNS TEXT AS $$
+    SELECT to_char(t AT TIME ZONE 'UTC', 'YYYY-MM-DD HH24:MI:SS')
+$$ LANGUAGE SQL IMMUTABLE;
+
+-- Parse a stored due time (UTC unless an offset is given); NULL if invalid.
+CREATE OR REPLACE FUNCTION omega_try_ts(v TEXT) RETURNS TIMESTAMPTZ AS $$
+BEGIN
+    RETURN v::TIMESTAMPTZ;
+EXCEPTION WHEN others THEN
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql STABLE SET TimeZone = 'UTC';
+
+CREATE TABLE IF NOT EXISTS conversations (
+    id            TEXT PRIMARY KEY,
+    channel       TEXT NOT NULL,
+    sender_id     TEXT NOT NULL,
+    project       TEXT NOT NULL DEFAULT '',
+    status        TEXT NOT NULL DEFAULT 'active',
+    summary       TEXT,
+    started_at    TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    last_activity TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+CREATE INDEX IF NOT EXISTS idx_conversations_project
+    ON conversations(channel, sender_id, project, status, last_activity);
+CREATE INDEX IF NOT EXISTS idx_conversations_status
+    ON conversations(status, last_activity);
+
+-- `search` indexes user messages only, like messages_fts on SQLite.
+CREATE TABLE IF NOT EXISTS messages (
+    id              TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL REFERENCES conversations(id),
+    role            TEXT NOT NULL CHECK (role IN ('user', 'assistant')),
+    content         TEXT NOT NULL,
+    timestamp       TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    metadata_json   TEXT,
+    search          TSVECTOR GENERATED ALWAYS AS (
+        CASE WHEN role = 'user' THEN to_tsvector('simple', content) END
+    ) STORED
+);
+CREATE INDEX IF NOT EXISTS idx_messages_conversation
+    ON messages(conversation_id, timestamp);
+CREATE INDEX IF NOT EXISTS idx_messages_search ON messages USING GIN (search);
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id            TEXT PRIMARY KEY,
+    timestamp     TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    channel       TEXT NOT NULL,
+    sender_id     TEXT NOT NULL,
+    sender_name   TEXT,
+    input_text    TEXT NOT NULL,
+    output_text   TEXT,
+    provider_used TEXT,
+    model         TEXT,
+    processing_ms BIGINT,
+    status        TEXT NOT NULL DEFAULT 'ok' CHECK (status IN ('ok', 'error', 'denied')),
+    denial_reason TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
+CREATE INDEX IF NOT EXISTS idx_audit_log_sender ON audit_log(channel, sender_id);
+
+CREATE TABLE IF NOT EXISTS facts (
+    id         TEXT PRIMARY KEY,
+    sender_id  TEXT NOT NULL,
+    key        TEXT NOT NULL,
+    value      TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    UNIQUE (sender_id, key)
+);
+
+CREATE TABLE IF NOT EXISTS scheduled_tasks (
+    id           TEXT PRIMARY KEY,
+    channel      TEXT NOT NULL,
+    sender_id    TEXT NOT NULL,
+    reply_target TEXT NOT NULL,
+    description  TEXT NOT NULL,
+    due_at       TEXT NOT NULL,
+    repeat       TEXT,
+    status       TEXT NOT NULL DEFAULT 'pending',
+    task_type    TEXT NOT NULL DEFAULT 'reminder',
+    project      TEXT NOT NULL DEFAULT '',
+    retry_count  INTEGER NOT NULL DEFAULT 0,
+    last_error   TEXT,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    delivered_at TIMESTAMPTZ
+);
+CREATE INDEX IF NOT EXISTS idx_scheduled_tasks_due ON scheduled_tasks(status, due_at);
+CREATE INDEX IF NOT EXISTS idx_scheduled_tasks_sender ON scheduled_tasks(sender_id, status);
+
+CREATE TABLE IF NOT EXISTS limitations (
+    id            TEXT PRIMARY KEY,
+    title         TEXT NOT NULL,
+    description   TEXT NOT NULL,
+    proposed_plan TEXT NOT NULL DEFAULT '',
+    status        TEXT NOT NULL DEFAULT 'open',
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    resolved_at   TIMESTAMPTZ
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_limitations_title ON limitations(lower(title));
+
+CREATE TABLE IF NOT EXISTS user_aliases (
+    alias_sender_id     TEXT PRIMARY KEY,
+    canonical_sender_id TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS outcomes (
+    id        TEXT PRIMARY KEY,
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    sender_id TEXT NOT NULL,
+    domain    TEXT NOT NULL,
+    score     INTEGER NOT NULL CHECK (score IN (-1, 0, 1)),
+    lesson    TEXT NOT NULL,
+    source    TEXT NOT NULL DEFAULT 'conversation',
+    project   TEXT NOT NULL DEFAULT ''
+);
+CREATE INDEX IF NOT EXISTS idx_outcomes_time ON outcomes(timestamp);
+CREATE INDEX IF NOT EXISTS idx_outcomes_project ON outcomes(sender_id, project, timestamp);
+
+CREATE TABLE IF NOT EXISTS lessons (
+    id          TEXT PRIMARY KEY,
+    sender_id   TEXT NOT NULL,
+    domain      TEXT NOT NULL,
+    rule        TEXT NOT NULL,
+    project     TEXT NOT NULL DEFAULT '',
+    occurrences INTEGER NOT NULL DEFAULT 1,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+CREATE INDEX IF NOT EXISTS idx_lessons_domain ON lessons(sender_id, domain, project);
+
+CREATE TABLE IF NOT EXISTS project_sessions (
+    id             TEXT PRIMARY KEY,
+    channel        TEXT NOT NULL,
+    sender_id      TEXT NOT NULL,
+    project        TEXT NOT NULL DEFAULT '',
+    session_id     TEXT NOT NULL,
+    parent_project TEXT,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    UNIQUE (channel, sender_id, project)
+);
+
+CREATE TABLE IF NOT EXISTS heartbeat_settings (
+    sender_id        TEXT PRIMARY KEY,
+    channel          TEXT NOT NULL,
+    reply_target     TEXT NOT NULL,
+    enabled          BOOLEAN NOT NULL DEFAULT TRUE,
+    interval_minutes BIGINT NOT NULL DEFAULT 30,
+    active_start     TEXT NOT NULL DEFAULT '',
+    active_end       TEXT NOT NULL DEFAULT '',
+    checklist        TEXT NOT NULL DEFAULT '',
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+-- cadence: '' (every pulse), 'hourly', 'daily HH:MM', 'weekly <day> HH:MM'.
+CREATE TABLE IF NOT EXISTS heartbeat_items (
+    id              TEXT PRIMARY KEY,
+    sender_id       TEXT NOT NULL,
+    project         TEXT NOT NULL DEFAULT '',
+    description     TEXT NOT NULL,
+    cadence         TEXT NOT NULL DEFAULT '',
+    alert_on_change BOOLEAN NOT NULL DEFAULT FALSE,
+    last_checked_at TIMESTAMPTZ,
+    last_result     TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+CREATE INDEX IF NOT EXISTS idx_heartbeat_items_sender ON heartbeat_items(sender_id, project);
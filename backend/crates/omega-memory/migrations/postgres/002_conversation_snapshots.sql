@@ -0,0 +1,12 @@
+-- Named copies of a conversation's messages (SQLite migration 017).
+CREATE TABLE IF NOT EXISTS conversation_snapshots (
+    id            TEXT PRIMARY KEY,
+    channel       TEXT NOT NULL,
+    sender_id     TEXT NOT NULL,
+    project       TEXT NOT NULL DEFAULT '',
+    name          TEXT NOT NULL,
+    messages      TEXT NOT NULL,
+    message_count BIGINT NOT NULL DEFAULT 0,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    UNIQUE (channel, sender_id, project, name)
+);
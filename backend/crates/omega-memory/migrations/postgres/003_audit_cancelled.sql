@@ -0,0 +1,4 @@
+-- Allow status = 'cancelled' for requests stopped with /stop (SQLite migration 018).
+ALTER TABLE audit_log DROP CONSTRAINT IF EXISTS audit_log_status_check;
+ALTER TABLE audit_log ADD CONSTRAINT audit_log_status_check
+    CHECK (status IN ('ok', 'error', 'denied', 'cancelled'));
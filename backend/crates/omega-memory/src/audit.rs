@@ -1,7 +1,10 @@
 //! Audit log — records every interaction through Omega.
 
+use crate::backend::{AuditStore, MemoryBackend};
+use crate::store::{SqliteBackend, Store};
+use async_trait::async_trait;
 use omega_core::error::OmegaError;
-use sqlx::SqlitePool;
+use std::sync::Arc;
 use tracing::debug;
 use uuid::Uuid;
 
@@ -24,32 +27,54 @@ pub enum AuditStatus {
     Ok,
     Error,
     Denied,
+    /// Stopped by the user (`/stop`).
+    Cancelled,
 }
 
 impl AuditStatus {
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             Self::Ok => "ok",
             Self::Error => "error",
             Self::Denied => "denied",
+            Self::Cancelled => "cancelled",
         }
     }
 }
 
-/// Audit logger backed by SQLite.
+/// Audit logger writing to the memory store's backend.
 #[derive(Clone)]
 pub struct AuditLogger {
-    pool: SqlitePool,
+    backend: Arc<dyn MemoryBackend>,
 }
 
 impl AuditLogger {
-    /// Create a new audit logger sharing the given pool.
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    /// Create a new audit logger sharing the store's backend.
+    pub fn new(store: &Store) -> Self {
+        Self {
+            backend: store.backend().clone(),
+        }
     }
 
     /// Write an entry to the audit log.
     pub async fn log(&self, entry: &AuditEntry) -> Result<(), OmegaError> {
+        self.backend.log_audit(entry).await?;
+
+        debug!(
+            "audit: {} {} [{}] {}",
+            entry.channel,
+            entry.sender_id,
+            entry.status.as_str(),
+            truncate(&entry.input_text, 80)
+        );
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditStore for SqliteBackend {
+    async fn log_audit(&self, entry: &AuditEntry) -> Result<(), OmegaError> {
         let id = Uuid::new_v4().to_string();
 
         sqlx::query(
@@ -69,18 +94,10 @@ impl AuditLogger {
         .bind(entry.processing_ms)
         .bind(entry.status.as_str())
         .bind(&entry.denial_reason)
-        .execute(&self.pool)
+        .execute(self.pool())
         .await
         .map_err(|e| OmegaError::Memory(format!("audit log write failed: {e}")))?;
 
-        debug!(
-            "audit: {} {} [{}] {}",
-            entry.channel,
-            entry.sender_id,
-            entry.status.as_str(),
-            truncate(&entry.input_text, 80)
-        );
-
         Ok(())
     }
 }
@@ -97,11 +114,11 @@ fn truncate(s: &str, max: usize) -> &str {
 mod tests {
     use super::*;
     use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-    use sqlx::Row;
+    use sqlx::{Row, SqlitePool};
     use std::str::FromStr;
 
-    /// Create an in-memory SQLite pool with the audit_log table.
-    async fn test_pool() -> SqlitePool {
+    /// Create an in-memory SQLite store and return it with its pool.
+    async fn test_store() -> (Store, SqlitePool) {
         let opts = SqliteConnectOptions::from_str("sqlite::memory:")
             .unwrap()
             .create_if_missing(true);
@@ -110,17 +127,14 @@ mod tests {
             .connect_with(opts)
             .await
             .unwrap();
-        sqlx::raw_sql(include_str!("../migrations/002_audit_log.sql"))
-            .execute(&pool)
-            .await
-            .unwrap();
-        pool
+        let backend = SqliteBackend::from_pool(pool.clone()).await.unwrap();
+        (Store::with_backend(Arc::new(backend), 10), pool)
     }
 
     #[tokio::test]
     async fn test_audit_logger_log_inserts_entry() {
-        let pool = test_pool().await;
-        let logger = AuditLogger::new(pool.clone());
+        let (store, pool) = test_store().await;
+        let logger = AuditLogger::new(&store);
 
         let entry = AuditEntry {
             channel: "telegram".to_string(),
@@ -170,6 +184,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_audit_logger_log_cancelled_status() {
+        let (store, pool) = test_store().await;
+        let logger = AuditLogger::new(&store);
+
+        let entry = AuditEntry {
+            channel: "telegram".to_string(),
+            sender_id: "user42".to_string(),
+            sender_name: None,
+            input_text: "[STOP] message".to_string(),
+            output_text: Some("[cancelled] after 3s".to_string()),
+            provider_used: Some("claude-code".to_string()),
+            model: None,
+            processing_ms: Some(3000),
+            status: AuditStatus::Cancelled,
+            denial_reason: None,
+        };
+
+        logger.log(&entry).await.unwrap();
+
+        let row = sqlx::query("SELECT status FROM audit_log LIMIT 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(row.get::<String, _>("status"), "cancelled");
+    }
+
     #[test]
     fn test_truncate_ascii() {
         assert_eq!(truncate("hello", 10), "hello");
@@ -0,0 +1,1658 @@
+//! In-memory backend — nothing is persisted, everything lives behind one mutex.
+//!
+//! Mirrors the SQLite backend's behavior so tests (and throwaway runs) do not
+//! need a database. User content is never written anywhere, so the cipher is
+//! ignored and recall is a case-insensitive substring match.
+
+use super::{
+    AuditStore, ConversationRef, ConversationStore, FactStore, HeartbeatItemStore, HeartbeatStore,
+    IdentityStore, LearningStore, MemoryBackend, MessageStore, PendingTask, SessionStore,
+    SnapshotStore, TaskStore, CONVERSATION_TIMEOUT_MINUTES,
+};
+use crate::audit::AuditEntry;
+use crate::store::{
+    descriptions_are_similar, normalize_due_at, ConversationSnapshot, DueTask, Exchange,
+    FactConflict, HeartbeatItem, HeartbeatSettings, MergeReport, NewHeartbeatItem,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDateTime, Utc, Weekday};
+use omega_core::{
+    crypto::Cipher,
+    error::OmegaError,
+    message::{IncomingMessage, MessageMetadata, OutgoingMessage},
+};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Timestamps are reported in the SQLite backend's format.
+const TS_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Backend that keeps every table in process memory.
+#[derive(Clone, Default)]
+pub struct InMemoryBackend {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Monotonic counter that breaks timestamp ties by insertion order.
+    seq: u64,
+    conversations: Vec<Conversation>,
+    messages: Vec<Message>,
+    facts: Vec<Fact>,
+    aliases: BTreeMap<String, String>,
+    limitations: Vec<Limitation>,
+    tasks: Vec<Task>,
+    outcomes: Vec<Outcome>,
+    lessons: Vec<Lesson>,
+    sessions: BTreeMap<(String, String, String), String>,
+    /// Keyed by (channel, sender_id, project, name).
+    snapshots: BTreeMap<(String, String, String, String), Snapshot>,
+    heartbeats: BTreeMap<String, (HeartbeatSettings, String)>,
+    heartbeat_items: Vec<HeartbeatItem>,
+    audit: Vec<AuditRecord>,
+}
+
+struct Conversation {
+    id: String,
+    channel: String,
+    sender_id: String,
+    project: String,
+    active: bool,
+    summary: Option<String>,
+    updated_at: (DateTime<Utc>, u64),
+    last_activity: DateTime<Utc>,
+}
+
+struct Message {
+    conversation_id: String,
+    role: String,
+    content: String,
+    timestamp: DateTime<Utc>,
+    metadata: Option<MessageMetadata>,
+}
+
+struct Snapshot {
+    messages: Vec<(String, String)>,
+    created_at: (DateTime<Utc>, u64),
+}
+
+struct Fact {
+    sender_id: String,
+    key: String,
+    value: String,
+    updated_at: (DateTime<Utc>, u64),
+}
+
+struct Limitation {
+    title: String,
+    description: String,
+    proposed_plan: String,
+}
+
+struct Task {
+    id: String,
+    channel: String,
+    sender_id: String,
+    reply_target: String,
+    description: String,
+    due_at: String,
+    repeat: Option<String>,
+    status: &'static str,
+    task_type: String,
+    project: String,
+    retry_count: u32,
+}
+
+struct Outcome {
+    sender_id: String,
+    domain: String,
+    score: i32,
+    lesson: String,
+    project: String,
+    timestamp: DateTime<Utc>,
+}
+
+struct Lesson {
+    sender_id: String,
+    domain: String,
+    rule: String,
+    project: String,
+    occurrences: i64,
+    updated_at: (DateTime<Utc>, u64),
+}
+
+/// Kept only so the audit trail exists while the process runs.
+#[allow(dead_code)]
+struct AuditRecord {
+    timestamp: DateTime<Utc>,
+    channel: String,
+    sender_id: String,
+    input_text: String,
+    status: &'static str,
+}
+
+impl InMemoryBackend {
+    /// Create an empty backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // A panic while holding the lock cannot leave a half-applied SQL
+        // transaction behind, so the data is still usable.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl State {
+    /// `now` plus a tiebreaker, for columns that are sorted on.
+    fn stamp(&mut self) -> (DateTime<Utc>, u64) {
+        self.seq += 1;
+        (Utc::now(), self.seq)
+    }
+}
+
+fn fmt(ts: DateTime<Utc>) -> String {
+    ts.format(TS_FORMAT).to_string()
+}
+
+/// Parse a stored due time the way SQLite's `datetime()` does (UTC unless an
+/// offset is given). `None` for anything unparseable.
+fn parse_due(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(&value.replacen(' ', "T", 1)) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        .map(|ts| ts.and_utc())
+}
+
+fn matches_prefix(id: &str, prefix: &str) -> bool {
+    id.starts_with(prefix)
+}
+
+#[async_trait]
+impl ConversationStore for InMemoryBackend {
+    async fn get_or_create_conversation(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<String, OmegaError> {
+        let mut state = self.lock();
+        let cutoff = Utc::now() - Duration::minutes(CONVERSATION_TIMEOUT_MINUTES);
+        let stamp = state.stamp();
+        if let Some(conv) = state
+            .conversations
+            .iter_mut()
+            .filter(|c| {
+                c.active
+                    && c.channel == channel
+                    && c.sender_id == sender_id
+                    && c.project == project
+                    && c.last_activity > cutoff
+            })
+            .max_by_key(|c| c.last_activity)
+        {
+            conv.last_activity = stamp.0;
+            conv.updated_at = stamp;
+            return Ok(conv.id.clone());
+        }
+
+        let id = Uuid::new_v4().to_string();
+        state.conversations.push(Conversation {
+            id: id.clone(),
+            channel: channel.to_string(),
+            sender_id: sender_id.to_string(),
+            project: project.to_string(),
+            active: true,
+            summary: None,
+            updated_at: stamp,
+            last_activity: stamp.0,
+        });
+        Ok(id)
+    }
+
+    async fn get_active_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let cutoff = Utc::now() - Duration::minutes(CONVERSATION_TIMEOUT_MINUTES);
+        Ok(self
+            .lock()
+            .conversations
+            .iter()
+            .filter(|c| {
+                c.active
+                    && c.channel == channel
+                    && c.sender_id == sender_id
+                    && c.project == project
+                    && c.last_activity > cutoff
+            })
+            .max_by_key(|c| c.last_activity)
+            .map(|c| c.id.clone()))
+    }
+
+    async fn get_open_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        Ok(self
+            .lock()
+            .conversations
+            .iter()
+            .filter(|c| {
+                c.active && c.channel == channel && c.sender_id == sender_id && c.project == project
+            })
+            .max_by_key(|c| c.last_activity)
+            .map(|c| c.id.clone()))
+    }
+
+    async fn get_conversation_sender(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        Ok(self
+            .lock()
+            .conversations
+            .iter()
+            .find(|c| c.id == conversation_id)
+            .map(|c| c.sender_id.clone()))
+    }
+
+    async fn find_idle_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError> {
+        let cutoff = Utc::now() - Duration::minutes(CONVERSATION_TIMEOUT_MINUTES);
+        Ok(self
+            .lock()
+            .conversations
+            .iter()
+            .filter(|c| c.active && c.last_activity <= cutoff)
+            .map(conversation_ref)
+            .collect())
+    }
+
+    async fn find_all_active_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError> {
+        Ok(self
+            .lock()
+            .conversations
+            .iter()
+            .filter(|c| c.active)
+            .map(conversation_ref)
+            .collect())
+    }
+
+    async fn get_conversation_messages(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        Ok(self
+            .lock()
+            .messages
+            .iter()
+            .filter(|m| m.conversation_id == conversation_id)
+            .map(|m| (m.role.clone(), m.content.clone()))
+            .collect())
+    }
+
+    async fn get_recent_messages(
+        &self,
+        conversation_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let mut rows = self.get_conversation_messages(conversation_id).await?;
+        let excess = rows.len().saturating_sub(limit.max(0) as usize);
+        rows.drain(..excess);
+        Ok(rows)
+    }
+
+    async fn close_conversation(
+        &self,
+        conversation_id: &str,
+        summary: &str,
+    ) -> Result<(), OmegaError> {
+        let mut state = self.lock();
+        let stamp = state.stamp();
+        if let Some(conv) = state
+            .conversations
+            .iter_mut()
+            .find(|c| c.id == conversation_id)
+        {
+            conv.active = false;
+            conv.summary = Some(summary.to_string());
+            conv.updated_at = stamp;
+        }
+        Ok(())
+    }
+
+    async fn close_current_conversation(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<bool, OmegaError> {
+        let mut state = self.lock();
+        let stamp = state.stamp();
+        let mut closed = false;
+        for conv in state.conversations.iter_mut().filter(|c| {
+            c.active && c.channel == channel && c.sender_id == sender_id && c.project == project
+        }) {
+            conv.active = false;
+            conv.updated_at = stamp;
+            closed = true;
+        }
+        Ok(closed)
+    }
+
+    async fn get_recent_summaries(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        Ok(closed_summaries(
+            &self.lock(),
+            |c| c.channel == channel && c.sender_id == sender_id && c.summary.is_some(),
+            limit,
+        ))
+    }
+
+    async fn get_all_recent_summaries(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        Ok(closed_summaries(
+            &self.lock(),
+            |c| c.summary.is_some(),
+            limit,
+        ))
+    }
+
+    async fn get_history(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        Ok(closed_summaries(
+            &self.lock(),
+            |c| c.channel == channel && c.sender_id == sender_id,
+            limit,
+        ))
+    }
+
+    async fn get_conversation_token_estimate(
+        &self,
+        conversation_id: &str,
+    ) -> Result<(i64, i64), OmegaError> {
+        let state = self.lock();
+        let (count, chars) = state
+            .messages
+            .iter()
+            .filter(|m| m.conversation_id == conversation_id)
+            .fold((0i64, 0i64), |(n, c), m| {
+                (n + 1, c + m.content.chars().count() as i64)
+            });
+        Ok((count, chars / 4))
+    }
+
+    async fn get_memory_stats(&self, sender_id: &str) -> Result<(i64, i64, i64), OmegaError> {
+        let state = self.lock();
+        let conversations: Vec<&str> = state
+            .conversations
+            .iter()
+            .filter(|c| c.sender_id == sender_id)
+            .map(|c| c.id.as_str())
+            .collect();
+        let messages = state
+            .messages
+            .iter()
+            .filter(|m| conversations.contains(&m.conversation_id.as_str()))
+            .count();
+        let facts = state
+            .facts
+            .iter()
+            .filter(|f| f.sender_id == sender_id)
+            .count();
+        Ok((conversations.len() as i64, messages as i64, facts as i64))
+    }
+}
+
+fn conversation_ref(c: &Conversation) -> ConversationRef {
+    (
+        c.id.clone(),
+        c.channel.clone(),
+        c.sender_id.clone(),
+        c.project.clone(),
+    )
+}
+
+/// Closed conversations matching `filter`, newest first, as `(summary, updated_at)`.
+fn closed_summaries(
+    state: &State,
+    filter: impl Fn(&Conversation) -> bool,
+    limit: i64,
+) -> Vec<(String, String)> {
+    let mut rows: Vec<&Conversation> = state
+        .conversations
+        .iter()
+        .filter(|c| !c.active && filter(c))
+        .collect();
+    rows.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+    rows.into_iter()
+        .take(limit.max(0) as usize)
+        .map(|c| {
+            (
+                c.summary.clone().unwrap_or_else(|| "(no summary)".into()),
+                fmt(c.updated_at.0),
+            )
+        })
+        .collect()
+}
+
+#[async_trait]
+impl MessageStore for InMemoryBackend {
+    async fn store_exchange(
+        &self,
+        incoming: &IncomingMessage,
+        response: &OutgoingMessage,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        let conversation_id = self
+            .get_or_create_conversation(&incoming.channel, &incoming.sender_id, project)
+            .await?;
+        let mut state = self.lock();
+        for (role, content, metadata) in [
+            ("user", &incoming.text, None),
+            ("assistant", &response.text, Some(response.metadata.clone())),
+        ] {
+            let timestamp = state.stamp().0;
+            state.messages.push(Message {
+                conversation_id: conversation_id.clone(),
+                role: role.to_string(),
+                content: content.clone(),
+                timestamp,
+                metadata,
+            });
+        }
+        Ok(())
+    }
+
+    async fn pop_last_exchange(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<Exchange>, OmegaError> {
+        let Some(conversation_id) = self
+            .get_open_conversation_id(channel, sender_id, project)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let mut state = self.lock();
+        let positions: Vec<usize> = state
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.conversation_id == conversation_id)
+            .map(|(i, _)| i)
+            .rev()
+            .take(2)
+            .collect();
+        let [asst, user] = positions[..] else {
+            return Ok(None);
+        };
+        if state.messages[asst].role != "assistant" || state.messages[user].role != "user" {
+            return Ok(None);
+        }
+        let assistant = state.messages.remove(asst);
+        let user = state.messages.remove(user);
+        Ok(Some(Exchange {
+            user: user.content,
+            assistant: assistant.content,
+            metadata: assistant.metadata.unwrap_or_default(),
+        }))
+    }
+
+    async fn search_messages(
+        &self,
+        query: &str,
+        exclude_conversation_id: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String, String)>, OmegaError> {
+        if query.len() < 3 {
+            return Ok(Vec::new());
+        }
+        let needle = query.to_lowercase();
+        let state = self.lock();
+        let senders: Vec<&str> = state
+            .conversations
+            .iter()
+            .filter(|c| c.sender_id == sender_id && c.id != exclude_conversation_id)
+            .map(|c| c.id.as_str())
+            .collect();
+        Ok(state
+            .messages
+            .iter()
+            .rev()
+            .filter(|m| {
+                m.role == "user"
+                    && senders.contains(&m.conversation_id.as_str())
+                    && m.content.to_lowercase().contains(&needle)
+            })
+            .take(limit.max(0) as usize)
+            .map(|m| (m.role.clone(), m.content.clone(), fmt(m.timestamp)))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl FactStore for InMemoryBackend {
+    async fn store_fact(&self, sender_id: &str, key: &str, value: &str) -> Result<(), OmegaError> {
+        let mut state = self.lock();
+        let stamp = state.stamp();
+        match state
+            .facts
+            .iter_mut()
+            .find(|f| f.sender_id == sender_id && f.key == key)
+        {
+            Some(fact) => {
+                fact.value = value.to_string();
+                fact.updated_at = stamp;
+            }
+            None => state.facts.push(Fact {
+                sender_id: sender_id.to_string(),
+                key: key.to_string(),
+                value: value.to_string(),
+                updated_at: stamp,
+            }),
+        }
+        Ok(())
+    }
+
+    async fn get_fact(&self, sender_id: &str, key: &str) -> Result<Option<String>, OmegaError> {
+        Ok(self
+            .lock()
+            .facts
+            .iter()
+            .find(|f| f.sender_id == sender_id && f.key == key)
+            .map(|f| f.value.clone()))
+    }
+
+    async fn delete_fact(&self, sender_id: &str, key: &str) -> Result<bool, OmegaError> {
+        Ok(self.delete_facts(sender_id, Some(key)).await? > 0)
+    }
+
+    async fn get_facts(&self, sender_id: &str) -> Result<Vec<(String, String)>, OmegaError> {
+        let mut rows: Vec<(String, String)> = self
+            .lock()
+            .facts
+            .iter()
+            .filter(|f| f.sender_id == sender_id)
+            .map(|f| (f.key.clone(), f.value.clone()))
+            .collect();
+        rows.sort();
+        Ok(rows)
+    }
+
+    async fn delete_facts(&self, sender_id: &str, key: Option<&str>) -> Result<u64, OmegaError> {
+        let mut state = self.lock();
+        let before = state.facts.len();
+        state
+            .facts
+            .retain(|f| !(f.sender_id == sender_id && key.is_none_or(|k| f.key == k)));
+        Ok((before - state.facts.len()) as u64)
+    }
+
+    async fn get_all_facts(&self) -> Result<Vec<(String, String)>, OmegaError> {
+        let mut rows: Vec<(String, String)> = self
+            .lock()
+            .facts
+            .iter()
+            .filter(|f| f.key != "welcomed")
+            .map(|f| (f.key.clone(), f.value.clone()))
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(rows)
+    }
+
+    async fn get_all_facts_by_key(&self, key: &str) -> Result<Vec<(String, String)>, OmegaError> {
+        let mut rows: Vec<(String, String)> = self
+            .lock()
+            .facts
+            .iter()
+            .filter(|f| f.key == key)
+            .map(|f| (f.sender_id.clone(), f.value.clone()))
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(rows)
+    }
+
+    async fn is_new_user(&self, sender_id: &str) -> Result<bool, OmegaError> {
+        Ok(self.get_fact(sender_id, "welcomed").await?.is_none())
+    }
+
+    async fn resolve_sender_id(&self, sender_id: &str) -> Result<String, OmegaError> {
+        Ok(self
+            .lock()
+            .aliases
+            .get(sender_id)
+            .cloned()
+            .unwrap_or_else(|| sender_id.to_string()))
+    }
+
+    async fn create_alias(&self, alias_id: &str, canonical_id: &str) -> Result<(), OmegaError> {
+        self.lock()
+            .aliases
+            .entry(alias_id.to_string())
+            .or_insert_with(|| canonical_id.to_string());
+        Ok(())
+    }
+
+    async fn get_aliases(&self, canonical_id: &str) -> Result<Vec<String>, OmegaError> {
+        Ok(self
+            .lock()
+            .aliases
+            .iter()
+            .filter(|(_, canonical)| *canonical == canonical_id)
+            .map(|(alias, _)| alias.clone())
+            .collect())
+    }
+
+    async fn find_canonical_user(
+        &self,
+        exclude_sender_id: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        Ok(self
+            .lock()
+            .facts
+            .iter()
+            .find(|f| f.key == "welcomed" && f.sender_id != exclude_sender_id)
+            .map(|f| f.sender_id.clone()))
+    }
+
+    async fn store_limitation(
+        &self,
+        title: &str,
+        description: &str,
+        proposed_plan: &str,
+    ) -> Result<bool, OmegaError> {
+        let mut state = self.lock();
+        if state
+            .limitations
+            .iter()
+            .any(|l| l.title.eq_ignore_ascii_case(title))
+        {
+            return Ok(false);
+        }
+        state.limitations.push(Limitation {
+            title: title.to_string(),
+            description: description.to_string(),
+            proposed_plan: proposed_plan.to_string(),
+        });
+        Ok(true)
+    }
+
+    async fn get_open_limitations(&self) -> Result<Vec<(String, String, String)>, OmegaError> {
+        Ok(self
+            .lock()
+            .limitations
+            .iter()
+            .map(|l| {
+                (
+                    l.title.clone(),
+                    l.description.clone(),
+                    l.proposed_plan.clone(),
+                )
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl IdentityStore for InMemoryBackend {
+    async fn merge_sender(&self, from: &str, into: &str) -> Result<MergeReport, OmegaError> {
+        if from == into {
+            return Err(OmegaError::Memory(
+                "cannot merge a sender into itself".into(),
+            ));
+        }
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        let mut report = MergeReport::default();
+
+        // Facts: move, drop duplicates, resolve conflicts by recency.
+        let stamp = state.stamp();
+        let (theirs, mut facts): (Vec<Fact>, Vec<Fact>) = std::mem::take(&mut state.facts)
+            .into_iter()
+            .partition(|f| f.sender_id == from);
+        for fact in theirs {
+            match facts
+                .iter_mut()
+                .find(|f| f.sender_id == into && f.key == fact.key)
+            {
+                None => {
+                    facts.push(Fact {
+                        sender_id: into.to_string(),
+                        ..fact
+                    });
+                    report.facts += 1;
+                }
+                Some(ours) if ours.value == fact.value => {}
+                Some(ours) => {
+                    let theirs_newer = fact.updated_at > ours.updated_at;
+                    let (kept, discarded) = if theirs_newer {
+                        let previous = std::mem::replace(&mut ours.value, fact.value.clone());
+                        ours.updated_at = stamp;
+                        (fact.value, previous)
+                    } else {
+                        (ours.value.clone(), fact.value)
+                    };
+                    report.conflicts.push(FactConflict {
+                        key: fact.key,
+                        kept,
+                        discarded,
+                    });
+                }
+            }
+        }
+        state.facts = facts;
+
+        // Conversations: one active conversation per (channel, sender, project).
+        let active: Vec<(String, String)> = state
+            .conversations
+            .iter()
+            .filter(|c| c.sender_id == into && c.active)
+            .map(|c| (c.channel.clone(), c.project.clone()))
+            .collect();
+        for conv in state
+            .conversations
+            .iter_mut()
+            .filter(|c| c.sender_id == from)
+        {
+            if conv.active && active.contains(&(conv.channel.clone(), conv.project.clone())) {
+                conv.active = false;
+                conv.updated_at = stamp;
+            }
+            conv.sender_id = into.to_string();
+            report.conversations += 1;
+        }
+
+        for lesson in state.lessons.iter_mut().filter(|l| l.sender_id == from) {
+            lesson.sender_id = into.to_string();
+            report.lessons += 1;
+        }
+        for task in state.tasks.iter_mut().filter(|t| t.sender_id == from) {
+            task.sender_id = into.to_string();
+            report.tasks += 1;
+        }
+        for outcome in state.outcomes.iter_mut().filter(|o| o.sender_id == from) {
+            outcome.sender_id = into.to_string();
+        }
+        for item in state
+            .heartbeat_items
+            .iter_mut()
+            .filter(|i| i.sender_id == from)
+        {
+            item.sender_id = into.to_string();
+        }
+
+        // Per-sender unique rows: the canonical id's row wins.
+        let sessions: Vec<_> = state
+            .sessions
+            .keys()
+            .filter(|(_, sender, _)| sender == from)
+            .cloned()
+            .collect();
+        for key in sessions {
+            let session = state.sessions.remove(&key).unwrap_or_default();
+            let (channel, _, project) = key;
+            state
+                .sessions
+                .entry((channel, into.to_string(), project))
+                .or_insert(session);
+        }
+        let snapshots: Vec<_> = state
+            .snapshots
+            .keys()
+            .filter(|(_, sender, _, _)| sender == from)
+            .cloned()
+            .collect();
+        for key in snapshots {
+            let Some(snapshot) = state.snapshots.remove(&key) else {
+                continue;
+            };
+            let (channel, _, project, name) = key;
+            state
+                .snapshots
+                .entry((channel, into.to_string(), project, name))
+                .or_insert(snapshot);
+        }
+        if let Some((mut settings, checklist)) = state.heartbeats.remove(from) {
+            settings.sender_id = into.to_string();
+            state
+                .heartbeats
+                .entry(into.to_string())
+                .or_insert((settings, checklist));
+        }
+
+        // Aliases: everything that pointed at `from` now points at `into`.
+        for canonical in state.aliases.values_mut().filter(|c| *c == from) {
+            *canonical = into.to_string();
+        }
+        state.aliases.insert(from.to_string(), into.to_string());
+        state.aliases.retain(|alias, canonical| alias != canonical);
+
+        Ok(report)
+    }
+
+    async fn remove_alias(&self, alias_id: &str) -> Result<bool, OmegaError> {
+        Ok(self.lock().aliases.remove(alias_id).is_some())
+    }
+}
+
+#[async_trait]
+impl TaskStore for InMemoryBackend {
+    async fn create_task(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        reply_target: &str,
+        description: &str,
+        due_at: &str,
+        repeat: Option<&str>,
+        task_type: &str,
+        project: &str,
+    ) -> Result<String, OmegaError> {
+        let normalized_due = normalize_due_at(due_at);
+        let mut state = self.lock();
+        let pending = |t: &&Task| t.sender_id == sender_id && t.status == "pending";
+
+        // Level 1: exact dedup on (sender, description, normalized due_at).
+        if let Some(task) = state
+            .tasks
+            .iter()
+            .filter(pending)
+            .find(|t| t.description == description && t.due_at == normalized_due)
+        {
+            return Ok(task.id.clone());
+        }
+
+        // Level 2: fuzzy dedup — similar description, due_at within 30 min.
+        if let Some(due) = parse_due(&normalized_due) {
+            if let Some(task) = state.tasks.iter().filter(pending).find(|t| {
+                parse_due(&t.due_at).is_some_and(|d| (d - due).num_seconds().abs() <= 1800)
+                    && descriptions_are_similar(description, &t.description)
+            }) {
+                return Ok(task.id.clone());
+            }
+        }
+
+        let id = Uuid::new_v4().to_string();
+        state.tasks.push(Task {
+            id: id.clone(),
+            channel: channel.to_string(),
+            sender_id: sender_id.to_string(),
+            reply_target: reply_target.to_string(),
+            description: description.to_string(),
+            due_at: normalized_due,
+            repeat: repeat.map(String::from),
+            status: "pending",
+            task_type: task_type.to_string(),
+            project: project.to_string(),
+            retry_count: 0,
+        });
+        Ok(id)
+    }
+
+    async fn get_due_tasks(&self) -> Result<Vec<DueTask>, OmegaError> {
+        let now = Utc::now();
+        Ok(self
+            .lock()
+            .tasks
+            .iter()
+            .filter(|t| t.status == "pending" && parse_due(&t.due_at).is_some_and(|d| d <= now))
+            .map(|t| DueTask {
+                id: t.id.clone(),
+                channel: t.channel.clone(),
+                sender_id: t.sender_id.clone(),
+                reply_target: t.reply_target.clone(),
+                description: t.description.clone(),
+                repeat: t.repeat.clone(),
+                task_type: t.task_type.clone(),
+                project: t.project.clone(),
+            })
+            .collect())
+    }
+
+    async fn complete_task(&self, id: &str, repeat: Option<&str>) -> Result<(), OmegaError> {
+        let mut state = self.lock();
+        let Some(task) = state.tasks.iter_mut().find(|t| t.id == id) else {
+            return Ok(());
+        };
+        match repeat {
+            None | Some("once") => task.status = "delivered",
+            Some(interval) => {
+                let Some(due) = parse_due(&task.due_at) else {
+                    return Ok(());
+                };
+                let mut next = match interval {
+                    "weekly" => due + Duration::days(7),
+                    "monthly" => due.checked_add_months(Months::new(1)).unwrap_or(due),
+                    _ => due + Duration::days(1),
+                };
+                if interval == "weekdays" {
+                    match next.weekday() {
+                        Weekday::Sat => next += Duration::days(2),
+                        Weekday::Sun => next += Duration::days(1),
+                        _ => {}
+                    }
+                }
+                task.due_at = fmt(next);
+            }
+        }
+        Ok(())
+    }
+
+    async fn fail_task(
+        &self,
+        id: &str,
+        _error: &str,
+        max_retries: u32,
+    ) -> Result<bool, OmegaError> {
+        let mut state = self.lock();
+        let Some(task) = state.tasks.iter_mut().find(|t| t.id == id) else {
+            return Ok(1 < max_retries);
+        };
+        task.retry_count += 1;
+        if task.retry_count < max_retries {
+            task.due_at = fmt(Utc::now() + Duration::minutes(2));
+            Ok(true)
+        } else {
+            task.status = "failed";
+            Ok(false)
+        }
+    }
+
+    async fn get_tasks_for_sender(&self, sender_id: &str) -> Result<Vec<PendingTask>, OmegaError> {
+        let mut rows: Vec<PendingTask> = self
+            .lock()
+            .tasks
+            .iter()
+            .filter(|t| t.sender_id == sender_id && t.status == "pending")
+            .map(|t| {
+                (
+                    t.id.clone(),
+                    t.description.clone(),
+                    t.due_at.clone(),
+                    t.repeat.clone(),
+                    t.task_type.clone(),
+                    t.project.clone(),
+                )
+            })
+            .collect();
+        rows.sort_by(|a, b| a.2.cmp(&b.2));
+        Ok(rows)
+    }
+
+    async fn cancel_task(&self, id_prefix: &str, sender_id: &str) -> Result<bool, OmegaError> {
+        let mut state = self.lock();
+        let mut matched = false;
+        for task in state
+            .tasks
+            .iter_mut()
+            .filter(|t| t.sender_id == sender_id && matches_prefix(&t.id, id_prefix))
+        {
+            match task.status {
+                "pending" => {
+                    task.status = "cancelled";
+                    matched = true;
+                }
+                "cancelled" => matched = true,
+                _ => {}
+            }
+        }
+        Ok(matched)
+    }
+
+    async fn update_task(
+        &self,
+        id_prefix: &str,
+        sender_id: &str,
+        description: Option<&str>,
+        due_at: Option<&str>,
+        repeat: Option<&str>,
+    ) -> Result<bool, OmegaError> {
+        if description.is_none() && due_at.is_none() && repeat.is_none() {
+            return Ok(false);
+        }
+        let mut state = self.lock();
+        let mut updated = false;
+        for task in state.tasks.iter_mut().filter(|t| {
+            t.sender_id == sender_id && t.status == "pending" && matches_prefix(&t.id, id_prefix)
+        }) {
+            if let Some(d) = description {
+                task.description = d.to_string();
+            }
+            if let Some(d) = due_at {
+                task.due_at = d.to_string();
+            }
+            if let Some(r) = repeat {
+                task.repeat = Some(r.to_string());
+            }
+            updated = true;
+        }
+        Ok(updated)
+    }
+
+    async fn defer_task(&self, id: &str, new_due_at: &str) -> Result<(), OmegaError> {
+        if let Some(task) = self
+            .lock()
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == id && t.status == "pending")
+        {
+            task.due_at = new_due_at.to_string();
+        }
+        Ok(())
+    }
+}
+
+/// Outcomes matching `filter`, newest first, as `(score, domain, lesson, timestamp)`.
+fn recent_outcomes(
+    state: &State,
+    filter: impl Fn(&Outcome) -> bool,
+    limit: i64,
+) -> Vec<(i32, String, String, String)> {
+    state
+        .outcomes
+        .iter()
+        .rev()
+        .filter(|o| filter(o))
+        .take(limit.max(0) as usize)
+        .map(|o| {
+            (
+                o.score,
+                o.domain.clone(),
+                o.lesson.clone(),
+                fmt(o.timestamp),
+            )
+        })
+        .collect()
+}
+
+/// Lessons matching `filter`, `project` first, then newest, capped at 50.
+fn ranked_lessons(
+    state: &State,
+    filter: impl Fn(&Lesson) -> bool,
+    project: Option<&str>,
+) -> Vec<(String, String, String)> {
+    let mut rows: Vec<&Lesson> = state.lessons.iter().filter(|l| filter(l)).collect();
+    rows.sort_by(|a, b| {
+        let first = |l: &Lesson| project.is_some_and(|p| l.project == p);
+        first(b)
+            .cmp(&first(a))
+            .then(b.updated_at.cmp(&a.updated_at))
+    });
+    rows.into_iter()
+        .take(50)
+        .map(|l| (l.domain.clone(), l.rule.clone(), l.project.clone()))
+        .collect()
+}
+
+#[async_trait]
+impl LearningStore for InMemoryBackend {
+    async fn store_outcome(
+        &self,
+        sender_id: &str,
+        domain: &str,
+        score: i32,
+        lesson: &str,
+        _source: &str,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        let mut state = self.lock();
+        let timestamp = state.stamp().0;
+        state.outcomes.push(Outcome {
+            sender_id: sender_id.to_string(),
+            domain: domain.to_string(),
+            score,
+            lesson: lesson.to_string(),
+            project: project.to_string(),
+            timestamp,
+        });
+        Ok(())
+    }
+
+    async fn get_recent_outcomes(
+        &self,
+        sender_id: &str,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        Ok(recent_outcomes(
+            &self.lock(),
+            |o| o.sender_id == sender_id && project.is_none_or(|p| o.project == p),
+            limit,
+        ))
+    }
+
+    async fn get_all_recent_outcomes(
+        &self,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        let since = Utc::now() - Duration::hours(hours);
+        Ok(recent_outcomes(
+            &self.lock(),
+            |o| o.timestamp >= since && project.is_none_or(|p| o.project == p),
+            limit,
+        ))
+    }
+
+    async fn get_recent_outcomes_since(
+        &self,
+        sender_id: &str,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        let since = Utc::now() - Duration::hours(hours);
+        Ok(recent_outcomes(
+            &self.lock(),
+            |o| {
+                o.sender_id == sender_id
+                    && o.timestamp >= since
+                    && project.is_none_or(|p| o.project == p)
+            },
+            limit,
+        ))
+    }
+
+    async fn store_lesson(
+        &self,
+        sender_id: &str,
+        domain: &str,
+        rule: &str,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        let mut state = self.lock();
+        let stamp = state.stamp();
+        let same_scope =
+            |l: &Lesson| l.sender_id == sender_id && l.domain == domain && l.project == project;
+
+        if let Some(lesson) = state
+            .lessons
+            .iter_mut()
+            .find(|l| same_scope(l) && l.rule == rule)
+        {
+            lesson.occurrences += 1;
+            lesson.updated_at = stamp;
+            return Ok(());
+        }
+
+        state.lessons.push(Lesson {
+            sender_id: sender_id.to_string(),
+            domain: domain.to_string(),
+            rule: rule.to_string(),
+            project: project.to_string(),
+            occurrences: 1,
+            updated_at: stamp,
+        });
+
+        // Cap enforcement: keep at most 10 per (sender, domain, project).
+        let mut scoped: Vec<(DateTime<Utc>, u64)> = state
+            .lessons
+            .iter()
+            .filter(|l| same_scope(l))
+            .map(|l| l.updated_at)
+            .collect();
+        if scoped.len() > 10 {
+            scoped.sort_by(|a, b| b.cmp(a));
+            let oldest_kept = scoped[9];
+            state
+                .lessons
+                .retain(|l| !same_scope(l) || l.updated_at >= oldest_kept);
+        }
+        Ok(())
+    }
+
+    async fn get_lessons(
+        &self,
+        sender_id: &str,
+        project: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, OmegaError> {
+        let p = project.unwrap_or("");
+        Ok(ranked_lessons(
+            &self.lock(),
+            |l| l.sender_id == sender_id && (l.project == p || l.project.is_empty()),
+            project,
+        ))
+    }
+
+    async fn get_all_lessons(
+        &self,
+        project: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, OmegaError> {
+        Ok(ranked_lessons(
+            &self.lock(),
+            |l| project.is_none_or(|p| l.project == p || l.project.is_empty()),
+            project,
+        ))
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemoryBackend {
+    async fn store_session(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        session_id: &str,
+    ) -> Result<(), OmegaError> {
+        self.lock().sessions.insert(
+            (channel.into(), sender_id.into(), project.into()),
+            session_id.to_string(),
+        );
+        Ok(())
+    }
+
+    async fn get_session(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        Ok(self
+            .lock()
+            .sessions
+            .get(&(channel.into(), sender_id.into(), project.into()))
+            .cloned())
+    }
+
+    async fn clear_session(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        self.lock()
+            .sessions
+            .remove(&(channel.into(), sender_id.into(), project.into()));
+        Ok(())
+    }
+
+    async fn clear_all_sessions_for_sender(&self, sender_id: &str) -> Result<(), OmegaError> {
+        self.lock()
+            .sessions
+            .retain(|(_, sender, _), _| sender != sender_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for InMemoryBackend {
+    async fn save_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError> {
+        let Some(conversation_id) = self
+            .get_open_conversation_id(channel, sender_id, project)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let messages = self.get_conversation_messages(&conversation_id).await?;
+        let count = messages.len();
+        let mut state = self.lock();
+        let created_at = state.stamp();
+        state.snapshots.insert(
+            (
+                channel.into(),
+                sender_id.into(),
+                project.into(),
+                name.into(),
+            ),
+            Snapshot {
+                messages,
+                created_at,
+            },
+        );
+        Ok(Some(count))
+    }
+
+    async fn list_snapshots(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<ConversationSnapshot>, OmegaError> {
+        let state = self.lock();
+        let mut rows: Vec<(&String, &Snapshot)> = state
+            .snapshots
+            .iter()
+            .filter(|((c, s, p, _), _)| c == channel && s == sender_id && p == project)
+            .map(|((_, _, _, name), snapshot)| (name, snapshot))
+            .collect();
+        rows.sort_by_key(|(_, snapshot)| std::cmp::Reverse(snapshot.created_at));
+        Ok(rows
+            .into_iter()
+            .map(|(name, snapshot)| ConversationSnapshot {
+                name: name.clone(),
+                message_count: snapshot.messages.len() as i64,
+                created_at: fmt(snapshot.created_at.0),
+            })
+            .collect())
+    }
+
+    async fn branch_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError> {
+        let key = (
+            channel.into(),
+            sender_id.into(),
+            project.into(),
+            name.into(),
+        );
+        let Some(messages) = self.lock().snapshots.get(&key).map(|s| s.messages.clone()) else {
+            return Ok(None);
+        };
+        self.close_current_conversation(channel, sender_id, project)
+            .await?;
+        let conversation_id = self
+            .get_or_create_conversation(channel, sender_id, project)
+            .await?;
+        let count = messages.len();
+        let mut state = self.lock();
+        for (role, content) in messages {
+            let timestamp = state.stamp().0;
+            state.messages.push(Message {
+                conversation_id: conversation_id.clone(),
+                role,
+                content,
+                timestamp,
+                metadata: None,
+            });
+        }
+        Ok(Some(count))
+    }
+
+    async fn delete_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<bool, OmegaError> {
+        let key = (
+            channel.into(),
+            sender_id.into(),
+            project.into(),
+            name.into(),
+        );
+        Ok(self.lock().snapshots.remove(&key).is_some())
+    }
+}
+
+/// Settings as the SQLite backend reads them back (interval clamped).
+fn settings_view(settings: &HeartbeatSettings) -> HeartbeatSettings {
+    HeartbeatSettings {
+        interval_minutes: settings.interval_minutes.clamp(1, 1440),
+        ..settings.clone()
+    }
+}
+
+#[async_trait]
+impl HeartbeatStore for InMemoryBackend {
+    async fn get_heartbeat_settings(
+        &self,
+        sender_id: &str,
+    ) -> Result<Option<HeartbeatSettings>, OmegaError> {
+        Ok(self
+            .lock()
+            .heartbeats
+            .get(sender_id)
+            .map(|(s, _)| settings_view(s)))
+    }
+
+    async fn get_enabled_heartbeats(&self) -> Result<Vec<HeartbeatSettings>, OmegaError> {
+        Ok(self
+            .lock()
+            .heartbeats
+            .values()
+            .filter(|(s, _)| s.enabled)
+            .map(|(s, _)| settings_view(s))
+            .collect())
+    }
+
+    async fn save_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<(), OmegaError> {
+        let mut state = self.lock();
+        let checklist = state
+            .heartbeats
+            .remove(&settings.sender_id)
+            .map(|(_, checklist)| checklist)
+            .unwrap_or_default();
+        state
+            .heartbeats
+            .insert(settings.sender_id.clone(), (settings.clone(), checklist));
+        Ok(())
+    }
+
+    async fn seed_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<bool, OmegaError> {
+        let mut state = self.lock();
+        if state.heartbeats.contains_key(&settings.sender_id) {
+            return Ok(false);
+        }
+        state.heartbeats.insert(
+            settings.sender_id.clone(),
+            (settings.clone(), String::new()),
+        );
+        Ok(true)
+    }
+
+    async fn get_legacy_heartbeat_checklists(&self) -> Result<Vec<(String, String)>, OmegaError> {
+        Ok(self
+            .lock()
+            .heartbeats
+            .iter()
+            .filter(|(_, (_, checklist))| !checklist.is_empty())
+            .map(|(sender, (_, checklist))| (sender.clone(), checklist.clone()))
+            .collect())
+    }
+
+    async fn clear_legacy_heartbeat_checklist(&self, sender_id: &str) -> Result<(), OmegaError> {
+        if let Some((_, checklist)) = self.lock().heartbeats.get_mut(sender_id) {
+            checklist.clear();
+        }
+        Ok(())
+    }
+}
+
+impl State {
+    /// Resolve an id prefix to exactly one of the user's items.
+    fn heartbeat_item(&mut self, sender_id: &str, id_prefix: &str) -> Option<&mut HeartbeatItem> {
+        if id_prefix.is_empty() {
+            return None;
+        }
+        let mut matches = self
+            .heartbeat_items
+            .iter_mut()
+            .filter(|i| i.sender_id == sender_id && matches_prefix(&i.id, id_prefix));
+        let item = matches.next()?;
+        matches.next().is_none().then_some(item)
+    }
+}
+
+#[async_trait]
+impl HeartbeatItemStore for InMemoryBackend {
+    async fn add_heartbeat_item(
+        &self,
+        sender_id: &str,
+        project: &str,
+        item: &NewHeartbeatItem,
+    ) -> Result<String, OmegaError> {
+        let mut state = self.lock();
+        let description = item.description.to_lowercase();
+        if let Some(existing) = state.heartbeat_items.iter_mut().find(|i| {
+            i.sender_id == sender_id
+                && i.project == project
+                && i.description.to_lowercase() == description
+        }) {
+            existing.cadence = item.cadence.clone();
+            existing.alert_on_change = item.alert_on_change;
+            return Ok(existing.id.clone());
+        }
+
+        let id = Uuid::new_v4().to_string();
+        state.heartbeat_items.push(HeartbeatItem {
+            id: id.clone(),
+            sender_id: sender_id.to_string(),
+            project: project.to_string(),
+            description: item.description.clone(),
+            cadence: item.cadence.clone(),
+            alert_on_change: item.alert_on_change,
+            last_checked_at: None,
+            last_result: None,
+            created_at: fmt(Utc::now()),
+        });
+        Ok(id)
+    }
+
+    async fn get_heartbeat_items(
+        &self,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<HeartbeatItem>, OmegaError> {
+        Ok(self
+            .lock()
+            .heartbeat_items
+            .iter()
+            .filter(|i| i.sender_id == sender_id && i.project == project)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_heartbeat_item_projects(
+        &self,
+        sender_id: &str,
+    ) -> Result<Vec<String>, OmegaError> {
+        let mut projects: Vec<String> = self
+            .lock()
+            .heartbeat_items
+            .iter()
+            .filter(|i| i.sender_id == sender_id && !i.project.is_empty())
+            .map(|i| i.project.clone())
+            .collect();
+        projects.sort();
+        projects.dedup();
+        Ok(projects)
+    }
+
+    async fn remove_heartbeat_item(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+    ) -> Result<bool, OmegaError> {
+        let mut state = self.lock();
+        let Some(id) = state
+            .heartbeat_item(sender_id, id_prefix)
+            .map(|i| i.id.clone())
+        else {
+            return Ok(false);
+        };
+        state.heartbeat_items.retain(|i| i.id != id);
+        Ok(true)
+    }
+
+    async fn mark_heartbeat_items_checked(&self, ids: &[String]) -> Result<(), OmegaError> {
+        let now = fmt(Utc::now());
+        for item in self
+            .lock()
+            .heartbeat_items
+            .iter_mut()
+            .filter(|i| ids.contains(&i.id))
+        {
+            item.last_checked_at = Some(now.clone());
+        }
+        Ok(())
+    }
+
+    async fn record_heartbeat_result(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+        result: &str,
+    ) -> Result<Option<bool>, OmegaError> {
+        let mut state = self.lock();
+        let Some(item) = state.heartbeat_item(sender_id, id_prefix) else {
+            return Ok(None);
+        };
+        let changed = item.last_result.as_deref() != Some(result);
+        item.last_result = Some(result.to_string());
+        item.last_checked_at = Some(fmt(Utc::now()));
+        Ok(Some(changed))
+    }
+}
+
+#[async_trait]
+impl AuditStore for InMemoryBackend {
+    async fn log_audit(&self, entry: &AuditEntry) -> Result<(), OmegaError> {
+        self.lock().audit.push(AuditRecord {
+            timestamp: Utc::now(),
+            channel: entry.channel.clone(),
+            sender_id: entry.sender_id.clone(),
+            input_text: entry.input_text.clone(),
+            status: entry.status.as_str(),
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MemoryBackend for InMemoryBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn with_cipher(&self, _cipher: Option<Arc<Cipher>>) -> Arc<dyn MemoryBackend> {
+        // Nothing is at rest, so there is nothing to encrypt.
+        Arc::new(self.clone())
+    }
+
+    fn is_encrypted(&self) -> bool {
+        false
+    }
+
+    async fn rekey(&self, _new: Option<&Cipher>) -> Result<u64, OmegaError> {
+        Ok(0)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
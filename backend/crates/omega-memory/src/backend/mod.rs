@@ -0,0 +1,611 @@
+//! Pluggable storage backends for the memory store.
+//!
+//! Storage is split into one trait per domain — conversations, messages,
+//! facts, identity, tasks, learning, sessions, conversation snapshots,
+//! heartbeats and audit — and
+//! [`MemoryBackend`] ties them together. [`Store`](crate::Store) wraps an
+//! `Arc<dyn MemoryBackend>` chosen by `memory.backend`:
+//! - `"sqlite"` — [`SqliteBackend`](crate::store::SqliteBackend), the default
+//! - `"postgres"` — [`PostgresBackend`], for a managed or shared database
+//! - `"memory"` — [`InMemoryBackend`], nothing persisted (tests)
+//!
+//! Backends store user content sealed with the configured [`Cipher`]; the
+//! SQLite backend additionally supports snapshots, retention, export/import
+//! and re-keying through [`Store`](crate::Store).
+
+mod memory;
+mod postgres;
+
+pub use memory::InMemoryBackend;
+pub use postgres::PostgresBackend;
+
+use crate::audit::AuditEntry;
+use crate::store::{
+    ConversationSnapshot, DueTask, Exchange, HeartbeatItem, HeartbeatSettings, MergeReport,
+    NewHeartbeatItem,
+};
+use async_trait::async_trait;
+use omega_core::{
+    crypto::Cipher,
+    error::OmegaError,
+    message::{IncomingMessage, OutgoingMessage},
+};
+use std::any::Any;
+use std::sync::Arc;
+
+/// How long (in minutes) before a conversation is considered idle.
+pub(crate) const CONVERSATION_TIMEOUT_MINUTES: i64 = 120;
+
+/// `(id, channel, sender_id, project)` of a conversation.
+pub type ConversationRef = (String, String, String, String);
+
+/// `(id, description, due_at, repeat, task_type, project)` of a pending task.
+pub type PendingTask = (String, String, String, Option<String>, String, String);
+
+/// Conversation lifecycle, summaries and stats.
+#[async_trait]
+pub trait ConversationStore: Send + Sync {
+    /// Get or create the active conversation for a channel + sender + project.
+    ///
+    /// Conversations idle for longer than the timeout are not reused.
+    async fn get_or_create_conversation(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<String, OmegaError>;
+
+    /// The active conversation within the timeout window, without creating one.
+    async fn get_active_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError>;
+
+    /// The most recent active conversation, however long it has been idle.
+    async fn get_open_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError>;
+
+    /// The sender a conversation belongs to.
+    async fn get_conversation_sender(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<String>, OmegaError>;
+
+    /// Active conversations that have been idle beyond the timeout.
+    async fn find_idle_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError>;
+
+    /// All active conversations (for shutdown).
+    async fn find_all_active_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError>;
+
+    /// All `(role, content)` messages of a conversation, oldest first.
+    async fn get_conversation_messages(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// The newest `limit` `(role, content)` messages of a conversation, oldest first.
+    async fn get_recent_messages(
+        &self,
+        conversation_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// Close a conversation with a summary.
+    async fn close_conversation(
+        &self,
+        conversation_id: &str,
+        summary: &str,
+    ) -> Result<(), OmegaError>;
+
+    /// Close the active conversation for a sender + project (for /forget).
+    async fn close_current_conversation(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<bool, OmegaError>;
+
+    /// Recent closed-conversation `(summary, updated_at)` for a sender.
+    async fn get_recent_summaries(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// Recent `(summary, updated_at)` across all users (heartbeat enrichment).
+    async fn get_all_recent_summaries(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// Closed conversations as `(summary, updated_at)`, "(no summary)" when missing.
+    async fn get_history(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// `(message_count, estimated_tokens)` for a conversation (`chars / 4`).
+    async fn get_conversation_token_estimate(
+        &self,
+        conversation_id: &str,
+    ) -> Result<(i64, i64), OmegaError>;
+
+    /// `(conversations, messages, facts)` counts for a sender.
+    async fn get_memory_stats(&self, sender_id: &str) -> Result<(i64, i64, i64), OmegaError>;
+}
+
+/// Message storage and recall search.
+#[async_trait]
+pub trait MessageStore: Send + Sync {
+    /// Store a user message and the assistant's response.
+    async fn store_exchange(
+        &self,
+        incoming: &IncomingMessage,
+        response: &OutgoingMessage,
+        project: &str,
+    ) -> Result<(), OmegaError>;
+
+    /// Remove the newest user + assistant pair from the sender's open
+    /// conversation (for /undo and /retry). `None` when the conversation does
+    /// not end with a complete exchange.
+    async fn pop_last_exchange(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<Exchange>, OmegaError>;
+
+    /// Full-text search of a sender's other conversations: `(role, content, timestamp)`.
+    async fn search_messages(
+        &self,
+        query: &str,
+        exclude_conversation_id: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String, String)>, OmegaError>;
+}
+
+/// User facts, cross-channel aliases and limitations.
+#[async_trait]
+pub trait FactStore: Send + Sync {
+    /// Store a fact (upsert by sender_id + key).
+    async fn store_fact(&self, sender_id: &str, key: &str, value: &str) -> Result<(), OmegaError>;
+
+    /// A single fact by sender and key.
+    async fn get_fact(&self, sender_id: &str, key: &str) -> Result<Option<String>, OmegaError>;
+
+    /// Delete a single fact. Returns `true` if it existed.
+    async fn delete_fact(&self, sender_id: &str, key: &str) -> Result<bool, OmegaError>;
+
+    /// All `(key, value)` facts for a sender, sorted by key.
+    async fn get_facts(&self, sender_id: &str) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// Delete all of a sender's facts (`key` = None) or one of them.
+    async fn delete_facts(&self, sender_id: &str, key: Option<&str>) -> Result<u64, OmegaError>;
+
+    /// Facts across all users except `welcomed` (heartbeat enrichment).
+    async fn get_all_facts(&self) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// `(sender_id, value)` for every user with a fact `key`.
+    async fn get_all_facts_by_key(&self, key: &str) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// Whether a sender has never been welcomed (no `welcomed` fact).
+    async fn is_new_user(&self, sender_id: &str) -> Result<bool, OmegaError>;
+
+    /// The canonical id an alias resolves to, or `sender_id` itself.
+    async fn resolve_sender_id(&self, sender_id: &str) -> Result<String, OmegaError>;
+
+    /// Map `alias_id` to `canonical_id` (no-op if the alias exists).
+    async fn create_alias(&self, alias_id: &str, canonical_id: &str) -> Result<(), OmegaError>;
+
+    /// All alias ids that resolve to `canonical_id`, sorted.
+    async fn get_aliases(&self, canonical_id: &str) -> Result<Vec<String>, OmegaError>;
+
+    /// A welcomed user other than `exclude_sender_id`, if any.
+    async fn find_canonical_user(
+        &self,
+        exclude_sender_id: &str,
+    ) -> Result<Option<String>, OmegaError>;
+
+    /// Store a limitation (dedup by title). Returns `true` if it is new.
+    async fn store_limitation(
+        &self,
+        title: &str,
+        description: &str,
+        proposed_plan: &str,
+    ) -> Result<bool, OmegaError>;
+
+    /// Open limitations as `(title, description, proposed_plan)`, oldest first.
+    async fn get_open_limitations(&self) -> Result<Vec<(String, String, String)>, OmegaError>;
+}
+
+/// Cross-channel identity linking.
+#[async_trait]
+pub trait IdentityStore: Send + Sync {
+    /// Merge everything stored under `from` into `into` and alias `from` to it.
+    ///
+    /// Facts present under both ids with different values keep the most
+    /// recently updated one. `from`'s active conversations that collide with
+    /// one of `into`'s (same channel and project) are closed first.
+    async fn merge_sender(&self, from: &str, into: &str) -> Result<MergeReport, OmegaError>;
+
+    /// Detach an alias. Returns `true` if it existed.
+    ///
+    /// Data merged earlier stays with the canonical id.
+    async fn remove_alias(&self, alias_id: &str) -> Result<bool, OmegaError>;
+}
+
+/// Scheduled tasks.
+#[async_trait]
+pub trait TaskStore: Send + Sync {
+    /// Create a task, reusing a pending one with the same or a similar
+    /// description due at (or within 30 minutes of) the same time.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_task(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        reply_target: &str,
+        description: &str,
+        due_at: &str,
+        repeat: Option<&str>,
+        task_type: &str,
+        project: &str,
+    ) -> Result<String, OmegaError>;
+
+    /// Pending tasks whose due time has passed.
+    async fn get_due_tasks(&self) -> Result<Vec<DueTask>, OmegaError>;
+
+    /// One-shot tasks become delivered; recurring tasks advance `due_at`.
+    async fn complete_task(&self, id: &str, repeat: Option<&str>) -> Result<(), OmegaError>;
+
+    /// Record a failure. Returns `true` if the task will be retried.
+    async fn fail_task(&self, id: &str, error: &str, max_retries: u32) -> Result<bool, OmegaError>;
+
+    /// A sender's pending tasks, soonest first.
+    async fn get_tasks_for_sender(&self, sender_id: &str) -> Result<Vec<PendingTask>, OmegaError>;
+
+    /// Cancel a task by id prefix (idempotent).
+    async fn cancel_task(&self, id_prefix: &str, sender_id: &str) -> Result<bool, OmegaError>;
+
+    /// Update the given fields of a pending task by id prefix.
+    async fn update_task(
+        &self,
+        id_prefix: &str,
+        sender_id: &str,
+        description: Option<&str>,
+        due_at: Option<&str>,
+        repeat: Option<&str>,
+    ) -> Result<bool, OmegaError>;
+
+    /// Move a pending task to a new due time (by exact id).
+    async fn defer_task(&self, id: &str, new_due_at: &str) -> Result<(), OmegaError>;
+}
+
+/// Reward-based learning: raw outcomes and distilled lessons.
+///
+/// `project` scopes rows; `""` is general Omega.
+#[async_trait]
+pub trait LearningStore: Send + Sync {
+    /// Store a raw outcome from a REWARD marker.
+    async fn store_outcome(
+        &self,
+        sender_id: &str,
+        domain: &str,
+        score: i32,
+        lesson: &str,
+        source: &str,
+        project: &str,
+    ) -> Result<(), OmegaError>;
+
+    /// A sender's newest `(score, domain, lesson, timestamp)` outcomes.
+    async fn get_recent_outcomes(
+        &self,
+        sender_id: &str,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError>;
+
+    /// Outcomes across all users within the last `hours`.
+    async fn get_all_recent_outcomes(
+        &self,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError>;
+
+    /// A sender's outcomes within the last `hours`.
+    async fn get_recent_outcomes_since(
+        &self,
+        sender_id: &str,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError>;
+
+    /// Store a lesson; an identical rule is reinforced instead. At most 10
+    /// lessons are kept per (sender, domain, project).
+    async fn store_lesson(
+        &self,
+        sender_id: &str,
+        domain: &str,
+        rule: &str,
+        project: &str,
+    ) -> Result<(), OmegaError>;
+
+    /// A sender's `(domain, rule, project)` lessons, project-specific first.
+    async fn get_lessons(
+        &self,
+        sender_id: &str,
+        project: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, OmegaError>;
+
+    /// Lessons across all users (heartbeat enrichment).
+    async fn get_all_lessons(
+        &self,
+        project: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, OmegaError>;
+}
+
+/// Project-scoped CLI sessions, keyed by (channel, sender_id, project).
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Insert or replace the session id.
+    async fn store_session(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        session_id: &str,
+    ) -> Result<(), OmegaError>;
+
+    /// The stored session id, if any.
+    async fn get_session(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError>;
+
+    /// Delete one session.
+    async fn clear_session(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<(), OmegaError>;
+
+    /// Delete all of a sender's sessions.
+    async fn clear_all_sessions_for_sender(&self, sender_id: &str) -> Result<(), OmegaError>;
+}
+
+/// Named copies of a conversation's messages (`/snapshot`, `/branch`),
+/// keyed by (channel, sender_id, project, name).
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    /// Save the open conversation's messages under `name`, replacing a
+    /// snapshot with the same name. Returns the number of messages saved,
+    /// `None` if there is no open conversation.
+    async fn save_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError>;
+
+    /// Snapshots for a sender + project, newest first.
+    async fn list_snapshots(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<ConversationSnapshot>, OmegaError>;
+
+    /// Close the open conversation and start a new one holding the snapshot's
+    /// messages. Returns the number of messages copied, `None` if there is no
+    /// snapshot called `name`.
+    async fn branch_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError>;
+
+    /// Delete a snapshot. Returns `true` if it existed.
+    async fn delete_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<bool, OmegaError>;
+}
+
+/// Per-user heartbeat settings.
+#[async_trait]
+pub trait HeartbeatStore: Send + Sync {
+    /// A user's heartbeat settings, if any.
+    async fn get_heartbeat_settings(
+        &self,
+        sender_id: &str,
+    ) -> Result<Option<HeartbeatSettings>, OmegaError>;
+
+    /// Every enabled heartbeat, ordered by sender.
+    async fn get_enabled_heartbeats(&self) -> Result<Vec<HeartbeatSettings>, OmegaError>;
+
+    /// Insert or fully replace a user's settings.
+    async fn save_heartbeat_settings(&self, settings: &HeartbeatSettings)
+        -> Result<(), OmegaError>;
+
+    /// Insert settings only if the user has none. Returns `true` if inserted.
+    async fn seed_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<bool, OmegaError>;
+
+    /// `(sender_id, checklist)` for rows that still have a legacy checklist.
+    async fn get_legacy_heartbeat_checklists(&self) -> Result<Vec<(String, String)>, OmegaError>;
+
+    /// Clear a user's legacy checklist.
+    async fn clear_legacy_heartbeat_checklist(&self, sender_id: &str) -> Result<(), OmegaError>;
+}
+
+/// Structured heartbeat checklist items.
+#[async_trait]
+pub trait HeartbeatItemStore: Send + Sync {
+    /// Add an item (or update the one with the same description) and return its id.
+    async fn add_heartbeat_item(
+        &self,
+        sender_id: &str,
+        project: &str,
+        item: &NewHeartbeatItem,
+    ) -> Result<String, OmegaError>;
+
+    /// A user's items in one project scope, oldest first.
+    async fn get_heartbeat_items(
+        &self,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<HeartbeatItem>, OmegaError>;
+
+    /// Projects in which a user has items, sorted.
+    async fn get_heartbeat_item_projects(&self, sender_id: &str)
+        -> Result<Vec<String>, OmegaError>;
+
+    /// Remove an item by id prefix; false unless exactly one matches.
+    async fn remove_heartbeat_item(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+    ) -> Result<bool, OmegaError>;
+
+    /// Stamp `last_checked_at = now` on the given items.
+    async fn mark_heartbeat_items_checked(&self, ids: &[String]) -> Result<(), OmegaError>;
+
+    /// Record an item's result: `None` if the prefix matches no single item,
+    /// otherwise whether the result changed.
+    async fn record_heartbeat_result(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+        result: &str,
+    ) -> Result<Option<bool>, OmegaError>;
+}
+
+/// The audit trail of every interaction.
+#[async_trait]
+pub trait AuditStore: Send + Sync {
+    /// Append an entry to the audit log.
+    async fn log_audit(&self, entry: &AuditEntry) -> Result<(), OmegaError>;
+}
+
+/// A complete storage backend for [`Store`](crate::Store).
+#[async_trait]
+pub trait MemoryBackend:
+    ConversationStore
+    + MessageStore
+    + FactStore
+    + IdentityStore
+    + TaskStore
+    + LearningStore
+    + SessionStore
+    + SnapshotStore
+    + HeartbeatStore
+    + HeartbeatItemStore
+    + AuditStore
+{
+    /// Backend name as written in `memory.backend`.
+    fn name(&self) -> &'static str;
+
+    /// This backend sealing user content with `cipher` (`None` = plaintext).
+    fn with_cipher(&self, cipher: Option<Arc<Cipher>>) -> Arc<dyn MemoryBackend>;
+
+    /// Whether new content is written encrypted.
+    fn is_encrypted(&self) -> bool;
+
+    /// Re-encrypt every stored value with `new` (`None` = decrypt to plaintext).
+    ///
+    /// Values are read with the current cipher, so plaintext rows are
+    /// encrypted too. Returns the number of values rewritten.
+    async fn rekey(&self, new: Option<&Cipher>) -> Result<u64, OmegaError>;
+
+    /// Downcast support — gives [`Store`](crate::Store) the SQLite-only operations.
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// Seals and opens user content for the encrypted columns.
+///
+/// Rows written before encryption was enabled stay readable as plaintext.
+#[derive(Clone, Default)]
+pub(crate) struct Sealer {
+    cipher: Option<Arc<Cipher>>,
+}
+
+impl Sealer {
+    pub(crate) fn new(cipher: Option<Arc<Cipher>>) -> Self {
+        Self { cipher }
+    }
+
+    pub(crate) fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Prepare a value for an encrypted column.
+    pub(crate) fn seal(&self, value: &str) -> String {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt(value),
+            None => value.to_string(),
+        }
+    }
+
+    /// Read a value from an encrypted column.
+    pub(crate) fn open(&self, value: String) -> Result<String, OmegaError> {
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&value),
+            None if Cipher::is_encrypted(&value) => Err(OmegaError::Memory(
+                "memory is encrypted but no master key is configured ([encryption])".into(),
+            )),
+            None => Ok(value),
+        }
+    }
+
+    /// [`Sealer::open`] for each row's second field (`(key, value)` pairs).
+    pub(crate) fn open_pairs(
+        &self,
+        rows: Vec<(String, String)>,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        rows.into_iter()
+            .map(|(a, b)| Ok((a, self.open(b)?)))
+            .collect()
+    }
+
+    /// Seal `(role, content)` messages as one JSON value (conversation snapshots).
+    pub(crate) fn seal_messages(
+        &self,
+        messages: &[(String, String)],
+    ) -> Result<String, OmegaError> {
+        let json = serde_json::to_string(messages)
+            .map_err(|e| OmegaError::Memory(format!("serialize failed: {e}")))?;
+        Ok(self.seal(&json))
+    }
+
+    /// Read messages sealed with [`Sealer::seal_messages`].
+    pub(crate) fn open_messages(&self, value: String) -> Result<Vec<(String, String)>, OmegaError> {
+        serde_json::from_str(&self.open(value)?)
+            .map_err(|e| OmegaError::Memory(format!("invalid snapshot: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests;
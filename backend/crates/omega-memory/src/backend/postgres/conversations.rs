@@ -0,0 +1,441 @@
+//! Conversations and messages on PostgreSQL.
+
+use super::PostgresBackend;
+use crate::backend::{
+    ConversationRef, ConversationStore, MessageStore, CONVERSATION_TIMEOUT_MINUTES,
+};
+use crate::store::Exchange;
+use async_trait::async_trait;
+use omega_core::{
+    error::OmegaError,
+    message::{IncomingMessage, OutgoingMessage},
+};
+use uuid::Uuid;
+
+#[async_trait]
+impl ConversationStore for PostgresBackend {
+    async fn get_or_create_conversation(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<String, OmegaError> {
+        // Touch the active conversation within the timeout window, if any.
+        let row: Option<(String,)> = sqlx::query_as(
+            "UPDATE conversations SET last_activity = clock_timestamp(), updated_at = clock_timestamp() \
+             WHERE id = ( \
+                 SELECT id FROM conversations \
+                 WHERE channel = $1 AND sender_id = $2 AND project = $3 AND status = 'active' \
+                 AND last_activity > now() - make_interval(mins => $4) \
+                 ORDER BY last_activity DESC LIMIT 1 \
+             ) RETURNING id",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(CONVERSATION_TIMEOUT_MINUTES as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("update failed: {e}")))?;
+
+        if let Some((id,)) = row {
+            return Ok(id);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO conversations (id, channel, sender_id, project, status) \
+             VALUES ($1, $2, $3, $4, 'active')",
+        )
+        .bind(&id)
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("insert failed: {e}")))?;
+
+        Ok(id)
+    }
+
+    async fn get_active_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM conversations \
+             WHERE channel = $1 AND sender_id = $2 AND project = $3 AND status = 'active' \
+             AND last_activity > now() - make_interval(mins => $4) \
+             ORDER BY last_activity DESC LIMIT 1",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(CONVERSATION_TIMEOUT_MINUTES as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    async fn get_open_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM conversations \
+             WHERE channel = $1 AND sender_id = $2 AND project = $3 AND status = 'active' \
+             ORDER BY last_activity DESC LIMIT 1",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    async fn get_conversation_sender(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT sender_id FROM conversations WHERE id = $1")
+                .bind(conversation_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    async fn find_idle_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError> {
+        sqlx::query_as(
+            "SELECT id, channel, sender_id, project FROM conversations \
+             WHERE status = 'active' AND last_activity <= now() - make_interval(mins => $1)",
+        )
+        .bind(CONVERSATION_TIMEOUT_MINUTES as i32)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))
+    }
+
+    async fn find_all_active_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError> {
+        sqlx::query_as(
+            "SELECT id, channel, sender_id, project FROM conversations WHERE status = 'active'",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))
+    }
+
+    async fn get_conversation_messages(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT role, content FROM messages \
+             WHERE conversation_id = $1 ORDER BY timestamp ASC",
+        )
+        .bind(conversation_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.sealer.open_pairs(rows)
+    }
+
+    async fn get_recent_messages(
+        &self,
+        conversation_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT role, content FROM ( \
+                 SELECT role, content, timestamp FROM messages \
+                 WHERE conversation_id = $1 ORDER BY timestamp DESC LIMIT $2 \
+             ) recent ORDER BY timestamp ASC",
+        )
+        .bind(conversation_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.sealer.open_pairs(rows)
+    }
+
+    async fn close_conversation(
+        &self,
+        conversation_id: &str,
+        summary: &str,
+    ) -> Result<(), OmegaError> {
+        sqlx::query(
+            "UPDATE conversations SET status = 'closed', summary = $1, \
+             updated_at = clock_timestamp() WHERE id = $2",
+        )
+        .bind(self.sealer.seal(summary))
+        .bind(conversation_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("update failed: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn close_current_conversation(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<bool, OmegaError> {
+        let result = sqlx::query(
+            "UPDATE conversations SET status = 'closed', updated_at = clock_timestamp() \
+             WHERE channel = $1 AND sender_id = $2 AND project = $3 AND status = 'active'",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("update failed: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_recent_summaries(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT summary, omega_ts(updated_at) FROM conversations \
+             WHERE channel = $1 AND sender_id = $2 AND status = 'closed' AND summary IS NOT NULL \
+             ORDER BY updated_at DESC LIMIT $3",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.open_summaries(rows)
+    }
+
+    async fn get_all_recent_summaries(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT summary, omega_ts(updated_at) FROM conversations \
+             WHERE status = 'closed' AND summary IS NOT NULL \
+             ORDER BY updated_at DESC LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.open_summaries(rows)
+    }
+
+    async fn get_history(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT COALESCE(summary, '(no summary)'), omega_ts(updated_at) FROM conversations \
+             WHERE channel = $1 AND sender_id = $2 AND status = 'closed' \
+             ORDER BY updated_at DESC LIMIT $3",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.open_summaries(rows)
+    }
+
+    async fn get_conversation_token_estimate(
+        &self,
+        conversation_id: &str,
+    ) -> Result<(i64, i64), OmegaError> {
+        let (count, chars): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*), COALESCE(SUM(char_length(content)), 0)::BIGINT \
+             FROM messages WHERE conversation_id = $1",
+        )
+        .bind(conversation_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok((count, chars / 4))
+    }
+
+    async fn get_memory_stats(&self, sender_id: &str) -> Result<(i64, i64, i64), OmegaError> {
+        sqlx::query_as(
+            "SELECT \
+                 (SELECT COUNT(*) FROM conversations WHERE sender_id = $1), \
+                 (SELECT COUNT(*) FROM messages m \
+                  JOIN conversations c ON m.conversation_id = c.id WHERE c.sender_id = $1), \
+                 (SELECT COUNT(*) FROM facts WHERE sender_id = $1)",
+        )
+        .bind(sender_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))
+    }
+}
+
+impl PostgresBackend {
+    fn open_summaries(
+        &self,
+        rows: Vec<(String, String)>,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        rows.into_iter()
+            .map(|(summary, ts)| Ok((self.sealer.open(summary)?, ts)))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MessageStore for PostgresBackend {
+    async fn store_exchange(
+        &self,
+        incoming: &IncomingMessage,
+        response: &OutgoingMessage,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        let conv_id = self
+            .get_or_create_conversation(&incoming.channel, &incoming.sender_id, project)
+            .await?;
+        let metadata_json = serde_json::to_string(&response.metadata)
+            .map_err(|e| OmegaError::Memory(format!("serialize failed: {e}")))?;
+
+        sqlx::query(
+            "INSERT INTO messages (id, conversation_id, role, content) VALUES ($1, $2, 'user', $3)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&conv_id)
+        .bind(self.sealer.seal(&incoming.text))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("insert failed: {e}")))?;
+
+        sqlx::query(
+            "INSERT INTO messages (id, conversation_id, role, content, metadata_json) \
+             VALUES ($1, $2, 'assistant', $3, $4)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&conv_id)
+        .bind(self.sealer.seal(&response.text))
+        .bind(&metadata_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("insert failed: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn pop_last_exchange(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<Exchange>, OmegaError> {
+        let Some(conv_id) = self
+            .get_open_conversation_id(channel, sender_id, project)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let rows: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+            "SELECT id, role, content, metadata_json FROM messages \
+             WHERE conversation_id = $1 ORDER BY timestamp DESC LIMIT 2",
+        )
+        .bind(&conv_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        let mut rows = rows.into_iter();
+        let (
+            Some((asst_id, asst_role, asst_content, metadata_json)),
+            Some((user_id, user_role, user_content, _)),
+        ) = (rows.next(), rows.next())
+        else {
+            return Ok(None);
+        };
+        if asst_role != "assistant" || user_role != "user" {
+            return Ok(None);
+        }
+
+        sqlx::query("DELETE FROM messages WHERE id IN ($1, $2)")
+            .bind(&asst_id)
+            .bind(&user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("delete failed: {e}")))?;
+
+        Ok(Some(Exchange {
+            user: self.sealer.open(user_content)?,
+            assistant: self.sealer.open(asst_content)?,
+            metadata: metadata_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+        }))
+    }
+
+    /// Phrase search over user messages via the `search` tsvector column.
+    ///
+    /// Always empty while encryption is on: the index only holds ciphertext.
+    async fn search_messages(
+        &self,
+        query: &str,
+        exclude_conversation_id: &str,
+        sender_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String, String)>, OmegaError> {
+        if query.len() < 3 || self.sealer.is_encrypted() {
+            return Ok(Vec::new());
+        }
+
+        // phraseto_tsquery ignores operators, so the input needs no escaping.
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT m.role, m.content, omega_ts(m.timestamp) \
+             FROM messages m \
+             JOIN conversations c ON c.id = m.conversation_id, \
+             phraseto_tsquery('simple', $1) q \
+             WHERE m.search @@ q \
+             AND m.conversation_id != $2 \
+             AND c.sender_id = $3 \
+             ORDER BY ts_rank(m.search, q) DESC, m.timestamp DESC \
+             LIMIT $4",
+        )
+        .bind(query)
+        .bind(exclude_conversation_id)
+        .bind(sender_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("search failed: {e}")))?;
+
+        rows.into_iter()
+            .map(|(role, content, ts)| Ok((role, self.sealer.open(content)?, ts)))
+            .collect()
+    }
+}
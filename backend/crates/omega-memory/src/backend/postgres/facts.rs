@@ -0,0 +1,359 @@
+//! Facts, aliases, limitations and sender merging on PostgreSQL.
+
+use super::PostgresBackend;
+use crate::backend::{FactStore, IdentityStore};
+use crate::store::{FactConflict, MergeReport};
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+/// Tables with a per-sender unique key: the canonical id's row wins.
+const KEEP_EXISTING_TABLES: [(&str, &str); 3] = [
+    (
+        "project_sessions",
+        "k.channel = t.channel AND k.project = t.project",
+    ),
+    (
+        "conversation_snapshots",
+        "k.channel = t.channel AND k.project = t.project AND k.name = t.name",
+    ),
+    ("heartbeat_settings", "TRUE"),
+];
+
+#[async_trait]
+impl FactStore for PostgresBackend {
+    async fn store_fact(&self, sender_id: &str, key: &str, value: &str) -> Result<(), OmegaError> {
+        sqlx::query(
+            "INSERT INTO facts (id, sender_id, key, value) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (sender_id, key) \
+             DO UPDATE SET value = excluded.value, updated_at = clock_timestamp()",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(sender_id)
+        .bind(key)
+        .bind(self.sealer.seal(value))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("upsert fact failed: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn get_fact(&self, sender_id: &str, key: &str) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT value FROM facts WHERE sender_id = $1 AND key = $2")
+                .bind(sender_id)
+                .bind(key)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        row.map(|(v,)| self.sealer.open(v)).transpose()
+    }
+
+    async fn delete_fact(&self, sender_id: &str, key: &str) -> Result<bool, OmegaError> {
+        Ok(self.delete_facts(sender_id, Some(key)).await? > 0)
+    }
+
+    async fn get_facts(&self, sender_id: &str) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM facts WHERE sender_id = $1 ORDER BY key")
+                .bind(sender_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.sealer.open_pairs(rows)
+    }
+
+    async fn delete_facts(&self, sender_id: &str, key: Option<&str>) -> Result<u64, OmegaError> {
+        sqlx::query("DELETE FROM facts WHERE sender_id = $1 AND ($2::TEXT IS NULL OR key = $2)")
+            .bind(sender_id)
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map(|r| r.rows_affected())
+            .map_err(|e| OmegaError::Memory(format!("delete failed: {e}")))
+    }
+
+    async fn get_all_facts(&self) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT key, value FROM facts WHERE key != 'welcomed' ORDER BY key")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.sealer.open_pairs(rows)
+    }
+
+    async fn get_all_facts_by_key(&self, key: &str) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT sender_id, value FROM facts WHERE key = $1 ORDER BY sender_id")
+                .bind(key)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("get facts by key failed: {e}")))?;
+
+        self.sealer.open_pairs(rows)
+    }
+
+    async fn is_new_user(&self, sender_id: &str) -> Result<bool, OmegaError> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT id FROM facts WHERE sender_id = $1 AND key = 'welcomed'")
+                .bind(sender_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.is_none())
+    }
+
+    async fn resolve_sender_id(&self, sender_id: &str) -> Result<String, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT canonical_sender_id FROM user_aliases WHERE alias_sender_id = $1",
+        )
+        .bind(sender_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("resolve alias failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id).unwrap_or_else(|| sender_id.to_string()))
+    }
+
+    async fn create_alias(&self, alias_id: &str, canonical_id: &str) -> Result<(), OmegaError> {
+        sqlx::query(
+            "INSERT INTO user_aliases (alias_sender_id, canonical_sender_id) VALUES ($1, $2) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(alias_id)
+        .bind(canonical_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("create alias failed: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn get_aliases(&self, canonical_id: &str) -> Result<Vec<String>, OmegaError> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT alias_sender_id FROM user_aliases WHERE canonical_sender_id = $1 \
+             ORDER BY alias_sender_id",
+        )
+        .bind(canonical_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get aliases failed: {e}")))?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    async fn find_canonical_user(
+        &self,
+        exclude_sender_id: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT sender_id FROM facts WHERE key = 'welcomed' AND sender_id != $1 \
+             ORDER BY created_at LIMIT 1",
+        )
+        .bind(exclude_sender_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    async fn store_limitation(
+        &self,
+        title: &str,
+        description: &str,
+        proposed_plan: &str,
+    ) -> Result<bool, OmegaError> {
+        let result = sqlx::query(
+            "INSERT INTO limitations (id, title, description, proposed_plan) \
+             VALUES ($1, $2, $3, $4) ON CONFLICT DO NOTHING",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(title)
+        .bind(description)
+        .bind(proposed_plan)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("store limitation failed: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_open_limitations(&self) -> Result<Vec<(String, String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT title, description, proposed_plan FROM limitations \
+             WHERE status = 'open' ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get open limitations failed: {e}")))
+    }
+}
+
+#[async_trait]
+impl IdentityStore for PostgresBackend {
+    async fn merge_sender(&self, from: &str, into: &str) -> Result<MergeReport, OmegaError> {
+        if from == into {
+            return Err(OmegaError::Memory(
+                "cannot merge a sender into itself".into(),
+            ));
+        }
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("merge sender failed: {e}"));
+        let mut tx = self.pool.begin().await.map_err(err)?;
+        let mut report = MergeReport::default();
+
+        // Facts: move, drop duplicates, resolve conflicts by recency.
+        let theirs: Vec<(String, String, Option<String>, Option<bool>)> = sqlx::query_as(
+            "SELECT t.key, t.value, o.value, t.updated_at > o.updated_at \
+             FROM facts t LEFT JOIN facts o ON o.sender_id = $2 AND o.key = t.key \
+             WHERE t.sender_id = $1",
+        )
+        .bind(from)
+        .bind(into)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(err)?;
+        for (key, value, ours, theirs_newer) in theirs {
+            match ours {
+                None => {
+                    sqlx::query(
+                        "UPDATE facts SET sender_id = $1 WHERE sender_id = $2 AND key = $3",
+                    )
+                    .bind(into)
+                    .bind(from)
+                    .bind(&key)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(err)?;
+                    report.facts += 1;
+                    continue;
+                }
+                Some(our_value) if our_value == value => {}
+                Some(our_value) => {
+                    let theirs_newer = theirs_newer.unwrap_or(false);
+                    if theirs_newer {
+                        sqlx::query(
+                            "UPDATE facts SET value = $1, updated_at = clock_timestamp() \
+                             WHERE sender_id = $2 AND key = $3",
+                        )
+                        .bind(&value)
+                        .bind(into)
+                        .bind(&key)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(err)?;
+                    }
+                    let (kept, discarded) = if theirs_newer {
+                        (value, our_value)
+                    } else {
+                        (our_value, value)
+                    };
+                    report.conflicts.push(FactConflict {
+                        key: key.clone(),
+                        kept: self.sealer.open(kept)?,
+                        discarded: self.sealer.open(discarded)?,
+                    });
+                }
+            }
+            sqlx::query("DELETE FROM facts WHERE sender_id = $1 AND key = $2")
+                .bind(from)
+                .bind(&key)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?;
+        }
+
+        // Conversations: one active conversation per (channel, sender, project).
+        sqlx::query(
+            "UPDATE conversations SET status = 'closed', updated_at = clock_timestamp() \
+             WHERE sender_id = $1 AND status = 'active' AND EXISTS ( \
+                 SELECT 1 FROM conversations c WHERE c.sender_id = $2 AND c.status = 'active' \
+                 AND c.channel = conversations.channel AND c.project = conversations.project)",
+        )
+        .bind(from)
+        .bind(into)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+
+        // Counted moves for the report, then plain moves.
+        for (table, counter) in [
+            ("conversations", Some(&mut report.conversations)),
+            ("lessons", Some(&mut report.lessons)),
+            ("scheduled_tasks", Some(&mut report.tasks)),
+            ("outcomes", None),
+            ("heartbeat_items", None),
+        ] {
+            let moved = sqlx::query(&format!(
+                "UPDATE {table} SET sender_id = $1 WHERE sender_id = $2"
+            ))
+            .bind(into)
+            .bind(from)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+            if let Some(counter) = counter {
+                *counter = moved;
+            }
+        }
+
+        for (table, same_key) in KEEP_EXISTING_TABLES {
+            sqlx::query(&format!(
+                "UPDATE {table} t SET sender_id = $1 WHERE t.sender_id = $2 AND NOT EXISTS ( \
+                     SELECT 1 FROM {table} k WHERE k.sender_id = $1 AND {same_key})"
+            ))
+            .bind(into)
+            .bind(from)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            sqlx::query(&format!("DELETE FROM {table} WHERE sender_id = $1"))
+                .bind(from)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?;
+        }
+
+        // Aliases: everything that pointed at `from` now points at `into`.
+        sqlx::query(
+            "UPDATE user_aliases SET canonical_sender_id = $1 WHERE canonical_sender_id = $2",
+        )
+        .bind(into)
+        .bind(from)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+        sqlx::query(
+            "INSERT INTO user_aliases (alias_sender_id, canonical_sender_id) VALUES ($1, $2) \
+             ON CONFLICT (alias_sender_id) DO UPDATE SET canonical_sender_id = excluded.canonical_sender_id",
+        )
+        .bind(from)
+        .bind(into)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+        sqlx::query("DELETE FROM user_aliases WHERE alias_sender_id = canonical_sender_id")
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+
+        tx.commit().await.map_err(err)?;
+        Ok(report)
+    }
+
+    async fn remove_alias(&self, alias_id: &str) -> Result<bool, OmegaError> {
+        let result = sqlx::query("DELETE FROM user_aliases WHERE alias_sender_id = $1")
+            .bind(alias_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("remove alias failed: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,325 @@
+//! Heartbeat settings and checklist items on PostgreSQL.
+
+use super::PostgresBackend;
+use crate::backend::{HeartbeatItemStore, HeartbeatStore};
+use crate::store::{HeartbeatItem, HeartbeatSettings, NewHeartbeatItem};
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+type SettingsRow = (String, String, String, bool, i64, String, String);
+
+const SETTINGS_COLUMNS: &str = "sender_id, channel, reply_target, enabled, interval_minutes, \
+                                active_start, active_end";
+
+fn settings_from_row(row: SettingsRow) -> HeartbeatSettings {
+    let (sender_id, channel, reply_target, enabled, interval, active_start, active_end) = row;
+    HeartbeatSettings {
+        sender_id,
+        channel,
+        reply_target,
+        enabled,
+        interval_minutes: interval.clamp(1, 1440) as u64,
+        active_start,
+        active_end,
+    }
+}
+
+type ItemRow = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    bool,
+    Option<String>,
+    Option<String>,
+    String,
+);
+
+const ITEM_COLUMNS: &str = "id, sender_id, project, description, cadence, alert_on_change, \
+                            omega_ts(last_checked_at), last_result, omega_ts(created_at)";
+
+fn item_from_row(row: ItemRow) -> HeartbeatItem {
+    let (
+        id,
+        sender_id,
+        project,
+        description,
+        cadence,
+        alert_on_change,
+        last_checked_at,
+        last_result,
+        created_at,
+    ) = row;
+    HeartbeatItem {
+        id,
+        sender_id,
+        project,
+        description,
+        cadence,
+        alert_on_change,
+        last_checked_at,
+        last_result,
+        created_at,
+    }
+}
+
+#[async_trait]
+impl HeartbeatStore for PostgresBackend {
+    async fn get_heartbeat_settings(
+        &self,
+        sender_id: &str,
+    ) -> Result<Option<HeartbeatSettings>, OmegaError> {
+        let row: Option<SettingsRow> = sqlx::query_as(&format!(
+            "SELECT {SETTINGS_COLUMNS} FROM heartbeat_settings WHERE sender_id = $1"
+        ))
+        .bind(sender_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get heartbeat settings: {e}")))?;
+
+        Ok(row.map(settings_from_row))
+    }
+
+    async fn get_enabled_heartbeats(&self) -> Result<Vec<HeartbeatSettings>, OmegaError> {
+        let rows: Vec<SettingsRow> = sqlx::query_as(&format!(
+            "SELECT {SETTINGS_COLUMNS} FROM heartbeat_settings WHERE enabled ORDER BY sender_id"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get enabled heartbeats: {e}")))?;
+
+        Ok(rows.into_iter().map(settings_from_row).collect())
+    }
+
+    async fn save_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<(), OmegaError> {
+        sqlx::query(&format!(
+            "INSERT INTO heartbeat_settings ({SETTINGS_COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             ON CONFLICT (sender_id) DO UPDATE SET \
+             channel = excluded.channel, reply_target = excluded.reply_target, \
+             enabled = excluded.enabled, interval_minutes = excluded.interval_minutes, \
+             active_start = excluded.active_start, active_end = excluded.active_end, \
+             updated_at = clock_timestamp()"
+        ))
+        .bind(&settings.sender_id)
+        .bind(&settings.channel)
+        .bind(&settings.reply_target)
+        .bind(settings.enabled)
+        .bind(settings.interval_minutes as i64)
+        .bind(&settings.active_start)
+        .bind(&settings.active_end)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("save heartbeat settings: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn seed_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<bool, OmegaError> {
+        let result = sqlx::query(&format!(
+            "INSERT INTO heartbeat_settings ({SETTINGS_COLUMNS}) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT DO NOTHING"
+        ))
+        .bind(&settings.sender_id)
+        .bind(&settings.channel)
+        .bind(&settings.reply_target)
+        .bind(settings.enabled)
+        .bind(settings.interval_minutes as i64)
+        .bind(&settings.active_start)
+        .bind(&settings.active_end)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("seed heartbeat settings: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_legacy_heartbeat_checklists(&self) -> Result<Vec<(String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT sender_id, checklist FROM heartbeat_settings \
+             WHERE checklist != '' ORDER BY sender_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get legacy checklists: {e}")))
+    }
+
+    async fn clear_legacy_heartbeat_checklist(&self, sender_id: &str) -> Result<(), OmegaError> {
+        sqlx::query("UPDATE heartbeat_settings SET checklist = '' WHERE sender_id = $1")
+            .bind(sender_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("clear legacy checklist: {e}")))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HeartbeatItemStore for PostgresBackend {
+    async fn add_heartbeat_item(
+        &self,
+        sender_id: &str,
+        project: &str,
+        item: &NewHeartbeatItem,
+    ) -> Result<String, OmegaError> {
+        let existing: Option<(String,)> = sqlx::query_as(
+            "UPDATE heartbeat_items SET cadence = $1, alert_on_change = $2, \
+             updated_at = clock_timestamp() \
+             WHERE id = ( \
+                 SELECT id FROM heartbeat_items \
+                 WHERE sender_id = $3 AND project = $4 AND lower(description) = lower($5) \
+                 LIMIT 1 \
+             ) RETURNING id",
+        )
+        .bind(&item.cadence)
+        .bind(item.alert_on_change)
+        .bind(sender_id)
+        .bind(project)
+        .bind(&item.description)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("update heartbeat item: {e}")))?;
+
+        if let Some((id,)) = existing {
+            return Ok(id);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO heartbeat_items \
+             (id, sender_id, project, description, cadence, alert_on_change) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&id)
+        .bind(sender_id)
+        .bind(project)
+        .bind(&item.description)
+        .bind(&item.cadence)
+        .bind(item.alert_on_change)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("add heartbeat item: {e}")))?;
+
+        Ok(id)
+    }
+
+    async fn get_heartbeat_items(
+        &self,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<HeartbeatItem>, OmegaError> {
+        let rows: Vec<ItemRow> = sqlx::query_as(&format!(
+            "SELECT {ITEM_COLUMNS} FROM heartbeat_items \
+             WHERE sender_id = $1 AND project = $2 ORDER BY created_at, id"
+        ))
+        .bind(sender_id)
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get heartbeat items: {e}")))?;
+
+        Ok(rows.into_iter().map(item_from_row).collect())
+    }
+
+    async fn get_heartbeat_item_projects(
+        &self,
+        sender_id: &str,
+    ) -> Result<Vec<String>, OmegaError> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT DISTINCT project FROM heartbeat_items \
+             WHERE sender_id = $1 AND project != '' ORDER BY project",
+        )
+        .bind(sender_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get heartbeat item projects: {e}")))?;
+
+        Ok(rows.into_iter().map(|(p,)| p).collect())
+    }
+
+    async fn remove_heartbeat_item(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+    ) -> Result<bool, OmegaError> {
+        let Some(id) = self.resolve_heartbeat_item(sender_id, id_prefix).await? else {
+            return Ok(false);
+        };
+        sqlx::query("DELETE FROM heartbeat_items WHERE id = $1")
+            .bind(&id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("remove heartbeat item: {e}")))?;
+        Ok(true)
+    }
+
+    async fn mark_heartbeat_items_checked(&self, ids: &[String]) -> Result<(), OmegaError> {
+        sqlx::query(
+            "UPDATE heartbeat_items SET last_checked_at = clock_timestamp() WHERE id = ANY($1)",
+        )
+        .bind(ids)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("mark heartbeat item checked: {e}")))?;
+        Ok(())
+    }
+
+    async fn record_heartbeat_result(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+        result: &str,
+    ) -> Result<Option<bool>, OmegaError> {
+        let Some(id) = self.resolve_heartbeat_item(sender_id, id_prefix).await? else {
+            return Ok(None);
+        };
+        // The subquery sees the row as it was before the update.
+        let (changed,): (bool,) = sqlx::query_as(
+            "UPDATE heartbeat_items h SET last_result = $1, \
+             last_checked_at = clock_timestamp(), updated_at = clock_timestamp() \
+             FROM (SELECT last_result FROM heartbeat_items WHERE id = $2) previous \
+             WHERE h.id = $2 \
+             RETURNING previous.last_result IS DISTINCT FROM $1",
+        )
+        .bind(result)
+        .bind(&id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("record heartbeat result: {e}")))?;
+
+        Ok(Some(changed))
+    }
+}
+
+impl PostgresBackend {
+    /// Resolve an id prefix to exactly one of the user's items.
+    async fn resolve_heartbeat_item(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        if id_prefix.is_empty() {
+            return Ok(None);
+        }
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT id FROM heartbeat_items WHERE sender_id = $1 AND id LIKE $2 LIMIT 2",
+        )
+        .bind(sender_id)
+        .bind(format!("{id_prefix}%"))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("resolve heartbeat item: {e}")))?;
+
+        Ok(match rows.as_slice() {
+            [(id,)] => Some(id.clone()),
+            _ => None,
+        })
+    }
+}
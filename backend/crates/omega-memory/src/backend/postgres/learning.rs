@@ -0,0 +1,190 @@
+//! Outcomes and lessons on PostgreSQL.
+//!
+//! A `NULL` project parameter means "any project", matching the `None`
+//! branches of the SQLite queries.
+
+use super::PostgresBackend;
+use crate::backend::LearningStore;
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+#[async_trait]
+impl LearningStore for PostgresBackend {
+    async fn store_outcome(
+        &self,
+        sender_id: &str,
+        domain: &str,
+        score: i32,
+        lesson: &str,
+        source: &str,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        sqlx::query(
+            "INSERT INTO outcomes (id, sender_id, domain, score, lesson, source, project) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(sender_id)
+        .bind(domain)
+        .bind(score)
+        .bind(lesson)
+        .bind(source)
+        .bind(project)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("store outcome: {e}")))?;
+        Ok(())
+    }
+
+    async fn get_recent_outcomes(
+        &self,
+        sender_id: &str,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT score, domain, lesson, omega_ts(timestamp) FROM outcomes \
+             WHERE sender_id = $1 AND ($2::TEXT IS NULL OR project = $2) \
+             ORDER BY timestamp DESC LIMIT $3",
+        )
+        .bind(sender_id)
+        .bind(project)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get recent outcomes: {e}")))
+    }
+
+    async fn get_all_recent_outcomes(
+        &self,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT score, domain, lesson, omega_ts(timestamp) FROM outcomes \
+             WHERE timestamp >= now() - make_interval(hours => $1) \
+             AND ($2::TEXT IS NULL OR project = $2) \
+             ORDER BY timestamp DESC LIMIT $3",
+        )
+        .bind(hours as i32)
+        .bind(project)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get all recent outcomes: {e}")))
+    }
+
+    async fn get_recent_outcomes_since(
+        &self,
+        sender_id: &str,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT score, domain, lesson, omega_ts(timestamp) FROM outcomes \
+             WHERE sender_id = $1 AND timestamp >= now() - make_interval(hours => $2) \
+             AND ($3::TEXT IS NULL OR project = $3) \
+             ORDER BY timestamp DESC LIMIT $4",
+        )
+        .bind(sender_id)
+        .bind(hours as i32)
+        .bind(project)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get recent outcomes since: {e}")))
+    }
+
+    async fn store_lesson(
+        &self,
+        sender_id: &str,
+        domain: &str,
+        rule: &str,
+        project: &str,
+    ) -> Result<(), OmegaError> {
+        // Content dedup: reinforce the same rule text instead of duplicating it.
+        let reinforced = sqlx::query(
+            "UPDATE lessons SET occurrences = occurrences + 1, updated_at = clock_timestamp() \
+             WHERE sender_id = $1 AND domain = $2 AND project = $3 AND rule = $4",
+        )
+        .bind(sender_id)
+        .bind(domain)
+        .bind(project)
+        .bind(rule)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("store lesson reinforce: {e}")))?
+        .rows_affected();
+        if reinforced > 0 {
+            return Ok(());
+        }
+
+        sqlx::query(
+            "INSERT INTO lessons (id, sender_id, domain, rule, project) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(sender_id)
+        .bind(domain)
+        .bind(rule)
+        .bind(project)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("store lesson insert: {e}")))?;
+
+        // Cap enforcement: keep at most 10 per (sender, domain, project).
+        sqlx::query(
+            "DELETE FROM lessons WHERE id IN ( \
+                 SELECT id FROM lessons \
+                 WHERE sender_id = $1 AND domain = $2 AND project = $3 \
+                 ORDER BY updated_at DESC, created_at DESC OFFSET 10 \
+             )",
+        )
+        .bind(sender_id)
+        .bind(domain)
+        .bind(project)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("store lesson cap: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn get_lessons(
+        &self,
+        sender_id: &str,
+        project: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, OmegaError> {
+        // Project-specific first, then general; newest first within each group.
+        sqlx::query_as(
+            "SELECT domain, rule, project FROM lessons \
+             WHERE sender_id = $1 AND (project = COALESCE($2, '') OR project = '') \
+             ORDER BY project = COALESCE($2, '') DESC, updated_at DESC \
+             LIMIT 50",
+        )
+        .bind(sender_id)
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get lessons: {e}")))
+    }
+
+    async fn get_all_lessons(
+        &self,
+        project: Option<&str>,
+    ) -> Result<Vec<(String, String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT domain, rule, project FROM lessons \
+             WHERE $1::TEXT IS NULL OR project = $1 OR project = '' \
+             ORDER BY project = $1 DESC, updated_at DESC \
+             LIMIT 50",
+        )
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get all lessons: {e}")))
+    }
+}
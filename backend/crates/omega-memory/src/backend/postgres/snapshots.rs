@@ -0,0 +1,146 @@
+//! Named conversation snapshots on PostgreSQL.
+
+use super::PostgresBackend;
+use crate::backend::{ConversationStore, SnapshotStore};
+use crate::store::ConversationSnapshot;
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+#[async_trait]
+impl SnapshotStore for PostgresBackend {
+    async fn save_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError> {
+        let Some(conv_id) = self
+            .get_open_conversation_id(channel, sender_id, project)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let messages = self.get_conversation_messages(&conv_id).await?;
+
+        sqlx::query(
+            "INSERT INTO conversation_snapshots \
+             (id, channel, sender_id, project, name, messages, message_count) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             ON CONFLICT (channel, sender_id, project, name) \
+             DO UPDATE SET messages = excluded.messages, \
+             message_count = excluded.message_count, created_at = clock_timestamp()",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(name)
+        .bind(self.sealer.seal_messages(&messages)?)
+        .bind(messages.len() as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("save snapshot: {e}")))?;
+
+        Ok(Some(messages.len()))
+    }
+
+    async fn list_snapshots(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<ConversationSnapshot>, OmegaError> {
+        let rows: Vec<(String, i64, String)> = sqlx::query_as(
+            "SELECT name, message_count, omega_ts(created_at) FROM conversation_snapshots \
+             WHERE channel = $1 AND sender_id = $2 AND project = $3 \
+             ORDER BY created_at DESC",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("list snapshots: {e}")))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, message_count, created_at)| ConversationSnapshot {
+                name,
+                message_count,
+                created_at,
+            })
+            .collect())
+    }
+
+    async fn branch_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT messages FROM conversation_snapshots \
+             WHERE channel = $1 AND sender_id = $2 AND project = $3 AND name = $4",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("branch snapshot: {e}")))?;
+        let Some((sealed,)) = row else {
+            return Ok(None);
+        };
+        let messages = self.sealer.open_messages(sealed)?;
+
+        self.close_current_conversation(channel, sender_id, project)
+            .await?;
+        let conv_id = self
+            .get_or_create_conversation(channel, sender_id, project)
+            .await?;
+
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("branch snapshot: {e}"));
+        let mut tx = self.pool.begin().await.map_err(err)?;
+        for (role, content) in &messages {
+            sqlx::query(
+                "INSERT INTO messages (id, conversation_id, role, content) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&conv_id)
+            .bind(role)
+            .bind(self.sealer.seal(content))
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        }
+        tx.commit().await.map_err(err)?;
+
+        Ok(Some(messages.len()))
+    }
+
+    async fn delete_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<bool, OmegaError> {
+        let result = sqlx::query(
+            "DELETE FROM conversation_snapshots \
+             WHERE channel = $1 AND sender_id = $2 AND project = $3 AND name = $4",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("delete snapshot: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,287 @@
+//! Scheduled tasks on PostgreSQL.
+
+use super::PostgresBackend;
+use crate::backend::{PendingTask, TaskStore};
+use crate::store::{descriptions_are_similar, normalize_due_at, DueTask};
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+type DueRow = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+);
+
+#[async_trait]
+impl TaskStore for PostgresBackend {
+    async fn create_task(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        reply_target: &str,
+        description: &str,
+        due_at: &str,
+        repeat: Option<&str>,
+        task_type: &str,
+        project: &str,
+    ) -> Result<String, OmegaError> {
+        let normalized_due = normalize_due_at(due_at);
+
+        // Level 1: exact dedup on (sender, description, normalized due_at).
+        let existing: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM scheduled_tasks \
+             WHERE sender_id = $1 AND description = $2 AND due_at = $3 AND status = 'pending' \
+             LIMIT 1",
+        )
+        .bind(sender_id)
+        .bind(description)
+        .bind(&normalized_due)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("dedup check failed: {e}")))?;
+
+        if let Some((id,)) = existing {
+            tracing::info!("scheduled task dedup: reusing existing {id}");
+            return Ok(id);
+        }
+
+        // Level 2: fuzzy dedup — same sender, similar description, due_at within 30 min.
+        let nearby: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, description FROM scheduled_tasks \
+             WHERE sender_id = $1 AND status = 'pending' \
+             AND abs(extract(epoch FROM omega_try_ts($2) - omega_try_ts(due_at))) <= 1800",
+        )
+        .bind(sender_id)
+        .bind(&normalized_due)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("fuzzy dedup check failed: {e}")))?;
+
+        if let Some((existing_id, _)) = nearby
+            .into_iter()
+            .find(|(_, existing_desc)| descriptions_are_similar(description, existing_desc))
+        {
+            tracing::info!("scheduled task fuzzy dedup: reusing {existing_id} (similar to new)");
+            return Ok(existing_id);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO scheduled_tasks \
+             (id, channel, sender_id, reply_target, description, due_at, repeat, task_type, project) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(&id)
+        .bind(channel)
+        .bind(sender_id)
+        .bind(reply_target)
+        .bind(description)
+        .bind(&normalized_due)
+        .bind(repeat)
+        .bind(task_type)
+        .bind(project)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("create task failed: {e}")))?;
+
+        Ok(id)
+    }
+
+    async fn get_due_tasks(&self) -> Result<Vec<DueTask>, OmegaError> {
+        let rows: Vec<DueRow> = sqlx::query_as(
+            "SELECT id, channel, sender_id, reply_target, description, repeat, task_type, project \
+             FROM scheduled_tasks \
+             WHERE status = 'pending' AND omega_try_ts(due_at) <= now()",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get due tasks failed: {e}")))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    id,
+                    channel,
+                    sender_id,
+                    reply_target,
+                    description,
+                    repeat,
+                    task_type,
+                    project,
+                )| {
+                    DueTask {
+                        id,
+                        channel,
+                        sender_id,
+                        reply_target,
+                        description,
+                        repeat,
+                        task_type,
+                        project,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    async fn complete_task(&self, id: &str, repeat: Option<&str>) -> Result<(), OmegaError> {
+        let Some(interval) = repeat.filter(|r| *r != "once") else {
+            sqlx::query(
+                "UPDATE scheduled_tasks SET status = 'delivered', delivered_at = clock_timestamp() \
+                 WHERE id = $1",
+            )
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("complete task failed: {e}")))?;
+            return Ok(());
+        };
+
+        let step = match interval {
+            "weekly" => "7 days",
+            "monthly" => "1 month",
+            _ => "1 day",
+        };
+        // For weekdays: skip Saturday (6) to Monday (+2 days), Sunday (0) (+1 day).
+        let skip = if interval == "weekdays" {
+            "CASE extract(dow FROM next) WHEN 6 THEN interval '2 days' \
+             WHEN 0 THEN interval '1 day' ELSE interval '0' END"
+        } else {
+            "interval '0'"
+        };
+        sqlx::query(&format!(
+            "UPDATE scheduled_tasks SET due_at = omega_ts(next + {skip}) \
+             FROM (SELECT (omega_try_ts(due_at) AT TIME ZONE 'UTC' + $1::INTERVAL) AT TIME ZONE 'UTC' AS next \
+                   FROM scheduled_tasks WHERE id = $2) advanced \
+             WHERE id = $2 AND next IS NOT NULL"
+        ))
+        .bind(step)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("advance task failed: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn fail_task(&self, id: &str, error: &str, max_retries: u32) -> Result<bool, OmegaError> {
+        let row: Option<(i32,)> =
+            sqlx::query_as("SELECT retry_count FROM scheduled_tasks WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("fail_task fetch failed: {e}")))?;
+
+        let new_count = row.map(|r| r.0).unwrap_or(0) as u32 + 1;
+        let retry = new_count < max_retries;
+
+        // Retry: keep pending, push due_at forward by 2 minutes. Otherwise fail for good.
+        sqlx::query(
+            "UPDATE scheduled_tasks SET retry_count = $1, last_error = $2, \
+             due_at = CASE WHEN $3 THEN omega_ts(now() + interval '2 minutes') ELSE due_at END, \
+             status = CASE WHEN $3 THEN status ELSE 'failed' END \
+             WHERE id = $4",
+        )
+        .bind(new_count as i32)
+        .bind(error)
+        .bind(retry)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("fail_task update failed: {e}")))?;
+
+        Ok(retry)
+    }
+
+    async fn get_tasks_for_sender(&self, sender_id: &str) -> Result<Vec<PendingTask>, OmegaError> {
+        sqlx::query_as(
+            "SELECT id, description, due_at, repeat, task_type, project \
+             FROM scheduled_tasks \
+             WHERE sender_id = $1 AND status = 'pending' \
+             ORDER BY due_at ASC",
+        )
+        .bind(sender_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get tasks failed: {e}")))
+    }
+
+    async fn cancel_task(&self, id_prefix: &str, sender_id: &str) -> Result<bool, OmegaError> {
+        let prefix = format!("{id_prefix}%");
+
+        let result = sqlx::query(
+            "UPDATE scheduled_tasks SET status = 'cancelled' \
+             WHERE id LIKE $1 AND sender_id = $2 AND status = 'pending'",
+        )
+        .bind(&prefix)
+        .bind(sender_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("cancel task failed: {e}")))?;
+
+        if result.rows_affected() > 0 {
+            return Ok(true);
+        }
+
+        // Idempotent: if already cancelled, treat as success.
+        let (already,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM scheduled_tasks \
+             WHERE id LIKE $1 AND sender_id = $2 AND status = 'cancelled'",
+        )
+        .bind(&prefix)
+        .bind(sender_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("cancel task check failed: {e}")))?;
+
+        Ok(already > 0)
+    }
+
+    async fn update_task(
+        &self,
+        id_prefix: &str,
+        sender_id: &str,
+        description: Option<&str>,
+        due_at: Option<&str>,
+        repeat: Option<&str>,
+    ) -> Result<bool, OmegaError> {
+        if description.is_none() && due_at.is_none() && repeat.is_none() {
+            return Ok(false);
+        }
+
+        let result = sqlx::query(
+            "UPDATE scheduled_tasks SET \
+             description = COALESCE($1, description), \
+             due_at = COALESCE($2, due_at), \
+             repeat = COALESCE($3, repeat) \
+             WHERE id LIKE $4 AND sender_id = $5 AND status = 'pending'",
+        )
+        .bind(description)
+        .bind(due_at)
+        .bind(repeat)
+        .bind(format!("{id_prefix}%"))
+        .bind(sender_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("update task failed: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn defer_task(&self, id: &str, new_due_at: &str) -> Result<(), OmegaError> {
+        sqlx::query("UPDATE scheduled_tasks SET due_at = $1 WHERE id = $2 AND status = 'pending'")
+            .bind(new_due_at)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("defer task failed: {e}")))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,894 @@
+//! Conformance tests: every backend must behave like SQLite.
+//!
+//! Each test runs against SQLite and the in-memory backend, and against
+//! PostgreSQL when `OMEGA_TEST_POSTGRES_URL` is set (each run gets its own
+//! schema, dropped on success).
+
+use super::{InMemoryBackend, MemoryBackend, PostgresBackend};
+use crate::store::{HeartbeatSettings, NewHeartbeatItem, SqliteBackend, Store};
+use omega_core::config::MemoryConfig;
+use omega_core::crypto::{generate_key, Cipher};
+use omega_core::message::{IncomingMessage, OutgoingMessage};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use std::str::FromStr;
+use std::sync::Arc;
+
+async fn sqlite() -> Arc<dyn MemoryBackend> {
+    let opts = SqliteConnectOptions::from_str("sqlite::memory:").unwrap();
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await
+        .unwrap();
+    Arc::new(SqliteBackend::from_pool(pool).await.unwrap())
+}
+
+/// A fresh schema on the test server, or `None` when none is configured.
+async fn postgres() -> Option<(Arc<dyn MemoryBackend>, String)> {
+    let url = std::env::var("OMEGA_TEST_POSTGRES_URL").ok()?;
+    let schema = format!("omega_test_{}", uuid::Uuid::new_v4().simple());
+    let admin = PgPoolOptions::new().connect(&url).await.unwrap();
+    sqlx::query(&format!("CREATE SCHEMA {schema}"))
+        .execute(&admin)
+        .await
+        .unwrap();
+    let opts = PgConnectOptions::from_str(&url)
+        .unwrap()
+        .options([("search_path", schema.as_str()), ("timezone", "UTC")]);
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_with(opts)
+        .await
+        .unwrap();
+    Some((
+        Arc::new(PostgresBackend::from_pool(pool).await.unwrap()),
+        schema,
+    ))
+}
+
+/// Run `check` against every available backend.
+async fn each_backend<F, Fut>(check: F)
+where
+    F: Fn(Store) -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    check(Store::with_backend(sqlite().await, 10)).await;
+    check(Store::with_backend(Arc::new(InMemoryBackend::new()), 10)).await;
+    if let Some((backend, schema)) = postgres().await {
+        check(Store::with_backend(backend.clone(), 10)).await;
+        let pg = backend.as_any().downcast_ref::<PostgresBackend>().unwrap();
+        sqlx::query(&format!("DROP SCHEMA {schema} CASCADE"))
+            .execute(pg.pool())
+            .await
+            .unwrap();
+    }
+}
+
+fn incoming(sender: &str, text: &str) -> IncomingMessage {
+    IncomingMessage {
+        id: uuid::Uuid::new_v4(),
+        channel: "telegram".to_string(),
+        sender_id: sender.to_string(),
+        sender_name: None,
+        text: text.to_string(),
+        timestamp: chrono::Utc::now(),
+        reply_to: None,
+        attachments: vec![],
+        reply_target: Some("chat1".to_string()),
+        is_group: false,
+        source: None,
+        platform_message_id: None,
+    }
+}
+
+fn reply(text: &str) -> OutgoingMessage {
+    OutgoingMessage {
+        text: text.to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_store_new_selects_backend() {
+    let config = MemoryConfig {
+        backend: "memory".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(Store::new(&config).await.unwrap().name(), "memory");
+
+    let config = MemoryConfig {
+        backend: "mongodb".to_string(),
+        ..Default::default()
+    };
+    let err = Store::new(&config).await.err().unwrap().to_string();
+    assert!(err.contains("unknown memory backend"), "{err}");
+
+    let config = MemoryConfig {
+        backend: "postgres".to_string(),
+        ..Default::default()
+    };
+    let err = Store::new(&config).await.err().unwrap().to_string();
+    assert!(err.contains("memory.url"), "{err}");
+}
+
+#[tokio::test]
+async fn test_sqlite_only_features_report_backend() {
+    let store = Store::with_backend(Arc::new(InMemoryBackend::new()), 10);
+    let err = store.db_size().await.unwrap_err().to_string();
+    assert!(err.contains("memory backend"), "{err}");
+    assert!(store.export_sender("u1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_conversation_lifecycle() {
+    each_backend(|store| async move {
+        let name = store.name();
+        let conv = store
+            .get_or_create_conversation("telegram", "u1", "")
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .get_or_create_conversation("telegram", "u1", "")
+                .await
+                .unwrap(),
+            conv,
+            "{name}"
+        );
+        assert_eq!(
+            store
+                .get_active_conversation_id("telegram", "u1", "")
+                .await
+                .unwrap(),
+            Some(conv.clone())
+        );
+        assert_eq!(
+            store.get_conversation_sender(&conv).await.unwrap(),
+            Some("u1".to_string())
+        );
+
+        for i in 0..3 {
+            store
+                .store_exchange(
+                    &incoming("u1", &format!("question {i}")),
+                    &reply(&format!("answer {i}")),
+                    "",
+                )
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            store.get_conversation_messages(&conv).await.unwrap().len(),
+            6
+        );
+        // SQLite stamps whole seconds, so order within one second is unspecified.
+        let recent = store.get_recent_messages(&conv, 3).await.unwrap();
+        assert_eq!(recent.len(), 3, "{name}");
+        let (count, tokens) = store.get_conversation_token_estimate(&conv).await.unwrap();
+        assert_eq!((count, tokens), (6, 54 / 4), "{name}");
+        assert_eq!(store.get_memory_stats("u1").await.unwrap(), (1, 6, 0));
+        assert_eq!(
+            store.find_all_active_conversations().await.unwrap().len(),
+            1
+        );
+        assert!(store.find_idle_conversations().await.unwrap().is_empty());
+
+        store
+            .close_conversation(&conv, "Talked shop")
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .get_open_conversation_id("telegram", "u1", "")
+                .await
+                .unwrap(),
+            None
+        );
+        let summaries = store
+            .get_recent_summaries("telegram", "u1", 5)
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].0, "Talked shop");
+        assert_eq!(summaries[0].1.len(), "2026-01-01 00:00:00".len(), "{name}");
+        assert_eq!(store.get_all_recent_summaries(5).await.unwrap().len(), 1);
+
+        // /forget closes without a summary; history still lists it.
+        let next = store
+            .get_or_create_conversation("telegram", "u1", "")
+            .await
+            .unwrap();
+        assert_ne!(next, conv);
+        assert!(store
+            .close_current_conversation("telegram", "u1", "")
+            .await
+            .unwrap());
+        let history = store.get_history("telegram", "u1", 5).await.unwrap();
+        assert!(history.iter().any(|(s, _)| s == "(no summary)"), "{name}");
+        assert_eq!(history.len(), 2);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_search_messages() {
+    each_backend(|store| async move {
+        let name = store.name();
+        store
+            .store_exchange(
+                &incoming("u1", "the deploy pipeline is broken"),
+                &reply("pipeline fixed"),
+                "",
+            )
+            .await
+            .unwrap();
+        let old = store
+            .get_active_conversation_id("telegram", "u1", "")
+            .await
+            .unwrap()
+            .unwrap();
+        store.close_conversation(&old, "deploys").await.unwrap();
+        let current = store
+            .get_or_create_conversation("telegram", "u1", "")
+            .await
+            .unwrap();
+
+        let hits = store
+            .search_messages("deploy pipeline", &current, "u1", 5)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1, "{name}");
+        assert_eq!(hits[0].0, "user");
+        assert_eq!(hits[0].1, "the deploy pipeline is broken");
+
+        // Assistant messages are not indexed; other senders are not searched.
+        assert!(store
+            .search_messages("fixed", &current, "u1", 5)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(store
+            .search_messages("deploy pipeline", &current, "u2", 5)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(store
+            .search_messages("de", &current, "u1", 5)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(store
+            .search_messages("NOT \"deploy\" OR *", &current, "u1", 5)
+            .await
+            .is_ok());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_facts_aliases_and_limitations() {
+    each_backend(|store| async move {
+        let name = store.name();
+        assert!(store.is_new_user("u1").await.unwrap());
+        store.store_fact("u1", "welcomed", "true").await.unwrap();
+        store.store_fact("u1", "name", "Ana").await.unwrap();
+        store.store_fact("u1", "city", "Lima").await.unwrap();
+        store.store_fact("u1", "city", "Quito").await.unwrap();
+        assert!(!store.is_new_user("u1").await.unwrap());
+        assert_eq!(
+            store.get_fact("u1", "city").await.unwrap().as_deref(),
+            Some("Quito")
+        );
+        let keys: Vec<String> = store
+            .get_facts("u1")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, ["city", "name", "welcomed"], "{name}");
+        assert_eq!(store.get_all_facts().await.unwrap().len(), 2);
+        assert_eq!(
+            store.get_all_facts_by_key("name").await.unwrap(),
+            vec![("u1".to_string(), "Ana".to_string())]
+        );
+        assert!(store.delete_fact("u1", "city").await.unwrap());
+        assert!(!store.delete_fact("u1", "city").await.unwrap());
+
+        assert_eq!(
+            store.find_canonical_user("u2").await.unwrap().as_deref(),
+            Some("u1")
+        );
+        store.create_alias("wa:1", "u1").await.unwrap();
+        store.create_alias("wa:1", "u9").await.unwrap();
+        assert_eq!(store.resolve_sender_id("wa:1").await.unwrap(), "u1");
+        assert_eq!(store.resolve_sender_id("wa:2").await.unwrap(), "wa:2");
+        assert_eq!(store.get_aliases("u1").await.unwrap(), ["wa:1"]);
+        assert!(store.remove_alias("wa:1").await.unwrap());
+        assert!(!store.remove_alias("wa:1").await.unwrap());
+
+        assert!(store
+            .store_limitation("No PDF", "Can't read", "Add")
+            .await
+            .unwrap());
+        assert!(!store.store_limitation("no pdf", "dup", "").await.unwrap());
+        assert_eq!(store.get_open_limitations().await.unwrap().len(), 1);
+
+        assert_eq!(store.delete_facts("u1", None).await.unwrap(), 2, "{name}");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_merge_sender() {
+    each_backend(|store| async move {
+        let name = store.name();
+        store.store_fact("u1", "name", "Ana").await.unwrap();
+        store.store_fact("u1", "city", "Lima").await.unwrap();
+        store.store_fact("u2", "name", "Ana").await.unwrap();
+        store.store_fact("u2", "pet", "cat").await.unwrap();
+        store.store_fact("u2", "city", "Quito").await.unwrap();
+        store.create_alias("wa:9", "u2").await.unwrap();
+        store
+            .store_session("telegram", "u1", "", "s1")
+            .await
+            .unwrap();
+        store
+            .store_session("telegram", "u2", "", "s2")
+            .await
+            .unwrap();
+        store
+            .store_session("whatsapp", "u2", "", "s3")
+            .await
+            .unwrap();
+        store
+            .get_or_create_conversation("telegram", "u1", "")
+            .await
+            .unwrap();
+        store
+            .get_or_create_conversation("telegram", "u2", "")
+            .await
+            .unwrap();
+        store
+            .store_lesson("u2", "style", "Be brief", "")
+            .await
+            .unwrap();
+
+        let report = store.merge_sender("u2", "u1").await.unwrap();
+        assert_eq!(report.facts, 1, "{name}");
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].key, "city");
+        let conflict = &report.conflicts[0];
+        let mut values = [conflict.kept.as_str(), conflict.discarded.as_str()];
+        values.sort();
+        assert_eq!(values, ["Lima", "Quito"], "{name}");
+        let kept = conflict.kept.clone();
+        assert_eq!(report.conversations, 1);
+        assert_eq!(report.lessons, 1);
+
+        assert_eq!(store.get_facts("u1").await.unwrap().len(), 3);
+        assert_eq!(store.get_fact("u1", "city").await.unwrap(), Some(kept));
+        assert!(store.get_facts("u2").await.unwrap().is_empty());
+        assert_eq!(store.resolve_sender_id("u2").await.unwrap(), "u1");
+        assert_eq!(store.resolve_sender_id("wa:9").await.unwrap(), "u1");
+        assert_eq!(
+            store
+                .get_session("telegram", "u1", "")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("s1")
+        );
+        assert_eq!(
+            store
+                .get_session("whatsapp", "u1", "")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("s3"),
+            "{name}"
+        );
+        // Only one of the two colliding conversations is still active.
+        assert_eq!(
+            store.find_all_active_conversations().await.unwrap().len(),
+            1
+        );
+        assert!(store.merge_sender("u1", "u1").await.is_err());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_task_lifecycle() {
+    each_backend(|store| async move {
+        let name = store.name();
+        let id = store
+            .create_task(
+                "telegram",
+                "u1",
+                "chat1",
+                "Call the dentist about the appointment",
+                "2020-01-03T09:00:00Z",
+                Some("weekdays"),
+                "reminder",
+                "",
+            )
+            .await
+            .unwrap();
+        // Exact and fuzzy duplicates reuse the task.
+        let exact = store
+            .create_task(
+                "telegram",
+                "u1",
+                "chat1",
+                "Call the dentist about the appointment",
+                "2020-01-03 09:00:00",
+                Some("weekdays"),
+                "reminder",
+                "",
+            )
+            .await
+            .unwrap();
+        let fuzzy = store
+            .create_task(
+                "telegram",
+                "u1",
+                "chat1",
+                "Call dentist about appointment",
+                "2020-01-03 09:20:00",
+                None,
+                "reminder",
+                "",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            (exact.as_str(), fuzzy.as_str()),
+            (id.as_str(), id.as_str()),
+            "{name}"
+        );
+
+        let due = store.get_due_tasks().await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].repeat.as_deref(), Some("weekdays"));
+
+        // 2020-01-03 is a Friday: the next weekday is Monday the 6th.
+        store.complete_task(&id, Some("weekdays")).await.unwrap();
+        let tasks = store.get_tasks_for_sender("u1").await.unwrap();
+        assert_eq!(tasks[0].2, "2020-01-06 09:00:00", "{name}");
+
+        assert!(store.fail_task(&id, "boom", 2).await.unwrap());
+        assert!(store.get_due_tasks().await.unwrap().is_empty(), "{name}");
+        assert!(!store.fail_task(&id, "boom", 2).await.unwrap());
+        assert!(store.get_tasks_for_sender("u1").await.unwrap().is_empty());
+
+        let other = store
+            .create_task(
+                "telegram",
+                "u1",
+                "chat1",
+                "Water plants",
+                "2099-01-01 08:00:00",
+                None,
+                "action",
+                "garden",
+            )
+            .await
+            .unwrap();
+        assert!(store
+            .update_task(
+                &other[..8],
+                "u1",
+                Some("Water the plants"),
+                None,
+                Some("daily")
+            )
+            .await
+            .unwrap());
+        assert!(!store
+            .update_task(&other[..8], "u2", Some("x"), None, None)
+            .await
+            .unwrap());
+        store
+            .defer_task(&other, "2099-02-01 08:00:00")
+            .await
+            .unwrap();
+        let tasks = store.get_tasks_for_sender("u1").await.unwrap();
+        assert_eq!(
+            tasks[0],
+            (
+                other.clone(),
+                "Water the plants".to_string(),
+                "2099-02-01 08:00:00".to_string(),
+                Some("daily".to_string()),
+                "action".to_string(),
+                "garden".to_string(),
+            ),
+            "{name}"
+        );
+        assert!(store.cancel_task(&other[..8], "u1").await.unwrap());
+        assert!(store.cancel_task(&other[..8], "u1").await.unwrap());
+        assert!(!store.cancel_task("zzzz", "u1").await.unwrap());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_outcomes_and_lessons() {
+    each_backend(|store| async move {
+        let name = store.name();
+        store
+            .store_outcome("u1", "tone", 1, "Liked brevity", "conversation", "")
+            .await
+            .unwrap();
+        store
+            .store_outcome("u1", "code", -1, "Wrong crate", "conversation", "omega")
+            .await
+            .unwrap();
+        let all = store.get_recent_outcomes("u1", 10, None).await.unwrap();
+        assert_eq!(all[0].1, "code", "{name}");
+        assert_eq!(
+            store
+                .get_recent_outcomes("u1", 10, Some("omega"))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            store
+                .get_all_recent_outcomes(24, 10, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            store
+                .get_recent_outcomes_since("u1", 24, 10, Some(""))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        for i in 0..12 {
+            store
+                .store_lesson("u1", "style", &format!("rule {i}"), "")
+                .await
+                .unwrap();
+        }
+        store
+            .store_lesson("u1", "style", "rule 11", "")
+            .await
+            .unwrap();
+        store
+            .store_lesson("u1", "code", "Use clippy", "omega")
+            .await
+            .unwrap();
+
+        let general = store.get_lessons("u1", None).await.unwrap();
+        assert_eq!(general.len(), 10, "{name}");
+        let scoped = store.get_lessons("u1", Some("omega")).await.unwrap();
+        assert_eq!(scoped.len(), 11);
+        assert_eq!(scoped[0].1, "Use clippy", "{name}");
+        assert!(scoped[1..].iter().all(|(_, _, p)| p.is_empty()), "{name}");
+        assert_eq!(store.get_all_lessons(None).await.unwrap().len(), 11);
+        assert_eq!(
+            store.get_all_lessons(Some("omega")).await.unwrap()[0].2,
+            "omega"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_sessions() {
+    each_backend(|store| async move {
+        store
+            .store_session("telegram", "u1", "", "a")
+            .await
+            .unwrap();
+        store
+            .store_session("telegram", "u1", "", "b")
+            .await
+            .unwrap();
+        store
+            .store_session("telegram", "u1", "omega", "c")
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .get_session("telegram", "u1", "")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("b")
+        );
+        store.clear_session("telegram", "u1", "").await.unwrap();
+        assert_eq!(store.get_session("telegram", "u1", "").await.unwrap(), None);
+        store.clear_all_sessions_for_sender("u1").await.unwrap();
+        assert_eq!(
+            store.get_session("telegram", "u1", "omega").await.unwrap(),
+            None
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_pop_last_exchange() {
+    each_backend(|store| async move {
+        let name = store.name();
+        assert!(store
+            .pop_last_exchange("telegram", "u1", "")
+            .await
+            .unwrap()
+            .is_none());
+
+        store
+            .store_exchange(&incoming("u1", "first"), &reply("one"), "")
+            .await
+            .unwrap();
+        let mut second = reply("two");
+        second.metadata.created_tasks = vec!["t1".to_string()];
+        store
+            .store_exchange(&incoming("u1", "second"), &second, "")
+            .await
+            .unwrap();
+
+        let popped = store
+            .pop_last_exchange("telegram", "u1", "")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(popped.user, "second", "{name}");
+        assert_eq!(popped.assistant, "two", "{name}");
+        assert_eq!(popped.metadata.created_tasks, vec!["t1"], "{name}");
+
+        let conv = store
+            .get_open_conversation_id("telegram", "u1", "")
+            .await
+            .unwrap()
+            .unwrap();
+        let messages = store.get_conversation_messages(&conv).await.unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                ("user".to_string(), "first".to_string()),
+                ("assistant".to_string(), "one".to_string())
+            ],
+            "{name}"
+        );
+
+        store
+            .pop_last_exchange("telegram", "u1", "")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(store
+            .pop_last_exchange("telegram", "u1", "")
+            .await
+            .unwrap()
+            .is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_snapshots_and_branching() {
+    each_backend(|store| async move {
+        let name = store.name();
+        assert_eq!(
+            store
+                .save_snapshot("telegram", "u1", "", "plan")
+                .await
+                .unwrap(),
+            None
+        );
+
+        store
+            .store_exchange(&incoming("u1", "draft a plan"), &reply("plan A"), "")
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .save_snapshot("telegram", "u1", "", "plan")
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        store
+            .store_exchange(&incoming("u1", "now make it worse"), &reply("plan B"), "")
+            .await
+            .unwrap();
+        let before = store
+            .get_open_conversation_id("telegram", "u1", "")
+            .await
+            .unwrap()
+            .unwrap();
+
+        let listed = store.list_snapshots("telegram", "u1", "").await.unwrap();
+        assert_eq!(listed.len(), 1, "{name}");
+        assert_eq!(listed[0].name, "plan");
+        assert_eq!(listed[0].message_count, 2);
+        assert!(store
+            .list_snapshots("telegram", "u1", "omega")
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            store
+                .branch_snapshot("telegram", "u1", "", "missing")
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            store
+                .branch_snapshot("telegram", "u1", "", "plan")
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        let after = store
+            .get_open_conversation_id("telegram", "u1", "")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(before, after, "{name}");
+        let messages = store.get_conversation_messages(&after).await.unwrap();
+        assert_eq!(messages.len(), 2, "{name}");
+        assert_eq!(messages[1].1, "plan A");
+        // The branch continues like any conversation.
+        assert_eq!(
+            store
+                .pop_last_exchange("telegram", "u1", "")
+                .await
+                .unwrap()
+                .unwrap()
+                .user,
+            "draft a plan"
+        );
+
+        assert!(store
+            .delete_snapshot("telegram", "u1", "", "plan")
+            .await
+            .unwrap());
+        assert!(!store
+            .delete_snapshot("telegram", "u1", "", "plan")
+            .await
+            .unwrap());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_heartbeats() {
+    each_backend(|store| async move {
+        let name = store.name();
+        let settings = HeartbeatSettings {
+            sender_id: "u1".to_string(),
+            channel: "telegram".to_string(),
+            reply_target: "chat1".to_string(),
+            enabled: true,
+            interval_minutes: 5000,
+            active_start: "08:00".to_string(),
+            active_end: "22:00".to_string(),
+        };
+        assert!(store.seed_heartbeat_settings(&settings).await.unwrap());
+        assert!(!store.seed_heartbeat_settings(&settings).await.unwrap());
+        let saved = store.get_heartbeat_settings("u1").await.unwrap().unwrap();
+        assert_eq!(saved.interval_minutes, 1440, "{name}");
+        store
+            .save_heartbeat_settings(&HeartbeatSettings {
+                enabled: false,
+                ..settings
+            })
+            .await
+            .unwrap();
+        assert!(store.get_enabled_heartbeats().await.unwrap().is_empty());
+        assert!(store
+            .get_legacy_heartbeat_checklists()
+            .await
+            .unwrap()
+            .is_empty());
+        store.clear_legacy_heartbeat_checklist("u1").await.unwrap();
+
+        let item = |description: &str, cadence: &str| NewHeartbeatItem {
+            description: description.to_string(),
+            cadence: cadence.to_string(),
+            alert_on_change: false,
+        };
+        let id = store
+            .add_heartbeat_item("u1", "", &item("Check the inbox", ""))
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .add_heartbeat_item("u1", "", &item("check the INBOX", "hourly"))
+                .await
+                .unwrap(),
+            id,
+            "{name}"
+        );
+        store
+            .add_heartbeat_item("u1", "omega", &item("CI status", ""))
+            .await
+            .unwrap();
+        let items = store.get_heartbeat_items("u1", "").await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].cadence, "hourly");
+        assert_eq!(items[0].description, "Check the inbox");
+        assert_eq!(
+            store.get_heartbeat_item_projects("u1").await.unwrap(),
+            ["omega"]
+        );
+
+        assert_eq!(
+            store
+                .record_heartbeat_result("u1", &id[..6], "3 new")
+                .await
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            store
+                .record_heartbeat_result("u1", &id[..6], "3 new")
+                .await
+                .unwrap(),
+            Some(false),
+            "{name}"
+        );
+        assert_eq!(
+            store
+                .record_heartbeat_result("u2", &id[..6], "x")
+                .await
+                .unwrap(),
+            None
+        );
+        store
+            .mark_heartbeat_items_checked(std::slice::from_ref(&id))
+            .await
+            .unwrap();
+        let items = store.get_heartbeat_items("u1", "").await.unwrap();
+        assert_eq!(items[0].last_result.as_deref(), Some("3 new"));
+        assert!(items[0].last_checked_at.is_some());
+        assert!(!store.remove_heartbeat_item("u1", "").await.unwrap());
+        assert!(store.remove_heartbeat_item("u1", &id[..6]).await.unwrap());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_encryption_and_rekey() {
+    each_backend(|store| async move {
+        let name = store.name();
+        let cipher = Arc::new(Cipher::new(&generate_key().unwrap()).unwrap());
+        store.store_fact("u1", "city", "Lima").await.unwrap();
+        let rewritten = store.rekey(Some(&cipher)).await.unwrap();
+        let sealed = store.clone().with_cipher(Some(cipher));
+        assert_eq!(
+            sealed.get_fact("u1", "city").await.unwrap().as_deref(),
+            Some("Lima"),
+            "{name}"
+        );
+        if name == "memory" {
+            // Nothing is at rest, so there is nothing to rewrite.
+            assert_eq!(rewritten, 0);
+            assert!(!sealed.is_encrypted());
+        } else {
+            assert_eq!(rewritten, 1, "{name}");
+            assert!(sealed.is_encrypted());
+            assert!(store.get_fact("u1", "city").await.is_err(), "{name}");
+            assert_eq!(sealed.rekey(None).await.unwrap(), 1);
+            assert_eq!(
+                store.get_fact("u1", "city").await.unwrap().as_deref(),
+                Some("Lima")
+            );
+        }
+    })
+    .await;
+}
@@ -1,11 +1,19 @@
 //! # omega-memory
 //!
-//! Persistent memory system for Omega (SQLite-backed).
+//! Persistent memory system for Omega (SQLite by default, PostgreSQL or in-memory).
 
 pub mod audit;
+pub mod backend;
 pub mod store;
 
 pub use audit::AuditLogger;
+pub use backend::{InMemoryBackend, MemoryBackend, PostgresBackend};
 pub use store::detect_language;
 pub use store::DueTask;
-pub use store::Store;
+pub use store::{ContextReport, SectionReport};
+pub use store::{ConversationSnapshot, Exchange};
+pub use store::{FactConflict, MergeReport};
+pub use store::{HeartbeatItem, HeartbeatSettings, NewHeartbeatItem};
+pub use store::{ImportReport, MemoryExport, EXPORT_VERSION};
+pub use store::{MaintenanceRun, PruneReport};
+pub use store::{SqliteBackend, Store};
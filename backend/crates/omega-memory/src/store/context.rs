@@ -13,12 +13,17 @@ use omega_core::{
 use omega_core::config::SYSTEM_FACT_KEYS;
 
 // Re-export helpers so existing `super::context::*` paths in tests keep working.
+use super::context_budget::{Budget, ContextReport};
 pub use super::context_helpers::detect_language;
 #[cfg(test)]
 pub(super) use super::context_helpers::onboarding_hint_text;
 pub(super) use super::context_helpers::{
     build_system_prompt, compute_onboarding_stage, SystemPromptContext,
 };
+use super::context_helpers::{
+    lesson_line, outcome_line, recall_header, recall_line, summary_line, task_line, LESSONS_HEADER,
+    OUTCOMES_HEADER, SUMMARIES_HEADER, TASKS_HEADER,
+};
 
 /// Identity fact keys — shown first in the user profile.
 const IDENTITY_KEYS: &[&str] = &["name", "preferred_name", "pronouns"];
@@ -35,6 +40,20 @@ impl Store {
         needs: &ContextNeeds,
         active_project: Option<&str>,
     ) -> Result<Context, OmegaError> {
+        self.build_context_with_report(incoming, base_system_prompt, needs, active_project)
+            .await
+            .map(|(context, _)| context)
+    }
+
+    /// Build a conversation context fitted to `needs.token_budget`, and a
+    /// report of what each section kept and dropped.
+    pub async fn build_context_with_report(
+        &self,
+        incoming: &IncomingMessage,
+        base_system_prompt: &str,
+        needs: &ContextNeeds,
+        active_project: Option<&str>,
+    ) -> Result<(Context, ContextReport), OmegaError> {
         let project_key = active_project.unwrap_or("");
         let conv_id = self
             .get_or_create_conversation(&incoming.channel, &incoming.sender_id, project_key)
@@ -43,23 +62,17 @@ impl Store {
         // Run independent DB queries in parallel. All depend on conv_id
         // (already resolved) or sender_id, so they are safe to parallelize.
         let history_fut = async {
-            let rows: Vec<(String, String)> = sqlx::query_as(
-                "SELECT role, content FROM (\
-                     SELECT role, content, timestamp FROM messages \
-                     WHERE conversation_id = ? ORDER BY timestamp DESC LIMIT ?\
-                 ) ORDER BY timestamp ASC",
-            )
-            .bind(&conv_id)
-            .bind(self.max_context_messages as i64)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
-
-            Ok::<Vec<ContextEntry>, OmegaError>(
-                rows.into_iter()
-                    .map(|(role, content)| ContextEntry { role, content })
-                    .collect(),
-            )
+            self.get_recent_messages(&conv_id, self.max_context_messages as i64)
+                .await
+                .map(|rows| {
+                    rows.into_iter()
+                        .map(|(role, content)| ContextEntry {
+                            role,
+                            content,
+                            media: Vec::new(),
+                        })
+                        .collect::<Vec<ContextEntry>>()
+                })
         };
 
         let facts_fut = async {
@@ -191,29 +204,93 @@ impl Store {
         };
 
         let facts_for_prompt: &[(String, String)] = if needs.profile { &facts } else { &[] };
-        let system_prompt = build_system_prompt(&SystemPromptContext {
+
+        // Fit the token budget, highest priority first. The base rules and
+        // the current message are always sent.
+        let mut budget = Budget::new(needs.token_budget);
+        budget.require(
+            "system",
+            &build_system_prompt(&SystemPromptContext {
+                base_rules: base_system_prompt,
+                facts: &[],
+                summaries: &[],
+                recall: &[],
+                pending_tasks: &[],
+                outcomes: &[],
+                lessons: &[],
+                language: &language,
+                onboarding_hint,
+            }),
+        );
+        budget.require("message", &incoming.text);
+
+        let profile = format_user_profile(facts_for_prompt);
+        let profile_lines: Vec<String> = if profile.is_empty() {
+            Vec::new()
+        } else {
+            vec![format!("\n\n{profile}")]
+        };
+        let facts_for_prompt = if budget.fit("profile", "", &profile_lines) > 0 {
+            facts_for_prompt
+        } else {
+            &[]
+        };
+        let lines: Vec<String> = lessons.iter().map(lesson_line).collect();
+        let lessons = &lessons[..budget.fit("lessons", LESSONS_HEADER, &lines)];
+        let lines: Vec<String> = pending_tasks.iter().map(task_line).collect();
+        let pending_tasks = &pending_tasks[..budget.fit("tasks", TASKS_HEADER, &lines)];
+        let history = budget.fit_history(history);
+        let lines: Vec<String> = summaries.iter().map(summary_line).collect();
+        let summaries = &summaries[..budget.fit("summaries", SUMMARIES_HEADER, &lines)];
+        let lines: Vec<String> = recall.iter().map(recall_line).collect();
+        let recall = &recall[..budget.fit("recall", &recall_header(), &lines)];
+        let now = chrono::Utc::now();
+        let lines: Vec<String> = outcomes.iter().map(|o| outcome_line(o, &now)).collect();
+        let outcomes = &outcomes[..budget.fit("outcomes", OUTCOMES_HEADER, &lines)];
+
+        let mut system_prompt = build_system_prompt(&SystemPromptContext {
             base_rules: base_system_prompt,
             facts: facts_for_prompt,
-            summaries: &summaries,
-            recall: &recall,
-            pending_tasks: &pending_tasks,
-            outcomes: &outcomes,
-            lessons: &lessons,
+            summaries,
+            recall,
+            pending_tasks,
+            outcomes,
+            lessons,
             language: &language,
             onboarding_hint,
         });
+        if let Some(dropped) = budget
+            .report
+            .section("history")
+            .map(|h| h.dropped)
+            .filter(|&n| n > 0)
+        {
+            system_prompt.push_str(&format!(
+                "\n\n({dropped} earlier messages of this conversation were left out \
+                 to fit the context window.)"
+            ));
+        }
 
-        Ok(Context {
+        let context = Context {
             system_prompt,
             history,
             current_message: incoming.text.clone(),
+            current_media: Vec::new(),
             mcp_servers: Vec::new(),
             max_turns: None,
             allowed_tools: None,
+            denied_tools: Vec::new(),
             model: None,
             session_id: None,
             agent_name: None,
-        })
+            approval: None,
+            workspace: None,
+            action_tools: false,
+            stable_prefix_len: 0,
+            response_schema: None,
+            progress: None,
+        };
+        Ok((context, budget.report))
     }
 }
 
@@ -0,0 +1,305 @@
+//! Token budgeting for context assembly.
+//!
+//! `build_context` prices every part of the context with
+//! [`estimate_tokens`] and fills the model's budget in priority order:
+//!
+//! 1. `system` — base rules, language and onboarding (always kept)
+//! 2. `message` — the current message (always kept)
+//! 3. `profile`, `lessons`, `tasks` — small and always relevant
+//! 4. `history` — newest first, rolling (see [`Budget::fit_history`])
+//! 5. `summaries`, `recall`, `outcomes` — background, dropped first
+//!
+//! Each section keeps the longest prefix of its items that fits (the lists
+//! come most relevant first) and the rest is dropped. What was kept and
+//! dropped is recorded in a [`ContextReport`] for `/context`.
+
+use omega_core::context::{estimate_tokens, ContextEntry};
+
+/// Newest messages kept whole whenever they fit.
+const VERBATIM_MESSAGES: usize = 4;
+/// Older messages that don't fit whole are cut to this many tokens.
+const OLDER_MESSAGE_TOKENS: usize = 300;
+/// A message is dropped rather than cut to fewer tokens than this.
+const MIN_CUT_TOKENS: usize = 50;
+/// Appended to a message that was cut to fit.
+const CUT_MARKER: &str = " [truncated]";
+
+/// What one section of the context cost, and what was left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SectionReport {
+    /// Section name: `system`, `message`, `profile`, `history`, ...
+    pub name: &'static str,
+    /// Items kept (messages, summaries, lessons, ...).
+    pub included: usize,
+    /// Kept items that were cut to fit.
+    pub truncated: usize,
+    /// Items dropped to fit the budget.
+    pub dropped: usize,
+    /// Estimated tokens of what was kept.
+    pub tokens: usize,
+    /// Estimated tokens of what was dropped or cut off.
+    pub dropped_tokens: usize,
+}
+
+/// How a context was fitted to its token budget.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContextReport {
+    /// The budget in estimated tokens (`0` = unlimited).
+    pub budget: usize,
+    /// Sections in priority order.
+    pub sections: Vec<SectionReport>,
+}
+
+impl ContextReport {
+    /// Estimated tokens of everything kept.
+    pub fn total_tokens(&self) -> usize {
+        self.sections.iter().map(|s| s.tokens).sum()
+    }
+
+    /// Estimated tokens of everything dropped or cut off.
+    pub fn dropped_tokens(&self) -> usize {
+        self.sections.iter().map(|s| s.dropped_tokens).sum()
+    }
+
+    /// The report for section `name`, if it was priced.
+    pub fn section(&self, name: &str) -> Option<&SectionReport> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+}
+
+/// Running budget while a context is assembled.
+pub(super) struct Budget {
+    /// Tokens left (`None` = unlimited).
+    remaining: Option<usize>,
+    pub report: ContextReport,
+}
+
+impl Budget {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            remaining: (budget > 0).then_some(budget),
+            report: ContextReport {
+                budget,
+                sections: Vec::new(),
+            },
+        }
+    }
+
+    /// Charge content that is always kept, even past the budget.
+    pub fn require(&mut self, name: &'static str, text: &str) {
+        let tokens = estimate_tokens(text);
+        self.charge(tokens);
+        self.report.sections.push(SectionReport {
+            name,
+            included: 1,
+            tokens,
+            ..Default::default()
+        });
+    }
+
+    /// Keep the longest prefix of `lines` that fits, `header` included with
+    /// the first line. Returns how many lines to keep.
+    pub fn fit(&mut self, name: &'static str, header: &str, lines: &[String]) -> usize {
+        let costs: Vec<usize> = lines.iter().map(|l| estimate_tokens(l)).collect();
+        let header_tokens = estimate_tokens(header);
+        let mut section = SectionReport {
+            name,
+            ..Default::default()
+        };
+        for (i, &cost) in costs.iter().enumerate() {
+            let cost = if i == 0 { cost + header_tokens } else { cost };
+            if !self.fits(cost) {
+                section.dropped = costs.len() - i;
+                section.dropped_tokens = costs[i..].iter().sum();
+                break;
+            }
+            self.charge(cost);
+            section.included += 1;
+            section.tokens += cost;
+        }
+        let included = section.included;
+        self.report.sections.push(section);
+        included
+    }
+
+    /// Fit the conversation history, newest message first.
+    ///
+    /// The newest messages are kept whole while they fit. Once one doesn't,
+    /// it is cut: a recent message to whatever is left, an older one to
+    /// `OLDER_MESSAGE_TOKENS` so the turns before it still get a chance.
+    /// When not even a cut message fits, it and everything older is dropped.
+    pub fn fit_history(&mut self, history: Vec<ContextEntry>) -> Vec<ContextEntry> {
+        let mut section = SectionReport {
+            name: "history",
+            ..Default::default()
+        };
+        let mut kept = Vec::with_capacity(history.len());
+        let mut older = history.into_iter().rev().enumerate();
+        for (i, mut entry) in older.by_ref() {
+            let cost = estimate_tokens(&entry.content);
+            if self.fits(cost) {
+                self.charge(cost);
+                section.included += 1;
+                section.tokens += cost;
+                kept.push(entry);
+                continue;
+            }
+            let left = self.remaining.unwrap_or(0);
+            let cap = if i < VERBATIM_MESSAGES {
+                left
+            } else {
+                left.min(OLDER_MESSAGE_TOKENS)
+            };
+            if cap < MIN_CUT_TOKENS {
+                section.dropped = 1;
+                section.dropped_tokens = cost;
+                break;
+            }
+            entry.content = cut_to_tokens(&entry.content, cap);
+            let kept_tokens = estimate_tokens(&entry.content);
+            self.charge(kept_tokens);
+            section.included += 1;
+            section.truncated += 1;
+            section.tokens += kept_tokens;
+            section.dropped_tokens += cost.saturating_sub(kept_tokens);
+            kept.push(entry);
+        }
+        for (_, entry) in older {
+            section.dropped += 1;
+            section.dropped_tokens += estimate_tokens(&entry.content);
+        }
+        self.report.sections.push(section);
+        kept.reverse();
+        kept
+    }
+
+    fn fits(&self, tokens: usize) -> bool {
+        self.remaining.is_none_or(|left| tokens <= left)
+    }
+
+    fn charge(&mut self, tokens: usize) {
+        if let Some(left) = self.remaining.as_mut() {
+            *left = left.saturating_sub(tokens);
+        }
+    }
+}
+
+/// Cut `text` at a whitespace boundary to about `max_tokens`, marker included.
+fn cut_to_tokens(text: &str, max_tokens: usize) -> String {
+    let limit = max_tokens.saturating_sub(estimate_tokens(CUT_MARKER));
+    let mut tokens = 0;
+    let mut end = 0;
+    for piece in text.split_inclusive(char::is_whitespace) {
+        tokens += estimate_tokens(piece);
+        if tokens > limit {
+            break;
+        }
+        end += piece.len();
+    }
+    if end == 0 {
+        // One long unbroken run (a pasted blob): no character costs more
+        // than a token, so `limit` bytes stay within the limit.
+        end = text.floor_char_boundary(limit);
+    }
+    format!("{}{CUT_MARKER}", text[..end].trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(role: &str, content: &str) -> ContextEntry {
+        ContextEntry {
+            role: role.to_string(),
+            content: content.to_string(),
+            media: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_unlimited_budget_keeps_everything() {
+        let mut budget = Budget::new(0);
+        budget.require("system", "You are helpful.");
+        let lines = vec!["\n- one".to_string(), "\n- two".to_string()];
+        assert_eq!(budget.fit("summaries", "\n\nSummaries:", &lines), 2);
+        let pasted = "word ".repeat(10_000);
+        let history = budget.fit_history(vec![entry("user", &pasted), entry("assistant", "ok")]);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].content, pasted);
+        assert_eq!(budget.report.dropped_tokens(), 0);
+    }
+
+    #[test]
+    fn test_fit_keeps_prefix_and_reports_dropped() {
+        let mut budget = Budget::new(10);
+        let lines = vec![
+            "\n- aaaa bbbb".to_string(),
+            "\n- cccc dddd".to_string(),
+            "\n- eeee ffff".to_string(),
+        ];
+        // Header (3) + first line (3) + second line (3) fit; the third doesn't.
+        assert_eq!(budget.fit("lessons", "\n\nRules:", &lines), 2);
+        let section = budget.report.section("lessons").unwrap();
+        assert_eq!((section.included, section.dropped), (2, 1));
+        assert_eq!(section.tokens, 9);
+        assert_eq!(section.dropped_tokens, 3);
+        // Nothing left for the next section.
+        assert_eq!(budget.fit("outcomes", "\n\nOutcomes:", &lines), 0);
+        assert_eq!(budget.report.section("outcomes").unwrap().dropped, 3);
+    }
+
+    #[test]
+    fn test_fit_history_cuts_older_paste_and_keeps_earlier_turns() {
+        let pasted = "word ".repeat(2_000);
+        let history = vec![
+            entry("user", "hi there"),
+            entry("assistant", "hello"),
+            entry("user", &pasted),
+            entry("assistant", "got it"),
+            entry("user", "thanks"),
+            entry("assistant", "anytime"),
+            entry("user", "one more thing"),
+        ];
+        let mut budget = Budget::new(1_000);
+        let kept = budget.fit_history(history);
+
+        // All seven turns survive; only the paste was cut.
+        assert_eq!(kept.len(), 7);
+        assert_eq!(kept[0].content, "hi there");
+        assert!(kept[2].content.ends_with(CUT_MARKER));
+        assert!(estimate_tokens(&kept[2].content) <= OLDER_MESSAGE_TOKENS);
+        let section = budget.report.section("history").unwrap();
+        assert_eq!(
+            (section.included, section.truncated, section.dropped),
+            (7, 1, 0)
+        );
+        assert!(section.tokens <= 1_000);
+        assert!(section.dropped_tokens > 1_500);
+    }
+
+    #[test]
+    fn test_fit_history_drops_oldest_when_full() {
+        let turn = "word ".repeat(100);
+        let history: Vec<ContextEntry> = (0..10).map(|_| entry("user", &turn)).collect();
+        let mut budget = Budget::new(520);
+        let kept = budget.fit_history(history);
+
+        // Five whole turns fit; the sixth is not worth cutting to 20 tokens.
+        assert_eq!(kept.len(), 5);
+        let section = budget.report.section("history").unwrap();
+        assert_eq!((section.included, section.dropped), (5, 5));
+        assert_eq!(section.dropped_tokens, 500);
+    }
+
+    #[test]
+    fn test_cut_to_tokens() {
+        let cut = cut_to_tokens("alpha beta gamma delta epsilon zeta", 12);
+        assert_eq!(cut, "alpha beta gamma delta [truncated]");
+        assert!(estimate_tokens(&cut) <= 12);
+
+        let blob = format!("{{\"data\":\"{}\"}}", "x".repeat(5_000));
+        let cut = cut_to_tokens(&blob, 100);
+        assert!(cut.starts_with("{\"data\""));
+        assert!(estimate_tokens(&cut) <= 100);
+    }
+}
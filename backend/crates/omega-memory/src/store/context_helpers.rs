@@ -2,6 +2,7 @@
 //! composition, language detection, and relative time formatting.
 
 use super::context::format_user_profile;
+use omega_core::sanitize::label_untrusted;
 
 /// Compute the next onboarding stage based on current state.
 ///
@@ -89,6 +90,13 @@ pub(super) struct SystemPromptContext<'a> {
     pub onboarding_hint: Option<u8>,
 }
 
+/// Section headings, shared with the token budget so it can price them.
+pub(super) const SUMMARIES_HEADER: &str = "\n\nRecent conversation history:";
+pub(super) const RECALL_HEADER: &str = "\n\nRelated past context:";
+pub(super) const TASKS_HEADER: &str = "\n\nUser's scheduled tasks:";
+pub(super) const LESSONS_HEADER: &str = "\n\nLearned behavioral rules:";
+pub(super) const OUTCOMES_HEADER: &str = "\n\nRecent outcomes:";
+
 /// Build a dynamic system prompt enriched with facts, conversation history, and recalled messages.
 pub(super) fn build_system_prompt(ctx: &SystemPromptContext<'_>) -> String {
     let mut prompt = String::from(ctx.base_rules);
@@ -100,70 +108,40 @@ pub(super) fn build_system_prompt(ctx: &SystemPromptContext<'_>) -> String {
     }
 
     if !ctx.summaries.is_empty() {
-        prompt.push_str("\n\nRecent conversation history:");
-        for (summary, timestamp) in ctx.summaries {
-            prompt.push_str(&format!("\n- [{timestamp}] {summary}"));
+        prompt.push_str(SUMMARIES_HEADER);
+        for summary in ctx.summaries {
+            prompt.push_str(&summary_line(summary));
         }
     }
 
     if !ctx.recall.is_empty() {
-        prompt.push_str("\n\nRelated past context:");
-        for (_role, content, timestamp) in ctx.recall {
-            let truncated = if content.len() > 200 {
-                let boundary = content.floor_char_boundary(200);
-                format!("{}...", &content[..boundary])
-            } else {
-                content.clone()
-            };
-            prompt.push_str(&format!("\n- [{timestamp}] User: {truncated}"));
-        }
+        // Past messages may quote webhook payloads or pasted pages.
+        let lines: String = ctx.recall.iter().map(recall_line).collect();
+        let labelled = label_untrusted("recall", lines.trim_start());
+        prompt.push_str(RECALL_HEADER);
+        prompt.push('\n');
+        prompt.push_str(&labelled.text);
     }
 
     if !ctx.pending_tasks.is_empty() {
-        prompt.push_str("\n\nUser's scheduled tasks:");
-        for (id, desc, due_at, repeat, task_type, project) in ctx.pending_tasks {
-            let r = repeat.as_deref().unwrap_or("once");
-            let type_badge = if task_type == "action" {
-                " [action]"
-            } else {
-                ""
-            };
-            let project_badge = if project.is_empty() {
-                String::new()
-            } else {
-                format!(" ({project})")
-            };
-            prompt.push_str(&format!(
-                "\n- [{id_short}] {desc}{type_badge}{project_badge} (due: {due_at}, {r})",
-                id_short = &id[..8.min(id.len())]
-            ));
+        prompt.push_str(TASKS_HEADER);
+        for task in ctx.pending_tasks {
+            prompt.push_str(&task_line(task));
         }
     }
 
     if !ctx.lessons.is_empty() {
-        prompt.push_str("\n\nLearned behavioral rules:");
-        for (domain, rule, project) in ctx.lessons {
-            if project.is_empty() {
-                prompt.push_str(&format!("\n- [{domain}] {rule}"));
-            } else {
-                prompt.push_str(&format!("\n- [{domain}] ({project}) {rule}"));
-            }
+        prompt.push_str(LESSONS_HEADER);
+        for lesson in ctx.lessons {
+            prompt.push_str(&lesson_line(lesson));
         }
     }
 
     if !ctx.outcomes.is_empty() {
-        prompt.push_str("\n\nRecent outcomes:");
+        prompt.push_str(OUTCOMES_HEADER);
         let now = chrono::Utc::now();
-        for (score, domain, lesson, timestamp) in ctx.outcomes {
-            let ago = format_relative_time(timestamp, &now);
-            let sign = if *score > 0 {
-                "+"
-            } else if *score < 0 {
-                "-"
-            } else {
-                "~"
-            };
-            prompt.push_str(&format!("\n- [{sign}] {domain}: {lesson} ({ago})"));
+        for outcome in ctx.outcomes {
+            prompt.push_str(&outcome_line(outcome, &now));
         }
     }
 
@@ -188,6 +166,80 @@ pub(super) fn build_system_prompt(ctx: &SystemPromptContext<'_>) -> String {
     prompt
 }
 
+/// The recall heading plus its untrusted-block wrapper, as priced by the budget.
+pub(super) fn recall_header() -> String {
+    format!("{RECALL_HEADER}\n{}", label_untrusted("recall", "").text)
+}
+
+/// One summary line: `(summary, timestamp)`.
+pub(super) fn summary_line((summary, timestamp): &(String, String)) -> String {
+    format!("\n- [{timestamp}] {summary}")
+}
+
+/// One recalled message, cut to 200 bytes: `(role, content, timestamp)`.
+pub(super) fn recall_line((_role, content, timestamp): &(String, String, String)) -> String {
+    let truncated = if content.len() > 200 {
+        let boundary = content.floor_char_boundary(200);
+        format!("{}...", &content[..boundary])
+    } else {
+        content.clone()
+    };
+    format!("\n- [{timestamp}] User: {truncated}")
+}
+
+/// One pending task: `(id, description, due_at, repeat, task_type, project)`.
+pub(super) fn task_line(
+    (id, desc, due_at, repeat, task_type, project): &(
+        String,
+        String,
+        String,
+        Option<String>,
+        String,
+        String,
+    ),
+) -> String {
+    let r = repeat.as_deref().unwrap_or("once");
+    let type_badge = if task_type == "action" {
+        " [action]"
+    } else {
+        ""
+    };
+    let project_badge = if project.is_empty() {
+        String::new()
+    } else {
+        format!(" ({project})")
+    };
+    format!(
+        "\n- [{id_short}] {desc}{type_badge}{project_badge} (due: {due_at}, {r})",
+        id_short = &id[..8.min(id.len())]
+    )
+}
+
+/// One learned rule: `(domain, rule, project)`.
+pub(super) fn lesson_line((domain, rule, project): &(String, String, String)) -> String {
+    if project.is_empty() {
+        format!("\n- [{domain}] {rule}")
+    } else {
+        format!("\n- [{domain}] ({project}) {rule}")
+    }
+}
+
+/// One outcome: `(score, domain, lesson, timestamp)`.
+pub(super) fn outcome_line(
+    (score, domain, lesson, timestamp): &(i32, String, String, String),
+    now: &chrono::DateTime<chrono::Utc>,
+) -> String {
+    let ago = format_relative_time(timestamp, now);
+    let sign = if *score > 0 {
+        "+"
+    } else if *score < 0 {
+        "-"
+    } else {
+        "~"
+    };
+    format!("\n- [{sign}] {domain}: {lesson} ({ago})")
+}
+
 /// Detect the most likely language of a text using stop-word heuristics.
 /// Returns a language name like "English", "Spanish", etc.
 pub fn detect_language(text: &str) -> &'static str {
@@ -1,15 +1,18 @@
 //! Conversation lifecycle — create, find, close, summaries, history, stats.
 
-use super::{Store, CONVERSATION_TIMEOUT_MINUTES};
+use super::SqliteBackend;
+use crate::backend::{ConversationRef, ConversationStore, CONVERSATION_TIMEOUT_MINUTES};
+use async_trait::async_trait;
 use omega_core::error::OmegaError;
 use uuid::Uuid;
 
-impl Store {
+#[async_trait]
+impl ConversationStore for SqliteBackend {
     /// Get or create an active conversation for a given channel + sender + project.
     ///
     /// Only returns conversations that are `active` AND have `last_activity`
     /// within the timeout window. Otherwise creates a new one.
-    pub(crate) async fn get_or_create_conversation(
+    async fn get_or_create_conversation(
         &self,
         channel: &str,
         sender_id: &str,
@@ -60,10 +63,8 @@ impl Store {
     }
 
     /// Find active conversations that have been idle beyond the timeout.
-    pub async fn find_idle_conversations(
-        &self,
-    ) -> Result<Vec<(String, String, String, String)>, OmegaError> {
-        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+    async fn find_idle_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError> {
+        let rows: Vec<ConversationRef> = sqlx::query_as(
             "SELECT id, channel, sender_id, project FROM conversations \
              WHERE status = 'active' \
              AND datetime(last_activity) <= datetime('now', ? || ' minutes')",
@@ -77,10 +78,8 @@ impl Store {
     }
 
     /// Find all active conversations (for shutdown).
-    pub async fn find_all_active_conversations(
-        &self,
-    ) -> Result<Vec<(String, String, String, String)>, OmegaError> {
-        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+    async fn find_all_active_conversations(&self) -> Result<Vec<ConversationRef>, OmegaError> {
+        let rows: Vec<ConversationRef> = sqlx::query_as(
             "SELECT id, channel, sender_id, project FROM conversations WHERE status = 'active'",
         )
         .fetch_all(&self.pool)
@@ -90,25 +89,83 @@ impl Store {
         Ok(rows)
     }
 
+    /// Find the most recent active conversation, ignoring the timeout (for /forget).
+    async fn get_open_conversation_id(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM conversations \
+             WHERE channel = ? AND sender_id = ? AND project = ? AND status = 'active' \
+             ORDER BY last_activity DESC LIMIT 1",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
+    /// Get the sender a conversation belongs to.
+    async fn get_conversation_sender(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT sender_id FROM conversations WHERE id = ?")
+                .bind(conversation_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        Ok(row.map(|(id,)| id))
+    }
+
     /// Get all messages for a conversation (for summarization).
-    pub async fn get_conversation_messages(
+    async fn get_conversation_messages(
         &self,
         conversation_id: &str,
     ) -> Result<Vec<(String, String)>, OmegaError> {
         let rows: Vec<(String, String)> = sqlx::query_as(
             "SELECT role, content FROM messages \
-             WHERE conversation_id = ? ORDER BY timestamp ASC",
+             WHERE conversation_id = ? ORDER BY timestamp ASC, rowid ASC",
         )
         .bind(conversation_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(rows)
+        self.sealer.open_pairs(rows)
+    }
+
+    /// Get the newest `limit` messages of a conversation, oldest first (for context).
+    async fn get_recent_messages(
+        &self,
+        conversation_id: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String)>, OmegaError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT role, content FROM (\
+                 SELECT role, content, timestamp, rowid AS seq FROM messages \
+                 WHERE conversation_id = ? ORDER BY timestamp DESC, rowid DESC LIMIT ?\
+             ) ORDER BY timestamp ASC, seq ASC",
+        )
+        .bind(conversation_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        self.sealer.open_pairs(rows)
     }
 
     /// Close a conversation with a summary.
-    pub async fn close_conversation(
+    async fn close_conversation(
         &self,
         conversation_id: &str,
         summary: &str,
@@ -116,7 +173,7 @@ impl Store {
         sqlx::query(
             "UPDATE conversations SET status = 'closed', summary = ?, updated_at = datetime('now') WHERE id = ?",
         )
-        .bind(summary)
+        .bind(self.sealer.seal(summary))
         .bind(conversation_id)
         .execute(&self.pool)
         .await
@@ -126,7 +183,7 @@ impl Store {
     }
 
     /// Close the current active conversation for a sender + project (for /forget).
-    pub async fn close_current_conversation(
+    async fn close_current_conversation(
         &self,
         channel: &str,
         sender_id: &str,
@@ -147,7 +204,7 @@ impl Store {
     }
 
     /// Get recent closed conversation summaries for a sender.
-    pub async fn get_recent_summaries(
+    async fn get_recent_summaries(
         &self,
         channel: &str,
         sender_id: &str,
@@ -165,11 +222,13 @@ impl Store {
         .await
         .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(rows)
+        rows.into_iter()
+            .map(|(summary, ts)| Ok((self.sealer.open(summary)?, ts)))
+            .collect()
     }
 
     /// Get recent conversation summaries across all users — for heartbeat context enrichment.
-    pub async fn get_all_recent_summaries(
+    async fn get_all_recent_summaries(
         &self,
         limit: i64,
     ) -> Result<Vec<(String, String)>, OmegaError> {
@@ -183,11 +242,13 @@ impl Store {
         .await
         .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(rows)
+        rows.into_iter()
+            .map(|(summary, ts)| Ok((self.sealer.open(summary)?, ts)))
+            .collect()
     }
 
     /// Get conversation history (summaries with timestamps) for a sender.
-    pub async fn get_history(
+    async fn get_history(
         &self,
         channel: &str,
         sender_id: &str,
@@ -205,14 +266,16 @@ impl Store {
         .await
         .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(rows)
+        rows.into_iter()
+            .map(|(summary, ts)| Ok((self.sealer.open(summary)?, ts)))
+            .collect()
     }
 
     /// Find the active conversation ID for a sender + project WITHOUT creating one.
     ///
     /// Returns `Ok(None)` when no active conversation exists within the timeout window.
     /// Unlike `get_or_create_conversation()`, this is purely read-only.
-    pub async fn get_active_conversation_id(
+    async fn get_active_conversation_id(
         &self,
         channel: &str,
         sender_id: &str,
@@ -239,7 +302,7 @@ impl Store {
     ///
     /// Tokens are estimated as `total_chars / 4` (standard approximation).
     /// Returns `(message_count, estimated_tokens)`.
-    pub async fn get_conversation_token_estimate(
+    async fn get_conversation_token_estimate(
         &self,
         conversation_id: &str,
     ) -> Result<(i64, i64), OmegaError> {
@@ -257,7 +320,7 @@ impl Store {
     }
 
     /// Get memory statistics for a sender.
-    pub async fn get_memory_stats(&self, sender_id: &str) -> Result<(i64, i64, i64), OmegaError> {
+    async fn get_memory_stats(&self, sender_id: &str) -> Result<(i64, i64, i64), OmegaError> {
         let (conv_count,): (i64,) =
             sqlx::query_as("SELECT COUNT(*) FROM conversations WHERE sender_id = ?")
                 .bind(sender_id)
@@ -0,0 +1,70 @@
+//! Encryption at rest for message content, conversation summaries and fact values.
+//!
+//! Encrypted columns hold `enc:v1:` values sealed by [`Cipher`]; rows written
+//! before encryption was enabled stay readable until `rekey` rewrites
+//! them. The full-text index only ever sees ciphertext, so recall search is
+//! unavailable while encryption is on.
+
+use super::SqliteBackend;
+use omega_core::{crypto::Cipher, error::OmegaError};
+
+/// Columns that hold user content: `(table, column)`.
+const ENCRYPTED_COLUMNS: &[(&str, &str)] = &[
+    ("messages", "content"),
+    ("facts", "value"),
+    ("conversations", "summary"),
+    ("conversation_snapshots", "messages"),
+];
+
+impl SqliteBackend {
+    /// Re-encrypt every stored value with `new` (`None` = decrypt to plaintext).
+    ///
+    /// Values are read with the current cipher, so plaintext rows are
+    /// encrypted too. Runs in one transaction, then vacuums so the old bytes
+    /// do not linger in free pages. Returns the number of rows rewritten.
+    pub(super) async fn reseal(&self, new: Option<&Cipher>) -> Result<u64, OmegaError> {
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("rekey failed: {e}"));
+        let mut tx = self.pool.begin().await.map_err(err)?;
+        let mut rewritten = 0;
+
+        for (table, column) in ENCRYPTED_COLUMNS {
+            let rows: Vec<(i64, String)> = sqlx::query_as(&format!(
+                "SELECT rowid, {column} FROM {table} WHERE {column} IS NOT NULL AND {column} != ''"
+            ))
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(err)?;
+            for (rowid, stored) in rows {
+                let plain = self.sealer.open(stored.clone())?;
+                let sealed = match new {
+                    Some(cipher) => cipher.encrypt(&plain),
+                    None => plain,
+                };
+                if sealed == stored {
+                    continue;
+                }
+                sqlx::query(&format!("UPDATE {table} SET {column} = ? WHERE rowid = ?"))
+                    .bind(&sealed)
+                    .bind(rowid)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(err)?;
+                rewritten += 1;
+            }
+        }
+        tx.commit().await.map_err(err)?;
+
+        if rewritten > 0 {
+            // Purge old index entries, then drop the freed pages.
+            sqlx::query("INSERT INTO messages_fts(messages_fts) VALUES('optimize')")
+                .execute(&self.pool)
+                .await
+                .map_err(err)?;
+            sqlx::query("VACUUM")
+                .execute(&self.pool)
+                .await
+                .map_err(err)?;
+        }
+        Ok(rewritten)
+    }
+}
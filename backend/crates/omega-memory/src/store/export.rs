@@ -0,0 +1,761 @@
+//! Per-user memory export and import — backups, GDPR requests, server moves.
+//!
+//! An export is one versioned JSON document holding everything stored under a
+//! canonical sender id. Row ids are not exported: import assigns fresh ids,
+//! remaps message → conversation links and skips rows the target already has.
+
+use super::Store;
+use omega_core::error::OmegaError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Format version written by [`Store::export_sender`].
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Everything Omega remembers about one person.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryExport {
+    pub version: u32,
+    /// When the export was taken (UTC, RFC 3339).
+    pub exported_at: String,
+    /// Canonical sender id the data was stored under.
+    pub sender_id: String,
+    /// Other platform ids aliased to `sender_id`.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub facts: Vec<ExportedFact>,
+    #[serde(default)]
+    pub conversations: Vec<ExportedConversation>,
+    #[serde(default)]
+    pub tasks: Vec<ExportedTask>,
+    #[serde(default)]
+    pub outcomes: Vec<ExportedOutcome>,
+    #[serde(default)]
+    pub lessons: Vec<ExportedLesson>,
+    #[serde(default)]
+    pub sessions: Vec<ExportedSession>,
+    #[serde(default)]
+    pub heartbeat: Option<ExportedHeartbeatSettings>,
+    #[serde(default)]
+    pub heartbeat_items: Vec<ExportedHeartbeatItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedFact {
+    pub key: String,
+    pub value: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A conversation with its summary and messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedConversation {
+    pub channel: String,
+    pub project: String,
+    pub status: String,
+    pub summary: Option<String>,
+    pub started_at: String,
+    pub updated_at: String,
+    pub last_activity: String,
+    pub messages: Vec<ExportedMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedMessage {
+    pub role: String,
+    pub content: String,
+    pub timestamp: String,
+    pub metadata_json: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedTask {
+    pub channel: String,
+    pub reply_target: String,
+    pub description: String,
+    pub due_at: String,
+    pub repeat: Option<String>,
+    pub status: String,
+    pub task_type: String,
+    pub project: String,
+    pub retry_count: i64,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedOutcome {
+    pub timestamp: String,
+    pub domain: String,
+    pub score: i64,
+    pub lesson: String,
+    pub source: String,
+    pub project: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedLesson {
+    pub domain: String,
+    pub rule: String,
+    pub project: String,
+    pub occurrences: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedSession {
+    pub channel: String,
+    pub project: String,
+    pub session_id: String,
+    pub parent_project: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedHeartbeatSettings {
+    pub channel: String,
+    pub reply_target: String,
+    pub enabled: bool,
+    pub interval_minutes: i64,
+    pub active_start: String,
+    pub active_end: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedHeartbeatItem {
+    pub project: String,
+    pub description: String,
+    pub cadence: String,
+    pub alert_on_change: bool,
+    pub last_checked_at: Option<String>,
+    pub last_result: Option<String>,
+    pub created_at: String,
+}
+
+/// What [`Store::import_sender`] added; rows the target already had are skipped.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub facts: u64,
+    pub conversations: u64,
+    pub messages: u64,
+    pub tasks: u64,
+    pub outcomes: u64,
+    pub lessons: u64,
+    pub sessions: u64,
+    pub heartbeat_items: u64,
+    pub aliases: u64,
+    /// Rows already present under the target id.
+    pub skipped: u64,
+}
+
+type ConversationRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+    String,
+);
+
+type TaskRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+    String,
+    i64,
+    Option<String>,
+    String,
+    Option<String>,
+);
+
+type HeartbeatItemRow = (
+    String,
+    String,
+    String,
+    bool,
+    Option<String>,
+    Option<String>,
+    String,
+);
+
+impl Store {
+    /// Collect everything stored under `sender_id` into a [`MemoryExport`].
+    pub async fn export_sender(&self, sender_id: &str) -> Result<MemoryExport, OmegaError> {
+        let db = self.sqlite()?;
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("export failed: {e}"));
+
+        let facts: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT key, value, created_at, updated_at FROM facts \
+             WHERE sender_id = ? ORDER BY key",
+        )
+        .bind(sender_id)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(err)?;
+
+        let conversation_rows: Vec<ConversationRow> = sqlx::query_as(
+            "SELECT id, channel, project, status, summary, started_at, updated_at, last_activity \
+             FROM conversations WHERE sender_id = ? ORDER BY started_at",
+        )
+        .bind(sender_id)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(err)?;
+        let mut conversations = Vec::with_capacity(conversation_rows.len());
+        for (id, channel, project, status, summary, started_at, updated_at, last_activity) in
+            conversation_rows
+        {
+            let messages: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+                "SELECT role, content, timestamp, metadata_json FROM messages \
+                 WHERE conversation_id = ? ORDER BY timestamp, rowid",
+            )
+            .bind(&id)
+            .fetch_all(&db.pool)
+            .await
+            .map_err(err)?;
+            conversations.push(ExportedConversation {
+                channel,
+                project,
+                status,
+                summary: summary.map(|s| db.sealer.open(s)).transpose()?,
+                started_at,
+                updated_at,
+                last_activity,
+                messages: messages
+                    .into_iter()
+                    .map(|(role, content, timestamp, metadata_json)| {
+                        Ok(ExportedMessage {
+                            role,
+                            content: db.sealer.open(content)?,
+                            timestamp,
+                            metadata_json,
+                        })
+                    })
+                    .collect::<Result<_, OmegaError>>()?,
+            });
+        }
+
+        let tasks: Vec<TaskRow> = sqlx::query_as(
+            "SELECT channel, reply_target, description, due_at, repeat, status, task_type, \
+             project, retry_count, last_error, created_at, delivered_at \
+             FROM scheduled_tasks WHERE sender_id = ? ORDER BY created_at",
+        )
+        .bind(sender_id)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(err)?;
+
+        let outcomes: Vec<(String, String, i64, String, String, String)> = sqlx::query_as(
+            "SELECT timestamp, domain, score, lesson, source, project FROM outcomes \
+             WHERE sender_id = ? ORDER BY timestamp",
+        )
+        .bind(sender_id)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(err)?;
+
+        let lessons: Vec<(String, String, String, i64, String, String)> = sqlx::query_as(
+            "SELECT domain, rule, project, occurrences, created_at, updated_at FROM lessons \
+             WHERE sender_id = ? ORDER BY created_at",
+        )
+        .bind(sender_id)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(err)?;
+
+        let sessions: Vec<(String, String, String, Option<String>, String, String)> =
+            sqlx::query_as(
+                "SELECT channel, project, session_id, parent_project, created_at, updated_at \
+                 FROM project_sessions WHERE sender_id = ? ORDER BY created_at",
+            )
+            .bind(sender_id)
+            .fetch_all(&db.pool)
+            .await
+            .map_err(err)?;
+
+        let heartbeat: Option<(String, String, bool, i64, String, String)> = sqlx::query_as(
+            "SELECT channel, reply_target, enabled, interval_minutes, active_start, active_end \
+             FROM heartbeat_settings WHERE sender_id = ?",
+        )
+        .bind(sender_id)
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(err)?;
+
+        let heartbeat_items: Vec<HeartbeatItemRow> = sqlx::query_as(
+            "SELECT project, description, cadence, alert_on_change, last_checked_at, \
+             last_result, created_at FROM heartbeat_items \
+             WHERE sender_id = ? ORDER BY created_at",
+        )
+        .bind(sender_id)
+        .fetch_all(&db.pool)
+        .await
+        .map_err(err)?;
+
+        Ok(MemoryExport {
+            version: EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            sender_id: sender_id.to_string(),
+            aliases: self.get_aliases(sender_id).await?,
+            facts: facts
+                .into_iter()
+                .map(|(key, value, created_at, updated_at)| {
+                    Ok(ExportedFact {
+                        key,
+                        value: db.sealer.open(value)?,
+                        created_at,
+                        updated_at,
+                    })
+                })
+                .collect::<Result<_, OmegaError>>()?,
+            conversations,
+            tasks: tasks
+                .into_iter()
+                .map(
+                    |(
+                        channel,
+                        reply_target,
+                        description,
+                        due_at,
+                        repeat,
+                        status,
+                        task_type,
+                        project,
+                        retry_count,
+                        last_error,
+                        created_at,
+                        delivered_at,
+                    )| ExportedTask {
+                        channel,
+                        reply_target,
+                        description,
+                        due_at,
+                        repeat,
+                        status,
+                        task_type,
+                        project,
+                        retry_count,
+                        last_error,
+                        created_at,
+                        delivered_at,
+                    },
+                )
+                .collect(),
+            outcomes: outcomes
+                .into_iter()
+                .map(
+                    |(timestamp, domain, score, lesson, source, project)| ExportedOutcome {
+                        timestamp,
+                        domain,
+                        score,
+                        lesson,
+                        source,
+                        project,
+                    },
+                )
+                .collect(),
+            lessons: lessons
+                .into_iter()
+                .map(
+                    |(domain, rule, project, occurrences, created_at, updated_at)| ExportedLesson {
+                        domain,
+                        rule,
+                        project,
+                        occurrences,
+                        created_at,
+                        updated_at,
+                    },
+                )
+                .collect(),
+            sessions: sessions
+                .into_iter()
+                .map(
+                    |(channel, project, session_id, parent_project, created_at, updated_at)| {
+                        ExportedSession {
+                            channel,
+                            project,
+                            session_id,
+                            parent_project,
+                            created_at,
+                            updated_at,
+                        }
+                    },
+                )
+                .collect(),
+            heartbeat: heartbeat.map(
+                |(channel, reply_target, enabled, interval_minutes, active_start, active_end)| {
+                    ExportedHeartbeatSettings {
+                        channel,
+                        reply_target,
+                        enabled,
+                        interval_minutes,
+                        active_start,
+                        active_end,
+                    }
+                },
+            ),
+            heartbeat_items: heartbeat_items
+                .into_iter()
+                .map(
+                    |(
+                        project,
+                        description,
+                        cadence,
+                        alert_on_change,
+                        last_checked_at,
+                        last_result,
+                        created_at,
+                    )| ExportedHeartbeatItem {
+                        project,
+                        description,
+                        cadence,
+                        alert_on_change,
+                        last_checked_at,
+                        last_result,
+                        created_at,
+                    },
+                )
+                .collect(),
+        })
+    }
+
+    /// Import an export under `target` (default: the exported sender id).
+    ///
+    /// Runs in one transaction. Every row gets a fresh id; rows the target
+    /// already has (same fact key, conversation start, task description and
+    /// due time, lesson rule, ...) are skipped, so importing twice is a no-op.
+    /// An imported active conversation that collides with one of the target's
+    /// is imported as closed.
+    pub async fn import_sender(
+        &self,
+        export: &MemoryExport,
+        target: Option<&str>,
+    ) -> Result<ImportReport, OmegaError> {
+        let db = self.sqlite()?;
+        if export.version == 0 || export.version > EXPORT_VERSION {
+            return Err(OmegaError::Memory(format!(
+                "unsupported export version {} (this build reads up to {EXPORT_VERSION})",
+                export.version
+            )));
+        }
+        let target = target.unwrap_or(&export.sender_id);
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("import failed: {e}"));
+        let mut tx = db.pool.begin().await.map_err(err)?;
+        let mut report = ImportReport::default();
+
+        for fact in &export.facts {
+            let added = sqlx::query(
+                "INSERT OR IGNORE INTO facts (id, sender_id, key, value, created_at, updated_at) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(target)
+            .bind(&fact.key)
+            .bind(db.sealer.seal(&fact.value))
+            .bind(&fact.created_at)
+            .bind(&fact.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+            count(&mut report.facts, &mut report.skipped, added > 0);
+        }
+
+        for conv in &export.conversations {
+            let exists: Option<(String,)> = sqlx::query_as(
+                "SELECT id FROM conversations \
+                 WHERE sender_id = ? AND channel = ? AND project = ? AND started_at = ?",
+            )
+            .bind(target)
+            .bind(&conv.channel)
+            .bind(&conv.project)
+            .bind(&conv.started_at)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(err)?;
+            if exists.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            let collides: Option<(String,)> = if conv.status == "active" {
+                sqlx::query_as(
+                    "SELECT id FROM conversations WHERE sender_id = ? AND channel = ? \
+                     AND project = ? AND status = 'active'",
+                )
+                .bind(target)
+                .bind(&conv.channel)
+                .bind(&conv.project)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(err)?
+            } else {
+                None
+            };
+            let status = if collides.is_some() {
+                "closed"
+            } else {
+                conv.status.as_str()
+            };
+            let conv_id = Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO conversations (id, channel, sender_id, project, status, summary, \
+                 started_at, updated_at, last_activity) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&conv_id)
+            .bind(&conv.channel)
+            .bind(target)
+            .bind(&conv.project)
+            .bind(status)
+            .bind(conv.summary.as_deref().map(|s| db.sealer.seal(s)))
+            .bind(&conv.started_at)
+            .bind(&conv.updated_at)
+            .bind(&conv.last_activity)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            report.conversations += 1;
+            for msg in &conv.messages {
+                sqlx::query(
+                    "INSERT INTO messages (id, conversation_id, role, content, timestamp, \
+                     metadata_json) VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&conv_id)
+                .bind(&msg.role)
+                .bind(db.sealer.seal(&msg.content))
+                .bind(&msg.timestamp)
+                .bind(&msg.metadata_json)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?;
+                report.messages += 1;
+            }
+        }
+
+        for task in &export.tasks {
+            let exists: Option<(String,)> = sqlx::query_as(
+                "SELECT id FROM scheduled_tasks \
+                 WHERE sender_id = ? AND description = ? AND due_at = ?",
+            )
+            .bind(target)
+            .bind(&task.description)
+            .bind(&task.due_at)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(err)?;
+            if exists.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO scheduled_tasks (id, channel, sender_id, reply_target, description, \
+                 due_at, repeat, status, task_type, project, retry_count, last_error, \
+                 created_at, delivered_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&task.channel)
+            .bind(target)
+            .bind(&task.reply_target)
+            .bind(&task.description)
+            .bind(&task.due_at)
+            .bind(&task.repeat)
+            .bind(&task.status)
+            .bind(&task.task_type)
+            .bind(&task.project)
+            .bind(task.retry_count)
+            .bind(&task.last_error)
+            .bind(&task.created_at)
+            .bind(&task.delivered_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            report.tasks += 1;
+        }
+
+        for outcome in &export.outcomes {
+            let exists: Option<(String,)> = sqlx::query_as(
+                "SELECT id FROM outcomes WHERE sender_id = ? AND timestamp = ? \
+                 AND domain = ? AND lesson = ?",
+            )
+            .bind(target)
+            .bind(&outcome.timestamp)
+            .bind(&outcome.domain)
+            .bind(&outcome.lesson)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(err)?;
+            if exists.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO outcomes (id, timestamp, sender_id, domain, score, lesson, source, \
+                 project) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&outcome.timestamp)
+            .bind(target)
+            .bind(&outcome.domain)
+            .bind(outcome.score)
+            .bind(&outcome.lesson)
+            .bind(&outcome.source)
+            .bind(&outcome.project)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            report.outcomes += 1;
+        }
+
+        for lesson in &export.lessons {
+            let exists: Option<(String,)> = sqlx::query_as(
+                "SELECT id FROM lessons \
+                 WHERE sender_id = ? AND domain = ? AND project = ? AND rule = ?",
+            )
+            .bind(target)
+            .bind(&lesson.domain)
+            .bind(&lesson.project)
+            .bind(&lesson.rule)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(err)?;
+            if exists.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO lessons (id, sender_id, domain, rule, project, occurrences, \
+                 created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(target)
+            .bind(&lesson.domain)
+            .bind(&lesson.rule)
+            .bind(&lesson.project)
+            .bind(lesson.occurrences)
+            .bind(&lesson.created_at)
+            .bind(&lesson.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            report.lessons += 1;
+        }
+
+        for session in &export.sessions {
+            let added = sqlx::query(
+                "INSERT OR IGNORE INTO project_sessions (id, channel, sender_id, project, \
+                 session_id, parent_project, created_at, updated_at) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&session.channel)
+            .bind(target)
+            .bind(&session.project)
+            .bind(&session.session_id)
+            .bind(&session.parent_project)
+            .bind(&session.created_at)
+            .bind(&session.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+            count(&mut report.sessions, &mut report.skipped, added > 0);
+        }
+
+        if let Some(hb) = &export.heartbeat {
+            sqlx::query(
+                "INSERT OR IGNORE INTO heartbeat_settings (sender_id, channel, reply_target, \
+                 enabled, interval_minutes, active_start, active_end) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(target)
+            .bind(&hb.channel)
+            .bind(&hb.reply_target)
+            .bind(hb.enabled)
+            .bind(hb.interval_minutes)
+            .bind(&hb.active_start)
+            .bind(&hb.active_end)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        }
+
+        for item in &export.heartbeat_items {
+            let exists: Option<(String,)> = sqlx::query_as(
+                "SELECT id FROM heartbeat_items \
+                 WHERE sender_id = ? AND project = ? AND description = ?",
+            )
+            .bind(target)
+            .bind(&item.project)
+            .bind(&item.description)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(err)?;
+            if exists.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO heartbeat_items (id, sender_id, project, description, cadence, \
+                 alert_on_change, last_checked_at, last_result, created_at) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(target)
+            .bind(&item.project)
+            .bind(&item.description)
+            .bind(&item.cadence)
+            .bind(item.alert_on_change)
+            .bind(&item.last_checked_at)
+            .bind(&item.last_result)
+            .bind(&item.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            report.heartbeat_items += 1;
+        }
+
+        // Aliases: the exported canonical id becomes one too when importing
+        // under a different target. Existing aliases are left alone.
+        let mut aliases: Vec<&str> = export.aliases.iter().map(String::as_str).collect();
+        aliases.push(&export.sender_id);
+        for alias in aliases {
+            if alias == target {
+                continue;
+            }
+            let added = sqlx::query(
+                "INSERT OR IGNORE INTO user_aliases (alias_sender_id, canonical_sender_id) \
+                 VALUES (?, ?)",
+            )
+            .bind(alias)
+            .bind(target)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+            count(&mut report.aliases, &mut report.skipped, added > 0);
+        }
+
+        tx.commit().await.map_err(err)?;
+        Ok(report)
+    }
+}
+
+/// Bump `added` or `skipped`.
+fn count(added: &mut u64, skipped: &mut u64, inserted: bool) {
+    if inserted {
+        *added += 1;
+    } else {
+        *skipped += 1;
+    }
+}
@@ -1,17 +1,15 @@
 //! User facts, cross-channel aliases, and limitations.
 
-use super::Store;
+use super::SqliteBackend;
+use crate::backend::FactStore;
+use async_trait::async_trait;
 use omega_core::error::OmegaError;
 use uuid::Uuid;
 
-impl Store {
+#[async_trait]
+impl FactStore for SqliteBackend {
     /// Store a fact (upsert by sender_id + key).
-    pub async fn store_fact(
-        &self,
-        sender_id: &str,
-        key: &str,
-        value: &str,
-    ) -> Result<(), OmegaError> {
+    async fn store_fact(&self, sender_id: &str, key: &str, value: &str) -> Result<(), OmegaError> {
         let id = Uuid::new_v4().to_string();
         sqlx::query(
             "INSERT INTO facts (id, sender_id, key, value) VALUES (?, ?, ?, ?) \
@@ -20,7 +18,7 @@ impl Store {
         .bind(&id)
         .bind(sender_id)
         .bind(key)
-        .bind(value)
+        .bind(self.sealer.seal(value))
         .execute(&self.pool)
         .await
         .map_err(|e| OmegaError::Memory(format!("upsert fact failed: {e}")))?;
@@ -29,7 +27,7 @@ impl Store {
     }
 
     /// Get a single fact by sender and key.
-    pub async fn get_fact(&self, sender_id: &str, key: &str) -> Result<Option<String>, OmegaError> {
+    async fn get_fact(&self, sender_id: &str, key: &str) -> Result<Option<String>, OmegaError> {
         let row: Option<(String,)> =
             sqlx::query_as("SELECT value FROM facts WHERE sender_id = ? AND key = ?")
                 .bind(sender_id)
@@ -38,11 +36,11 @@ impl Store {
                 .await
                 .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(row.map(|(v,)| v))
+        row.map(|(v,)| self.sealer.open(v)).transpose()
     }
 
     /// Delete a single fact by sender and key. Returns `true` if a row was deleted.
-    pub async fn delete_fact(&self, sender_id: &str, key: &str) -> Result<bool, OmegaError> {
+    async fn delete_fact(&self, sender_id: &str, key: &str) -> Result<bool, OmegaError> {
         let result = sqlx::query("DELETE FROM facts WHERE sender_id = ? AND key = ?")
             .bind(sender_id)
             .bind(key)
@@ -54,7 +52,7 @@ impl Store {
     }
 
     /// Get all facts for a sender.
-    pub async fn get_facts(&self, sender_id: &str) -> Result<Vec<(String, String)>, OmegaError> {
+    async fn get_facts(&self, sender_id: &str) -> Result<Vec<(String, String)>, OmegaError> {
         let rows: Vec<(String, String)> =
             sqlx::query_as("SELECT key, value FROM facts WHERE sender_id = ? ORDER BY key")
                 .bind(sender_id)
@@ -62,15 +60,11 @@ impl Store {
                 .await
                 .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(rows)
+        self.sealer.open_pairs(rows)
     }
 
     /// Delete facts for a sender — all facts if key is None, specific fact if key is Some.
-    pub async fn delete_facts(
-        &self,
-        sender_id: &str,
-        key: Option<&str>,
-    ) -> Result<u64, OmegaError> {
+    async fn delete_facts(&self, sender_id: &str, key: Option<&str>) -> Result<u64, OmegaError> {
         let result = if let Some(k) = key {
             sqlx::query("DELETE FROM facts WHERE sender_id = ? AND key = ?")
                 .bind(sender_id)
@@ -90,34 +84,31 @@ impl Store {
     }
 
     /// Get all facts across all users — for heartbeat context enrichment.
-    pub async fn get_all_facts(&self) -> Result<Vec<(String, String)>, OmegaError> {
+    async fn get_all_facts(&self) -> Result<Vec<(String, String)>, OmegaError> {
         let rows: Vec<(String, String)> =
             sqlx::query_as("SELECT key, value FROM facts WHERE key != 'welcomed' ORDER BY key")
                 .fetch_all(&self.pool)
                 .await
                 .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
 
-        Ok(rows)
+        self.sealer.open_pairs(rows)
     }
 
     /// Get all `(sender_id, value)` pairs for a given fact key across all users.
     ///
     /// Used to find all users with a specific fact (e.g., `active_project`).
-    pub async fn get_all_facts_by_key(
-        &self,
-        key: &str,
-    ) -> Result<Vec<(String, String)>, OmegaError> {
+    async fn get_all_facts_by_key(&self, key: &str) -> Result<Vec<(String, String)>, OmegaError> {
         let rows: Vec<(String, String)> =
             sqlx::query_as("SELECT sender_id, value FROM facts WHERE key = ? ORDER BY sender_id")
                 .bind(key)
                 .fetch_all(&self.pool)
                 .await
                 .map_err(|e| OmegaError::Memory(format!("get facts by key failed: {e}")))?;
-        Ok(rows)
+        self.sealer.open_pairs(rows)
     }
 
     /// Check if a sender has never been welcomed (no `welcomed` fact).
-    pub async fn is_new_user(&self, sender_id: &str) -> Result<bool, OmegaError> {
+    async fn is_new_user(&self, sender_id: &str) -> Result<bool, OmegaError> {
         let row: Option<(String,)> =
             sqlx::query_as("SELECT value FROM facts WHERE sender_id = ? AND key = 'welcomed'")
                 .bind(sender_id)
@@ -132,7 +123,7 @@ impl Store {
 
     /// Resolve a sender_id to its canonical form via the user_aliases table.
     /// Returns the canonical sender_id if an alias exists, otherwise the original.
-    pub async fn resolve_sender_id(&self, sender_id: &str) -> Result<String, OmegaError> {
+    async fn resolve_sender_id(&self, sender_id: &str) -> Result<String, OmegaError> {
         let row: Option<(String,)> = sqlx::query_as(
             "SELECT canonical_sender_id FROM user_aliases WHERE alias_sender_id = ?",
         )
@@ -145,7 +136,7 @@ impl Store {
     }
 
     /// Create an alias mapping: alias_id → canonical_id.
-    pub async fn create_alias(&self, alias_id: &str, canonical_id: &str) -> Result<(), OmegaError> {
+    async fn create_alias(&self, alias_id: &str, canonical_id: &str) -> Result<(), OmegaError> {
         sqlx::query(
             "INSERT OR IGNORE INTO user_aliases (alias_sender_id, canonical_sender_id) \
              VALUES (?, ?)",
@@ -159,9 +150,23 @@ impl Store {
         Ok(())
     }
 
+    /// All alias ids that resolve to `canonical_id`, sorted.
+    async fn get_aliases(&self, canonical_id: &str) -> Result<Vec<String>, OmegaError> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT alias_sender_id FROM user_aliases WHERE canonical_sender_id = ? \
+             ORDER BY alias_sender_id",
+        )
+        .bind(canonical_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get aliases failed: {e}")))?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
     /// Find an existing welcomed user different from `sender_id` and return their sender_id.
     /// Used to create cross-channel aliases (e.g., WhatsApp phone → Telegram ID).
-    pub async fn find_canonical_user(
+    async fn find_canonical_user(
         &self,
         exclude_sender_id: &str,
     ) -> Result<Option<String>, OmegaError> {
@@ -180,7 +185,7 @@ impl Store {
 
     /// Store a limitation (deduplicates by title, case-insensitive).
     /// Returns `true` if the limitation is new, `false` if it already existed.
-    pub async fn store_limitation(
+    async fn store_limitation(
         &self,
         title: &str,
         description: &str,
@@ -203,7 +208,7 @@ impl Store {
     }
 
     /// Get all open limitations: (title, description, proposed_plan).
-    pub async fn get_open_limitations(&self) -> Result<Vec<(String, String, String)>, OmegaError> {
+    async fn get_open_limitations(&self) -> Result<Vec<(String, String, String)>, OmegaError> {
         let rows: Vec<(String, String, String)> = sqlx::query_as(
             "SELECT title, description, proposed_plan FROM limitations \
              WHERE status = 'open' ORDER BY created_at ASC",
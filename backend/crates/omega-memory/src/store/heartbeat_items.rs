@@ -0,0 +1,264 @@
+//! Structured heartbeat checklist items — stable ids, cadence and last result.
+
+use super::SqliteBackend;
+use crate::backend::HeartbeatItemStore;
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+/// One monitored heartbeat item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeartbeatItem {
+    /// Unique item identifier (UUID; the first 8 chars are shown to users).
+    pub id: String,
+    /// Owner of the item (canonical sender id).
+    pub sender_id: String,
+    /// Project scope ("" = personal checklist).
+    pub project: String,
+    /// What to check (may span several lines).
+    pub description: String,
+    /// Normalized cadence ("" = every pulse, "hourly", "daily 09:00", "weekly mon 09:00").
+    pub cadence: String,
+    /// Only alert when the result differs from `last_result`.
+    pub alert_on_change: bool,
+    /// When the item was last included in a heartbeat run (UTC, SQLite format).
+    pub last_checked_at: Option<String>,
+    /// Short result reported by the last run (`HEARTBEAT_CHECKED:`).
+    pub last_result: Option<String>,
+    /// When the item was added (UTC, SQLite format).
+    pub created_at: String,
+}
+
+/// Fields needed to add an item; id and timestamps are assigned by the store.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NewHeartbeatItem {
+    pub description: String,
+    pub cadence: String,
+    pub alert_on_change: bool,
+}
+
+type ItemRow = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    bool,
+    Option<String>,
+    Option<String>,
+    String,
+);
+
+const ITEM_COLUMNS: &str = "id, sender_id, project, description, cadence, alert_on_change, \
+                            last_checked_at, last_result, created_at";
+
+fn from_row(row: ItemRow) -> HeartbeatItem {
+    let (
+        id,
+        sender_id,
+        project,
+        description,
+        cadence,
+        alert_on_change,
+        last_checked_at,
+        last_result,
+        created_at,
+    ) = row;
+    HeartbeatItem {
+        id,
+        sender_id,
+        project,
+        description,
+        cadence,
+        alert_on_change,
+        last_checked_at,
+        last_result,
+        created_at,
+    }
+}
+
+#[async_trait]
+impl HeartbeatItemStore for SqliteBackend {
+    /// Add an item to a user's checklist and return its id.
+    ///
+    /// Re-adding an existing description (case-insensitive, same project)
+    /// updates its cadence and alert flag instead of creating a duplicate.
+    async fn add_heartbeat_item(
+        &self,
+        sender_id: &str,
+        project: &str,
+        item: &NewHeartbeatItem,
+    ) -> Result<String, OmegaError> {
+        let existing: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM heartbeat_items \
+             WHERE sender_id = ? AND project = ? AND lower(description) = lower(?) LIMIT 1",
+        )
+        .bind(sender_id)
+        .bind(project)
+        .bind(&item.description)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("heartbeat item dedup check: {e}")))?;
+
+        if let Some((id,)) = existing {
+            sqlx::query(
+                "UPDATE heartbeat_items SET cadence = ?, alert_on_change = ?, \
+                 updated_at = datetime('now') WHERE id = ?",
+            )
+            .bind(&item.cadence)
+            .bind(item.alert_on_change)
+            .bind(&id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("update heartbeat item: {e}")))?;
+            return Ok(id);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO heartbeat_items \
+             (id, sender_id, project, description, cadence, alert_on_change) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(sender_id)
+        .bind(project)
+        .bind(&item.description)
+        .bind(&item.cadence)
+        .bind(item.alert_on_change)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("add heartbeat item: {e}")))?;
+
+        Ok(id)
+    }
+
+    /// Get a user's items for one project scope ("" = personal), oldest first.
+    async fn get_heartbeat_items(
+        &self,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<HeartbeatItem>, OmegaError> {
+        let rows: Vec<ItemRow> = sqlx::query_as(&format!(
+            "SELECT {ITEM_COLUMNS} FROM heartbeat_items \
+             WHERE sender_id = ? AND project = ? ORDER BY created_at, id"
+        ))
+        .bind(sender_id)
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get heartbeat items: {e}")))?;
+
+        Ok(rows.into_iter().map(from_row).collect())
+    }
+
+    /// Projects in which a user has at least one item, sorted by name.
+    async fn get_heartbeat_item_projects(
+        &self,
+        sender_id: &str,
+    ) -> Result<Vec<String>, OmegaError> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT DISTINCT project FROM heartbeat_items \
+             WHERE sender_id = ? AND project != '' ORDER BY project",
+        )
+        .bind(sender_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get heartbeat item projects: {e}")))?;
+
+        Ok(rows.into_iter().map(|(p,)| p).collect())
+    }
+
+    /// Remove one of the user's items by id prefix.
+    ///
+    /// Returns false when no item — or more than one — matches the prefix.
+    async fn remove_heartbeat_item(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+    ) -> Result<bool, OmegaError> {
+        let Some(id) = self.resolve_heartbeat_item(sender_id, id_prefix).await? else {
+            return Ok(false);
+        };
+        sqlx::query("DELETE FROM heartbeat_items WHERE id = ?")
+            .bind(&id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("remove heartbeat item: {e}")))?;
+        Ok(true)
+    }
+
+    /// Stamp `last_checked_at = now` on items that were just run.
+    async fn mark_heartbeat_items_checked(&self, ids: &[String]) -> Result<(), OmegaError> {
+        for id in ids {
+            sqlx::query(
+                "UPDATE heartbeat_items SET last_checked_at = datetime('now') WHERE id = ?",
+            )
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("mark heartbeat item checked: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// Record the result reported for one of the user's items.
+    ///
+    /// Returns `None` when the id prefix matches no single item, otherwise
+    /// `Some(changed)` — true when the result differs from the previous one
+    /// (the first result always counts as a change).
+    async fn record_heartbeat_result(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+        result: &str,
+    ) -> Result<Option<bool>, OmegaError> {
+        let Some(id) = self.resolve_heartbeat_item(sender_id, id_prefix).await? else {
+            return Ok(None);
+        };
+        let (previous,): (Option<String>,) =
+            sqlx::query_as("SELECT last_result FROM heartbeat_items WHERE id = ?")
+                .bind(&id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| OmegaError::Memory(format!("get heartbeat result: {e}")))?;
+
+        sqlx::query(
+            "UPDATE heartbeat_items SET last_result = ?, last_checked_at = datetime('now'), \
+             updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(result)
+        .bind(&id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("record heartbeat result: {e}")))?;
+
+        Ok(Some(previous.as_deref() != Some(result)))
+    }
+}
+
+impl SqliteBackend {
+    /// Resolve an id prefix to exactly one of the user's items.
+    async fn resolve_heartbeat_item(
+        &self,
+        sender_id: &str,
+        id_prefix: &str,
+    ) -> Result<Option<String>, OmegaError> {
+        if id_prefix.is_empty() {
+            return Ok(None);
+        }
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT id FROM heartbeat_items WHERE sender_id = ? AND id LIKE ? LIMIT 2",
+        )
+        .bind(sender_id)
+        .bind(format!("{id_prefix}%"))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("resolve heartbeat item: {e}")))?;
+
+        Ok(match rows.as_slice() {
+            [(id,)] => Some(id.clone()),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,152 @@
+//! Per-user heartbeat settings — pulse, active hours, delivery target.
+//!
+//! The checklist itself lives in `heartbeat_items` (see `heartbeat_items.rs`).
+
+use super::SqliteBackend;
+use crate::backend::HeartbeatStore;
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+
+/// One user's heartbeat configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeartbeatSettings {
+    /// Owner of the heartbeat (canonical sender id).
+    pub sender_id: String,
+    /// Channel that receives heartbeat alerts (e.g. "telegram").
+    pub channel: String,
+    /// Platform-specific delivery target (e.g. chat_id).
+    pub reply_target: String,
+    /// Whether the heartbeat runs for this user.
+    pub enabled: bool,
+    /// Pulse in minutes (1–1440), aligned to clock boundaries.
+    pub interval_minutes: u64,
+    /// Active hours start (e.g. "08:00"). Empty = always active.
+    pub active_start: String,
+    /// Active hours end (e.g. "22:00"). Empty = always active.
+    pub active_end: String,
+}
+
+type SettingsRow = (String, String, String, bool, i64, String, String);
+
+const SETTINGS_COLUMNS: &str = "sender_id, channel, reply_target, enabled, interval_minutes, \
+                                active_start, active_end";
+
+fn from_row(row: SettingsRow) -> HeartbeatSettings {
+    let (sender_id, channel, reply_target, enabled, interval, active_start, active_end) = row;
+    HeartbeatSettings {
+        sender_id,
+        channel,
+        reply_target,
+        enabled,
+        interval_minutes: interval.clamp(1, 1440) as u64,
+        active_start,
+        active_end,
+    }
+}
+
+#[async_trait]
+impl HeartbeatStore for SqliteBackend {
+    /// Get a user's heartbeat settings, if they have any.
+    async fn get_heartbeat_settings(
+        &self,
+        sender_id: &str,
+    ) -> Result<Option<HeartbeatSettings>, OmegaError> {
+        let row: Option<SettingsRow> = sqlx::query_as(&format!(
+            "SELECT {SETTINGS_COLUMNS} FROM heartbeat_settings WHERE sender_id = ?"
+        ))
+        .bind(sender_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get heartbeat settings: {e}")))?;
+
+        Ok(row.map(from_row))
+    }
+
+    /// Get every enabled heartbeat, ordered by sender for deterministic runs.
+    async fn get_enabled_heartbeats(&self) -> Result<Vec<HeartbeatSettings>, OmegaError> {
+        let rows: Vec<SettingsRow> = sqlx::query_as(&format!(
+            "SELECT {SETTINGS_COLUMNS} FROM heartbeat_settings \
+             WHERE enabled = 1 ORDER BY sender_id"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get enabled heartbeats: {e}")))?;
+
+        Ok(rows.into_iter().map(from_row).collect())
+    }
+
+    /// Insert or fully replace a user's heartbeat settings.
+    async fn save_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<(), OmegaError> {
+        sqlx::query(&format!(
+            "INSERT INTO heartbeat_settings ({SETTINGS_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(sender_id) DO UPDATE SET \
+             channel = excluded.channel, reply_target = excluded.reply_target, \
+             enabled = excluded.enabled, interval_minutes = excluded.interval_minutes, \
+             active_start = excluded.active_start, active_end = excluded.active_end, \
+             updated_at = datetime('now')"
+        ))
+        .bind(&settings.sender_id)
+        .bind(&settings.channel)
+        .bind(&settings.reply_target)
+        .bind(settings.enabled)
+        .bind(settings.interval_minutes as i64)
+        .bind(&settings.active_start)
+        .bind(&settings.active_end)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("save heartbeat settings: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Insert settings only when the user has none yet. Returns true if inserted.
+    ///
+    /// Used to seed the owner's heartbeat from `[heartbeat]` in config.toml.
+    async fn seed_heartbeat_settings(
+        &self,
+        settings: &HeartbeatSettings,
+    ) -> Result<bool, OmegaError> {
+        let result = sqlx::query(&format!(
+            "INSERT OR IGNORE INTO heartbeat_settings ({SETTINGS_COLUMNS}) \
+             VALUES (?, ?, ?, ?, ?, ?, ?)"
+        ))
+        .bind(&settings.sender_id)
+        .bind(&settings.channel)
+        .bind(&settings.reply_target)
+        .bind(settings.enabled)
+        .bind(settings.interval_minutes as i64)
+        .bind(&settings.active_start)
+        .bind(&settings.active_end)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("seed heartbeat settings: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Free-form markdown checklists saved before structured items existed.
+    ///
+    /// Returns `(sender_id, checklist)` for every row that still has one.
+    async fn get_legacy_heartbeat_checklists(&self) -> Result<Vec<(String, String)>, OmegaError> {
+        sqlx::query_as(
+            "SELECT sender_id, checklist FROM heartbeat_settings \
+             WHERE checklist != '' ORDER BY sender_id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("get legacy checklists: {e}")))
+    }
+
+    /// Clear a user's legacy checklist once it has been converted to items.
+    async fn clear_legacy_heartbeat_checklist(&self, sender_id: &str) -> Result<(), OmegaError> {
+        sqlx::query("UPDATE heartbeat_settings SET checklist = '' WHERE sender_id = ?")
+            .bind(sender_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("clear legacy checklist: {e}")))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//! Cross-channel identity linking — merging one sender's data into another.
+
+use super::SqliteBackend;
+use crate::backend::IdentityStore;
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+
+/// A fact both identities had, with different values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactConflict {
+    pub key: String,
+    pub kept: String,
+    pub discarded: String,
+}
+
+/// What [`Store::merge_sender`] moved into the canonical id.
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    pub facts: u64,
+    /// Facts that differed; the most recently updated value was kept.
+    pub conflicts: Vec<FactConflict>,
+    pub conversations: u64,
+    pub tasks: u64,
+    pub lessons: u64,
+}
+
+/// Tables whose rows move to the canonical id as-is (besides the counted ones).
+const MOVED_TABLES: [&str; 2] = ["outcomes", "heartbeat_items"];
+
+/// Tables with a per-sender unique key: the canonical id's row wins.
+const KEEP_EXISTING_TABLES: [&str; 3] = [
+    "project_sessions",
+    "conversation_snapshots",
+    "heartbeat_settings",
+];
+
+#[async_trait]
+impl IdentityStore for SqliteBackend {
+    /// Merge everything stored under `from` into `into` and alias `from`
+    /// (and the ids already aliased to it) to `into`.
+    ///
+    /// Facts present under both ids with different values keep the most
+    /// recently updated one. `from`'s active conversations that collide with
+    /// one of `into`'s (same channel and project) are closed first.
+    async fn merge_sender(&self, from: &str, into: &str) -> Result<MergeReport, OmegaError> {
+        if from == into {
+            return Err(OmegaError::Memory(
+                "cannot merge a sender into itself".into(),
+            ));
+        }
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("merge sender failed: {e}"));
+        let mut tx = self.pool.begin().await.map_err(err)?;
+        let mut report = MergeReport::default();
+
+        // Facts: move, drop duplicates, resolve conflicts by recency.
+        let theirs: Vec<(String, String, String)> =
+            sqlx::query_as("SELECT key, value, updated_at FROM facts WHERE sender_id = ?")
+                .bind(from)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(err)?;
+        for (key, value, updated_at) in theirs {
+            let ours: Option<(String, String)> = sqlx::query_as(
+                "SELECT value, updated_at FROM facts WHERE sender_id = ? AND key = ?",
+            )
+            .bind(into)
+            .bind(&key)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(err)?;
+            match ours {
+                None => {
+                    sqlx::query("UPDATE facts SET sender_id = ? WHERE sender_id = ? AND key = ?")
+                        .bind(into)
+                        .bind(from)
+                        .bind(&key)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(err)?;
+                    report.facts += 1;
+                    continue;
+                }
+                Some((our_value, _)) if our_value == value => {}
+                Some((our_value, our_updated_at)) => {
+                    let theirs_newer = updated_at > our_updated_at;
+                    if theirs_newer {
+                        sqlx::query(
+                            "UPDATE facts SET value = ?, updated_at = datetime('now') \
+                             WHERE sender_id = ? AND key = ?",
+                        )
+                        .bind(&value)
+                        .bind(into)
+                        .bind(&key)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(err)?;
+                    }
+                    let (kept, discarded) = if theirs_newer {
+                        (value, our_value)
+                    } else {
+                        (our_value, value)
+                    };
+                    report.conflicts.push(FactConflict {
+                        key: key.clone(),
+                        kept: self.sealer.open(kept)?,
+                        discarded: self.sealer.open(discarded)?,
+                    });
+                }
+            }
+            sqlx::query("DELETE FROM facts WHERE sender_id = ? AND key = ?")
+                .bind(from)
+                .bind(&key)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?;
+        }
+
+        // Conversations: one active conversation per (channel, sender, project).
+        sqlx::query(
+            "UPDATE conversations SET status = 'closed', updated_at = datetime('now') \
+             WHERE sender_id = ? AND status = 'active' AND EXISTS ( \
+                 SELECT 1 FROM conversations c WHERE c.sender_id = ? AND c.status = 'active' \
+                 AND c.channel = conversations.channel AND c.project = conversations.project)",
+        )
+        .bind(from)
+        .bind(into)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+        report.conversations =
+            sqlx::query("UPDATE conversations SET sender_id = ? WHERE sender_id = ?")
+                .bind(into)
+                .bind(from)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?
+                .rows_affected();
+
+        report.lessons = sqlx::query("UPDATE lessons SET sender_id = ? WHERE sender_id = ?")
+            .bind(into)
+            .bind(from)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+        report.tasks = sqlx::query("UPDATE scheduled_tasks SET sender_id = ? WHERE sender_id = ?")
+            .bind(into)
+            .bind(from)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+
+        for table in MOVED_TABLES {
+            sqlx::query(&format!(
+                "UPDATE {table} SET sender_id = ? WHERE sender_id = ?"
+            ))
+            .bind(into)
+            .bind(from)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        }
+
+        for table in KEEP_EXISTING_TABLES {
+            sqlx::query(&format!(
+                "UPDATE OR IGNORE {table} SET sender_id = ? WHERE sender_id = ?"
+            ))
+            .bind(into)
+            .bind(from)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            sqlx::query(&format!("DELETE FROM {table} WHERE sender_id = ?"))
+                .bind(from)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?;
+        }
+
+        // Aliases: everything that pointed at `from` now points at `into`.
+        sqlx::query(
+            "UPDATE user_aliases SET canonical_sender_id = ? WHERE canonical_sender_id = ?",
+        )
+        .bind(into)
+        .bind(from)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+        sqlx::query(
+            "INSERT OR REPLACE INTO user_aliases (alias_sender_id, canonical_sender_id) \
+             VALUES (?, ?)",
+        )
+        .bind(from)
+        .bind(into)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+        sqlx::query("DELETE FROM user_aliases WHERE alias_sender_id = canonical_sender_id")
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+
+        tx.commit().await.map_err(err)?;
+        Ok(report)
+    }
+
+    /// Detach an alias so the id becomes its own sender again.
+    ///
+    /// Data merged earlier stays with the canonical id. Returns `true` if the
+    /// alias existed.
+    async fn remove_alias(&self, alias_id: &str) -> Result<bool, OmegaError> {
+        let result = sqlx::query("DELETE FROM user_aliases WHERE alias_sender_id = ?")
+            .bind(alias_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("remove alias failed: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,360 @@
+//! Database maintenance — snapshots, retention, compaction and integrity checks.
+
+use super::Store;
+use libsqlite3_sys as ffi;
+use omega_core::{config::MaintenanceConfig, error::OmegaError};
+use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use uuid::Uuid;
+
+/// Snapshot files are `memory-<YYYYMMDD-HHMMSS>.db`, so names sort by age.
+const SNAPSHOT_PREFIX: &str = "memory-";
+const SNAPSHOT_EXT: &str = ".db";
+
+/// Rows deleted by [`Store::prune`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PruneReport {
+    pub audit: u64,
+    pub outcomes: u64,
+    pub messages: u64,
+}
+
+/// One finished maintenance run, as recorded in `maintenance_runs`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MaintenanceRun {
+    /// When the run finished (UTC, SQLite format).
+    pub finished_at: String,
+    /// Snapshot written by the run, if any.
+    pub snapshot: Option<String>,
+    pub pruned: PruneReport,
+    pub reclaimed_bytes: u64,
+    /// Problems reported by the integrity check (empty = healthy).
+    pub integrity: Vec<String>,
+}
+
+type RunRow = (String, Option<String>, i64, i64, i64, i64, String);
+
+impl Store {
+    /// Write a copy of the live database to `path` with SQLite's online
+    /// backup API.
+    ///
+    /// Safe while the gateway is writing. Fails if `path` already exists.
+    /// Returns the size of the copy in bytes.
+    pub async fn backup_to(&self, path: &Path) -> Result<u64, OmegaError> {
+        let db = self.sqlite()?;
+        if path.exists() {
+            return Err(OmegaError::Memory(format!(
+                "backup target {} already exists",
+                path.display()
+            )));
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| OmegaError::Memory(format!("failed to create backup dir: {e}")))?;
+        }
+        let mut conn = db
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| OmegaError::Memory(format!("backup failed: {e}")))?;
+        let mut handle = conn
+            .lock_handle()
+            .await
+            .map_err(|e| OmegaError::Memory(format!("backup failed: {e}")))?;
+        if let Err(e) = online_backup(handle.as_raw_handle().as_ptr(), path) {
+            let _ = std::fs::remove_file(path);
+            return Err(OmegaError::Memory(format!("backup failed: {e}")));
+        }
+
+        std::fs::metadata(path)
+            .map(|m| m.len())
+            .map_err(|e| OmegaError::Memory(format!("backup not written: {e}")))
+    }
+
+    /// Take a timestamped snapshot in `dir` and keep only the newest `keep`.
+    pub async fn snapshot(&self, dir: &Path, keep: usize) -> Result<PathBuf, OmegaError> {
+        let name = format!(
+            "{SNAPSHOT_PREFIX}{}{SNAPSHOT_EXT}",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = dir.join(name);
+        self.backup_to(&path).await?;
+        rotate_snapshots(dir, keep)?;
+        Ok(path)
+    }
+
+    /// Apply the retention windows in `cfg` (`0` days = keep forever).
+    ///
+    /// Raw messages are only deleted from closed conversations that have a
+    /// summary, so the summary stays as the record of the conversation.
+    pub async fn prune(&self, cfg: &MaintenanceConfig) -> Result<PruneReport, OmegaError> {
+        let db = self.sqlite()?;
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("prune failed: {e}"));
+        let mut report = PruneReport::default();
+
+        if cfg.audit_retention_days > 0 {
+            report.audit =
+                sqlx::query("DELETE FROM audit_log WHERE timestamp < datetime('now', ?)")
+                    .bind(days_ago(cfg.audit_retention_days))
+                    .execute(&db.pool)
+                    .await
+                    .map_err(err)?
+                    .rows_affected();
+        }
+
+        if cfg.outcome_retention_days > 0 {
+            report.outcomes =
+                sqlx::query("DELETE FROM outcomes WHERE timestamp < datetime('now', ?)")
+                    .bind(days_ago(cfg.outcome_retention_days))
+                    .execute(&db.pool)
+                    .await
+                    .map_err(err)?
+                    .rows_affected();
+        }
+
+        if cfg.message_retention_days > 0 {
+            let expired = "SELECT m.id FROM messages m JOIN conversations c \
+                           ON c.id = m.conversation_id \
+                           WHERE c.status = 'closed' AND c.summary IS NOT NULL \
+                           AND c.summary != '' AND c.last_activity < datetime('now', ?)";
+            let cutoff = days_ago(cfg.message_retention_days);
+            let mut tx = db.pool.begin().await.map_err(err)?;
+            sqlx::query(&format!(
+                "UPDATE facts SET source_message_id = NULL WHERE source_message_id IN ({expired})"
+            ))
+            .bind(&cutoff)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+            report.messages = sqlx::query(&format!("DELETE FROM messages WHERE id IN ({expired})"))
+                .bind(&cutoff)
+                .execute(&mut *tx)
+                .await
+                .map_err(err)?
+                .rows_affected();
+            tx.commit().await.map_err(err)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Merge the full-text index segments and refresh query-planner statistics.
+    pub async fn optimize(&self) -> Result<(), OmegaError> {
+        let db = self.sqlite()?;
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("optimize failed: {e}"));
+        sqlx::query("INSERT INTO messages_fts(messages_fts) VALUES('optimize')")
+            .execute(&db.pool)
+            .await
+            .map_err(err)?;
+        sqlx::query("PRAGMA optimize")
+            .execute(&db.pool)
+            .await
+            .map_err(err)?;
+        Ok(())
+    }
+
+    /// Return free pages to the filesystem and report the bytes reclaimed.
+    ///
+    /// The first run switches the database to incremental auto-vacuum with one
+    /// full `VACUUM`; later runs only need `PRAGMA incremental_vacuum`.
+    pub async fn compact(&self) -> Result<u64, OmegaError> {
+        let db = self.sqlite()?;
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("compact failed: {e}"));
+        let before = self.db_size().await?;
+
+        // auto_vacuum changes only take effect on the connection that vacuums.
+        let mut conn = db.pool.acquire().await.map_err(err)?;
+        let (mode,): (i64,) = sqlx::query_as("PRAGMA auto_vacuum")
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(err)?;
+        if mode == 2 {
+            sqlx::query("PRAGMA incremental_vacuum")
+                .execute(&mut *conn)
+                .await
+                .map_err(err)?;
+        } else {
+            sqlx::query("PRAGMA auto_vacuum = INCREMENTAL")
+                .execute(&mut *conn)
+                .await
+                .map_err(err)?;
+            sqlx::query("VACUUM")
+                .execute(&mut *conn)
+                .await
+                .map_err(err)?;
+        }
+        drop(conn);
+
+        Ok(before.saturating_sub(self.db_size().await?))
+    }
+
+    /// Check the database file for corruption; an empty list means healthy.
+    ///
+    /// `full` runs `PRAGMA integrity_check` (also verifies indexes), otherwise
+    /// the faster `PRAGMA quick_check`.
+    pub async fn integrity_check(&self, full: bool) -> Result<Vec<String>, OmegaError> {
+        let db = self.sqlite()?;
+        let pragma = if full {
+            "PRAGMA integrity_check"
+        } else {
+            "PRAGMA quick_check"
+        };
+        let rows: Vec<(String,)> = sqlx::query_as(pragma)
+            .fetch_all(&db.pool)
+            .await
+            .map_err(|e| OmegaError::Memory(format!("integrity check failed: {e}")))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(line,)| line)
+            .filter(|line| line != "ok")
+            .collect())
+    }
+
+    /// Record a finished maintenance run.
+    pub async fn record_maintenance(&self, run: &MaintenanceRun) -> Result<(), OmegaError> {
+        let db = self.sqlite()?;
+        let integrity = if run.integrity.is_empty() {
+            "ok".to_string()
+        } else {
+            run.integrity.join("\n")
+        };
+        sqlx::query(
+            "INSERT INTO maintenance_runs (id, snapshot, pruned_audit, pruned_outcomes, \
+             pruned_messages, reclaimed_bytes, integrity) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&run.snapshot)
+        .bind(run.pruned.audit as i64)
+        .bind(run.pruned.outcomes as i64)
+        .bind(run.pruned.messages as i64)
+        .bind(run.reclaimed_bytes as i64)
+        .bind(integrity)
+        .execute(&db.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("failed to record maintenance: {e}")))?;
+
+        Ok(())
+    }
+
+    /// The most recent maintenance run, if any.
+    pub async fn last_maintenance(&self) -> Result<Option<MaintenanceRun>, OmegaError> {
+        let db = self.sqlite()?;
+        let row: Option<RunRow> = sqlx::query_as(
+            "SELECT finished_at, snapshot, pruned_audit, pruned_outcomes, pruned_messages, \
+             reclaimed_bytes, integrity FROM maintenance_runs \
+             ORDER BY finished_at DESC, rowid DESC LIMIT 1",
+        )
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("failed to read maintenance runs: {e}")))?;
+
+        Ok(row.map(
+            |(finished_at, snapshot, audit, outcomes, messages, reclaimed, integrity)| {
+                MaintenanceRun {
+                    finished_at,
+                    snapshot,
+                    pruned: PruneReport {
+                        audit: audit as u64,
+                        outcomes: outcomes as u64,
+                        messages: messages as u64,
+                    },
+                    reclaimed_bytes: reclaimed as u64,
+                    integrity: if integrity == "ok" {
+                        Vec::new()
+                    } else {
+                        integrity.lines().map(String::from).collect()
+                    },
+                }
+            },
+        ))
+    }
+}
+
+/// SQLite `datetime()` modifier for `days` days ago.
+fn days_ago(days: u32) -> String {
+    format!("-{days} days")
+}
+
+/// Copy the `main` database of `src` into a new database file at `dest` in
+/// one `sqlite3_backup_step` pass.
+///
+/// `src` must stay locked for the duration (the caller holds its
+/// `LockedSqliteHandle`).
+fn online_backup(src: *mut ffi::sqlite3, dest: &Path) -> Result<(), String> {
+    let dest = CString::new(dest.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+    let main = c"main";
+    // SAFETY: `src` is a live connection we hold exclusively; `dst` is opened
+    // here and closed on every path, and the backup object is finished before
+    // it is closed.
+    unsafe {
+        let mut dst = ptr::null_mut();
+        let rc = ffi::sqlite3_open_v2(
+            dest.as_ptr(),
+            &mut dst,
+            ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE,
+            ptr::null(),
+        );
+        if rc != ffi::SQLITE_OK {
+            let err = errmsg(dst);
+            ffi::sqlite3_close(dst);
+            return Err(err);
+        }
+        let backup = ffi::sqlite3_backup_init(dst, main.as_ptr(), src, main.as_ptr());
+        if backup.is_null() {
+            let err = errmsg(dst);
+            ffi::sqlite3_close(dst);
+            return Err(err);
+        }
+        let step = ffi::sqlite3_backup_step(backup, -1);
+        ffi::sqlite3_backup_finish(backup);
+        let result = if step == ffi::SQLITE_DONE {
+            Ok(())
+        } else {
+            Err(errmsg(dst))
+        };
+        ffi::sqlite3_close(dst);
+        result
+    }
+}
+
+/// The last error message of `db`.
+///
+/// # Safety
+/// `db` must be a connection returned by `sqlite3_open_v2` (or null).
+unsafe fn errmsg(db: *mut ffi::sqlite3) -> String {
+    if db.is_null() {
+        return "out of memory".to_string();
+    }
+    CStr::from_ptr(ffi::sqlite3_errmsg(db))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Delete the oldest snapshots in `dir` beyond the newest `keep`.
+///
+/// Only files named like [`Store::snapshot`] output are touched. Returns how
+/// many were removed.
+pub fn rotate_snapshots(dir: &Path, keep: usize) -> Result<usize, OmegaError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| OmegaError::Memory(format!("cannot read {}: {e}", dir.display())))?;
+    let mut snapshots: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(SNAPSHOT_PREFIX) && n.ends_with(SNAPSHOT_EXT))
+        })
+        .collect();
+    snapshots.sort();
+
+    let excess = snapshots.len().saturating_sub(keep);
+    for old in &snapshots[..excess] {
+        std::fs::remove_file(old).map_err(|e| {
+            OmegaError::Memory(format!("cannot remove snapshot {}: {e}", old.display()))
+        })?;
+    }
+    Ok(excess)
+}
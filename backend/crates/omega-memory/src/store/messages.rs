@@ -1,15 +1,28 @@
 //! Message storage and full-text search.
 
-use super::Store;
+use super::SqliteBackend;
+use crate::backend::{ConversationStore, MessageStore};
+use async_trait::async_trait;
 use omega_core::{
     error::OmegaError,
-    message::{IncomingMessage, OutgoingMessage},
+    message::{IncomingMessage, MessageMetadata, OutgoingMessage},
 };
 use uuid::Uuid;
 
-impl Store {
+/// A user message and the assistant's reply, removed by
+/// [`MessageStore::pop_last_exchange`].
+#[derive(Debug, Clone)]
+pub struct Exchange {
+    pub user: String,
+    pub assistant: String,
+    /// The reply's metadata (model, session, tasks created in the turn).
+    pub metadata: MessageMetadata,
+}
+
+#[async_trait]
+impl MessageStore for SqliteBackend {
     /// Store a user message and assistant response.
-    pub async fn store_exchange(
+    async fn store_exchange(
         &self,
         incoming: &IncomingMessage,
         response: &OutgoingMessage,
@@ -26,7 +39,7 @@ impl Store {
         )
         .bind(&user_id)
         .bind(&conv_id)
-        .bind(&incoming.text)
+        .bind(self.sealer.seal(&incoming.text))
         .execute(&self.pool)
         .await
         .map_err(|e| OmegaError::Memory(format!("insert failed: {e}")))?;
@@ -41,7 +54,7 @@ impl Store {
         )
         .bind(&asst_id)
         .bind(&conv_id)
-        .bind(&response.text)
+        .bind(self.sealer.seal(&response.text))
         .bind(&metadata_json)
         .execute(&self.pool)
         .await
@@ -50,8 +63,70 @@ impl Store {
         Ok(())
     }
 
+    /// Delete the newest user + assistant pair of the open conversation.
+    async fn pop_last_exchange(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Option<Exchange>, OmegaError> {
+        let Some(conv_id) = self
+            .get_open_conversation_id(channel, sender_id, project)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let rows: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+            "SELECT id, role, content, metadata_json FROM messages \
+             WHERE conversation_id = ? ORDER BY timestamp DESC, rowid DESC LIMIT 2",
+        )
+        .bind(&conv_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("query failed: {e}")))?;
+
+        let mut rows = rows.into_iter();
+        let (
+            Some((asst_id, asst_role, asst_content, metadata_json)),
+            Some((user_id, user_role, user_content, _)),
+        ) = (rows.next(), rows.next())
+        else {
+            return Ok(None);
+        };
+        if asst_role != "assistant" || user_role != "user" {
+            return Ok(None);
+        }
+
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("delete failed: {e}"));
+        let mut tx = self.pool.begin().await.map_err(err)?;
+        sqlx::query("UPDATE facts SET source_message_id = NULL WHERE source_message_id IN (?, ?)")
+            .bind(&asst_id)
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        sqlx::query("DELETE FROM messages WHERE id IN (?, ?)")
+            .bind(&asst_id)
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        tx.commit().await.map_err(err)?;
+
+        Ok(Some(Exchange {
+            user: self.sealer.open(user_content)?,
+            assistant: self.sealer.open(asst_content)?,
+            metadata: metadata_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+        }))
+    }
+
     /// Search past messages across all conversations using FTS5 full-text search.
-    pub async fn search_messages(
+    ///
+    /// Always empty while encryption is on: the index only holds ciphertext.
+    async fn search_messages(
         &self,
         query: &str,
         exclude_conversation_id: &str,
@@ -59,7 +134,7 @@ impl Store {
         limit: i64,
     ) -> Result<Vec<(String, String, String)>, OmegaError> {
         // Skip short queries — they produce noisy results.
-        if query.len() < 3 {
+        if query.len() < 3 || self.sealer.is_encrypted() {
             return Ok(Vec::new());
         }
 
@@ -87,6 +162,8 @@ impl Store {
         .await
         .map_err(|e| OmegaError::Memory(format!("fts search failed: {e}")))?;
 
-        Ok(rows)
+        rows.into_iter()
+            .map(|(role, content, ts)| Ok((role, self.sealer.open(content)?, ts)))
+            .collect()
     }
 }
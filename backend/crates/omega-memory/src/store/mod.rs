@@ -1,45 +1,169 @@
-//! SQLite-backed persistent memory store.
+//! The memory store and its SQLite backend.
 //!
-//! Split into focused submodules:
+//! [`Store`] is the handle the rest of Omega uses: it wraps the configured
+//! [`MemoryBackend`] and adds context building plus the SQLite-only
+//! operations. [`SqliteBackend`] implements the backend traits, split into
+//! focused submodules:
 //! - `conversations` — conversation lifecycle (create, find, close, summaries)
-//! - `messages` — message storage and full-text search
+//! - `messages` — message storage, full-text search and /undo
+//! - `snapshots` — named conversation snapshots and branching
 //! - `facts` — user facts, aliases, and limitations
+//! - `identity` — cross-channel identity linking (merge, unlink)
+//! - `export` — per-user export and import
+//! - `encryption` — encryption at rest and re-keying
+//! - `maintenance` — snapshots, retention, compaction and integrity checks
 //! - `tasks` — scheduled task CRUD and dedup
+//! - `heartbeats` — per-user heartbeat settings and checklists
 //! - `context` — context building and user profile formatting
+//! - `context_budget` — fitting the context to a per-model token budget
 //! - `context_helpers` — onboarding stages, system prompt composition, language detection
 
 mod context;
+mod context_budget;
 mod context_helpers;
 mod conversations;
+mod encryption;
+mod export;
 mod facts;
+mod heartbeat_items;
+mod heartbeats;
+mod identity;
+mod maintenance;
 mod messages;
 mod outcomes;
 mod sessions;
+mod snapshots;
 mod tasks;
 
 pub use context::{detect_language, format_user_profile};
+pub use context_budget::{ContextReport, SectionReport};
+pub use export::{
+    ExportedConversation, ExportedFact, ExportedHeartbeatItem, ExportedHeartbeatSettings,
+    ExportedLesson, ExportedMessage, ExportedOutcome, ExportedSession, ExportedTask, ImportReport,
+    MemoryExport, EXPORT_VERSION,
+};
+pub use heartbeat_items::{HeartbeatItem, NewHeartbeatItem};
+pub use heartbeats::HeartbeatSettings;
+pub use identity::{FactConflict, MergeReport};
+pub use maintenance::{rotate_snapshots, MaintenanceRun, PruneReport};
+pub use messages::Exchange;
+pub use snapshots::ConversationSnapshot;
 pub use tasks::DueTask;
+pub(crate) use tasks::{descriptions_are_similar, normalize_due_at};
 
-use omega_core::{config::MemoryConfig, error::OmegaError, shellexpand};
+use crate::backend::{InMemoryBackend, MemoryBackend, PostgresBackend, Sealer};
+use async_trait::async_trait;
+use omega_core::{
+    config::{ContextBudgetConfig, MemoryConfig},
+    crypto::Cipher,
+    error::OmegaError,
+    shellexpand,
+};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use sqlx::SqlitePool;
+use std::any::Any;
+use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::Arc;
 use tracing::info;
 
-/// How long (in minutes) before a conversation is considered idle.
-const CONVERSATION_TIMEOUT_MINUTES: i64 = 120;
-
-/// Persistent memory store backed by SQLite.
+/// Persistent memory store over the configured backend.
+///
+/// Derefs to [`MemoryBackend`], so every storage operation is called on the
+/// store directly. Cloning is cheap and shares the backend.
 #[derive(Clone)]
 pub struct Store {
-    pool: SqlitePool,
+    backend: Arc<dyn MemoryBackend>,
     max_context_messages: usize,
+    context_budget: ContextBudgetConfig,
 }
 
 impl Store {
-    /// Create a new store, running migrations on first use.
+    /// Open the backend selected by `memory.backend`, running migrations on first use.
     pub async fn new(config: &MemoryConfig) -> Result<Self, OmegaError> {
-        let db_path = shellexpand(&config.db_path);
+        let backend: Arc<dyn MemoryBackend> = match config.backend.as_str() {
+            "sqlite" => Arc::new(SqliteBackend::open(&config.db_path).await?),
+            "postgres" => Arc::new(PostgresBackend::connect(&config.url).await?),
+            "memory" => Arc::new(InMemoryBackend::new()),
+            other => {
+                return Err(OmegaError::Memory(format!(
+                    "unknown memory backend \"{other}\" (expected sqlite, postgres or memory)"
+                )))
+            }
+        };
+        Ok(Self::with_backend(backend, config.max_context_messages)
+            .with_context_budget(config.context_budget.clone()))
+    }
+
+    /// Wrap an already-open backend.
+    pub fn with_backend(backend: Arc<dyn MemoryBackend>, max_context_messages: usize) -> Self {
+        Self {
+            backend,
+            max_context_messages,
+            context_budget: ContextBudgetConfig::default(),
+        }
+    }
+
+    /// Fit contexts to the per-model token budgets in `budget`.
+    pub fn with_context_budget(mut self, budget: ContextBudgetConfig) -> Self {
+        self.context_budget = budget;
+        self
+    }
+
+    /// The context token budget for `model` (`0` = unlimited).
+    pub fn token_budget(&self, model: &str) -> usize {
+        self.context_budget.tokens_for(model)
+    }
+
+    /// Encrypt user content with `cipher` from now on (`None` = plaintext).
+    pub fn with_cipher(mut self, cipher: Option<Arc<Cipher>>) -> Self {
+        self.backend = self.backend.with_cipher(cipher);
+        self
+    }
+
+    /// The underlying backend.
+    pub fn backend(&self) -> &Arc<dyn MemoryBackend> {
+        &self.backend
+    }
+
+    /// The SQLite backend, for operations only it supports.
+    pub fn sqlite(&self) -> Result<&SqliteBackend, OmegaError> {
+        self.backend
+            .as_any()
+            .downcast_ref::<SqliteBackend>()
+            .ok_or_else(|| {
+                OmegaError::Memory(format!(
+                    "not supported by the {} memory backend (sqlite only)",
+                    self.backend.name()
+                ))
+            })
+    }
+
+    /// Get the database file size in bytes.
+    pub async fn db_size(&self) -> Result<u64, OmegaError> {
+        self.sqlite()?.db_size().await
+    }
+}
+
+impl Deref for Store {
+    type Target = dyn MemoryBackend;
+
+    fn deref(&self) -> &Self::Target {
+        self.backend.as_ref()
+    }
+}
+
+/// SQLite implementation of [`MemoryBackend`] — the default backend.
+#[derive(Clone)]
+pub struct SqliteBackend {
+    pool: SqlitePool,
+    sealer: Sealer,
+}
+
+impl SqliteBackend {
+    /// Open (or create) the database at `db_path` in WAL mode and migrate it.
+    pub async fn open(db_path: &str) -> Result<Self, OmegaError> {
+        let db_path = shellexpand(db_path);
 
         // Ensure parent directory exists.
         if let Some(parent) = std::path::Path::new(&db_path).parent() {
@@ -58,14 +182,17 @@ impl Store {
             .await
             .map_err(|e| OmegaError::Memory(format!("failed to connect to sqlite: {e}")))?;
 
-        // Run migrations.
-        Self::run_migrations(&pool).await?;
-
+        let backend = Self::from_pool(pool).await?;
         info!("Memory store initialized at {db_path}");
+        Ok(backend)
+    }
 
+    /// Use an existing pool, running migrations.
+    pub async fn from_pool(pool: SqlitePool) -> Result<Self, OmegaError> {
+        Self::run_migrations(&pool).await?;
         Ok(Self {
             pool,
-            max_context_messages: config.max_context_messages,
+            sealer: Sealer::default(),
         })
     }
 
@@ -88,7 +215,6 @@ impl Store {
 
         Ok((page_count * page_size) as u64)
     }
-
     /// Run SQL migrations, tracking which have already been applied.
     async fn run_migrations(pool: &SqlitePool) -> Result<(), OmegaError> {
         // Create migration tracking table.
@@ -185,6 +311,26 @@ impl Store {
                 "013_multi_lessons",
                 include_str!("../../migrations/013_multi_lessons.sql"),
             ),
+            (
+                "014_heartbeat_settings",
+                include_str!("../../migrations/014_heartbeat_settings.sql"),
+            ),
+            (
+                "015_heartbeat_items",
+                include_str!("../../migrations/015_heartbeat_items.sql"),
+            ),
+            (
+                "016_maintenance_runs",
+                include_str!("../../migrations/016_maintenance_runs.sql"),
+            ),
+            (
+                "017_conversation_snapshots",
+                include_str!("../../migrations/017_conversation_snapshots.sql"),
+            ),
+            (
+                "018_audit_cancelled",
+                include_str!("../../migrations/018_audit_cancelled.sql"),
+            ),
         ];
 
         for (name, sql) in migrations {
@@ -218,5 +364,31 @@ impl Store {
     }
 }
 
+#[async_trait]
+impl MemoryBackend for SqliteBackend {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn with_cipher(&self, cipher: Option<Arc<Cipher>>) -> Arc<dyn MemoryBackend> {
+        Arc::new(Self {
+            pool: self.pool.clone(),
+            sealer: Sealer::new(cipher),
+        })
+    }
+
+    fn is_encrypted(&self) -> bool {
+        self.sealer.is_encrypted()
+    }
+
+    async fn rekey(&self, new: Option<&Cipher>) -> Result<u64, OmegaError> {
+        self.reseal(new).await
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -3,12 +3,15 @@
 //! All functions accept a `project` parameter for project-scoped isolation.
 //! Empty string `""` = general OMEGA (no project).
 
-use super::Store;
+use super::SqliteBackend;
+use crate::backend::LearningStore;
+use async_trait::async_trait;
 use omega_core::error::OmegaError;
 
-impl Store {
+#[async_trait]
+impl LearningStore for SqliteBackend {
     /// Store a raw outcome from a REWARD marker.
-    pub async fn store_outcome(
+    async fn store_outcome(
         &self,
         sender_id: &str,
         domain: &str,
@@ -40,7 +43,7 @@ impl Store {
     /// When `project` is Some, returns only outcomes for that project.
     /// When `project` is None, returns all outcomes (general behavior).
     /// Returns `(score, domain, lesson, timestamp)` ordered newest first.
-    pub async fn get_recent_outcomes(
+    async fn get_recent_outcomes(
         &self,
         sender_id: &str,
         limit: i64,
@@ -78,7 +81,7 @@ impl Store {
     /// When `project` is Some, returns only outcomes for that project.
     /// When `project` is None, returns all outcomes.
     /// Returns `(score, domain, lesson, timestamp)` within the last N hours.
-    pub async fn get_all_recent_outcomes(
+    async fn get_all_recent_outcomes(
         &self,
         hours: i64,
         limit: i64,
@@ -114,13 +117,57 @@ impl Store {
         Ok(rows)
     }
 
+    /// Get a sender's outcomes within the last N hours (for heartbeat enrichment).
+    ///
+    /// When `project` is Some, returns only outcomes for that project.
+    /// Returns `(score, domain, lesson, timestamp)` ordered newest first.
+    async fn get_recent_outcomes_since(
+        &self,
+        sender_id: &str,
+        hours: i64,
+        limit: i64,
+        project: Option<&str>,
+    ) -> Result<Vec<(i32, String, String, String)>, OmegaError> {
+        let rows: Vec<(i32, String, String, String)> = match project {
+            Some(p) => {
+                sqlx::query_as(
+                    "SELECT score, domain, lesson, timestamp FROM outcomes \
+                     WHERE sender_id = ? AND project = ? \
+                     AND datetime(timestamp) >= datetime('now', ? || ' hours') \
+                     ORDER BY timestamp DESC LIMIT ?",
+                )
+                .bind(sender_id)
+                .bind(p)
+                .bind(-hours)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await
+            }
+            None => {
+                sqlx::query_as(
+                    "SELECT score, domain, lesson, timestamp FROM outcomes \
+                     WHERE sender_id = ? \
+                     AND datetime(timestamp) >= datetime('now', ? || ' hours') \
+                     ORDER BY timestamp DESC LIMIT ?",
+                )
+                .bind(sender_id)
+                .bind(-hours)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await
+            }
+        }
+        .map_err(|e| OmegaError::Memory(format!("get recent outcomes since: {e}")))?;
+        Ok(rows)
+    }
+
     /// Store a distilled lesson with content-based deduplication.
     ///
     /// Multiple lessons can exist per (sender_id, domain, project). If the exact
     /// same rule text already exists, its `occurrences` counter is bumped instead
     /// of creating a duplicate. After insertion, a cap of 10 lessons per
     /// (sender_id, domain, project) is enforced — oldest are pruned.
-    pub async fn store_lesson(
+    async fn store_lesson(
         &self,
         sender_id: &str,
         domain: &str,
@@ -191,7 +238,7 @@ impl Store {
     /// When `project` is Some, returns project-specific lessons first, then general.
     /// When `project` is None, returns general lessons only (project = '').
     /// Returns `(domain, rule, project)` ordered by most-updated first.
-    pub async fn get_lessons(
+    async fn get_lessons(
         &self,
         sender_id: &str,
         project: Option<&str>,
@@ -232,7 +279,7 @@ impl Store {
     /// When `project` is Some, returns project-specific + general lessons.
     /// When `project` is None, returns all lessons.
     /// Returns `(domain, rule, project)` ordered by most-updated first.
-    pub async fn get_all_lessons(
+    async fn get_all_lessons(
         &self,
         project: Option<&str>,
     ) -> Result<Vec<(String, String, String)>, OmegaError> {
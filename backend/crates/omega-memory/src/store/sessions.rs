@@ -3,15 +3,18 @@
 //! Replaces the in-memory `HashMap<String, String>` with SQLite-backed sessions
 //! that survive restarts and are scoped per (channel, sender_id, project).
 
-use super::Store;
+use super::SqliteBackend;
+use crate::backend::SessionStore;
+use async_trait::async_trait;
 use omega_core::error::OmegaError;
 use uuid::Uuid;
 
-impl Store {
+#[async_trait]
+impl SessionStore for SqliteBackend {
     /// Upsert a CLI session for a (channel, sender_id, project) tuple.
     ///
     /// If a session already exists for the same key, updates the session_id.
-    pub async fn store_session(
+    async fn store_session(
         &self,
         channel: &str,
         sender_id: &str,
@@ -38,7 +41,7 @@ impl Store {
     }
 
     /// Look up the CLI session_id for a (channel, sender_id, project) tuple.
-    pub async fn get_session(
+    async fn get_session(
         &self,
         channel: &str,
         sender_id: &str,
@@ -59,7 +62,7 @@ impl Store {
     }
 
     /// Delete the CLI session for a specific (channel, sender_id, project).
-    pub async fn clear_session(
+    async fn clear_session(
         &self,
         channel: &str,
         sender_id: &str,
@@ -80,7 +83,7 @@ impl Store {
     }
 
     /// Delete all CLI sessions for a sender (used by /forget-all scenarios).
-    pub async fn clear_all_sessions_for_sender(&self, sender_id: &str) -> Result<(), OmegaError> {
+    async fn clear_all_sessions_for_sender(&self, sender_id: &str) -> Result<(), OmegaError> {
         sqlx::query("DELETE FROM project_sessions WHERE sender_id = ?")
             .bind(sender_id)
             .execute(&self.pool)
@@ -0,0 +1,161 @@
+//! Named conversation snapshots — saved copies of a conversation's messages
+//! that a new conversation can be branched from.
+
+use super::SqliteBackend;
+use crate::backend::{ConversationStore, SnapshotStore};
+use async_trait::async_trait;
+use omega_core::error::OmegaError;
+use uuid::Uuid;
+
+/// A saved snapshot, as listed by `/snapshot`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversationSnapshot {
+    /// Name chosen by the user (unique per channel, sender and project).
+    pub name: String,
+    /// Number of messages saved.
+    pub message_count: i64,
+    /// When the snapshot was saved (UTC, SQLite format).
+    pub created_at: String,
+}
+
+#[async_trait]
+impl SnapshotStore for SqliteBackend {
+    /// Save the open conversation's messages under `name` (upsert).
+    async fn save_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError> {
+        let Some(conv_id) = self
+            .get_open_conversation_id(channel, sender_id, project)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let messages = self.get_conversation_messages(&conv_id).await?;
+
+        sqlx::query(
+            "INSERT INTO conversation_snapshots \
+             (id, channel, sender_id, project, name, messages, message_count) \
+             VALUES (?, ?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(channel, sender_id, project, name) \
+             DO UPDATE SET messages = excluded.messages, \
+             message_count = excluded.message_count, created_at = datetime('now')",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(name)
+        .bind(self.sealer.seal_messages(&messages)?)
+        .bind(messages.len() as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("save_snapshot failed: {e}")))?;
+
+        Ok(Some(messages.len()))
+    }
+
+    /// List snapshots for a (channel, sender_id, project), newest first.
+    async fn list_snapshots(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+    ) -> Result<Vec<ConversationSnapshot>, OmegaError> {
+        let rows: Vec<(String, i64, String)> = sqlx::query_as(
+            "SELECT name, message_count, created_at FROM conversation_snapshots \
+             WHERE channel = ? AND sender_id = ? AND project = ? \
+             ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("list_snapshots failed: {e}")))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, message_count, created_at)| ConversationSnapshot {
+                name,
+                message_count,
+                created_at,
+            })
+            .collect())
+    }
+
+    /// Close the open conversation and copy the snapshot into a new one.
+    async fn branch_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<Option<usize>, OmegaError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            "SELECT messages FROM conversation_snapshots \
+             WHERE channel = ? AND sender_id = ? AND project = ? AND name = ?",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("branch_snapshot failed: {e}")))?;
+        let Some((sealed,)) = row else {
+            return Ok(None);
+        };
+        let messages = self.sealer.open_messages(sealed)?;
+
+        self.close_current_conversation(channel, sender_id, project)
+            .await?;
+        let conv_id = self
+            .get_or_create_conversation(channel, sender_id, project)
+            .await?;
+
+        let err = |e: sqlx::Error| OmegaError::Memory(format!("branch_snapshot failed: {e}"));
+        let mut tx = self.pool.begin().await.map_err(err)?;
+        for (role, content) in &messages {
+            sqlx::query(
+                "INSERT INTO messages (id, conversation_id, role, content) VALUES (?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&conv_id)
+            .bind(role)
+            .bind(self.sealer.seal(content))
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        }
+        tx.commit().await.map_err(err)?;
+
+        Ok(Some(messages.len()))
+    }
+
+    /// Delete a snapshot by name.
+    async fn delete_snapshot(
+        &self,
+        channel: &str,
+        sender_id: &str,
+        project: &str,
+        name: &str,
+    ) -> Result<bool, OmegaError> {
+        let result = sqlx::query(
+            "DELETE FROM conversation_snapshots \
+             WHERE channel = ? AND sender_id = ? AND project = ? AND name = ?",
+        )
+        .bind(channel)
+        .bind(sender_id)
+        .bind(project)
+        .bind(name)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OmegaError::Memory(format!("delete_snapshot failed: {e}")))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
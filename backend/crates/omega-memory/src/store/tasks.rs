@@ -1,6 +1,8 @@
 //! Scheduled task CRUD, deduplication, and retry logic.
 
-use super::Store;
+use super::SqliteBackend;
+use crate::backend::TaskStore;
+use async_trait::async_trait;
 use omega_core::error::OmegaError;
 use uuid::Uuid;
 
@@ -24,12 +26,13 @@ pub struct DueTask {
     pub project: String,
 }
 
-impl Store {
+#[async_trait]
+impl TaskStore for SqliteBackend {
     /// Create a scheduled task. Deduplicates on two levels:
     /// 1. Exact match: same sender + description + normalized due_at.
     /// 2. Fuzzy match: same sender + similar description + due_at within 30 min.
     #[allow(clippy::too_many_arguments)]
-    pub async fn create_task(
+    async fn create_task(
         &self,
         channel: &str,
         sender_id: &str,
@@ -104,7 +107,7 @@ impl Store {
 
     /// Get tasks that are due for delivery.
     #[allow(clippy::type_complexity)]
-    pub async fn get_due_tasks(&self) -> Result<Vec<DueTask>, OmegaError> {
+    async fn get_due_tasks(&self) -> Result<Vec<DueTask>, OmegaError> {
         let rows: Vec<(
             String,
             String,
@@ -152,7 +155,7 @@ impl Store {
     }
 
     /// Complete a task: one-shot tasks become 'delivered', recurring tasks advance due_at.
-    pub async fn complete_task(&self, id: &str, repeat: Option<&str>) -> Result<(), OmegaError> {
+    async fn complete_task(&self, id: &str, repeat: Option<&str>) -> Result<(), OmegaError> {
         match repeat {
             None | Some("once") => {
                 sqlx::query(
@@ -209,12 +212,7 @@ impl Store {
     /// Fail an action task: increment retry count and either reschedule or permanently fail.
     ///
     /// Returns `true` if the task will be retried, `false` if permanently failed.
-    pub async fn fail_task(
-        &self,
-        id: &str,
-        error: &str,
-        max_retries: u32,
-    ) -> Result<bool, OmegaError> {
+    async fn fail_task(&self, id: &str, error: &str, max_retries: u32) -> Result<bool, OmegaError> {
         // Get current retry count.
         let row: Option<(i64,)> =
             sqlx::query_as("SELECT retry_count FROM scheduled_tasks WHERE id = ?")
@@ -259,7 +257,7 @@ impl Store {
     }
 
     /// Get pending tasks for a sender (for /tasks command).
-    pub async fn get_tasks_for_sender(
+    async fn get_tasks_for_sender(
         &self,
         sender_id: &str,
     ) -> Result<Vec<(String, String, String, Option<String>, String, String)>, OmegaError> {
@@ -279,7 +277,7 @@ impl Store {
     }
 
     /// Cancel a task by ID prefix (must match sender).
-    pub async fn cancel_task(&self, id_prefix: &str, sender_id: &str) -> Result<bool, OmegaError> {
+    async fn cancel_task(&self, id_prefix: &str, sender_id: &str) -> Result<bool, OmegaError> {
         let prefix = format!("{id_prefix}%");
 
         // Try to cancel pending tasks first.
@@ -314,7 +312,7 @@ impl Store {
     /// Update fields of a pending task by ID prefix (must match sender).
     ///
     /// Only non-`None` fields are updated. Returns `true` if a row was modified.
-    pub async fn update_task(
+    async fn update_task(
         &self,
         id_prefix: &str,
         sender_id: &str,
@@ -363,7 +361,7 @@ impl Store {
     }
 
     /// Defer a pending task to a new due_at time (by exact ID).
-    pub async fn defer_task(&self, id: &str, new_due_at: &str) -> Result<(), OmegaError> {
+    async fn defer_task(&self, id: &str, new_due_at: &str) -> Result<(), OmegaError> {
         sqlx::query("UPDATE scheduled_tasks SET due_at = ? WHERE id = ? AND status = 'pending'")
             .bind(new_due_at)
             .bind(id)
@@ -378,7 +376,7 @@ impl Store {
 ///
 /// Strips trailing `Z`, replaces `T` separator with space when not followed by
 /// timezone info, so `2026-02-22T07:00:00Z` and `2026-02-22 07:00:00` match.
-pub(super) fn normalize_due_at(due_at: &str) -> String {
+pub(crate) fn normalize_due_at(due_at: &str) -> String {
     let s = due_at.trim_end_matches('Z');
     s.replacen('T', " ", 1)
 }
@@ -388,7 +386,7 @@ pub(super) fn normalize_due_at(due_at: &str) -> String {
 /// Extracts significant words (3+ chars, excluding stop words), returns true
 /// if 50%+ of the smaller word set overlaps with the larger. Requires at least
 /// 3 significant words in each description to avoid false positives on short text.
-pub(super) fn descriptions_are_similar(a: &str, b: &str) -> bool {
+pub(crate) fn descriptions_are_similar(a: &str, b: &str) -> bool {
     let words_a = significant_words(a);
     let words_b = significant_words(b);
 
@@ -1,7 +1,7 @@
 use super::context::format_user_profile;
 use super::tasks::{descriptions_are_similar, normalize_due_at};
-use super::Store;
-use omega_core::config::MemoryConfig;
+use super::{FactConflict, MaintenanceRun, MemoryExport, PruneReport, SqliteBackend, Store};
+use omega_core::config::{MaintenanceConfig, MemoryConfig};
 use omega_core::context::ContextNeeds;
 use omega_core::message::IncomingMessage;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
@@ -12,7 +12,10 @@ async fn test_store() -> Store {
     let _config = MemoryConfig {
         backend: "sqlite".to_string(),
         db_path: ":memory:".to_string(),
+        url: String::new(),
         max_context_messages: 10,
+        context_budget: Default::default(),
+        maintenance: Default::default(),
     };
     // For in-memory, we need to bypass shellexpand.
     let opts = SqliteConnectOptions::from_str("sqlite::memory:")
@@ -23,11 +26,8 @@ async fn test_store() -> Store {
         .connect_with(opts)
         .await
         .unwrap();
-    Store::run_migrations(&pool).await.unwrap();
-    Store {
-        pool,
-        max_context_messages: 10,
-    }
+    let backend = SqliteBackend::from_pool(pool).await.unwrap();
+    Store::with_backend(std::sync::Arc::new(backend), 10)
 }
 
 #[tokio::test]
@@ -486,7 +486,7 @@ async fn test_get_all_recent_summaries() {
         "INSERT INTO conversations (id, channel, sender_id, status, summary, last_activity, updated_at) \
          VALUES ('c1', 'telegram', 'user1', 'closed', 'Discussed project planning', datetime('now'), datetime('now'))",
     )
-    .execute(store.pool())
+    .execute(store.sqlite().unwrap().pool())
     .await
     .unwrap();
 
@@ -859,6 +859,77 @@ async fn test_build_context_advances_onboarding_stage() {
     );
 }
 
+#[tokio::test]
+async fn test_build_context_fits_token_budget() {
+    let store = test_store().await;
+    let msg = |text: &str| IncomingMessage {
+        id: uuid::Uuid::new_v4(),
+        channel: "telegram".to_string(),
+        sender_id: "budget_user".to_string(),
+        sender_name: None,
+        text: text.to_string(),
+        timestamp: chrono::Utc::now(),
+        reply_to: None,
+        attachments: vec![],
+        reply_target: Some("chat1".to_string()),
+        is_group: false,
+        source: None,
+        platform_message_id: None,
+    };
+    let reply = |text: &str| omega_core::message::OutgoingMessage {
+        text: text.to_string(),
+        ..Default::default()
+    };
+    store
+        .store_fact("budget_user", "name", "Alice")
+        .await
+        .unwrap();
+    let pasted = "log line ".repeat(3_000);
+    store
+        .store_exchange(&msg("where did I park?"), &reply("Level 2."), "")
+        .await
+        .unwrap();
+    store
+        .store_exchange(&msg(&pasted), &reply("That is a long log."), "")
+        .await
+        .unwrap();
+    for i in 0..3 {
+        store
+            .store_exchange(&msg(&format!("question {i}")), &reply("answer"), "")
+            .await
+            .unwrap();
+    }
+
+    // Unlimited: everything is sent as stored.
+    let needs = ContextNeeds::default();
+    let (ctx, report) = store
+        .build_context_with_report(&msg("hello"), "Base rules", &needs, None)
+        .await
+        .unwrap();
+    assert_eq!(ctx.history.len(), 10);
+    assert!(ctx.history.iter().any(|e| e.content == pasted));
+    assert_eq!(report.budget, 0);
+    assert_eq!(report.dropped_tokens(), 0);
+
+    // A small model's budget: the old paste is cut, the turns around it stay.
+    let needs = ContextNeeds {
+        token_budget: 2_000,
+        ..Default::default()
+    };
+    let (ctx, report) = store
+        .build_context_with_report(&msg("hello"), "Base rules", &needs, None)
+        .await
+        .unwrap();
+    assert_eq!(ctx.history.len(), 10);
+    assert_eq!(ctx.history[0].content, "where did I park?");
+    assert!(ctx.history[2].content.ends_with("[truncated]"));
+    assert!(ctx.system_prompt.contains("name: Alice"));
+    assert!(report.total_tokens() <= 2_000);
+    let history = report.section("history").unwrap();
+    assert_eq!((history.included, history.truncated), (10, 1));
+    assert!(history.dropped_tokens > 5_000);
+}
+
 // --- User alias tests ---
 
 #[tokio::test]
@@ -887,6 +958,19 @@ async fn test_create_alias_idempotent() {
     assert_eq!(resolved, "telegram456");
 }
 
+#[tokio::test]
+async fn test_get_aliases() {
+    let store = test_store().await;
+    assert!(store.get_aliases("telegram456").await.unwrap().is_empty());
+    store.create_alias("phone123", "telegram456").await.unwrap();
+    store.create_alias("api-7", "telegram456").await.unwrap();
+    store.create_alias("phone999", "telegram111").await.unwrap();
+    assert_eq!(
+        store.get_aliases("telegram456").await.unwrap(),
+        vec!["api-7".to_string(), "phone123".to_string()]
+    );
+}
+
 #[tokio::test]
 async fn test_find_canonical_user() {
     let store = test_store().await;
@@ -1124,7 +1208,7 @@ async fn test_fail_task_stores_error() {
     let row: Option<(String, i64)> =
         sqlx::query_as("SELECT last_error, retry_count FROM scheduled_tasks WHERE id = ?")
             .bind(&id)
-            .fetch_optional(store.pool())
+            .fetch_optional(store.sqlite().unwrap().pool())
             .await
             .unwrap();
 
@@ -1318,7 +1402,7 @@ async fn test_lessons_content_dedup() {
     let (occurrences,): (i64,) = sqlx::query_as(
         "SELECT occurrences FROM lessons WHERE sender_id = 'user1' AND domain = 'trading'",
     )
-    .fetch_one(store.pool())
+    .fetch_one(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     assert_eq!(occurrences, 2, "occurrences should be 2 after dedup");
@@ -1394,7 +1478,7 @@ async fn test_lessons_cap_project_isolation() {
         "SELECT domain, rule, project FROM lessons \
          WHERE sender_id = 'user1' AND project = 'project-a'",
     )
-    .fetch_all(store.pool())
+    .fetch_all(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     assert_eq!(a_lessons.len(), 10, "project A capped at 10");
@@ -1404,7 +1488,7 @@ async fn test_lessons_cap_project_isolation() {
         "SELECT domain, rule, project FROM lessons \
          WHERE sender_id = 'user1' AND project = 'project-b'",
     )
-    .fetch_all(store.pool())
+    .fetch_all(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     assert_eq!(
@@ -1728,7 +1812,7 @@ async fn test_find_idle_conversations_includes_project() {
         "INSERT INTO conversations (id, channel, sender_id, project, status, last_activity) \
          VALUES ('old1', 'telegram', 'user1', 'trader', 'active', datetime('now', '-3 hours'))",
     )
-    .execute(store.pool())
+    .execute(store.sqlite().unwrap().pool())
     .await
     .unwrap();
 
@@ -1760,15 +1844,15 @@ async fn test_lessons_dedup_reorders_by_updated_at() {
 
     // Manually set distinct timestamps: A oldest, C newest.
     sqlx::query("UPDATE lessons SET updated_at = '2026-01-01 00:00:00' WHERE rule = 'Rule A'")
-        .execute(store.pool())
+        .execute(store.sqlite().unwrap().pool())
         .await
         .unwrap();
     sqlx::query("UPDATE lessons SET updated_at = '2026-01-01 00:01:00' WHERE rule = 'Rule B'")
-        .execute(store.pool())
+        .execute(store.sqlite().unwrap().pool())
         .await
         .unwrap();
     sqlx::query("UPDATE lessons SET updated_at = '2026-01-01 00:02:00' WHERE rule = 'Rule C'")
-        .execute(store.pool())
+        .execute(store.sqlite().unwrap().pool())
         .await
         .unwrap();
 
@@ -1806,7 +1890,7 @@ async fn test_lessons_reinforced_survives_cap() {
             "UPDATE lessons SET updated_at = '2026-01-01 00:{:02}:00' WHERE rule = 'Rule {}'",
             i, i
         ))
-        .execute(store.pool())
+        .execute(store.sqlite().unwrap().pool())
         .await
         .unwrap();
     }
@@ -1858,13 +1942,13 @@ async fn test_lessons_dedup_cross_project_isolation() {
     let a: Vec<(String,)> = sqlx::query_as(
         "SELECT rule FROM lessons WHERE sender_id = 'user1' AND project = 'project-a'",
     )
-    .fetch_all(store.pool())
+    .fetch_all(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     let b: Vec<(String,)> = sqlx::query_as(
         "SELECT rule FROM lessons WHERE sender_id = 'user1' AND project = 'project-b'",
     )
-    .fetch_all(store.pool())
+    .fetch_all(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     assert_eq!(a.len(), 1, "project-a should have its own row");
@@ -1874,13 +1958,13 @@ async fn test_lessons_dedup_cross_project_isolation() {
     let (occ_a,): (i64,) = sqlx::query_as(
         "SELECT occurrences FROM lessons WHERE sender_id = 'user1' AND project = 'project-a'",
     )
-    .fetch_one(store.pool())
+    .fetch_one(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     let (occ_b,): (i64,) = sqlx::query_as(
         "SELECT occurrences FROM lessons WHERE sender_id = 'user1' AND project = 'project-b'",
     )
-    .fetch_one(store.pool())
+    .fetch_one(store.sqlite().unwrap().pool())
     .await
     .unwrap();
     assert_eq!(occ_a, 1, "project-a occurrences should be 1");
@@ -1903,12 +1987,12 @@ async fn test_lessons_dedup_cross_sender_isolation() {
 
     let u1: Vec<(String,)> =
         sqlx::query_as("SELECT rule FROM lessons WHERE sender_id = 'user1' AND domain = 'cooking'")
-            .fetch_all(store.pool())
+            .fetch_all(store.sqlite().unwrap().pool())
             .await
             .unwrap();
     let u2: Vec<(String,)> =
         sqlx::query_as("SELECT rule FROM lessons WHERE sender_id = 'user2' AND domain = 'cooking'")
-            .fetch_all(store.pool())
+            .fetch_all(store.sqlite().unwrap().pool())
             .await
             .unwrap();
     assert_eq!(u1.len(), 1, "user1 should have its own row");
@@ -1997,6 +2081,9 @@ fn test_build_system_prompt_recall_multibyte_truncation() {
         onboarding_hint: None,
     });
     assert!(result.contains("Related past context"));
+    // Recalled messages are labelled untrusted.
+    assert!(result.contains("<<untrusted source=\"recall\">>\n"));
+    assert!(result.contains("<</untrusted>>"));
 }
 
 // --- FTS5 query sanitization tests ---
@@ -2032,6 +2119,10 @@ async fn test_search_messages_with_fts5_operators() {
             processing_time_ms: 0,
             model: None,
             session_id: None,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+            created_tasks: Vec::new(),
+            untrusted_sources: Vec::new(),
         },
         reply_target: None,
         ..Default::default()
@@ -2191,6 +2282,10 @@ async fn test_get_conversation_token_estimate_with_messages() {
             processing_time_ms: 0,
             model: None,
             session_id: None,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+            created_tasks: Vec::new(),
+            untrusted_sources: Vec::new(),
         },
         reply_target: None,
         ..Default::default()
@@ -2208,3 +2303,811 @@ async fn test_get_conversation_token_estimate_with_messages() {
     // Total chars = 11 + 25 = 36, tokens = 36 / 4 = 9
     assert_eq!(tokens, 9, "should estimate 9 tokens (36 chars / 4)");
 }
+
+// --- Per-user heartbeat tests ---
+
+fn heartbeat_settings(sender_id: &str) -> super::HeartbeatSettings {
+    super::HeartbeatSettings {
+        sender_id: sender_id.to_string(),
+        channel: "telegram".to_string(),
+        reply_target: format!("chat-{sender_id}"),
+        enabled: true,
+        interval_minutes: 30,
+        active_start: String::new(),
+        active_end: String::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_heartbeat_settings_roundtrip() {
+    let store = test_store().await;
+    assert!(store
+        .get_heartbeat_settings("user1")
+        .await
+        .unwrap()
+        .is_none());
+
+    let mut settings = heartbeat_settings("user1");
+    store.save_heartbeat_settings(&settings).await.unwrap();
+    assert_eq!(
+        store.get_heartbeat_settings("user1").await.unwrap(),
+        Some(settings.clone())
+    );
+
+    settings.interval_minutes = 60;
+    settings.active_start = "08:00".to_string();
+    settings.active_end = "22:00".to_string();
+    store.save_heartbeat_settings(&settings).await.unwrap();
+    let loaded = store
+        .get_heartbeat_settings("user1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.interval_minutes, 60);
+    assert_eq!(loaded.active_start, "08:00");
+}
+
+#[tokio::test]
+async fn test_heartbeat_seed_does_not_overwrite() {
+    let store = test_store().await;
+    let mut settings = heartbeat_settings("owner");
+    assert!(store.seed_heartbeat_settings(&settings).await.unwrap());
+
+    settings.interval_minutes = 5;
+    assert!(!store.seed_heartbeat_settings(&settings).await.unwrap());
+    let loaded = store
+        .get_heartbeat_settings("owner")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.interval_minutes, 30);
+}
+
+#[tokio::test]
+async fn test_get_enabled_heartbeats_skips_disabled() {
+    let store = test_store().await;
+    store
+        .save_heartbeat_settings(&heartbeat_settings("alice"))
+        .await
+        .unwrap();
+    let mut bob = heartbeat_settings("bob");
+    bob.enabled = false;
+    store.save_heartbeat_settings(&bob).await.unwrap();
+
+    let enabled = store.get_enabled_heartbeats().await.unwrap();
+    assert_eq!(enabled.len(), 1);
+    assert_eq!(enabled[0].sender_id, "alice");
+}
+
+#[tokio::test]
+async fn test_recent_outcomes_since_scoped_to_sender() {
+    let store = test_store().await;
+    store
+        .store_outcome("alice", "health", 1, "Took a walk", "conversation", "")
+        .await
+        .unwrap();
+    store
+        .store_outcome("bob", "trading", -1, "Missed stop", "conversation", "")
+        .await
+        .unwrap();
+
+    let alice = store
+        .get_recent_outcomes_since("alice", 24, 20, None)
+        .await
+        .unwrap();
+    assert_eq!(alice.len(), 1);
+    assert_eq!(alice[0].1, "health");
+}
+
+// --- Heartbeat item tests ---
+
+fn new_item(description: &str) -> super::NewHeartbeatItem {
+    super::NewHeartbeatItem {
+        description: description.to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_heartbeat_items_add_dedup_and_scope() {
+    let store = test_store().await;
+    let id = store
+        .add_heartbeat_item("alice", "", &new_item("Check inbox"))
+        .await
+        .unwrap();
+    let again = store
+        .add_heartbeat_item(
+            "alice",
+            "",
+            &super::NewHeartbeatItem {
+                description: "check INBOX".to_string(),
+                cadence: "hourly".to_string(),
+                alert_on_change: true,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(id, again, "same description reuses the item");
+
+    store
+        .add_heartbeat_item("alice", "garden", &new_item("Water tomatoes"))
+        .await
+        .unwrap();
+    store
+        .add_heartbeat_item("bob", "", &new_item("Check inbox"))
+        .await
+        .unwrap();
+
+    let personal = store.get_heartbeat_items("alice", "").await.unwrap();
+    assert_eq!(personal.len(), 1);
+    assert_eq!(personal[0].cadence, "hourly");
+    assert!(personal[0].alert_on_change);
+    assert!(personal[0].last_checked_at.is_none());
+    assert_eq!(
+        store.get_heartbeat_item_projects("alice").await.unwrap(),
+        vec!["garden"]
+    );
+    assert!(store
+        .get_heartbeat_item_projects("bob")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_heartbeat_item_remove_by_id_only() {
+    let store = test_store().await;
+    let exercise = store
+        .add_heartbeat_item("alice", "", &new_item("Check exercise habits"))
+        .await
+        .unwrap();
+    store
+        .add_heartbeat_item("alice", "", &new_item("Exercise bike maintenance"))
+        .await
+        .unwrap();
+
+    // Other users cannot remove it, and descriptions are not ids.
+    assert!(!store
+        .remove_heartbeat_item("bob", &exercise[..8])
+        .await
+        .unwrap());
+    assert!(!store
+        .remove_heartbeat_item("alice", "exercise")
+        .await
+        .unwrap());
+    assert!(!store.remove_heartbeat_item("alice", "").await.unwrap());
+
+    assert!(store
+        .remove_heartbeat_item("alice", &exercise[..8])
+        .await
+        .unwrap());
+    let left = store.get_heartbeat_items("alice", "").await.unwrap();
+    assert_eq!(left.len(), 1);
+    assert_eq!(left[0].description, "Exercise bike maintenance");
+}
+
+#[tokio::test]
+async fn test_heartbeat_item_results_track_changes() {
+    let store = test_store().await;
+    let id = store
+        .add_heartbeat_item("alice", "", &new_item("Disk usage"))
+        .await
+        .unwrap();
+    let short = &id[..8];
+
+    assert_eq!(
+        store
+            .record_heartbeat_result("alice", short, "42% used")
+            .await
+            .unwrap(),
+        Some(true),
+        "first result is a change"
+    );
+    assert_eq!(
+        store
+            .record_heartbeat_result("alice", short, "42% used")
+            .await
+            .unwrap(),
+        Some(false)
+    );
+    assert_eq!(
+        store
+            .record_heartbeat_result("alice", short, "91% used")
+            .await
+            .unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        store
+            .record_heartbeat_result("bob", short, "x")
+            .await
+            .unwrap(),
+        None
+    );
+
+    let item = &store.get_heartbeat_items("alice", "").await.unwrap()[0];
+    assert_eq!(item.last_result.as_deref(), Some("91% used"));
+    assert!(item.last_checked_at.is_some());
+}
+
+#[tokio::test]
+async fn test_heartbeat_items_mark_checked() {
+    let store = test_store().await;
+    let id = store
+        .add_heartbeat_item("alice", "", &new_item("Check inbox"))
+        .await
+        .unwrap();
+    store.mark_heartbeat_items_checked(&[id]).await.unwrap();
+    let item = &store.get_heartbeat_items("alice", "").await.unwrap()[0];
+    assert!(item.last_checked_at.is_some());
+    assert!(item.last_result.is_none());
+}
+
+#[tokio::test]
+async fn test_legacy_heartbeat_checklists() {
+    let store = test_store().await;
+    store
+        .save_heartbeat_settings(&heartbeat_settings("alice"))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE heartbeat_settings SET checklist = '- Old item' WHERE sender_id = ?")
+        .bind("alice")
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        store.get_legacy_heartbeat_checklists().await.unwrap(),
+        vec![("alice".to_string(), "- Old item".to_string())]
+    );
+    store
+        .clear_legacy_heartbeat_checklist("alice")
+        .await
+        .unwrap();
+    assert!(store
+        .get_legacy_heartbeat_checklists()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+// --- Identity linking ---
+
+#[tokio::test]
+async fn test_merge_sender_moves_data_and_resolves_conflicts() {
+    let store = test_store().await;
+    store.store_fact("tg1", "name", "Ana").await.unwrap();
+    store.store_fact("tg1", "city", "Lisbon").await.unwrap();
+    store.store_fact("wa1", "city", "Lisbon").await.unwrap();
+    store.store_fact("wa1", "name", "Ann").await.unwrap();
+    store.store_fact("wa1", "pet", "cat").await.unwrap();
+    store.store_fact("wa1", "job", "pilot").await.unwrap();
+    store.store_fact("tg1", "job", "chef").await.unwrap();
+    // wa1's `job` is the most recent value.
+    sqlx::query("UPDATE facts SET updated_at = '2099-01-01 00:00:00' WHERE sender_id = 'wa1' AND key = 'job'")
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    store
+        .get_or_create_conversation("whatsapp", "wa1", "")
+        .await
+        .unwrap();
+    store
+        .create_task(
+            "whatsapp",
+            "wa1",
+            "wa1",
+            "Call mom",
+            "2099-01-01T10:00:00",
+            None,
+            "reminder",
+            "",
+        )
+        .await
+        .unwrap();
+    store.create_alias("api-wa1", "wa1").await.unwrap();
+
+    let report = store.merge_sender("wa1", "tg1").await.unwrap();
+    assert_eq!(report.facts, 1);
+    assert_eq!(report.conversations, 1);
+    assert_eq!(report.tasks, 1);
+    let mut conflicts = report.conflicts.clone();
+    conflicts.sort_by(|a, b| a.key.cmp(&b.key));
+    assert_eq!(
+        conflicts,
+        vec![
+            FactConflict {
+                key: "job".into(),
+                kept: "pilot".into(),
+                discarded: "chef".into(),
+            },
+            FactConflict {
+                key: "name".into(),
+                kept: "Ana".into(),
+                discarded: "Ann".into(),
+            },
+        ]
+    );
+
+    assert!(store.get_facts("wa1").await.unwrap().is_empty());
+    assert_eq!(
+        store.get_fact("tg1", "pet").await.unwrap().as_deref(),
+        Some("cat")
+    );
+    assert_eq!(
+        store.get_fact("tg1", "job").await.unwrap().as_deref(),
+        Some("pilot")
+    );
+    assert!(store
+        .get_active_conversation_id("whatsapp", "tg1", "")
+        .await
+        .unwrap()
+        .is_some());
+    assert_eq!(store.get_tasks_for_sender("tg1").await.unwrap().len(), 1);
+    assert_eq!(store.resolve_sender_id("wa1").await.unwrap(), "tg1");
+    assert_eq!(store.resolve_sender_id("api-wa1").await.unwrap(), "tg1");
+}
+
+#[tokio::test]
+async fn test_merge_sender_closes_colliding_conversation() {
+    let store = test_store().await;
+    let ours = store
+        .get_or_create_conversation("telegram", "tg1", "")
+        .await
+        .unwrap();
+    store
+        .get_or_create_conversation("telegram", "tg2", "")
+        .await
+        .unwrap();
+    store.merge_sender("tg2", "tg1").await.unwrap();
+    assert_eq!(
+        store
+            .get_active_conversation_id("telegram", "tg1", "")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some(ours.as_str())
+    );
+    assert!(store.merge_sender("tg1", "tg1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_remove_alias() {
+    let store = test_store().await;
+    store.create_alias("wa1", "tg1").await.unwrap();
+    assert!(store.remove_alias("wa1").await.unwrap());
+    assert!(!store.remove_alias("wa1").await.unwrap());
+    assert_eq!(store.resolve_sender_id("wa1").await.unwrap(), "wa1");
+}
+
+// --- Export / import ---
+
+/// A store holding a bit of everything for `tg1`.
+async fn populated_store() -> Store {
+    let store = test_store().await;
+    store.store_fact("tg1", "name", "Ana").await.unwrap();
+    store.store_fact("tg1", "city", "Lisbon").await.unwrap();
+    let conv_id = store
+        .get_or_create_conversation("telegram", "tg1", "")
+        .await
+        .unwrap();
+    for (role, content) in [("user", "Hello"), ("assistant", "Hi Ana")] {
+        sqlx::query(
+            "INSERT INTO messages (id, conversation_id, role, content) VALUES (?, ?, ?, ?)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&conv_id)
+        .bind(role)
+        .bind(content)
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    }
+    store
+        .close_conversation(&conv_id, "Greetings")
+        .await
+        .unwrap();
+    store
+        .create_task(
+            "telegram",
+            "tg1",
+            "tg1",
+            "Call mom",
+            "2099-01-01T10:00:00",
+            None,
+            "reminder",
+            "",
+        )
+        .await
+        .unwrap();
+    store
+        .store_outcome("tg1", "tone", 1, "liked brevity", "conversation", "")
+        .await
+        .unwrap();
+    store
+        .store_lesson("tg1", "tone", "Keep replies short", "")
+        .await
+        .unwrap();
+    store
+        .store_session("telegram", "tg1", "web", "sess-1")
+        .await
+        .unwrap();
+    store.create_alias("wa1", "tg1").await.unwrap();
+    store
+}
+
+#[tokio::test]
+async fn test_export_sender_collects_everything() {
+    let store = populated_store().await;
+    let export = store.export_sender("tg1").await.unwrap();
+    assert_eq!(export.version, super::EXPORT_VERSION);
+    assert_eq!(export.sender_id, "tg1");
+    assert_eq!(export.aliases, vec!["wa1".to_string()]);
+    assert_eq!(export.facts.len(), 2);
+    assert_eq!(export.conversations.len(), 1);
+    assert_eq!(
+        export.conversations[0].summary.as_deref(),
+        Some("Greetings")
+    );
+    assert_eq!(export.conversations[0].messages.len(), 2);
+    assert_eq!(export.conversations[0].messages[0].content, "Hello");
+    assert_eq!(export.tasks.len(), 1);
+    assert_eq!(export.outcomes.len(), 1);
+    assert_eq!(export.lessons.len(), 1);
+    assert_eq!(export.sessions.len(), 1);
+
+    // Nothing leaks in from other senders.
+    assert!(store.export_sender("other").await.unwrap().facts.is_empty());
+}
+
+#[tokio::test]
+async fn test_import_sender_round_trip_and_dedup() {
+    let source = populated_store().await;
+    let json = serde_json::to_string(&source.export_sender("tg1").await.unwrap()).unwrap();
+    let export: MemoryExport = serde_json::from_str(&json).unwrap();
+
+    let target = test_store().await;
+    let report = target.import_sender(&export, None).await.unwrap();
+    assert_eq!(report.facts, 2);
+    assert_eq!(report.conversations, 1);
+    assert_eq!(report.messages, 2);
+    assert_eq!(report.tasks, 1);
+    assert_eq!(report.outcomes, 1);
+    assert_eq!(report.lessons, 1);
+    assert_eq!(report.sessions, 1);
+    assert_eq!(report.aliases, 1);
+    assert_eq!(report.skipped, 0);
+    assert_eq!(target.resolve_sender_id("wa1").await.unwrap(), "tg1");
+
+    let mut reexport = target.export_sender("tg1").await.unwrap();
+    reexport.exported_at = export.exported_at.clone();
+    assert_eq!(reexport, export);
+
+    // Importing the same archive again adds nothing.
+    let again = target.import_sender(&export, None).await.unwrap();
+    assert_eq!(
+        again.facts + again.conversations + again.tasks + again.lessons,
+        0
+    );
+    assert_eq!(again.skipped, 8);
+}
+
+#[tokio::test]
+async fn test_import_sender_under_new_id_keeps_existing_facts() {
+    let source = populated_store().await;
+    let export = source.export_sender("tg1").await.unwrap();
+
+    let target = test_store().await;
+    target
+        .store_fact("new1", "name", "Ana Maria")
+        .await
+        .unwrap();
+    let report = target.import_sender(&export, Some("new1")).await.unwrap();
+    assert_eq!(report.facts, 1);
+    assert_eq!(report.aliases, 2);
+    assert_eq!(
+        target.get_fact("new1", "name").await.unwrap().as_deref(),
+        Some("Ana Maria")
+    );
+    assert_eq!(target.resolve_sender_id("tg1").await.unwrap(), "new1");
+    assert_eq!(target.resolve_sender_id("wa1").await.unwrap(), "new1");
+}
+
+#[tokio::test]
+async fn test_import_sender_rejects_unknown_version() {
+    let store = test_store().await;
+    let export = MemoryExport {
+        version: super::EXPORT_VERSION + 1,
+        sender_id: "tg1".into(),
+        ..Default::default()
+    };
+    assert!(store.import_sender(&export, None).await.is_err());
+}
+
+// --- Maintenance ---
+
+#[tokio::test]
+async fn test_prune_applies_retention_windows() {
+    let store = test_store().await;
+    for (ts, input) in [
+        ("2000-01-01 00:00:00", "old"),
+        ("2099-01-01 00:00:00", "new"),
+    ] {
+        sqlx::query(
+            "INSERT INTO audit_log (id, timestamp, channel, sender_id, input_text) \
+             VALUES (?, ?, 'telegram', 'u1', ?)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(ts)
+        .bind(input)
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    }
+    store
+        .store_outcome("u1", "tone", 1, "liked brevity", "conversation", "")
+        .await
+        .unwrap();
+    sqlx::query("UPDATE outcomes SET timestamp = '2000-01-01 00:00:00'")
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+
+    // An old summarized conversation loses its messages; an unsummarized one keeps them.
+    let mut conv_ids = Vec::new();
+    for summary in [Some("Talked about plants"), None] {
+        let conv_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO conversations (id, channel, sender_id, status, summary, last_activity) \
+             VALUES (?, 'telegram', 'u1', 'closed', ?, '2000-01-01 00:00:00')",
+        )
+        .bind(&conv_id)
+        .bind(summary)
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO messages (id, conversation_id, role, content) VALUES (?, ?, 'user', 'hi')",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&conv_id)
+        .execute(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+        conv_ids.push(conv_id);
+    }
+
+    // Defaults keep messages forever.
+    let report = store.prune(&MaintenanceConfig::default()).await.unwrap();
+    assert_eq!(
+        report,
+        PruneReport {
+            audit: 1,
+            outcomes: 1,
+            messages: 0,
+        }
+    );
+
+    let cfg = MaintenanceConfig {
+        message_retention_days: 30,
+        ..Default::default()
+    };
+    assert_eq!(store.prune(&cfg).await.unwrap().messages, 1);
+    assert!(store
+        .get_conversation_messages(&conv_ids[0])
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        store
+            .get_conversation_messages(&conv_ids[1])
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM audit_log")
+        .fetch_one(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    assert_eq!(remaining, 1);
+}
+
+#[tokio::test]
+async fn test_snapshot_rotation_and_backup() {
+    // A file-backed live database, like the gateway's.
+    let dir = std::env::temp_dir().join(format!("__omega_snapshots_{}__", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let store = Store::new(&MemoryConfig {
+        backend: "sqlite".to_string(),
+        db_path: dir.join("live.sqlite").to_string_lossy().to_string(),
+        url: String::new(),
+        max_context_messages: 10,
+        context_budget: Default::default(),
+        maintenance: Default::default(),
+    })
+    .await
+    .unwrap();
+    store.store_fact("u1", "name", "Ana").await.unwrap();
+    for name in [
+        "memory-20000101-000000.db",
+        "memory-20000102-000000.db",
+        "notes.txt",
+    ] {
+        std::fs::write(dir.join(name), "old").unwrap();
+    }
+
+    let path = store.snapshot(&dir, 2).await.unwrap();
+    assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    assert!(!dir.join("memory-20000101-000000.db").exists());
+    assert!(dir.join("memory-20000102-000000.db").exists());
+    assert!(dir.join("notes.txt").exists());
+    // Never overwrite an existing file.
+    assert!(store.backup_to(&path).await.is_err());
+
+    // The snapshot is a working database.
+    let opts = SqliteConnectOptions::from_str(&format!("sqlite:{}", path.display())).unwrap();
+    let pool = SqlitePoolOptions::new().connect_with(opts).await.unwrap();
+    let (value,): (String,) =
+        sqlx::query_as("SELECT value FROM facts WHERE sender_id = 'u1' AND key = 'name'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(value, "Ana");
+    pool.close().await;
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_optimize_compact_and_integrity() {
+    let store = test_store().await;
+    store.optimize().await.unwrap();
+    store.compact().await.unwrap();
+    let (mode,): (i64,) = sqlx::query_as("PRAGMA auto_vacuum")
+        .fetch_one(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    assert_eq!(mode, 2, "compact switches to incremental auto-vacuum");
+    store.compact().await.unwrap();
+    assert!(store.integrity_check(false).await.unwrap().is_empty());
+    assert!(store.integrity_check(true).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_record_and_read_last_maintenance() {
+    let store = test_store().await;
+    assert!(store.last_maintenance().await.unwrap().is_none());
+    let run = MaintenanceRun {
+        snapshot: Some("/backups/memory-1.db".into()),
+        pruned: PruneReport {
+            audit: 3,
+            outcomes: 2,
+            messages: 1,
+        },
+        reclaimed_bytes: 4096,
+        integrity: vec!["row 5 missing from index".into()],
+        ..Default::default()
+    };
+    store.record_maintenance(&run).await.unwrap();
+    let last = store.last_maintenance().await.unwrap().unwrap();
+    assert_eq!(last.snapshot, run.snapshot);
+    assert_eq!(last.pruned, run.pruned);
+    assert_eq!(last.reclaimed_bytes, 4096);
+    assert_eq!(last.integrity, run.integrity);
+    assert!(!last.finished_at.is_empty());
+}
+
+// --- Encryption at rest ---
+
+fn test_cipher() -> std::sync::Arc<omega_core::crypto::Cipher> {
+    let key = omega_core::crypto::generate_key().unwrap();
+    std::sync::Arc::new(omega_core::crypto::Cipher::new(&key).unwrap())
+}
+
+async fn raw_fact(store: &Store, key: &str) -> String {
+    let (value,): (String,) = sqlx::query_as("SELECT value FROM facts WHERE key = ?")
+        .bind(key)
+        .fetch_one(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    value
+}
+
+#[tokio::test]
+async fn test_encrypted_store_roundtrip() {
+    let store = test_store().await.with_cipher(Some(test_cipher()));
+    store.store_fact("u1", "city", "Madrid").await.unwrap();
+    assert!(raw_fact(&store, "city").await.starts_with("enc:v1:"));
+    assert_eq!(
+        store.get_fact("u1", "city").await.unwrap().as_deref(),
+        Some("Madrid")
+    );
+    assert_eq!(
+        store.get_facts("u1").await.unwrap(),
+        vec![("city".to_string(), "Madrid".to_string())]
+    );
+
+    let conv_id = store
+        .get_or_create_conversation("telegram", "u1", "")
+        .await
+        .unwrap();
+    let incoming = IncomingMessage {
+        id: uuid::Uuid::new_v4(),
+        channel: "telegram".to_string(),
+        sender_id: "u1".to_string(),
+        sender_name: None,
+        text: "my passport number is X123".to_string(),
+        timestamp: chrono::Utc::now(),
+        reply_to: None,
+        attachments: vec![],
+        reply_target: None,
+        is_group: false,
+        source: None,
+        platform_message_id: None,
+    };
+    let response = omega_core::message::OutgoingMessage {
+        text: "Noted.".into(),
+        ..Default::default()
+    };
+    store
+        .store_exchange(&incoming, &response, "")
+        .await
+        .unwrap();
+    let (raw,): (String,) = sqlx::query_as("SELECT content FROM messages WHERE role = 'user'")
+        .fetch_one(store.sqlite().unwrap().pool())
+        .await
+        .unwrap();
+    assert!(!raw.contains("passport"));
+    let messages = store.get_conversation_messages(&conv_id).await.unwrap();
+    assert_eq!(messages[0].1, "my passport number is X123");
+    assert!(store
+        .search_messages("passport", "", "u1", 5)
+        .await
+        .unwrap()
+        .is_empty());
+
+    store
+        .close_conversation(&conv_id, "Shared passport details")
+        .await
+        .unwrap();
+    let summaries = store
+        .get_recent_summaries("telegram", "u1", 5)
+        .await
+        .unwrap();
+    assert_eq!(summaries[0].0, "Shared passport details");
+
+    // Without the key, encrypted rows are an error rather than garbage.
+    let locked = store.clone().with_cipher(None);
+    assert!(locked.get_fact("u1", "city").await.is_err());
+}
+
+#[tokio::test]
+async fn test_rekey_encrypts_rotates_and_decrypts() {
+    let plain = test_store().await;
+    plain.store_fact("u1", "city", "Madrid").await.unwrap();
+    let conv_id = plain
+        .get_or_create_conversation("telegram", "u1", "")
+        .await
+        .unwrap();
+    plain.close_conversation(&conv_id, "Talked").await.unwrap();
+
+    let first = test_cipher();
+    assert_eq!(plain.rekey(Some(&first)).await.unwrap(), 2);
+    let encrypted = plain.clone().with_cipher(Some(first.clone()));
+    assert!(raw_fact(&encrypted, "city").await.starts_with("enc:v1:"));
+    assert_eq!(encrypted.rekey(Some(&first)).await.unwrap(), 0);
+
+    let second = test_cipher();
+    let before = raw_fact(&encrypted, "city").await;
+    assert_eq!(encrypted.rekey(Some(&second)).await.unwrap(), 2);
+    assert_ne!(raw_fact(&encrypted, "city").await, before);
+    let rotated = encrypted.with_cipher(Some(second));
+    assert_eq!(
+        rotated.get_fact("u1", "city").await.unwrap().as_deref(),
+        Some("Madrid")
+    );
+
+    assert_eq!(rotated.rekey(None).await.unwrap(), 2);
+    assert_eq!(raw_fact(&rotated, "city").await, "Madrid");
+}
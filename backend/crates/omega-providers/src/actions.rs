@@ -0,0 +1,347 @@
+//! Gateway actions as native tools.
+//!
+//! The same actions the system prompt describes as text markers (`SCHEDULE:`,
+//! `CANCEL_TASK:`, `REWARD:`, ...) exposed as typed `omega_*` tools for
+//! providers with function calling. Calls are not executed here: the
+//! [`ToolExecutor`](crate::tools::ToolExecutor) validates and records them,
+//! and the gateway applies them after the reply, through the same path as
+//! markers.
+
+use crate::tools::ToolDef;
+use serde_json::{json, Value};
+
+/// Name prefix shared by every action tool.
+pub const ACTION_TOOL_PREFIX: &str = "omega_";
+
+/// Appended to the system prompt when the action tools are offered.
+pub const ACTION_TOOLS_PROMPT: &str = "\n\n## Actions\n\
+    Reminders, scheduled actions, task changes, heartbeat items, rewards, lessons, \
+    language and personality changes, projects and the other actions described above \
+    are available as `omega_*` tools. Call the tool instead of writing the marker line \
+    (`SCHEDULE:`, `CANCEL_TASK:`, ...): marker lines in your reply are ignored. \
+    Omega carries the calls out after your reply and confirms the result to the user.";
+
+const REPEAT: [&str; 5] = ["once", "daily", "weekly", "monthly", "weekdays"];
+
+/// The action tool definitions, in the order the marker protocol lists them.
+pub fn action_tool_defs() -> Vec<ToolDef> {
+    let schedule = |what: &str| {
+        json!({
+            "type": "object",
+            "properties": {
+                "description": {"type": "string", "description": what},
+                "due_at": {
+                    "type": "string",
+                    "description": "When, as ISO 8601 local time (2026-03-01T09:00:00)"
+                },
+                "repeat": {"type": "string", "enum": REPEAT, "description": "Default: once"}
+            },
+            "required": ["description", "due_at"]
+        })
+    };
+    let text = |field: &str, what: &str| {
+        json!({
+            "type": "object",
+            "properties": {field: {"type": "string", "description": what}},
+            "required": [field]
+        })
+    };
+    let none = || json!({"type": "object", "properties": {}});
+
+    let defs = [
+        (
+            "schedule",
+            "Create a reminder that is delivered to the user as a message.",
+            schedule("What to remind the user of"),
+        ),
+        (
+            "schedule_action",
+            "Schedule a task that Omega executes autonomously at the given time.",
+            schedule("What Omega should do"),
+        ),
+        (
+            "cancel_task",
+            "Cancel a pending scheduled task.",
+            text("task_id", "Task id or unique id prefix"),
+        ),
+        (
+            "update_task",
+            "Change a pending scheduled task. Omitted fields are kept.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "task_id": {"type": "string", "description": "Task id or unique id prefix"},
+                    "description": {"type": "string"},
+                    "due_at": {"type": "string", "description": "ISO 8601 local time"},
+                    "repeat": {"type": "string", "enum": REPEAT}
+                },
+                "required": ["task_id"]
+            }),
+        ),
+        (
+            "heartbeat_add",
+            "Add an item to the user's heartbeat checklist.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "description": {"type": "string", "description": "What to check"},
+                    "cadence": {
+                        "type": "string",
+                        "description": "hourly, daily at HH:MM or weekly on <day> [HH:MM]; \
+                                        omit for every pulse"
+                    },
+                    "alert_on_change": {
+                        "type": "boolean",
+                        "description": "Only report when the result changes"
+                    }
+                },
+                "required": ["description"]
+            }),
+        ),
+        (
+            "heartbeat_remove",
+            "Remove an item from the user's heartbeat checklist.",
+            text("item_id", "Item id or unique id prefix"),
+        ),
+        (
+            "heartbeat_interval",
+            "Change how often the heartbeat runs.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "minutes": {"type": "integer", "minimum": 1, "maximum": 1440}
+                },
+                "required": ["minutes"]
+            }),
+        ),
+        (
+            "heartbeat_suppress_section",
+            "Stop reporting a section of the heartbeat file.",
+            text("section", "Section name (the ## header)"),
+        ),
+        (
+            "heartbeat_unsuppress_section",
+            "Resume reporting a suppressed heartbeat section.",
+            text("section", "Section name (the ## header)"),
+        ),
+        (
+            "reward",
+            "Record how well something worked for the user.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "score": {"type": "integer", "minimum": -1, "maximum": 1},
+                    "domain": {"type": "string", "description": "Short topic, e.g. training"},
+                    "lesson": {"type": "string", "description": "What was observed"}
+                },
+                "required": ["score", "domain", "lesson"]
+            }),
+        ),
+        (
+            "lesson",
+            "Store a lasting rule learned about the user.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "domain": {"type": "string"},
+                    "rule": {"type": "string"}
+                },
+                "required": ["domain", "rule"]
+            }),
+        ),
+        (
+            "lang_switch",
+            "Switch the language Omega uses with this user.",
+            text("language", "Language name in English, e.g. Spanish"),
+        ),
+        (
+            "personality",
+            "Change how Omega talks to this user.",
+            text("style", "The requested style, or \"reset\" for the default"),
+        ),
+        (
+            "project_activate",
+            "Activate one of the user's projects.",
+            text("name", "Project name"),
+        ),
+        (
+            "project_deactivate",
+            "Deactivate the current project.",
+            none(),
+        ),
+        (
+            "build_proposal",
+            "Propose a build; the user is asked to confirm it.",
+            text("description", "What to build"),
+        ),
+        (
+            "skill_improve",
+            "Append a lesson to a skill after a mistake with it.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "skill": {"type": "string", "description": "Skill name"},
+                    "lesson": {"type": "string"}
+                },
+                "required": ["skill", "lesson"]
+            }),
+        ),
+        (
+            "bug_report",
+            "Log a limitation or bug in Omega itself.",
+            text("description", "What went wrong"),
+        ),
+        (
+            "forget_conversation",
+            "Close the current conversation and start fresh.",
+            none(),
+        ),
+        (
+            "purge_facts",
+            "Delete everything Omega knows about the user (keeps system settings).",
+            none(),
+        ),
+        (
+            "whatsapp_setup",
+            "Start WhatsApp pairing (shows a QR code).",
+            none(),
+        ),
+        ("google_setup", "Start connecting a Google account.", none()),
+    ];
+
+    defs.into_iter()
+        .map(|(name, description, parameters)| ToolDef {
+            name: format!("{ACTION_TOOL_PREFIX}{name}"),
+            description: description.to_string(),
+            parameters,
+        })
+        .collect()
+}
+
+/// Check `args` against the tool's schema: required fields, types, enums and ranges.
+pub fn validate_action(def: &ToolDef, args: &Value) -> Result<(), String> {
+    let empty = serde_json::Map::new();
+    let args = match args {
+        Value::Object(map) => map,
+        Value::Null => &empty,
+        _ => return Err("arguments must be an object".to_string()),
+    };
+    let schema = &def.parameters;
+
+    for field in schema["required"].as_array().into_iter().flatten() {
+        let field = field.as_str().unwrap_or_default();
+        match args.get(field) {
+            None | Some(Value::Null) => return Err(format!("'{field}' is required")),
+            Some(Value::String(s)) if s.trim().is_empty() => {
+                return Err(format!("'{field}' must not be empty"))
+            }
+            _ => {}
+        }
+    }
+
+    let Some(properties) = schema["properties"].as_object() else {
+        return Ok(());
+    };
+    for (field, value) in args {
+        let Some(spec) = properties.get(field) else {
+            continue;
+        };
+        if value.is_null() {
+            continue;
+        }
+        let ok = match spec["type"].as_str() {
+            Some("string") => value.is_string(),
+            Some("integer") => value.as_i64().is_some(),
+            Some("boolean") => value.is_boolean(),
+            _ => true,
+        };
+        if !ok {
+            return Err(format!("'{field}' must be a {}", spec["type"]));
+        }
+        if let Some(allowed) = spec["enum"].as_array() {
+            let lower = value.as_str().unwrap_or_default().to_lowercase();
+            if !allowed.iter().any(|a| a.as_str() == Some(lower.as_str())) {
+                return Err(format!("'{field}' must be one of {}", spec["enum"]));
+            }
+        }
+        if let Some(n) = value.as_i64() {
+            let min = spec["minimum"].as_i64().unwrap_or(i64::MIN);
+            let max = spec["maximum"].as_i64().unwrap_or(i64::MAX);
+            if n < min || n > max {
+                return Err(format!("'{field}' must be between {min} and {max}"));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(name: &str) -> ToolDef {
+        action_tool_defs()
+            .into_iter()
+            .find(|d| d.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_action_tool_names_are_prefixed_and_unique() {
+        let defs = action_tool_defs();
+        let mut names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert!(names.iter().all(|n| n.starts_with(ACTION_TOOL_PREFIX)));
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), defs.len());
+    }
+
+    #[test]
+    fn test_validate_action_required_fields() {
+        let schedule = def("omega_schedule");
+        assert!(validate_action(
+            &schedule,
+            &json!({"description": "Call mom", "due_at": "2026-03-01T09:00:00"})
+        )
+        .is_ok());
+        let err = validate_action(&schedule, &json!({"description": "Call mom"})).unwrap_err();
+        assert!(err.contains("due_at"), "{err}");
+        let err =
+            validate_action(&schedule, &json!({"description": " ", "due_at": "x"})).unwrap_err();
+        assert!(err.contains("empty"), "{err}");
+        assert!(validate_action(&schedule, &json!("SCHEDULE: x")).is_err());
+        assert!(validate_action(&def("omega_purge_facts"), &Value::Null).is_ok());
+    }
+
+    #[test]
+    fn test_validate_action_types_enums_and_ranges() {
+        let schedule = def("omega_schedule");
+        let base = json!({"description": "d", "due_at": "2026-03-01T09:00:00"});
+        let mut bad = base.clone();
+        bad["repeat"] = json!("hourly");
+        assert!(validate_action(&schedule, &bad)
+            .unwrap_err()
+            .contains("one of"));
+        bad["repeat"] = json!("Daily");
+        assert!(validate_action(&schedule, &bad).is_ok());
+
+        let reward = def("omega_reward");
+        assert!(validate_action(
+            &reward,
+            &json!({"score": 1, "domain": "tone", "lesson": "brief"})
+        )
+        .is_ok());
+        assert!(validate_action(
+            &reward,
+            &json!({"score": 5, "domain": "tone", "lesson": "brief"})
+        )
+        .unwrap_err()
+        .contains("between"));
+        assert!(validate_action(
+            &reward,
+            &json!({"score": "+1", "domain": "tone", "lesson": "brief"})
+        )
+        .unwrap_err()
+        .contains("integer"));
+    }
+}
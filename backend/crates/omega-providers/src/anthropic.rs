@@ -2,15 +2,30 @@
 //!
 //! Calls the Anthropic Messages API directly (not via Claude Code CLI).
 //! Uses content blocks (text/tool_use/tool_result) for tool calling.
+//!
+//! Prompt caching puts `cache_control` breakpoints on the stable prefix: the
+//! tool definitions, the SYSTEM_PROMPT.md sections, the older history and the
+//! newest message of each agentic turn. Extended thinking can be enabled for
+//! `model_complex` calls; thinking blocks are echoed back during tool use but
+//! never reach the response text.
+//!
+//! Structured output is done by forcing a single tool whose input schema is
+//! the requested schema; its input becomes the response text.
 
 use async_trait::async_trait;
-use omega_core::{context::Context, error::OmegaError, message::OutgoingMessage, traits::Provider};
+use omega_core::{
+    context::{ApiMessage, ContentBlock, Context},
+    error::OmegaError,
+    message::OutgoingMessage,
+    structured::ResponseSchema,
+    traits::Provider,
+};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
-use crate::tools::{build_response, tools_enabled, ToolDef, ToolExecutor};
+use crate::tools::{agentic_system_prompt, build_response, tools_enabled, ToolDef, ToolExecutor};
 
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
@@ -18,6 +33,9 @@ const ANTHROPIC_VERSION: &str = "2023-06-01";
 /// Default max agentic loop iterations.
 const DEFAULT_MAX_TURNS: u32 = 50;
 
+/// Smallest thinking budget the API accepts.
+const MIN_THINKING_BUDGET: u32 = 1024;
+
 /// Anthropic Messages API provider.
 pub struct AnthropicProvider {
     client: reqwest::Client,
@@ -25,6 +43,10 @@ pub struct AnthropicProvider {
     model: String,
     max_tokens: u32,
     workspace_path: Option<PathBuf>,
+    prompt_caching: bool,
+    /// Model that gets extended thinking (empty = thinking off).
+    thinking_model: String,
+    thinking_budget: u32,
 }
 
 impl AnthropicProvider {
@@ -44,8 +66,81 @@ impl AnthropicProvider {
             model,
             max_tokens,
             workspace_path,
+            prompt_caching: true,
+            thinking_model: String::new(),
+            thinking_budget: 0,
+        })
+    }
+
+    /// Enable or disable `cache_control` breakpoints (on by default).
+    pub fn with_prompt_caching(mut self, enabled: bool) -> Self {
+        self.prompt_caching = enabled;
+        self
+    }
+
+    /// Enable extended thinking for calls to `model` with the given token budget.
+    ///
+    /// A budget of `0` disables thinking; smaller budgets are raised to the
+    /// API minimum of 1024 tokens.
+    pub fn with_thinking(mut self, model: String, budget: u32) -> Self {
+        self.thinking_model = model;
+        self.thinking_budget = if budget == 0 {
+            0
+        } else {
+            budget.max(MIN_THINKING_BUDGET)
+        };
+        self
+    }
+
+    /// Thinking settings for a call to `model`, if thinking applies.
+    fn thinking_for(&self, model: &str) -> Option<AnthropicThinking> {
+        (self.thinking_budget > 0 && model == self.thinking_model).then(|| AnthropicThinking {
+            thinking_type: "enabled".to_string(),
+            budget_tokens: self.thinking_budget,
         })
     }
+
+    /// Build a request body. The thinking budget comes on top of `max_tokens`,
+    /// and with caching on, the last message gets a rolling breakpoint.
+    fn request_body(
+        &self,
+        model: &str,
+        system: Option<AnthropicContent>,
+        messages: &[AnthropicMessage],
+        tools: Option<Vec<AnthropicToolDef>>,
+    ) -> AnthropicRequest {
+        let mut messages = messages.to_vec();
+        if self.prompt_caching {
+            if let Some(last) = messages.last_mut() {
+                mark_cache_breakpoint(last);
+            }
+        }
+        let thinking = self.thinking_for(model);
+        let max_tokens = match thinking {
+            Some(ref t) => self.max_tokens + t.budget_tokens,
+            None => self.max_tokens,
+        };
+        AnthropicRequest {
+            model: model.to_string(),
+            max_tokens,
+            system,
+            messages,
+            tools,
+            tool_choice: None,
+            thinking,
+        }
+    }
+
+    /// Initial messages; with caching on, the last history message is a breakpoint.
+    fn initial_messages(&self, api_messages: &[ApiMessage]) -> Vec<AnthropicMessage> {
+        let mut messages: Vec<AnthropicMessage> =
+            api_messages.iter().map(to_anthropic_message).collect();
+        if self.prompt_caching && messages.len() >= 2 {
+            let last_history = messages.len() - 2;
+            mark_cache_breakpoint(&mut messages[last_history]);
+        }
+        messages
+    }
 }
 
 // --- Serde types for the Anthropic Messages API ---
@@ -54,11 +149,66 @@ impl AnthropicProvider {
 struct AnthropicRequest {
     model: String,
     max_tokens: u32,
-    #[serde(skip_serializing_if = "String::is_empty")]
-    system: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<AnthropicContent>,
     messages: Vec<AnthropicMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<AnthropicToolDef>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<AnthropicToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<AnthropicThinking>,
+}
+
+impl AnthropicRequest {
+    /// Force a single tool taking `schema` as input. Extended thinking
+    /// cannot be combined with a forced tool, so it is dropped.
+    fn with_response_schema(mut self, schema: &ResponseSchema) -> Self {
+        if let Some(t) = self.thinking.take() {
+            self.max_tokens -= t.budget_tokens;
+        }
+        self.tools = Some(vec![AnthropicToolDef {
+            name: schema.name.clone(),
+            description: "Return the result.".to_string(),
+            input_schema: schema.schema.clone(),
+            cache_control: None,
+        }]);
+        self.tool_choice = Some(AnthropicToolChoice {
+            choice_type: "tool".to_string(),
+            name: schema.name.clone(),
+        });
+        self
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct AnthropicToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct AnthropicThinking {
+    #[serde(rename = "type")]
+    thinking_type: String,
+    budget_tokens: u32,
+}
+
+/// A `cache_control` breakpoint: everything up to and including the marked
+/// block is cached.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    cache_type: String,
+}
+
+impl CacheControl {
+    fn ephemeral() -> Option<Self> {
+        Some(Self {
+            cache_type: "ephemeral".to_string(),
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -81,7 +231,23 @@ enum AnthropicContent {
 #[serde(tag = "type")]
 enum AnthropicContentBlock {
     #[serde(rename = "text")]
-    Text { text: String },
+    Text {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+    #[serde(rename = "image")]
+    Image { source: AnthropicSource },
+    #[serde(rename = "document")]
+    Document {
+        source: AnthropicSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String, signature: String },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
     #[serde(rename = "tool_use")]
     ToolUse {
         id: String,
@@ -94,14 +260,37 @@ enum AnthropicContentBlock {
         content: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
 }
 
+/// Inline base64 data for image and document blocks.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct AnthropicSource {
+    #[serde(rename = "type")]
+    source_type: String,
+    media_type: String,
+    data: String,
+}
+
+impl AnthropicSource {
+    fn base64(media_type: String, data: String) -> Self {
+        Self {
+            source_type: "base64".to_string(),
+            media_type,
+            data,
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 struct AnthropicToolDef {
     name: String,
     description: String,
     input_schema: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
 }
 
 #[derive(Deserialize)]
@@ -118,6 +307,10 @@ struct AnthropicResponse {
 enum AnthropicResponseBlock {
     #[serde(rename = "text")]
     Text { text: String },
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String, signature: String },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
     #[serde(rename = "tool_use")]
     ToolUse {
         id: String,
@@ -132,17 +325,148 @@ struct AnthropicUsage {
     input_tokens: u64,
     #[serde(default)]
     output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+impl AnthropicUsage {
+    /// All tokens billed for the call, cached or not.
+    fn total(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_input_tokens
+            + self.cache_read_input_tokens
+    }
+}
+
+/// Cache hits and writes summed over the calls of one request.
+#[derive(Default)]
+struct CacheUsage {
+    read: u64,
+    write: u64,
 }
 
-/// Convert ToolDef to Anthropic format.
-fn to_anthropic_tools(defs: &[ToolDef]) -> Vec<AnthropicToolDef> {
-    defs.iter()
+impl CacheUsage {
+    fn add(&mut self, usage: &AnthropicUsage) {
+        self.read += usage.cache_read_input_tokens;
+        self.write += usage.cache_creation_input_tokens;
+    }
+
+    /// Record the totals on the response metadata.
+    fn apply(self, mut resp: OutgoingMessage) -> OutgoingMessage {
+        if self.read > 0 || self.write > 0 {
+            debug!(
+                "anthropic: prompt cache read={} write={}",
+                self.read, self.write
+            );
+        }
+        resp.metadata.cache_read_tokens = Some(self.read);
+        resp.metadata.cache_write_tokens = Some(self.write);
+        resp
+    }
+}
+
+/// Convert ToolDef to Anthropic format; with caching on, the last tool is a breakpoint.
+fn to_anthropic_tools(defs: &[ToolDef], caching: bool) -> Vec<AnthropicToolDef> {
+    let mut tools: Vec<AnthropicToolDef> = defs
+        .iter()
         .map(|d| AnthropicToolDef {
             name: d.name.clone(),
             description: d.description.clone(),
             input_schema: d.parameters.clone(),
+            cache_control: None,
         })
-        .collect()
+        .collect();
+    if caching {
+        if let Some(last) = tools.last_mut() {
+            last.cache_control = CacheControl::ephemeral();
+        }
+    }
+    tools
+}
+
+/// System prompt content. With caching on, the first `stable_len` bytes
+/// (the SYSTEM_PROMPT.md sections) become their own cached block.
+fn to_anthropic_system(system: &str, stable_len: usize, caching: bool) -> Option<AnthropicContent> {
+    if system.is_empty() {
+        return None;
+    }
+    if !caching || stable_len == 0 || !system.is_char_boundary(stable_len) {
+        return Some(AnthropicContent::Text(system.to_string()));
+    }
+    let (stable, rest) = system.split_at(stable_len);
+    let mut blocks = vec![AnthropicContentBlock::Text {
+        text: stable.to_string(),
+        cache_control: CacheControl::ephemeral(),
+    }];
+    if !rest.trim().is_empty() {
+        blocks.push(AnthropicContentBlock::Text {
+            text: rest.to_string(),
+            cache_control: None,
+        });
+    }
+    Some(AnthropicContent::Blocks(blocks))
+}
+
+/// Put a cache breakpoint on the last block of a message.
+///
+/// Plain text content is turned into a single text block. Messages ending in
+/// a block that cannot carry `cache_control` here are left unmarked.
+fn mark_cache_breakpoint(msg: &mut AnthropicMessage) {
+    if let AnthropicContent::Text(text) = &msg.content {
+        if text.is_empty() {
+            return;
+        }
+        msg.content = AnthropicContent::Blocks(vec![AnthropicContentBlock::Text {
+            text: text.clone(),
+            cache_control: None,
+        }]);
+    }
+    if let AnthropicContent::Blocks(blocks) = &mut msg.content {
+        match blocks.last_mut() {
+            Some(AnthropicContentBlock::Text { cache_control, .. })
+            | Some(AnthropicContentBlock::ToolResult { cache_control, .. }) => {
+                *cache_control = CacheControl::ephemeral();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert an API message; attachments turn it into content blocks.
+fn to_anthropic_message(m: &ApiMessage) -> AnthropicMessage {
+    let content = if m.media.is_empty() {
+        AnthropicContent::Text(m.content.clone())
+    } else {
+        AnthropicContent::Blocks(
+            m.content_blocks()
+                .into_iter()
+                .map(|block| match block {
+                    ContentBlock::Text { text } => AnthropicContentBlock::Text {
+                        text,
+                        cache_control: None,
+                    },
+                    ContentBlock::Image { media_type, data } => AnthropicContentBlock::Image {
+                        source: AnthropicSource::base64(media_type, data),
+                    },
+                    ContentBlock::Document {
+                        media_type,
+                        data,
+                        name,
+                    } => AnthropicContentBlock::Document {
+                        source: AnthropicSource::base64(media_type, data),
+                        title: Some(name),
+                    },
+                })
+                .collect(),
+        )
+    };
+    AnthropicMessage {
+        role: m.role.clone(),
+        content,
+    }
 }
 
 #[async_trait]
@@ -164,13 +488,20 @@ impl Provider for AnthropicProvider {
 
         if has_tools {
             if let Some(ref ws) = self.workspace_path {
-                let mut executor = ToolExecutor::new(ws.clone());
+                let mut executor = ToolExecutor::new(ws.clone())
+                    .with_workspace(context.workspace.clone())
+                    .with_denied_tools(&context.denied_tools)
+                    .with_approval(context.approval.clone())
+                    .with_actions(context.action_tools);
                 executor.connect_mcp_servers(&context.mcp_servers).await;
+                let system = agentic_system_prompt(&system, context);
+                let system =
+                    to_anthropic_system(&system, context.stable_prefix_len, self.prompt_caching);
 
                 let result = self
                     .agentic_loop(
                         effective_model,
-                        &system,
+                        system,
                         &api_messages,
                         &mut executor,
                         max_turns,
@@ -178,27 +509,18 @@ impl Provider for AnthropicProvider {
                     .await;
 
                 executor.shutdown_mcp().await;
-                return result;
+                return result.map(|resp| executor.finish(resp));
             }
         }
 
         // Fallback: no tools.
         let start = Instant::now();
-        let messages: Vec<AnthropicMessage> = api_messages
-            .iter()
-            .map(|m| AnthropicMessage {
-                role: m.role.clone(),
-                content: AnthropicContent::Text(m.content.clone()),
-            })
-            .collect();
-
-        let body = AnthropicRequest {
-            model: effective_model.to_string(),
-            max_tokens: self.max_tokens,
-            system,
-            messages,
-            tools: None,
-        };
+        let messages = self.initial_messages(&api_messages);
+        let system = to_anthropic_system(&system, context.stable_prefix_len, self.prompt_caching);
+        let mut body = self.request_body(effective_model, system, &messages, None);
+        if let Some(ref schema) = context.response_schema {
+            body = body.with_response_schema(schema);
+        }
 
         debug!("anthropic: POST {ANTHROPIC_API_URL} model={effective_model} (no tools)");
 
@@ -225,21 +547,26 @@ impl Provider for AnthropicProvider {
             OmegaError::Provider(format!("anthropic: failed to parse response: {e}"))
         })?;
 
-        let text = extract_text_from_response(&parsed);
-        let tokens = parsed
-            .usage
-            .as_ref()
-            .map(|u| u.input_tokens + u.output_tokens)
-            .unwrap_or(0);
+        let text = match context.response_schema {
+            Some(_) => {
+                extract_tool_input(&parsed).unwrap_or_else(|| extract_text_from_response(&parsed))
+            }
+            None => extract_text_from_response(&parsed),
+        };
+        let tokens = parsed.usage.as_ref().map(|u| u.total()).unwrap_or(0);
+        let mut cache = CacheUsage::default();
+        if let Some(ref u) = parsed.usage {
+            cache.add(u);
+        }
         let elapsed_ms = start.elapsed().as_millis() as u64;
 
-        Ok(build_response(
+        Ok(cache.apply(build_response(
             text,
             "anthropic",
             tokens,
             elapsed_ms,
             parsed.model,
-        ))
+        )))
     }
 
     async fn is_available(&self) -> bool {
@@ -256,39 +583,28 @@ impl AnthropicProvider {
     async fn agentic_loop(
         &self,
         model: &str,
-        system: &str,
-        api_messages: &[omega_core::context::ApiMessage],
+        system: Option<AnthropicContent>,
+        api_messages: &[ApiMessage],
         executor: &mut ToolExecutor,
         max_turns: u32,
     ) -> Result<OutgoingMessage, OmegaError> {
         let start = Instant::now();
 
-        let mut messages: Vec<AnthropicMessage> = api_messages
-            .iter()
-            .map(|m| AnthropicMessage {
-                role: m.role.clone(),
-                content: AnthropicContent::Text(m.content.clone()),
-            })
-            .collect();
+        let mut messages = self.initial_messages(api_messages);
 
         let all_tool_defs = executor.all_tool_defs();
         let tools = if all_tool_defs.is_empty() {
             None
         } else {
-            Some(to_anthropic_tools(&all_tool_defs))
+            Some(to_anthropic_tools(&all_tool_defs, self.prompt_caching))
         };
 
         let mut last_model: Option<String> = None;
         let mut total_tokens: u64 = 0;
+        let mut cache = CacheUsage::default();
 
         for turn in 0..max_turns {
-            let body = AnthropicRequest {
-                model: model.to_string(),
-                max_tokens: self.max_tokens,
-                system: system.to_string(),
-                messages: messages.clone(),
-                tools: tools.clone(),
-            };
+            let body = self.request_body(model, system.clone(), &messages, tools.clone());
 
             debug!("anthropic: POST {ANTHROPIC_API_URL} model={model} turn={turn}");
 
@@ -319,7 +635,8 @@ impl AnthropicProvider {
                 last_model = Some(m.clone());
             }
             if let Some(ref u) = parsed.usage {
-                total_tokens += u.input_tokens + u.output_tokens;
+                total_tokens += u.total();
+                cache.add(u);
             }
 
             // Check for tool_use in response.
@@ -327,15 +644,32 @@ impl AnthropicProvider {
             let blocks = parsed.content.unwrap_or_default();
 
             if has_tool_use {
-                // Build the assistant message with response blocks.
+                // Build the assistant message with response blocks. Thinking
+                // blocks must be sent back unchanged alongside the tool calls.
                 let mut assistant_blocks: Vec<AnthropicContentBlock> = Vec::new();
                 let mut tool_result_blocks: Vec<AnthropicContentBlock> = Vec::new();
 
                 for block in &blocks {
                     match block {
                         AnthropicResponseBlock::Text { text } => {
-                            assistant_blocks
-                                .push(AnthropicContentBlock::Text { text: text.clone() });
+                            assistant_blocks.push(AnthropicContentBlock::Text {
+                                text: text.clone(),
+                                cache_control: None,
+                            });
+                        }
+                        AnthropicResponseBlock::Thinking {
+                            thinking,
+                            signature,
+                        } => {
+                            assistant_blocks.push(AnthropicContentBlock::Thinking {
+                                thinking: thinking.clone(),
+                                signature: signature.clone(),
+                            });
+                        }
+                        AnthropicResponseBlock::RedactedThinking { data } => {
+                            assistant_blocks.push(AnthropicContentBlock::RedactedThinking {
+                                data: data.clone(),
+                            });
                         }
                         AnthropicResponseBlock::ToolUse { id, name, input } => {
                             assistant_blocks.push(AnthropicContentBlock::ToolUse {
@@ -352,6 +686,7 @@ impl AnthropicProvider {
                                 tool_use_id: id.clone(),
                                 content: result.content,
                                 is_error: if result.is_error { Some(true) } else { None },
+                                cache_control: None,
                             });
                         }
                     }
@@ -370,7 +705,7 @@ impl AnthropicProvider {
                 continue;
             }
 
-            // Text-only response.
+            // Text-only response (thinking blocks are dropped).
             let text = blocks
                 .iter()
                 .filter_map(|b| match b {
@@ -387,28 +722,28 @@ impl AnthropicProvider {
             };
 
             let elapsed_ms = start.elapsed().as_millis() as u64;
-            return Ok(build_response(
+            return Ok(cache.apply(build_response(
                 text,
                 "anthropic",
                 total_tokens,
                 elapsed_ms,
                 last_model,
-            ));
+            )));
         }
 
         // Max turns exhausted.
         let elapsed_ms = start.elapsed().as_millis() as u64;
-        Ok(build_response(
+        Ok(cache.apply(build_response(
             format!("anthropic: reached max turns ({max_turns}) without final response"),
             "anthropic",
             total_tokens,
             elapsed_ms,
             last_model,
-        ))
+        )))
     }
 }
 
-/// Extract text from an Anthropic response.
+/// Extract text from an Anthropic response (thinking blocks are dropped).
 fn extract_text_from_response(resp: &AnthropicResponse) -> String {
     resp.content
         .as_ref()
@@ -426,6 +761,14 @@ fn extract_text_from_response(resp: &AnthropicResponse) -> String {
         .unwrap_or_else(|| "No response from Anthropic.".to_string())
 }
 
+/// The forced tool's input, serialized, for structured-output calls.
+fn extract_tool_input(resp: &AnthropicResponse) -> Option<String> {
+    resp.content.as_ref()?.iter().find_map(|b| match b {
+        AnthropicResponseBlock::ToolUse { input, .. } => Some(input.to_string()),
+        _ => None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,12 +791,14 @@ mod tests {
         let body = AnthropicRequest {
             model: "claude-sonnet-4-20250514".into(),
             max_tokens: 8192,
-            system: "Be helpful.".into(),
+            system: Some(AnthropicContent::Text("Be helpful.".into())),
             messages: vec![AnthropicMessage {
                 role: "user".into(),
                 content: AnthropicContent::Text("Hello".into()),
             }],
             tools: None,
+            tool_choice: None,
+            thinking: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert_eq!(json["model"], "claude-sonnet-4-20250514");
@@ -468,12 +813,14 @@ mod tests {
         let body = AnthropicRequest {
             model: "claude-sonnet-4-20250514".into(),
             max_tokens: 8192,
-            system: String::new(),
+            system: to_anthropic_system("", 0, true),
             messages: vec![AnthropicMessage {
                 role: "user".into(),
                 content: AnthropicContent::Text("Hello".into()),
             }],
             tools: None,
+            tool_choice: None,
+            thinking: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert!(json.get("system").is_none());
@@ -513,20 +860,24 @@ mod tests {
     #[test]
     fn test_anthropic_request_with_tools() {
         let defs = crate::tools::builtin_tool_defs();
-        let tools = to_anthropic_tools(&defs);
+        let tools = to_anthropic_tools(&defs, false);
         let body = AnthropicRequest {
             model: "claude-sonnet-4-20250514".into(),
             max_tokens: 8192,
-            system: "test".into(),
+            system: Some(AnthropicContent::Text("test".into())),
             messages: vec![AnthropicMessage {
                 role: "user".into(),
                 content: AnthropicContent::Text("list files".into()),
             }],
             tools: Some(tools),
+            tool_choice: None,
+            thinking: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert_eq!(json["tools"].as_array().unwrap().len(), 4);
         assert_eq!(json["tools"][0]["name"], "bash");
+        assert!(json["tools"][3].get("cache_control").is_none());
+        assert!(json.get("thinking").is_none());
     }
 
     #[test]
@@ -537,6 +888,7 @@ mod tests {
                 tool_use_id: "toolu_123".into(),
                 content: "file1.txt\nfile2.txt".into(),
                 is_error: None,
+                cache_control: None,
             }]),
         };
         let json = serde_json::to_value(&msg).unwrap();
@@ -545,4 +897,191 @@ mod tests {
         assert_eq!(blocks[0]["type"], "tool_result");
         assert_eq!(blocks[0]["tool_use_id"], "toolu_123");
     }
+
+    #[test]
+    fn test_anthropic_message_with_media() {
+        let plain = to_anthropic_message(&ApiMessage {
+            role: "user".into(),
+            content: "Hi".into(),
+            media: Vec::new(),
+        });
+        assert_eq!(serde_json::to_value(&plain).unwrap()["content"], "Hi");
+
+        let msg = to_anthropic_message(&ApiMessage {
+            role: "user".into(),
+            content: "What is this?".into(),
+            media: vec![
+                ContentBlock::Image {
+                    media_type: "image/png".into(),
+                    data: "iVBORw0K".into(),
+                },
+                ContentBlock::Document {
+                    media_type: "application/pdf".into(),
+                    data: "JVBERi0x".into(),
+                    name: "invoice.pdf".into(),
+                },
+            ],
+        });
+        let json = serde_json::to_value(&msg).unwrap();
+        let blocks = json["content"].as_array().unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0]["type"], "image");
+        assert_eq!(blocks[0]["source"]["type"], "base64");
+        assert_eq!(blocks[0]["source"]["media_type"], "image/png");
+        assert_eq!(blocks[0]["source"]["data"], "iVBORw0K");
+        assert_eq!(blocks[1]["type"], "document");
+        assert_eq!(blocks[1]["title"], "invoice.pdf");
+        assert_eq!(blocks[2]["type"], "text");
+        assert_eq!(blocks[2]["text"], "What is this?");
+    }
+
+    fn provider() -> AnthropicProvider {
+        AnthropicProvider::from_config(
+            "sk-ant-test".into(),
+            "claude-sonnet-4-20250514".into(),
+            8192,
+            None,
+        )
+        .unwrap()
+    }
+
+    fn user(text: &str) -> ApiMessage {
+        ApiMessage {
+            role: "user".into(),
+            content: text.into(),
+            media: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_anthropic_system_cache_split() {
+        let system = "Rules section.\n\nCurrent time: 10:00";
+        let stable = "Rules section.".len();
+
+        let json = serde_json::to_value(to_anthropic_system(system, stable, true)).unwrap();
+        let blocks = json.as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["text"], "Rules section.");
+        assert_eq!(blocks[0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(blocks[1]["text"], "\n\nCurrent time: 10:00");
+        assert!(blocks[1].get("cache_control").is_none());
+
+        // Caching off, or no known stable prefix: plain string.
+        let json = serde_json::to_value(to_anthropic_system(system, stable, false)).unwrap();
+        assert_eq!(json, system);
+        let json = serde_json::to_value(to_anthropic_system(system, 0, true)).unwrap();
+        assert_eq!(json, system);
+    }
+
+    #[test]
+    fn test_anthropic_tools_cache_breakpoint() {
+        let defs = crate::tools::builtin_tool_defs();
+        let json = serde_json::to_value(to_anthropic_tools(&defs, true)).unwrap();
+        let tools = json.as_array().unwrap();
+        assert!(tools[0].get("cache_control").is_none());
+        assert_eq!(tools.last().unwrap()["cache_control"]["type"], "ephemeral");
+    }
+
+    #[test]
+    fn test_anthropic_history_and_rolling_breakpoints() {
+        let p = provider();
+        let api_msgs = vec![
+            user("Hi"),
+            ApiMessage {
+                role: "assistant".into(),
+                content: "Hello!".into(),
+                media: Vec::new(),
+            },
+            user("How are you?"),
+        ];
+        let messages = p.initial_messages(&api_msgs);
+        let body = p.request_body("claude-sonnet-4-20250514", None, &messages, None);
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["messages"][0]["content"], "Hi");
+        assert_eq!(
+            json["messages"][1]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+        assert_eq!(
+            json["messages"][2]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+        // The rolling breakpoint is added per request, not to the kept messages.
+        assert!(matches!(messages[2].content, AnthropicContent::Text(_)));
+
+        let p = p.with_prompt_caching(false);
+        let body = p.request_body(
+            "claude-sonnet-4-20250514",
+            None,
+            &p.initial_messages(&api_msgs),
+            None,
+        );
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["messages"][1]["content"], "Hello!");
+        assert_eq!(json["messages"][2]["content"], "How are you?");
+    }
+
+    #[test]
+    fn test_anthropic_thinking_only_for_complex_model() {
+        let p = provider().with_thinking("claude-opus-4-20250514".into(), 500);
+        let body = p.request_body("claude-opus-4-20250514", None, &[], None);
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["thinking"]["type"], "enabled");
+        assert_eq!(json["thinking"]["budget_tokens"], MIN_THINKING_BUDGET);
+        assert_eq!(json["max_tokens"], 8192 + MIN_THINKING_BUDGET);
+
+        let body = p.request_body("claude-sonnet-4-20250514", None, &[], None);
+        let json = serde_json::to_value(&body).unwrap();
+        assert!(json.get("thinking").is_none());
+        assert_eq!(json["max_tokens"], 8192);
+
+        let off = provider().with_thinking("claude-opus-4-20250514".into(), 0);
+        assert!(off.thinking_for("claude-opus-4-20250514").is_none());
+    }
+
+    #[test]
+    fn test_anthropic_response_schema_forces_tool() {
+        let p = provider().with_thinking("claude-opus-4-20250514".into(), 2048);
+        let schema = ResponseSchema::new(
+            "summary",
+            serde_json::json!({"type": "object", "properties": {"summary": {"type": "string"}}}),
+        );
+        let body = p
+            .request_body("claude-opus-4-20250514", None, &[], None)
+            .with_response_schema(&schema);
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["tool_choice"]["type"], "tool");
+        assert_eq!(json["tool_choice"]["name"], "summary");
+        assert_eq!(json["tools"][0]["name"], "summary");
+        assert_eq!(json["tools"][0]["input_schema"]["type"], "object");
+        assert!(json.get("thinking").is_none());
+        assert_eq!(json["max_tokens"], 8192);
+
+        let resp: AnthropicResponse = serde_json::from_str(
+            r#"{"content":[{"type":"tool_use","id":"t1","name":"summary","input":{"summary":"ok"}}],"model":"m","usage":{"input_tokens":1,"output_tokens":1}}"#,
+        )
+        .unwrap();
+        assert_eq!(extract_tool_input(&resp).unwrap(), r#"{"summary":"ok"}"#);
+    }
+
+    #[test]
+    fn test_anthropic_thinking_response_excluded_from_text() {
+        let json = r#"{"content":[{"type":"thinking","thinking":"Let me reason...","signature":"sig"},{"type":"redacted_thinking","data":"xyz"},{"type":"text","text":"The answer is 4."}],"model":"claude-opus-4-20250514","usage":{"input_tokens":10,"output_tokens":50,"cache_creation_input_tokens":1000,"cache_read_input_tokens":3000},"stop_reason":"end_turn"}"#;
+        let resp: AnthropicResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(extract_text_from_response(&resp), "The answer is 4.");
+
+        let usage = resp.usage.as_ref().unwrap();
+        assert_eq!(usage.total(), 4060);
+        let mut cache = CacheUsage::default();
+        cache.add(usage);
+        let out = cache.apply(build_response(
+            "x".into(),
+            "anthropic",
+            usage.total(),
+            1,
+            None,
+        ));
+        assert_eq!(out.metadata.cache_read_tokens, Some(3000));
+        assert_eq!(out.metadata.cache_write_tokens, Some(1000));
+    }
 }
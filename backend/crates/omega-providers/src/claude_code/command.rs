@@ -1,7 +1,14 @@
 //! CLI command building and subprocess execution.
 
+use super::stream::{CliOutput, CliStream};
 use super::ClaudeCodeProvider;
+use omega_core::context::ProgressSink;
+use omega_core::crypto::{MaterializedSecret, GOOGLE_SECRET};
 use omega_core::error::OmegaError;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::process::Command;
 use tracing::debug;
 
@@ -41,8 +48,10 @@ impl ClaudeCodeProvider {
         args.push("-p".to_string());
         args.push(prompt.to_string());
 
+        // stream-json requires --verbose in print mode.
         args.push("--output-format".to_string());
-        args.push("json".to_string());
+        args.push("stream-json".to_string());
+        args.push("--verbose".to_string());
 
         args.push("--max-turns".to_string());
         args.push(max_turns.to_string());
@@ -66,13 +75,14 @@ impl ClaudeCodeProvider {
         // cannot prompt for approval — tools must be pre-approved or
         // permissions bypassed entirely.
         //
-        // - Agent mode → always bypass (agent frontmatter controls tools).
+        // - Agent mode, no whitelist → bypass (agent frontmatter controls tools).
         // - `context_disabled_tools` = caller wants NO tools (classification).
         // - `allowed_tools` empty = full access intended → bypass.
-        // - `allowed_tools` non-empty = explicit whitelist → pre-approve only those.
-        if use_agent {
+        // - `allowed_tools` non-empty = explicit whitelist → pre-approve only those
+        //   (also in agent mode, e.g. a topology phase restricting its tools).
+        if use_agent && allowed_tools.is_empty() {
             args.push("--dangerously-skip-permissions".to_string());
-        } else if context_disabled_tools {
+        } else if context_disabled_tools && !use_agent {
             args.push("--allowedTools".to_string());
             args.push(String::new());
         } else if allowed_tools.is_empty() {
@@ -96,6 +106,48 @@ impl ClaudeCodeProvider {
         args
     }
 
+    /// Build the CLI arguments that confine a tenant run to its tenant dir.
+    ///
+    /// `workspace` is a tenant workspace (`{data_dir}/tenants/<name>/workspace`).
+    /// The tenant dir is added with `--add-dir`; other tenants' dirs are denied
+    /// for reading and editing, and the shared `workspace/`, `skills/` and
+    /// `projects/` for editing. Bash can't be confined by path rules, so the
+    /// gateway denies it to tenant members (`Context::denied_tools`).
+    /// Empty for the shared workspace.
+    pub(super) fn tenant_args(data_dir: &Path, workspace: Option<&Path>) -> Vec<String> {
+        let tenants = data_dir.join("tenants");
+        let Some(root) = workspace
+            .and_then(Path::parent)
+            .filter(|r| r.parent() == Some(tenants.as_path()))
+        else {
+            return Vec::new();
+        };
+        // `//abs/path` is the CLI's syntax for an absolute path rule.
+        let rule = |tool: &str, dir: &Path| format!("{tool}(/{}/**)", dir.display());
+        let mut args = vec!["--add-dir".to_string(), root.display().to_string()];
+        let mut others: Vec<_> = std::fs::read_dir(&tenants)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir() && p != root)
+                    .collect()
+            })
+            .unwrap_or_default();
+        others.sort();
+        for dir in &others {
+            args.extend(["--disallowedTools".to_string(), rule("Read", dir)]);
+            args.extend(["--disallowedTools".to_string(), rule("Edit", dir)]);
+        }
+        for shared in ["workspace", "skills", "projects"] {
+            args.extend([
+                "--disallowedTools".to_string(),
+                rule("Edit", &data_dir.join(shared)),
+            ]);
+        }
+        args
+    }
+
     /// Run the claude CLI subprocess with a timeout.
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn run_cli(
@@ -108,8 +160,11 @@ impl ClaudeCodeProvider {
         context_disabled_tools: bool,
         session_id: Option<&str>,
         agent_name: Option<&str>,
-    ) -> Result<std::process::Output, OmegaError> {
-        let mut cmd = self.base_command();
+        disallowed_tools: &[String],
+        workspace: Option<&Path>,
+        progress: Option<&ProgressSink>,
+    ) -> Result<CliOutput, OmegaError> {
+        let mut cmd = self.base_command(workspace);
 
         let args = Self::build_run_cli_args(
             prompt,
@@ -122,6 +177,9 @@ impl ClaudeCodeProvider {
             agent_name,
         );
         cmd.args(&args);
+        for rule in disallowed_tools {
+            cmd.arg("--disallowedTools").arg(rule);
+        }
 
         debug!(
             "executing: claude {}",
@@ -131,10 +189,11 @@ impl ClaudeCodeProvider {
                 "-p <prompt>"
             }
         );
-        self.execute_with_timeout(cmd, "claude CLI").await
+        self.execute_streaming(cmd, "claude CLI", progress).await
     }
 
     /// Run the claude CLI subprocess with a specific session ID (for auto-resume).
+    #[allow(clippy::too_many_arguments)]
     pub(super) async fn run_cli_with_session(
         &self,
         prompt: &str,
@@ -143,13 +202,17 @@ impl ClaudeCodeProvider {
         max_turns: u32,
         allowed_tools: &[String],
         model: &str,
-    ) -> Result<std::process::Output, OmegaError> {
-        let mut cmd = self.base_command();
+        disallowed_tools: &[String],
+        workspace: Option<&Path>,
+        progress: Option<&ProgressSink>,
+    ) -> Result<CliOutput, OmegaError> {
+        let mut cmd = self.base_command(workspace);
 
         cmd.arg("-p")
             .arg(prompt)
             .arg("--output-format")
-            .arg("json")
+            .arg("stream-json")
+            .arg("--verbose")
             .arg("--max-turns")
             .arg(max_turns.to_string())
             .arg("--resume")
@@ -175,20 +238,29 @@ impl ClaudeCodeProvider {
                 cmd.arg("--allowedTools").arg(tool);
             }
         }
+        for rule in disallowed_tools {
+            cmd.arg("--disallowedTools").arg(rule);
+        }
 
         debug!("executing: claude -p <resume> --resume {session_id}");
-        self.execute_with_timeout(cmd, "claude CLI resume").await
+        self.execute_streaming(cmd, "claude CLI resume", progress)
+            .await
     }
 
     /// Build the base `Command` with working directory and system protection.
-    fn base_command(&self) -> Command {
+    ///
+    /// `workspace` overrides the configured working directory (a tenant's
+    /// workspace); the protected data dir is always the configured one's parent.
+    fn base_command(&self, workspace: Option<&Path>) -> Command {
         let mut cmd = match self.working_dir {
-            Some(ref dir) => {
+            Some(ref default_dir) => {
                 // Protection blocks writes to data dir (parent of workspace)
                 // so memory.db is safe, but skills, projects, etc. are writable.
-                let data_dir = dir.parent().unwrap_or(dir);
+                let data_dir = default_dir.parent().unwrap_or(default_dir);
+                let dir = workspace.unwrap_or(default_dir);
                 let mut c = omega_sandbox::protected_command("claude", data_dir);
                 c.current_dir(dir);
+                c.args(Self::tenant_args(data_dir, workspace));
                 // Expose stores dir so tools like omg-gog find credentials.
                 c.env("OMEGA_STORES_DIR", data_dir.join("stores"));
                 c
@@ -197,6 +269,11 @@ impl ClaudeCodeProvider {
         };
         // Remove CLAUDECODE env var so the CLI doesn't think it's nested.
         cmd.env_remove("CLAUDECODE");
+        // Kill the CLI if the caller drops the future (e.g. a per-phase timeout).
+        cmd.kill_on_drop(true);
+        // Own process group, so MCP servers the CLI starts can be killed with it.
+        #[cfg(unix)]
+        cmd.process_group(0);
         // Inject OAuth token if configured.
         if let Some(ref token) = self.oauth_token {
             cmd.env("CLAUDE_CODE_OAUTH_TOKEN", token);
@@ -204,30 +281,119 @@ impl ClaudeCodeProvider {
         cmd
     }
 
-    /// Execute a command with the configured timeout and standard error handling.
-    async fn execute_with_timeout(
+    /// Write `stores/google.json` from the secrets store for the lifetime of
+    /// one CLI run, so omg-gog finds it without a plaintext copy at rest.
+    fn google_credentials(&self) -> Option<MaterializedSecret> {
+        let secrets = self.secrets.as_ref()?;
+        let default_dir = self.working_dir.as_ref()?;
+        let data_dir = default_dir.parent().unwrap_or(default_dir);
+        secrets.materialize(GOOGLE_SECRET, &data_dir.join("stores").join("google.json"))
+    }
+
+    /// Run a command with the configured timeout, reading its stream-json
+    /// output line by line and reporting tool use to `progress`.
+    ///
+    /// On timeout, a read error or cancellation (the future is dropped) the
+    /// CLI's whole process group is killed, MCP servers included.
+    async fn execute_streaming(
         &self,
         mut cmd: Command,
         label: &str,
-    ) -> Result<std::process::Output, OmegaError> {
-        let output = tokio::time::timeout(self.timeout, cmd.output())
-            .await
-            .map_err(|_| {
-                OmegaError::Provider(format!(
+        progress: Option<&ProgressSink>,
+    ) -> Result<CliOutput, OmegaError> {
+        let _google = self.google_credentials();
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| OmegaError::Provider(format!("failed to run {label}: {e}")))?;
+        let mut group = ProcessGroupGuard(child.id());
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| OmegaError::Provider(format!("{label}: no stdout")))?;
+        // Drain stderr concurrently so a chatty CLI never blocks on a full pipe.
+        let stderr_task = child.stderr.take().map(|mut stderr| {
+            tokio::spawn(async move {
+                let mut buf = String::new();
+                let _ = stderr.read_to_string(&mut buf).await;
+                buf
+            })
+        });
+
+        let mut stream = CliStream::new(Instant::now());
+        let mut lines = BufReader::new(stdout).lines();
+        let read = async {
+            while let Some(line) = lines.next_line().await? {
+                if let Some(status) = stream.push(&line, Instant::now()) {
+                    if let Some(sink) = progress {
+                        sink.0.report(&status).await;
+                    }
+                }
+            }
+            child.wait().await
+        };
+        let result = tokio::time::timeout(self.timeout, read).await;
+
+        let status = match result {
+            Ok(Ok(status)) => status,
+            Ok(Err(e)) => {
+                // Kill the group before reaping, while its id is still ours.
+                drop(group);
+                let _ = child.kill().await;
+                return Err(OmegaError::Provider(format!("failed to run {label}: {e}")));
+            }
+            Err(_) => {
+                drop(group);
+                let _ = child.kill().await;
+                return Err(OmegaError::Provider(format!(
                     "{label} timed out after {}s",
                     self.timeout.as_secs()
-                ))
-            })?
-            .map_err(|e| OmegaError::Provider(format!("failed to run {label}: {e}")))?;
+                )));
+            }
+        };
+        // Exited on its own: the pid may be reused, so leave the group alone.
+        group.disarm();
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if !status.success() {
+            let stderr = match stderr_task {
+                Some(task) => task.await.unwrap_or_default(),
+                None => String::new(),
+            };
             return Err(OmegaError::Provider(format!(
-                "{label} exited with {}: {stderr}",
-                output.status
+                "{label} exited with {status}: {stderr}"
             )));
         }
 
-        Ok(output)
+        Ok(stream.finish())
+    }
+}
+
+/// Kills the CLI's process group when dropped, unless disarmed after a normal exit.
+struct ProcessGroupGuard(Option<u32>);
+
+impl ProcessGroupGuard {
+    fn disarm(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for ProcessGroupGuard {
+    fn drop(&mut self) {
+        if let Some(pid) = self.0 {
+            kill_process_group(pid);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(pid: u32) {
+    // SAFETY: plain syscall; the CLI leads its own group (`process_group(0)`).
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
     }
 }
+
+#[cfg(not(unix))]
+fn kill_process_group(_pid: u32) {}
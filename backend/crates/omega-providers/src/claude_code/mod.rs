@@ -2,17 +2,22 @@
 //!
 //! Uses the locally installed `claude` CLI as a subprocess.
 //! Zero API keys needed — relies on the user's existing `claude` authentication.
+//! Output is read as stream-json, so tool use is reported as live progress
+//! and token usage is captured per turn.
 
 mod command;
 mod mcp;
 mod provider;
 mod response;
+mod stream;
 
 #[cfg(test)]
 mod tests;
 
+use omega_core::crypto::SecretStore;
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
 
@@ -37,9 +42,12 @@ pub struct ClaudeCodeProvider {
     model: String,
     /// OAuth token injected as `CLAUDE_CODE_OAUTH_TOKEN` env var.
     oauth_token: Option<String>,
+    /// Encrypted secrets; Google credentials are written out per invocation.
+    secrets: Option<Arc<SecretStore>>,
 }
 
-/// JSON response from `claude -p --output-format json`.
+/// Final `result` event of `claude -p --output-format stream-json` (the same
+/// shape as the `--output-format json` response).
 #[derive(Debug, Deserialize)]
 struct ClaudeCliResponse {
     /// "result"
@@ -72,6 +80,7 @@ impl ClaudeCodeProvider {
             max_resume_attempts: 5,
             model: String::new(),
             oauth_token: None,
+            secrets: None,
         }
     }
 
@@ -93,9 +102,17 @@ impl ClaudeCodeProvider {
             max_resume_attempts,
             model,
             oauth_token,
+            secrets: None,
         }
     }
 
+    /// Read Google credentials from the encrypted secrets store instead of
+    /// a standing `stores/google.json`.
+    pub fn with_secrets(mut self, secrets: Option<Arc<SecretStore>>) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
     /// Check if the `claude` CLI is installed and accessible.
     pub async fn check_cli() -> bool {
         Command::new("claude")
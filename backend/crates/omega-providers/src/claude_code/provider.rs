@@ -1,12 +1,15 @@
 //! Provider trait implementation with auto-resume logic.
 
+use super::stream::{total_usage, CliOutput, TurnUsage};
 use super::{mcp, ClaudeCliResponse, ClaudeCodeProvider};
 use async_trait::async_trait;
 use omega_core::{
+    context::ProgressSink,
     error::OmegaError,
     message::{MessageMetadata, OutgoingMessage},
     traits::Provider,
 };
+use std::path::Path;
 use std::time::{Duration, Instant};
 use tracing::{info, warn};
 
@@ -27,9 +30,12 @@ impl Provider for ClaudeCodeProvider {
         let prompt = context.to_prompt_string();
         let start = Instant::now();
 
+        // Per-request workspace (tenant) overrides the configured working dir.
+        let workspace = context.workspace.as_deref();
+
         // Write MCP settings if any servers are declared.
         let mcp_settings_path = if !context.mcp_servers.is_empty() {
-            if let Some(ref dir) = self.working_dir {
+            if let Some(dir) = workspace.or(self.working_dir.as_deref()) {
                 match mcp::write_mcp_settings(dir, &context.mcp_servers) {
                     Ok(path) => Some(path),
                     Err(e) => {
@@ -45,14 +51,21 @@ impl Provider for ClaudeCodeProvider {
         };
 
         let extra_tools = mcp::mcp_tool_patterns(&context.mcp_servers);
+        let deny_rules = context.denied_tools.clone();
 
         // Resolve effective max_turns, allowed_tools, and model from context overrides.
         let effective_max_turns = context.max_turns.unwrap_or(self.max_turns);
         let tools_disabled = matches!(&context.allowed_tools, Some(t) if t.is_empty());
-        let effective_tools: Vec<String> = context
-            .allowed_tools
-            .clone()
-            .unwrap_or_else(|| self.allowed_tools.clone());
+        // Agent mode only honors a per-call whitelist; the provider default
+        // would otherwise restrict every build agent.
+        let effective_tools: Vec<String> = if context.agent_name.is_some() {
+            context.allowed_tools.clone().unwrap_or_default()
+        } else {
+            context
+                .allowed_tools
+                .clone()
+                .unwrap_or_else(|| self.allowed_tools.clone())
+        };
         let effective_model = context.model.as_deref().unwrap_or(&self.model);
 
         // First call with original prompt.
@@ -66,6 +79,9 @@ impl Provider for ClaudeCodeProvider {
                 tools_disabled,
                 context.session_id.as_deref(),
                 context.agent_name.as_deref(),
+                &deny_rules,
+                workspace,
+                context.progress.as_ref(),
             )
             .await;
 
@@ -74,8 +90,11 @@ impl Provider for ClaudeCodeProvider {
             mcp::cleanup_mcp_settings(path);
         }
 
-        let output = result?;
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let CliOutput {
+            stdout,
+            mut turns,
+            mut untrusted,
+        } = result?;
         let (mut text, mut model) = self.parse_response(&stdout, effective_max_turns);
         // CLI doesn't always echo the model back — fall back to what we requested.
         if model.is_none() && !effective_model.is_empty() {
@@ -98,7 +117,12 @@ impl Provider for ClaudeCodeProvider {
                             effective_max_turns,
                             &effective_tools,
                             effective_model,
+                            &deny_rules,
+                            workspace,
+                            context.progress.as_ref(),
                             &mut model,
+                            &mut turns,
+                            &mut untrusted,
                         )
                         .await;
                 }
@@ -110,14 +134,21 @@ impl Provider for ClaudeCodeProvider {
         // Capture session_id from the provider response for conversation continuity.
         let returned_session_id = parsed.as_ref().and_then(|r| r.session_id.clone());
 
+        // Per-turn usage from the stream; older CLIs report none.
+        let usage = (!turns.is_empty()).then(|| total_usage(&turns));
+
         Ok(OutgoingMessage {
             text,
             metadata: MessageMetadata {
                 provider_used: "claude-code".to_string(),
-                tokens_used: None,
+                tokens_used: usage.map(|u| u.total()),
                 processing_time_ms: elapsed_ms,
                 model,
                 session_id: returned_session_id,
+                cache_read_tokens: usage.map(|u| u.cache_read_input_tokens),
+                cache_write_tokens: usage.map(|u| u.cache_creation_input_tokens),
+                created_tasks: Vec::new(),
+                untrusted_sources: untrusted,
             },
             reply_target: None,
             ..Default::default()
@@ -142,7 +173,12 @@ impl ClaudeCodeProvider {
         effective_max_turns: u32,
         effective_tools: &[String],
         effective_model: &str,
+        deny_rules: &[String],
+        workspace: Option<&Path>,
+        progress: Option<&ProgressSink>,
         model: &mut Option<String>,
+        turns: &mut Vec<TurnUsage>,
+        untrusted: &mut Vec<String>,
     ) -> String {
         let mut accumulated = initial_text;
         let mut resume_session = session_id.to_string();
@@ -167,12 +203,21 @@ impl ClaudeCodeProvider {
                     effective_max_turns,
                     effective_tools,
                     effective_model,
+                    deny_rules,
+                    workspace,
+                    progress,
                 )
                 .await;
 
             match resume_result {
                 Ok(resume_output) => {
-                    let resume_stdout = String::from_utf8_lossy(&resume_output.stdout);
+                    turns.extend_from_slice(&resume_output.turns);
+                    for source in resume_output.untrusted {
+                        if !untrusted.contains(&source) {
+                            untrusted.push(source);
+                        }
+                    }
+                    let resume_stdout = resume_output.stdout;
                     let (resume_text, resume_model) =
                         self.parse_response(&resume_stdout, effective_max_turns);
                     accumulated = format!("{accumulated}\n\n---\n\n{resume_text}");
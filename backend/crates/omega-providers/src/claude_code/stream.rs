@@ -0,0 +1,273 @@
+//! Incremental parsing of `--output-format stream-json` output.
+//!
+//! The CLI prints one JSON event per line: `system` (init), `assistant`
+//! (one per content block, carrying the turn's usage), `user` (tool results)
+//! and a final `result` with the same shape as the `--output-format json`
+//! response. Tool-use blocks become throttled status lines; the `result`
+//! line is kept for [`ClaudeCodeProvider::parse_response`](super::ClaudeCodeProvider).
+
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Status updates start after this long, so quick replies stay silent.
+const PROGRESS_DELAY: Duration = Duration::from_secs(10);
+
+/// Minimum gap between two status updates.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Longest command or pattern quoted in a status line.
+const STATUS_DETAIL_CHARS: usize = 60;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    Assistant {
+        message: StreamMessage,
+    },
+    Result,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct StreamMessage {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    content: Vec<StreamBlock>,
+    #[serde(default)]
+    usage: Option<TurnUsage>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamBlock {
+    ToolUse {
+        name: String,
+        #[serde(default)]
+        input: serde_json::Value,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Token usage of one model turn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+pub(super) struct TurnUsage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+}
+
+impl TurnUsage {
+    /// All tokens billed for the turn, cached or not.
+    pub fn total(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_input_tokens
+            + self.cache_read_input_tokens
+    }
+}
+
+/// Everything collected from one CLI run.
+#[derive(Debug, Default)]
+pub(super) struct CliOutput {
+    /// The `result` event line, or the raw output when there was none.
+    pub stdout: String,
+    /// Usage per model turn, in order.
+    pub turns: Vec<TurnUsage>,
+    /// Tools that brought in outside content (`mcp:...`, `web:...`), in order.
+    pub untrusted: Vec<String>,
+}
+
+/// Usage summed over turns.
+pub(super) fn total_usage(turns: &[TurnUsage]) -> TurnUsage {
+    turns.iter().fold(TurnUsage::default(), |acc, t| TurnUsage {
+        input_tokens: acc.input_tokens + t.input_tokens,
+        output_tokens: acc.output_tokens + t.output_tokens,
+        cache_creation_input_tokens: acc.cache_creation_input_tokens
+            + t.cache_creation_input_tokens,
+        cache_read_input_tokens: acc.cache_read_input_tokens + t.cache_read_input_tokens,
+    })
+}
+
+/// Accumulates stream events from one CLI run.
+pub(super) struct CliStream {
+    started: Instant,
+    last_report: Option<Instant>,
+    last_status: Option<String>,
+    /// Message id of the last recorded turn — each content block repeats it.
+    last_turn_id: Option<String>,
+    output: CliOutput,
+    raw: String,
+}
+
+impl CliStream {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            started,
+            last_report: None,
+            last_status: None,
+            last_turn_id: None,
+            output: CliOutput::default(),
+            raw: String::new(),
+        }
+    }
+
+    /// Handle one stdout line. Returns a status line when one is due.
+    pub fn push(&mut self, line: &str, now: Instant) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        let event = match serde_json::from_str::<StreamEvent>(line) {
+            Ok(event) => event,
+            Err(_) => {
+                // Not an event (plain output or a CLI without stream-json).
+                self.raw.push_str(line);
+                self.raw.push('\n');
+                return None;
+            }
+        };
+        match event {
+            StreamEvent::Result => {
+                self.output.stdout = line.to_string();
+                None
+            }
+            StreamEvent::Assistant { message } => {
+                self.record_turn(&message);
+                self.record_untrusted(&message);
+                let status = message.content.iter().rev().find_map(|b| match b {
+                    StreamBlock::ToolUse { name, input } => Some(describe_tool_use(name, input)),
+                    StreamBlock::Other => None,
+                })?;
+                self.throttle(status, now)
+            }
+            StreamEvent::Other => None,
+        }
+    }
+
+    /// Finish the run: the collected output.
+    pub fn finish(mut self) -> CliOutput {
+        if self.output.stdout.is_empty() {
+            self.output.stdout = self.raw;
+        }
+        self.output
+    }
+
+    fn record_turn(&mut self, message: &StreamMessage) {
+        let Some(usage) = message.usage else {
+            return;
+        };
+        if message.id.is_some() && message.id == self.last_turn_id {
+            return;
+        }
+        self.last_turn_id = message.id.clone();
+        debug!(
+            "claude turn {}: in={} out={} cache_read={} cache_write={}",
+            self.output.turns.len() + 1,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_input_tokens,
+            usage.cache_creation_input_tokens
+        );
+        self.output.turns.push(usage);
+    }
+
+    fn record_untrusted(&mut self, message: &StreamMessage) {
+        for block in &message.content {
+            let StreamBlock::ToolUse { name, input } = block else {
+                continue;
+            };
+            let Some(source) = untrusted_source(name, input) else {
+                continue;
+            };
+            if !self.output.untrusted.contains(&source) {
+                self.output.untrusted.push(source);
+            }
+        }
+    }
+
+    /// Pass `status` through unless it is too early, too soon after the
+    /// last one, or a repeat.
+    fn throttle(&mut self, status: String, now: Instant) -> Option<String> {
+        if now.duration_since(self.started) < PROGRESS_DELAY {
+            return None;
+        }
+        if let Some(last) = self.last_report {
+            if now.duration_since(last) < PROGRESS_INTERVAL {
+                return None;
+            }
+        }
+        if self.last_status.as_deref() == Some(status.as_str()) {
+            return None;
+        }
+        self.last_report = Some(now);
+        self.last_status = Some(status.clone());
+        Some(status)
+    }
+}
+
+/// Where a tool call's result comes from, when that is outside the operator's
+/// control: MCP servers (browsers, APIs), the web tools and shell commands
+/// (`curl`, `cat page.html`).
+pub(super) fn untrusted_source(name: &str, input: &serde_json::Value) -> Option<String> {
+    if let Some(tool) = name.strip_prefix("mcp__") {
+        return Some(format!("mcp:{tool}"));
+    }
+    match name {
+        "WebFetch" => {
+            let url = input.get("url").and_then(|v| v.as_str()).unwrap_or("");
+            Some(format!("web:{url}"))
+        }
+        "WebSearch" => Some("web:search".to_string()),
+        "Bash" => {
+            let command = input.get("command").and_then(|v| v.as_str()).unwrap_or("");
+            Some(format!("bash:{}", shorten(command)))
+        }
+        _ => None,
+    }
+}
+
+/// A short human-readable line for a tool call ("Editing src/main.rs").
+pub(super) fn describe_tool_use(name: &str, input: &serde_json::Value) -> String {
+    let field = |key: &str| {
+        input
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(shorten)
+            .unwrap_or_default()
+    };
+    match name {
+        "Edit" | "MultiEdit" => format!("Editing {}", field("file_path")),
+        "Write" => format!("Writing {}", field("file_path")),
+        "Read" => format!("Reading {}", field("file_path")),
+        "Bash" => format!("Running {}", field("command")),
+        "Grep" | "Glob" => format!("Searching for {}", field("pattern")),
+        "WebFetch" => format!("Fetching {}", field("url")),
+        "WebSearch" => format!("Searching the web for {}", field("query")),
+        "Task" => format!("Delegating: {}", field("description")),
+        "TodoWrite" => "Updating the plan".to_string(),
+        _ => match name.strip_prefix("mcp__").and_then(|r| r.split_once("__")) {
+            Some((server, tool)) => format!("Using {server} ({tool})"),
+            None => format!("Using {name}"),
+        },
+    }
+}
+
+/// First line of `text`, cut to [`STATUS_DETAIL_CHARS`].
+fn shorten(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > STATUS_DETAIL_CHARS {
+        let cut: String = line.chars().take(STATUS_DETAIL_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        line.to_string()
+    }
+}
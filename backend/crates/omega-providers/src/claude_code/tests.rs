@@ -1,6 +1,7 @@
 //! Tests for the Claude Code CLI provider.
 
 use super::mcp;
+use super::stream::{describe_tool_use, total_usage, untrusted_source, CliStream, TurnUsage};
 use super::*;
 use omega_core::context::McpServer;
 use omega_core::traits::Provider;
@@ -85,11 +86,13 @@ fn test_mcp_tool_patterns() {
             name: "playwright".into(),
             command: "npx".into(),
             args: vec!["@playwright/mcp".into()],
+            sensitive: false,
         },
         McpServer {
             name: "postgres".into(),
             command: "npx".into(),
             args: vec!["@pg/mcp".into()],
+            sensitive: false,
         },
     ];
     let patterns = mcp::mcp_tool_patterns(&servers);
@@ -106,6 +109,7 @@ fn test_write_and_cleanup_mcp_settings() {
         name: "playwright".into(),
         command: "npx".into(),
         args: vec!["@playwright/mcp".into(), "--headless".into()],
+        sensitive: false,
     }];
 
     let path = mcp::write_mcp_settings(&tmp, &servers).unwrap();
@@ -172,9 +176,10 @@ fn test_build_run_cli_args_no_agent_name() {
         .position(|a| a == "-p")
         .expect("-p flag must be present");
     assert_eq!(args[p_idx + 1], "hello world", "prompt must follow -p");
-    // Verify --output-format json is present.
+    // Verify --output-format stream-json is present (requires --verbose).
     assert!(args.contains(&"--output-format".to_string()));
-    assert!(args.contains(&"json".to_string()));
+    assert!(args.contains(&"stream-json".to_string()));
+    assert!(args.contains(&"--verbose".to_string()));
     // Verify --max-turns is present.
     assert!(args.contains(&"--max-turns".to_string()));
     assert!(args.contains(&"100".to_string()));
@@ -271,6 +276,33 @@ fn test_build_run_cli_args_agent_with_skip_permissions() {
     );
 }
 
+// Agent mode with a per-phase whitelist pre-approves only those tools.
+#[test]
+fn test_build_run_cli_args_agent_with_allowed_tools() {
+    let args = ClaudeCodeProvider::build_run_cli_args(
+        "Begin.",
+        &[],
+        100,
+        &["Read".to_string(), "Grep".to_string()],
+        "",
+        false,
+        None,
+        Some("build-reviewer"),
+    );
+    assert!(args.contains(&"--agent".to_string()));
+    assert!(
+        !args.contains(&"--dangerously-skip-permissions".to_string()),
+        "a whitelist must not bypass permissions"
+    );
+    let allowed: Vec<&String> = args
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i > 0 && args[i - 1] == "--allowedTools")
+        .map(|(_, a)| a)
+        .collect();
+    assert_eq!(allowed, vec!["Read", "Grep"]);
+}
+
 // Requirement: REQ-BAP-004 (Must)
 // Acceptance: --max-turns still applied with --agent
 #[test]
@@ -451,3 +483,152 @@ fn test_build_run_cli_args_disabled_tools() {
         "Disabled tools should pass empty --allowedTools"
     );
 }
+
+#[test]
+fn test_tenant_args_confine_to_tenant_dir() {
+    let data_dir = std::env::temp_dir().join("__omega_test_cc_tenant_args__");
+    let alice = data_dir.join("tenants/alice");
+    std::fs::create_dir_all(alice.join("workspace")).unwrap();
+    std::fs::create_dir_all(data_dir.join("tenants/bob")).unwrap();
+
+    let args = ClaudeCodeProvider::tenant_args(&data_dir, Some(&alice.join("workspace")));
+    let d = data_dir.display();
+    assert_eq!(
+        args,
+        vec![
+            "--add-dir".to_string(),
+            alice.display().to_string(),
+            "--disallowedTools".to_string(),
+            format!("Read(/{d}/tenants/bob/**)"),
+            "--disallowedTools".to_string(),
+            format!("Edit(/{d}/tenants/bob/**)"),
+            "--disallowedTools".to_string(),
+            format!("Edit(/{d}/workspace/**)"),
+            "--disallowedTools".to_string(),
+            format!("Edit(/{d}/skills/**)"),
+            "--disallowedTools".to_string(),
+            format!("Edit(/{d}/projects/**)"),
+        ]
+    );
+
+    // Shared workspace, or a workspace outside `tenants/`: no confinement.
+    assert!(ClaudeCodeProvider::tenant_args(&data_dir, None).is_empty());
+    assert!(
+        ClaudeCodeProvider::tenant_args(&data_dir, Some(&data_dir.join("workspace"))).is_empty()
+    );
+    let _ = std::fs::remove_dir_all(&data_dir);
+}
+
+// --- stream-json ---
+
+const STREAM: &[&str] = &[
+    r#"{"type":"system","subtype":"init","session_id":"sess-1","model":"claude-sonnet-4-6"}"#,
+    r#"{"type":"assistant","message":{"id":"msg_1","content":[{"type":"text","text":"Let me look."}],"usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":900}}}"#,
+    r#"{"type":"assistant","message":{"id":"msg_1","content":[{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"src/main.rs"}}],"usage":{"input_tokens":100,"output_tokens":10,"cache_read_input_tokens":900}}}"#,
+    r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+    r#"{"type":"assistant","message":{"id":"msg_2","content":[{"type":"tool_use","id":"t2","name":"Bash","input":{"command":"cargo test"}}],"usage":{"input_tokens":50,"output_tokens":5,"cache_creation_input_tokens":20}}}"#,
+    r#"{"type":"result","subtype":"success","result":"all done","session_id":"sess-1","num_turns":2}"#,
+];
+
+#[test]
+fn test_stream_collects_result_and_per_turn_usage() {
+    let start = std::time::Instant::now();
+    let mut stream = CliStream::new(start);
+    for line in STREAM {
+        stream.push(line, start);
+    }
+    let output = stream.finish();
+    assert!(output.stdout.contains("\"result\":\"all done\""));
+    // The two content blocks of msg_1 count as one turn.
+    assert_eq!(output.turns.len(), 2);
+    let usage = total_usage(&output.turns);
+    assert_eq!(usage.input_tokens, 150);
+    assert_eq!(usage.cache_read_input_tokens, 900);
+    assert_eq!(usage.cache_creation_input_tokens, 20);
+    assert_eq!(usage.total(), 1085);
+
+    let provider = ClaudeCodeProvider::new();
+    let (text, _) = provider.parse_response(&output.stdout, 25);
+    assert_eq!(text, "all done");
+}
+
+#[test]
+fn test_stream_progress_is_throttled() {
+    let start = std::time::Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let mut stream = CliStream::new(start);
+    // Too early: quick replies stay silent.
+    assert_eq!(stream.push(STREAM[2], at(2)), None);
+    assert_eq!(
+        stream.push(STREAM[2], at(12)).as_deref(),
+        Some("Editing src/main.rs")
+    );
+    // Within the interval of the last update.
+    assert_eq!(stream.push(STREAM[4], at(20)), None);
+    assert_eq!(
+        stream.push(STREAM[4], at(40)).as_deref(),
+        Some("Running cargo test")
+    );
+    // A repeat of the last status is dropped.
+    assert_eq!(stream.push(STREAM[4], at(80)), None);
+}
+
+#[test]
+fn test_stream_without_result_keeps_raw_output() {
+    let start = std::time::Instant::now();
+    let mut stream = CliStream::new(start);
+    stream.push("plain text reply", start);
+    let output = stream.finish();
+    assert_eq!(output.stdout, "plain text reply\n");
+    assert!(output.turns.is_empty());
+}
+
+#[test]
+fn test_stream_records_untrusted_tool_sources() {
+    let start = std::time::Instant::now();
+    let mut stream = CliStream::new(start);
+    for line in STREAM {
+        stream.push(line, start);
+    }
+    stream.push(
+        r#"{"type":"assistant","message":{"id":"msg_3","content":[{"type":"tool_use","id":"t3","name":"mcp__playwright__browser_snapshot","input":{}},{"type":"tool_use","id":"t4","name":"WebFetch","input":{"url":"https://example.com"}}]}}"#,
+        start,
+    );
+    let output = stream.finish();
+    // Edit is not untrusted; the shell, the browser and the fetch are.
+    assert_eq!(
+        output.untrusted,
+        vec![
+            "bash:cargo test",
+            "mcp:playwright__browser_snapshot",
+            "web:https://example.com"
+        ]
+    );
+    assert_eq!(
+        untrusted_source("WebSearch", &serde_json::Value::Null).as_deref(),
+        Some("web:search")
+    );
+    assert_eq!(untrusted_source("Read", &serde_json::Value::Null), None);
+}
+
+#[test]
+fn test_describe_tool_use() {
+    assert_eq!(
+        describe_tool_use("Read", &serde_json::json!({"file_path": "a.rs"})),
+        "Reading a.rs"
+    );
+    assert_eq!(
+        describe_tool_use("mcp__playwright__browser_click", &serde_json::Value::Null),
+        "Using playwright (browser_click)"
+    );
+    assert_eq!(
+        describe_tool_use("Unknown", &serde_json::Value::Null),
+        "Using Unknown"
+    );
+    let long = describe_tool_use(
+        "Bash",
+        &serde_json::json!({"command": format!("{}\nsecond line", "x".repeat(100))}),
+    );
+    assert_eq!(long, format!("Running {}…", "x".repeat(60)));
+    assert_eq!(TurnUsage::default().total(), 0);
+}
@@ -4,13 +4,18 @@
 //! Uses `functionCall` / `functionResponse` parts for tool calling.
 
 use async_trait::async_trait;
-use omega_core::{context::Context, error::OmegaError, message::OutgoingMessage, traits::Provider};
+use omega_core::{
+    context::{ApiMessage, ContentBlock, Context},
+    error::OmegaError,
+    message::OutgoingMessage,
+    traits::Provider,
+};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
-use crate::tools::{build_response, tools_enabled, ToolDef, ToolExecutor};
+use crate::tools::{agentic_system_prompt, build_response, tools_enabled, ToolDef, ToolExecutor};
 
 const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
 
@@ -54,6 +59,25 @@ struct GeminiRequest {
     system_instruction: Option<GeminiContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<GeminiToolDeclaration>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GeminiGenerationConfig>,
+}
+
+/// Schema-constrained JSON output.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiGenerationConfig {
+    response_mime_type: String,
+    response_schema: serde_json::Value,
+}
+
+impl GeminiGenerationConfig {
+    fn from_context(context: &Context) -> Option<Self> {
+        context.response_schema.as_ref().map(|s| Self {
+            response_mime_type: "application/json".into(),
+            response_schema: s.schema.clone(),
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -72,6 +96,16 @@ struct GeminiPart {
     function_call: Option<GeminiFunctionCall>,
     #[serde(skip_serializing_if = "Option::is_none")]
     function_response: Option<GeminiFunctionResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inline_data: Option<GeminiInlineData>,
+}
+
+/// Inline base64 data (images, PDFs).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GeminiInlineData {
+    mime_type: String,
+    data: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -132,8 +166,8 @@ fn to_gemini_tools(defs: &[ToolDef]) -> Vec<GeminiToolDeclaration> {
     }]
 }
 
-/// Build Gemini contents from API messages.
-fn build_gemini_contents(api_messages: &[omega_core::context::ApiMessage]) -> Vec<GeminiContent> {
+/// Build Gemini contents from API messages. Attachments become `inlineData` parts.
+fn build_gemini_contents(api_messages: &[ApiMessage]) -> Vec<GeminiContent> {
     api_messages
         .iter()
         .map(|m| {
@@ -142,13 +176,34 @@ fn build_gemini_contents(api_messages: &[omega_core::context::ApiMessage]) -> Ve
             } else {
                 "user"
             };
+            let parts = m
+                .content_blocks()
+                .into_iter()
+                .map(|block| {
+                    let (text, inline_data) = match block {
+                        ContentBlock::Text { text } => (Some(text), None),
+                        ContentBlock::Image { media_type, data }
+                        | ContentBlock::Document {
+                            media_type, data, ..
+                        } => (
+                            None,
+                            Some(GeminiInlineData {
+                                mime_type: media_type,
+                                data,
+                            }),
+                        ),
+                    };
+                    GeminiPart {
+                        text,
+                        function_call: None,
+                        function_response: None,
+                        inline_data,
+                    }
+                })
+                .collect();
             GeminiContent {
                 role: Some(role.to_string()),
-                parts: vec![GeminiPart {
-                    text: Some(m.content.clone()),
-                    function_call: None,
-                    function_response: None,
-                }],
+                parts,
             }
         })
         .collect()
@@ -173,8 +228,13 @@ impl Provider for GeminiProvider {
 
         if has_tools {
             if let Some(ref ws) = self.workspace_path {
-                let mut executor = ToolExecutor::new(ws.clone());
+                let mut executor = ToolExecutor::new(ws.clone())
+                    .with_workspace(context.workspace.clone())
+                    .with_denied_tools(&context.denied_tools)
+                    .with_approval(context.approval.clone())
+                    .with_actions(context.action_tools);
                 executor.connect_mcp_servers(&context.mcp_servers).await;
+                let system = agentic_system_prompt(&system, context);
 
                 let result = self
                     .agentic_loop(
@@ -187,7 +247,7 @@ impl Provider for GeminiProvider {
                     .await;
 
                 executor.shutdown_mcp().await;
-                return result;
+                return result.map(|resp| executor.finish(resp));
             }
         }
 
@@ -202,6 +262,7 @@ impl Provider for GeminiProvider {
                     text: Some(system),
                     function_call: None,
                     function_response: None,
+                    inline_data: None,
                 }],
             })
         };
@@ -212,6 +273,7 @@ impl Provider for GeminiProvider {
             contents,
             system_instruction,
             tools: None,
+            generation_config: GeminiGenerationConfig::from_context(context),
         };
 
         let url = format!("{GEMINI_BASE_URL}/models/{effective_model}:generateContent");
@@ -284,7 +346,7 @@ impl GeminiProvider {
         &self,
         model: &str,
         system: &str,
-        api_messages: &[omega_core::context::ApiMessage],
+        api_messages: &[ApiMessage],
         executor: &mut ToolExecutor,
         max_turns: u32,
     ) -> Result<OutgoingMessage, OmegaError> {
@@ -301,6 +363,7 @@ impl GeminiProvider {
                     text: Some(system.to_string()),
                     function_call: None,
                     function_response: None,
+                    inline_data: None,
                 }],
             })
         };
@@ -319,6 +382,7 @@ impl GeminiProvider {
                 contents: contents.clone(),
                 system_instruction: system_instruction.clone(),
                 tools: tools.clone(),
+                generation_config: None,
             };
 
             let url = format!("{GEMINI_BASE_URL}/models/{model}:generateContent");
@@ -387,6 +451,7 @@ impl GeminiProvider {
                                 "is_error": result.is_error
                             }),
                         }),
+                        inline_data: None,
                     });
                 }
 
@@ -472,6 +537,7 @@ mod tests {
                     text: Some("Hello".into()),
                     function_call: None,
                     function_response: None,
+                    inline_data: None,
                 }],
             }],
             system_instruction: Some(GeminiContent {
@@ -480,9 +546,11 @@ mod tests {
                     text: Some("Be helpful.".into()),
                     function_call: None,
                     function_response: None,
+                    inline_data: None,
                 }],
             }),
             tools: None,
+            generation_config: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert!(json.get("systemInstruction").is_some());
@@ -500,13 +568,30 @@ mod tests {
                     text: Some("Hello".into()),
                     function_call: None,
                     function_response: None,
+                    inline_data: None,
                 }],
             }],
             system_instruction: None,
             tools: None,
+            generation_config: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         assert!(json.get("systemInstruction").is_none());
+        assert!(json.get("generationConfig").is_none());
+    }
+
+    #[test]
+    fn test_generation_config_from_response_schema() {
+        let mut ctx = Context::new("Summarize.");
+        assert!(GeminiGenerationConfig::from_context(&ctx).is_none());
+        ctx.response_schema = Some(omega_core::structured::ResponseSchema::new(
+            "summary",
+            serde_json::json!({"type": "object"}),
+        ));
+        let config = GeminiGenerationConfig::from_context(&ctx).unwrap();
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["responseMimeType"], "application/json");
+        assert_eq!(json["responseSchema"]["type"], "object");
     }
 
     #[test]
@@ -520,10 +605,12 @@ mod tests {
                     text: Some("list files".into()),
                     function_call: None,
                     function_response: None,
+                    inline_data: None,
                 }],
             }],
             system_instruction: None,
             tools: Some(tools),
+            generation_config: None,
         };
         let json = serde_json::to_value(&body).unwrap();
         let decls = &json["tools"][0]["functionDeclarations"];
@@ -533,13 +620,15 @@ mod tests {
     #[test]
     fn test_gemini_role_mapping() {
         let api_msgs = vec![
-            omega_core::context::ApiMessage {
+            ApiMessage {
                 role: "user".into(),
                 content: "Hi".into(),
+                media: Vec::new(),
             },
-            omega_core::context::ApiMessage {
+            ApiMessage {
                 role: "assistant".into(),
                 content: "Hello!".into(),
+                media: Vec::new(),
             },
         ];
         let contents = build_gemini_contents(&api_msgs);
@@ -585,10 +674,29 @@ mod tests {
                 name: "bash".into(),
                 response: serde_json::json!({"result": "file1.txt\nfile2.txt", "is_error": false}),
             }),
+            inline_data: None,
         };
         let json = serde_json::to_value(&part).unwrap();
         assert!(json.get("text").is_none());
         assert!(json.get("functionCall").is_none());
         assert_eq!(json["functionResponse"]["name"], "bash");
     }
+
+    #[test]
+    fn test_gemini_contents_with_media() {
+        let api_msgs = vec![ApiMessage {
+            role: "user".into(),
+            content: "What is this?".into(),
+            media: vec![ContentBlock::Image {
+                media_type: "image/png".into(),
+                data: "iVBORw0K".into(),
+            }],
+        }];
+        let contents = build_gemini_contents(&api_msgs);
+        let json = serde_json::to_value(&contents[0]).unwrap();
+        assert_eq!(json["parts"][0]["inlineData"]["mimeType"], "image/png");
+        assert_eq!(json["parts"][0]["inlineData"]["data"], "iVBORw0K");
+        assert!(json["parts"][0].get("text").is_none());
+        assert_eq!(json["parts"][1]["text"], "What is this?");
+    }
 }
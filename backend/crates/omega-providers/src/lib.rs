@@ -2,6 +2,7 @@
 //!
 //! AI provider implementations for Omega.
 
+pub mod actions;
 pub mod anthropic;
 pub mod claude_code;
 pub mod gemini;
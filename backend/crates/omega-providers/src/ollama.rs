@@ -4,13 +4,18 @@
 //! Tool calling format is similar to OpenAI but has no `tool_call_id`.
 
 use async_trait::async_trait;
-use omega_core::{context::Context, error::OmegaError, message::OutgoingMessage, traits::Provider};
+use omega_core::{
+    context::{ApiMessage, ContentBlock, Context},
+    error::OmegaError,
+    message::OutgoingMessage,
+    traits::Provider,
+};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
-use crate::tools::{build_response, tools_enabled, ToolDef, ToolExecutor};
+use crate::tools::{agentic_system_prompt, build_response, tools_enabled, ToolDef, ToolExecutor};
 
 /// Default max agentic loop iterations.
 const DEFAULT_MAX_TURNS: u32 = 50;
@@ -60,6 +65,9 @@ struct OllamaChatMessage {
     content: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_calls: Option<Vec<OllamaToolCall>>,
+    /// Base64 images for vision models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    images: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -112,17 +120,28 @@ fn to_ollama_tools(defs: &[ToolDef]) -> Vec<OllamaToolDef> {
         .collect()
 }
 
+/// Base64 images of a message. Ollama has no document input, so PDFs are dropped.
+fn ollama_images(m: &ApiMessage) -> Option<Vec<String>> {
+    let images: Vec<String> = m
+        .media
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::Image { data, .. } => Some(data.clone()),
+            _ => None,
+        })
+        .collect();
+    (!images.is_empty()).then_some(images)
+}
+
 /// Build Ollama-format messages from context.
-fn build_ollama_messages(
-    system: &str,
-    api_messages: &[omega_core::context::ApiMessage],
-) -> Vec<OllamaChatMessage> {
+fn build_ollama_messages(system: &str, api_messages: &[ApiMessage]) -> Vec<OllamaChatMessage> {
     let mut messages = Vec::with_capacity(api_messages.len() + 1);
     if !system.is_empty() {
         messages.push(OllamaChatMessage {
             role: "system".to_string(),
             content: Some(system.to_string()),
             tool_calls: None,
+            images: None,
         });
     }
     for m in api_messages {
@@ -130,6 +149,7 @@ fn build_ollama_messages(
             role: m.role.clone(),
             content: Some(m.content.clone()),
             tool_calls: None,
+            images: ollama_images(m),
         });
     }
     messages
@@ -156,8 +176,13 @@ impl Provider for OllamaProvider {
 
         if has_tools {
             if let Some(ref ws) = self.workspace_path {
-                let mut executor = ToolExecutor::new(ws.clone());
+                let mut executor = ToolExecutor::new(ws.clone())
+                    .with_workspace(context.workspace.clone())
+                    .with_denied_tools(&context.denied_tools)
+                    .with_approval(context.approval.clone())
+                    .with_actions(context.action_tools);
                 executor.connect_mcp_servers(&context.mcp_servers).await;
+                let system = agentic_system_prompt(&system, context);
 
                 let result = self
                     .agentic_loop(
@@ -171,7 +196,7 @@ impl Provider for OllamaProvider {
                     .await;
 
                 executor.shutdown_mcp().await;
-                return result;
+                return result.map(|resp| executor.finish(resp));
             }
         }
 
@@ -181,18 +206,26 @@ impl Provider for OllamaProvider {
         let simple_msgs: Vec<_> = messages
             .drain(..)
             .map(|m| {
-                serde_json::json!({
+                let mut msg = serde_json::json!({
                     "role": m.role,
                     "content": m.content.unwrap_or_default()
-                })
+                });
+                if let Some(images) = m.images {
+                    msg["images"] = serde_json::json!(images);
+                }
+                msg
             })
             .collect();
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": effective_model,
             "messages": simple_msgs,
             "stream": false
         });
+        // Schema-constrained JSON output.
+        if let Some(ref schema) = context.response_schema {
+            body["format"] = schema.schema.clone();
+        }
 
         debug!("ollama: POST {url} model={effective_model} (no tools)");
 
@@ -258,7 +291,7 @@ impl OllamaProvider {
         url: &str,
         model: &str,
         system: &str,
-        api_messages: &[omega_core::context::ApiMessage],
+        api_messages: &[ApiMessage],
         executor: &mut ToolExecutor,
         max_turns: u32,
     ) -> Result<OutgoingMessage, OmegaError> {
@@ -336,6 +369,7 @@ impl OllamaProvider {
                             role: "tool".to_string(),
                             content: Some(result.content),
                             tool_calls: None,
+                            images: None,
                         });
                     }
 
@@ -391,11 +425,13 @@ mod tests {
                     role: "system".into(),
                     content: Some("Be helpful.".into()),
                     tool_calls: None,
+                    images: None,
                 },
                 OllamaChatMessage {
                     role: "user".into(),
                     content: Some("Hello".into()),
                     tool_calls: None,
+                    images: None,
                 },
             ],
             stream: false,
@@ -418,6 +454,7 @@ mod tests {
                 role: "user".into(),
                 content: Some("list files".into()),
                 tool_calls: None,
+                images: None,
             }],
             stream: false,
             tools: Some(tools),
@@ -448,4 +485,40 @@ mod tests {
         assert_eq!(tcs[0].function.name, "bash");
         assert_eq!(tcs[0].function.arguments["command"], "ls");
     }
+
+    #[test]
+    fn test_build_ollama_messages_with_images() {
+        let api_msgs = vec![ApiMessage {
+            role: "user".into(),
+            content: "What is this?".into(),
+            media: vec![
+                ContentBlock::Image {
+                    media_type: "image/png".into(),
+                    data: "iVBORw0K".into(),
+                },
+                ContentBlock::Document {
+                    media_type: "application/pdf".into(),
+                    data: "JVBERi0x".into(),
+                    name: "invoice.pdf".into(),
+                },
+            ],
+        }];
+        let messages = build_ollama_messages("", &api_msgs);
+        let json = serde_json::to_value(&messages[0]).unwrap();
+        assert_eq!(json["content"], "What is this?");
+        assert_eq!(json["images"], serde_json::json!(["iVBORw0K"]));
+
+        let plain = build_ollama_messages(
+            "",
+            &[ApiMessage {
+                role: "user".into(),
+                content: "Hi".into(),
+                media: Vec::new(),
+            }],
+        );
+        assert!(serde_json::to_value(&plain[0])
+            .unwrap()
+            .get("images")
+            .is_none());
+    }
 }
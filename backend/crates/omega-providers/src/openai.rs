@@ -5,7 +5,7 @@
 
 use async_trait::async_trait;
 use omega_core::{
-    context::{ApiMessage, Context},
+    context::{ApiMessage, ContentBlock, Context},
     error::OmegaError,
     message::OutgoingMessage,
     traits::Provider,
@@ -15,7 +15,7 @@ use std::path::PathBuf;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
-use crate::tools::{build_response, tools_enabled, ToolDef, ToolExecutor};
+use crate::tools::{agentic_system_prompt, build_response, tools_enabled, ToolDef, ToolExecutor};
 
 /// Default max agentic loop iterations.
 const DEFAULT_MAX_TURNS: u32 = 50;
@@ -58,7 +58,7 @@ pub(crate) fn build_openai_messages(system: &str, api_messages: &[ApiMessage]) -
     if !system.is_empty() {
         messages.push(ChatMessage {
             role: "system".to_string(),
-            content: Some(system.to_string()),
+            content: Some(ChatContent::Text(system.to_string())),
             tool_calls: None,
             tool_call_id: None,
         });
@@ -66,7 +66,7 @@ pub(crate) fn build_openai_messages(system: &str, api_messages: &[ApiMessage]) -
     for m in api_messages {
         messages.push(ChatMessage {
             role: m.role.clone(),
-            content: Some(m.content.clone()),
+            content: Some(to_chat_content(m)),
             tool_calls: None,
             tool_call_id: None,
         });
@@ -74,17 +74,91 @@ pub(crate) fn build_openai_messages(system: &str, api_messages: &[ApiMessage]) -
     messages
 }
 
+/// Convert an API message's content; attachments turn it into parts.
+pub(crate) fn to_chat_content(m: &ApiMessage) -> ChatContent {
+    if m.media.is_empty() {
+        return ChatContent::Text(m.content.clone());
+    }
+    let parts = m
+        .content_blocks()
+        .into_iter()
+        .map(|block| match block {
+            ContentBlock::Text { text } => ChatContentPart::Text { text },
+            ContentBlock::Image { media_type, data } => ChatContentPart::ImageUrl {
+                image_url: ChatImageUrl {
+                    url: format!("data:{media_type};base64,{data}"),
+                },
+            },
+            ContentBlock::Document {
+                media_type,
+                data,
+                name,
+            } => ChatContentPart::File {
+                file: ChatFile {
+                    filename: name,
+                    file_data: format!("data:{media_type};base64,{data}"),
+                },
+            },
+        })
+        .collect();
+    ChatContent::Parts(parts)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub(crate) struct ChatMessage {
     pub role: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub content: Option<String>,
+    pub content: Option<ChatContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCallMsg>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
 }
 
+/// Message content: plain text, or parts for multimodal user messages.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub(crate) enum ChatContent {
+    Text(String),
+    Parts(Vec<ChatContentPart>),
+}
+
+impl ChatContent {
+    /// The text of the content, joining text parts.
+    pub fn into_text(self) -> String {
+        match self {
+            Self::Text(text) => text,
+            Self::Parts(parts) => parts
+                .into_iter()
+                .filter_map(|p| match p {
+                    ChatContentPart::Text { text } => Some(text),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ChatContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ChatImageUrl },
+    File { file: ChatFile },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct ChatImageUrl {
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct ChatFile {
+    pub filename: String,
+    pub file_data: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub(crate) struct ToolCallMsg {
     pub id: String,
@@ -105,6 +179,36 @@ pub(crate) struct ChatCompletionRequest {
     pub messages: Vec<ChatMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<OpenAiToolDef>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<OpenAiResponseFormat>,
+}
+
+/// `response_format` for schema-constrained JSON output.
+#[derive(Serialize, Clone)]
+pub(crate) struct OpenAiResponseFormat {
+    #[serde(rename = "type")]
+    pub format_type: String,
+    pub json_schema: OpenAiJsonSchema,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct OpenAiJsonSchema {
+    pub name: String,
+    pub schema: serde_json::Value,
+}
+
+/// Map the context's response schema to `response_format`.
+pub(crate) fn to_openai_response_format(context: &Context) -> Option<OpenAiResponseFormat> {
+    context
+        .response_schema
+        .as_ref()
+        .map(|s| OpenAiResponseFormat {
+            format_type: "json_schema".into(),
+            json_schema: OpenAiJsonSchema {
+                name: s.name.clone(),
+                schema: s.schema.clone(),
+            },
+        })
 }
 
 #[derive(Serialize, Clone)]
@@ -190,6 +294,7 @@ pub(crate) async fn openai_agentic_complete(
             model: model.to_string(),
             messages: messages.clone(),
             tools: tools.clone(),
+            response_format: None,
         };
 
         debug!("{provider_name}: POST {url} model={model} turn={turn}");
@@ -253,7 +358,7 @@ pub(crate) async fn openai_agentic_complete(
                     // Append tool result message.
                     messages.push(ChatMessage {
                         role: "tool".to_string(),
-                        content: Some(result.content),
+                        content: Some(ChatContent::Text(result.content)),
                         tool_calls: None,
                         tool_call_id: Some(tc.id.clone()),
                     });
@@ -266,6 +371,7 @@ pub(crate) async fn openai_agentic_complete(
         // Text-only response — we're done.
         let text = assistant_msg
             .content
+            .map(ChatContent::into_text)
             .unwrap_or_else(|| format!("No response from {provider_name}."));
 
         let elapsed_ms = start.elapsed().as_millis() as u64;
@@ -310,8 +416,13 @@ impl Provider for OpenAiProvider {
 
         if has_tools {
             if let Some(ref ws) = self.workspace_path {
-                let mut executor = ToolExecutor::new(ws.clone());
+                let mut executor = ToolExecutor::new(ws.clone())
+                    .with_workspace(context.workspace.clone())
+                    .with_denied_tools(&context.denied_tools)
+                    .with_approval(context.approval.clone())
+                    .with_actions(context.action_tools);
                 executor.connect_mcp_servers(&context.mcp_servers).await;
+                let system = agentic_system_prompt(&system, context);
 
                 let result = openai_agentic_complete(
                     &self.client,
@@ -327,7 +438,7 @@ impl Provider for OpenAiProvider {
                 .await;
 
                 executor.shutdown_mcp().await;
-                return result;
+                return result.map(|resp| executor.finish(resp));
             }
         }
 
@@ -338,6 +449,7 @@ impl Provider for OpenAiProvider {
             model: effective_model.to_string(),
             messages,
             tools: None,
+            response_format: to_openai_response_format(context),
         };
 
         debug!("openai: POST {url} model={effective_model} (no tools)");
@@ -370,6 +482,7 @@ impl Provider for OpenAiProvider {
             .and_then(|c| c.first())
             .and_then(|c| c.message.as_ref())
             .and_then(|m| m.content.clone())
+            .map(ChatContent::into_text)
             .unwrap_or_else(|| "No response from OpenAI.".to_string());
 
         let tokens = parsed
@@ -413,6 +526,7 @@ impl Provider for OpenAiProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use omega_core::structured::ResponseSchema;
 
     #[test]
     fn test_openai_provider_name() {
@@ -433,20 +547,26 @@ mod tests {
             ApiMessage {
                 role: "user".into(),
                 content: "Hi".into(),
+                media: Vec::new(),
             },
             ApiMessage {
                 role: "assistant".into(),
                 content: "Hello!".into(),
+                media: Vec::new(),
             },
             ApiMessage {
                 role: "user".into(),
                 content: "How?".into(),
+                media: Vec::new(),
             },
         ];
         let messages = build_openai_messages("Be helpful.", &api_msgs);
         assert_eq!(messages.len(), 4);
         assert_eq!(messages[0].role, "system");
-        assert_eq!(messages[0].content.as_deref(), Some("Be helpful."));
+        assert_eq!(
+            messages[0].content.clone().map(ChatContent::into_text),
+            Some("Be helpful.".into())
+        );
         assert_eq!(messages[3].role, "user");
     }
 
@@ -455,6 +575,7 @@ mod tests {
         let api_msgs = vec![ApiMessage {
             role: "user".into(),
             content: "Hi".into(),
+            media: Vec::new(),
         }];
         let messages = build_openai_messages("", &api_msgs);
         assert_eq!(messages.len(), 1);
@@ -470,7 +591,8 @@ mod tests {
             .as_ref()
             .and_then(|c| c.first())
             .and_then(|c| c.message.as_ref())
-            .and_then(|m| m.content.clone());
+            .and_then(|m| m.content.clone())
+            .map(ChatContent::into_text);
         assert_eq!(text, Some("Hello!".into()));
         assert_eq!(resp.usage.as_ref().and_then(|u| u.total_tokens), Some(42));
     }
@@ -510,14 +632,30 @@ mod tests {
             model: "gpt-4o".into(),
             messages: vec![ChatMessage {
                 role: "user".into(),
-                content: Some("Hi".into()),
+                content: Some(ChatContent::Text("Hi".into())),
                 tool_calls: None,
                 tool_call_id: None,
             }],
             tools: None,
+            response_format: None,
         };
         let json = serde_json::to_value(&req).unwrap();
         assert!(json.get("tools").is_none());
+        assert!(json.get("response_format").is_none());
+    }
+
+    #[test]
+    fn test_response_format_from_context() {
+        let mut ctx = Context::new("Summarize.");
+        assert!(to_openai_response_format(&ctx).is_none());
+        ctx.response_schema = Some(ResponseSchema::new(
+            "summary",
+            serde_json::json!({"type": "object"}),
+        ));
+        let json = serde_json::to_value(to_openai_response_format(&ctx).unwrap()).unwrap();
+        assert_eq!(json["type"], "json_schema");
+        assert_eq!(json["json_schema"]["name"], "summary");
+        assert_eq!(json["json_schema"]["schema"]["type"], "object");
     }
 
     #[test]
@@ -527,13 +665,50 @@ mod tests {
             model: "gpt-4o".into(),
             messages: vec![ChatMessage {
                 role: "user".into(),
-                content: Some("list files".into()),
+                content: Some(ChatContent::Text("list files".into())),
                 tool_calls: None,
                 tool_call_id: None,
             }],
             tools: Some(to_openai_tools(&defs)),
+            response_format: None,
         };
         let json = serde_json::to_value(&req).unwrap();
         assert!(json.get("tools").unwrap().as_array().unwrap().len() == 4);
     }
+
+    #[test]
+    fn test_build_openai_messages_with_media() {
+        let api_msgs = vec![ApiMessage {
+            role: "user".into(),
+            content: "What is this?".into(),
+            media: vec![
+                ContentBlock::Image {
+                    media_type: "image/jpeg".into(),
+                    data: "/9j/4AAQ".into(),
+                },
+                ContentBlock::Document {
+                    media_type: "application/pdf".into(),
+                    data: "JVBERi0x".into(),
+                    name: "invoice.pdf".into(),
+                },
+            ],
+        }];
+        let messages = build_openai_messages("", &api_msgs);
+        let json = serde_json::to_value(&messages[0]).unwrap();
+        let parts = json["content"].as_array().unwrap();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0]["type"], "image_url");
+        assert_eq!(
+            parts[0]["image_url"]["url"],
+            "data:image/jpeg;base64,/9j/4AAQ"
+        );
+        assert_eq!(parts[1]["type"], "file");
+        assert_eq!(parts[1]["file"]["filename"], "invoice.pdf");
+        assert_eq!(
+            parts[1]["file"]["file_data"],
+            "data:application/pdf;base64,JVBERi0x"
+        );
+        assert_eq!(parts[2]["type"], "text");
+        assert_eq!(parts[2]["text"], "What is this?");
+    }
 }
@@ -10,9 +10,10 @@ use std::time::Instant;
 use tracing::{debug, warn};
 
 use crate::openai::{
-    build_openai_messages, openai_agentic_complete, ChatCompletionRequest, ChatCompletionResponse,
+    build_openai_messages, openai_agentic_complete, to_openai_response_format,
+    ChatCompletionRequest, ChatCompletionResponse, ChatContent,
 };
-use crate::tools::{build_response, tools_enabled, ToolExecutor};
+use crate::tools::{agentic_system_prompt, build_response, tools_enabled, ToolExecutor};
 
 const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
 
@@ -67,8 +68,13 @@ impl Provider for OpenRouterProvider {
 
         if has_tools {
             if let Some(ref ws) = self.workspace_path {
-                let mut executor = ToolExecutor::new(ws.clone());
+                let mut executor = ToolExecutor::new(ws.clone())
+                    .with_workspace(context.workspace.clone())
+                    .with_denied_tools(&context.denied_tools)
+                    .with_approval(context.approval.clone())
+                    .with_actions(context.action_tools);
                 executor.connect_mcp_servers(&context.mcp_servers).await;
+                let system = agentic_system_prompt(&system, context);
 
                 let result = openai_agentic_complete(
                     &self.client,
@@ -84,7 +90,7 @@ impl Provider for OpenRouterProvider {
                 .await;
 
                 executor.shutdown_mcp().await;
-                return result;
+                return result.map(|resp| executor.finish(resp));
             }
         }
 
@@ -95,6 +101,7 @@ impl Provider for OpenRouterProvider {
             model: effective_model.to_string(),
             messages,
             tools: None,
+            response_format: to_openai_response_format(context),
         };
 
         debug!("openrouter: POST {url} model={effective_model} (no tools)");
@@ -126,6 +133,7 @@ impl Provider for OpenRouterProvider {
             .and_then(|c| c.first())
             .and_then(|c| c.message.as_ref())
             .and_then(|m| m.content.clone())
+            .map(ChatContent::into_text)
             .unwrap_or_else(|| "No response from OpenRouter.".to_string());
 
         let tokens = parsed
@@ -120,9 +120,12 @@ fn apply_landlock(home: &str, data_dir: &std::path::Path) -> Result<(), anyhow::
     // empty file would break the TOML parser on startup. The code-level
     // enforcement via is_read_blocked()/is_write_blocked() provides protection
     // even when config.toml doesn't exist yet on first run.
-    let config_file = data_dir.join("config.toml");
-    if config_file.exists() {
-        ruleset = ruleset.add_rules(path_beneath_rules(&[config_file], refer_only()))?;
+    // The master key and secrets store get the same treatment.
+    for name in super::PROTECTED_FILES {
+        let file = data_dir.join(name);
+        if file.exists() {
+            ruleset = ruleset.add_rules(path_beneath_rules(&[file], refer_only()))?;
+        }
     }
 
     let status = ruleset.restrict_self()?;
@@ -6,18 +6,18 @@
 //! dangerous system directories and OMEGA's core data are blocked.
 //!
 //! - **macOS**: Apple Seatbelt via `sandbox-exec -p <profile>` — denies reads
-//!   and writes to `{data_dir}/data/` (memory.db) and `config.toml`; denies
+//!   and writes to `{data_dir}/data/` (memory.db) and [`PROTECTED_FILES`]; denies
 //!   writes to `/System`, `/bin`, `/sbin`, `/usr/{bin,sbin,lib,libexec}`,
 //!   `/private/etc`, `/Library`.
 //! - **Linux**: Landlock LSM via `pre_exec` hook (kernel 5.13+) — broad
 //!   read-only on `/` with full access to `$HOME`, `/tmp`, `/var/tmp`, `/opt`,
 //!   `/srv`, `/run`, `/media`, `/mnt`; restricted access to `{data_dir}/data/`
-//!   and `config.toml`.
+//!   and [`PROTECTED_FILES`].
 //! - **Other**: Falls back to a plain command with a warning.
 //!
 //! Also provides [`is_write_blocked`] and [`is_read_blocked`] for code-level
-//! enforcement in HTTP provider tool executors (protects memory.db and
-//! config.toml on all platforms).
+//! enforcement in HTTP provider tool executors (protects memory.db,
+//! config.toml and key material on all platforms).
 
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
@@ -31,6 +31,17 @@ mod seatbelt;
 #[cfg(target_os = "linux")]
 mod landlock_sandbox;
 
+/// Files in `data_dir` that hold credentials: config (API keys), the
+/// encryption master key and the encrypted secrets store.
+pub const PROTECTED_FILES: &[&str] = &["config.toml", "master.key", "secrets.enc"];
+
+/// Whether `resolved` is one of [`PROTECTED_FILES`] in `data_dir`.
+fn is_protected_file(resolved: &Path, data_dir: &Path) -> bool {
+    PROTECTED_FILES
+        .iter()
+        .any(|name| resolved == try_canonicalize(&data_dir.join(name)))
+}
+
 /// Build a [`Command`] with OS-level system protection.
 ///
 /// Always active — blocks writes to dangerous system directories and
@@ -76,9 +87,8 @@ pub fn is_write_blocked(path: &Path, data_dir: &Path) -> bool {
         return true;
     }
 
-    // Block writes to OMEGA's config file (API keys, auth settings).
-    let config_file = try_canonicalize(&data_dir.join("config.toml"));
-    if resolved == config_file {
+    // Block writes to OMEGA's config file and key material.
+    if is_protected_file(&resolved, data_dir) {
         return true;
     }
 
@@ -116,6 +126,7 @@ pub fn is_write_blocked(path: &Path, data_dir: &Path) -> bool {
 /// Returns `true` if the path targets a protected location:
 /// - OMEGA's core data directory (`{data_dir}/data/`) — protects memory.db
 /// - OMEGA's config file (`{data_dir}/config.toml`) — protects API keys
+/// - The master key and secrets store (`master.key`, `secrets.enc`)
 /// - The actual config file at `config_path` (may differ from data_dir) — protects secrets
 ///
 /// Resolves symlinks before comparison to prevent bypass via symlink chains.
@@ -137,9 +148,9 @@ pub fn is_read_blocked(path: &Path, data_dir: &Path, config_path: Option<&Path>)
         return true;
     }
 
-    // Block reads to OMEGA's config file in data_dir (API keys, secrets).
-    let config_in_data = try_canonicalize(&data_dir.join("config.toml"));
-    if resolved == config_in_data {
+    // Block reads to OMEGA's config file in data_dir (API keys, secrets)
+    // and to the master key and secrets store.
+    if is_protected_file(&resolved, data_dir) {
         return true;
     }
 
@@ -294,6 +305,16 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_key_material_blocked() {
+        let data_dir = PathBuf::from("/home/user/.omega");
+        for name in ["master.key", "secrets.enc"] {
+            let path = data_dir.join(name);
+            assert!(is_read_blocked(&path, &data_dir, None), "{name} read");
+            assert!(is_write_blocked(&path, &data_dir), "{name} write");
+        }
+    }
+
     #[test]
     fn test_is_read_blocked_external_config() {
         let data_dir = PathBuf::from("/home/user/.omega");
@@ -4,6 +4,7 @@
 //! Denies reads to OMEGA's core data directory and config file.
 //! Everything else is allowed by default.
 
+use super::PROTECTED_FILES;
 use std::path::Path;
 use tokio::process::Command;
 use tracing::warn;
@@ -17,11 +18,14 @@ const SANDBOX_EXEC: &str = "/usr/bin/sandbox-exec";
 /// `data_dir` is the Omega data directory (`~/.omega/`).
 /// - Writes to system dirs and `{data_dir}/data/` are denied.
 /// - Reads to `{data_dir}/data/` and `{data_dir}/config.toml` are denied.
+/// - Reads and writes to the master key and secrets store are denied.
 fn build_profile(data_dir: &Path) -> String {
     let data_data = data_dir.join("data");
     let data_data_str = data_data.display();
-    let config_file = data_dir.join("config.toml");
-    let config_str = config_file.display();
+    let files: String = PROTECTED_FILES
+        .iter()
+        .map(|name| format!("\n  (literal \"{}\")", data_dir.join(name).display()))
+        .collect();
 
     format!(
         r#"(version 1)
@@ -36,12 +40,10 @@ fn build_profile(data_dir: &Path) -> String {
   (subpath "/usr/libexec")
   (subpath "/private/etc")
   (subpath "/Library")
-  (subpath "{data_data_str}")
-  (literal "{config_str}")
+  (subpath "{data_data_str}"){files}
 )
 (deny file-read*
-  (subpath "{data_data_str}")
-  (literal "{config_str}")
+  (subpath "{data_data_str}"){files}
 )"#
     )
 }
@@ -1,17 +1,5 @@
 //! Shared parsing utilities for skill and project frontmatter.
 
-use std::path::Path;
-
-/// Expand `~` to the user's home directory.
-pub(crate) fn expand_tilde(path: &str) -> String {
-    if let Some(rest) = path.strip_prefix("~/") {
-        if let Some(home) = std::env::var_os("HOME") {
-            return format!("{}/{rest}", home.to_string_lossy());
-        }
-    }
-    path.to_string()
-}
-
 /// Strip surrounding quotes (single or double) from a string.
 pub(crate) fn unquote(s: &str) -> String {
     let s = s.trim();
@@ -75,8 +63,3 @@ pub(crate) fn which_exists(tool: &str) -> bool {
         })
         .unwrap_or(false)
 }
-
-/// Resolve `{data_dir}/` with tilde expansion, appending a subdirectory.
-pub(crate) fn data_path(data_dir: &str, sub: &str) -> std::path::PathBuf {
-    Path::new(&expand_tilde(data_dir)).join(sub)
-}
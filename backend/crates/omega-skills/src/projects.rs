@@ -1,8 +1,9 @@
 //! Project loading and parsing.
 
-use crate::parse::{data_path, expand_tilde, parse_yaml_list};
+use crate::parse::parse_yaml_list;
+use omega_core::config::DataDir;
 use serde::Deserialize;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use tracing::warn;
 
 /// A loaded project definition.
@@ -16,6 +17,8 @@ pub struct Project {
     pub path: PathBuf,
     /// Skills declared in ROLE.md frontmatter.
     pub skills: Vec<String>,
+    /// Build topology declared in ROLE.md frontmatter (default: "development").
+    pub topology: Option<String>,
 }
 
 /// Frontmatter parsed from a `ROLE.md` file.
@@ -23,6 +26,8 @@ pub struct Project {
 struct ProjectFrontmatter {
     #[serde(default)]
     skills: Vec<String>,
+    #[serde(default)]
+    topology: Option<String>,
 }
 
 /// Parse optional frontmatter from a ROLE.md file.
@@ -47,23 +52,31 @@ fn parse_project_frontmatter(content: &str) -> (ProjectFrontmatter, &str) {
         return (fm, body);
     }
 
-    // Fallback: parse YAML-style skills list.
+    // Fallback: parse YAML-style keys.
     let mut skills = Vec::new();
+    let mut topology = None;
     for line in block.lines() {
         let line = line.trim();
         if let Some((key, val)) = line.split_once(':') {
-            if key.trim() == "skills" {
-                skills = parse_yaml_list(val);
+            match key.trim() {
+                "skills" => skills = parse_yaml_list(val),
+                "topology" => {
+                    let val = val.trim().trim_matches(|c| c == '"' || c == '\'');
+                    if !val.is_empty() {
+                        topology = Some(val.to_string());
+                    }
+                }
+                _ => {}
             }
         }
     }
 
-    (ProjectFrontmatter { skills }, body)
+    (ProjectFrontmatter { skills, topology }, body)
 }
 
 /// Create `{data_dir}/projects/` if it doesn't exist.
 pub fn ensure_projects_dir(data_dir: &str) {
-    let dir = data_path(data_dir, "projects");
+    let dir = DataDir::new(data_dir).projects();
     if let Err(e) = std::fs::create_dir_all(&dir) {
         warn!("projects: failed to create {}: {e}", dir.display());
     }
@@ -71,7 +84,7 @@ pub fn ensure_projects_dir(data_dir: &str) {
 
 /// Scan `{data_dir}/projects/*/ROLE.md` and return all valid projects.
 pub fn load_projects(data_dir: &str) -> Vec<Project> {
-    let dir = Path::new(&expand_tilde(data_dir)).join("projects");
+    let dir = DataDir::new(data_dir).projects();
     let entries = match std::fs::read_dir(&dir) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
@@ -120,6 +133,7 @@ pub fn load_projects(data_dir: &str) -> Vec<Project> {
             instructions,
             path,
             skills: fm.skills,
+            topology: fm.topology,
         });
     }
 
@@ -193,6 +207,7 @@ mod tests {
             instructions: "Track my portfolio.".into(),
             path: PathBuf::from("/home/user/.omega/projects/stocks"),
             skills: Vec::new(),
+            topology: None,
         }];
         assert_eq!(
             get_project_instructions(&projects, "stocks"),
@@ -231,6 +246,21 @@ You are a trading assistant.
         assert!(body.contains("trading assistant"));
     }
 
+    #[test]
+    fn test_parse_project_frontmatter_topology() {
+        let toml = "---\ntopology = \"research\"\n---\nBody.";
+        assert_eq!(
+            parse_project_frontmatter(toml).0.topology.as_deref(),
+            Some("research")
+        );
+        let yaml = "---\nskills: [a]\ntopology: research\n---\nBody.";
+        assert_eq!(
+            parse_project_frontmatter(yaml).0.topology.as_deref(),
+            Some("research")
+        );
+        assert!(parse_project_frontmatter("Body.").0.topology.is_none());
+    }
+
     #[test]
     fn test_parse_project_frontmatter_none() {
         let content = "You are a trading assistant.";
@@ -1,6 +1,7 @@
 //! Skill loading, parsing, deployment, and trigger matching.
 
-use crate::parse::{data_path, extract_bins_from_metadata, parse_yaml_list, unquote, which_exists};
+use crate::parse::{extract_bins_from_metadata, parse_yaml_list, unquote, which_exists};
+use omega_core::config::DataDir;
 use omega_core::context::McpServer;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -15,7 +16,7 @@ use crate::bundled::BUNDLED_SKILLS;
 /// Deploys all bundled files (SKILL.md, indexes, functionalities, scripts, etc.).
 /// Never overwrites existing files so user edits are preserved.
 pub fn install_bundled_skills(data_dir: &str) {
-    let dir = data_path(data_dir, "skills");
+    let dir = DataDir::new(data_dir).skills();
     if let Err(e) = std::fs::create_dir_all(&dir) {
         warn!("skills: failed to create {}: {e}", dir.display());
         return;
@@ -53,7 +54,7 @@ pub fn install_bundled_skills(data_dir: &str) {
 /// subdirectory and moves the file into it as `SKILL.md`. Existing directories
 /// are never overwritten.
 pub fn migrate_flat_skills(data_dir: &str) {
-    let dir = data_path(data_dir, "skills");
+    let dir = DataDir::new(data_dir).skills();
     let entries = match std::fs::read_dir(&dir) {
         Ok(e) => e,
         Err(_) => return,
@@ -128,6 +129,9 @@ struct McpFrontmatter {
     command: String,
     #[serde(default)]
     args: Vec<String>,
+    /// Tools from this server need human approval (see `[approval]` config).
+    #[serde(default)]
+    sensitive: bool,
 }
 
 /// Validate an MCP command name contains only safe characters.
@@ -161,7 +165,7 @@ struct SkillFrontmatter {
 
 /// Scan `{data_dir}/skills/*/SKILL.md` and return all valid skill definitions.
 pub fn load_skills(data_dir: &str) -> Vec<Skill> {
-    let dir = data_path(data_dir, "skills");
+    let dir = DataDir::new(data_dir).skills();
     let entries = match std::fs::read_dir(&dir) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
@@ -199,6 +203,7 @@ pub fn load_skills(data_dir: &str) -> Vec<Skill> {
                         name,
                         command: mfm.command,
                         args: mfm.args,
+                        sensitive: mfm.sensitive,
                     })
                 } else {
                     warn!(
@@ -318,7 +323,14 @@ fn parse_yaml_frontmatter(block: &str) -> Option<SkillFrontmatter> {
                         let command = parts.first().unwrap_or(&"").to_string();
                         if is_safe_mcp_command(&command) {
                             let args = parts[1..].iter().map(|s| s.to_string()).collect();
-                            mcp.insert(server_name, McpFrontmatter { command, args });
+                            mcp.insert(
+                                server_name,
+                                McpFrontmatter {
+                                    command,
+                                    args,
+                                    sensitive: false,
+                                },
+                            );
                         }
                     }
                 }
@@ -676,6 +688,22 @@ Body text.
         );
     }
 
+    #[test]
+    fn test_parse_toml_frontmatter_mcp_sensitive() {
+        let content = r#"---
+name = "mailer"
+description = "Send email."
+
+[mcp.gmail]
+command = "npx"
+args = ["@gmail/mcp"]
+sensitive = true
+---
+"#;
+        let fm = parse_skill_file(content).unwrap();
+        assert!(fm.mcp["gmail"].sensitive);
+    }
+
     #[test]
     fn test_parse_yaml_frontmatter_with_mcp_key() {
         let content = "\
@@ -733,6 +761,7 @@ description = \"No trigger or MCP.\"
             name: name.into(),
             command: "npx".into(),
             args: vec![format!("@{name}/mcp")],
+            sensitive: false,
         }
     }
 
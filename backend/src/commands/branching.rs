@@ -0,0 +1,104 @@
+//! Conversation rollback and snapshot handlers: /undo, /snapshot.
+
+use super::status::escape_md;
+use crate::i18n;
+use omega_core::error::OmegaError;
+use omega_memory::{Exchange, Store};
+
+/// Longest snapshot name accepted, in characters.
+const MAX_SNAPSHOT_NAME: usize = 40;
+
+/// Characters of the removed message quoted back by /undo.
+const UNDO_PREVIEW_CHARS: usize = 60;
+
+pub(super) async fn rollback(
+    store: &Store,
+    channel: &str,
+    sender_id: &str,
+    project: &str,
+) -> Result<Option<(Exchange, usize)>, OmegaError> {
+    let Some(exchange) = store.pop_last_exchange(channel, sender_id, project).await? else {
+        return Ok(None);
+    };
+    let mut cancelled = 0;
+    for id in &exchange.metadata.created_tasks {
+        if store.cancel_task(id, sender_id).await? {
+            cancelled += 1;
+        }
+    }
+    store.clear_session(channel, sender_id, project).await?;
+    Ok(Some((exchange, cancelled)))
+}
+
+pub(super) async fn handle_undo(
+    store: &Store,
+    channel: &str,
+    sender_id: &str,
+    project: &str,
+    lang: &str,
+) -> String {
+    match rollback(store, channel, sender_id, project).await {
+        Ok(Some((exchange, cancelled))) => {
+            i18n::undo_done(lang, &preview(&exchange.user), cancelled)
+        }
+        Ok(None) => i18n::t("nothing_to_undo", lang).to_string(),
+        Err(e) => format!("Error: {e}"),
+    }
+}
+
+/// Handle /snapshot — list, save (`<name>`) or delete (`delete <name>`).
+pub(super) async fn handle_snapshot(
+    store: &Store,
+    channel: &str,
+    sender_id: &str,
+    project: &str,
+    text: &str,
+    lang: &str,
+) -> String {
+    let args: Vec<&str> = text.split_whitespace().skip(1).collect();
+    match args.as_slice() {
+        [] => match store.list_snapshots(channel, sender_id, project).await {
+            Ok(snapshots) if snapshots.is_empty() => i18n::t("no_snapshots", lang).to_string(),
+            Ok(snapshots) => {
+                let mut out = format!("{}\n", i18n::t("snapshots_header", lang));
+                for s in &snapshots {
+                    out.push_str(&format!(
+                        "\n- {} ({}) {} {}",
+                        escape_md(&s.name),
+                        s.created_at,
+                        i18n::t("messages", lang),
+                        s.message_count
+                    ));
+                }
+                out.push_str(&format!("\n\n{}", i18n::t("snapshot_usage", lang)));
+                out
+            }
+            Err(e) => format!("Error: {e}"),
+        },
+        ["delete", name] => match store
+            .delete_snapshot(channel, sender_id, project, name)
+            .await
+        {
+            Ok(true) => i18n::t("snapshot_deleted", lang).to_string(),
+            Ok(false) => i18n::t("snapshot_not_found", lang).to_string(),
+            Err(e) => format!("Error: {e}"),
+        },
+        [name] if *name != "delete" && name.chars().count() <= MAX_SNAPSHOT_NAME => {
+            match store.save_snapshot(channel, sender_id, project, name).await {
+                Ok(Some(count)) => i18n::snapshot_saved(lang, &escape_md(name), count),
+                Ok(None) => i18n::t("no_active_conversation", lang).to_string(),
+                Err(e) => format!("Error: {e}"),
+            }
+        }
+        _ => i18n::t("snapshot_usage", lang).to_string(),
+    }
+}
+
+/// The start of `text` on one line, for quoting it back.
+pub(super) fn preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(UNDO_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}\u{2026}", &line[..end]),
+        None => line,
+    }
+}
@@ -1,14 +1,19 @@
 //! Built-in bot commands — instant responses, no provider call.
 
+mod branching;
 mod learning;
 mod settings;
 mod status;
 mod tasks;
 
+pub use settings::QueueMode;
+
 #[cfg(test)]
 mod tests;
 
-use omega_memory::Store;
+use crate::i18n;
+use omega_core::{config::TenantRole, error::OmegaError};
+use omega_memory::{Exchange, Store};
 use std::time::Instant;
 
 /// Grouped context for command execution.
@@ -21,8 +26,6 @@ pub struct CommandContext<'a> {
     pub provider_name: &'a str,
     pub skills: &'a [omega_skills::Skill],
     pub projects: &'a [omega_skills::Project],
-    pub heartbeat_enabled: bool,
-    pub heartbeat_interval_mins: u64,
     pub active_project: Option<&'a str>,
     /// Base system prompt size in characters (identity + soul + system).
     pub base_prompt_chars: usize,
@@ -35,8 +38,15 @@ pub enum Command {
     History,
     Facts,
     Forget,
+    Retry,
+    Undo,
+    Snapshot,
+    Branch,
     Tasks,
     Cancel,
+    Stop,
+    Restart,
+    Queue,
     Language,
     Personality,
     Skills,
@@ -45,15 +55,71 @@ pub enum Command {
     Purge,
     WhatsApp,
     Heartbeat,
+    Link,
+    Unlink,
+    Export,
     Learning,
     Token,
     Context,
     Setup,
     Google,
+    Build,
+    Topologies,
     Help,
 }
 
 impl Command {
+    /// Whether a sender with `role` may run this command.
+    ///
+    /// Commands that write to the shared data dir or instance-wide
+    /// integrations need admin; `/purge` is closed to guests.
+    pub fn allowed_for(&self, role: TenantRole) -> bool {
+        match self {
+            Self::Google | Self::WhatsApp | Self::Setup | Self::Build => role.is_admin(),
+            Self::Purge => role.can_modify(),
+            _ => true,
+        }
+    }
+
+    /// Name without the slash, as used in `[roles]` command lists.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Memory => "memory",
+            Self::History => "history",
+            Self::Facts => "facts",
+            Self::Forget => "forget",
+            Self::Retry => "retry",
+            Self::Undo => "undo",
+            Self::Snapshot => "snapshot",
+            Self::Branch => "branch",
+            Self::Tasks => "tasks",
+            Self::Cancel => "cancel",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+            Self::Queue => "queue",
+            Self::Language => "language",
+            Self::Personality => "personality",
+            Self::Skills => "skills",
+            Self::Projects => "projects",
+            Self::Project => "project",
+            Self::Purge => "purge",
+            Self::WhatsApp => "whatsapp",
+            Self::Heartbeat => "heartbeat",
+            Self::Link => "link",
+            Self::Unlink => "unlink",
+            Self::Export => "export",
+            Self::Learning => "learning",
+            Self::Token => "token",
+            Self::Context => "context",
+            Self::Setup => "setup",
+            Self::Google => "google",
+            Self::Build => "build",
+            Self::Topologies => "topologies",
+            Self::Help => "help",
+        }
+    }
+
     /// Parse a command from message text. Returns `None` for unknown `/` prefixes
     /// (which should pass through to the provider).
     pub fn parse(text: &str) -> Option<Self> {
@@ -66,8 +132,15 @@ impl Command {
             "/history" => Some(Self::History),
             "/facts" => Some(Self::Facts),
             "/forget" => Some(Self::Forget),
+            "/retry" => Some(Self::Retry),
+            "/undo" => Some(Self::Undo),
+            "/snapshot" => Some(Self::Snapshot),
+            "/branch" => Some(Self::Branch),
             "/tasks" => Some(Self::Tasks),
             "/cancel" => Some(Self::Cancel),
+            "/stop" => Some(Self::Stop),
+            "/restart" => Some(Self::Restart),
+            "/queue" => Some(Self::Queue),
             "/language" | "/lang" => Some(Self::Language),
             "/personality" => Some(Self::Personality),
             "/skills" => Some(Self::Skills),
@@ -76,11 +149,16 @@ impl Command {
             "/purge" => Some(Self::Purge),
             "/whatsapp" => Some(Self::WhatsApp),
             "/heartbeat" => Some(Self::Heartbeat),
+            "/link" => Some(Self::Link),
+            "/unlink" => Some(Self::Unlink),
+            "/export" => Some(Self::Export),
             "/learning" => Some(Self::Learning),
             "/token" => Some(Self::Token),
             "/context" => Some(Self::Context),
             "/setup" => Some(Self::Setup),
             "/google" => Some(Self::Google),
+            "/build" => Some(Self::Build),
+            "/topologies" => Some(Self::Topologies),
             "/help" => Some(Self::Help),
             _ => None,
         }
@@ -97,11 +175,43 @@ async fn resolve_lang(store: &Store, sender_id: &str) -> String {
         .unwrap_or_else(|| "English".to_string())
 }
 
+/// Return the /queue response for one user (public for gateway intercepts).
+pub async fn queue_text(store: &Store, sender_id: &str, text: &str, queued: usize) -> String {
+    let lang = resolve_lang(store, sender_id).await;
+    settings::handle_queue(store, sender_id, text, queued, &lang).await
+}
+
+/// Roll back the last exchange of a conversation (public for gateway intercepts).
+///
+/// Removes the message pair, cancels the tasks its markers created and drops
+/// the CLI session, which still holds the removed turn. Returns the removed
+/// exchange and how many tasks were cancelled.
+pub async fn rollback_last_exchange(
+    store: &Store,
+    channel: &str,
+    sender_id: &str,
+    project: &str,
+) -> Result<Option<(Exchange, usize)>, OmegaError> {
+    branching::rollback(store, channel, sender_id, project).await
+}
+
 /// Return the /help text for the given language (public for gateway intercepts).
 pub fn handle_help_text(lang: &str) -> String {
     status::handle_help(lang)
 }
 
+/// Return the /heartbeat status text for one user (public for gateway intercepts).
+pub fn heartbeat_status_text(
+    data_dir: &str,
+    enabled: bool,
+    settings: Option<&omega_memory::HeartbeatSettings>,
+    items: &[omega_memory::HeartbeatItem],
+    active_project: Option<&str>,
+    lang: &str,
+) -> String {
+    settings::handle_heartbeat(data_dir, enabled, settings, items, active_project, lang)
+}
+
 /// Handle a command and return the response text.
 pub async fn handle(cmd: Command, ctx: &CommandContext<'_>) -> String {
     let lang = resolve_lang(ctx.store, ctx.sender_id).await;
@@ -115,8 +225,39 @@ pub async fn handle(cmd: Command, ctx: &CommandContext<'_>) -> String {
         }
         Command::Facts => status::handle_facts(ctx.store, ctx.sender_id, &lang).await,
         Command::Forget => tasks::handle_forget(ctx.store, ctx.channel, ctx.sender_id, &lang).await,
+        // Retry and Branch are intercepted early in pipeline.rs -- these arms are a fallback.
+        Command::Retry | Command::Branch => status::handle_help(&lang),
+        Command::Undo => {
+            branching::handle_undo(
+                ctx.store,
+                ctx.channel,
+                ctx.sender_id,
+                ctx.active_project.unwrap_or(""),
+                &lang,
+            )
+            .await
+        }
+        Command::Snapshot => {
+            branching::handle_snapshot(
+                ctx.store,
+                ctx.channel,
+                ctx.sender_id,
+                ctx.active_project.unwrap_or(""),
+                ctx.text,
+                &lang,
+            )
+            .await
+        }
         Command::Tasks => tasks::handle_tasks(ctx.store, ctx.sender_id, &lang).await,
         Command::Cancel => tasks::handle_cancel(ctx.store, ctx.sender_id, ctx.text, &lang).await,
+        // A running request is stopped in dispatch_message -- reaching here means none is.
+        Command::Stop => i18n::t("nothing_running", &lang).to_string(),
+        // Likewise /restart: reaching here means no reply has messages queued behind it.
+        Command::Restart => i18n::t("nothing_to_restart", &lang).to_string(),
+        // While busy, /queue is answered in dispatch_message with the live queue length.
+        Command::Queue => {
+            settings::handle_queue(ctx.store, ctx.sender_id, ctx.text, 0, &lang).await
+        }
         Command::Language => {
             settings::handle_language(ctx.store, ctx.sender_id, ctx.text, &lang).await
         }
@@ -140,12 +281,12 @@ pub async fn handle(cmd: Command, ctx: &CommandContext<'_>) -> String {
         }
         Command::Purge => tasks::handle_purge(ctx.store, ctx.sender_id, &lang).await,
         Command::WhatsApp => settings::handle_whatsapp(),
-        Command::Heartbeat => settings::handle_heartbeat(
-            ctx.heartbeat_enabled,
-            ctx.heartbeat_interval_mins,
-            ctx.active_project,
-            &lang,
-        ),
+        // Heartbeat is intercepted early in pipeline.rs -- this arm is a fallback.
+        Command::Heartbeat => status::handle_help(&lang),
+        // Link/Unlink are intercepted early in pipeline.rs -- these arms are a fallback.
+        Command::Link | Command::Unlink => status::handle_help(&lang),
+        // Export is intercepted early in pipeline.rs -- this arm is a fallback.
+        Command::Export => status::handle_help(&lang),
         Command::Token => {
             status::handle_token(
                 ctx.store,
@@ -164,6 +305,10 @@ pub async fn handle(cmd: Command, ctx: &CommandContext<'_>) -> String {
         Command::Setup => status::handle_help(&lang),
         // Google is intercepted early in pipeline.rs -- this arm is a fallback.
         Command::Google => status::handle_help(&lang),
+        // Build is intercepted early in pipeline.rs -- this arm is a fallback.
+        Command::Build => status::handle_help(&lang),
+        // Topologies is intercepted early in pipeline.rs -- this arm is a fallback.
+        Command::Topologies => status::handle_help(&lang),
         Command::Help => status::handle_help(&lang),
     }
 }
@@ -1,9 +1,10 @@
-//! Configuration command handlers: /language, /personality, /skills, /projects, /project,
-//! /whatsapp, /heartbeat.
+//! Configuration command handlers: /language, /personality, /queue, /skills, /projects,
+//! /project, /whatsapp, /heartbeat.
 
 use crate::i18n;
-use crate::markers::{read_heartbeat_file, read_project_heartbeat_file};
-use omega_memory::Store;
+use crate::markers::{read_project_heartbeat_file, render_heartbeat_items};
+use omega_core::config::HeartbeatConfig;
+use omega_memory::{HeartbeatItem, HeartbeatSettings, Store};
 
 pub(super) async fn handle_language(
     store: &Store,
@@ -77,6 +78,79 @@ pub(super) async fn handle_personality(
     }
 }
 
+/// How messages sent while a request is running are handled (`/queue`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueMode {
+    /// Queued messages are answered together in one follow-up turn.
+    #[default]
+    Merge,
+    /// Like `Merge`, and each queued message offers `/restart`.
+    Ask,
+    /// Messages are handled concurrently instead of queued.
+    Parallel,
+}
+
+impl QueueMode {
+    /// Fact key holding the sender's mode.
+    pub const FACT_KEY: &'static str = "queue_mode";
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "merge" => Some(Self::Merge),
+            "ask" => Some(Self::Ask),
+            "parallel" => Some(Self::Parallel),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Ask => "ask",
+            Self::Parallel => "parallel",
+        }
+    }
+
+    /// The sender's stored mode, or the default.
+    pub async fn load(store: &Store, sender_id: &str) -> Self {
+        store
+            .get_fact(sender_id, Self::FACT_KEY)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or_default()
+    }
+}
+
+/// Handle /queue — show or set the busy-sender queue mode.
+///
+/// `queued` is the number of messages currently waiting, shown with the mode.
+pub(super) async fn handle_queue(
+    store: &Store,
+    sender_id: &str,
+    text: &str,
+    queued: usize,
+    lang: &str,
+) -> String {
+    let arg = text.split_whitespace().nth(1).unwrap_or_default();
+    if arg.is_empty() {
+        let mode = QueueMode::load(store, sender_id).await;
+        return i18n::queue_show(lang, mode.as_str(), queued);
+    }
+    let Some(mode) = QueueMode::parse(arg) else {
+        let current = QueueMode::load(store, sender_id).await;
+        return i18n::queue_show(lang, current.as_str(), queued);
+    };
+    match store
+        .store_fact(sender_id, QueueMode::FACT_KEY, mode.as_str())
+        .await
+    {
+        Ok(()) => i18n::queue_set(lang, mode.as_str()),
+        Err(e) => format!("Error: {e}"),
+    }
+}
+
 pub(super) fn handle_skills(skills: &[omega_skills::Skill], lang: &str) -> String {
     if skills.is_empty() {
         return i18n::t("no_skills", lang).to_string();
@@ -177,37 +251,56 @@ pub(super) fn handle_whatsapp() -> String {
     "WHATSAPP_QR".to_string()
 }
 
-/// Handle /heartbeat — show heartbeat status, interval, and watchlist items.
+/// Render /heartbeat — the user's heartbeat status, schedule, and watchlist items.
 ///
-/// When `active_project` is `Some`, reads the project-specific heartbeat file
-/// first and falls back to the global file if the project has none.
+/// `settings` is the sender's own heartbeat (None = never configured) and
+/// `items` the sender's items in the current scope. When `active_project` is
+/// `Some`, the project's HEARTBEAT.md under `data_dir` is shown above them.
 pub(super) fn handle_heartbeat(
+    data_dir: &str,
     enabled: bool,
-    interval_mins: u64,
+    settings: Option<&HeartbeatSettings>,
+    items: &[HeartbeatItem],
     active_project: Option<&str>,
     lang: &str,
 ) -> String {
     let status_label = i18n::t("heartbeat_status", lang);
-    let status_value = if enabled {
+    let status_value = if enabled && settings.is_some_and(|s| s.enabled) {
         i18n::t("heartbeat_enabled", lang)
     } else {
         i18n::t("heartbeat_disabled", lang)
     };
+    let interval_mins = settings
+        .map(|s| s.interval_minutes)
+        .unwrap_or_else(|| HeartbeatConfig::default().interval_minutes);
+    let hours = match settings {
+        Some(s) if !s.active_start.is_empty() && !s.active_end.is_empty() => {
+            format!("{}\u{2013}{}", s.active_start, s.active_end)
+        }
+        _ => i18n::t("heartbeat_always", lang).to_string(),
+    };
 
     let mut out = format!(
-        "{}\n\n{} {}\n{} {} {}",
+        "{}\n\n{} {}\n{} {} {}\n{} {}",
         i18n::t("heartbeat_header", lang),
         status_label,
         status_value,
         i18n::t("heartbeat_interval", lang),
         interval_mins,
         i18n::t("heartbeat_minutes", lang),
+        i18n::t("heartbeat_hours", lang),
+        hours,
     );
 
-    let checklist = match active_project {
-        Some(proj) => read_project_heartbeat_file(proj),
-        None => read_heartbeat_file(),
-    };
+    let mut parts: Vec<String> = active_project
+        .and_then(|proj| read_project_heartbeat_file(data_dir, proj))
+        .map(|c| c.trim().to_string())
+        .into_iter()
+        .collect();
+    if !items.is_empty() {
+        parts.push(render_heartbeat_items(items));
+    }
+    let checklist = (!parts.is_empty()).then(|| parts.join("\n\n"));
 
     match checklist {
         Some(content) => {
@@ -221,5 +314,6 @@ pub(super) fn handle_heartbeat(
             out.push_str(&format!("\n\n{}", i18n::t("heartbeat_no_watchlist", lang)));
         }
     }
+    out.push_str(&format!("\n\n{}", i18n::t("heartbeat_usage", lang)));
     out
 }
@@ -21,15 +21,30 @@ pub(super) async fn handle_status(
         .map(format_bytes)
         .unwrap_or_else(|_| "unknown".to_string());
 
+    let maintenance = match store.last_maintenance().await {
+        Ok(Some(run)) if run.integrity.is_empty() => {
+            format!("{} ({})", run.finished_at, i18n::t("integrity_ok", lang))
+        }
+        Ok(Some(run)) => format!(
+            "{} ({})",
+            run.finished_at,
+            i18n::t("integrity_failed", lang)
+        ),
+        Ok(None) => i18n::t("maintenance_never", lang).to_string(),
+        Err(_) => "unknown".to_string(),
+    };
+
     format!(
         "{}\n\
          {} {hours}h {minutes}m {secs}s\n\
          {} {provider_name}\n\
-         {} {db_size}",
+         {} {db_size}\n\
+         {} {maintenance}",
         i18n::t("status_header", lang),
         i18n::t("uptime", lang),
         i18n::t("provider", lang),
         i18n::t("database", lang),
+        i18n::t("maintenance_label", lang),
     )
 }
 
@@ -140,6 +155,17 @@ pub(super) fn handle_help(lang: &str) -> String {
          {}\n\
          {}\n\
          {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
+         {}\n\
          {}",
         i18n::t("commands_header", lang),
         i18n::t("help_status", lang),
@@ -149,8 +175,14 @@ pub(super) fn handle_help(lang: &str) -> String {
         i18n::t("help_history", lang),
         i18n::t("help_facts", lang),
         i18n::t("help_forget", lang),
+        i18n::t("help_retry", lang),
+        i18n::t("help_undo", lang),
+        i18n::t("help_snapshot", lang),
+        i18n::t("help_branch", lang),
         i18n::t("help_tasks", lang),
         i18n::t("help_cancel", lang),
+        i18n::t("help_stop", lang),
+        i18n::t("help_queue", lang),
         i18n::t("help_language", lang),
         i18n::t("help_personality", lang),
         i18n::t("help_purge", lang),
@@ -160,8 +192,13 @@ pub(super) fn handle_help(lang: &str) -> String {
         i18n::t("help_whatsapp", lang),
         i18n::t("help_learning", lang),
         i18n::t("help_heartbeat", lang),
+        i18n::t("help_link", lang),
+        i18n::t("help_unlink", lang),
+        i18n::t("help_export", lang),
         i18n::t("help_google", lang),
         i18n::t("help_setup", lang),
+        i18n::t("help_build", lang),
+        i18n::t("help_topologies", lang),
         i18n::t("help_help", lang),
     )
 }
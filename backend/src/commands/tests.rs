@@ -16,7 +16,10 @@ async fn test_store() -> Store {
     let config = MemoryConfig {
         backend: "sqlite".to_string(),
         db_path,
+        url: String::new(),
         max_context_messages: 10,
+        context_budget: Default::default(),
+        maintenance: Default::default(),
     };
     Store::new(&config).await.unwrap()
 }
@@ -46,6 +49,22 @@ fn test_parse_all_commands() {
     assert!(matches!(Command::parse("/forget"), Some(Command::Forget)));
     assert!(matches!(Command::parse("/tasks"), Some(Command::Tasks)));
     assert!(matches!(Command::parse("/cancel x"), Some(Command::Cancel)));
+    assert!(matches!(Command::parse("/stop"), Some(Command::Stop)));
+    assert!(matches!(Command::parse("/restart"), Some(Command::Restart)));
+    assert!(matches!(Command::parse("/queue ask"), Some(Command::Queue)));
+    assert!(matches!(
+        Command::parse("/retry complex"),
+        Some(Command::Retry)
+    ));
+    assert!(matches!(Command::parse("/undo"), Some(Command::Undo)));
+    assert!(matches!(
+        Command::parse("/snapshot plan"),
+        Some(Command::Snapshot)
+    ));
+    assert!(matches!(
+        Command::parse("/branch plan"),
+        Some(Command::Branch)
+    ));
     assert!(matches!(
         Command::parse("/language"),
         Some(Command::Language)
@@ -77,6 +96,14 @@ fn test_parse_all_commands() {
     assert!(matches!(Command::parse("/token"), Some(Command::Token)));
     assert!(matches!(Command::parse("/setup"), Some(Command::Setup)));
     assert!(matches!(Command::parse("/google"), Some(Command::Google)));
+    assert!(matches!(
+        Command::parse("/build resume todo-api"),
+        Some(Command::Build)
+    ));
+    assert!(matches!(
+        Command::parse("/topologies"),
+        Some(Command::Topologies)
+    ));
     assert!(matches!(Command::parse("/help"), Some(Command::Help)));
 }
 
@@ -111,6 +138,33 @@ fn test_parse_purge_command() {
     ));
 }
 
+#[test]
+fn test_command_allowed_for_role() {
+    for cmd in [
+        Command::Google,
+        Command::WhatsApp,
+        Command::Setup,
+        Command::Build,
+    ] {
+        assert!(cmd.allowed_for(TenantRole::Admin));
+        assert!(!cmd.allowed_for(TenantRole::User));
+        assert!(!cmd.allowed_for(TenantRole::Guest));
+    }
+    assert!(Command::Purge.allowed_for(TenantRole::User));
+    assert!(!Command::Purge.allowed_for(TenantRole::Guest));
+    assert!(Command::Help.allowed_for(TenantRole::Guest));
+    assert!(Command::Projects.allowed_for(TenantRole::Guest));
+}
+
+#[test]
+fn test_command_name_round_trips() {
+    for text in ["/status", "/purge", "/whatsapp", "/topologies", "/help"] {
+        let cmd = Command::parse(text).unwrap();
+        assert_eq!(format!("/{}", cmd.name()), text);
+    }
+    assert_eq!(Command::parse("/lang").unwrap().name(), "language");
+}
+
 #[test]
 fn test_parse_unknown_returns_none() {
     assert!(Command::parse("/unknown").is_none());
@@ -177,6 +231,137 @@ async fn test_personality_reset_when_already_default() {
     );
 }
 
+#[tokio::test]
+async fn test_queue_mode_set_and_show() {
+    let store = test_store().await;
+    assert_eq!(QueueMode::load(&store, "user1").await, QueueMode::Merge);
+
+    let result = settings::handle_queue(&store, "user1", "/queue ask", 0, "English").await;
+    assert!(result.contains("ask"), "should confirm the mode was set");
+    assert_eq!(QueueMode::load(&store, "user1").await, QueueMode::Ask);
+
+    let result = settings::handle_queue(&store, "user1", "/queue", 3, "English").await;
+    assert!(result.contains("Queue mode: ask"));
+    assert!(result.contains("Waiting messages: 3"));
+
+    // Unknown modes leave the setting alone and show usage.
+    let result = settings::handle_queue(&store, "user1", "/queue later", 0, "English").await;
+    assert!(result.contains("Usage: /queue"));
+    assert_eq!(QueueMode::load(&store, "user1").await, QueueMode::Ask);
+}
+
+#[tokio::test]
+async fn test_undo_removes_exchange_and_cancels_its_tasks() {
+    use omega_core::message::{IncomingMessage, OutgoingMessage};
+
+    let store = test_store().await;
+    let result = branching::handle_undo(&store, "telegram", "user1", "", "English").await;
+    assert!(result.contains("Nothing to undo"), "{result}");
+
+    let task_id = store
+        .create_task(
+            "telegram",
+            "user1",
+            "chat1",
+            "Call the dentist",
+            "2099-01-01T09:00:00",
+            None,
+            "reminder",
+            "",
+        )
+        .await
+        .unwrap();
+    let incoming = IncomingMessage {
+        id: uuid::Uuid::new_v4(),
+        channel: "telegram".to_string(),
+        sender_id: "user1".to_string(),
+        sender_name: None,
+        text: "Remind me to call the dentist".to_string(),
+        timestamp: chrono::Utc::now(),
+        reply_to: None,
+        attachments: vec![],
+        reply_target: Some("chat1".to_string()),
+        is_group: false,
+        source: None,
+        platform_message_id: None,
+    };
+    let mut response = OutgoingMessage {
+        text: "Done, I'll remind you.".to_string(),
+        ..Default::default()
+    };
+    response.metadata.created_tasks = vec![task_id];
+    store
+        .store_exchange(&incoming, &response, "")
+        .await
+        .unwrap();
+    store
+        .store_session("telegram", "user1", "", "sess-1")
+        .await
+        .unwrap();
+
+    let result = branching::handle_undo(&store, "telegram", "user1", "", "English").await;
+    assert!(result.contains("Remind me to call the dentist"), "{result}");
+    assert!(result.contains("cancelled 1 scheduled"), "{result}");
+    assert!(store
+        .get_tasks_for_sender("user1")
+        .await
+        .unwrap()
+        .is_empty());
+    // The CLI session still holds the removed turn.
+    assert_eq!(
+        store.get_session("telegram", "user1", "").await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_snapshot_save_list_delete() {
+    let store = test_store().await;
+    let result =
+        branching::handle_snapshot(&store, "telegram", "user1", "", "/snapshot plan", "English")
+            .await;
+    assert!(result.contains("No active conversation"), "{result}");
+
+    store
+        .get_or_create_conversation("telegram", "user1", "")
+        .await
+        .unwrap();
+    let result =
+        branching::handle_snapshot(&store, "telegram", "user1", "", "/snapshot plan", "English")
+            .await;
+    assert!(result.contains("/branch plan"), "{result}");
+
+    let result =
+        branching::handle_snapshot(&store, "telegram", "user1", "", "/snapshot", "English").await;
+    assert!(result.contains("- plan"), "{result}");
+
+    let result = branching::handle_snapshot(
+        &store,
+        "telegram",
+        "user1",
+        "",
+        "/snapshot delete plan",
+        "English",
+    )
+    .await;
+    assert!(result.contains("deleted"), "{result}");
+    let result =
+        branching::handle_snapshot(&store, "telegram", "user1", "", "/snapshot", "English").await;
+    assert!(result.contains("No snapshots"), "{result}");
+}
+
+#[test]
+fn test_undo_preview_is_one_short_line() {
+    assert_eq!(
+        branching::preview("call\n the  dentist"),
+        "call the dentist"
+    );
+    let long = "word ".repeat(40);
+    let short = branching::preview(&long);
+    assert!(short.ends_with('\u{2026}'));
+    assert_eq!(short.chars().count(), 61);
+}
+
 #[tokio::test]
 async fn test_purge_preserves_system_facts() {
     let store = test_store().await;
@@ -255,9 +440,36 @@ fn test_parse_heartbeat_command() {
     ));
 }
 
+fn heartbeat_settings(interval: u64) -> omega_memory::HeartbeatSettings {
+    omega_memory::HeartbeatSettings {
+        sender_id: "user1".to_string(),
+        channel: "telegram".to_string(),
+        reply_target: "user1".to_string(),
+        enabled: true,
+        interval_minutes: interval,
+        active_start: String::new(),
+        active_end: String::new(),
+    }
+}
+
+fn heartbeat_item(id: &str, description: &str) -> omega_memory::HeartbeatItem {
+    omega_memory::HeartbeatItem {
+        id: id.to_string(),
+        sender_id: "user1".to_string(),
+        project: String::new(),
+        description: description.to_string(),
+        cadence: "daily 09:00".to_string(),
+        alert_on_change: false,
+        last_checked_at: None,
+        last_result: None,
+        created_at: "2026-03-01 08:00:00".to_string(),
+    }
+}
+
 #[test]
 fn test_heartbeat_enabled() {
-    let result = settings::handle_heartbeat(true, 30, None, "English");
+    let hb = heartbeat_settings(30);
+    let result = settings::handle_heartbeat("/nonexistent", true, Some(&hb), &[], None, "English");
     assert!(result.contains("Heartbeat"), "should have header: {result}");
     assert!(result.contains("active"), "should show active: {result}");
     assert!(result.contains("30"), "should show interval: {result}");
@@ -265,20 +477,55 @@ fn test_heartbeat_enabled() {
         result.contains("minutes"),
         "should show minutes label: {result}"
     );
+    assert!(
+        result.contains("Active hours: always"),
+        "should show hours: {result}"
+    );
 }
 
 #[test]
 fn test_heartbeat_disabled() {
-    let result = settings::handle_heartbeat(false, 15, None, "English");
+    let hb = heartbeat_settings(15);
+    let result = settings::handle_heartbeat("/nonexistent", false, Some(&hb), &[], None, "English");
     assert!(
         result.contains("disabled"),
         "should show disabled: {result}"
     );
 }
 
+#[test]
+fn test_heartbeat_disabled_for_user() {
+    let mut hb = heartbeat_settings(15);
+    hb.enabled = false;
+    let result = settings::handle_heartbeat("/nonexistent", true, Some(&hb), &[], None, "English");
+    assert!(result.contains("disabled"), "{result}");
+    let unconfigured = settings::handle_heartbeat("/nonexistent", true, None, &[], None, "English");
+    assert!(unconfigured.contains("disabled"), "{unconfigured}");
+}
+
+#[test]
+fn test_heartbeat_shows_personal_checklist_and_hours() {
+    let mut hb = heartbeat_settings(60);
+    hb.active_start = "08:00".to_string();
+    hb.active_end = "22:00".to_string();
+    let items = [heartbeat_item("a1b2c3d4-0000", "Check inbox")];
+    let result =
+        settings::handle_heartbeat("/nonexistent", true, Some(&hb), &items, None, "English");
+    assert!(result.contains("08:00\u{2013}22:00"), "{result}");
+    assert!(
+        result.contains("- [a1b2c3d4] Check inbox (daily 09:00)"),
+        "{result}"
+    );
+    assert!(
+        result.contains("/heartbeat ["),
+        "should show usage: {result}"
+    );
+}
+
 #[test]
 fn test_heartbeat_localized() {
-    let result = settings::handle_heartbeat(true, 60, None, "Spanish");
+    let hb = heartbeat_settings(60);
+    let result = settings::handle_heartbeat("/nonexistent", true, Some(&hb), &[], None, "Spanish");
     assert!(
         result.contains("activo"),
         "should show Spanish status: {result}"
@@ -291,18 +538,50 @@ fn test_heartbeat_localized() {
 
 #[test]
 fn test_heartbeat_with_active_project_no_fallback_to_global() {
-    // When a project is active but has no HEARTBEAT.md, should NOT show global heartbeat
-    let with_project =
-        settings::handle_heartbeat(true, 30, Some("nonexistent-project-xyz"), "English");
+    // When a project is active but has no HEARTBEAT.md or items, show no watchlist
+    let hb = heartbeat_settings(30);
+    let with_project = settings::handle_heartbeat(
+        "/nonexistent",
+        true,
+        Some(&hb),
+        &[],
+        Some("nonexistent-project-xyz"),
+        "English",
+    );
     assert!(
-        with_project.contains("No watchlist")
-            || with_project.contains("no watchlist")
-            || with_project.contains("no_watchlist")
-            || !with_project.contains("Watchlist"),
-        "active project with no heartbeat file should show no-watchlist message, not global: {with_project}"
+        with_project.contains("No watchlist") && !with_project.contains("Personal item"),
+        "active project with no heartbeat file should show no-watchlist message, not personal: {with_project}"
     );
 }
 
+#[test]
+fn test_heartbeat_reads_project_file_from_data_dir() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data_dir = tmp.path().to_str().unwrap();
+    let project_dir = tmp.path().join("projects/garden");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("HEARTBEAT.md"), "- Water tomatoes\n").unwrap();
+
+    let hb = heartbeat_settings(30);
+    let items = [heartbeat_item("e5f6a7b8-0000", "Check soil moisture")];
+    let result =
+        settings::handle_heartbeat(data_dir, true, Some(&hb), &items, Some("garden"), "English");
+    assert!(result.contains("- Water tomatoes"), "{result}");
+    assert!(
+        result.contains("- [e5f6a7b8] Check soil moisture"),
+        "{result}"
+    );
+    let other = settings::handle_heartbeat(
+        "/nonexistent",
+        true,
+        Some(&hb),
+        &[],
+        Some("garden"),
+        "English",
+    );
+    assert!(!other.contains("Water tomatoes"), "{other}");
+}
+
 #[test]
 fn test_help_includes_heartbeat() {
     let result = status::handle_help("English");
@@ -312,6 +591,46 @@ fn test_help_includes_heartbeat() {
     );
 }
 
+#[test]
+fn test_help_includes_link_and_unlink() {
+    let result = status::handle_help("English");
+    assert!(result.contains("/link"), "help should list /link: {result}");
+    assert!(
+        result.contains("/unlink"),
+        "help should list /unlink: {result}"
+    );
+    assert!(matches!(
+        Command::parse("/link 042917"),
+        Some(Command::Link)
+    ));
+    assert!(matches!(Command::parse("/unlink"), Some(Command::Unlink)));
+}
+
+#[tokio::test]
+async fn test_status_shows_last_maintenance() {
+    let store = test_store().await;
+    let uptime = Instant::now();
+    let result = status::handle_status(&store, &uptime, "claude-code", "English").await;
+    assert!(result.contains("Maintenance: not run yet"), "{result}");
+
+    store
+        .record_maintenance(&omega_memory::MaintenanceRun::default())
+        .await
+        .unwrap();
+    let result = status::handle_status(&store, &uptime, "claude-code", "English").await;
+    assert!(result.contains("(integrity ok)"), "{result}");
+}
+
+#[test]
+fn test_help_includes_export() {
+    let result = status::handle_help("English");
+    assert!(
+        result.contains("/export"),
+        "help should list /export: {result}"
+    );
+    assert!(matches!(Command::parse("/export"), Some(Command::Export)));
+}
+
 #[test]
 fn test_parse_learning_command() {
     assert!(matches!(
@@ -696,6 +1015,10 @@ async fn test_token_with_active_conversation() {
             processing_time_ms: 0,
             model: None,
             session_id: None,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+            created_tasks: Vec::new(),
+            untrusted_sources: Vec::new(),
         },
         reply_target: None,
         ..Default::default()
@@ -0,0 +1,498 @@
+//! Human-in-the-loop approval for dangerous tool calls.
+//!
+//! HTTP providers ask a [`ChannelApprover`] before running a call flagged by
+//! the `[approval]` policy. The approver sends an approve/deny prompt to the
+//! user's channel and parks a oneshot sender in [`PendingApprovals`] under a
+//! request id shown in the prompt. The reply is intercepted in
+//! `dispatch_message` — before the busy-sender buffer, since the sender is by
+//! definition mid-request — and resolves it. Parallel requests from one sender
+//! can wait at once: "approve 7" answers prompt #7, a bare "approve" the oldest.
+//! No reply within the timeout counts as a denial. Every decision is written
+//! to the audit log.
+
+use super::keywords::{is_build_cancelled, is_build_confirmed};
+use super::Gateway;
+use async_trait::async_trait;
+use omega_core::{
+    config::ApprovalConfig,
+    context::{ApprovalGate, ApprovalRequest},
+    message::{IncomingMessage, MessageMetadata, OutgoingMessage},
+    traits::{Channel, ToolApprover},
+};
+use omega_memory::audit::{AuditEntry, AuditLogger, AuditStatus};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex};
+use tracing::{info, warn};
+
+/// Approvals waiting on a reply, keyed by `channel:sender_id`, then request id.
+pub(super) type PendingApprovals =
+    Arc<Mutex<HashMap<String, BTreeMap<u64, oneshot::Sender<bool>>>>>;
+
+/// Source of approval request ids (unique for the process lifetime, so a late
+/// reply can't hit a newer prompt).
+static NEXT_APPROVAL_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Asks one user, on the channel they wrote from, whether a tool call may run.
+pub(super) struct ChannelApprover {
+    channel: Arc<dyn Channel>,
+    channel_name: String,
+    sender_id: String,
+    sender_name: Option<String>,
+    reply_target: Option<String>,
+    sender_key: String,
+    lang: String,
+    timeout: Duration,
+    pending: PendingApprovals,
+    audit: AuditLogger,
+}
+
+#[async_trait]
+impl ToolApprover for ChannelApprover {
+    async fn request_approval(&self, request: &ApprovalRequest) -> bool {
+        let (tx, rx) = oneshot::channel();
+        let id = NEXT_APPROVAL_ID.fetch_add(1, Ordering::Relaxed);
+        self.pending
+            .lock()
+            .await
+            .entry(self.sender_key.clone())
+            .or_default()
+            .insert(id, tx);
+
+        let prompt = approval_prompt_message(&self.lang, id, request, self.timeout.as_secs());
+        let delivered = self
+            .channel
+            .send(OutgoingMessage {
+                text: prompt,
+                metadata: MessageMetadata::default(),
+                reply_target: self.reply_target.clone(),
+                // Commands and paths routinely contain `_` and `*`.
+                plain_text: true,
+                ..Default::default()
+            })
+            .await;
+
+        let outcome = match delivered {
+            Err(e) => {
+                warn!(
+                    "approval: failed to send prompt to {}: {e}",
+                    self.sender_key
+                );
+                "undelivered"
+            }
+            Ok(()) => match tokio::time::timeout(self.timeout, rx).await {
+                Ok(Ok(true)) => "approved",
+                Ok(Ok(false)) => "denied",
+                _ => {
+                    let msg = OutgoingMessage {
+                        text: approval_timeout_message(&self.lang).to_string(),
+                        metadata: MessageMetadata::default(),
+                        reply_target: self.reply_target.clone(),
+                        ..Default::default()
+                    };
+                    let _ = self.channel.send(msg).await;
+                    "timed out"
+                }
+            },
+        };
+        forget_pending(&self.pending, &self.sender_key, id).await;
+
+        let approved = outcome == "approved";
+        info!(
+            "approval: {} {} for {} ({})",
+            request.tool, outcome, self.sender_key, request.reason
+        );
+        let _ = self
+            .audit
+            .log(&AuditEntry {
+                channel: self.channel_name.clone(),
+                sender_id: self.sender_id.clone(),
+                sender_name: self.sender_name.clone(),
+                input_text: format!("[APPROVAL] {}: {}", request.tool, request.summary),
+                output_text: Some(outcome.to_string()),
+                provider_used: None,
+                model: None,
+                processing_ms: None,
+                status: if approved {
+                    AuditStatus::Ok
+                } else {
+                    AuditStatus::Denied
+                },
+                denial_reason: if approved {
+                    None
+                } else {
+                    Some(format!("{} ({outcome})", request.reason))
+                },
+            })
+            .await;
+
+        approved
+    }
+}
+
+/// Approval gate for background work (action tasks, heartbeats, build phases).
+///
+/// Nobody is there to ask, so calls the policy flags are denied. Returns
+/// `None` when approval is disabled.
+pub(super) fn background_approval_gate(config: &ApprovalConfig) -> Option<ApprovalGate> {
+    config.enabled.then(|| ApprovalGate {
+        policy: config.clone(),
+        approver: None,
+    })
+}
+
+impl Gateway {
+    /// Build the approval gate for a user-initiated request.
+    ///
+    /// Returns `None` when approval is disabled, so providers run ungated.
+    pub(super) async fn approval_gate(
+        &self,
+        incoming: &IncomingMessage,
+        sender_key: &str,
+    ) -> Option<ApprovalGate> {
+        if !self.approval_config.enabled {
+            return None;
+        }
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        let approver = self.channels.get(&incoming.channel).map(|channel| {
+            Arc::new(ChannelApprover {
+                channel: channel.clone(),
+                channel_name: incoming.channel.clone(),
+                sender_id: incoming.sender_id.clone(),
+                sender_name: incoming.sender_name.clone(),
+                reply_target: incoming.reply_target.clone(),
+                sender_key: sender_key.to_string(),
+                lang,
+                timeout: Duration::from_secs(self.approval_config.timeout_secs),
+                pending: self.pending_approvals.clone(),
+                audit: self.audit.clone(),
+            }) as Arc<dyn ToolApprover>
+        });
+        Some(ApprovalGate {
+            policy: self.approval_config.clone(),
+            approver,
+        })
+    }
+
+    /// Resolve a pending approval when `text` is an approve/deny reply.
+    ///
+    /// Returns `true` when the message was consumed as a decision.
+    pub(super) async fn resolve_pending_approval(&self, sender_key: &str, text: &str) -> bool {
+        resolve_pending(&self.pending_approvals, sender_key, text).await
+    }
+}
+
+/// Answer one of `sender_key`'s pending approvals from an approve/deny reply.
+///
+/// "approve 7" / "deny #7" targets request 7; a bare reply answers the oldest.
+/// Returns `true` when the message was consumed as a decision.
+async fn resolve_pending(pending: &PendingApprovals, sender_key: &str, text: &str) -> bool {
+    let Some((approved, id)) = parse_approval_decision(text) else {
+        return false;
+    };
+    let mut pending = pending.lock().await;
+    let Some(waiting) = pending.get_mut(sender_key) else {
+        return false;
+    };
+    let tx = match id {
+        Some(id) => waiting.remove(&id),
+        None => waiting.pop_first().map(|(_, tx)| tx),
+    };
+    if waiting.is_empty() {
+        pending.remove(sender_key);
+    }
+    match tx {
+        Some(tx) => {
+            let _ = tx.send(approved);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Drop request `id` from `sender_key`'s pending approvals (answered or expired).
+async fn forget_pending(pending: &PendingApprovals, sender_key: &str, id: u64) {
+    let mut pending = pending.lock().await;
+    if let Some(waiting) = pending.get_mut(sender_key) {
+        waiting.remove(&id);
+        if waiting.is_empty() {
+            pending.remove(sender_key);
+        }
+    }
+}
+
+/// [`parse_approval_reply`] plus an optional trailing request id (`7` or `#7`).
+fn parse_approval_decision(text: &str) -> Option<(bool, Option<u64>)> {
+    let trimmed = text.trim();
+    if let Some((word, last)) = trimmed.rsplit_once(char::is_whitespace) {
+        if let Ok(id) = last.trim_start_matches('#').parse::<u64>() {
+            return parse_approval_reply(word).map(|approved| (approved, Some(id)));
+        }
+    }
+    parse_approval_reply(trimmed).map(|approved| (approved, None))
+}
+
+/// Interpret a reply to an approval prompt: `Some(true)` approve, `Some(false)` deny.
+///
+/// Reuses the multilingual build confirmation words, so "yes"/"sí"/"да" work
+/// as well as the explicit "approve"/"deny".
+pub(super) fn parse_approval_reply(text: &str) -> Option<bool> {
+    let normalized = text.trim().to_lowercase();
+    if matches!(normalized.as_str(), "approve" | "approved" | "allow") || is_build_confirmed(text) {
+        Some(true)
+    } else if matches!(normalized.as_str(), "deny" | "denied" | "reject")
+        || is_build_cancelled(text)
+    {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Localized approve/deny prompt for a flagged tool call.
+fn approval_prompt_message(
+    lang: &str,
+    id: u64,
+    request: &ApprovalRequest,
+    timeout_secs: u64,
+) -> String {
+    let (header, reason, footer) = match lang {
+        "Spanish" => (
+            "⚠️ Aprobación necesaria",
+            "Motivo",
+            format!("Responde \"sí\" para aprobar o \"no\" para denegar (se deniega en {timeout_secs}s)."),
+        ),
+        "Portuguese" => (
+            "⚠️ Aprovação necessária",
+            "Motivo",
+            format!("Responda \"sim\" para aprovar ou \"não\" para negar (negado em {timeout_secs}s)."),
+        ),
+        "French" => (
+            "⚠️ Approbation requise",
+            "Raison",
+            format!("Répondez « oui » pour approuver ou « non » pour refuser (refusé dans {timeout_secs}s)."),
+        ),
+        "German" => (
+            "⚠️ Freigabe erforderlich",
+            "Grund",
+            format!("Antworte \"ja\" zum Freigeben oder \"nein\" zum Ablehnen (Ablehnung in {timeout_secs}s)."),
+        ),
+        "Italian" => (
+            "⚠️ Approvazione richiesta",
+            "Motivo",
+            format!("Rispondi \"sì\" per approvare o \"no\" per negare (negato tra {timeout_secs}s)."),
+        ),
+        "Dutch" => (
+            "⚠️ Goedkeuring vereist",
+            "Reden",
+            format!("Antwoord \"ja\" om goed te keuren of \"nee\" om te weigeren (geweigerd na {timeout_secs}s)."),
+        ),
+        "Russian" => (
+            "⚠️ Требуется подтверждение",
+            "Причина",
+            format!("Ответьте «да», чтобы разрешить, или «нет», чтобы запретить (отказ через {timeout_secs} с)."),
+        ),
+        _ => (
+            "⚠️ Approval needed",
+            "Reason",
+            format!("Reply \"approve\" or \"deny\" (auto-deny in {timeout_secs}s)."),
+        ),
+    };
+    format!(
+        "{header} (#{id})\n\n{}: {}\n{reason}: {}\n\n{footer}",
+        request.tool, request.summary, request.reason
+    )
+}
+
+/// Localized notice that an approval prompt expired.
+fn approval_timeout_message(lang: &str) -> &'static str {
+    match lang {
+        "Spanish" => "Sin respuesta — acción denegada.",
+        "Portuguese" => "Sem resposta — ação negada.",
+        "French" => "Pas de réponse — action refusée.",
+        "German" => "Keine Antwort — Aktion abgelehnt.",
+        "Italian" => "Nessuna risposta — azione negata.",
+        "Dutch" => "Geen antwoord — actie geweigerd.",
+        "Russian" => "Нет ответа — действие отклонено.",
+        _ => "No answer — action denied.",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use omega_core::{config::MemoryConfig, error::OmegaError};
+    use omega_memory::Store;
+    use std::any::Any;
+
+    /// Channel that records every prompt it is asked to send.
+    struct RecordingChannel {
+        sent: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        async fn start(&self) -> Result<tokio::sync::mpsc::Receiver<IncomingMessage>, OmegaError> {
+            let (_tx, rx) = tokio::sync::mpsc::channel(1);
+            Ok(rx)
+        }
+
+        async fn send(&self, message: OutgoingMessage) -> Result<(), OmegaError> {
+            self.sent.lock().unwrap().push(message.text);
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<(), OmegaError> {
+            Ok(())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    async fn test_store() -> Store {
+        let dir =
+            std::env::temp_dir().join(format!("__omega_approval_test_{}__", std::process::id()));
+        let _ = std::fs::create_dir_all(&dir);
+        let db_path = dir.join("test.db").to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&db_path);
+        let config = MemoryConfig {
+            backend: "sqlite".to_string(),
+            db_path,
+            url: String::new(),
+            max_context_messages: 10,
+            context_budget: Default::default(),
+            maintenance: Default::default(),
+        };
+        Store::new(&config).await.unwrap()
+    }
+
+    /// Wait until `n` requests of `sender_key` are pending; returns their ids.
+    async fn pending_ids(pending: &PendingApprovals, sender_key: &str, n: usize) -> Vec<u64> {
+        for _ in 0..200 {
+            if let Some(waiting) = pending.lock().await.get(sender_key) {
+                if waiting.len() == n {
+                    return waiting.keys().copied().collect();
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("expected {n} pending approvals");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_approvals_from_one_sender() {
+        let store = test_store().await;
+        let channel = Arc::new(RecordingChannel {
+            sent: std::sync::Mutex::new(Vec::new()),
+        });
+        let pending: PendingApprovals = Arc::new(Mutex::new(HashMap::new()));
+        let approver = Arc::new(ChannelApprover {
+            channel: channel.clone(),
+            channel_name: "test".to_string(),
+            sender_id: "alice".to_string(),
+            sender_name: None,
+            reply_target: None,
+            sender_key: "test:alice".to_string(),
+            lang: "English".to_string(),
+            timeout: Duration::from_secs(5),
+            pending: pending.clone(),
+            audit: AuditLogger::new(&store),
+        });
+        let request = |summary: &str| ApprovalRequest {
+            tool: "bash".to_string(),
+            summary: summary.to_string(),
+            reason: "command matches \"rm -rf\"".to_string(),
+        };
+
+        // Two parallel requests wait at once; neither overwrites the other.
+        let first = {
+            let approver = approver.clone();
+            let req = request("rm -rf a/");
+            tokio::spawn(async move { approver.request_approval(&req).await })
+        };
+        let ids = pending_ids(&pending, "test:alice", 1).await;
+        let second = {
+            let approver = approver.clone();
+            let req = request("rm -rf b/");
+            tokio::spawn(async move { approver.request_approval(&req).await })
+        };
+        let ids_both = pending_ids(&pending, "test:alice", 2).await;
+        let (first_id, second_id) = (ids[0], ids_both[1]);
+
+        // Deny the second by id; the first keeps waiting.
+        assert!(resolve_pending(&pending, "test:alice", &format!("deny #{second_id}")).await);
+        assert!(!second.await.unwrap());
+        let tag = format!("(#{second_id})");
+        assert!(channel
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|m| m.contains(&tag)));
+        assert_eq!(pending_ids(&pending, "test:alice", 1).await, vec![first_id]);
+
+        // A bare reply answers the remaining (oldest) one.
+        assert!(resolve_pending(&pending, "test:alice", "approve").await);
+        assert!(first.await.unwrap());
+        assert!(pending.lock().await.is_empty());
+        assert!(!resolve_pending(&pending, "test:alice", "approve").await);
+    }
+
+    #[test]
+    fn test_parse_approval_decision() {
+        assert_eq!(parse_approval_decision("approve"), Some((true, None)));
+        assert_eq!(parse_approval_decision("approve 7"), Some((true, Some(7))));
+        assert_eq!(parse_approval_decision(" no #12 "), Some((false, Some(12))));
+        assert_eq!(parse_approval_decision("rm 3"), None);
+    }
+
+    #[test]
+    fn test_parse_approval_reply() {
+        assert_eq!(parse_approval_reply("approve"), Some(true));
+        assert_eq!(parse_approval_reply("  Yes "), Some(true));
+        assert_eq!(parse_approval_reply("да"), Some(true));
+        assert_eq!(parse_approval_reply("deny"), Some(false));
+        assert_eq!(parse_approval_reply("No"), Some(false));
+        assert_eq!(parse_approval_reply("nein"), Some(false));
+        assert_eq!(parse_approval_reply("what does rm -rf do?"), None);
+    }
+
+    #[test]
+    fn test_approval_prompt_message_all_languages() {
+        let request = ApprovalRequest {
+            tool: "bash".to_string(),
+            summary: "rm -rf build/".to_string(),
+            reason: "command matches \"rm -rf\"".to_string(),
+        };
+        for lang in [
+            "English",
+            "Spanish",
+            "Portuguese",
+            "French",
+            "German",
+            "Italian",
+            "Dutch",
+            "Russian",
+        ] {
+            let msg = approval_prompt_message(lang, 7, &request, 120);
+            assert!(msg.contains("(#7)"), "{lang}: {msg}");
+            assert!(msg.contains("bash: rm -rf build/"), "{lang}: {msg}");
+            assert!(msg.contains("120"), "{lang}: {msg}");
+            assert!(!approval_timeout_message(lang).is_empty());
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! Authentication checks and WhatsApp QR pairing flow.
 
 use super::Gateway;
+use crate::i18n;
 use omega_channels::whatsapp;
 use omega_core::config::ChannelConfig;
 use omega_core::message::IncomingMessage;
+use omega_memory::audit::{AuditEntry, AuditStatus};
 use tracing::warn;
 
 /// Core auth logic — pure function operating on config, testable without a full Gateway.
@@ -64,6 +66,44 @@ impl Gateway {
         check_auth_inner(&self.channel_config, incoming)
     }
 
+    /// Refuse a request the sender's role does not allow: log it, record it
+    /// in the audit log with `reason`, and reply with the localized `i18n_key`.
+    pub(super) async fn deny_access(
+        &self,
+        incoming: &IncomingMessage,
+        reason: String,
+        i18n_key: &str,
+    ) {
+        warn!(
+            "access denied for {} on {}: {reason}",
+            incoming.sender_id, incoming.channel
+        );
+        let _ = self
+            .audit
+            .log(&AuditEntry {
+                channel: incoming.channel.clone(),
+                sender_id: incoming.sender_id.clone(),
+                sender_name: incoming.sender_name.clone(),
+                input_text: incoming.text.clone(),
+                output_text: None,
+                provider_used: None,
+                model: None,
+                processing_ms: None,
+                status: AuditStatus::Denied,
+                denial_reason: Some(reason),
+            })
+            .await;
+        let user_lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        self.send_text(incoming, i18n::t(i18n_key, &user_lang))
+            .await;
+    }
+
     /// Handle the WHATSAPP_QR flow: use the running bot's event stream for pairing.
     ///
     /// If WhatsApp is dormant (disabled/unconfigured), starts it on-demand
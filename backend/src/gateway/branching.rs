@@ -0,0 +1,125 @@
+//! `/retry` and `/branch` — rewinding the conversation.
+//!
+//! Both need more than a command reply: `/retry` rolls back the last
+//! exchange and hands the previous user message back to the pipeline to be
+//! answered again, and `/branch` summarizes the conversation it leaves in
+//! the background like `/forget`. The CLI session is dropped either way — it
+//! holds the turns that were rolled back or left behind — so the next call
+//! rebuilds its context from the stored messages.
+
+use omega_core::message::IncomingMessage;
+use tracing::{error, info};
+
+use super::Gateway;
+use crate::commands;
+use crate::i18n;
+
+impl Gateway {
+    /// Roll back the last exchange for `/retry [fast|complex|<model>]`.
+    ///
+    /// Returns the user message to answer again and the model asked for
+    /// (`None` = the default). Replies and returns `None` when there is
+    /// nothing to retry.
+    pub(super) async fn prepare_retry(
+        &self,
+        incoming: &IncomingMessage,
+        text: &str,
+        project: &str,
+    ) -> Option<(String, Option<String>)> {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+
+        match commands::rollback_last_exchange(
+            &self.memory,
+            &incoming.channel,
+            &incoming.sender_id,
+            project,
+        )
+        .await
+        {
+            Ok(Some((exchange, cancelled))) => {
+                let model = match text.split_whitespace().nth(1) {
+                    None => None,
+                    Some("fast") => Some(self.model_fast.clone()),
+                    Some("complex") => Some(self.model_complex.clone()),
+                    Some(model) => Some(model.to_string()),
+                };
+                info!(
+                    "[{}] retrying last message from {} (model: {}, {cancelled} task(s) cancelled)",
+                    incoming.channel,
+                    incoming.sender_id,
+                    model.as_deref().unwrap_or("default")
+                );
+                Some((exchange.user, model))
+            }
+            Ok(None) => {
+                self.send_text(incoming, i18n::t("nothing_to_retry", &lang))
+                    .await;
+                None
+            }
+            Err(e) => {
+                error!("retry: rollback failed for {}: {e}", incoming.sender_id);
+                self.send_text(incoming, &format!("Error: {e}")).await;
+                None
+            }
+        }
+    }
+
+    /// Handle `/branch <name>`: continue from a snapshot in a new conversation.
+    pub(super) async fn handle_branch_command(&self, incoming: &IncomingMessage, project: &str) {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+
+        let name = match incoming.text.split_whitespace().collect::<Vec<_>>()[..] {
+            [_, name] => name,
+            _ => {
+                self.send_text(incoming, i18n::t("branch_usage", &lang))
+                    .await;
+                return;
+            }
+        };
+
+        let previous = self
+            .memory
+            .get_open_conversation_id(&incoming.channel, &incoming.sender_id, project)
+            .await
+            .ok()
+            .flatten();
+        let text = match self
+            .memory
+            .branch_snapshot(&incoming.channel, &incoming.sender_id, project, name)
+            .await
+        {
+            Ok(Some(count)) => {
+                let _ = self
+                    .memory
+                    .clear_session(&incoming.channel, &incoming.sender_id, project)
+                    .await;
+                if let Some(conversation_id) = previous {
+                    self.summarize_in_background(conversation_id, "/branch");
+                }
+                info!(
+                    "[{}] {} branched from snapshot {name}",
+                    incoming.channel, incoming.sender_id
+                );
+                i18n::branch_done(&lang, name, count)
+            }
+            Ok(None) => i18n::t("snapshot_not_found", &lang).to_string(),
+            Err(e) => {
+                error!("branch: failed for {}: {e}", incoming.sender_id);
+                format!("Error: {e}")
+            }
+        };
+        self.send_text(incoming, &text).await;
+    }
+}
@@ -1,27 +1,38 @@
 //! Topology-driven build orchestrator — dispatches build phases from a loaded topology.
 //!
-//! Loads the "development" topology from `~/.omega/topologies/development/TOPOLOGY.toml`,
-//! then iterates over `topology.phases`, dispatching each based on its `phase_type`:
+//! Loads the selected topology from `~/.omega/topologies/<name>/TOPOLOGY.toml`
+//! ("development" unless the proposal or the active project's ROLE.md names
+//! another), then iterates over `topology.phases`, dispatching each based on its
+//! `phase_type`:
 //!
 //! - ParseBrief: run agent, parse output via parse_project_brief(), create dir
 //! - Standard: run agent, check for error, proceed
 //! - CorrectiveLoop: run agent, parse result, retry with fix_agent on failure
 //! - ParseSummary: run agent, parse output via parse_build_summary(), send final msg
 //!
+//! Phases whose `when` condition does not match the brief are skipped, and
+//! consecutive standard phases sharing a `parallel_group` run concurrently
+//! (see `builds_steps.rs`).
+//!
 //! Safety controls:
-//! - Pre-validation before phases (from topology config)
+//! - Pre-validation before phases (from topology config, incl. sandboxed commands)
+//! - Per-phase timeouts and tool whitelists
 //! - Post-validation after phases (from topology config)
 //! - Corrective loops with configurable retries
-//! - Chain state persisted on failure for recovery inspection
+//! - Chain state persisted on failure; `/build resume` continues from it
+//!
+//! Each completed step is committed to the project's git repository; a
+//! successful build ends with artifact delivery (see `builds_artifacts.rs`).
 
 use super::builds_agents::AgentFilesGuard;
+use super::builds_i18n::{phase_skipped_message, topology_load_failed_message};
 use super::builds_parse::*;
-use super::builds_topology::{self, PhaseType};
+use super::builds_resume::BuildProgressGuard;
+use super::builds_topology::{self, LoopStyle, PhaseType};
 use super::Gateway;
-use omega_core::{config::shellexpand, context::Context, message::IncomingMessage};
+use omega_core::{config::DataDir, message::IncomingMessage};
 use omega_memory::audit::{AuditEntry, AuditStatus};
 use std::path::PathBuf;
-use tracing::warn;
 
 /// State accumulated during orchestration, passed between phases.
 #[derive(Default)]
@@ -36,6 +47,8 @@ pub(super) struct OrchestratorState {
     pub(super) project_dir_str: Option<String>,
     /// Phases completed so far (names, for chain state).
     pub(super) completed_phases: Vec<String>,
+    /// Canonical sender id of whoever started the build.
+    pub(super) owner_id: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -58,22 +71,109 @@ impl Gateway {
             .flatten()
             .unwrap_or_else(|| "English".to_string());
 
-        // Load topology.
-        let loaded = match builds_topology::load_topology(&self.data_dir, "development") {
+        // Builds write to the shared workspace — admin only in multi-tenant
+        // mode, and only for named roles with `builds = true`.
+        let tenant = self.tenant_scope(&incoming.sender_id).await;
+        let denial = if !tenant.role.is_admin() {
+            Some(format!(
+                "tenant role '{}' may not run builds",
+                tenant.role.as_str()
+            ))
+        } else if !tenant.permissions.builds {
+            Some(format!(
+                "role '{}' may not run builds",
+                tenant.role_name.as_deref().unwrap_or_default()
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = denial {
+            if let Some(h) = typing_handle {
+                h.abort();
+            }
+            self.deny_access(incoming, reason, "tenant_denied").await;
+            return;
+        }
+
+        // Select topology: explicit `| name` suffix on the proposal, then the
+        // active project's ROLE.md, then the bundled default.
+        let installed: Vec<String> = builds_topology::list_topologies(&self.data_dir)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let (request, explicit) =
+            builds_topology::split_topology_suffix(&incoming.text, &installed);
+        let topology_name = match explicit {
+            Some(name) => name.to_string(),
+            None => self
+                .project_topology(&incoming.sender_id)
+                .await
+                .unwrap_or_else(|| "development".to_string()),
+        };
+        let mut build_incoming = incoming.clone();
+        build_incoming.text = request.to_string();
+
+        let loaded = match builds_topology::load_topology(&self.data_dir, &topology_name) {
             Ok(t) => t,
             Err(e) => {
                 if let Some(h) = typing_handle {
                     h.abort();
                 }
-                self.send_text(incoming, &format!("Failed to load topology: {e}"))
-                    .await;
+                self.send_text(
+                    incoming,
+                    &topology_load_failed_message(&user_lang, &topology_name, &e),
+                )
+                .await;
                 return;
             }
         };
 
+        self.run_build_phases(
+            &build_incoming,
+            typing_handle,
+            &user_lang,
+            &topology_name,
+            &loaded,
+            OrchestratorState {
+                owner_id: incoming.sender_id.clone(),
+                ..Default::default()
+            },
+        )
+        .await;
+    }
+
+    /// Build topology declared in the active project's ROLE.md, if any.
+    pub(super) async fn project_topology(&self, sender_id: &str) -> Option<String> {
+        let active = self
+            .memory
+            .get_fact(sender_id, "active_project")
+            .await
+            .ok()
+            .flatten()?;
+        let tenant = self.tenant_scope(sender_id).await;
+        self.tenant_projects(&tenant)
+            .into_iter()
+            .find(|p| p.name == active)?
+            .topology
+    }
+
+    /// Run the topology's phases, starting at the first one not yet in
+    /// `state.completed_phases` (all of them for a fresh build).
+    ///
+    /// Shared by fresh builds and `/build resume`. Progress is published to
+    /// `active_builds` for `/build status` until this returns.
+    pub(super) async fn run_build_phases(
+        &self,
+        incoming: &IncomingMessage,
+        typing_handle: Option<tokio::task::JoinHandle<()>>,
+        user_lang: &str,
+        topology_name: &str,
+        loaded: &builds_topology::LoadedTopology,
+        mut state: OrchestratorState,
+    ) {
         // Write agent files to workspace root BEFORE any phase runs.
-        let workspace_dir = PathBuf::from(shellexpand(&self.data_dir)).join("workspace");
-        let _agent_guard = match AgentFilesGuard::write_from_topology(&workspace_dir, &loaded).await
+        let workspace_dir = DataDir::new(&self.data_dir).workspace();
+        let _agent_guard = match AgentFilesGuard::write_from_topology(&workspace_dir, loaded).await
         {
             Ok(guard) => guard,
             Err(e) => {
@@ -86,179 +186,81 @@ impl Gateway {
             }
         };
 
-        let mut state = OrchestratorState::default();
-
-        for phase in &loaded.topology.phases {
-            let model = loaded.resolve_model(phase, &self.model_fast, &self.model_complex);
-
-            // Send localized phase message.
-            self.send_text(incoming, &phase_message_by_name(&user_lang, &phase.name))
-                .await;
-
-            // Run pre-validation if configured.
-            if let Some(ref validation) = phase.pre_validation {
-                if let Some(project_dir) = &state.project_dir {
-                    if let Some(err) = Gateway::run_validation(project_dir, validation) {
-                        if let Some(h) = typing_handle {
-                            h.abort();
-                        }
-                        self.send_text(incoming, &err).await;
-                        let cs = Self::chain_state_topo(
-                            &state,
-                            &format!("{} (validation)", phase.name),
-                            err,
-                        );
-                        if let Some(pd) = &state.project_dir {
-                            Gateway::save_chain_state(pd, &cs).await;
-                        }
-                        return;
-                    }
+        let phases = &loaded.topology.phases;
+        let start = phases
+            .iter()
+            .position(|p| !state.completed_phases.contains(&p.name))
+            .unwrap_or(phases.len());
+        let progress = BuildProgressGuard::register(
+            &self.active_builds,
+            format!("{}:{}", incoming.channel, incoming.sender_id),
+            topology_name,
+            phases.len(),
+        );
+        progress.record(&state);
+
+        for step in builds_topology::phase_groups(phases, start) {
+            // A resumed parallel group may be partly done; `when` may exclude phases.
+            let mut runnable = Vec::new();
+            for phase in &phases[step.clone()] {
+                if state.completed_phases.contains(&phase.name) {
+                    continue;
                 }
-            }
-
-            // Dispatch based on phase type.
-            match phase.phase_type {
-                PhaseType::ParseBrief => {
-                    if let Err(reason) = self
-                        .execute_parse_brief(incoming, phase, model, &mut state)
-                        .await
-                    {
-                        if let Some(h) = typing_handle {
-                            h.abort();
-                        }
-                        self.send_text(incoming, &reason).await;
-                        return;
-                    }
+                if phase.applies_to(state.brief.as_ref()) {
+                    runnable.push(phase);
+                } else {
+                    self.send_text(incoming, &phase_skipped_message(user_lang, &phase.name))
+                        .await;
+                    state.completed_phases.push(phase.name.clone());
                 }
-                PhaseType::Standard => {
-                    if let Err(reason) = self.execute_standard(incoming, phase, model, &state).await
-                    {
-                        if let Some(h) = typing_handle {
-                            h.abort();
-                        }
-                        self.send_text(incoming, &reason).await;
-                        return;
-                    }
+            }
+            let names: Vec<&str> = runnable.iter().map(|p| p.name.as_str()).collect();
+            progress.set_phase(step.start, &names.join(" + "));
+
+            let ok = match runnable.as_slice() {
+                [] => true,
+                [phase] => {
+                    self.run_phase(
+                        incoming,
+                        user_lang,
+                        topology_name,
+                        loaded,
+                        phase,
+                        &mut state,
+                    )
+                    .await
                 }
-                PhaseType::CorrectiveLoop => {
-                    let retry = match &phase.retry {
-                        Some(r) => r,
-                        None => {
-                            if let Some(h) = typing_handle {
-                                h.abort();
-                            }
-                            self.send_text(
-                                incoming,
-                                &format!(
-                                    "Configuration error: phase '{}' is corrective-loop but has no retry config",
-                                    phase.name
-                                ),
-                            )
-                            .await;
-                            return;
-                        }
-                    };
-
-                    if let Err(reason) = self
-                        .run_corrective_loop(incoming, &state, &user_lang, phase, retry, model)
+                group => {
+                    let models: Vec<&str> = group
+                        .iter()
+                        .map(|p| loaded.resolve_model(p, &self.model_fast, &self.model_complex))
+                        .collect();
+                    match self
+                        .run_parallel_phases(incoming, user_lang, group, &models, &mut state)
                         .await
                     {
-                        if let Some(h) = typing_handle {
-                            h.abort();
+                        Ok(()) => true,
+                        Err((failed, reason)) => {
+                            self.send_text(incoming, &reason).await;
+                            Self::save_topo_failure(&state, topology_name, &failed, reason).await;
+                            false
                         }
-                        let project_dir_str =
-                            state.project_dir_str.as_deref().unwrap_or("(unknown)");
-                        let brief_name = state
-                            .brief
-                            .as_ref()
-                            .map(|b| b.name.as_str())
-                            .unwrap_or("(unknown)");
-
-                        // Send exhausted message matching the loop type.
-                        let exhausted_msg = if phase.name == "qa" {
-                            qa_exhausted_message(&user_lang, retry.max, &reason, project_dir_str)
-                        } else {
-                            review_exhausted_message(
-                                &user_lang,
-                                retry.max,
-                                &reason,
-                                project_dir_str,
-                            )
-                        };
-                        self.send_text(incoming, &exhausted_msg).await;
-                        self.audit_build(incoming, brief_name, "failed", &reason)
-                            .await;
-                        let cs = Self::chain_state_topo(&state, &phase.name, reason);
-                        if let Some(pd) = &state.project_dir {
-                            Gateway::save_chain_state(pd, &cs).await;
-                        }
-                        return;
                     }
                 }
-                PhaseType::ParseSummary => {
-                    if let Err(reason) = self
-                        .execute_parse_summary(incoming, phase, model, &state)
-                        .await
-                    {
-                        // Delivery error is partial success.
-                        if let Some(h) = typing_handle {
-                            h.abort();
-                        }
-                        let brief_name = state
-                            .brief
-                            .as_ref()
-                            .map(|b| b.name.as_str())
-                            .unwrap_or("(unknown)");
-                        self.send_text(
-                            incoming,
-                            &format!(
-                                "Build complete but delivery had issues: {reason}\nProject: `{brief_name}`"
-                            ),
-                        )
-                        .await;
-                        self.audit_build(incoming, brief_name, "partial", &reason)
-                            .await;
-                        return;
-                    }
+            };
+            if !ok {
+                if let Some(h) = typing_handle {
+                    h.abort();
                 }
+                return;
             }
-
-            // Run post-validation if configured.
-            if let Some(ref paths) = phase.post_validation {
+            // One commit per step, so the project history shows each agent's changes.
+            if !names.is_empty() {
                 if let Some(project_dir) = &state.project_dir {
-                    for path in paths {
-                        // Reject path traversal in post_validation paths.
-                        if path.contains("..") || path.starts_with('/') || path.contains('\\') {
-                            if let Some(h) = typing_handle {
-                                h.abort();
-                            }
-                            let msg = format!(
-                                "Post-validation rejected: path '{}' contains invalid characters.",
-                                path
-                            );
-                            self.send_text(incoming, &msg).await;
-                            return;
-                        }
-                        if !project_dir.join(path).exists() {
-                            if let Some(h) = typing_handle {
-                                h.abort();
-                            }
-                            let msg = format!(
-                                "{} phase completed but {} was not generated. Build stopped.",
-                                phase.name, path
-                            );
-                            self.send_text(incoming, &msg).await;
-                            return;
-                        }
-                    }
-                    // Post-validation passed — send confirmation for architect.
-                    if phase.name == "architect" {
-                        self.send_text(incoming, "Architecture defined.").await;
-                    }
+                    self.commit_phase(project_dir, &names.join(" + ")).await;
                 }
             }
-
-            state.completed_phases.push(phase.name.clone());
+            progress.record(&state);
         }
 
         // All phases completed successfully.
@@ -270,152 +272,159 @@ impl Gateway {
             .as_ref()
             .map(|b| b.name.as_str())
             .unwrap_or("(unknown)");
+        self.deliver_build_artifacts(incoming, user_lang, loaded, &state)
+            .await;
         self.audit_build(incoming, brief_name, "success", "").await;
+        if let Some(pd) = &state.project_dir {
+            Gateway::clear_chain_state(pd).await;
+        }
     }
 
-    /// Execute a ParseBrief phase: run analyst, parse brief, create project dir.
-    async fn execute_parse_brief(
+    /// Run one phase: pre-validation, dispatch by `phase_type`, post-validation.
+    ///
+    /// Reports failures to the user (and persists chain state where resumable);
+    /// returns false to stop the build.
+    async fn run_phase(
         &self,
         incoming: &IncomingMessage,
+        user_lang: &str,
+        topology_name: &str,
+        loaded: &builds_topology::LoadedTopology,
         phase: &builds_topology::Phase,
-        model: &str,
         state: &mut OrchestratorState,
-    ) -> Result<(), String> {
-        let brief_text = self
-            .run_build_phase(&phase.agent, &incoming.text, model, phase.max_turns)
-            .await
-            .map_err(|e| format!("Could not analyze your build request: {e}"))?;
-
-        let brief = parse_project_brief(&brief_text)
-            .ok_or("Could not parse the build brief. Please try rephrasing.")?;
+    ) -> bool {
+        let model = loaded.resolve_model(phase, &self.model_fast, &self.model_complex);
 
-        let project_dir = PathBuf::from(shellexpand(&self.data_dir))
-            .join("workspace/builds")
-            .join(&brief.name);
-        let project_dir_str = project_dir.display().to_string();
-
-        tokio::fs::create_dir_all(&project_dir)
-            .await
-            .map_err(|e| format!("Failed to create project directory: {e}"))?;
-
-        self.send_text(
-            incoming,
-            &format!(
-                "Building `{}` \u{2014} {}. I'll keep you posted.",
-                brief.name, brief.scope
-            ),
-        )
-        .await;
-
-        state.brief_text = Some(brief_text);
-        state.project_dir = Some(project_dir);
-        state.project_dir_str = Some(project_dir_str);
-        state.brief = Some(brief);
-        Ok(())
-    }
-
-    /// Execute a Standard phase: run agent, check for error.
-    async fn execute_standard(
-        &self,
-        incoming: &IncomingMessage,
-        phase: &builds_topology::Phase,
-        model: &str,
-        state: &OrchestratorState,
-    ) -> Result<(), String> {
-        let project_dir_str = state.project_dir_str.as_deref().unwrap_or("");
-        let brief_text = state.brief_text.as_deref().unwrap_or("");
-        let brief_name = state.brief.as_ref().map(|b| b.name.as_str()).unwrap_or("");
+        // Send localized phase message.
+        self.send_text(incoming, &phase_message_by_name(user_lang, &phase.name))
+            .await;
 
-        // Build phase-specific prompt.
-        let prompt = match phase.name.as_str() {
-            "architect" => {
-                format!(
-                    "Project brief:\n{brief_text}\nBegin architecture design in {project_dir_str}."
+        // Run pre-validation if configured.
+        if let (Some(validation), Some(project_dir)) = (&phase.pre_validation, &state.project_dir) {
+            if let Some(err) = self
+                .run_pre_validation(project_dir, validation, phase)
+                .await
+            {
+                self.send_text(incoming, &err).await;
+                Self::save_topo_failure(
+                    state,
+                    topology_name,
+                    &format!("{} (validation)", phase.name),
+                    err,
                 )
+                .await;
+                return false;
             }
-            "test-writer" => {
-                format!("Read specs/ in {project_dir_str} and write failing tests. Begin.")
+        }
+
+        // Dispatch based on phase type.
+        match phase.phase_type {
+            PhaseType::ParseBrief => {
+                if let Err(reason) = self
+                    .execute_parse_brief(incoming, phase, model, state)
+                    .await
+                {
+                    self.send_text(incoming, &reason).await;
+                    return false;
+                }
             }
-            "developer" => {
-                format!("Read the tests and specs/ in {project_dir_str}. Implement until all tests pass. Begin.")
+            PhaseType::Standard => {
+                if let Err(reason) = self.execute_standard(incoming, phase, model, state).await {
+                    self.send_text(incoming, &reason).await;
+                    Self::save_topo_failure(state, topology_name, &phase.name, reason).await;
+                    return false;
+                }
+            }
+            PhaseType::CorrectiveLoop => {
+                let Some(retry) = &phase.retry else {
+                    self.send_text(
+                        incoming,
+                        &format!(
+                            "Configuration error: phase '{}' is corrective-loop but has no retry config",
+                            phase.name
+                        ),
+                    )
+                    .await;
+                    return false;
+                };
+
+                if let Err(reason) = self
+                    .run_corrective_loop(incoming, state, user_lang, phase, retry, model)
+                    .await
+                {
+                    let project_dir_str = state.project_dir_str.as_deref().unwrap_or("(unknown)");
+                    let brief_name = state
+                        .brief
+                        .as_ref()
+                        .map(|b| b.name.as_str())
+                        .unwrap_or("(unknown)");
+
+                    // Send exhausted message matching the loop style.
+                    let exhausted_msg = match phase.loop_style() {
+                        LoopStyle::Qa => {
+                            qa_exhausted_message(user_lang, retry.max, &reason, project_dir_str)
+                        }
+                        LoopStyle::Review => {
+                            review_exhausted_message(user_lang, retry.max, &reason, project_dir_str)
+                        }
+                    };
+                    self.send_text(incoming, &exhausted_msg).await;
+                    self.audit_build(incoming, brief_name, "failed", &reason)
+                        .await;
+                    Self::save_topo_failure(state, topology_name, &phase.name, reason).await;
+                    return false;
+                }
+            }
+            PhaseType::ParseSummary => {
+                if let Err(reason) = self
+                    .execute_parse_summary(incoming, phase, model, state)
+                    .await
+                {
+                    // Delivery error is partial success.
+                    let brief_name = state
+                        .brief
+                        .as_ref()
+                        .map(|b| b.name.as_str())
+                        .unwrap_or("(unknown)");
+                    self.send_text(
+                        incoming,
+                        &format!(
+                            "Build complete but delivery had issues: {reason}\nProject: `{brief_name}`"
+                        ),
+                    )
+                    .await;
+                    self.audit_build(incoming, brief_name, "partial", &reason)
+                        .await;
+                    Self::save_topo_failure(state, topology_name, &phase.name, reason).await;
+                    return false;
+                }
             }
-            _ => format!("Execute phase '{}' in {project_dir_str}.", phase.name),
-        };
-
-        self.run_build_phase(&phase.agent, &prompt, model, phase.max_turns)
-            .await
-            .map_err(|e| {
-                format!(
-                    "{} phase failed: {e}. Partial results in `{brief_name}`.",
-                    phase.name
-                )
-            })?;
-
-        // Phase-specific completion messages.
-        if phase.name == "test-writer" {
-            self.send_text(incoming, "Tests written.").await;
-        } else if phase.name == "developer" {
-            self.send_text(incoming, "Implementation complete \u{2014} verifying...")
-                .await;
         }
 
-        Ok(())
-    }
-
-    /// Execute a ParseSummary phase: run delivery, parse summary, send final message.
-    ///
-    /// Note: typing handle lifecycle is managed by the caller (handle_build_request).
-    /// This method does NOT abort the typing handle.
-    async fn execute_parse_summary(
-        &self,
-        incoming: &IncomingMessage,
-        phase: &builds_topology::Phase,
-        model: &str,
-        state: &OrchestratorState,
-    ) -> Result<(), String> {
-        let project_dir_str = state.project_dir_str.as_deref().unwrap_or("");
-        let brief_name = state.brief.as_ref().map(|b| b.name.as_str()).unwrap_or("");
-        let skills_dir = PathBuf::from(shellexpand(&self.data_dir)).join("skills");
-        let skills_dir_str = skills_dir.display().to_string();
-
-        let delivery_prompt = format!(
-            "Create docs and skill file for {brief_name} in {project_dir_str}. Skills dir: {skills_dir_str}.",
-        );
-
-        let delivery_text = self
-            .run_build_phase(&phase.agent, &delivery_prompt, model, phase.max_turns)
-            .await?;
-
-        // Parse and send final summary.
-        let final_msg = if let Some(summary) = parse_build_summary(&delivery_text) {
-            format!(
-                "Build complete!\n\n\
-                 *{}*\n\
-                 {}\n\n\
-                 Location: `{}`\n\
-                 Language: {}\n\
-                 Usage: `{}`{}",
-                summary.project,
-                summary.summary,
-                summary.location,
-                summary.language,
-                summary.usage,
-                summary
-                    .skill
-                    .as_ref()
-                    .map(|s| format!("\nSkill: {s}"))
-                    .unwrap_or_default(),
-            )
-        } else {
-            format!("Build complete!\n\nProject `{brief_name}` is ready.",)
-        };
+        // Run post-validation if configured.
+        if let (Some(_), Some(project_dir)) = (&phase.post_validation, &state.project_dir) {
+            if let Err(msg) = Self::run_post_validation(phase, project_dir) {
+                self.send_text(incoming, &msg).await;
+                Self::save_topo_failure(state, topology_name, &phase.name, msg).await;
+                return false;
+            }
+            // Post-validation passed — send confirmation for architect.
+            if phase.name == "architect" {
+                self.send_text(incoming, "Architecture defined.").await;
+            }
+        }
 
-        self.send_text(incoming, &final_msg).await;
-        Ok(())
+        state.completed_phases.push(phase.name.clone());
+        true
     }
 
     /// Build a `ChainState` from the current orchestrator state at a given failure point.
-    fn chain_state_topo(state: &OrchestratorState, failed: &str, reason: String) -> ChainState {
+    fn chain_state_topo(
+        state: &OrchestratorState,
+        topology_name: &str,
+        failed: &str,
+        reason: String,
+    ) -> ChainState {
         ChainState {
             project_name: state
                 .brief
@@ -426,40 +435,23 @@ impl Gateway {
             completed_phases: state.completed_phases.clone(),
             failed_phase: Some(failed.to_string()),
             failure_reason: Some(reason),
-            topology_name: Some("development".to_string()),
+            topology_name: Some(topology_name.to_string()),
+            brief_text: state.brief_text.clone(),
+            owner_id: Some(state.owner_id.clone()),
         }
     }
 
-    /// Generic phase runner with retry logic (3 attempts, 2s delay).
-    ///
-    /// Each phase gets a fresh Context with `agent_name` set and no session_id.
-    /// The agent file provides the system prompt; only the user message is sent via `-p`.
-    pub(super) async fn run_build_phase(
-        &self,
-        agent_name: &str,
-        user_message: &str,
-        model: &str,
-        max_turns: Option<u32>,
-    ) -> Result<String, String> {
-        let mut ctx = Context::new(user_message);
-        ctx.system_prompt = String::new();
-        ctx.agent_name = Some(agent_name.to_string());
-        ctx.model = Some(model.to_string());
-        // Explicit max_turns prevents auto-resume from losing agent context.
-        ctx.max_turns = Some(max_turns.unwrap_or(100));
-
-        for attempt in 1..=3u32 {
-            match self.provider.complete(&ctx).await {
-                Ok(resp) => return Ok(resp.text),
-                Err(e) => {
-                    warn!("build phase '{agent_name}' attempt {attempt}/3 failed: {e}");
-                    if attempt < 3 {
-                        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-                    }
-                }
-            }
+    /// Persist chain state for a failed phase (no-op before the project dir exists).
+    async fn save_topo_failure(
+        state: &OrchestratorState,
+        topology_name: &str,
+        failed: &str,
+        reason: String,
+    ) {
+        if let Some(pd) = &state.project_dir {
+            let cs = Self::chain_state_topo(state, topology_name, failed, reason);
+            Gateway::save_chain_state(pd, &cs).await;
         }
-        Err(format!("phase '{agent_name}' failed after 3 attempts"))
     }
 
     /// Log an audit entry for a build operation.
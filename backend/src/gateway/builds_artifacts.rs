@@ -0,0 +1,342 @@
+//! Build artifacts — per-phase git history and end-of-build delivery.
+//!
+//! Every build project is a git repository with one commit per completed phase
+//! (or parallel group), so `git log -p` shows what each agent changed. After the
+//! last phase the user receives the file tree, a zip of the project
+//! (`git archive`), the history as a git bundle, and any test/coverage reports
+//! produced by `command` validations.
+
+use super::builds::OrchestratorState;
+use super::builds_i18n::{
+    build_archive_caption, build_artifact_too_large_message, build_files_header,
+    build_history_caption, build_report_caption,
+};
+use super::builds_steps::validation_log_path;
+use super::builds_topology::{LoadedTopology, ValidationType};
+use super::Gateway;
+use omega_core::{config::shellexpand, message::IncomingMessage};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tracing::{info, warn};
+
+/// Paths kept out of the project history (orchestrator state, build output).
+const GIT_EXCLUDES: &str = "docs/.workflow/\ntarget/\nnode_modules/\n__pycache__/\n.venv/\n";
+
+/// Entries listed in the file tree message before it is truncated.
+const MAX_TREE_ENTRIES: usize = 80;
+
+/// Maximum directory depth shown in the file tree.
+const MAX_TREE_DEPTH: usize = 6;
+
+/// Largest file sent through a channel (Telegram bots are capped at 50 MB).
+const MAX_DOCUMENT_BYTES: u64 = 45 * 1024 * 1024;
+
+impl Gateway {
+    /// A sandboxed `git` command in the project directory with a fixed identity.
+    fn build_git(&self, project_dir: &Path) -> Command {
+        let data_dir = PathBuf::from(shellexpand(&self.data_dir));
+        let mut cmd = omega_sandbox::protected_command("git", &data_dir);
+        cmd.current_dir(project_dir)
+            .env("GIT_AUTHOR_NAME", "OMEGA")
+            .env("GIT_AUTHOR_EMAIL", "omega@localhost")
+            .env("GIT_COMMITTER_NAME", "OMEGA")
+            .env("GIT_COMMITTER_EMAIL", "omega@localhost")
+            .kill_on_drop(true);
+        cmd
+    }
+
+    /// Run a git subcommand; logs and returns false on failure.
+    async fn run_build_git(&self, project_dir: &Path, args: &[&str]) -> bool {
+        match self.build_git(project_dir).args(args).output().await {
+            Ok(out) if out.status.success() => true,
+            Ok(out) => {
+                warn!(
+                    "build git {} failed: {}",
+                    args.first().unwrap_or(&""),
+                    String::from_utf8_lossy(&out.stderr).trim()
+                );
+                false
+            }
+            Err(e) => {
+                warn!("build git unavailable: {e}");
+                false
+            }
+        }
+    }
+
+    /// Commit the project after a phase. Initializes the repository on first use.
+    ///
+    /// Best-effort: a missing `git` binary only costs the history, not the build.
+    pub(super) async fn commit_phase(&self, project_dir: &Path, label: &str) {
+        if !project_dir.join(".git").exists() {
+            if !self.run_build_git(project_dir, &["init", "-q"]).await {
+                return;
+            }
+            let exclude = project_dir.join(".git/info/exclude");
+            if let Some(parent) = exclude.parent() {
+                let _ = tokio::fs::create_dir_all(parent).await;
+            }
+            if let Err(e) = tokio::fs::write(&exclude, GIT_EXCLUDES).await {
+                warn!("failed to write build git excludes: {e}");
+            }
+        }
+        if self.run_build_git(project_dir, &["add", "-A"]).await {
+            let message = format!("phase: {label}");
+            self.run_build_git(
+                project_dir,
+                &["commit", "-q", "--allow-empty", "-m", &message],
+            )
+            .await;
+        }
+    }
+
+    /// Send the file tree, project archive, history bundle, and validation
+    /// reports after a successful build. Each artifact is best-effort.
+    pub(super) async fn deliver_build_artifacts(
+        &self,
+        incoming: &IncomingMessage,
+        user_lang: &str,
+        loaded: &LoadedTopology,
+        state: &OrchestratorState,
+    ) {
+        let (Some(project_dir), Some(brief)) = (&state.project_dir, &state.brief) else {
+            return;
+        };
+
+        let tree = render_file_tree(project_dir, MAX_TREE_ENTRIES);
+        self.send_text(
+            incoming,
+            &format!(
+                "{}\n```\n{tree}\n```",
+                build_files_header(user_lang, &brief.name)
+            ),
+        )
+        .await;
+
+        let workflow = project_dir.join("docs/.workflow");
+        if let Err(e) = tokio::fs::create_dir_all(&workflow).await {
+            warn!("failed to create {}: {e}", workflow.display());
+            return;
+        }
+        let archive = workflow.join(format!("{}.zip", brief.name));
+        let prefix = format!("--prefix={}/", brief.name);
+        let output = format!("--output={}", archive.display());
+        if self
+            .run_build_git(
+                project_dir,
+                &["archive", "--format=zip", &prefix, &output, "HEAD"],
+            )
+            .await
+        {
+            self.send_artifact(
+                incoming,
+                user_lang,
+                &archive,
+                &build_archive_caption(user_lang, &brief.name),
+            )
+            .await;
+        }
+
+        let bundle = workflow.join(format!("{}.bundle", brief.name));
+        let bundle_str = bundle.display().to_string();
+        if self
+            .run_build_git(project_dir, &["bundle", "create", &bundle_str, "--all"])
+            .await
+        {
+            self.send_artifact(
+                incoming,
+                user_lang,
+                &bundle,
+                &build_history_caption(user_lang, &brief.name),
+            )
+            .await;
+        }
+
+        for phase in &loaded.topology.phases {
+            let Some(v) = &phase.pre_validation else {
+                continue;
+            };
+            if v.validation_type != ValidationType::Command {
+                continue;
+            }
+            let declared = v.report.as_ref().map(|r| project_dir.join(r));
+            let report = declared
+                .filter(|p| p.is_file())
+                .unwrap_or_else(|| validation_log_path(project_dir, &phase.name));
+            if report.is_file() && is_inside(&report, project_dir) {
+                self.send_artifact(
+                    incoming,
+                    user_lang,
+                    &report,
+                    &build_report_caption(user_lang, &phase.name),
+                )
+                .await;
+            }
+        }
+    }
+
+    /// Send one file as a document, or its path when it is too large.
+    ///
+    /// Returns `true` once the channel accepted the document.
+    pub(super) async fn send_artifact(
+        &self,
+        incoming: &IncomingMessage,
+        user_lang: &str,
+        path: &Path,
+        caption: &str,
+    ) -> bool {
+        let size = tokio::fs::metadata(path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        if size > MAX_DOCUMENT_BYTES {
+            self.send_text(
+                incoming,
+                &build_artifact_too_large_message(user_lang, &path.display().to_string()),
+            )
+            .await;
+            return false;
+        }
+        let bytes = match tokio::fs::read(path).await {
+            Ok(b) => b,
+            Err(e) => {
+                warn!("failed to read artifact {}: {e}", path.display());
+                return false;
+            }
+        };
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("artifact");
+        let Some(channel) = self.channels.get(&incoming.channel) else {
+            return false;
+        };
+        let target = incoming.reply_target.as_deref().unwrap_or("");
+        match channel
+            .send_document(target, file_name, &bytes, caption)
+            .await
+        {
+            Ok(()) => {
+                info!("sent artifact: {file_name}");
+                true
+            }
+            Err(e) => {
+                warn!("failed to send artifact {file_name}: {e}");
+                false
+            }
+        }
+    }
+}
+
+/// Whether `path` resolves (symlinks included) to somewhere inside `dir`.
+fn is_inside(path: &Path, dir: &Path) -> bool {
+    match (std::fs::canonicalize(path), std::fs::canonicalize(dir)) {
+        (Ok(path), Ok(dir)) => path.starts_with(dir),
+        _ => false,
+    }
+}
+
+/// Render an indented file tree of `dir`, skipping hidden entries and build
+/// output. Directories sort before files; the listing stops at `max_entries`.
+fn render_file_tree(dir: &Path, max_entries: usize) -> String {
+    let mut lines = Vec::new();
+    let mut omitted = 0usize;
+    walk_tree(dir, 0, max_entries, &mut lines, &mut omitted);
+    if lines.is_empty() {
+        lines.push("(empty)".to_string());
+    }
+    if omitted > 0 {
+        lines.push(format!("... ({omitted} more)"));
+    }
+    lines.join("\n")
+}
+
+fn walk_tree(dir: &Path, depth: usize, max: usize, lines: &mut Vec<String>, omitted: &mut usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<(String, PathBuf, bool)> = entries
+        .flatten()
+        .map(|e| {
+            let path = e.path();
+            let is_dir = path.is_dir() && !path.is_symlink();
+            (e.file_name().to_string_lossy().to_string(), path, is_dir)
+        })
+        .filter(|(name, _, is_dir)| {
+            let skipped_dir =
+                *is_dir && matches!(name.as_str(), "target" | "node_modules" | "__pycache__");
+            !(name.starts_with('.') || skipped_dir)
+        })
+        .collect();
+    entries.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+
+    for (name, path, is_dir) in entries {
+        if lines.len() >= max {
+            *omitted += 1;
+            continue;
+        }
+        let indent = "  ".repeat(depth);
+        if is_dir {
+            lines.push(format!("{indent}{name}/"));
+            if depth + 1 < MAX_TREE_DEPTH {
+                walk_tree(&path, depth + 1, max, lines, omitted);
+            }
+        } else {
+            lines.push(format!("{indent}{name}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_file_tree_orders_and_skips() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+        std::fs::write(root.join("README.md"), "").unwrap();
+
+        let tree = render_file_tree(root, 80);
+        assert_eq!(tree, "src/\n  main.rs\nCargo.toml\nREADME.md");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_inside_resolves_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("project");
+        std::fs::create_dir_all(project.join("target")).unwrap();
+        std::fs::write(project.join("target/report.txt"), "ok").unwrap();
+        std::fs::write(tmp.path().join("secret.txt"), "no").unwrap();
+        std::os::unix::fs::symlink(tmp.path().join("secret.txt"), project.join("link.txt"))
+            .unwrap();
+
+        assert!(is_inside(&project.join("target/report.txt"), &project));
+        assert!(!is_inside(&project.join("link.txt"), &project));
+        assert!(!is_inside(&project.join("../secret.txt"), &project));
+        assert!(!is_inside(&project.join("missing.txt"), &project));
+    }
+
+    #[test]
+    fn test_render_file_tree_truncates() {
+        let tmp = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            std::fs::write(tmp.path().join(format!("f{i}.txt")), "").unwrap();
+        }
+        let tree = render_file_tree(tmp.path(), 3);
+        assert!(tree.ends_with("... (2 more)"), "{tree}");
+        assert_eq!(tree.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_render_file_tree_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(render_file_tree(tmp.path(), 80), "(empty)");
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Extracted from `builds_parse.rs` to keep that module under the 500-line limit.
 //! Contains phase progress messages (8 languages x 7 phases), QA pass/retry/exhausted
-//! messages, and review pass/retry/exhausted messages.
+//! messages, review pass/retry/exhausted messages, and `/build resume`/`/build status`
+//! messages.
 
 // ---------------------------------------------------------------------------
 // Phase progress messages
@@ -288,10 +289,216 @@ pub(super) fn review_exhausted_message(
     }
 }
 
+// ---------------------------------------------------------------------------
+// /build resume and /build status messages
+// ---------------------------------------------------------------------------
+
+/// Localized notice that a failed build is being resumed at `phase`.
+pub(super) fn build_resume_message(lang: &str, project: &str, phase: &str) -> String {
+    match lang {
+        "Spanish" => format!("Reanudando `{project}` desde la fase {phase}."),
+        "Portuguese" => format!("Retomando `{project}` a partir da fase {phase}."),
+        "French" => format!("Reprise de `{project}` à partir de la phase {phase}."),
+        "German" => format!("Setze `{project}` ab Phase {phase} fort."),
+        "Italian" => format!("Riprendo `{project}` dalla fase {phase}."),
+        "Dutch" => format!("`{project}` wordt hervat vanaf fase {phase}."),
+        "Russian" => format!("Возобновляю `{project}` с фазы {phase}."),
+        _ => format!("Resuming `{project}` from phase {phase}."),
+    }
+}
+
+/// Localized notice that the selected topology could not be loaded.
+pub(super) fn topology_load_failed_message(lang: &str, name: &str, error: &str) -> String {
+    match lang {
+        "Spanish" => format!("No se pudo cargar la topología `{name}`: {error}\nUsa /topologies para ver las topologías instaladas."),
+        "Portuguese" => format!("Não foi possível carregar a topologia `{name}`: {error}\nUse /topologies para ver as topologias instaladas."),
+        "French" => format!("Impossible de charger la topologie `{name}` : {error}\nUtilisez /topologies pour voir les topologies installées."),
+        "German" => format!("Topologie `{name}` konnte nicht geladen werden: {error}\nMit /topologies siehst du die installierten Topologien."),
+        "Italian" => format!("Impossibile caricare la topologia `{name}`: {error}\nUsa /topologies per vedere le topologie installate."),
+        "Dutch" => format!("Topologie `{name}` kon niet worden geladen: {error}\nGebruik /topologies om de geïnstalleerde topologieën te zien."),
+        "Russian" => format!("Не удалось загрузить топологию `{name}`: {error}\nИспользуйте /topologies, чтобы увидеть установленные топологии."),
+        _ => format!("Failed to load topology `{name}`: {error}\nUse /topologies to see installed topologies."),
+    }
+}
+
+/// Localized notice that there is no failed build to resume.
+pub(super) fn build_nothing_to_resume_message(lang: &str) -> &'static str {
+    match lang {
+        "Spanish" => "No hay ninguna construcción interrumpida para reanudar.",
+        "Portuguese" => "Não há nenhuma construção interrompida para retomar.",
+        "French" => "Aucune construction interrompue à reprendre.",
+        "German" => "Kein abgebrochener Build zum Fortsetzen vorhanden.",
+        "Italian" => "Nessuna build interrotta da riprendere.",
+        "Dutch" => "Er is geen onderbroken build om te hervatten.",
+        "Russian" => "Нет прерванной сборки для возобновления.",
+        _ => "There is no interrupted build to resume.",
+    }
+}
+
+/// Localized notice that the requested project is already being built.
+pub(super) fn build_already_running_message(lang: &str, project: &str) -> String {
+    match lang {
+        "Spanish" => format!("`{project}` ya se está construyendo."),
+        "Portuguese" => format!("`{project}` já está sendo construído."),
+        "French" => format!("`{project}` est déjà en cours de construction."),
+        "German" => format!("`{project}` wird bereits gebaut."),
+        "Italian" => format!("`{project}` è già in costruzione."),
+        "Dutch" => format!("`{project}` wordt al gebouwd."),
+        "Russian" => format!("`{project}` уже собирается."),
+        _ => format!("`{project}` is already being built."),
+    }
+}
+
+/// Localized live progress line for `/build status`.
+pub(super) fn build_status_message(
+    lang: &str,
+    project: &str,
+    phase: &str,
+    step: usize,
+    total: usize,
+    elapsed: &str,
+    phase_elapsed: &str,
+) -> String {
+    match lang {
+        "Spanish" => format!("Construyendo `{project}` — fase {step}/{total}: {phase} ({phase_elapsed} en esta fase, {elapsed} en total)"),
+        "Portuguese" => format!("Construindo `{project}` — fase {step}/{total}: {phase} ({phase_elapsed} nesta fase, {elapsed} no total)"),
+        "French" => format!("Construction de `{project}` — phase {step}/{total} : {phase} ({phase_elapsed} sur cette phase, {elapsed} au total)"),
+        "German" => format!("Baue `{project}` — Phase {step}/{total}: {phase} ({phase_elapsed} in dieser Phase, {elapsed} insgesamt)"),
+        "Italian" => format!("Costruendo `{project}` — fase {step}/{total}: {phase} ({phase_elapsed} in questa fase, {elapsed} in totale)"),
+        "Dutch" => format!("Bouwt `{project}` — fase {step}/{total}: {phase} ({phase_elapsed} in deze fase, {elapsed} totaal)"),
+        "Russian" => format!("Сборка `{project}` — фаза {step}/{total}: {phase} ({phase_elapsed} в этой фазе, {elapsed} всего)"),
+        _ => format!("Building `{project}` — phase {step}/{total}: {phase} ({phase_elapsed} in this phase, {elapsed} total)"),
+    }
+}
+
+/// Localized notice that no build is running for `/build status`.
+pub(super) fn build_status_idle_message(lang: &str) -> &'static str {
+    match lang {
+        "Spanish" => "No hay ninguna construcción en curso. Usa /build resume para continuar una interrumpida.",
+        "Portuguese" => "Nenhuma construção em andamento. Use /build resume para continuar uma interrompida.",
+        "French" => "Aucune construction en cours. Utilisez /build resume pour reprendre une construction interrompue.",
+        "German" => "Kein Build läuft. Mit /build resume setzt du einen abgebrochenen fort.",
+        "Italian" => "Nessuna build in corso. Usa /build resume per riprenderne una interrotta.",
+        "Dutch" => "Er loopt geen build. Gebruik /build resume om een onderbroken build te hervatten.",
+        "Russian" => "Сборка не выполняется. Используйте /build resume, чтобы продолжить прерванную.",
+        _ => "No build is running. Use /build resume to continue an interrupted one.",
+    }
+}
+
+/// Localized notice that a phase's `when` condition excluded it from this build.
+pub(super) fn phase_skipped_message(lang: &str, phase: &str) -> String {
+    match lang {
+        "Spanish" => format!("Fase `{phase}` omitida: no aplica a este proyecto."),
+        "Portuguese" => format!("Fase `{phase}` ignorada: não se aplica a este projeto."),
+        "French" => format!("Phase `{phase}` ignorée : elle ne s'applique pas à ce projet."),
+        "German" => format!("Phase `{phase}` übersprungen: gilt nicht für dieses Projekt."),
+        "Italian" => format!("Fase `{phase}` saltata: non si applica a questo progetto."),
+        "Dutch" => format!("Fase `{phase}` overgeslagen: niet van toepassing op dit project."),
+        "Russian" => format!("Фаза `{phase}` пропущена: не относится к этому проекту."),
+        _ => format!("Skipped phase `{phase}`: it does not apply to this project."),
+    }
+}
+
+/// Localized header for the generated file tree sent after a build.
+pub(super) fn build_files_header(lang: &str, project: &str) -> String {
+    match lang {
+        "Spanish" => format!("Archivos de `{project}`:"),
+        "Portuguese" => format!("Arquivos de `{project}`:"),
+        "French" => format!("Fichiers de `{project}` :"),
+        "German" => format!("Dateien in `{project}`:"),
+        "Italian" => format!("File di `{project}`:"),
+        "Dutch" => format!("Bestanden in `{project}`:"),
+        "Russian" => format!("Файлы `{project}`:"),
+        _ => format!("Files in `{project}`:"),
+    }
+}
+
+/// Localized caption for the zipped project archive.
+pub(super) fn build_archive_caption(lang: &str, project: &str) -> String {
+    match lang {
+        "Spanish" => format!("{project}: proyecto completo."),
+        "Portuguese" => format!("{project}: projeto completo."),
+        "French" => format!("{project} : projet complet."),
+        "German" => format!("{project}: komplettes Projekt."),
+        "Italian" => format!("{project}: progetto completo."),
+        "Dutch" => format!("{project}: volledig project."),
+        "Russian" => format!("{project}: готовый проект."),
+        _ => format!("{project}: full project."),
+    }
+}
+
+/// Localized caption for the git bundle holding one commit per phase.
+pub(super) fn build_history_caption(lang: &str, project: &str) -> String {
+    match lang {
+        "Spanish" => {
+            format!("Historial por fase: `git clone {project}.bundle` y luego `git log -p`.")
+        }
+        "Portuguese" => {
+            format!("Histórico por fase: `git clone {project}.bundle` e depois `git log -p`.")
+        }
+        "French" => {
+            format!("Historique par phase : `git clone {project}.bundle` puis `git log -p`.")
+        }
+        "German" => format!("Verlauf pro Phase: `git clone {project}.bundle`, dann `git log -p`."),
+        "Italian" => {
+            format!("Cronologia per fase: `git clone {project}.bundle` e poi `git log -p`.")
+        }
+        "Dutch" => {
+            format!("Geschiedenis per fase: `git clone {project}.bundle` en dan `git log -p`.")
+        }
+        "Russian" => format!("История по фазам: `git clone {project}.bundle`, затем `git log -p`."),
+        _ => format!("Per-phase history: `git clone {project}.bundle`, then `git log -p`."),
+    }
+}
+
+/// Localized caption for a test/coverage report produced by a validation.
+pub(super) fn build_report_caption(lang: &str, phase: &str) -> String {
+    match lang {
+        "Spanish" => format!("Informe de pruebas ({phase})"),
+        "Portuguese" => format!("Relatório de testes ({phase})"),
+        "French" => format!("Rapport de tests ({phase})"),
+        "German" => format!("Testbericht ({phase})"),
+        "Italian" => format!("Report dei test ({phase})"),
+        "Dutch" => format!("Testrapport ({phase})"),
+        "Russian" => format!("Отчёт о тестах ({phase})"),
+        _ => format!("Test report ({phase})"),
+    }
+}
+
+/// Localized notice that an artifact is too large to send through the channel.
+pub(super) fn build_artifact_too_large_message(lang: &str, path: &str) -> String {
+    match lang {
+        "Spanish" => format!("El archivo es demasiado grande para enviarlo. Está en `{path}`."),
+        "Portuguese" => format!("O arquivo é grande demais para enviar. Está em `{path}`."),
+        "French" => {
+            format!("Le fichier est trop volumineux pour être envoyé. Il se trouve dans `{path}`.")
+        }
+        "German" => format!("Die Datei ist zu groß zum Senden. Sie liegt unter `{path}`."),
+        "Italian" => format!("Il file è troppo grande da inviare. Si trova in `{path}`."),
+        "Dutch" => format!("Het bestand is te groot om te versturen. Het staat in `{path}`."),
+        "Russian" => format!("Файл слишком большой для отправки. Он находится в `{path}`."),
+        _ => format!("The file is too large to send. It is at `{path}`."),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
 
+/// Localized notice that `/stop` interrupted a build.
+pub(super) fn build_stopped_message(lang: &str, project: &str, phase: &str) -> String {
+    match lang {
+        "Spanish" => format!("Construcción `{project}` detenida en la fase {phase}. Usa /build resume para continuar."),
+        "Portuguese" => format!("Construção `{project}` parada na fase {phase}. Use /build resume para continuar."),
+        "French" => format!("Construction de `{project}` arrêtée pendant la phase {phase}. Utilisez /build resume pour reprendre."),
+        "German" => format!("Build `{project}` in Phase {phase} gestoppt. Mit /build resume geht es weiter."),
+        "Italian" => format!("Build `{project}` interrotta durante la fase {phase}. Usa /build resume per riprendere."),
+        "Dutch" => format!("Build `{project}` gestopt tijdens fase {phase}. Gebruik /build resume om verder te gaan."),
+        "Russian" => format!("Сборка `{project}` остановлена на фазе {phase}. Используйте /build resume, чтобы продолжить."),
+        _ => format!("Build `{project}` stopped during phase {phase}. Use /build resume to continue."),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -802,4 +1009,36 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_build_resume_and_status_messages_all_languages() {
+        for lang in [
+            "English",
+            "Spanish",
+            "Portuguese",
+            "French",
+            "German",
+            "Italian",
+            "Dutch",
+            "Russian",
+        ] {
+            assert!(build_resume_message(lang, "todo-api", "developer").contains("`todo-api`"));
+            let failed = topology_load_failed_message(lang, "research", "missing TOPOLOGY.toml");
+            assert!(failed.contains("`research`") && failed.contains("/topologies"));
+            assert!(build_already_running_message(lang, "todo-api").contains("`todo-api`"));
+            let status = build_status_message(lang, "todo-api", "qa", 5, 7, "12m 03s", "45s");
+            assert!(status.contains("5/7"), "{lang}: {status}");
+            assert!(status.contains("12m 03s"), "{lang}: {status}");
+            assert!(!build_nothing_to_resume_message(lang).is_empty());
+            assert!(build_status_idle_message(lang).contains("/build resume"));
+            assert!(phase_skipped_message(lang, "frontend").contains("`frontend`"));
+            let stopped = build_stopped_message(lang, "todo-api", "qa");
+            assert!(stopped.contains("`todo-api`") && stopped.contains("/build resume"));
+            assert!(build_files_header(lang, "todo-api").contains("`todo-api`"));
+            assert!(build_archive_caption(lang, "todo-api").contains("todo-api"));
+            assert!(build_history_caption(lang, "todo-api").contains("todo-api.bundle"));
+            assert!(build_report_caption(lang, "qa").contains("(qa)"));
+            assert!(build_artifact_too_large_message(lang, "/x.zip").contains("`/x.zip`"));
+        }
+    }
 }
@@ -5,7 +5,7 @@
 //! compatibility, and chain state persistence for failure recovery.
 
 use super::builds_parse::*;
-use super::builds_topology::{Phase, RetryConfig, ValidationConfig, ValidationType};
+use super::builds_topology::{LoopStyle, Phase, RetryConfig, ValidationConfig, ValidationType};
 use super::Gateway;
 use omega_core::message::IncomingMessage;
 use std::path::Path;
@@ -16,7 +16,7 @@ impl Gateway {
     ///
     /// Parameters come from the topology's RetryConfig for the phase.
     /// The verify agent is the phase's own agent. The fix agent comes from retry config.
-    /// Dispatches to QA or reviewer parsing based on the phase name.
+    /// Dispatches to QA or reviewer parsing based on the phase's loop style.
     pub(super) async fn run_corrective_loop(
         &self,
         incoming: &IncomingMessage,
@@ -27,9 +27,7 @@ impl Gateway {
         model: &str,
     ) -> Result<(), String> {
         let project_dir_str = state.project_dir_str.as_deref().unwrap_or("");
-        // NOTE: Dispatches QA vs review by phase name. A future improvement could add
-        // an explicit loop_style enum to RetryConfig (e.g. LoopStyle::Qa / LoopStyle::Review).
-        let is_qa = phase.name == "qa";
+        let is_qa = phase.loop_style() == LoopStyle::Qa;
 
         let verify_prompt = if is_qa {
             format!(
@@ -45,7 +43,12 @@ impl Gateway {
 
         for attempt in 1..=retry.max {
             let verification = match self
-                .run_build_phase(&phase.agent, &verify_prompt, model, phase.max_turns)
+                .run_build_phase_limited(
+                    &phase.agent,
+                    &verify_prompt,
+                    model,
+                    &phase.limits().with_progress(self.progress_sink(incoming)),
+                )
                 .await
             {
                 Ok(text) => {
@@ -107,7 +110,14 @@ impl Gateway {
                         };
 
                         if let Err(e) = self
-                            .run_build_phase(&retry.fix_agent, &fix_prompt, model, None)
+                            .run_build_phase_limited(
+                                &retry.fix_agent,
+                                &fix_prompt,
+                                model,
+                                &phase
+                                    .fix_limits()
+                                    .with_progress(self.progress_sink(incoming)),
+                            )
                             .await
                         {
                             let label = if is_qa {
@@ -338,6 +348,9 @@ impl Gateway {
     /// Run pre-phase validation from topology config.
     /// Returns Some(error_message) on failure, None on success.
     ///
+    /// File checks only; `command` validations are async and go through
+    /// `run_pre_validation()`.
+    ///
     /// REQ-TOP-007: Parameterized pre/post-phase validation from topology config.
     pub(super) fn run_validation(project_dir: &Path, config: &ValidationConfig) -> Option<String> {
         match config.validation_type {
@@ -369,6 +382,9 @@ impl Gateway {
                 }
                 None
             }
+            ValidationType::Command => {
+                Some("Pre-validation failed: command validations must run async".to_string())
+            }
         }
     }
 
@@ -412,6 +428,17 @@ impl Gateway {
         if let Err(e) = tokio::fs::write(&path, content).await {
             warn!("Failed to write chain-state: {e}");
         }
+
+        // Machine-readable twin for `/build resume`.
+        match serde_json::to_string_pretty(state) {
+            Ok(json) => {
+                let path = workflow_dir.join("chain-state.json");
+                if let Err(e) = tokio::fs::write(&path, json).await {
+                    warn!("Failed to write chain-state.json: {e}");
+                }
+            }
+            Err(e) => warn!("Failed to serialize chain-state: {e}"),
+        }
     }
 }
 
@@ -445,6 +472,8 @@ mod tests {
             failed_phase: Some("qa".to_string()),
             failure_reason: Some("3 tests failing".to_string()),
             topology_name: None,
+            brief_text: None,
+            owner_id: None,
         };
         Gateway::save_chain_state(tmp.path(), &state).await;
 
@@ -514,6 +543,8 @@ mod tests {
             validation_type: ValidationType::FileExists,
             paths: vec!["specs/architecture.md".to_string()],
             patterns: vec![],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -532,6 +563,8 @@ mod tests {
             validation_type: ValidationType::FileExists,
             paths: vec!["specs/architecture.md".to_string()],
             patterns: vec![],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -560,6 +593,8 @@ mod tests {
                 "specs/requirements.md".to_string(),
             ],
             patterns: vec![],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -579,6 +614,8 @@ mod tests {
             validation_type: ValidationType::FilePatterns,
             paths: vec![],
             patterns: vec!["test".to_string(), "spec".to_string(), "_test.".to_string()],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -598,6 +635,8 @@ mod tests {
             validation_type: ValidationType::FilePatterns,
             paths: vec![],
             patterns: vec!["test".to_string(), "spec".to_string()],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -623,6 +662,8 @@ mod tests {
             validation_type: ValidationType::FilePatterns,
             paths: vec![],
             patterns: vec![".rs".to_string()],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -640,6 +681,8 @@ mod tests {
             validation_type: ValidationType::FileExists,
             paths: vec!["specs/architecture.md".to_string()],
             patterns: vec![],
+            command: None,
+            report: None,
         };
 
         let old_result = Gateway::validate_phase_output(tmp.path(), "test-writer");
@@ -659,6 +702,8 @@ mod tests {
             validation_type: ValidationType::FilePatterns,
             paths: vec![],
             patterns: vec!["test".to_string(), "spec".to_string(), "_test.".to_string()],
+            command: None,
+            report: None,
         };
 
         let old_result = Gateway::validate_phase_output(tmp.path(), "developer");
@@ -678,6 +723,8 @@ mod tests {
             validation_type: ValidationType::FileExists,
             paths: vec![],
             patterns: vec![],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -696,6 +743,8 @@ mod tests {
             validation_type: ValidationType::FilePatterns,
             paths: vec![],
             patterns: vec![],
+            command: None,
+            report: None,
         };
 
         let result = Gateway::run_validation(tmp.path(), &config);
@@ -719,6 +768,8 @@ mod tests {
             failed_phase: Some("architect".to_string()),
             failure_reason: Some("specs missing".to_string()),
             topology_name: Some("development".to_string()),
+            brief_text: None,
+            owner_id: None,
         };
 
         assert_eq!(state.topology_name.as_deref(), Some("development"));
@@ -745,6 +796,8 @@ mod tests {
             failed_phase: None,
             failure_reason: None,
             topology_name: None,
+            brief_text: None,
+            owner_id: None,
         };
 
         assert!(state.topology_name.is_none());
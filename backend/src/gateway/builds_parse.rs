@@ -1,5 +1,7 @@
 //! Pure parsing functions, data structures, and prompt templates for the build pipeline.
 
+use omega_core::structured::ResponseSchema;
+
 // ---------------------------------------------------------------------------
 // Data structures
 // ---------------------------------------------------------------------------
@@ -10,15 +12,55 @@
 /// via the raw `brief_text` string. They are available for future orchestrator logic
 /// (e.g., conditional frontend phase, language-specific verification commands).
 #[allow(dead_code)]
+#[derive(serde::Deserialize)]
 pub(super) struct ProjectBrief {
     pub(super) name: String,
     pub(super) language: String,
     pub(super) database: String,
     pub(super) frontend: bool,
     pub(super) scope: String,
+    #[serde(default)]
     pub(super) components: Vec<String>,
 }
 
+impl ProjectBrief {
+    /// JSON Schema for extracting a brief with structured output, used when
+    /// the analyst's text does not parse.
+    pub(super) fn schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "project_brief",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "language": {"type": "string"},
+                    "database": {"type": "string"},
+                    "frontend": {"type": "boolean"},
+                    "scope": {"type": "string"},
+                    "components": {"type": "array", "items": {"type": "string"}}
+                },
+                "required": ["name", "language", "database", "frontend", "scope", "components"]
+            }),
+        )
+    }
+
+    /// Render in the Phase 1 text format, so `parse_project_brief` reads it back.
+    pub(super) fn to_text(&self) -> String {
+        let mut text = format!(
+            "PROJECT_NAME: {}\nLANGUAGE: {}\nDATABASE: {}\nFRONTEND: {}\nSCOPE: {}\nCOMPONENTS:",
+            self.name,
+            self.language,
+            self.database,
+            if self.frontend { "yes" } else { "no" },
+            self.scope
+        );
+        for component in &self.components {
+            text.push_str(&format!("\n- {component}"));
+        }
+        text
+    }
+}
+
 /// Result of Phase 4 (Verification).
 pub(super) enum VerificationResult {
     Pass,
@@ -42,7 +84,8 @@ pub(super) enum ReviewResult {
 }
 
 /// Snapshot of build pipeline progress — written to `docs/.workflow/chain-state.md`
-/// on failure so the user can resume or inspect partial results.
+/// (human-readable) and `chain-state.json` (for `/build resume`) on failure.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub(super) struct ChainState {
     pub(super) project_name: String,
     pub(super) project_dir: String,
@@ -51,6 +94,12 @@ pub(super) struct ChainState {
     pub(super) failure_reason: Option<String>,
     /// Which topology was used for this build (REQ-TOP-015).
     pub(super) topology_name: Option<String>,
+    /// Raw analyst output, re-parsed into the brief on resume.
+    #[serde(default)]
+    pub(super) brief_text: Option<String>,
+    /// Canonical sender id of whoever started the build; only they may resume it.
+    #[serde(default)]
+    pub(super) owner_id: Option<String>,
 }
 
 // Phase prompt templates have been replaced by embedded agent definitions
@@ -66,6 +115,18 @@ fn strip_markdown(line: &str) -> String {
     line.trim().replace("**", "")
 }
 
+/// Strict validation: alphanumeric start, hyphens/underscores allowed, max 64 chars.
+/// Rejects spaces, shell metacharacters, path traversal, and unicode control chars.
+pub(super) fn is_valid_project_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.')
+        && !name.contains("..")
+}
+
 /// Parse structured output from Phase 1 into a `ProjectBrief`.
 ///
 /// Resilient to LLM output that wraps fields in markdown bold (`**PROJECT_NAME:**`)
@@ -81,16 +142,7 @@ pub(super) fn parse_project_brief(text: &str) -> Option<ProjectBrief> {
     let name = get_field("PROJECT_NAME")?;
     // Strip backticks that LLMs sometimes wrap values in.
     let name = name.trim_matches('`').trim().to_string();
-    // Strict validation: alphanumeric start, hyphens/underscores allowed, max 64 chars.
-    // Rejects spaces, shell metacharacters, path traversal, and unicode control chars.
-    if name.is_empty()
-        || name.len() > 64
-        || !name
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
-        || name.starts_with('.')
-        || name.contains("..")
-    {
+    if !is_valid_project_name(&name) {
         return None;
     }
 
@@ -218,6 +270,31 @@ mod tests {
         assert!(!brief.frontend); // default
     }
 
+    #[test]
+    fn test_project_brief_json_round_trips_through_text() {
+        let brief: ProjectBrief = omega_core::structured::parse(
+            r#"{"name": "price-tracker", "language": "Go", "database": "Postgres",
+                "frontend": true, "scope": "Tracks prices.", "components": ["fetcher", "alerts"]}"#,
+        )
+        .unwrap();
+        let parsed = parse_project_brief(&brief.to_text()).unwrap();
+        assert_eq!(parsed.name, "price-tracker");
+        assert_eq!(parsed.language, "Go");
+        assert_eq!(parsed.database, "Postgres");
+        assert!(parsed.frontend);
+        assert_eq!(parsed.scope, "Tracks prices.");
+        assert_eq!(parsed.components, vec!["fetcher", "alerts"]);
+    }
+
+    #[test]
+    fn test_is_valid_project_name() {
+        assert!(is_valid_project_name("price-tracker_v2.1"));
+        assert!(!is_valid_project_name(""));
+        assert!(!is_valid_project_name("../etc"));
+        assert!(!is_valid_project_name("my project"));
+        assert!(!is_valid_project_name(&"a".repeat(65)));
+    }
+
     #[test]
     fn test_parse_project_brief_missing_name() {
         let text = "LANGUAGE: Python\nSCOPE: A web scraper";
@@ -600,6 +677,8 @@ mod tests {
             failed_phase: Some("qa".to_string()),
             failure_reason: Some("tests failing".to_string()),
             topology_name: None,
+            brief_text: None,
+            owner_id: None,
         };
         assert_eq!(state.project_name, "test-project");
         assert_eq!(state.completed_phases.len(), 2);
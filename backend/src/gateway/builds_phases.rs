@@ -0,0 +1,208 @@
+//! Build phase executors — one per `PhaseType` that runs a single agent.
+//!
+//! Corrective loops live in `builds_loop.rs`; the phase loop that dispatches
+//! here lives in `builds.rs`.
+
+use super::builds::OrchestratorState;
+use super::builds_parse::*;
+use super::builds_topology;
+use super::Gateway;
+use omega_core::{
+    config::DataDir, context::Context, message::IncomingMessage, structured::complete_structured,
+};
+use tracing::warn;
+
+impl Gateway {
+    /// Execute a ParseBrief phase: run analyst, parse brief, create project dir.
+    pub(super) async fn execute_parse_brief(
+        &self,
+        incoming: &IncomingMessage,
+        phase: &builds_topology::Phase,
+        model: &str,
+        state: &mut OrchestratorState,
+    ) -> Result<(), String> {
+        let brief_text = self
+            .run_build_phase_limited(
+                &phase.agent,
+                &incoming.text,
+                model,
+                &phase.limits().with_progress(self.progress_sink(incoming)),
+            )
+            .await
+            .map_err(|e| format!("Could not analyze your build request: {e}"))?;
+
+        // The analyst's text format drifts; recover with a structured extraction.
+        let (brief, brief_text) = match parse_project_brief(&brief_text) {
+            Some(brief) => (brief, brief_text),
+            None => {
+                let brief = self
+                    .extract_project_brief(&brief_text, model)
+                    .await
+                    .ok_or("Could not parse the build brief. Please try rephrasing.")?;
+                let text = brief.to_text();
+                (brief, text)
+            }
+        };
+
+        let project_dir = DataDir::new(&self.data_dir)
+            .workspace()
+            .join("builds")
+            .join(&brief.name);
+        let project_dir_str = project_dir.display().to_string();
+
+        tokio::fs::create_dir_all(&project_dir)
+            .await
+            .map_err(|e| format!("Failed to create project directory: {e}"))?;
+
+        self.send_text(
+            incoming,
+            &format!(
+                "Building `{}` \u{2014} {}. I'll keep you posted.",
+                brief.name, brief.scope
+            ),
+        )
+        .await;
+
+        state.brief_text = Some(brief_text);
+        state.project_dir = Some(project_dir);
+        state.project_dir_str = Some(project_dir_str);
+        state.brief = Some(brief);
+        Ok(())
+    }
+
+    /// Extract a brief from analyst output that `parse_project_brief` rejected.
+    async fn extract_project_brief(
+        &self,
+        analyst_output: &str,
+        model: &str,
+    ) -> Option<ProjectBrief> {
+        let mut ctx = Context::new(&format!(
+            "Extract the project brief from this analyst output. The name is a short \
+             directory name (letters, digits, hyphens, underscores).\n\n{analyst_output}"
+        ));
+        ctx.allowed_tools = Some(vec![]);
+        ctx.model = Some(model.to_string());
+        ctx.response_schema = Some(ProjectBrief::schema());
+        match complete_structured::<ProjectBrief>(self.provider.as_ref(), &ctx).await {
+            Ok(brief) if is_valid_project_name(&brief.name) => Some(brief),
+            Ok(brief) => {
+                warn!("build: extracted brief has invalid name {:?}", brief.name);
+                None
+            }
+            Err(e) => {
+                warn!("build: brief extraction failed: {e}");
+                None
+            }
+        }
+    }
+
+    /// Execute a Standard phase: run agent, check for error.
+    pub(super) async fn execute_standard(
+        &self,
+        incoming: &IncomingMessage,
+        phase: &builds_topology::Phase,
+        model: &str,
+        state: &OrchestratorState,
+    ) -> Result<(), String> {
+        let project_dir_str = state.project_dir_str.as_deref().unwrap_or("");
+        let brief_text = state.brief_text.as_deref().unwrap_or("");
+        let brief_name = state.brief.as_ref().map(|b| b.name.as_str()).unwrap_or("");
+
+        // Build phase-specific prompt.
+        let prompt = match phase.name.as_str() {
+            "architect" => {
+                format!(
+                    "Project brief:\n{brief_text}\nBegin architecture design in {project_dir_str}."
+                )
+            }
+            "test-writer" => {
+                format!("Read specs/ in {project_dir_str} and write failing tests. Begin.")
+            }
+            "developer" => {
+                format!("Read the tests and specs/ in {project_dir_str}. Implement until all tests pass. Begin.")
+            }
+            _ => format!("Execute phase '{}' in {project_dir_str}.", phase.name),
+        };
+
+        self.run_build_phase_limited(
+            &phase.agent,
+            &prompt,
+            model,
+            &phase.limits().with_progress(self.progress_sink(incoming)),
+        )
+        .await
+        .map_err(|e| {
+            format!(
+                "{} phase failed: {e}. Partial results in `{brief_name}`.",
+                phase.name
+            )
+        })?;
+
+        // Phase-specific completion messages.
+        if phase.name == "test-writer" {
+            self.send_text(incoming, "Tests written.").await;
+        } else if phase.name == "developer" {
+            self.send_text(incoming, "Implementation complete \u{2014} verifying...")
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// Execute a ParseSummary phase: run delivery, parse summary, send final message.
+    ///
+    /// Note: typing handle lifecycle is managed by the caller (handle_build_request).
+    /// This method does NOT abort the typing handle.
+    pub(super) async fn execute_parse_summary(
+        &self,
+        incoming: &IncomingMessage,
+        phase: &builds_topology::Phase,
+        model: &str,
+        state: &OrchestratorState,
+    ) -> Result<(), String> {
+        let project_dir_str = state.project_dir_str.as_deref().unwrap_or("");
+        let brief_name = state.brief.as_ref().map(|b| b.name.as_str()).unwrap_or("");
+        let skills_dir = DataDir::new(&self.data_dir).skills();
+        let skills_dir_str = skills_dir.display().to_string();
+
+        let delivery_prompt = format!(
+            "Create docs and skill file for {brief_name} in {project_dir_str}. Skills dir: {skills_dir_str}.",
+        );
+
+        let delivery_text = self
+            .run_build_phase_limited(
+                &phase.agent,
+                &delivery_prompt,
+                model,
+                &phase.limits().with_progress(self.progress_sink(incoming)),
+            )
+            .await?;
+
+        // Parse and send final summary.
+        let final_msg = if let Some(summary) = parse_build_summary(&delivery_text) {
+            format!(
+                "Build complete!\n\n\
+                 *{}*\n\
+                 {}\n\n\
+                 Location: `{}`\n\
+                 Language: {}\n\
+                 Usage: `{}`{}",
+                summary.project,
+                summary.summary,
+                summary.location,
+                summary.language,
+                summary.usage,
+                summary
+                    .skill
+                    .as_ref()
+                    .map(|s| format!("\nSkill: {s}"))
+                    .unwrap_or_default(),
+            )
+        } else {
+            format!("Build complete!\n\nProject `{brief_name}` is ready.",)
+        };
+
+        self.send_text(incoming, &final_msg).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,577 @@
+//! `/build` command — resume interrupted builds and report live progress.
+//!
+//! - `/build resume [project]` reloads `docs/.workflow/chain-state.json` (the
+//!   most recent one when no project is named), rebuilds the `OrchestratorState`
+//!   and re-enters the phase loop at the first incomplete phase, re-running its
+//!   pre-validation.
+//! - `/build status` reads the in-memory progress registry that the phase loop
+//!   updates. It is also answered from `dispatch_message`, because the sender is
+//!   busy (and would be buffered) for as long as their build runs.
+
+use super::builds::OrchestratorState;
+use super::builds_i18n::*;
+use super::builds_parse::{parse_project_brief, ChainState};
+use super::builds_topology::{self, PhaseType};
+use super::Gateway;
+use omega_core::{config::DataDir, message::IncomingMessage};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Running builds keyed by `channel:sender_id`.
+pub(super) type ActiveBuilds = Arc<Mutex<HashMap<String, BuildProgress>>>;
+
+/// Live progress of one running build.
+pub(crate) struct BuildProgress {
+    pub(super) project: Option<String>,
+    pub(super) topology: String,
+    pub(super) phase: String,
+    pub(super) phase_index: usize,
+    pub(super) total_phases: usize,
+    pub(super) started: Instant,
+    pub(super) phase_started: Instant,
+    /// Checkpoint of the orchestrator state after the last finished step,
+    /// used to persist chain state when the build is stopped.
+    pub(super) project_dir: Option<PathBuf>,
+    pub(super) completed_phases: Vec<String>,
+    pub(super) brief_text: Option<String>,
+    pub(super) owner_id: String,
+}
+
+impl BuildProgress {
+    /// Chain state recording the build as stopped at its current phase, so
+    /// `/build resume` can continue it. `None` before the project dir exists.
+    pub(super) fn stopped_chain_state(&self) -> Option<(PathBuf, ChainState)> {
+        let project_dir = self.project_dir.clone()?;
+        let chain = ChainState {
+            project_name: self.project.clone().unwrap_or_default(),
+            project_dir: project_dir.display().to_string(),
+            completed_phases: self.completed_phases.clone(),
+            failed_phase: Some(self.phase.clone()),
+            failure_reason: Some("stopped by user".to_string()),
+            topology_name: Some(self.topology.clone()),
+            brief_text: self.brief_text.clone(),
+            owner_id: Some(self.owner_id.clone()),
+        };
+        Some((project_dir, chain))
+    }
+}
+
+/// Registers a build in [`ActiveBuilds`] and removes it when dropped,
+/// so every early return of the phase loop clears the entry.
+pub(super) struct BuildProgressGuard {
+    builds: ActiveBuilds,
+    key: String,
+}
+
+impl BuildProgressGuard {
+    pub(super) fn register(
+        builds: &ActiveBuilds,
+        key: String,
+        topology: &str,
+        total_phases: usize,
+    ) -> Self {
+        let now = Instant::now();
+        builds.lock().unwrap().insert(
+            key.clone(),
+            BuildProgress {
+                project: None,
+                topology: topology.to_string(),
+                phase: String::new(),
+                phase_index: 0,
+                total_phases,
+                started: now,
+                phase_started: now,
+                project_dir: None,
+                completed_phases: Vec::new(),
+                brief_text: None,
+                owner_id: String::new(),
+            },
+        );
+        Self {
+            builds: builds.clone(),
+            key,
+        }
+    }
+
+    /// Checkpoint the orchestrator state (project, directory, finished phases).
+    pub(super) fn record(&self, state: &OrchestratorState) {
+        if let Some(p) = self.builds.lock().unwrap().get_mut(&self.key) {
+            if let Some(brief) = &state.brief {
+                p.project = Some(brief.name.clone());
+            }
+            p.project_dir = state.project_dir.clone();
+            p.completed_phases = state.completed_phases.clone();
+            p.brief_text = state.brief_text.clone();
+            p.owner_id = state.owner_id.clone();
+        }
+    }
+
+    pub(super) fn set_phase(&self, index: usize, phase: &str) {
+        if let Some(p) = self.builds.lock().unwrap().get_mut(&self.key) {
+            p.phase = phase.to_string();
+            p.phase_index = index;
+            p.phase_started = Instant::now();
+        }
+    }
+}
+
+impl Drop for BuildProgressGuard {
+    fn drop(&mut self) {
+        if let Ok(mut builds) = self.builds.lock() {
+            builds.remove(&self.key);
+        }
+    }
+}
+
+impl Gateway {
+    /// Handle `/build status` and `/build resume [project]`.
+    pub(super) async fn handle_build_command(&self, incoming: &IncomingMessage) {
+        // Builds are keyed and owned by the canonical id, as in `intercept_build_status`.
+        let sender_id = self
+            .memory
+            .resolve_sender_id(&incoming.sender_id)
+            .await
+            .unwrap_or_else(|_| incoming.sender_id.clone());
+        let lang = self
+            .memory
+            .get_fact(&sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+
+        let mut args = incoming.text.split_whitespace().skip(1);
+        match args.next().map(|a| a.to_lowercase()).as_deref() {
+            Some("resume") => {
+                self.resume_build(incoming, &sender_id, args.next(), &lang)
+                    .await
+            }
+            Some("status") | None => {
+                let key = format!("{}:{}", incoming.channel, sender_id);
+                let text = self
+                    .build_status_text(&key, &lang)
+                    .unwrap_or_else(|| build_status_idle_message(&lang).to_string());
+                self.send_text(incoming, &text).await;
+            }
+            Some(_) => {
+                self.send_text(incoming, "Usage: /build status | /build resume [project]")
+                    .await;
+            }
+        }
+    }
+
+    /// Answer `/build status` for a sender whose build is still running.
+    ///
+    /// Called before the busy-sender buffer. Returns `true` when answered;
+    /// `false` (no running build, or not a status request) lets the message
+    /// take the normal path.
+    pub(super) async fn intercept_build_status(&self, incoming: &IncomingMessage) -> bool {
+        if !is_build_status_request(&incoming.text) {
+            return false;
+        }
+        let sender_id = self
+            .memory
+            .resolve_sender_id(&incoming.sender_id)
+            .await
+            .unwrap_or_else(|_| incoming.sender_id.clone());
+        let key = format!("{}:{}", incoming.channel, sender_id);
+        if !self.active_builds.lock().unwrap().contains_key(&key) {
+            return false;
+        }
+        let lang = self
+            .memory
+            .get_fact(&sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        match self.build_status_text(&key, &lang) {
+            Some(text) => {
+                self.send_text(incoming, &text).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Render the live progress line for the build registered under `key`.
+    fn build_status_text(&self, key: &str, lang: &str) -> Option<String> {
+        let builds = self.active_builds.lock().unwrap();
+        let p = builds.get(key)?;
+        let project = p.project.as_deref().unwrap_or(&p.topology);
+        Some(build_status_message(
+            lang,
+            project,
+            &p.phase,
+            p.phase_index + 1,
+            p.total_phases,
+            &format_elapsed(p.started.elapsed()),
+            &format_elapsed(p.phase_started.elapsed()),
+        ))
+    }
+
+    /// Reload persisted chain state and continue the build from its first incomplete phase.
+    ///
+    /// Only builds started by `sender_id` (canonical) are considered.
+    async fn resume_build(
+        &self,
+        incoming: &IncomingMessage,
+        sender_id: &str,
+        project: Option<&str>,
+        lang: &str,
+    ) {
+        let builds_dir = DataDir::new(&self.data_dir).workspace().join("builds");
+        let Some((project_dir, chain)) = find_resumable(&builds_dir, project, sender_id) else {
+            self.send_text(incoming, build_nothing_to_resume_message(lang))
+                .await;
+            return;
+        };
+
+        let already_running = self
+            .active_builds
+            .lock()
+            .unwrap()
+            .values()
+            .any(|p| p.project.as_deref() == Some(chain.project_name.as_str()));
+        if already_running {
+            self.send_text(
+                incoming,
+                &build_already_running_message(lang, &chain.project_name),
+            )
+            .await;
+            return;
+        }
+
+        let topology_name = chain
+            .topology_name
+            .clone()
+            .unwrap_or_else(|| "development".to_string());
+        let loaded = match builds_topology::load_topology(&self.data_dir, &topology_name) {
+            Ok(t) => t,
+            Err(e) => {
+                self.send_text(incoming, &format!("Failed to load topology: {e}"))
+                    .await;
+                return;
+            }
+        };
+
+        let state = orchestrator_state_from_chain(chain, project_dir, &loaded);
+        let Some(next) = loaded
+            .topology
+            .phases
+            .iter()
+            .find(|p| !state.completed_phases.contains(&p.name))
+        else {
+            self.send_text(incoming, build_nothing_to_resume_message(lang))
+                .await;
+            return;
+        };
+        let project_name = state
+            .brief
+            .as_ref()
+            .map(|b| b.name.clone())
+            .unwrap_or_default();
+
+        info!(
+            "[{}] resuming build '{project_name}' at phase '{}'",
+            incoming.channel, next.name
+        );
+        self.send_text(
+            incoming,
+            &build_resume_message(lang, &project_name, &next.name),
+        )
+        .await;
+
+        let typing_handle = self.spawn_build_typing(incoming).await;
+        self.run_build_phases(
+            incoming,
+            typing_handle,
+            lang,
+            &topology_name,
+            &loaded,
+            state,
+        )
+        .await;
+    }
+
+    /// Keep the typing indicator alive for a long-running resumed build.
+    async fn spawn_build_typing(
+        &self,
+        incoming: &IncomingMessage,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        let ch = self.channels.get(&incoming.channel)?.clone();
+        let target = incoming.reply_target.clone()?;
+        let _ = ch.send_typing(&target).await;
+        Some(self.spawn_scoped(incoming, async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                if ch.send_typing(&target).await.is_err() {
+                    break;
+                }
+            }
+        }))
+    }
+
+    /// Remove `chain-state.json` after a successful build so it is not resumed again.
+    pub(super) async fn clear_chain_state(project_dir: &Path) {
+        let path = project_dir.join("docs/.workflow/chain-state.json");
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove chain-state.json: {e}");
+            }
+        }
+    }
+}
+
+/// Whether `text` is `/build status` (with optional `@botname` suffix).
+fn is_build_status_request(text: &str) -> bool {
+    let mut words = text.split_whitespace();
+    let cmd = words.next().unwrap_or("");
+    cmd.split('@').next() == Some("/build")
+        && words
+            .next()
+            .is_some_and(|w| w.eq_ignore_ascii_case("status"))
+}
+
+/// Read `chain-state.json` from a project directory.
+fn load_chain_state(project_dir: &Path) -> Option<ChainState> {
+    let path = project_dir.join("docs/.workflow/chain-state.json");
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Locate a resumable build owned by `owner`: the named project, or their
+/// most recently failed one. Chain state without an owner is never resumed.
+fn find_resumable(
+    builds_dir: &Path,
+    project: Option<&str>,
+    owner: &str,
+) -> Option<(PathBuf, ChainState)> {
+    let owned = |cs: &ChainState| cs.owner_id.as_deref() == Some(owner);
+    if let Some(name) = project {
+        // Project names become directory names — reject traversal.
+        if name.is_empty() || name.contains('/') || name.contains('\\') || name.contains("..") {
+            return None;
+        }
+        let dir = builds_dir.join(name);
+        return load_chain_state(&dir).filter(owned).map(|cs| (dir, cs));
+    }
+
+    std::fs::read_dir(builds_dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let modified = std::fs::metadata(dir.join("docs/.workflow/chain-state.json"))
+                .and_then(|m| m.modified())
+                .ok()?;
+            let cs = load_chain_state(&dir).filter(owned)?;
+            Some((modified, dir, cs))
+        })
+        .max_by_key(|(modified, _, _)| *modified)
+        .map(|(_, dir, cs)| (dir, cs))
+}
+
+/// Rebuild the orchestrator state from persisted chain state.
+///
+/// The brief is re-parsed from the stored analyst output; once it parses,
+/// brief-parsing phases count as complete so the analyst is not re-run
+/// against the `/build resume` message.
+fn orchestrator_state_from_chain(
+    chain: ChainState,
+    project_dir: PathBuf,
+    loaded: &builds_topology::LoadedTopology,
+) -> OrchestratorState {
+    let brief = chain.brief_text.as_deref().and_then(parse_project_brief);
+    let owner_id = chain.owner_id.unwrap_or_default();
+    let mut completed_phases = chain.completed_phases;
+    if brief.is_some() {
+        for phase in &loaded.topology.phases {
+            if phase.phase_type == PhaseType::ParseBrief && !completed_phases.contains(&phase.name)
+            {
+                completed_phases.push(phase.name.clone());
+            }
+        }
+    }
+    OrchestratorState {
+        brief_text: chain.brief_text,
+        brief,
+        project_dir_str: Some(project_dir.display().to_string()),
+        project_dir: Some(project_dir),
+        completed_phases,
+        owner_id,
+    }
+}
+
+/// Format a duration as `1h 02m`, `3m 05s`, or `42s`.
+pub(super) fn format_elapsed(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(name: &str, dir: &Path, completed: &[&str]) -> ChainState {
+        ChainState {
+            project_name: name.to_string(),
+            project_dir: dir.display().to_string(),
+            completed_phases: completed.iter().map(|s| s.to_string()).collect(),
+            failed_phase: Some("qa".to_string()),
+            failure_reason: Some("2 tests failing".to_string()),
+            topology_name: Some("development".to_string()),
+            brief_text: Some(format!(
+                "PROJECT_NAME: {name}\nLANGUAGE: Rust\nDATABASE: none\nFRONTEND: no\nSCOPE: demo\nCOMPONENTS:\n- core"
+            )),
+            owner_id: Some("alice".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_is_build_status_request() {
+        assert!(is_build_status_request("/build status"));
+        assert!(is_build_status_request("/build@omega_bot STATUS"));
+        assert!(!is_build_status_request("/build resume"));
+        assert!(!is_build_status_request("/build"));
+        assert!(!is_build_status_request("build status"));
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_elapsed(Duration::from_secs(3720)), "1h 02m");
+    }
+
+    #[tokio::test]
+    async fn test_chain_state_json_roundtrip_and_clear() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("todo-api");
+        let cs = chain("todo-api", &dir, &["analyst", "architect"]);
+        Gateway::save_chain_state(&dir, &cs).await;
+
+        let loaded = load_chain_state(&dir).expect("chain-state.json should load");
+        assert_eq!(loaded.project_name, "todo-api");
+        assert_eq!(loaded.completed_phases, vec!["analyst", "architect"]);
+        assert_eq!(loaded.topology_name.as_deref(), Some("development"));
+        assert!(loaded
+            .brief_text
+            .unwrap()
+            .contains("PROJECT_NAME: todo-api"));
+
+        Gateway::clear_chain_state(&dir).await;
+        assert!(load_chain_state(&dir).is_none());
+        // Human-readable copy stays for inspection.
+        assert!(dir.join("docs/.workflow/chain-state.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_find_resumable_named_and_latest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let older = tmp.path().join("older");
+        let newer = tmp.path().join("newer");
+        Gateway::save_chain_state(&older, &chain("older", &older, &["analyst"])).await;
+        std::thread::sleep(Duration::from_millis(20));
+        Gateway::save_chain_state(&newer, &chain("newer", &newer, &["analyst"])).await;
+
+        let (dir, cs) = find_resumable(tmp.path(), None, "alice").unwrap();
+        assert_eq!(dir, newer);
+        assert_eq!(cs.project_name, "newer");
+
+        let (dir, _) = find_resumable(tmp.path(), Some("older"), "alice").unwrap();
+        assert_eq!(dir, older);
+
+        assert!(find_resumable(tmp.path(), Some("missing"), "alice").is_none());
+        assert!(find_resumable(tmp.path(), Some("../older"), "alice").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_resumable_only_returns_own_builds() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mine = tmp.path().join("mine");
+        let theirs = tmp.path().join("theirs");
+        let legacy = tmp.path().join("legacy");
+        Gateway::save_chain_state(&mine, &chain("mine", &mine, &["analyst"])).await;
+        std::thread::sleep(Duration::from_millis(20));
+        let mut cs = chain("theirs", &theirs, &["analyst"]);
+        cs.owner_id = Some("bob".to_string());
+        Gateway::save_chain_state(&theirs, &cs).await;
+        let mut cs = chain("legacy", &legacy, &["analyst"]);
+        cs.owner_id = None;
+        Gateway::save_chain_state(&legacy, &cs).await;
+
+        // Bob's build is newer, but alice only sees her own.
+        let (dir, _) = find_resumable(tmp.path(), None, "alice").unwrap();
+        assert_eq!(dir, mine);
+        assert!(find_resumable(tmp.path(), Some("theirs"), "alice").is_none());
+        assert!(find_resumable(tmp.path(), Some("legacy"), "alice").is_none());
+        let (dir, _) = find_resumable(tmp.path(), Some("theirs"), "bob").unwrap();
+        assert_eq!(dir, theirs);
+    }
+
+    #[test]
+    fn test_orchestrator_state_from_chain_skips_parsed_brief() {
+        let tmp = tempfile::tempdir().unwrap();
+        let loaded =
+            builds_topology::load_topology(tmp.path().to_str().unwrap(), "development").unwrap();
+        let dir = tmp.path().join("todo-api");
+        // Analyst failed post-validation: brief exists but the phase was not recorded.
+        let state =
+            orchestrator_state_from_chain(chain("todo-api", &dir, &[]), dir.clone(), &loaded);
+
+        assert_eq!(state.brief.as_ref().unwrap().name, "todo-api");
+        assert_eq!(state.project_dir.as_deref(), Some(dir.as_path()));
+        let next = loaded
+            .topology
+            .phases
+            .iter()
+            .find(|p| !state.completed_phases.contains(&p.name))
+            .unwrap();
+        assert_eq!(next.name, "architect");
+    }
+
+    #[test]
+    fn test_progress_guard_registers_and_clears() {
+        let builds: ActiveBuilds = Arc::new(Mutex::new(HashMap::new()));
+        {
+            let guard =
+                BuildProgressGuard::register(&builds, "telegram:1".to_string(), "development", 7);
+            let dir = PathBuf::from("/tmp/todo-api");
+            let cs = chain("todo-api", &dir, &["analyst", "architect"]);
+            guard.record(&OrchestratorState {
+                brief: cs.brief_text.as_deref().and_then(parse_project_brief),
+                brief_text: cs.brief_text,
+                project_dir_str: Some(cs.project_dir),
+                project_dir: Some(dir.clone()),
+                completed_phases: cs.completed_phases,
+                owner_id: "alice".to_string(),
+            });
+            guard.set_phase(3, "developer");
+            let map = builds.lock().unwrap();
+            let p = map.get("telegram:1").unwrap();
+            assert_eq!(p.project.as_deref(), Some("todo-api"));
+            assert_eq!(p.phase, "developer");
+            assert_eq!(p.phase_index, 3);
+            assert_eq!(p.total_phases, 7);
+
+            let (stopped_dir, stopped) = p.stopped_chain_state().unwrap();
+            assert_eq!(stopped_dir, dir);
+            assert_eq!(stopped.completed_phases, vec!["analyst", "architect"]);
+            assert_eq!(stopped.failed_phase.as_deref(), Some("developer"));
+            assert_eq!(stopped.failure_reason.as_deref(), Some("stopped by user"));
+            assert!(stopped.brief_text.is_some());
+            assert_eq!(stopped.owner_id.as_deref(), Some("alice"));
+        }
+        assert!(builds.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,406 @@
+//! Per-phase execution controls for topology builds.
+//!
+//! - Agent runs with topology limits (max_turns, allowed_tools, timeout_secs)
+//! - Pre-validation dispatch, including sandboxed `command` validations
+//! - Post-validation of generated files
+//! - Parallel groups: consecutive standard phases run concurrently
+
+use super::builds::OrchestratorState;
+use super::builds_parse::phase_message_by_name;
+use super::builds_topology::{Phase, ValidationConfig, ValidationType};
+use super::Gateway;
+use omega_core::{
+    context::{Context, ProgressSink},
+    message::IncomingMessage,
+};
+use std::path::Path;
+use std::time::Duration;
+use tracing::warn;
+
+/// Timeout for a `command` validation when its phase sets no `timeout_secs`.
+const COMMAND_VALIDATION_TIMEOUT_SECS: u64 = 600;
+
+/// Maximum characters of command output quoted in a validation failure.
+const COMMAND_OUTPUT_TAIL: usize = 1500;
+
+/// Topology overrides for a single agent run.
+#[derive(Default)]
+pub(super) struct PhaseLimits<'a> {
+    pub max_turns: Option<u32>,
+    pub allowed_tools: Option<&'a [String]>,
+    pub timeout: Option<Duration>,
+    /// Where tool-use progress of the run is relayed.
+    pub progress: Option<ProgressSink>,
+}
+
+impl PhaseLimits<'_> {
+    /// Relay the run's tool-use progress to `progress`.
+    pub fn with_progress(mut self, progress: Option<ProgressSink>) -> Self {
+        self.progress = progress;
+        self
+    }
+}
+
+impl Phase {
+    /// Limits for the phase's own agent.
+    pub fn limits(&self) -> PhaseLimits<'_> {
+        PhaseLimits {
+            max_turns: self.max_turns,
+            allowed_tools: self.allowed_tools.as_deref(),
+            timeout: self.timeout_secs.map(Duration::from_secs),
+            progress: None,
+        }
+    }
+
+    /// Limits for the corrective-loop fix agent: same timeout, but full tool
+    /// access (a read-only verifier must not leave the fixer unable to write).
+    pub fn fix_limits(&self) -> PhaseLimits<'_> {
+        PhaseLimits {
+            timeout: self.timeout_secs.map(Duration::from_secs),
+            ..Default::default()
+        }
+    }
+}
+
+impl Gateway {
+    /// Generic phase runner with retry logic (3 attempts, 2s delay).
+    ///
+    /// Each phase gets a fresh Context with `agent_name` set and no session_id.
+    /// The agent file provides the system prompt; only the user message is sent via `-p`.
+    pub(super) async fn run_build_phase(
+        &self,
+        agent_name: &str,
+        user_message: &str,
+        model: &str,
+        max_turns: Option<u32>,
+    ) -> Result<String, String> {
+        let limits = PhaseLimits {
+            max_turns,
+            ..Default::default()
+        };
+        self.run_build_phase_limited(agent_name, user_message, model, &limits)
+            .await
+    }
+
+    /// [`run_build_phase`](Self::run_build_phase) with topology limits applied.
+    ///
+    /// A timed-out attempt is not retried: the agent already used its budget.
+    pub(super) async fn run_build_phase_limited(
+        &self,
+        agent_name: &str,
+        user_message: &str,
+        model: &str,
+        limits: &PhaseLimits<'_>,
+    ) -> Result<String, String> {
+        let mut ctx = Context::new(user_message);
+        ctx.system_prompt = String::new();
+        ctx.agent_name = Some(agent_name.to_string());
+        ctx.model = Some(model.to_string());
+        // Explicit max_turns prevents auto-resume from losing agent context.
+        ctx.max_turns = Some(limits.max_turns.unwrap_or(100));
+        ctx.allowed_tools = limits.allowed_tools.map(<[String]>::to_vec);
+        ctx.progress = limits.progress.clone();
+        ctx.approval = super::approval::background_approval_gate(&self.approval_config);
+
+        for attempt in 1..=3u32 {
+            let result = match limits.timeout {
+                Some(limit) => {
+                    match tokio::time::timeout(limit, self.provider.complete(&ctx)).await {
+                        Ok(r) => r,
+                        Err(_) => {
+                            warn!(
+                                "build phase '{agent_name}' timed out after {}s",
+                                limit.as_secs()
+                            );
+                            return Err(format!(
+                                "phase '{agent_name}' timed out after {}s",
+                                limit.as_secs()
+                            ));
+                        }
+                    }
+                }
+                None => self.provider.complete(&ctx).await,
+            };
+            match result {
+                Ok(resp) => return Ok(resp.text),
+                Err(e) => {
+                    warn!("build phase '{agent_name}' attempt {attempt}/3 failed: {e}");
+                    if attempt < 3 {
+                        tokio::time::sleep(Duration::from_secs(2)).await;
+                    }
+                }
+            }
+        }
+        Err(format!("phase '{agent_name}' failed after 3 attempts"))
+    }
+
+    /// Run a phase's pre-validation. File checks use [`Gateway::run_validation`];
+    /// `command` validations run in the sandbox from the project directory.
+    pub(super) async fn run_pre_validation(
+        &self,
+        project_dir: &Path,
+        config: &ValidationConfig,
+        phase: &Phase,
+    ) -> Option<String> {
+        if config.validation_type != ValidationType::Command {
+            return Self::run_validation(project_dir, config);
+        }
+        let command = config.command.as_deref().unwrap_or("").trim();
+        if command.is_empty() {
+            return Some("Pre-validation failed: command validation has no command".to_string());
+        }
+        let secs = phase
+            .timeout_secs
+            .unwrap_or(COMMAND_VALIDATION_TIMEOUT_SECS);
+        let data_dir = omega_core::config::shellexpand(&self.data_dir);
+        let log = validation_log_path(project_dir, &phase.name);
+        run_command_check(Path::new(&data_dir), project_dir, command, secs, &log).await
+    }
+
+    /// Check that a phase produced its `post_validation` files.
+    pub(super) fn run_post_validation(phase: &Phase, project_dir: &Path) -> Result<(), String> {
+        for path in phase.post_validation.iter().flatten() {
+            // Reject path traversal in post_validation paths.
+            if path.contains("..") || path.starts_with('/') || path.contains('\\') {
+                return Err(format!(
+                    "Post-validation rejected: path '{path}' contains invalid characters."
+                ));
+            }
+            if !project_dir.join(path).exists() {
+                return Err(format!(
+                    "{} phase completed but {path} was not generated. Build stopped.",
+                    phase.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a parallel group of standard phases concurrently.
+    ///
+    /// Pre-validation runs for every phase first; then all agents run at once.
+    /// Phases that succeed (including post-validation) are marked completed even
+    /// when a sibling fails, so `/build resume` only reruns the failures.
+    /// Returns the failed phase names and reasons.
+    pub(super) async fn run_parallel_phases(
+        &self,
+        incoming: &IncomingMessage,
+        user_lang: &str,
+        phases: &[&Phase],
+        models: &[&str],
+        state: &mut OrchestratorState,
+    ) -> Result<(), (String, String)> {
+        for phase in phases {
+            self.send_text(incoming, &phase_message_by_name(user_lang, &phase.name))
+                .await;
+            if let (Some(validation), Some(project_dir)) =
+                (&phase.pre_validation, &state.project_dir)
+            {
+                if let Some(err) = self
+                    .run_pre_validation(project_dir, validation, phase)
+                    .await
+                {
+                    return Err((format!("{} (validation)", phase.name), err));
+                }
+            }
+        }
+
+        let runs = phases
+            .iter()
+            .zip(models)
+            .map(|(phase, model)| self.execute_standard(incoming, phase, model, state));
+        let results = futures_util::future::join_all(runs).await;
+
+        let mut failed = Vec::new();
+        let mut reasons = Vec::new();
+        for (phase, result) in phases.iter().zip(results) {
+            let result = result.and_then(|()| match &state.project_dir {
+                Some(dir) => Self::run_post_validation(phase, dir),
+                None => Ok(()),
+            });
+            match result {
+                Ok(()) => state.completed_phases.push(phase.name.clone()),
+                Err(reason) => {
+                    failed.push(phase.name.clone());
+                    reasons.push(reason);
+                }
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err((failed.join(", "), reasons.join("\n")))
+        }
+    }
+}
+
+/// Run `sh -c <command>` in the sandbox from `project_dir`; a non-zero exit,
+/// spawn error, or timeout is a failure quoting the output tail.
+///
+/// The full output is written to `log` (best-effort) for artifact delivery.
+async fn run_command_check(
+    data_dir: &Path,
+    project_dir: &Path,
+    command: &str,
+    secs: u64,
+    log: &Path,
+) -> Option<String> {
+    let mut cmd = omega_sandbox::protected_command("sh", data_dir);
+    cmd.arg("-c").arg(command);
+    cmd.current_dir(project_dir);
+    cmd.kill_on_drop(true);
+
+    match tokio::time::timeout(Duration::from_secs(secs), cmd.output()).await {
+        Ok(Ok(output)) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            if let Some(parent) = log.parent() {
+                let _ = tokio::fs::create_dir_all(parent).await;
+            }
+            if let Err(e) = tokio::fs::write(log, &text).await {
+                warn!("failed to write validation log {}: {e}", log.display());
+            }
+            if output.status.success() {
+                return None;
+            }
+            Some(format!(
+                "Pre-validation failed: `{command}` exited with code {}\n{}",
+                output.status.code().unwrap_or(-1),
+                output_tail(&text)
+            ))
+        }
+        Ok(Err(e)) => Some(format!(
+            "Pre-validation failed: cannot run `{command}`: {e}"
+        )),
+        Err(_) => Some(format!(
+            "Pre-validation failed: `{command}` timed out after {secs}s"
+        )),
+    }
+}
+
+/// Where a phase's `command` validation output is kept (inside the project,
+/// excluded from its git history).
+pub(super) fn validation_log_path(project_dir: &Path, phase: &str) -> std::path::PathBuf {
+    project_dir
+        .join("docs/.workflow/reports")
+        .join(format!("{phase}.log"))
+}
+
+/// Last [`COMMAND_OUTPUT_TAIL`] characters of command output.
+fn output_tail(text: &str) -> &str {
+    let text = text.trim_end();
+    match text.char_indices().rev().nth(COMMAND_OUTPUT_TAIL - 1) {
+        Some((i, _)) => &text[i..],
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_tail_keeps_short_output() {
+        assert_eq!(output_tail("ok\n"), "ok");
+    }
+
+    #[test]
+    fn test_output_tail_truncates_from_the_front() {
+        let long = format!("{}END", "x".repeat(5000));
+        let tail = output_tail(&long);
+        assert_eq!(tail.chars().count(), COMMAND_OUTPUT_TAIL);
+        assert!(tail.ends_with("END"));
+    }
+
+    #[tokio::test]
+    async fn test_command_check_exit_code() {
+        let data = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("marker"), "").unwrap();
+
+        let log = validation_log_path(project.path(), "qa");
+        assert!(
+            run_command_check(data.path(), project.path(), "test -f marker", 30, &log)
+                .await
+                .is_none()
+        );
+        let err = run_command_check(data.path(), project.path(), "echo broken; exit 3", 30, &log)
+            .await
+            .unwrap();
+        assert!(err.contains("exited with code 3"), "{err}");
+        assert!(err.contains("broken"), "{err}");
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), "broken\n");
+    }
+
+    #[tokio::test]
+    async fn test_command_check_timeout() {
+        let data = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let err = run_command_check(
+            data.path(),
+            project.path(),
+            "sleep 5",
+            1,
+            &project.path().join("t.log"),
+        )
+        .await
+        .unwrap();
+        assert!(err.contains("timed out after 1s"), "{err}");
+    }
+
+    #[test]
+    fn test_phase_limits_from_topology() {
+        let topo: super::super::builds_topology::Topology = toml::from_str(
+            r#"
+[topology]
+name = "t"
+description = "d"
+version = 1
+
+[[phases]]
+name = "review"
+agent = "build-reviewer"
+max_turns = 20
+timeout_secs = 300
+allowed_tools = ["Read", "Grep"]
+"#,
+        )
+        .unwrap();
+        let phase = &topo.phases[0];
+        let limits = phase.limits();
+        assert_eq!(limits.max_turns, Some(20));
+        assert_eq!(limits.timeout, Some(Duration::from_secs(300)));
+        assert_eq!(
+            limits.allowed_tools,
+            Some(&["Read".to_string(), "Grep".to_string()][..])
+        );
+        let fix = phase.fix_limits();
+        assert!(fix.allowed_tools.is_none());
+        assert_eq!(fix.timeout, Some(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn test_post_validation_reports_missing_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let topo: super::super::builds_topology::Topology = toml::from_str(
+            r#"
+[topology]
+name = "t"
+description = "d"
+version = 1
+
+[[phases]]
+name = "architect"
+agent = "build-architect"
+post_validation = ["specs/architecture.md"]
+"#,
+        )
+        .unwrap();
+        let phase = &topo.phases[0];
+        let err = Gateway::run_post_validation(phase, tmp.path()).unwrap_err();
+        assert!(err.contains("specs/architecture.md"));
+        std::fs::create_dir_all(tmp.path().join("specs")).unwrap();
+        std::fs::write(tmp.path().join("specs/architecture.md"), "# A").unwrap();
+        assert!(Gateway::run_post_validation(phase, tmp.path()).is_ok());
+    }
+}
@@ -4,19 +4,23 @@
 //! build pipeline. The "development" topology is bundled in the binary and
 //! auto-deployed to `~/.omega/topologies/development/` on first build request.
 //!
-//! Public interface (all pub(super)):
-//! - Topology, TopologyMeta, Phase, PhaseType, ModelTier, RetryConfig,
-//!   ValidationConfig, ValidationType structs with serde::Deserialize
+//! Public interface (pub(super) unless noted):
+//! - Topology, TopologyMeta, Phase, PhaseType, ModelTier, RetryConfig, LoopStyle,
+//!   PhaseCondition, ValidationConfig, ValidationType structs with serde::Deserialize
+//! - phase_groups(phases, start) -> consecutive runs sharing a parallel_group
 //! - LoadedTopology: topology + agent content map + helper methods
 //! - load_topology(data_dir, name) -> Result<LoadedTopology, String>
 //! - deploy_bundled_topology(data_dir) -> Result<(), String>
 //! - validate_topology_name(name) -> Result<(), String>
+//! - split_topology_suffix(request, installed), list_topologies(data_dir)
+//! - validate_topology_dir(dir) -> TopologyReport (pub(crate), for `omega topology validate`)
 
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::PathBuf;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
+use super::builds_parse::ProjectBrief;
 use omega_core::config::shellexpand;
 
 // ---------------------------------------------------------------------------
@@ -58,6 +62,39 @@ pub(super) struct Phase {
     pub pre_validation: Option<ValidationConfig>,
     #[serde(default)]
     pub post_validation: Option<Vec<String>>,
+    /// Wall-clock limit for each agent run in this phase (provider timeout if unset).
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Tool whitelist for this phase's agents. Unset = full access.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Run only when the parsed brief matches; otherwise the phase is skipped.
+    #[serde(default)]
+    pub when: Option<PhaseCondition>,
+    /// Consecutive standard phases with the same group name run concurrently.
+    #[serde(default)]
+    pub parallel_group: Option<String>,
+}
+
+impl Phase {
+    /// Result parsing for a corrective loop: explicit `retry.loop_style`, else
+    /// inferred from the phase name ("qa" → QA, anything else → review).
+    pub fn loop_style(&self) -> LoopStyle {
+        match self.retry.as_ref().and_then(|r| r.loop_style.clone()) {
+            Some(style) => style,
+            None if self.name == "qa" => LoopStyle::Qa,
+            None => LoopStyle::Review,
+        }
+    }
+
+    /// Whether the phase applies to this brief. Phases always run before the
+    /// brief exists (`omega topology validate` flags such conditions).
+    pub fn applies_to(&self, brief: Option<&ProjectBrief>) -> bool {
+        match (&self.when, brief) {
+            (Some(cond), Some(brief)) => cond.matches(brief),
+            _ => true,
+        }
+    }
 }
 
 fn default_model_tier() -> ModelTier {
@@ -93,6 +130,50 @@ pub(super) enum PhaseType {
 pub(super) struct RetryConfig {
     pub max: u32,
     pub fix_agent: String,
+    #[serde(default)]
+    pub loop_style: Option<LoopStyle>,
+}
+
+/// How a corrective loop prompts its verifier and parses the verdict.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum LoopStyle {
+    /// Build/lint/test run, `VERIFICATION: PASS|FAIL`.
+    Qa,
+    /// Code review, `REVIEW: PASS|FAIL`.
+    Review,
+}
+
+/// `[phases.when]` — every listed field must match (case-insensitive).
+#[derive(Debug, Clone, Deserialize, Default)]
+pub(super) struct PhaseCondition {
+    /// Brief language is one of these.
+    #[serde(default)]
+    pub language: Vec<String>,
+    /// Brief scope mentions at least one of these.
+    #[serde(default)]
+    pub scope_contains: Vec<String>,
+    /// Brief does (or does not) include a frontend.
+    #[serde(default)]
+    pub frontend: Option<bool>,
+}
+
+impl PhaseCondition {
+    pub fn matches(&self, brief: &ProjectBrief) -> bool {
+        let language_ok = self.language.is_empty()
+            || self
+                .language
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(brief.language.trim()));
+        let scope = brief.scope.to_lowercase();
+        let scope_ok = self.scope_contains.is_empty()
+            || self
+                .scope_contains
+                .iter()
+                .any(|w| scope.contains(&w.to_lowercase()));
+        let frontend_ok = self.frontend.is_none_or(|f| f == brief.frontend);
+        language_ok && scope_ok && frontend_ok
+    }
 }
 
 /// Pre-phase validation rules.
@@ -104,14 +185,23 @@ pub(super) struct ValidationConfig {
     pub paths: Vec<String>,
     #[serde(default)]
     pub patterns: Vec<String>,
+    /// Shell command for `type = "command"`, run in the project directory.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Report file the command writes (e.g. `coverage/lcov.info`), relative to
+    /// the project; sent to the user with the build artifacts.
+    #[serde(default)]
+    pub report: Option<String>,
 }
 
-/// The two validation strategies that exist today.
+/// Pre-phase validation strategies.
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub(super) enum ValidationType {
     FileExists,
     FilePatterns,
+    /// Run `command` in the sandbox; a non-zero exit fails validation.
+    Command,
 }
 
 // ---------------------------------------------------------------------------
@@ -159,6 +249,24 @@ impl LoadedTopology {
     }
 }
 
+/// Split `phases[start..]` into execution steps: consecutive phases sharing a
+/// `parallel_group` form one step, every other phase is a step of its own.
+pub(super) fn phase_groups(phases: &[Phase], start: usize) -> Vec<std::ops::Range<usize>> {
+    let mut groups = Vec::new();
+    let mut i = start;
+    while i < phases.len() {
+        let mut end = i + 1;
+        if let Some(group) = &phases[i].parallel_group {
+            while end < phases.len() && phases[end].parallel_group.as_ref() == Some(group) {
+                end += 1;
+            }
+        }
+        groups.push(i..end);
+        i = end;
+    }
+    groups
+}
+
 // ---------------------------------------------------------------------------
 // Bundled defaults — compiled into the binary via include_str!()
 // ---------------------------------------------------------------------------
@@ -259,6 +367,19 @@ pub(super) fn load_topology(data_dir: &str, name: &str) -> Result<LoadedTopology
         .map_err(|e| format!("failed to read TOPOLOGY.toml: {e}"))?;
     let topology: Topology =
         toml::from_str(&toml_content).map_err(|e| format!("failed to parse TOPOLOGY.toml: {e}"))?;
+    // Reports are sent to the user, so they must stay inside the project.
+    for phase in &topology.phases {
+        let report = phase
+            .pre_validation
+            .as_ref()
+            .and_then(|v| v.report.as_ref());
+        if let Some(path) = report.filter(|p| !is_project_relative(p)) {
+            return Err(format!(
+                "phase '{}' report '{path}' must be relative to the project",
+                phase.name
+            ));
+        }
+    }
 
     // Load all referenced agent .md files.
     let mut agents = HashMap::new();
@@ -329,6 +450,244 @@ pub(super) fn validate_topology_name(name: &str) -> Result<(), String> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Selection, listing, and validation
+// ---------------------------------------------------------------------------
+
+/// Whether a topology-declared path stays inside the project directory.
+fn is_project_relative(path: &str) -> bool {
+    !(path.is_empty()
+        || path.contains("..")
+        || path.starts_with('/')
+        || path.contains('\\')
+        || Path::new(path).is_absolute())
+}
+
+/// Split a trailing ` | <topology>` selector off a build request.
+///
+/// `BUILD_PROPOSAL: <description> | research` selects the "research" topology.
+/// Only names in `installed` (see [`list_topologies`]) count; any other suffix
+/// is left as part of the request.
+pub(super) fn split_topology_suffix<'a>(
+    request: &'a str,
+    installed: &[String],
+) -> (&'a str, Option<&'a str>) {
+    if let Some((desc, candidate)) = request.rsplit_once('|') {
+        let candidate = candidate.trim();
+        if installed.iter().any(|name| name == candidate) {
+            return (desc.trim_end(), Some(candidate));
+        }
+    }
+    (request, None)
+}
+
+/// List installed topologies as `(dir name, parsed TOPOLOGY.toml or error)`, sorted.
+///
+/// Deploys the bundled "development" topology first so it always appears.
+pub(super) fn list_topologies(data_dir: &str) -> Vec<(String, Result<Topology, String>)> {
+    if let Err(e) = deploy_bundled_topology(data_dir) {
+        warn!("topologies: {e}");
+    }
+    let base = PathBuf::from(shellexpand(data_dir)).join("topologies");
+    let Ok(entries) = std::fs::read_dir(&base) else {
+        return Vec::new();
+    };
+    let mut out: Vec<_> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let parsed = std::fs::read_to_string(e.path().join("TOPOLOGY.toml"))
+                .map_err(|e| format!("failed to read TOPOLOGY.toml: {e}"))
+                .and_then(|c| toml::from_str::<Topology>(&c).map_err(|e| e.to_string()));
+            (name, parsed)
+        })
+        .collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+/// Outcome of [`validate_topology_dir`]. Errors block a build; warnings do not.
+#[derive(Debug, Default)]
+pub(crate) struct TopologyReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    /// Phase names in order, when TOPOLOGY.toml parsed.
+    pub phases: Vec<String>,
+}
+
+/// Check a topology directory (`TOPOLOGY.toml` + `agents/*.md`) before deployment.
+///
+/// Stricter than [`load_topology`]: it also checks phase wiring that the
+/// orchestrator relies on at run time.
+pub(crate) fn validate_topology_dir(dir: &Path) -> TopologyReport {
+    let mut report = TopologyReport::default();
+
+    let content = match std::fs::read_to_string(dir.join("TOPOLOGY.toml")) {
+        Ok(c) => c,
+        Err(e) => {
+            report
+                .errors
+                .push(format!("cannot read TOPOLOGY.toml: {e}"));
+            return report;
+        }
+    };
+    let topology: Topology = match toml::from_str(&content) {
+        Ok(t) => t,
+        Err(e) => {
+            report.errors.push(format!("invalid TOPOLOGY.toml: {e}"));
+            return report;
+        }
+    };
+
+    let meta_name = &topology.topology.name;
+    if let Err(e) = validate_topology_name(meta_name) {
+        report.errors.push(e);
+    }
+    if let Some(dir_name) = dir.file_name().and_then(|n| n.to_str()) {
+        if dir_name != meta_name {
+            report.warnings.push(format!(
+                "directory '{dir_name}' differs from topology name '{meta_name}' (builds select by directory name)"
+            ));
+        }
+    }
+
+    if topology.phases.is_empty() {
+        report.errors.push("topology has no phases".to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    for phase in &topology.phases {
+        report.phases.push(phase.name.clone());
+        if phase.name.trim().is_empty() {
+            report.errors.push("phase with empty name".to_string());
+        } else if !seen.insert(phase.name.as_str()) {
+            report
+                .errors
+                .push(format!("duplicate phase name '{}'", phase.name));
+        }
+    }
+
+    let brief_index = topology
+        .phases
+        .iter()
+        .position(|p| p.phase_type == PhaseType::ParseBrief);
+    match brief_index {
+        None => report.errors.push(
+            "no parse-brief phase — builds need it to name and create the project directory"
+                .to_string(),
+        ),
+        Some(0) => {}
+        Some(_) => report.warnings.push(
+            "parse-brief phase is not first; earlier phases run without a project directory"
+                .to_string(),
+        ),
+    }
+
+    let mut finished_groups = std::collections::HashSet::new();
+    for range in phase_groups(&topology.phases, 0) {
+        let group = &topology.phases[range.start].parallel_group;
+        let Some(name) = group else { continue };
+        if !finished_groups.insert(name.as_str()) {
+            report.errors.push(format!(
+                "parallel_group '{name}' is split; its phases must be consecutive"
+            ));
+        }
+        if range.len() == 1 {
+            report.warnings.push(format!(
+                "parallel_group '{name}' has a single phase and runs sequentially"
+            ));
+        }
+        for phase in &topology.phases[range] {
+            if phase.phase_type != PhaseType::Standard {
+                report.errors.push(format!(
+                    "phase '{}' is in parallel_group '{name}' but only standard phases can run in parallel",
+                    phase.name
+                ));
+            }
+        }
+    }
+
+    let mut agents: Vec<&str> = Vec::new();
+    for (index, phase) in topology.phases.iter().enumerate() {
+        agents.push(&phase.agent);
+        if phase.timeout_secs == Some(0) {
+            report
+                .errors
+                .push(format!("phase '{}' has timeout_secs = 0", phase.name));
+        }
+        if matches!(&phase.allowed_tools, Some(t) if t.is_empty()) {
+            report.warnings.push(format!(
+                "phase '{}' has an empty allowed_tools list and runs with full access",
+                phase.name
+            ));
+        }
+        if phase.when.is_some() && brief_index.is_none_or(|b| index <= b) {
+            report.warnings.push(format!(
+                "phase '{}' has a when condition but runs before the brief is parsed; it always runs",
+                phase.name
+            ));
+        }
+        if phase.phase_type == PhaseType::CorrectiveLoop {
+            match &phase.retry {
+                None => report.errors.push(format!(
+                    "phase '{}' is corrective-loop but has no [phases.retry]",
+                    phase.name
+                )),
+                Some(r) if r.max == 0 => report
+                    .errors
+                    .push(format!("phase '{}' has retry.max = 0", phase.name)),
+                Some(_) => {}
+            }
+        }
+        if let Some(retry) = &phase.retry {
+            agents.push(&retry.fix_agent);
+        }
+        if let Some(v) = &phase.pre_validation {
+            let empty = match v.validation_type {
+                ValidationType::FileExists => v.paths.is_empty(),
+                ValidationType::FilePatterns => v.patterns.is_empty(),
+                ValidationType::Command => v.command.as_deref().is_none_or(|c| c.trim().is_empty()),
+            };
+            if empty {
+                report.errors.push(format!(
+                    "phase '{}' pre_validation has nothing to check",
+                    phase.name
+                ));
+            }
+        }
+        let report_file = phase
+            .pre_validation
+            .as_ref()
+            .and_then(|v| v.report.as_ref());
+        for path in phase.post_validation.iter().flatten().chain(report_file) {
+            if !is_project_relative(path) {
+                report.errors.push(format!(
+                    "phase '{}' path '{path}' must be relative to the project",
+                    phase.name
+                ));
+            }
+        }
+    }
+
+    agents.sort_unstable();
+    agents.dedup();
+    for agent in agents {
+        if agent.is_empty() || agent.contains('/') || agent.contains('\\') || agent.contains("..") {
+            report.errors.push(format!("invalid agent name '{agent}'"));
+            continue;
+        }
+        match std::fs::read_to_string(dir.join("agents").join(format!("{agent}.md"))) {
+            Ok(c) if c.trim().is_empty() => {
+                report.errors.push(format!("agents/{agent}.md is empty"))
+            }
+            Ok(_) => {}
+            Err(_) => report.errors.push(format!("agents/{agent}.md not found")),
+        }
+    }
+
+    report
+}
+
 // ===========================================================================
 // Tests
 // ===========================================================================
@@ -1482,6 +1841,10 @@ fix_agent = "build-developer"
             retry: None,
             pre_validation: None,
             post_validation: None,
+            timeout_secs: None,
+            allowed_tools: None,
+            when: None,
+            parallel_group: None,
         };
 
         let complex_phase = Phase {
@@ -1493,6 +1856,10 @@ fix_agent = "build-developer"
             retry: None,
             pre_validation: None,
             post_validation: None,
+            timeout_secs: None,
+            allowed_tools: None,
+            when: None,
+            parallel_group: None,
         };
 
         assert_eq!(
@@ -1603,4 +1970,281 @@ fix_agent = "build-fix"
             "Structurally valid TOML should parse even if semantically wrong"
         );
     }
+
+    // ===================================================================
+    // Topology selection, listing, and validation
+    // ===================================================================
+
+    #[test]
+    fn test_split_topology_suffix() {
+        let installed = vec!["development".to_string(), "research".to_string()];
+        assert_eq!(
+            split_topology_suffix("A CLI todo app | research", &installed),
+            ("A CLI todo app", Some("research"))
+        );
+        assert_eq!(
+            split_topology_suffix("A CLI todo app", &installed),
+            ("A CLI todo app", None)
+        );
+        // Not a topology name — the pipe belongs to the description.
+        assert_eq!(
+            split_topology_suffix("Parse a | b pipes", &installed),
+            ("Parse a | b pipes", None)
+        );
+        // A valid name that is not installed is description too.
+        assert_eq!(
+            split_topology_suffix("Sort input | uniq", &installed),
+            ("Sort input | uniq", None)
+        );
+    }
+
+    #[test]
+    fn test_list_topologies_includes_bundled_and_custom() {
+        let tmp = tempfile::tempdir().unwrap();
+        let data_dir = tmp.path().to_str().unwrap();
+        let custom = tmp.path().join("topologies/research");
+        std::fs::create_dir_all(&custom).unwrap();
+        std::fs::write(custom.join("TOPOLOGY.toml"), "not toml [").unwrap();
+
+        let list = list_topologies(data_dir);
+        let names: Vec<&str> = list.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["development", "research"]);
+        assert!(list[0].1.is_ok());
+        assert!(
+            list[1].1.is_err(),
+            "broken TOPOLOGY.toml is reported, not hidden"
+        );
+    }
+
+    #[test]
+    fn test_validate_topology_dir_bundled_is_clean() {
+        let tmp = tempfile::tempdir().unwrap();
+        deploy_bundled_topology(tmp.path().to_str().unwrap()).unwrap();
+        let report = validate_topology_dir(&tmp.path().join("topologies/development"));
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert_eq!(report.phases.first().map(String::as_str), Some("analyst"));
+    }
+
+    #[test]
+    fn test_validate_topology_dir_reports_problems() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("broken");
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+        std::fs::write(
+            dir.join("TOPOLOGY.toml"),
+            r#"
+[topology]
+name = "other"
+description = "Broken"
+version = 1
+
+[[phases]]
+name = "qa"
+agent = "build-qa"
+phase_type = "corrective-loop"
+post_validation = ["../escape.md"]
+
+[[phases]]
+name = "qa"
+agent = "build-missing"
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("agents/build-qa.md"), "QA").unwrap();
+
+        let report = validate_topology_dir(&dir);
+        let errors = report.errors.join("\n");
+        assert!(errors.contains("duplicate phase name 'qa'"), "{errors}");
+        assert!(errors.contains("no parse-brief phase"), "{errors}");
+        assert!(errors.contains("has no [phases.retry]"), "{errors}");
+        assert!(errors.contains("../escape.md"), "{errors}");
+        assert!(
+            errors.contains("agents/build-missing.md not found"),
+            "{errors}"
+        );
+        assert!(report.warnings.iter().any(|w| w.contains("differs")));
+    }
+
+    #[test]
+    fn test_validate_topology_dir_missing_toml() {
+        let tmp = tempfile::tempdir().unwrap();
+        let report = validate_topology_dir(tmp.path());
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].contains("TOPOLOGY.toml"));
+    }
+
+    #[test]
+    fn test_load_topology_rejects_report_outside_project() {
+        for report in ["/etc/passwd", "../../secrets.txt"] {
+            let tmp = tempfile::tempdir().unwrap();
+            let topo_dir = tmp.path().join("topologies/leaky");
+            std::fs::create_dir_all(topo_dir.join("agents")).unwrap();
+            std::fs::write(
+                topo_dir.join("TOPOLOGY.toml"),
+                format!(
+                    "[topology]\nname = \"leaky\"\ndescription = \"x\"\nversion = 1\n\n\
+                     [[phases]]\nname = \"tests\"\nagent = \"build-qa\"\n\n\
+                     [phases.pre_validation]\ntype = \"command\"\ncommand = \"true\"\nreport = \"{report}\"\n"
+                ),
+            )
+            .unwrap();
+            std::fs::write(topo_dir.join("agents/build-qa.md"), "QA").unwrap();
+
+            let err = load_topology(tmp.path().to_str().unwrap(), "leaky")
+                .err()
+                .unwrap_or_else(|| panic!("{report} should be rejected"));
+            assert!(err.contains("must be relative"), "{err}");
+        }
+        assert!(is_project_relative("target/test-report.txt"));
+    }
+
+    // ===================================================================
+    // Phase semantics: when, loop_style, parallel_group, command validation
+    // ===================================================================
+
+    const RICH_TOPOLOGY: &str = r#"
+[topology]
+name = "rich"
+description = "Rich phases"
+version = 1
+
+[[phases]]
+name = "analyst"
+agent = "build-analyst"
+phase_type = "parse-brief"
+
+[[phases]]
+name = "docs"
+agent = "build-docs"
+parallel_group = "write"
+timeout_secs = 600
+allowed_tools = ["Read", "Write"]
+
+[[phases]]
+name = "frontend"
+agent = "build-frontend"
+parallel_group = "write"
+
+[phases.when]
+frontend = true
+language = ["TypeScript", "javascript"]
+
+[[phases]]
+name = "tests"
+agent = "build-checker"
+phase_type = "corrective-loop"
+
+[phases.retry]
+max = 2
+fix_agent = "build-developer"
+loop_style = "qa"
+
+[phases.pre_validation]
+type = "command"
+command = "cargo test"
+"#;
+
+    fn brief(language: &str, scope: &str, frontend: bool) -> ProjectBrief {
+        ProjectBrief {
+            name: "demo".to_string(),
+            language: language.to_string(),
+            database: "none".to_string(),
+            frontend,
+            scope: scope.to_string(),
+            components: vec![],
+        }
+    }
+
+    #[test]
+    fn test_topology_deserialize_rich_phase_fields() {
+        let topo: Topology = toml::from_str(RICH_TOPOLOGY).unwrap();
+        let docs = &topo.phases[1];
+        assert_eq!(docs.parallel_group.as_deref(), Some("write"));
+        assert_eq!(docs.timeout_secs, Some(600));
+        assert_eq!(docs.allowed_tools.as_ref().unwrap().len(), 2);
+        assert!(topo.phases[2].when.is_some());
+
+        let tests = &topo.phases[3];
+        assert_eq!(tests.loop_style(), LoopStyle::Qa);
+        let v = tests.pre_validation.as_ref().unwrap();
+        assert_eq!(v.validation_type, ValidationType::Command);
+        assert_eq!(v.command.as_deref(), Some("cargo test"));
+    }
+
+    #[test]
+    fn test_loop_style_inferred_from_name() {
+        let topo: Topology = toml::from_str(RICH_TOPOLOGY).unwrap();
+        let mut phase = topo.phases[3].clone();
+        phase.retry.as_mut().unwrap().loop_style = None;
+        assert_eq!(phase.loop_style(), LoopStyle::Review);
+        phase.name = "qa".to_string();
+        assert_eq!(phase.loop_style(), LoopStyle::Qa);
+    }
+
+    #[test]
+    fn test_phase_when_condition() {
+        let topo: Topology = toml::from_str(RICH_TOPOLOGY).unwrap();
+        let frontend = &topo.phases[2];
+        assert!(frontend.applies_to(Some(&brief("JavaScript", "todo app", true))));
+        assert!(!frontend.applies_to(Some(&brief("JavaScript", "todo app", false))));
+        assert!(!frontend.applies_to(Some(&brief("Rust", "todo app", true))));
+        assert!(frontend.applies_to(None), "no brief yet -> phase runs");
+
+        let cond = PhaseCondition {
+            scope_contains: vec!["API".to_string()],
+            ..Default::default()
+        };
+        assert!(cond.matches(&brief("Go", "REST api for todos", false)));
+        assert!(!cond.matches(&brief("Go", "CLI tool", false)));
+    }
+
+    #[test]
+    fn test_phase_groups() {
+        let topo: Topology = toml::from_str(RICH_TOPOLOGY).unwrap();
+        assert_eq!(phase_groups(&topo.phases, 0), vec![0..1, 1..3, 3..4]);
+        assert_eq!(phase_groups(&topo.phases, 2), vec![2..3, 3..4]);
+        assert!(phase_groups(&topo.phases, 4).is_empty());
+    }
+
+    #[test]
+    fn test_validate_topology_dir_rich_phases() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("rich");
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+        let toml = RICH_TOPOLOGY.replace(
+            "phase_type = \"corrective-loop\"",
+            "phase_type = \"corrective-loop\"\nparallel_group = \"write\"",
+        );
+        std::fs::write(dir.join("TOPOLOGY.toml"), toml).unwrap();
+        for agent in [
+            "build-analyst",
+            "build-docs",
+            "build-frontend",
+            "build-checker",
+            "build-developer",
+        ] {
+            std::fs::write(dir.join(format!("agents/{agent}.md")), "agent").unwrap();
+        }
+        let report = validate_topology_dir(&dir);
+        let errors = report.errors.join("\n");
+        assert!(
+            errors.contains("only standard phases can run in parallel"),
+            "{errors}"
+        );
+
+        // Valid as written.
+        std::fs::write(dir.join("TOPOLOGY.toml"), RICH_TOPOLOGY).unwrap();
+        let report = validate_topology_dir(&dir);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+
+        // Empty command is flagged.
+        let toml = RICH_TOPOLOGY.replace("command = \"cargo test\"", "command = \" \"");
+        std::fs::write(dir.join("TOPOLOGY.toml"), toml).unwrap();
+        let report = validate_topology_dir(&dir);
+        assert!(report
+            .errors
+            .iter()
+            .any(|e| e.contains("pre_validation has nothing to check")));
+    }
 }
@@ -0,0 +1,407 @@
+//! `/stop` — user-initiated cancellation of in-flight requests.
+//!
+//! Every message handled by `dispatch_message`, and every scheduled action
+//! task, runs as an abortable future registered in [`ActiveRequests`] under
+//! `channel:sender_id`. Helper tasks a request spawns (provider call, typing
+//! indicator, status nudges) go through [`Gateway::spawn_scoped`] so they are
+//! aborted with it. `/stop` is answered from `dispatch_message`, before the
+//! busy-sender buffer: it aborts the sender's requests and drops their queued
+//! messages. Dropping the provider future kills the Claude Code CLI together
+//! with its process group (MCP servers included). A running build's chain
+//! state is saved as stopped, so `/build resume` can continue it. Every
+//! cancellation is written to the audit log.
+
+use super::builds_i18n::build_stopped_message;
+use super::builds_resume::format_elapsed;
+use super::Gateway;
+use futures_util::future::{abortable, AbortHandle};
+use omega_core::message::{IncomingMessage, ReplyButton};
+use omega_memory::audit::{AuditEntry, AuditStatus};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::info;
+use uuid::Uuid;
+
+/// Requests in flight, keyed by `channel:sender_id`.
+pub(super) type ActiveRequests = Arc<Mutex<HashMap<String, Vec<RunningRequest>>>>;
+
+/// Source of [`RunningRequest::id`].
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// One cancellable request.
+pub(crate) struct RunningRequest {
+    id: u64,
+    /// The message being handled; `None` for scheduled action tasks.
+    message_id: Option<Uuid>,
+    /// What the user sees in the stop report.
+    label: String,
+    started: Instant,
+    abort: AbortHandle,
+    /// Tasks spawned on the request's behalf.
+    helpers: Vec<tokio::task::AbortHandle>,
+}
+
+impl RunningRequest {
+    fn abort(&self) {
+        self.abort.abort();
+        for helper in &self.helpers {
+            helper.abort();
+        }
+    }
+}
+
+/// Removes a request from [`ActiveRequests`] when it ends, however it ends,
+/// and aborts helper tasks it left behind.
+struct RequestGuard {
+    requests: ActiveRequests,
+    key: String,
+    id: u64,
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        let Ok(mut requests) = self.requests.lock() else {
+            return;
+        };
+        if let Some(list) = requests.get_mut(&self.key) {
+            if let Some(pos) = list.iter().position(|r| r.id == self.id) {
+                for helper in &list.remove(pos).helpers {
+                    helper.abort();
+                }
+            }
+            if list.is_empty() {
+                requests.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Run `fut` as a request of `key` that `/stop` can abort.
+///
+/// Returns `None` when it was stopped.
+pub(super) async fn run_cancellable<F: Future>(
+    requests: &ActiveRequests,
+    key: &str,
+    message_id: Option<Uuid>,
+    label: String,
+    fut: F,
+) -> Option<F::Output> {
+    let (fut, abort) = abortable(fut);
+    let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    requests
+        .lock()
+        .unwrap()
+        .entry(key.to_string())
+        .or_default()
+        .push(RunningRequest {
+            id,
+            message_id,
+            label,
+            started: Instant::now(),
+            abort,
+            helpers: Vec::new(),
+        });
+    let _guard = RequestGuard {
+        requests: requests.clone(),
+        key: key.to_string(),
+        id,
+    };
+    fut.await.ok()
+}
+
+/// Inline button that sends `/stop`. The label is the command itself, so it
+/// reads the same in every language.
+pub(super) fn stop_button() -> ReplyButton {
+    ReplyButton {
+        label: "⏹ /stop".to_string(),
+        command: "/stop".to_string(),
+    }
+}
+
+impl Gateway {
+    /// Handle `incoming` as a request of `sender_key` that `/stop` can abort.
+    pub(super) async fn handle_cancellable(&self, sender_key: &str, incoming: IncomingMessage) {
+        let label = request_label(&incoming.text);
+        let message_id = Some(incoming.id);
+        let handled = run_cancellable(
+            &self.active_requests,
+            sender_key,
+            message_id,
+            label,
+            self.handle_message(incoming),
+        )
+        .await;
+        if handled.is_none() {
+            info!("request from {sender_key} stopped");
+        }
+    }
+
+    /// Spawn a helper task for the request handling `incoming`; `/stop`
+    /// aborts it along with the request.
+    pub(super) fn spawn_scoped<F>(
+        &self,
+        incoming: &IncomingMessage,
+        fut: F,
+    ) -> tokio::task::JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let handle = tokio::spawn(fut);
+        let mut requests = self.active_requests.lock().unwrap();
+        if let Some(request) = requests
+            .values_mut()
+            .flatten()
+            .find(|r| r.message_id == Some(incoming.id))
+        {
+            request.helpers.push(handle.abort_handle());
+        }
+        handle
+    }
+
+    /// Abort the request handling message `message_id`, if it is still running.
+    pub(super) fn abort_message(&self, message_id: Uuid) -> bool {
+        let mut requests = self.active_requests.lock().unwrap();
+        for list in requests.values_mut() {
+            if let Some(pos) = list.iter().position(|r| r.message_id == Some(message_id)) {
+                list.remove(pos).abort();
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Answer `/stop` for a sender with requests in flight: abort them, drop
+    /// their queued messages, and report what was interrupted.
+    ///
+    /// Called before the busy-sender buffer. Returns `false` (not a stop
+    /// request, or nothing running) to let the message take the normal path,
+    /// where `/stop` answers that nothing is running.
+    pub(super) async fn intercept_stop(&self, incoming: &IncomingMessage) -> bool {
+        if !is_stop_request(&incoming.text) {
+            return false;
+        }
+        let sender_key = format!("{}:{}", incoming.channel, incoming.sender_id);
+        let sender_id = self
+            .memory
+            .resolve_sender_id(&incoming.sender_id)
+            .await
+            .unwrap_or_else(|_| incoming.sender_id.clone());
+        // Requests run under the raw id; builds and action tasks under the canonical one.
+        let canonical_key = format!("{}:{}", incoming.channel, sender_id);
+
+        // Snapshot the build before its progress guard drops with the aborted task.
+        let build = self
+            .active_builds
+            .lock()
+            .unwrap()
+            .get(&canonical_key)
+            .map(|p| {
+                (
+                    p.project.clone().unwrap_or_else(|| p.topology.clone()),
+                    p.phase.clone(),
+                    p.stopped_chain_state(),
+                )
+            });
+
+        let stopped: Vec<RunningRequest> = {
+            let mut requests = self.active_requests.lock().unwrap();
+            let mut stopped = requests.remove(&sender_key).unwrap_or_default();
+            if canonical_key != sender_key {
+                stopped.extend(requests.remove(&canonical_key).unwrap_or_default());
+            }
+            stopped
+        };
+        if stopped.is_empty() {
+            return false;
+        }
+        for request in &stopped {
+            request.abort();
+        }
+        let dropped = self
+            .active_senders
+            .lock()
+            .await
+            .get_mut(&sender_key)
+            .map(|queue| std::mem::take(&mut queue.queued).len())
+            .unwrap_or(0);
+
+        let lang = self
+            .memory
+            .get_fact(&sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+
+        let mut report = Vec::new();
+        if let Some((project, phase, chain)) = &build {
+            if let Some((project_dir, chain)) = chain {
+                Gateway::save_chain_state(project_dir, chain).await;
+            }
+            report.push(build_stopped_message(&lang, project, phase));
+        }
+        for request in &stopped {
+            let elapsed = request.started.elapsed();
+            info!(
+                "[{}] stopped '{}' for {sender_key} after {}s",
+                incoming.channel,
+                request.label,
+                elapsed.as_secs()
+            );
+            let detail = match &build {
+                Some((project, phase, _)) => format!("build {project} at {phase}"),
+                None => format!("after {}", format_elapsed(elapsed)),
+            };
+            let _ = self
+                .audit
+                .log(&AuditEntry {
+                    channel: incoming.channel.clone(),
+                    sender_id: sender_id.clone(),
+                    sender_name: incoming.sender_name.clone(),
+                    input_text: format!("[STOP] {}", request.label),
+                    output_text: Some(format!("[cancelled] {detail}")),
+                    provider_used: Some(self.provider.name().to_string()),
+                    model: None,
+                    processing_ms: Some(elapsed.as_millis() as i64),
+                    status: AuditStatus::Cancelled,
+                    denial_reason: None,
+                })
+                .await;
+            if build.is_none() {
+                report.push(stopped_message(
+                    &lang,
+                    &request.label,
+                    &format_elapsed(elapsed),
+                ));
+            }
+        }
+        if dropped > 0 {
+            report.push(queue_dropped_message(&lang, dropped));
+        }
+        self.send_text(incoming, &report.join("\n")).await;
+        true
+    }
+}
+
+/// Whether `text` is `/stop` (with optional `@botname` suffix).
+fn is_stop_request(text: &str) -> bool {
+    let mut words = text.split_whitespace();
+    words.next().and_then(|cmd| cmd.split('@').next()) == Some("/stop") && words.next().is_none()
+}
+
+/// Quoted preview of a request's text for the stop report.
+fn request_label(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > 60 {
+        let cut: String = line.chars().take(60).collect();
+        format!("\"{cut}…\"")
+    } else {
+        format!("\"{line}\"")
+    }
+}
+
+/// Localized report for one stopped request.
+fn stopped_message(lang: &str, what: &str, elapsed: &str) -> String {
+    match lang {
+        "Spanish" => format!("Detenido: {what} (tras {elapsed})."),
+        "Portuguese" => format!("Parado: {what} (após {elapsed})."),
+        "French" => format!("Arrêté : {what} (après {elapsed})."),
+        "German" => format!("Gestoppt: {what} (nach {elapsed})."),
+        "Italian" => format!("Interrotto: {what} (dopo {elapsed})."),
+        "Dutch" => format!("Gestopt: {what} (na {elapsed})."),
+        "Russian" => format!("Остановлено: {what} (через {elapsed})."),
+        _ => format!("Stopped: {what} (after {elapsed})."),
+    }
+}
+
+/// Localized notice that queued messages were dropped along with the request.
+fn queue_dropped_message(lang: &str, n: usize) -> String {
+    match lang {
+        "Spanish" => format!("Mensajes en cola descartados: {n}."),
+        "Portuguese" => format!("Mensagens na fila descartadas: {n}."),
+        "French" => format!("Messages en attente ignorés : {n}."),
+        "German" => format!("Verworfene wartende Nachrichten: {n}."),
+        "Italian" => format!("Messaggi in coda scartati: {n}."),
+        "Dutch" => format!("Genegeerde wachtende berichten: {n}."),
+        "Russian" => format!("Отброшено сообщений в очереди: {n}."),
+        _ => format!("Dropped queued messages: {n}."),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> ActiveRequests {
+        Arc::new(Mutex::new(HashMap::new()))
+    }
+
+    #[test]
+    fn test_is_stop_request() {
+        assert!(is_stop_request("/stop"));
+        assert!(is_stop_request(" /stop@omega_bot "));
+        assert!(!is_stop_request("/stop now"));
+        assert!(!is_stop_request("stop"));
+        assert!(!is_stop_request("/stopwatch"));
+    }
+
+    #[test]
+    fn test_request_label_truncates_first_line() {
+        assert_eq!(request_label("fix the build\nthanks"), "\"fix the build\"");
+        let long = "x".repeat(80);
+        assert_eq!(request_label(&long), format!("\"{}…\"", "x".repeat(60)));
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_registers_and_clears() {
+        let requests = registry();
+        let out = run_cancellable(&requests, "telegram:1", None, "q".into(), async {
+            assert_eq!(requests.lock().unwrap()["telegram:1"].len(), 1);
+            7
+        })
+        .await;
+        assert_eq!(out, Some(7));
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_abort_stops_request_and_helpers() {
+        let requests = registry();
+        let helper = tokio::spawn(std::future::pending::<()>());
+        let helper_abort = helper.abort_handle();
+
+        let run = run_cancellable(
+            &requests,
+            "telegram:1",
+            None,
+            "q".into(),
+            std::future::pending::<()>(),
+        );
+        let stop = async {
+            tokio::task::yield_now().await;
+            let list = requests.lock().unwrap().remove("telegram:1").unwrap();
+            for mut request in list {
+                request.helpers.push(helper_abort.clone());
+                request.abort();
+            }
+        };
+        let (out, ()) = tokio::join!(run, stop);
+        assert_eq!(out, None);
+        assert!(helper.await.unwrap_err().is_cancelled());
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stop_messages_localized() {
+        assert_eq!(
+            stopped_message("English", "\"hi\"", "42s"),
+            "Stopped: \"hi\" (after 42s)."
+        );
+        assert!(stopped_message("Spanish", "\"hola\"", "3m 05s").starts_with("Detenido"));
+        assert!(queue_dropped_message("German", 2).contains('2'));
+    }
+}
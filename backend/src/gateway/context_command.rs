@@ -1,10 +1,12 @@
-//! `/context` command handler — shows the assembled system prompt sections.
+//! `/context` command handler — shows the assembled system prompt sections
+//! and how the sender's last request was fitted to its token budget.
 //!
 //! Intercepted in pipeline.rs (like `/setup` and `/google`) because it needs
 //! access to `Gateway` fields (`prompts`, `provider`, `projects`, etc.) to
 //! build the prompt.
 
 use omega_core::message::IncomingMessage;
+use omega_memory::ContextReport;
 use tracing::info;
 
 use super::Gateway;
@@ -25,7 +27,8 @@ impl Gateway {
             .flatten()
             .unwrap_or_else(|| "English".to_string());
 
-        let projects = &omega_skills::load_projects(&self.data_dir);
+        let tenant = self.tenant_scope(&incoming.sender_id).await;
+        let projects = &self.tenant_projects(&tenant);
 
         // Parse optional argument: `/context full` shows the raw prompt.
         let arg = incoming
@@ -38,7 +41,9 @@ impl Gateway {
         let show_full = arg == "full";
 
         // Build the prompt exactly as the pipeline would (all sections always injected).
-        let prompt = self.build_system_prompt(incoming, active_project, projects);
+        let prompt = self
+            .build_system_prompt(incoming, active_project, projects, &tenant)
+            .await;
 
         if show_full {
             // Send the raw prompt (split if needed for Telegram's 4096 char limit).
@@ -71,8 +76,13 @@ impl Gateway {
         let total_tokens = total_chars / 4;
 
         let sections_text = sections.join("\n  ");
+        let last_request = {
+            let reports = self.last_context.lock().unwrap();
+            let key = format!("{}:{}", incoming.channel, incoming.sender_id);
+            format_report(reports.get(&key), &lang)
+        };
         let response = format!(
-            "{}\n\n  {sections_text}\n\n{} ~{total_tokens} (~{total_chars} chars)\n\n{}",
+            "{}\n\n  {sections_text}\n\n{} ~{total_tokens} (~{total_chars} chars)\n\n{last_request}\n\n{}",
             i18n::t("context_header", &lang),
             i18n::t("context_total", &lang),
             i18n::t("context_tip", &lang),
@@ -87,6 +97,61 @@ impl Gateway {
     }
 }
 
+/// What the last request's context kept and left out, section by section.
+fn format_report(report: Option<&ContextReport>, lang: &str) -> String {
+    let header = i18n::t("context_last_request", lang);
+    let Some(report) = report else {
+        return format!("{header} {}", i18n::t("context_no_request", lang));
+    };
+    let budget = if report.budget == 0 {
+        i18n::t("context_unlimited", lang).to_string()
+    } else {
+        format!("~{}", report.budget)
+    };
+    let mut lines = vec![format!(
+        "{header} ~{} / {budget} tokens",
+        report.total_tokens()
+    )];
+    for section in &report.sections {
+        if section.included == 0 && section.dropped == 0 {
+            continue;
+        }
+        let state = if section.included == 0 {
+            i18n::t("context_off", lang)
+        } else {
+            i18n::t("context_on", lang)
+        };
+        let mut line = format!(
+            "[{state}] {}: {} (~{} tokens)",
+            section.name, section.included, section.tokens
+        );
+        if section.dropped > 0 || section.truncated > 0 {
+            let mut notes = Vec::new();
+            if section.dropped > 0 {
+                notes.push(format!(
+                    "{} {}",
+                    section.dropped,
+                    i18n::t("context_dropped", lang)
+                ));
+            }
+            if section.truncated > 0 {
+                notes.push(format!(
+                    "{} {}",
+                    section.truncated,
+                    i18n::t("context_cut", lang)
+                ));
+            }
+            line.push_str(&format!(
+                " \u{2014} {}, -{} tokens",
+                notes.join(", "),
+                section.dropped_tokens
+            ));
+        }
+        lines.push(line);
+    }
+    lines.join("\n  ")
+}
+
 /// Split a long message into chunks that fit within `max_chars`.
 fn split_message(text: &str, max_chars: usize) -> Vec<String> {
     if text.len() <= max_chars {
@@ -106,3 +171,55 @@ fn split_message(text: &str, max_chars: usize) -> Vec<String> {
     }
     chunks
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use omega_memory::SectionReport;
+
+    #[test]
+    fn test_format_report() {
+        assert_eq!(
+            format_report(None, "English"),
+            "Last request: No request yet since the last restart."
+        );
+
+        let report = ContextReport {
+            budget: 6000,
+            sections: vec![
+                SectionReport {
+                    name: "system",
+                    included: 1,
+                    tokens: 1200,
+                    ..Default::default()
+                },
+                SectionReport {
+                    name: "recall",
+                    ..Default::default()
+                },
+                SectionReport {
+                    name: "history",
+                    included: 12,
+                    truncated: 1,
+                    dropped: 8,
+                    tokens: 4500,
+                    dropped_tokens: 9100,
+                },
+                SectionReport {
+                    name: "outcomes",
+                    dropped: 15,
+                    dropped_tokens: 600,
+                    ..Default::default()
+                },
+            ],
+        };
+        let text = format_report(Some(&report), "English");
+        assert_eq!(
+            text,
+            "Last request: ~5700 / ~6000 tokens\n  \
+             [on] system: 1 (~1200 tokens)\n  \
+             [on] history: 12 (~4500 tokens) \u{2014} 8 left out, 1 cut, -9100 tokens\n  \
+             [off] outcomes: 0 (~0 tokens) \u{2014} 15 left out, -600 tokens"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! `/export` — send the user everything Omega remembers about them.
+//!
+//! The archive is the same JSON `omega export` writes, so it can be loaded
+//! on another server with `omega import`. It is written to the tenant's
+//! `exports/` dir, sent as a document and removed again once delivered;
+//! archives too large to send stay on disk and the user gets the path.
+
+use omega_core::config::DataDir;
+use omega_core::message::IncomingMessage;
+use tracing::{error, info, warn};
+
+use super::Gateway;
+use crate::i18n;
+use crate::memory_export::export_file_name;
+
+impl Gateway {
+    /// Handle `/export`.
+    pub(super) async fn handle_export_command(&self, incoming: &IncomingMessage) {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+
+        let export = match self.memory.export_sender(&incoming.sender_id).await {
+            Ok(export) => export,
+            Err(e) => {
+                error!("export: failed for {}: {e}", incoming.sender_id);
+                self.send_text(incoming, &format!("Error: {e}")).await;
+                return;
+            }
+        };
+        let json = match serde_json::to_vec_pretty(&export) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("export: failed to serialize {}: {e}", incoming.sender_id);
+                self.send_text(incoming, &format!("Error: {e}")).await;
+                return;
+            }
+        };
+
+        let tenant = self.tenant_scope(&incoming.sender_id).await;
+        let dir = DataDir::new(&self.tenant_data_dir(&tenant)).exports();
+        let path = dir.join(export_file_name(&incoming.sender_id, chrono::Utc::now()));
+        if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+            warn!("failed to create {}: {e}", dir.display());
+        }
+        if let Err(e) = tokio::fs::write(&path, &json).await {
+            error!("export: failed to write {}: {e}", path.display());
+            self.send_text(incoming, &format!("Error: {e}")).await;
+            return;
+        }
+        info!(
+            "exported memory of {} to {}",
+            incoming.sender_id,
+            path.display()
+        );
+
+        let caption = i18n::export_caption(
+            &lang,
+            export.facts.len(),
+            export.conversations.len(),
+            export.tasks.len(),
+        );
+        if self.send_artifact(incoming, &lang, &path, &caption).await {
+            let _ = tokio::fs::remove_file(&path).await;
+        }
+    }
+}
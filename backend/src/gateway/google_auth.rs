@@ -10,6 +10,7 @@
 use std::path::PathBuf;
 
 use omega_core::config::shellexpand;
+use omega_core::crypto::GOOGLE_SECRET;
 use omega_core::message::IncomingMessage;
 use omega_memory::audit::{AuditEntry, AuditStatus};
 use tracing::warn;
@@ -102,7 +103,11 @@ impl Gateway {
         let stores_path = PathBuf::from(shellexpand(&self.data_dir))
             .join("stores")
             .join("google.json");
-        let google_exists = tokio::fs::try_exists(&stores_path).await.unwrap_or(false);
+        let google_exists = tokio::fs::try_exists(&stores_path).await.unwrap_or(false)
+            || self
+                .secrets
+                .as_ref()
+                .is_some_and(|s| s.get(GOOGLE_SECRET).ok().flatten().is_some());
 
         let base_msg = google_step_project_id_message(&user_lang, google_exists);
         let msg = format!("{channel_notice}{base_msg}");
@@ -370,6 +375,7 @@ impl Gateway {
     ) {
         match write_google_credentials(
             &self.data_dir,
+            self.secrets.as_deref(),
             client_id,
             client_secret,
             refresh_token,
@@ -6,6 +6,7 @@
 use std::path::PathBuf;
 
 use omega_core::config::shellexpand;
+use omega_core::crypto::{SecretStore, GOOGLE_SECRET};
 use omega_memory::Store;
 
 /// Try to extract `client_id` and `client_secret` from a Google credentials JSON blob.
@@ -51,8 +52,12 @@ pub(super) fn parse_google_step(fact_value: &str) -> (&str, &str) {
 
 /// Write the credential JSON file to `<data_dir>/stores/google.json`.
 /// Creates the `stores/` directory if missing.
+///
+/// With encryption on (`secrets` is `Some`), the document goes into the
+/// secrets store instead and any plaintext `google.json` is removed.
 pub(super) async fn write_google_credentials(
     data_dir: &str,
+    secrets: Option<&SecretStore>,
     client_id: &str,
     client_secret: &str,
     refresh_token: &str,
@@ -77,6 +82,13 @@ pub(super) async fn write_google_credentials(
         serde_json::to_string_pretty(&json).map_err(|e| format!("JSON serialize: {e}"))?;
 
     let path = stores_dir.join("google.json");
+    if let Some(secrets) = secrets {
+        secrets
+            .set(GOOGLE_SECRET, &json_str)
+            .map_err(|e| format!("secrets store: {e}"))?;
+        let _ = tokio::fs::remove_file(&path).await;
+        return Ok(());
+    }
     tokio::fs::write(&path, json_str.as_bytes())
         .await
         .map_err(|e| format!("write google.json: {e}"))?;
@@ -205,7 +217,10 @@ mod tests {
         let config = MemoryConfig {
             backend: "sqlite".to_string(),
             db_path,
+            url: String::new(),
             max_context_messages: 10,
+            context_budget: Default::default(),
+            maintenance: Default::default(),
         };
         Store::new(&config).await.unwrap()
     }
@@ -326,7 +341,8 @@ mod tests {
         let data_dir = dir.to_string_lossy().to_string();
 
         let result =
-            write_google_credentials(&data_dir, "cid", "csec", "rtok", "test@example.com").await;
+            write_google_credentials(&data_dir, None, "cid", "csec", "rtok", "test@example.com")
+                .await;
         assert!(result.is_ok(), "write_google_credentials must succeed");
 
         let path = dir.join("stores").join("google.json");
@@ -355,7 +371,7 @@ mod tests {
         let _ = std::fs::create_dir_all(&dir);
         let data_dir = dir.to_string_lossy().to_string();
 
-        write_google_credentials(&data_dir, "a", "b", "c", "d@e.com")
+        write_google_credentials(&data_dir, None, "a", "b", "c", "d@e.com")
             .await
             .unwrap();
 
@@ -366,6 +382,32 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[tokio::test]
+    async fn test_write_google_credentials_to_secrets_store() {
+        let dir = std::env::temp_dir().join(format!(
+            "__omega_gauth_secrets_{}__",
+            TEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let stale = dir.join("stores").join("google.json");
+        std::fs::create_dir_all(stale.parent().unwrap()).unwrap();
+        std::fs::write(&stale, "{}").unwrap();
+        let data_dir = dir.to_string_lossy().to_string();
+        let key = omega_core::crypto::generate_key().unwrap();
+        let cipher = std::sync::Arc::new(omega_core::crypto::Cipher::new(&key).unwrap());
+        let secrets = SecretStore::new(dir.join("secrets.enc"), cipher);
+
+        write_google_credentials(&data_dir, Some(&secrets), "cid", "csec", "rtok", "a@b.com")
+            .await
+            .unwrap();
+
+        assert!(!stale.exists(), "plaintext google.json must be removed");
+        let stored = secrets.get(GOOGLE_SECRET).unwrap().unwrap();
+        let json: serde_json::Value = serde_json::from_str(&stored).unwrap();
+        assert_eq!(json["refresh_token"], "rtok");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     // ===================================================================
     // Session cleanup
     // ===================================================================
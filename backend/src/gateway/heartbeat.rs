@@ -1,24 +1,30 @@
 //! Periodic heartbeat check-in loop.
 //!
+//! Every user has their own heartbeat (checklist, pulse, active hours and
+//! delivery target) stored in `heartbeat_settings`. The loop sleeps until the
+//! earliest due boundary across all users and runs each due user in its own task.
 //! A fast Sonnet classification groups related checklist items by domain.
 //! Each group gets its own focused Opus session **in parallel**.
 //! Falls back to a single call when all items belong to the same domain.
-//! After global heartbeat, runs project-specific heartbeats for all projects with a HEARTBEAT.md.
 
+use super::heartbeat_cycle::HeartbeatRuntime;
 use super::heartbeat_helpers::{
-    build_enrichment, build_system_prompt, process_heartbeat_markers, send_heartbeat_result,
+    migrate_legacy_checklists, migrate_legacy_suppress_file, process_heartbeat_markers,
+    record_item_checks, seed_owner_heartbeat,
 };
+use super::tenants::resolve_tenant_scope;
 use super::Gateway;
 use crate::markers::*;
 use omega_core::{
-    config::{HeartbeatConfig, Prompts},
+    config::{ApprovalConfig, HeartbeatConfig, Prompts, RolesConfig, TenantsConfig, TrustConfig},
     context::Context,
+    structured::{complete_structured, ResponseSchema},
     traits::{Channel, Provider},
 };
-use omega_memory::{audit::AuditLogger, Store};
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use omega_memory::{audit::AuditLogger, HeartbeatItem, HeartbeatSettings, Store};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tokio::sync::Notify;
 use tracing::{error, info, warn};
@@ -32,6 +38,11 @@ pub(crate) fn next_clock_boundary(current_minute: u64, interval: u64) -> u64 {
     ((current_minute / interval) + 1) * interval
 }
 
+/// Whether `current_minute` (0..1439) falls on a clock-aligned boundary.
+pub(crate) fn is_clock_boundary(current_minute: u64, interval: u64) -> bool {
+    current_minute.is_multiple_of(interval.max(1))
+}
+
 /// Compute seconds until the next clock-aligned boundary from a local timestamp.
 fn secs_until_boundary(now: &chrono::DateTime<chrono::Local>, interval_mins: u64) -> u64 {
     use chrono::Timelike;
@@ -62,12 +73,32 @@ fn secs_until_active_start(active_start: &str) -> u64 {
     }
 }
 
+/// Whether the user's active-hours window is open (always, when unset).
+fn within_user_hours(settings: &HeartbeatSettings) -> bool {
+    settings.active_start.is_empty()
+        || settings.active_end.is_empty()
+        || is_within_active_hours(&settings.active_start, &settings.active_end)
+}
+
+/// Seconds until this user's next heartbeat could fire.
+///
+/// Quiet-hours jump-ahead: during quiet hours, waits until `active_start`
+/// instead of waking at every boundary just to check and skip.
+fn secs_until_user_due(now: &chrono::DateTime<chrono::Local>, settings: &HeartbeatSettings) -> u64 {
+    if within_user_hours(settings) {
+        secs_until_boundary(now, settings.interval_minutes)
+    } else {
+        secs_until_active_start(&settings.active_start)
+    }
+}
+
 impl Gateway {
-    /// Background task: periodic heartbeat check-in.
+    /// Background task: periodic heartbeat check-ins for every user.
     ///
-    /// Skips the provider call entirely when no checklist is configured.
-    /// After the global heartbeat, runs project-specific heartbeats for
-    /// all projects that have their own HEARTBEAT.md (filesystem-based discovery).
+    /// Seeds the owner's settings from `[heartbeat]` on first start, then
+    /// sleeps until the next due boundary across all enabled users. `notify`
+    /// wakes the loop early when a user changes their heartbeat. A user whose
+    /// previous cycle is still running skips the boundary.
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn heartbeat_loop(
         provider: Arc<dyn Provider>,
@@ -75,7 +106,6 @@ impl Gateway {
         config: HeartbeatConfig,
         prompts: Prompts,
         memory: Store,
-        interval: Arc<AtomicU64>,
         notify: Arc<Notify>,
         model_complex: String,
         model_fast: String,
@@ -83,293 +113,186 @@ impl Gateway {
         audit: AuditLogger,
         provider_name: String,
         data_dir: String,
-        config_path: String,
+        trust: TrustConfig,
+        approval: ApprovalConfig,
+        tenants: TenantsConfig,
+        roles: RolesConfig,
     ) {
+        let owner_id = memory
+            .resolve_sender_id(&config.reply_target)
+            .await
+            .unwrap_or_else(|_| config.reply_target.clone());
+        seed_owner_heartbeat(&memory, &config, &owner_id, &data_dir).await;
+        migrate_legacy_checklists(&memory).await;
+        migrate_legacy_suppress_file(&data_dir, &owner_id);
+
+        let runtime = HeartbeatRuntime {
+            provider,
+            channels,
+            prompts,
+            memory,
+            notify: notify.clone(),
+            model_complex,
+            model_fast,
+            skills,
+            audit,
+            provider_name,
+            data_dir,
+            trust,
+            approval,
+            tenants,
+            roles,
+            owner_id,
+        };
+        let in_flight: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+        let mut last_fired: HashMap<String, String> = HashMap::new();
+
         loop {
-            let mins = interval.load(Ordering::Relaxed);
-
-            // Quiet-hours jump-ahead: sleep directly to active_start instead
-            // of waking every boundary just to check and skip.
-            if !config.active_start.is_empty()
-                && !config.active_end.is_empty()
-                && !is_within_active_hours(&config.active_start, &config.active_end)
-            {
-                let secs = secs_until_active_start(&config.active_start);
-                info!(
-                    "heartbeat: quiet hours, sleeping until {} (~{}m)",
-                    config.active_start,
-                    secs / 60
-                );
-                tokio::select! {
-                    _ = tokio::time::sleep(std::time::Duration::from_secs(secs)) => {}
-                    _ = notify.notified() => {
-                        info!("heartbeat: interval changed during quiet hours, recalculating");
-                    }
+            let users = match runtime.memory.get_enabled_heartbeats().await {
+                Ok(u) => u,
+                Err(e) => {
+                    error!("heartbeat: failed to load settings: {e}");
+                    Vec::new()
                 }
-                // After waking (timer or notify), re-check from the top.
-                continue;
-            }
+            };
 
             // Clock-aligned sleep: fire at clean boundaries (e.g. :00, :30).
             let now = chrono::Local::now();
-            let wait_secs = secs_until_boundary(&now, mins);
-            let target_boundary = {
-                use chrono::Timelike;
-                let cm = u64::from(now.hour()) * 60 + u64::from(now.minute());
-                next_clock_boundary(cm, mins)
-            };
+            let wait_secs = users
+                .iter()
+                .map(|u| secs_until_user_due(&now, u))
+                .min()
+                .unwrap_or(3600);
             let interrupted = tokio::select! {
                 _ = tokio::time::sleep(std::time::Duration::from_secs(wait_secs)) => false,
                 _ = notify.notified() => true,
             };
-
             if interrupted {
-                info!("heartbeat: interval changed, recalculating next boundary");
+                info!("heartbeat: settings changed, recalculating next boundary");
                 continue;
             }
 
-            // Wall-clock re-snap: if system sleep caused us to overshoot the
-            // target boundary, recalculate from the actual wake-up time.
+            // Wall-clock check: if system sleep caused us to overshoot, no
+            // boundary matches the actual wake-up minute and we re-align.
             let actual = chrono::Local::now();
             let actual_minute = {
                 use chrono::Timelike;
                 u64::from(actual.hour()) * 60 + u64::from(actual.minute())
             };
-            // Allow ±2 minutes tolerance for normal jitter.
-            // Normalize target_boundary for midnight wrap (1440 → 0).
-            let target_normalized = target_boundary % 1440;
-            let on_target = actual_minute >= target_normalized.saturating_sub(2)
-                && actual_minute <= target_normalized.saturating_add(2);
-            if !on_target {
-                info!(
-                    "heartbeat: system sleep detected (target :{:02}, actual :{:02}), re-aligning",
-                    target_boundary % 60,
-                    actual_minute % 60
-                );
-                // Don't fire now — loop back to recalculate from current time.
-                continue;
-            }
-
-            // Re-check active hours after sleep (boundary might land outside).
-            if !config.active_start.is_empty()
-                && !config.active_end.is_empty()
-                && !is_within_active_hours(&config.active_start, &config.active_end)
-            {
-                continue;
-            }
-
-            let cycle_start = chrono::Local::now();
-            info!(
-                "heartbeat: cycle started at {}",
-                cycle_start.format("%H:%M")
-            );
-
-            let sender_id = &config.reply_target;
-            let channel_name = &config.channel;
-
-            // --- Discover ALL projects with a HEARTBEAT.md file ---
-            // Filesystem-based: heartbeats run regardless of active_project state,
-            // so `/project off` only exits conversation context, not monitoring.
-            let projects_with_heartbeat: Vec<String> = {
-                let dir = format!("{}/projects", omega_core::config::shellexpand(&data_dir));
-                let mut names: Vec<String> = std::fs::read_dir(&dir)
-                    .into_iter()
-                    .flatten()
-                    .filter_map(|e| e.ok())
-                    .filter(|e| e.path().is_dir())
-                    .filter(|e| !e.path().join(".disabled").exists())
-                    .filter_map(|e| {
-                        let name = e.file_name().to_string_lossy().to_string();
-                        if read_project_heartbeat_file(&name).is_some() {
-                            Some(name)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                names.sort(); // deterministic order
-                names
-            };
-
-            // --- Global heartbeat ---
-            if let Some(checklist) = read_heartbeat_file()
-                .and_then(|c| strip_project_sections(&c, &projects_with_heartbeat))
-                .and_then(|c| filter_suppressed_sections(&c, None))
-            {
-                let enrichment = build_enrichment(&memory, None).await;
-                let system = build_system_prompt(&prompts, None, None);
-
-                let groups = classify_heartbeat_groups(&*provider, &model_fast, &checklist).await;
-
-                match groups {
-                    None => {
-                        info!("heartbeat: DIRECT (single call)");
-                        let result = execute_heartbeat_group(
-                            provider.clone(),
-                            model_complex.clone(),
-                            checklist.clone(),
-                            prompts.heartbeat_checklist.clone(),
-                            enrichment,
-                            system,
-                            skills.clone(),
-                            memory.clone(),
-                            sender_id.clone(),
-                            channel_name.clone(),
-                            interval.clone(),
-                            String::new(),
-                            config_path.clone(),
-                            provider_name.clone(),
-                        )
-                        .await;
-                        send_heartbeat_result(
-                            result,
-                            channel_name,
-                            sender_id,
-                            &channels,
-                            &config,
-                            &audit,
-                            &provider_name,
-                            &model_complex,
-                        )
-                        .await;
-                    }
-                    Some(groups) => {
-                        let group_count = groups.len();
-                        info!("heartbeat: classified into {group_count} groups");
-
-                        let mut handles = Vec::new();
-                        for group in groups {
-                            handles.push(tokio::spawn(execute_heartbeat_group(
-                                provider.clone(),
-                                model_complex.clone(),
-                                group,
-                                prompts.heartbeat_checklist.clone(),
-                                enrichment.clone(),
-                                system.clone(),
-                                skills.clone(),
-                                memory.clone(),
-                                sender_id.clone(),
-                                channel_name.clone(),
-                                interval.clone(),
-                                String::new(),
-                                config_path.clone(),
-                                provider_name.clone(),
-                            )));
-                        }
-
-                        let mut texts = Vec::new();
-                        let mut max_ms: i64 = 0;
-                        for (i, handle) in handles.into_iter().enumerate() {
-                            match handle.await {
-                                Ok(Some((text, ms))) => {
-                                    texts.push(text);
-                                    if ms > max_ms {
-                                        max_ms = ms;
-                                    }
-                                }
-                                Ok(None) => info!("heartbeat: group {} OK", i + 1),
-                                Err(e) => error!("heartbeat: group {} panicked: {e}", i + 1),
-                            }
-                        }
-
-                        if texts.is_empty() {
-                            info!("heartbeat: all {group_count} groups OK");
-                        } else {
-                            let combined = texts.join("\n\n---\n\n");
-                            send_heartbeat_result(
-                                Some((combined, max_ms)),
-                                channel_name,
-                                sender_id,
-                                &channels,
-                                &config,
-                                &audit,
-                                &provider_name,
-                                &model_complex,
-                            )
-                            .await;
-                        }
-                    }
+            let stamp = actual.format("%Y-%m-%d %H:%M").to_string();
+
+            // Reload: settings may have changed while we slept.
+            let users = runtime
+                .memory
+                .get_enabled_heartbeats()
+                .await
+                .unwrap_or_default();
+            for settings in users {
+                if !is_clock_boundary(actual_minute, settings.interval_minutes)
+                    || !within_user_hours(&settings)
+                    || last_fired.get(&settings.sender_id) == Some(&stamp)
+                {
+                    continue;
                 }
-            } else {
-                info!("heartbeat: no global checklist configured, skipping");
-            }
-
-            // --- Project heartbeats ---
-            for project_name in &projects_with_heartbeat {
-                // Check if this project has its own HEARTBEAT.md.
-                let project_checklist = match read_project_heartbeat_file(project_name)
-                    .and_then(|c| filter_suppressed_sections(&c, Some(project_name)))
+                if !in_flight
+                    .lock()
+                    .expect("heartbeat in-flight lock")
+                    .insert(settings.sender_id.clone())
                 {
-                    Some(cl) => cl,
-                    None => continue,
-                };
-
-                info!("heartbeat: running project heartbeat for '{project_name}'");
-                let enrichment = build_enrichment(&memory, Some(project_name)).await;
-                let system = build_system_prompt(&prompts, Some(project_name), Some(&data_dir));
-
-                // Project heartbeats always run as a single call (simpler, focused).
-                let result = execute_heartbeat_group(
-                    provider.clone(),
-                    model_complex.clone(),
-                    project_checklist,
-                    prompts.heartbeat_checklist.clone(),
-                    enrichment,
-                    system,
-                    skills.clone(),
-                    memory.clone(),
-                    sender_id.clone(),
-                    channel_name.clone(),
-                    interval.clone(),
-                    project_name.clone(),
-                    config_path.clone(),
-                    provider_name.clone(),
-                )
-                .await;
-                send_heartbeat_result(
-                    result,
-                    channel_name,
-                    sender_id,
-                    &channels,
-                    &config,
-                    &audit,
-                    &provider_name,
-                    &model_complex,
-                )
-                .await;
+                    info!(
+                        "heartbeat: previous cycle for {} still running, skipping",
+                        settings.sender_id
+                    );
+                    continue;
+                }
+                last_fired.insert(settings.sender_id.clone(), stamp.clone());
+
+                let rt = runtime.clone();
+                let in_flight = in_flight.clone();
+                tokio::spawn(async move {
+                    let sender_id = settings.sender_id.clone();
+                    let cycle_start = chrono::Local::now();
+                    info!(
+                        "heartbeat: cycle for {sender_id} started at {}",
+                        cycle_start.format("%H:%M")
+                    );
+                    rt.run_user_heartbeat(settings).await;
+                    let cycle_secs = chrono::Local::now()
+                        .signed_duration_since(cycle_start)
+                        .num_seconds();
+                    info!("heartbeat: cycle for {sender_id} completed in {cycle_secs}s");
+                    in_flight
+                        .lock()
+                        .expect("heartbeat in-flight lock")
+                        .remove(&sender_id);
+                });
             }
-
-            let cycle_secs = chrono::Local::now()
-                .signed_duration_since(cycle_start)
-                .num_seconds();
-            info!("heartbeat: cycle completed in {cycle_secs}s");
         }
     }
 }
 
 // --- Heartbeat helper functions ---
 
+/// Structured reply of the heartbeat classification call.
+#[derive(Debug, Deserialize)]
+struct HeartbeatPlan {
+    /// All items are related (or there are 3 or fewer): run one call.
+    direct: bool,
+    /// One entry per group: the group's items, comma-separated.
+    #[serde(default)]
+    groups: Vec<String>,
+}
+
+impl HeartbeatPlan {
+    fn schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "heartbeat_plan",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "direct": {"type": "boolean"},
+                    "groups": {"type": "array", "items": {"type": "string"}}
+                },
+                "required": ["direct", "groups"]
+            }),
+        )
+    }
+
+    /// The groups to run in parallel, or `None` for a single call
+    /// (DIRECT, or fewer than 2 non-empty groups).
+    fn into_groups(self) -> Option<Vec<String>> {
+        if self.direct {
+            return None;
+        }
+        let groups: Vec<String> = self
+            .groups
+            .into_iter()
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty())
+            .collect();
+        (groups.len() >= 2).then_some(groups)
+    }
+}
+
 /// Fast Sonnet classification: group heartbeat items by domain.
 ///
 /// Returns `None` for DIRECT (all items related, or ≤3 items).
 /// Returns `Some(groups)` when items span different domains.
-async fn classify_heartbeat_groups(
+pub(super) async fn classify_heartbeat_groups(
     provider: &dyn Provider,
     model_fast: &str,
     checklist: &str,
 ) -> Option<Vec<String>> {
     let prompt = format!(
-        "You are a heartbeat checklist organizer. Do NOT use any tools — respond with text only.\n\n\
+        "You are a heartbeat checklist organizer. Do NOT use any tools.\n\n\
          Given this checklist, decide how to group items for focused execution.\n\n\
-         Respond DIRECT if:\n\
+         Set direct to true if:\n\
          - All items are closely related (same domain, same tools)\n\
          - There are 3 or fewer items total\n\n\
-         Otherwise, group related items together. Each group becomes one focused \
-         execution session. Items in the same domain (e.g., all trading tasks, all \
-         personal reminders, all system monitoring) belong in the same group.\n\n\
-         Format each group as a single numbered line:\n\
-         1. First group item, second group item, third group item\n\
-         2. Fourth group item, fifth group item\n\n\
+         Otherwise set direct to false and group related items together. Each group \
+         becomes one focused execution session. Items in the same domain (e.g., all \
+         trading tasks, all personal reminders, all system monitoring) belong in the \
+         same group. Write each group as one string listing its items, comma-separated.\n\n\
          Checklist:\n{checklist}"
     );
 
@@ -377,9 +300,10 @@ async fn classify_heartbeat_groups(
     ctx.max_turns = Some(25);
     ctx.allowed_tools = Some(vec![]);
     ctx.model = Some(model_fast.to_string());
+    ctx.response_schema = Some(HeartbeatPlan::schema());
 
-    match provider.complete(&ctx).await {
-        Ok(resp) => parse_plan_response(&resp.text),
+    match complete_structured::<HeartbeatPlan>(provider, &ctx).await {
+        Ok(plan) => plan.into_groups(),
         Err(e) => {
             warn!("heartbeat classification failed, falling back to single call: {e}");
             None
@@ -389,44 +313,63 @@ async fn classify_heartbeat_groups(
 
 /// Execute a single heartbeat group via Opus.
 ///
+/// `items` are the structured items rendered into `group_items`; their
+/// `HEARTBEAT_CHECKED:` results are recorded. When every item in the group is
+/// alert-on-change and each reported an unchanged result, the alert is dropped.
+///
 /// Returns `None` if HEARTBEAT_OK (nothing to report).
 /// Returns `Some((text, elapsed_ms))` if content should be sent to the user.
-#[allow(clippy::too_many_arguments)]
-async fn execute_heartbeat_group(
-    provider: Arc<dyn Provider>,
-    model_complex: String,
+pub(super) async fn execute_heartbeat_group(
+    rt: HeartbeatRuntime,
+    settings: HeartbeatSettings,
     group_items: String,
-    heartbeat_template: String,
+    items: Vec<HeartbeatItem>,
     enrichment: String,
     system_prompt: String,
-    skills: Vec<omega_skills::Skill>,
-    memory: Store,
-    sender_id: String,
-    channel_name: String,
-    interval: Arc<AtomicU64>,
     project: String,
-    config_path: String,
-    provider_name: String,
 ) -> Option<(String, i64)> {
     // Enrichment (facts, lessons, outcomes) goes BEFORE the checklist so learned
     // behavioral rules frame the AI's approach before it encounters detailed instructions.
     let mut prompt = enrichment;
     prompt.push('\n');
-    prompt.push_str(&heartbeat_template.replace("{checklist}", &group_items));
+    prompt.push_str(
+        &rt.prompts
+            .heartbeat_checklist
+            .replace("{checklist}", &group_items),
+    );
+    if !items.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(HEARTBEAT_ITEM_PROTOCOL);
+    }
 
     let mut ctx = Context::new(&prompt);
     ctx.system_prompt = system_prompt;
-    ctx.model = Some(model_complex);
+    ctx.model = Some(rt.model_complex.clone());
+    ctx.approval = super::approval::background_approval_gate(&rt.approval);
+    // The heartbeat runs as its user: their tenant workspace and role's tools.
+    let scope = resolve_tenant_scope(
+        &rt.tenants,
+        &rt.roles,
+        &rt.memory,
+        &rt.data_dir,
+        &settings.sender_id,
+    )
+    .await;
+    ctx.workspace = scope.workspace();
+    if let Some(ref tools) = scope.permissions.tools {
+        ctx.allowed_tools = Some(tools.clone());
+    }
+    ctx.denied_tools = scope.denied_tools();
     // Claude Code CLI: always activate all MCP servers (cheap config write).
     // HTTP providers: keyword-based trigger matching (real per-message cost).
-    ctx.mcp_servers = if provider_name == "claude-code" {
-        omega_skills::collect_all_mcp_servers(&skills)
+    ctx.mcp_servers = if rt.provider_name == "claude-code" {
+        omega_skills::collect_all_mcp_servers(&rt.skills)
     } else {
-        omega_skills::match_skill_triggers(&skills, &group_items)
+        omega_skills::match_skill_triggers(&rt.skills, &group_items)
     };
 
     let started = Instant::now();
-    let resp = match provider.complete(&ctx).await {
+    let resp = match rt.provider.complete(&ctx).await {
         Ok(r) => r,
         Err(e) => {
             error!("heartbeat: group execution failed: {e}");
@@ -434,15 +377,30 @@ async fn execute_heartbeat_group(
         }
     };
     let elapsed_ms = started.elapsed().as_millis() as i64;
-
+    rt.mark_checked(&items).await;
+
+    let reported: Vec<String> = extract_heartbeat_checked_markers(&resp.text)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    let changed = record_item_checks(&rt.memory, &settings.sender_id, &resp.text).await;
+    let unchanged_only = only_unchanged_on_change_items(&items, &reported, &changed);
+
+    let mut text = strip_heartbeat_checked_markers(&resp.text);
+    super::shared_markers::gate_untrusted_actions(
+        &mut text,
+        &rt.trust,
+        &resp.metadata.untrusted_sources,
+        false,
+        "heartbeat",
+    );
     let text = process_heartbeat_markers(
-        resp.text,
-        &memory,
-        &sender_id,
-        &channel_name,
-        &interval,
+        text,
+        &rt.memory,
+        &rt.data_dir,
+        &settings,
+        &rt.notify,
         &project,
-        &config_path,
     )
     .await;
 
@@ -454,6 +412,9 @@ async fn execute_heartbeat_group(
     let without_ok = cleaned.replace("HEARTBEAT_OK", "");
     if without_ok.trim().is_empty() {
         None
+    } else if unchanged_only {
+        info!("heartbeat: all items alert on change and none changed, suppressing alert");
+        None
     } else {
         let text = text
             .replace("**HEARTBEAT_OK**", "")
@@ -462,10 +423,63 @@ async fn execute_heartbeat_group(
     }
 }
 
+/// Whether a group consists only of alert-on-change items that all reported
+/// an unchanged result. Unreported items keep the alert (fail open).
+fn only_unchanged_on_change_items(
+    items: &[HeartbeatItem],
+    reported: &[String],
+    changed: &[String],
+) -> bool {
+    let matches = |ids: &[String], item: &HeartbeatItem| {
+        ids.iter()
+            .any(|id| id.len() >= 4 && item.id.starts_with(id.as_str()))
+    };
+    !items.is_empty()
+        && items
+            .iter()
+            .all(|i| i.alert_on_change && matches(reported, i) && !matches(changed, i))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // --- Classification plan ---
+
+    fn plan(json: &str) -> Option<Vec<String>> {
+        omega_core::structured::parse::<HeartbeatPlan>(json)
+            .unwrap()
+            .into_groups()
+    }
+
+    #[test]
+    fn test_heartbeat_plan_direct() {
+        assert!(plan(r#"{"direct": true, "groups": ["a, b", "c"]}"#).is_none());
+        assert!(plan(r#"{"direct": true}"#).is_none());
+    }
+
+    #[test]
+    fn test_heartbeat_plan_groups() {
+        let groups =
+            plan(r#"{"direct": false, "groups": [" Check BTC, Check ETH ", "Water plants"]}"#)
+                .unwrap();
+        assert_eq!(groups, vec!["Check BTC, Check ETH", "Water plants"]);
+    }
+
+    #[test]
+    fn test_heartbeat_plan_single_group_runs_direct() {
+        assert!(plan(r#"{"direct": false, "groups": ["Everything", " "]}"#).is_none());
+    }
+
+    #[test]
+    fn test_heartbeat_plan_schema_requires_fields() {
+        let schema = HeartbeatPlan::schema();
+        assert_eq!(
+            schema.schema["required"],
+            serde_json::json!(["direct", "groups"])
+        );
+    }
+
     // --- REQ-HB-005: Unit tests for next_clock_boundary ---
 
     #[test]
@@ -533,6 +547,100 @@ mod tests {
         assert_eq!(target % 1440, 0); // normalizes to 0 for comparison
     }
 
+    #[test]
+    fn test_is_clock_boundary() {
+        assert!(is_clock_boundary(600, 60));
+        assert!(!is_clock_boundary(601, 60));
+        assert!(is_clock_boundary(90, 45));
+        assert!(is_clock_boundary(0, 45), "midnight is always a boundary");
+        assert!(is_clock_boundary(17, 1));
+    }
+
+    // --- Per-user scheduling ---
+
+    fn user(interval: u64, start: &str, end: &str) -> HeartbeatSettings {
+        HeartbeatSettings {
+            sender_id: "u1".to_string(),
+            channel: "telegram".to_string(),
+            reply_target: "u1".to_string(),
+            enabled: true,
+            interval_minutes: interval,
+            active_start: start.to_string(),
+            active_end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_secs_until_user_due_always_active() {
+        let now = chrono::Local::now();
+        let secs = secs_until_user_due(&now, &user(30, "", ""));
+        assert!(secs > 0 && secs <= 30 * 60);
+    }
+
+    #[test]
+    fn test_secs_until_user_due_quiet_hours_jump() {
+        // An empty window (start == end) is never active: wait for active_start.
+        let now = chrono::Local::now();
+        let settings = user(1, "00:00", "00:00");
+        assert!(!within_user_hours(&settings));
+        let secs = secs_until_user_due(&now, &settings);
+        assert!(secs > 0 && secs <= 24 * 3600);
+    }
+
+    // --- Alert-on-change gate ---
+
+    fn on_change_item(id: &str, alert_on_change: bool) -> HeartbeatItem {
+        HeartbeatItem {
+            id: id.to_string(),
+            sender_id: "u1".to_string(),
+            project: String::new(),
+            description: "Disk usage".to_string(),
+            cadence: String::new(),
+            alert_on_change,
+            last_checked_at: None,
+            last_result: None,
+            created_at: "2026-03-01 08:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_only_unchanged_on_change_items() {
+        let ids = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let items = vec![
+            on_change_item("aaaa1111-x", true),
+            on_change_item("bbbb2222-x", true),
+        ];
+        // Both reported, none changed → suppress.
+        assert!(only_unchanged_on_change_items(
+            &items,
+            &ids(&["aaaa1111", "bbbb2222"]),
+            &[]
+        ));
+        // One changed → alert.
+        assert!(!only_unchanged_on_change_items(
+            &items,
+            &ids(&["aaaa1111", "bbbb2222"]),
+            &ids(&["bbbb2222"])
+        ));
+        // One unreported → alert (fail open).
+        assert!(!only_unchanged_on_change_items(
+            &items,
+            &ids(&["aaaa1111"]),
+            &[]
+        ));
+        // A regular item in the group → alert.
+        let mixed = vec![
+            on_change_item("aaaa1111-x", true),
+            on_change_item("cccc3333-x", false),
+        ];
+        assert!(!only_unchanged_on_change_items(
+            &mixed,
+            &ids(&["aaaa1111", "cccc3333"]),
+            &[]
+        ));
+        assert!(!only_unchanged_on_change_items(&[], &[], &[]));
+    }
+
     // --- secs_until_active_start ---
 
     #[test]
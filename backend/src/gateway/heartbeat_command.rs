@@ -0,0 +1,245 @@
+//! `/heartbeat` command handler — each user manages their own heartbeat.
+//!
+//! Intercepted in pipeline.rs (like `/context`) because changes must wake the
+//! heartbeat loop so the new schedule applies immediately.
+
+use omega_core::message::IncomingMessage;
+use tracing::error;
+
+use super::heartbeat_helpers::{new_heartbeat_settings, update_user_heartbeat};
+use super::Gateway;
+use crate::commands;
+use crate::i18n;
+use crate::markers::HeartbeatAction;
+
+/// A parsed `/heartbeat` subcommand.
+#[derive(Debug, PartialEq)]
+enum HeartbeatArgs {
+    Show,
+    Enable(bool),
+    Interval(u64),
+    /// Active hours as normalized `HH:MM` pair; `None` = always active.
+    Hours(Option<(String, String)>),
+    /// Item spec: `description [| cadence] [| on_change]`.
+    Add(String),
+    /// Item id (or unique id prefix).
+    Remove(String),
+    /// Deliver alerts to the chat the command was sent from.
+    Here,
+}
+
+/// Parse the text after `/heartbeat`. Returns `None` for invalid usage.
+fn parse_heartbeat_args(text: &str) -> Option<HeartbeatArgs> {
+    let mut parts = text.split_whitespace().skip(1);
+    let Some(sub) = parts.next() else {
+        return Some(HeartbeatArgs::Show);
+    };
+    let rest = parts.collect::<Vec<_>>().join(" ");
+    match sub.to_lowercase().as_str() {
+        "on" if rest.is_empty() => Some(HeartbeatArgs::Enable(true)),
+        "off" if rest.is_empty() => Some(HeartbeatArgs::Enable(false)),
+        "here" if rest.is_empty() => Some(HeartbeatArgs::Here),
+        "interval" => rest
+            .parse::<u64>()
+            .ok()
+            .filter(|m| (1..=1440).contains(m))
+            .map(HeartbeatArgs::Interval),
+        "hours" if rest.eq_ignore_ascii_case("off") => Some(HeartbeatArgs::Hours(None)),
+        "hours" => {
+            let (start, end) = rest.split_once('-')?;
+            let (start, end) = (normalize_hhmm(start)?, normalize_hhmm(end)?);
+            (start != end).then_some(HeartbeatArgs::Hours(Some((start, end))))
+        }
+        "add" if !rest.is_empty() => Some(HeartbeatArgs::Add(rest)),
+        "remove" if !rest.is_empty() && !rest.contains(' ') => Some(HeartbeatArgs::Remove(
+            rest.trim_matches(|c| c == '[' || c == ']').to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// Parse `H:MM` / `HH:MM` into zero-padded `HH:MM` (active-hours comparisons are lexical).
+fn normalize_hhmm(s: &str) -> Option<String> {
+    chrono::NaiveTime::parse_from_str(s.trim(), "%H:%M")
+        .ok()
+        .map(|t| t.format("%H:%M").to_string())
+}
+
+impl Gateway {
+    /// Handle `/heartbeat [subcommand]` for the sender's own heartbeat.
+    pub(super) async fn handle_heartbeat_command(
+        &self,
+        incoming: &IncomingMessage,
+        active_project: Option<&str>,
+    ) {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+
+        let Some(args) = parse_heartbeat_args(&incoming.text) else {
+            self.send_text(incoming, i18n::t("heartbeat_usage", &lang))
+                .await;
+            return;
+        };
+
+        let reply_target = incoming
+            .reply_target
+            .as_deref()
+            .unwrap_or(&incoming.sender_id);
+        let item_action = match &args {
+            HeartbeatArgs::Add(item) => Some(HeartbeatAction::Add(item.clone())),
+            HeartbeatArgs::Remove(item) => Some(HeartbeatAction::Remove(item.clone())),
+            _ => None,
+        };
+        if let Some(action) = item_action {
+            update_user_heartbeat(
+                &self.memory,
+                &incoming.sender_id,
+                &incoming.channel,
+                reply_target,
+                &[action],
+                active_project.unwrap_or(""),
+                &self.heartbeat_notify,
+            )
+            .await;
+        } else if args != HeartbeatArgs::Show {
+            let existing = self
+                .memory
+                .get_heartbeat_settings(&incoming.sender_id)
+                .await
+                .unwrap_or_else(|e| {
+                    error!("heartbeat: failed to load settings: {e}");
+                    None
+                });
+            let mut settings = existing.unwrap_or_else(|| {
+                new_heartbeat_settings(&incoming.sender_id, &incoming.channel, reply_target)
+            });
+            match args {
+                HeartbeatArgs::Enable(on) => settings.enabled = on,
+                HeartbeatArgs::Interval(mins) => settings.interval_minutes = mins,
+                HeartbeatArgs::Hours(Some((start, end))) => {
+                    settings.active_start = start;
+                    settings.active_end = end;
+                }
+                HeartbeatArgs::Hours(None) => {
+                    settings.active_start.clear();
+                    settings.active_end.clear();
+                }
+                HeartbeatArgs::Here => {
+                    settings.channel = incoming.channel.clone();
+                    settings.reply_target = reply_target.to_string();
+                }
+                HeartbeatArgs::Show | HeartbeatArgs::Add(_) | HeartbeatArgs::Remove(_) => {}
+            }
+            if let Err(e) = self.memory.save_heartbeat_settings(&settings).await {
+                error!("heartbeat: failed to save settings: {e}");
+            }
+            self.heartbeat_notify.notify_one();
+        }
+
+        let settings = self
+            .memory
+            .get_heartbeat_settings(&incoming.sender_id)
+            .await
+            .ok()
+            .flatten();
+        let items = self
+            .memory
+            .get_heartbeat_items(&incoming.sender_id, active_project.unwrap_or(""))
+            .await
+            .unwrap_or_else(|e| {
+                error!("heartbeat: failed to load items: {e}");
+                Vec::new()
+            });
+        let text = commands::heartbeat_status_text(
+            &self.data_dir,
+            self.heartbeat_config.enabled,
+            settings.as_ref(),
+            &items,
+            active_project,
+            &lang,
+        );
+        self.send_text(incoming, &text).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_heartbeat_args_show_and_toggles() {
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat"),
+            Some(HeartbeatArgs::Show)
+        );
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat ON"),
+            Some(HeartbeatArgs::Enable(true))
+        );
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat off"),
+            Some(HeartbeatArgs::Enable(false))
+        );
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat here"),
+            Some(HeartbeatArgs::Here)
+        );
+        assert_eq!(parse_heartbeat_args("/heartbeat on now"), None);
+        assert_eq!(parse_heartbeat_args("/heartbeat bogus"), None);
+    }
+
+    #[test]
+    fn test_parse_heartbeat_args_interval_bounds() {
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat interval 15"),
+            Some(HeartbeatArgs::Interval(15))
+        );
+        assert_eq!(parse_heartbeat_args("/heartbeat interval 0"), None);
+        assert_eq!(parse_heartbeat_args("/heartbeat interval 1441"), None);
+        assert_eq!(parse_heartbeat_args("/heartbeat interval"), None);
+    }
+
+    #[test]
+    fn test_parse_heartbeat_args_hours() {
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat hours 8:00-22:30"),
+            Some(HeartbeatArgs::Hours(Some((
+                "08:00".to_string(),
+                "22:30".to_string()
+            ))))
+        );
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat hours off"),
+            Some(HeartbeatArgs::Hours(None))
+        );
+        assert_eq!(parse_heartbeat_args("/heartbeat hours 25:00-08:00"), None);
+        assert_eq!(parse_heartbeat_args("/heartbeat hours 08:00-08:00"), None);
+        assert_eq!(parse_heartbeat_args("/heartbeat hours 08:00"), None);
+    }
+
+    #[test]
+    fn test_parse_heartbeat_args_items() {
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat add Check   the inbox"),
+            Some(HeartbeatArgs::Add("Check the inbox".to_string()))
+        );
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat add Check inbox | daily 09:00 | on_change"),
+            Some(HeartbeatArgs::Add(
+                "Check inbox | daily 09:00 | on_change".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_heartbeat_args("/heartbeat remove [a1b2c3d4]"),
+            Some(HeartbeatArgs::Remove("a1b2c3d4".to_string()))
+        );
+        // Removal is by id only — free text would be a substring match.
+        assert_eq!(parse_heartbeat_args("/heartbeat remove the inbox"), None);
+        assert_eq!(parse_heartbeat_args("/heartbeat add"), None);
+    }
+}
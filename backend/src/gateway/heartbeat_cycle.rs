@@ -0,0 +1,289 @@
+//! One user's heartbeat cycle — personal checklist, then owner project heartbeats.
+//!
+//! The personal checklist is the user's structured `heartbeat_items` that are
+//! due on this pulse, enriched with that user's memory only. Project heartbeats
+//! combine the owner's project HEARTBEAT.md instructions with the user's due
+//! project items.
+
+use super::heartbeat::{classify_heartbeat_groups, execute_heartbeat_group};
+use super::heartbeat_helpers::{build_enrichment, build_system_prompt, send_heartbeat_result};
+use crate::markers::*;
+use omega_core::{
+    config::{ApprovalConfig, DataDir, Prompts, RolesConfig, TenantsConfig, TrustConfig},
+    traits::{Channel, Provider},
+};
+use omega_memory::{audit::AuditLogger, HeartbeatItem, HeartbeatSettings, Store};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+/// Everything a heartbeat cycle needs, shared across users.
+#[derive(Clone)]
+pub(super) struct HeartbeatRuntime {
+    pub provider: Arc<dyn Provider>,
+    pub channels: HashMap<String, Arc<dyn Channel>>,
+    pub prompts: Prompts,
+    pub memory: Store,
+    pub notify: Arc<Notify>,
+    pub model_complex: String,
+    pub model_fast: String,
+    pub skills: Vec<omega_skills::Skill>,
+    pub audit: AuditLogger,
+    pub provider_name: String,
+    pub data_dir: String,
+    /// Untrusted-content policy (`[trust]`).
+    pub trust: TrustConfig,
+    /// Approval policy; flagged calls are denied (nobody to ask).
+    pub approval: ApprovalConfig,
+    /// Tenants and roles, so each user's heartbeat runs in their own scope.
+    pub tenants: TenantsConfig,
+    pub roles: RolesConfig,
+    /// Canonical sender id of the config owner (`[heartbeat] reply_target`).
+    pub owner_id: String,
+}
+
+impl HeartbeatRuntime {
+    /// Run one heartbeat for `settings.sender_id`.
+    ///
+    /// Only items whose cadence is due run; the provider call is skipped
+    /// entirely when nothing is due. Project heartbeats run for every project
+    /// with due items and, for the owner, every project with its own
+    /// HEARTBEAT.md (filesystem-based discovery).
+    pub(super) async fn run_user_heartbeat(&self, settings: HeartbeatSettings) {
+        let is_owner = settings.sender_id == self.owner_id;
+        let now = chrono::Local::now();
+
+        // --- Discover ALL projects with a HEARTBEAT.md file ---
+        // Filesystem-based: heartbeats run regardless of active_project state,
+        // so `/project off` only exits conversation context, not monitoring.
+        let projects_with_heartbeat: Vec<String> = if is_owner {
+            let dir = DataDir::new(&self.data_dir).projects();
+            let mut names: Vec<String> = std::fs::read_dir(&dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter(|e| !e.path().join(".disabled").exists())
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    read_project_heartbeat_file(&self.data_dir, &name).map(|_| name)
+                })
+                .collect();
+            names.sort(); // deterministic order
+            names
+        } else {
+            Vec::new()
+        };
+
+        // --- Personal heartbeat ---
+        let suppressed = read_suppress_file(&self.data_dir, &settings.sender_id, None);
+        let personal: Vec<HeartbeatItem> = self
+            .due_items(&settings.sender_id, "", now)
+            .await
+            .into_iter()
+            .filter(|i| {
+                item_section_name(&i.description)
+                    .is_none_or(|name| !suppressed.iter().any(|s| s.eq_ignore_ascii_case(&name)))
+            })
+            .filter(|i| strip_project_sections(&i.description, &projects_with_heartbeat).is_some())
+            .collect();
+        if personal.is_empty() {
+            info!(
+                "heartbeat: no due items for {}, skipping",
+                settings.sender_id
+            );
+        } else {
+            self.run_personal(&settings, personal).await;
+        }
+
+        // --- Project heartbeats ---
+        let mut projects = projects_with_heartbeat.clone();
+        match self
+            .memory
+            .get_heartbeat_item_projects(&settings.sender_id)
+            .await
+        {
+            Ok(names) => projects.extend(names),
+            Err(e) => warn!("heartbeat: failed to list item projects: {e}"),
+        }
+        projects.sort();
+        projects.dedup();
+
+        let projects_dir = DataDir::new(&self.data_dir);
+        for project_name in &projects {
+            if projects_dir
+                .project(project_name)
+                .join(".disabled")
+                .exists()
+            {
+                continue;
+            }
+            let file_checklist = if projects_with_heartbeat.contains(project_name) {
+                read_project_heartbeat_file(&self.data_dir, project_name).and_then(|c| {
+                    filter_suppressed_sections(
+                        &self.data_dir,
+                        &settings.sender_id,
+                        &c,
+                        Some(project_name),
+                    )
+                })
+            } else {
+                None
+            };
+            let items = self.due_items(&settings.sender_id, project_name, now).await;
+            let project_checklist = match (&file_checklist, items.is_empty()) {
+                (None, true) => continue,
+                (Some(file), true) => file.clone(),
+                (None, false) => render_heartbeat_items(&items),
+                (Some(file), false) => format!("{file}\n\n{}", render_heartbeat_items(&items)),
+            };
+            // Free-form file instructions can always produce an alert, so the
+            // alert-on-change gate only applies to item-only checklists.
+            let gate_items = if file_checklist.is_some() && !items.is_empty() {
+                let mut ungated = items.clone();
+                ungated.iter_mut().for_each(|i| i.alert_on_change = false);
+                ungated
+            } else {
+                items
+            };
+
+            info!("heartbeat: running project heartbeat for '{project_name}'");
+            let enrichment = build_enrichment(&self.memory, &settings, Some(project_name)).await;
+            let system =
+                build_system_prompt(&self.prompts, Some(project_name), Some(&self.data_dir));
+
+            // Project heartbeats always run as a single call (simpler, focused).
+            let result = execute_heartbeat_group(
+                self.clone(),
+                settings.clone(),
+                project_checklist,
+                gate_items,
+                enrichment,
+                system,
+                project_name.clone(),
+            )
+            .await;
+            send_heartbeat_result(
+                result,
+                &settings,
+                &self.channels,
+                &self.audit,
+                &self.provider_name,
+                &self.model_complex,
+            )
+            .await;
+        }
+    }
+
+    /// The user's items in one project scope whose cadence is due at `now`.
+    async fn due_items(
+        &self,
+        sender_id: &str,
+        project: &str,
+        now: chrono::DateTime<chrono::Local>,
+    ) -> Vec<HeartbeatItem> {
+        match self.memory.get_heartbeat_items(sender_id, project).await {
+            Ok(items) => items.into_iter().filter(|i| is_item_due(i, now)).collect(),
+            Err(e) => {
+                error!("heartbeat: failed to load items for {sender_id}: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Stamp items a completed run covered so their cadence restarts from now.
+    ///
+    /// Called only after the provider answered: a failed call leaves the items
+    /// due, so the next pulse retries them.
+    pub(super) async fn mark_checked(&self, items: &[HeartbeatItem]) {
+        let ids: Vec<String> = items.iter().map(|i| i.id.clone()).collect();
+        if let Err(e) = self.memory.mark_heartbeat_items_checked(&ids).await {
+            error!("heartbeat: failed to mark items checked: {e}");
+        }
+    }
+
+    /// Run the user's own checklist, split into parallel domain groups when useful.
+    async fn run_personal(&self, settings: &HeartbeatSettings, items: Vec<HeartbeatItem>) {
+        let checklist = render_heartbeat_items(&items);
+        let enrichment = build_enrichment(&self.memory, settings, None).await;
+        let system = build_system_prompt(&self.prompts, None, None);
+
+        let groups = classify_heartbeat_groups(&*self.provider, &self.model_fast, &checklist).await;
+
+        let Some(groups) = groups else {
+            info!("heartbeat: DIRECT (single call)");
+            let result = execute_heartbeat_group(
+                self.clone(),
+                settings.clone(),
+                checklist,
+                items,
+                enrichment,
+                system,
+                String::new(),
+            )
+            .await;
+            send_heartbeat_result(
+                result,
+                settings,
+                &self.channels,
+                &self.audit,
+                &self.provider_name,
+                &self.model_complex,
+            )
+            .await;
+            return;
+        };
+
+        let group_count = groups.len();
+        info!("heartbeat: classified into {group_count} groups");
+
+        let mut handles = Vec::new();
+        for group in groups {
+            let group_items: Vec<HeartbeatItem> = items
+                .iter()
+                .filter(|i| group.contains(&format!("[{}]", short_item_id(&i.id))))
+                .cloned()
+                .collect();
+            handles.push(tokio::spawn(execute_heartbeat_group(
+                self.clone(),
+                settings.clone(),
+                group,
+                group_items,
+                enrichment.clone(),
+                system.clone(),
+                String::new(),
+            )));
+        }
+
+        let mut texts = Vec::new();
+        let mut max_ms: i64 = 0;
+        for (i, handle) in handles.into_iter().enumerate() {
+            match handle.await {
+                Ok(Some((text, ms))) => {
+                    texts.push(text);
+                    if ms > max_ms {
+                        max_ms = ms;
+                    }
+                }
+                Ok(None) => info!("heartbeat: group {} OK", i + 1),
+                Err(e) => error!("heartbeat: group {} panicked: {e}", i + 1),
+            }
+        }
+
+        if texts.is_empty() {
+            info!("heartbeat: all {group_count} groups OK");
+        } else {
+            let combined = texts.join("\n\n---\n\n");
+            send_heartbeat_result(
+                Some((combined, max_ms)),
+                settings,
+                &self.channels,
+                &self.audit,
+                &self.provider_name,
+                &self.model_complex,
+            )
+            .await;
+        }
+    }
+}
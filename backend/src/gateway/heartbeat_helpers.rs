@@ -2,33 +2,43 @@
 
 use crate::markers::*;
 use omega_core::{
-    config::{HeartbeatConfig, Prompts},
+    config::{DataDir, HeartbeatConfig, Prompts},
     message::{MessageMetadata, OutgoingMessage},
     traits::Channel,
 };
 use omega_memory::{
     audit::{AuditEntry, AuditLogger, AuditStatus},
-    Store,
+    HeartbeatSettings, NewHeartbeatItem, Store,
 };
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tokio::sync::Notify;
 use tracing::{error, info, warn};
 
-/// Build enrichment context from user facts and recent conversation summaries.
+/// Build enrichment context from one user's facts and recent conversation summaries.
 ///
+/// Only the heartbeat owner's memory is used — other senders never leak in.
 /// When `project` is Some, uses project-scoped lessons/outcomes.
-pub async fn build_enrichment(memory: &Store, project: Option<&str>) -> String {
+pub async fn build_enrichment(
+    memory: &Store,
+    settings: &HeartbeatSettings,
+    project: Option<&str>,
+) -> String {
+    let sender_id = settings.sender_id.as_str();
     let mut enrichment = String::new();
-    if let Ok(facts) = memory.get_all_facts().await {
+    if let Ok(facts) = memory.get_facts(sender_id).await {
+        let facts: Vec<_> = facts.iter().filter(|(k, _)| k != "welcomed").collect();
         if !facts.is_empty() {
             enrichment.push_str("\n\nKnown about the user:");
-            for (key, value) in &facts {
+            for (key, value) in facts {
                 enrichment.push_str(&format!("\n- {key}: {value}"));
             }
         }
     }
-    if let Ok(summaries) = memory.get_all_recent_summaries(3).await {
+    if let Ok(summaries) = memory
+        .get_recent_summaries(&settings.channel, sender_id, 3)
+        .await
+    {
         if !summaries.is_empty() {
             enrichment.push_str("\n\nRecent activity:");
             for (summary, timestamp) in &summaries {
@@ -36,7 +46,7 @@ pub async fn build_enrichment(memory: &Store, project: Option<&str>) -> String {
             }
         }
     }
-    if let Ok(lessons) = memory.get_all_lessons(project).await {
+    if let Ok(lessons) = memory.get_lessons(sender_id, project).await {
         if !lessons.is_empty() {
             enrichment.push_str("\n\nLearned behavioral rules:");
             for (domain, rule, proj) in &lessons {
@@ -48,7 +58,10 @@ pub async fn build_enrichment(memory: &Store, project: Option<&str>) -> String {
             }
         }
     }
-    if let Ok(outcomes) = memory.get_all_recent_outcomes(24, 20, project).await {
+    if let Ok(outcomes) = memory
+        .get_recent_outcomes_since(sender_id, 24, 20, project)
+        .await
+    {
         if !outcomes.is_empty() {
             enrichment.push_str("\n\nRecent outcomes (last 24h):");
             for (score, domain, lesson, timestamp) in &outcomes {
@@ -85,8 +98,7 @@ pub fn build_system_prompt(
 
     // Inject project ROLE.md for project-scoped heartbeats.
     if let (Some(proj), Some(dd)) = (project, data_dir) {
-        let data_path = omega_core::config::shellexpand(dd);
-        let projects = omega_skills::load_projects(&data_path);
+        let projects = omega_skills::load_projects(dd);
         if let Some(instructions) = omega_skills::get_project_instructions(&projects, proj) {
             system.push_str(&format!(
                 "\n\n---\n\n[Active project: {proj}]\n{instructions}"
@@ -97,6 +109,199 @@ pub fn build_system_prompt(
     system
 }
 
+/// Default settings for a user who has never configured a heartbeat.
+///
+/// Alerts go to the chat the user is talking from; the pulse follows the
+/// config default and there are no quiet hours.
+pub fn new_heartbeat_settings(
+    sender_id: &str,
+    channel: &str,
+    reply_target: &str,
+) -> HeartbeatSettings {
+    HeartbeatSettings {
+        sender_id: sender_id.to_string(),
+        channel: channel.to_string(),
+        reply_target: reply_target.to_string(),
+        enabled: true,
+        interval_minutes: HeartbeatConfig::default().interval_minutes,
+        active_start: String::new(),
+        active_end: String::new(),
+    }
+}
+
+/// Seed the owner's heartbeat from `[heartbeat]` in config.toml.
+///
+/// Runs once per database: each entry of `{data_dir}/prompts/HEARTBEAT.md`
+/// becomes one of the owner's items. `owner_id` is the canonical sender id
+/// `reply_target` resolves to, so a linked owner keeps a single heartbeat.
+/// Later edits go through `/heartbeat` and markers.
+pub async fn seed_owner_heartbeat(
+    memory: &Store,
+    config: &HeartbeatConfig,
+    owner_id: &str,
+    data_dir: &str,
+) {
+    if config.channel.is_empty() || config.reply_target.is_empty() {
+        return;
+    }
+    let settings = HeartbeatSettings {
+        interval_minutes: config.interval_minutes.clamp(1, 1440),
+        active_start: config.active_start.clone(),
+        active_end: config.active_end.clone(),
+        ..new_heartbeat_settings(owner_id, &config.channel, &config.reply_target)
+    };
+    match memory.seed_heartbeat_settings(&settings).await {
+        Ok(true) => {
+            let checklist = read_heartbeat_file(data_dir).unwrap_or_default();
+            let count = add_checklist_items(memory, &settings.sender_id, &checklist).await;
+            info!("heartbeat: seeded owner settings from config ({count} items)");
+        }
+        Ok(false) => {}
+        Err(e) => error!("heartbeat: failed to seed owner settings: {e}"),
+    }
+}
+
+/// Hand the single personal suppress list used before per-sender lists to the owner.
+///
+/// Idempotent: the legacy `{data_dir}/prompts/HEARTBEAT.suppress` is moved,
+/// and never over a list the owner already has.
+pub fn migrate_legacy_suppress_file(data_dir: &str, owner_id: &str) {
+    let dir = DataDir::new(data_dir);
+    let legacy = dir.prompts().join("HEARTBEAT.suppress");
+    let target = dir.heartbeat_suppress_file(owner_id, None);
+    if owner_id.is_empty() || !legacy.exists() || target.exists() {
+        return;
+    }
+    if let Some(parent) = target.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match std::fs::rename(&legacy, &target) {
+        Ok(()) => info!("heartbeat: moved legacy suppress list to {owner_id}"),
+        Err(e) => error!("heartbeat: failed to move legacy suppress list: {e}"),
+    }
+}
+
+/// Convert checklists saved before structured items existed into items.
+///
+/// Idempotent: each legacy checklist is cleared once its items are stored.
+pub async fn migrate_legacy_checklists(memory: &Store) {
+    let legacy = match memory.get_legacy_heartbeat_checklists().await {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!("heartbeat: failed to load legacy checklists: {e}");
+            return;
+        }
+    };
+    for (sender_id, checklist) in legacy {
+        let count = add_checklist_items(memory, &sender_id, &checklist).await;
+        if let Err(e) = memory.clear_legacy_heartbeat_checklist(&sender_id).await {
+            error!("heartbeat: failed to clear legacy checklist for {sender_id}: {e}");
+            continue;
+        }
+        info!("heartbeat: converted legacy checklist for {sender_id} into {count} items");
+    }
+}
+
+/// Store each entry of a markdown checklist as a personal item. Returns the count.
+async fn add_checklist_items(memory: &Store, sender_id: &str, checklist: &str) -> usize {
+    let mut count = 0;
+    for description in checklist_to_item_descriptions(checklist) {
+        let item = NewHeartbeatItem {
+            description,
+            ..Default::default()
+        };
+        match memory.add_heartbeat_item(sender_id, "", &item).await {
+            Ok(_) => count += 1,
+            Err(e) => error!("heartbeat: failed to add item for {sender_id}: {e}"),
+        }
+    }
+    count
+}
+
+/// Apply HEARTBEAT_ADD / HEARTBEAT_REMOVE / HEARTBEAT_INTERVAL for one user.
+///
+/// Adds go to the user's items in `project` ("" = personal); removes match the
+/// user's item ids in any scope. Interval changes update the user's pulse.
+/// Creates the user's settings on first use and wakes the heartbeat loop.
+pub async fn update_user_heartbeat(
+    memory: &Store,
+    sender_id: &str,
+    channel: &str,
+    reply_target: &str,
+    actions: &[HeartbeatAction],
+    project: &str,
+    notify: &Notify,
+) {
+    for action in actions {
+        match action {
+            HeartbeatAction::Add(spec) => {
+                let item = parse_item_spec(spec);
+                if item.description.is_empty() {
+                    continue;
+                }
+                match memory.add_heartbeat_item(sender_id, project, &item).await {
+                    Ok(id) => info!("heartbeat: {sender_id} added item {}", short_item_id(&id)),
+                    Err(e) => error!("heartbeat: failed to add item for {sender_id}: {e}"),
+                }
+            }
+            HeartbeatAction::Remove(id) => {
+                match memory.remove_heartbeat_item(sender_id, id).await {
+                    Ok(true) => info!("heartbeat: {sender_id} removed item {id}"),
+                    Ok(false) => {
+                        warn!("heartbeat: no single item matches id '{id}' for {sender_id}")
+                    }
+                    Err(e) => error!("heartbeat: failed to remove item for {sender_id}: {e}"),
+                }
+            }
+            HeartbeatAction::SetInterval(_) => {}
+        }
+    }
+
+    let existing = match memory.get_heartbeat_settings(sender_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            error!("heartbeat: failed to load settings for {sender_id}: {e}");
+            return;
+        }
+    };
+    let is_new = existing.is_none();
+    let mut settings =
+        existing.unwrap_or_else(|| new_heartbeat_settings(sender_id, channel, reply_target));
+    let before = settings.clone();
+    for action in actions {
+        if let HeartbeatAction::SetInterval(mins) = action {
+            settings.interval_minutes = *mins;
+            info!("heartbeat: {sender_id} interval changed to {mins} minutes");
+        }
+    }
+    if is_new || settings != before {
+        if let Err(e) = memory.save_heartbeat_settings(&settings).await {
+            error!("heartbeat: failed to save settings for {sender_id}: {e}");
+            return;
+        }
+    }
+    notify.notify_one();
+}
+
+/// Record `HEARTBEAT_CHECKED:` results for one user.
+///
+/// Returns the short ids whose result changed since the previous run.
+pub async fn record_item_checks(memory: &Store, sender_id: &str, text: &str) -> Vec<String> {
+    let mut changed = Vec::new();
+    for (id, result) in extract_heartbeat_checked_markers(text) {
+        match memory
+            .record_heartbeat_result(sender_id, &id, &result)
+            .await
+        {
+            Ok(Some(true)) => changed.push(id),
+            Ok(Some(false)) => {}
+            Ok(None) => warn!("heartbeat: HEARTBEAT_CHECKED for unknown item '{id}'"),
+            Err(e) => error!("heartbeat: failed to record result for {id}: {e}"),
+        }
+    }
+    changed
+}
+
 /// Process all markers in a heartbeat response.
 ///
 /// Handles: SCHEDULE, SCHEDULE_ACTION, heartbeat markers (interval, add/remove),
@@ -105,12 +310,14 @@ pub fn build_system_prompt(
 pub async fn process_heartbeat_markers(
     mut text: String,
     memory: &Store,
-    sender_id: &str,
-    channel_name: &str,
-    interval: &Arc<AtomicU64>,
+    data_dir: &str,
+    settings: &HeartbeatSettings,
+    notify: &Notify,
     project: &str,
-    config_path: &str,
 ) -> String {
+    let sender_id = settings.sender_id.as_str();
+    let channel_name = settings.channel.as_str();
+    let reply_target = settings.reply_target.as_str();
     for sched_line in extract_all_schedule_markers(&text) {
         if let Some((desc, due, rep)) = parse_schedule_line(&sched_line) {
             let rep_opt = if rep == "once" {
@@ -122,7 +329,7 @@ pub async fn process_heartbeat_markers(
                 .create_task(
                     channel_name,
                     sender_id,
-                    sender_id,
+                    reply_target,
                     &desc,
                     &due,
                     rep_opt,
@@ -149,7 +356,7 @@ pub async fn process_heartbeat_markers(
                 .create_task(
                     channel_name,
                     sender_id,
-                    sender_id,
+                    reply_target,
                     &desc,
                     &due,
                     rep_opt,
@@ -167,19 +374,16 @@ pub async fn process_heartbeat_markers(
 
     let hb_actions = extract_heartbeat_markers(&text);
     if !hb_actions.is_empty() {
-        let hb_project = if project.is_empty() {
-            None
-        } else {
-            Some(project)
-        };
-        apply_heartbeat_changes(&hb_actions, hb_project);
-        for action in &hb_actions {
-            if let HeartbeatAction::SetInterval(mins) = action {
-                interval.store(*mins, Ordering::Relaxed);
-                omega_core::config::patch_heartbeat_interval(config_path, *mins);
-                info!("heartbeat: interval changed to {mins} minutes (via heartbeat loop)");
-            }
-        }
+        update_user_heartbeat(
+            memory,
+            sender_id,
+            channel_name,
+            reply_target,
+            &hb_actions,
+            project,
+            notify,
+        )
+        .await;
         text = strip_heartbeat_markers(&text);
     }
 
@@ -201,7 +405,7 @@ pub async fn process_heartbeat_markers(
         } else {
             Some(project)
         };
-        apply_suppress_actions(&suppress_actions, hb_project);
+        apply_suppress_actions(data_dir, sender_id, &suppress_actions, hb_project);
         text = strip_suppress_section_markers(&text);
     }
 
@@ -211,14 +415,11 @@ pub async fn process_heartbeat_markers(
     text
 }
 
-/// Audit and send a heartbeat result to the user.
-#[allow(clippy::too_many_arguments)]
+/// Audit and send a heartbeat result to the user's delivery target.
 pub async fn send_heartbeat_result(
     result: Option<(String, i64)>,
-    channel_name: &str,
-    sender_id: &str,
+    settings: &HeartbeatSettings,
     channels: &HashMap<String, Arc<dyn Channel>>,
-    config: &HeartbeatConfig,
     audit: &AuditLogger,
     provider_name: &str,
     model: &str,
@@ -231,9 +432,10 @@ pub async fn send_heartbeat_result(
         }
     };
 
+    let channel_name = settings.channel.as_str();
     let audit_entry = AuditEntry {
         channel: channel_name.to_string(),
-        sender_id: sender_id.to_string(),
+        sender_id: settings.sender_id.clone(),
         sender_name: None,
         input_text: "[HEARTBEAT]".to_string(),
         output_text: Some(text.clone()),
@@ -251,7 +453,7 @@ pub async fn send_heartbeat_result(
         let msg = OutgoingMessage {
             text,
             metadata: MessageMetadata::default(),
-            reply_target: Some(config.reply_target.clone()),
+            reply_target: Some(settings.reply_target.clone()),
             ..Default::default()
         };
         if let Err(e) = ch.send(msg).await {
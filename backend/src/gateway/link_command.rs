@@ -0,0 +1,378 @@
+//! `/link` and `/unlink` — deliberate cross-channel identity linking.
+//!
+//! `/link` on one account stores a one-time code (`pending_link` fact,
+//! `code|unix_ts|failures`). `/link <code>` on the other account merges that
+//! account's facts, conversations, tasks and lessons into the code owner's
+//! canonical id and aliases it there. `/unlink` detaches an alias again;
+//! merged data stays.
+//!
+//! Guessing is bounded: a wrong code counts against every pending code (the
+//! guess can't tell which one it aimed at), and a code is dropped after
+//! [`MAX_CODE_FAILURES`] wrong tries. The guessing sender is locked out for
+//! [`LINK_LOCKOUT_SECS`] after [`MAX_SENDER_FAILURES`] (`link_failures` fact,
+//! `count|first_unix_ts`).
+//!
+//! Intercepted in pipeline.rs (like `/heartbeat`) because unlinking needs the
+//! platform id from before alias resolution.
+
+use omega_core::message::IncomingMessage;
+use tracing::{error, info, warn};
+
+use super::Gateway;
+use crate::i18n;
+
+/// How long a `/link` code stays valid.
+const LINK_CODE_TTL_SECS: i64 = 600;
+
+/// Characters of a link code: no `0`/`O`, `1`/`I` to misread.
+const LINK_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Length of a link code (50 bits).
+const LINK_CODE_LEN: usize = 10;
+
+/// Wrong `/link <code>` tries after which pending codes are dropped.
+const MAX_CODE_FAILURES: u32 = 3;
+
+/// Wrong tries after which a sender is locked out of `/link <code>`.
+const MAX_SENDER_FAILURES: u32 = 5;
+
+/// How long a sender's wrong tries count, and the lockout lasts.
+const LINK_LOCKOUT_SECS: i64 = 3600;
+
+/// Facts whose conflicts are bookkeeping, not worth reporting.
+const QUIET_CONFLICT_KEYS: &[&str] = &["welcomed", "onboarding_stage"];
+
+/// A fresh one-time code of [`LINK_CODE_LEN`] characters.
+fn new_link_code() -> String {
+    let mut bits = uuid::Uuid::new_v4().as_u128();
+    (0..LINK_CODE_LEN)
+        .map(|_| {
+            let c = LINK_CODE_ALPHABET[(bits % 32) as usize];
+            bits >>= 5;
+            c as char
+        })
+        .collect()
+}
+
+/// Split a stored `code|unix_ts|failures` value (`failures` may be missing).
+fn parse_pending(stored: &str) -> Option<(&str, i64, u32)> {
+    let mut parts = stored.split('|');
+    let code = parts.next()?;
+    let ts = parts.next()?.parse().ok()?;
+    let failures = parts.next().map_or(Some(0), |f| f.parse().ok())?;
+    Some((code, ts, failures))
+}
+
+/// Whether a stored pending code matches `code` and has not expired.
+fn link_code_matches(stored: &str, code: &str, now: i64) -> bool {
+    parse_pending(stored).is_some_and(|(stored_code, ts, _)| {
+        stored_code.eq_ignore_ascii_case(code) && now - ts <= LINK_CODE_TTL_SECS
+    })
+}
+
+/// Count one wrong try against a stored pending code.
+///
+/// Returns the updated value, or `None` when the code is expired or has now
+/// had [`MAX_CODE_FAILURES`] wrong tries and should be dropped.
+fn pending_after_failure(stored: &str, now: i64) -> Option<String> {
+    let (code, ts, failures) = parse_pending(stored)?;
+    let failures = failures + 1;
+    (now - ts <= LINK_CODE_TTL_SECS && failures < MAX_CODE_FAILURES)
+        .then(|| format!("{code}|{ts}|{failures}"))
+}
+
+/// Parse a sender's `count|first_unix_ts` failure record, ignoring stale ones.
+fn sender_failures(stored: Option<&str>, now: i64) -> (u32, i64) {
+    stored
+        .and_then(|s| s.split_once('|'))
+        .and_then(|(count, ts)| Some((count.parse().ok()?, ts.parse().ok()?)))
+        .filter(|(_, ts)| now - ts <= LINK_LOCKOUT_SECS)
+        .unwrap_or((0, now))
+}
+
+impl Gateway {
+    /// Handle `/link` (issue a code) and `/link <code>` (merge into the code owner).
+    pub(super) async fn handle_link_command(&self, incoming: &IncomingMessage) {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        let now = chrono::Utc::now().timestamp();
+
+        let Some(code) = incoming.text.split_whitespace().nth(1) else {
+            let code = new_link_code();
+            if let Err(e) = self
+                .memory
+                .store_fact(
+                    &incoming.sender_id,
+                    "pending_link",
+                    &format!("{code}|{now}|0"),
+                )
+                .await
+            {
+                error!("link: failed to store code: {e}");
+            }
+            self.send_text(incoming, &i18n::link_code(&lang, &code))
+                .await;
+            return;
+        };
+
+        let stored_failures = self
+            .memory
+            .get_fact(&incoming.sender_id, "link_failures")
+            .await
+            .ok()
+            .flatten();
+        let (failures, since) = sender_failures(stored_failures.as_deref(), now);
+        if failures >= MAX_SENDER_FAILURES {
+            warn!("link: {} is locked out", incoming.sender_id);
+            self.send_text(incoming, i18n::t("link_locked", &lang))
+                .await;
+            return;
+        }
+
+        let pending = self
+            .memory
+            .get_all_facts_by_key("pending_link")
+            .await
+            .unwrap_or_default();
+        let owner = pending
+            .iter()
+            .find(|(_, stored)| link_code_matches(stored, code, now))
+            .map(|(sender, _)| sender.clone());
+        let Some(owner) = owner else {
+            self.record_link_failure(incoming, &pending, failures, since, now)
+                .await;
+            self.send_text(incoming, i18n::t("link_invalid", &lang))
+                .await;
+            return;
+        };
+        let _ = self
+            .memory
+            .delete_fact(&incoming.sender_id, "link_failures")
+            .await;
+        if owner == incoming.sender_id {
+            self.send_text(incoming, i18n::t("link_same", &lang)).await;
+            return;
+        }
+        let _ = self.memory.delete_fact(&owner, "pending_link").await;
+        let _ = self
+            .memory
+            .delete_fact(&incoming.sender_id, "pending_link")
+            .await;
+
+        // Tenant membership follows aliases, so linking across tenants
+        // would make the merged identity ambiguous.
+        let (ours, theirs) = (
+            self.tenant_scope(&incoming.sender_id).await,
+            self.tenant_scope(&owner).await,
+        );
+        if ours.name != theirs.name {
+            let reason = format!(
+                "cannot link {} to {owner}: different tenants",
+                incoming.sender_id
+            );
+            self.deny_access(incoming, reason, "role_denied").await;
+            return;
+        }
+
+        let report = match self.memory.merge_sender(&incoming.sender_id, &owner).await {
+            Ok(report) => report,
+            Err(e) => {
+                error!(
+                    "link: merge {} into {owner} failed: {e}",
+                    incoming.sender_id
+                );
+                self.send_text(incoming, &format!("Error: {e}")).await;
+                return;
+            }
+        };
+        info!(
+            "linked {} into {owner}: {} facts, {} conversations, {} tasks, {} lessons, {} conflicts",
+            incoming.sender_id,
+            report.facts,
+            report.conversations,
+            report.tasks,
+            report.lessons,
+            report.conflicts.len()
+        );
+
+        let mut reply = i18n::link_done(&lang, report.facts, report.conversations, report.tasks);
+        let conflicts: Vec<_> = report
+            .conflicts
+            .iter()
+            .filter(|c| !QUIET_CONFLICT_KEYS.contains(&c.key.as_str()))
+            .collect();
+        if !conflicts.is_empty() {
+            reply.push_str(&format!("\n\n{}", i18n::t("link_conflicts", &lang)));
+            for c in conflicts {
+                reply.push_str(&format!(
+                    "\n- {}: {} (\u{2260} {})",
+                    c.key, c.kept, c.discarded
+                ));
+            }
+        }
+        self.send_text(incoming, &reply).await;
+    }
+
+    /// Count a wrong `/link <code>` against the sender and every pending code.
+    async fn record_link_failure(
+        &self,
+        incoming: &IncomingMessage,
+        pending: &[(String, String)],
+        failures: u32,
+        since: i64,
+        now: i64,
+    ) {
+        for (owner, stored) in pending {
+            let result = match pending_after_failure(stored, now) {
+                Some(updated) => {
+                    self.memory
+                        .store_fact(owner, "pending_link", &updated)
+                        .await
+                }
+                None => self
+                    .memory
+                    .delete_fact(owner, "pending_link")
+                    .await
+                    .map(|_| ()),
+            };
+            if let Err(e) = result {
+                error!("link: failed to update code of {owner}: {e}");
+            }
+        }
+        let failures = failures + 1;
+        warn!(
+            "link: wrong code from {} ({failures}/{MAX_SENDER_FAILURES})",
+            incoming.sender_id
+        );
+        if let Err(e) = self
+            .memory
+            .store_fact(
+                &incoming.sender_id,
+                "link_failures",
+                &format!("{failures}|{since}"),
+            )
+            .await
+        {
+            error!("link: failed to record failure: {e}");
+        }
+    }
+
+    /// Handle `/unlink` (detach this account) and `/unlink <id>` (detach another).
+    ///
+    /// `platform_id` is the sender id before alias resolution.
+    pub(super) async fn handle_unlink_command(
+        &self,
+        incoming: &IncomingMessage,
+        platform_id: &str,
+    ) {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        let aliases = self
+            .memory
+            .get_aliases(&incoming.sender_id)
+            .await
+            .unwrap_or_default();
+
+        let target = match incoming.text.split_whitespace().nth(1) {
+            Some(id) => aliases.iter().find(|a| *a == id).cloned(),
+            None if platform_id != incoming.sender_id => Some(platform_id.to_string()),
+            None => None,
+        };
+        let Some(target) = target else {
+            let reply = if aliases.is_empty() {
+                i18n::t("unlink_none", &lang).to_string()
+            } else {
+                let list: Vec<String> = aliases.iter().map(|a| format!("- {a}")).collect();
+                format!("{}\n{}", i18n::t("unlink_usage", &lang), list.join("\n"))
+            };
+            self.send_text(incoming, &reply).await;
+            return;
+        };
+
+        match self.memory.remove_alias(&target).await {
+            Ok(_) => {
+                // Mark the detached id as known so it is not auto-aliased back.
+                let _ = self.memory.store_fact(&target, "welcomed", "true").await;
+                let _ = self
+                    .memory
+                    .store_fact(&target, "preferred_language", &lang)
+                    .await;
+                info!("unlinked {target} from {}", incoming.sender_id);
+                self.send_text(incoming, i18n::t("unlink_done", &lang))
+                    .await;
+            }
+            Err(e) => {
+                error!("unlink: failed to remove alias {target}: {e}");
+                self.send_text(incoming, &format!("Error: {e}")).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_code_format() {
+        let code = new_link_code();
+        assert_eq!(code.len(), LINK_CODE_LEN);
+        assert!(code.bytes().all(|c| LINK_CODE_ALPHABET.contains(&c)));
+        assert_ne!(code, new_link_code());
+    }
+
+    #[test]
+    fn test_link_code_matches_and_expires() {
+        assert!(link_code_matches(
+            "K7QX2MPA9R|1000|0",
+            "K7QX2MPA9R",
+            1000 + 60
+        ));
+        assert!(link_code_matches("K7QX2MPA9R|1000|2", "k7qx2mpa9r", 1000));
+        // Codes stored before failures were counted.
+        assert!(link_code_matches("042917|1000", "042917", 1000));
+        assert!(!link_code_matches("K7QX2MPA9R|1000|0", "K7QX2MPA9S", 1000));
+        assert!(!link_code_matches(
+            "K7QX2MPA9R|1000|0",
+            "K7QX2MPA9R",
+            1000 + LINK_CODE_TTL_SECS + 1
+        ));
+        assert!(!link_code_matches("garbage", "042917", 1000));
+    }
+
+    #[test]
+    fn test_pending_code_dropped_after_wrong_tries() {
+        let mut stored = "K7QX2MPA9R|1000|0".to_string();
+        for _ in 1..MAX_CODE_FAILURES {
+            stored = pending_after_failure(&stored, 1000).unwrap();
+        }
+        assert_eq!(stored, format!("K7QX2MPA9R|1000|{}", MAX_CODE_FAILURES - 1));
+        assert!(pending_after_failure(&stored, 1000).is_none());
+        // Expired codes are dropped on the first wrong try.
+        assert!(
+            pending_after_failure("K7QX2MPA9R|1000|0", 1000 + LINK_CODE_TTL_SECS + 1).is_none()
+        );
+    }
+
+    #[test]
+    fn test_sender_failures_window() {
+        assert_eq!(sender_failures(None, 5000), (0, 5000));
+        assert_eq!(sender_failures(Some("4|4000"), 5000), (4, 4000));
+        // Outside the lockout window the count starts over.
+        assert_eq!(
+            sender_failures(Some("5|1000"), 1000 + LINK_LOCKOUT_SECS + 1),
+            (0, 1000 + LINK_LOCKOUT_SECS + 1)
+        );
+        assert_eq!(sender_failures(Some("garbage"), 5000), (0, 5000));
+    }
+}
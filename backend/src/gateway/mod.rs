@@ -3,27 +3,40 @@
 //! Includes: auth enforcement, prompt sanitization, audit logging,
 //! background conversation summarization, and graceful shutdown.
 
+mod approval;
 mod auth;
+mod branching;
 mod builds;
 mod builds_agents;
+mod builds_artifacts;
 mod builds_i18n;
 mod builds_loop;
 mod builds_parse;
+mod builds_phases;
+mod builds_resume;
+mod builds_steps;
 mod builds_topology;
+mod cancel;
 mod context_command;
+mod export_command;
 mod google_auth;
 mod google_auth_i18n;
 mod google_auth_i18n_guide;
 mod google_auth_oauth;
 mod google_auth_utils;
 mod heartbeat;
+mod heartbeat_command;
+mod heartbeat_cycle;
 mod heartbeat_helpers;
 mod keywords;
 mod keywords_data;
+mod link_command;
 mod pipeline;
 mod pipeline_builds;
 mod process_markers;
+mod progress;
 mod prompt_builder;
+mod queue;
 mod routing;
 mod scheduler;
 mod scheduler_action;
@@ -31,20 +44,23 @@ mod setup;
 mod setup_response;
 mod shared_markers;
 mod summarizer;
+mod tenants;
+mod topology_command;
+
+pub(crate) use builds_topology::validate_topology_dir;
 
 use crate::markers::*;
 use omega_core::{
     config::{
-        shellexpand, ApiConfig, AuthConfig, ChannelConfig, HeartbeatConfig, Prompts,
-        SchedulerConfig,
+        ApiConfig, ApprovalConfig, AuthConfig, ChannelConfig, DataDir, HeartbeatConfig,
+        MaintenanceConfig, Prompts, RolesConfig, SchedulerConfig, TenantsConfig, TrustConfig,
     },
+    crypto::SecretStore,
     message::{IncomingMessage, MessageMetadata, OutgoingMessage},
     traits::{Channel, Provider},
 };
-use omega_memory::{audit::AuditLogger, Store};
+use omega_memory::{audit::AuditLogger, ContextReport, Store};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{mpsc, Mutex, Notify};
@@ -68,6 +84,18 @@ pub struct GatewayConfig {
     pub scheduler_config: SchedulerConfig,
     /// HTTP API settings.
     pub api_config: ApiConfig,
+    /// Human-in-the-loop approval policy for dangerous tool calls.
+    pub approval_config: ApprovalConfig,
+    /// Multi-tenant mode: per-tenant workspace, projects, skills and roles.
+    pub tenants_config: TenantsConfig,
+    /// Named roles and their permissions.
+    pub roles_config: RolesConfig,
+    /// Database snapshots, retention and compaction.
+    pub maintenance_config: MaintenanceConfig,
+    /// Handling of turns that consumed untrusted content.
+    pub trust_config: TrustConfig,
+    /// Encrypted secrets store (`None` when encryption is off).
+    pub secrets: Option<Arc<SecretStore>>,
     /// Loaded prompt templates.
     pub prompts: Prompts,
     /// Base data directory (e.g. "~/.omega").
@@ -93,6 +121,12 @@ pub struct Gateway {
     pub(super) heartbeat_config: HeartbeatConfig,
     pub(super) scheduler_config: SchedulerConfig,
     pub(super) api_config: ApiConfig,
+    pub(super) approval_config: ApprovalConfig,
+    pub(super) tenants_config: TenantsConfig,
+    pub(super) roles_config: RolesConfig,
+    pub(super) maintenance_config: MaintenanceConfig,
+    pub(super) trust_config: TrustConfig,
+    pub(super) secrets: Option<Arc<SecretStore>>,
     pub(super) prompts: Prompts,
     pub(super) data_dir: String,
     pub(super) skills: Vec<omega_skills::Skill>,
@@ -101,23 +135,29 @@ pub struct Gateway {
     pub(super) model_fast: String,
     /// Complex model for multi-step autonomous execution (Opus).
     pub(super) model_complex: String,
-    /// Tracks senders with active provider calls. New messages are buffered here.
-    pub(super) active_senders: Mutex<HashMap<String, Vec<IncomingMessage>>>,
-    /// Shared heartbeat interval (minutes) — updated at runtime via `HEARTBEAT_INTERVAL:` marker.
-    pub(super) heartbeat_interval: Arc<AtomicU64>,
-    /// Wakes the heartbeat loop when `HEARTBEAT_INTERVAL:` changes so it re-sleeps with the new value.
+    /// Tracks senders with active provider calls. New messages are queued here.
+    pub(super) active_senders: Mutex<HashMap<String, queue::SenderQueue>>,
+    /// Wakes the heartbeat loop when a user's heartbeat settings change so it re-sleeps.
     pub(super) heartbeat_notify: Arc<Notify>,
-    /// Path to config.toml — used for persisting runtime changes (e.g. heartbeat interval).
+    /// Path to config.toml — used for persisting runtime changes (e.g. WhatsApp enablement).
     pub(super) config_path: String,
     /// Gateway sender — stored so dormant channels can be started on-demand.
     pub(super) gateway_tx: Mutex<Option<mpsc::Sender<IncomingMessage>>>,
+    /// Tool calls waiting on an approve/deny reply, keyed by `channel:sender_id`.
+    pub(super) pending_approvals: approval::PendingApprovals,
+    /// Live progress of running builds, keyed by `channel:sender_id` (for `/build status`).
+    pub(super) active_builds: builds_resume::ActiveBuilds,
+    /// Requests in flight that `/stop` can abort, keyed by `channel:sender_id`.
+    pub(super) active_requests: cancel::ActiveRequests,
+    /// How each sender's last context was fitted to its token budget, keyed
+    /// by `channel:sender_id` (for `/context`).
+    pub(super) last_context: std::sync::Mutex<HashMap<String, ContextReport>>,
 }
 
 impl Gateway {
     /// Create a new gateway from a configuration bundle.
     pub fn new(cfg: GatewayConfig) -> Self {
-        let audit = AuditLogger::new(cfg.memory.pool().clone());
-        let heartbeat_interval = Arc::new(AtomicU64::new(cfg.heartbeat_config.interval_minutes));
+        let audit = AuditLogger::new(&cfg.memory);
         let heartbeat_notify = Arc::new(Notify::new());
         Self {
             provider: cfg.provider,
@@ -129,6 +169,12 @@ impl Gateway {
             heartbeat_config: cfg.heartbeat_config,
             scheduler_config: cfg.scheduler_config,
             api_config: cfg.api_config,
+            approval_config: cfg.approval_config,
+            tenants_config: cfg.tenants_config,
+            roles_config: cfg.roles_config,
+            maintenance_config: cfg.maintenance_config,
+            trust_config: cfg.trust_config,
+            secrets: cfg.secrets,
             prompts: cfg.prompts,
             data_dir: cfg.data_dir,
             skills: cfg.skills,
@@ -136,10 +182,13 @@ impl Gateway {
             model_fast: cfg.model_fast,
             model_complex: cfg.model_complex,
             active_senders: Mutex::new(HashMap::new()),
-            heartbeat_interval,
             heartbeat_notify,
             config_path: cfg.config_path,
             gateway_tx: Mutex::new(None),
+            pending_approvals: Arc::new(Mutex::new(HashMap::new())),
+            active_builds: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            active_requests: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            last_context: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -161,8 +210,9 @@ impl Gateway {
 
         // Ensure workspace CLAUDE.md exists (Claude Code provider only).
         if self.provider.name() == "claude-code" {
-            let workspace = PathBuf::from(shellexpand(&self.data_dir)).join("workspace");
-            let data_dir = PathBuf::from(shellexpand(&self.data_dir));
+            let data_dir = DataDir::new(&self.data_dir);
+            let workspace = data_dir.workspace();
+            let data_dir = data_dir.root().to_path_buf();
             tokio::spawn(async move {
                 crate::claudemd::ensure_claudemd(&workspace, &data_dir).await;
             });
@@ -218,7 +268,7 @@ impl Gateway {
             let api_channels = self.channels.clone();
             let api_uptime = self.uptime;
             let api_tx = tx.clone();
-            let api_audit = AuditLogger::new(self.memory.pool().clone());
+            let api_audit = AuditLogger::new(&self.memory);
             let api_channel_config = self.channel_config.clone();
             Some(tokio::spawn(async move {
                 crate::api::serve(
@@ -255,14 +305,17 @@ impl Gateway {
             let sched_skills = self.skills.clone();
             let sched_prompts = self.prompts.clone();
             let sched_model = self.model_complex.clone();
-            let sched_hb_interval = self.heartbeat_interval.clone();
             let sched_hb_notify = self.heartbeat_notify.clone();
-            let sched_audit = AuditLogger::new(self.memory.pool().clone());
+            let sched_audit = AuditLogger::new(&self.memory);
             let sched_provider_name = self.provider.name().to_string();
             let sched_data_dir = self.data_dir.clone();
-            let sched_config_path = self.config_path.clone();
             let sched_active_start = self.heartbeat_config.active_start.clone();
             let sched_active_end = self.heartbeat_config.active_end.clone();
+            let sched_requests = self.active_requests.clone();
+            let sched_trust = self.trust_config.clone();
+            let sched_tenants = self.tenants_config.clone();
+            let sched_roles = self.roles_config.clone();
+            let sched_approval = self.approval_config.clone();
             Some(tokio::spawn(async move {
                 Self::scheduler_loop(
                     sched_store,
@@ -272,14 +325,17 @@ impl Gateway {
                     sched_skills,
                     sched_prompts,
                     sched_model,
-                    sched_hb_interval,
                     sched_hb_notify,
                     sched_audit,
                     sched_provider_name,
                     sched_data_dir,
-                    sched_config_path,
                     sched_active_start,
                     sched_active_end,
+                    sched_requests,
+                    sched_trust,
+                    sched_tenants,
+                    sched_roles,
+                    sched_approval,
                 )
                 .await;
             }))
@@ -294,15 +350,17 @@ impl Gateway {
             let hb_config = self.heartbeat_config.clone();
             let hb_prompts = self.prompts.clone();
             let hb_memory = self.memory.clone();
-            let hb_interval = self.heartbeat_interval.clone();
             let hb_notify = self.heartbeat_notify.clone();
             let hb_model = self.model_complex.clone();
             let hb_model_fast = self.model_fast.clone();
             let hb_skills = self.skills.clone();
-            let hb_audit = AuditLogger::new(self.memory.pool().clone());
+            let hb_audit = AuditLogger::new(&self.memory);
             let hb_provider_name = self.provider.name().to_string();
             let hb_data_dir = self.data_dir.clone();
-            let hb_config_path = self.config_path.clone();
+            let hb_trust = self.trust_config.clone();
+            let hb_approval = self.approval_config.clone();
+            let hb_tenants = self.tenants_config.clone();
+            let hb_roles = self.roles_config.clone();
             Some(tokio::spawn(async move {
                 Self::heartbeat_loop(
                     hb_provider,
@@ -310,7 +368,6 @@ impl Gateway {
                     hb_config,
                     hb_prompts,
                     hb_memory,
-                    hb_interval,
                     hb_notify,
                     hb_model,
                     hb_model_fast,
@@ -318,7 +375,10 @@ impl Gateway {
                     hb_audit,
                     hb_provider_name,
                     hb_data_dir,
-                    hb_config_path,
+                    hb_trust,
+                    hb_approval,
+                    hb_tenants,
+                    hb_roles,
                 )
                 .await;
             }))
@@ -328,8 +388,9 @@ impl Gateway {
 
         // Spawn CLAUDE.md maintenance loop (Claude Code provider only, 24h interval).
         let claudemd_handle = if self.provider.name() == "claude-code" {
-            let ws = PathBuf::from(shellexpand(&self.data_dir)).join("workspace");
-            let dd = PathBuf::from(shellexpand(&self.data_dir));
+            let data_dir = DataDir::new(&self.data_dir);
+            let ws = data_dir.workspace();
+            let dd = data_dir.root().to_path_buf();
             Some(tokio::spawn(async move {
                 crate::claudemd::claudemd_loop(ws, dd, 24).await;
             }))
@@ -337,6 +398,20 @@ impl Gateway {
             None
         };
 
+        // Spawn database maintenance loop (snapshots, retention, compaction).
+        // SQLite only: a managed database is maintained by its operator.
+        let maintenance_handle = if self.maintenance_config.enabled && self.memory.sqlite().is_ok()
+        {
+            let mt_store = self.memory.clone();
+            let mt_config = self.maintenance_config.clone();
+            let mt_dir = crate::maintenance::backup_dir(&mt_config, &self.data_dir);
+            Some(tokio::spawn(async move {
+                crate::maintenance::maintenance_loop(mt_store, mt_config, mt_dir).await;
+            }))
+        } else {
+            None
+        };
+
         // Main event loop with graceful shutdown.
         loop {
             tokio::select! {
@@ -359,62 +434,56 @@ impl Gateway {
             &sched_handle,
             &hb_handle,
             &claudemd_handle,
+            &maintenance_handle,
             &api_handle,
         )
         .await;
         Ok(())
     }
 
-    /// Dispatch a message: buffer if sender is busy, otherwise process.
+    /// Dispatch a message: queue it if the sender is busy, otherwise process.
     async fn dispatch_message(self: Arc<Self>, incoming: IncomingMessage) {
         let sender_key = format!("{}:{}", incoming.channel, incoming.sender_id);
 
+        // An approve/deny reply must reach the waiting tool call, not the buffer.
+        if self
+            .resolve_pending_approval(&sender_key, &incoming.text)
+            .await
         {
-            let mut active = self.active_senders.lock().await;
-            if let Some(buf) = active.get_mut(&sender_key) {
-                // Sender already has an active call — buffer this message.
-                buf.push(incoming.clone());
-                info!(
-                    "buffered message from {} (active call in progress)",
-                    sender_key
-                );
-                // Skip acknowledgment for WhatsApp self-chat: the ack echoes back
-                // as a new incoming message, creating an infinite loop.
-                if incoming.channel != "whatsapp" {
-                    self.send_text(&incoming, "Got it, I'll get to this next.")
-                        .await;
-                }
+            return;
+        }
+
+        // `/stop` must reach the running request, not queue up behind it.
+        if self.intercept_stop(&incoming).await {
+            return;
+        }
+
+        // `/restart` and `/queue` act on the queue itself.
+        if self.intercept_restart(&incoming).await || self.intercept_queue(&incoming).await {
+            return;
+        }
+
+        // `/build status` must answer while the build itself keeps the sender busy.
+        if self.intercept_build_status(&incoming).await {
+            return;
+        }
+
+        match self.claim_sender(&sender_key, &incoming).await {
+            queue::Claim::Queued => return,
+            queue::Claim::Parallel => {
+                self.handle_cancellable(&sender_key, incoming).await;
                 return;
             }
-            // Mark sender as active (empty buffer).
-            active.insert(sender_key.clone(), Vec::new());
+            queue::Claim::Owner => {}
         }
 
         // Process the message.
-        self.handle_message(incoming).await;
+        self.handle_cancellable(&sender_key, incoming).await;
 
-        // Drain any buffered messages for this sender.
-        loop {
-            let next = {
-                let mut active = self.active_senders.lock().await;
-                let buffer = active.get_mut(&sender_key);
-                match buffer {
-                    Some(buf) if !buf.is_empty() => Some(buf.remove(0)),
-                    _ => {
-                        // No more buffered messages — remove sender from active.
-                        active.remove(&sender_key);
-                        None
-                    }
-                }
-            };
-
-            match next {
-                Some(buffered_msg) => {
-                    info!("processing buffered message from {}", sender_key);
-                    self.handle_message(buffered_msg).await;
-                }
-                None => break,
-            }
+        // Drain queued messages for this sender, merged into turns.
+        while let Some(next) = self.next_queued(&sender_key).await {
+            info!("processing queued message from {}", sender_key);
+            self.handle_cancellable(&sender_key, next).await;
         }
     }
 
@@ -425,6 +494,7 @@ impl Gateway {
         sched_handle: &Option<tokio::task::JoinHandle<()>>,
         hb_handle: &Option<tokio::task::JoinHandle<()>>,
         claudemd_handle: &Option<tokio::task::JoinHandle<()>>,
+        maintenance_handle: &Option<tokio::task::JoinHandle<()>>,
         api_handle: &Option<tokio::task::JoinHandle<()>>,
     ) {
         info!("Shutting down...");
@@ -440,6 +510,9 @@ impl Gateway {
         if let Some(h) = claudemd_handle {
             h.abort();
         }
+        if let Some(h) = maintenance_handle {
+            h.abort();
+        }
         if let Some(h) = api_handle {
             h.abort();
         }
@@ -516,6 +589,7 @@ impl Gateway {
             metadata: MessageMetadata::default(),
             reply_target: incoming.reply_target.clone(),
             plain_text: true,
+            ..Default::default()
         };
 
         if let Some(channel) = self.channels.get(&incoming.channel) {
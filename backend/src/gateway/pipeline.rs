@@ -1,10 +1,13 @@
 //! Message processing pipeline — the main handle_message flow.
 
-use std::sync::atomic::Ordering;
-
 use tracing::{error, info, warn};
 
-use omega_core::{context::ContextNeeds, message::IncomingMessage, sanitize};
+use omega_core::{
+    config::DataDir,
+    context::{ContentBlock, ContextNeeds},
+    message::IncomingMessage,
+    sanitize,
+};
 use omega_memory::{
     audit::{AuditEntry, AuditStatus},
     detect_language,
@@ -62,7 +65,15 @@ impl Gateway {
         }
 
         // --- 2. SANITIZE INPUT ---
-        let sanitized = sanitize::sanitize(&incoming.text);
+        // Attachment references are added by the gateway only; typed ones are escaped.
+        incoming.text = omega_core::media::escape_references(&incoming.text);
+        // Webhook payloads come from another system, not the user: label them.
+        let sanitized = match incoming.source {
+            Some(ref source) => {
+                sanitize::label_untrusted(&format!("webhook:{source}"), &incoming.text)
+            }
+            None => sanitize::sanitize(&incoming.text),
+        };
         if sanitized.was_modified {
             warn!(
                 "sanitized input from {}: {:?}",
@@ -73,28 +84,21 @@ impl Gateway {
         let mut clean_incoming = incoming.clone();
         clean_incoming.text = sanitized.text;
 
-        // --- 2a. SAVE INCOMING IMAGE ATTACHMENTS ---
-        let _inbox_guard = if !incoming.attachments.is_empty() {
-            let inbox = ensure_inbox_dir(&self.data_dir);
-            let paths = save_attachments_to_inbox(&inbox, &incoming.attachments);
-            for path in &paths {
-                clean_incoming.text = format!(
-                    "[Attached image: {}]\n{}",
-                    path.display(),
-                    clean_incoming.text
-                );
-            }
-            InboxGuard::new(paths)
-        } else {
-            InboxGuard::new(Vec::new())
-        };
-
         // --- 2b. CROSS-CHANNEL USER IDENTITY ---
+        // Auto-aliasing assumes a single person behind the instance; tenants
+        // are different people, so it is skipped in multi-tenant mode.
         let original_sender_id = incoming.sender_id.clone();
         if let Ok(true) = self.memory.is_new_user(&incoming.sender_id).await {
-            if let Ok(Some(canonical_id)) =
-                self.memory.find_canonical_user(&incoming.sender_id).await
-            {
+            let canonical = if self.tenants_config.enabled {
+                None
+            } else {
+                self.memory
+                    .find_canonical_user(&incoming.sender_id)
+                    .await
+                    .ok()
+                    .flatten()
+            };
+            if let Some(canonical_id) = canonical {
                 let _ = self
                     .memory
                     .create_alias(&incoming.sender_id, &canonical_id)
@@ -124,20 +128,87 @@ impl Gateway {
             }
         }
 
+        // --- 2c. TENANT (workspace, projects, skills, role) ---
+        let tenant = self.tenant_scope(&incoming.sender_id).await;
+        if let Some(ref name) = tenant.name {
+            info!(
+                "[{}] tenant: {name} ({})",
+                incoming.channel,
+                tenant.role.as_str()
+            );
+        }
+
+        // --- 2d. SAVE INCOMING IMAGE ATTACHMENTS (tenant inbox) ---
+        let _inbox_guard = if !incoming.attachments.is_empty() {
+            let inbox = ensure_inbox_dir(&self.tenant_data_dir(&tenant));
+            let paths = save_attachments_to_inbox(&inbox, &incoming.attachments);
+            for path in &paths {
+                clean_incoming.text = format!(
+                    "[Attached image: {}]\n{}",
+                    path.display(),
+                    clean_incoming.text
+                );
+            }
+            InboxGuard::new(paths)
+        } else {
+            InboxGuard::new(Vec::new())
+        };
+
+        // --- 2e. KEEP ATTACHMENT MEDIA (content blocks + history references) ---
+        let saved_media = if incoming.attachments.is_empty() {
+            Vec::new()
+        } else {
+            let media_dir = DataDir::new(&self.tenant_data_dir(&tenant)).media();
+            save_attachments_to_media(&media_dir, &incoming.attachments)
+        };
+        for (path, block) in &saved_media {
+            // Images are already referenced through the inbox copy.
+            if matches!(block, ContentBlock::Document { .. }) {
+                clean_incoming.text = format!(
+                    "[Attached document: {}]\n{}",
+                    path.display(),
+                    clean_incoming.text
+                );
+            }
+        }
+
         // --- 3. ACTIVE PROJECT (needed by commands + pipeline) ---
         let active_project: Option<String> = self
             .memory
             .get_fact(&incoming.sender_id, "active_project")
             .await
             .ok()
-            .flatten();
+            .flatten()
+            .filter(|p| tenant.permissions.allows_project(p));
 
         // --- 3a. COMMAND DISPATCH ---
         // Hot-reload projects from disk so newly added/removed projects are visible
         // without restarting the gateway (consistent with process_markers.rs).
-        let fresh_projects = omega_skills::load_projects(&self.data_dir);
+        let fresh_projects = self.tenant_projects(&tenant);
         let projects = &fresh_projects;
+        let skills = self.tenant_skills(&tenant);
+        // Set by /retry: the model to answer the previous message with.
+        let mut retry_model: Option<String> = None;
         if let Some(cmd) = commands::Command::parse(&clean_incoming.text) {
+            let role = tenant.role_name.as_deref().unwrap_or_default();
+            let denial = if !cmd.allowed_for(tenant.role) {
+                Some(format!(
+                    "tenant role '{}' may not run /{}",
+                    tenant.role.as_str(),
+                    cmd.name()
+                ))
+            } else if !tenant.permissions.allows_command(cmd.name())
+                || (matches!(cmd, commands::Command::Build) && !tenant.permissions.builds)
+            {
+                Some(format!("role '{role}' may not run /{}", cmd.name()))
+            } else {
+                None
+            };
+            if let Some(reason) = denial {
+                self.deny_access(&incoming, reason, "tenant_denied").await;
+                return;
+            }
+
             if matches!(cmd, commands::Command::Forget) {
                 let response = self
                     .handle_forget(&incoming.channel, &incoming.sender_id)
@@ -174,7 +245,7 @@ impl Gateway {
                             let ch = ch.clone();
                             let target = target.clone();
                             let _ = ch.send_typing(&target).await;
-                            Some(tokio::spawn(async move {
+                            Some(self.spawn_scoped(&incoming, async move {
                                 loop {
                                     tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                                     if ch.send_typing(&target).await.is_err() {
@@ -197,6 +268,42 @@ impl Gateway {
                 return;
             }
 
+            // --- /build intercept (resume/status need the orchestrator) ---
+            if matches!(cmd, commands::Command::Build) {
+                self.handle_build_command(&incoming).await;
+                return;
+            }
+
+            // --- /topologies intercept ---
+            if matches!(cmd, commands::Command::Topologies) {
+                self.handle_topologies_command(&incoming).await;
+                return;
+            }
+
+            // --- /heartbeat intercept (changes must wake the heartbeat loop) ---
+            if matches!(cmd, commands::Command::Heartbeat) {
+                self.handle_heartbeat_command(&incoming, active_project.as_deref())
+                    .await;
+                return;
+            }
+
+            // --- /link and /unlink intercepts (need the pre-alias sender id) ---
+            if matches!(cmd, commands::Command::Link) {
+                self.handle_link_command(&incoming).await;
+                return;
+            }
+            if matches!(cmd, commands::Command::Unlink) {
+                self.handle_unlink_command(&incoming, &original_sender_id)
+                    .await;
+                return;
+            }
+
+            // --- /export intercept (sends the archive as a document) ---
+            if matches!(cmd, commands::Command::Export) {
+                self.handle_export_command(&incoming).await;
+                return;
+            }
+
             // --- /context intercept ---
             if matches!(cmd, commands::Command::Context) {
                 self.handle_context_command(&incoming, active_project.as_deref())
@@ -204,29 +311,51 @@ impl Gateway {
                 return;
             }
 
-            let ctx = commands::CommandContext {
-                store: &self.memory,
-                channel: &incoming.channel,
-                sender_id: &incoming.sender_id,
-                text: &clean_incoming.text,
-                uptime: &self.uptime,
-                provider_name: self.provider.name(),
-                skills: &self.skills,
-                projects,
-                heartbeat_enabled: self.heartbeat_config.enabled,
-                heartbeat_interval_mins: self.heartbeat_interval.load(Ordering::Relaxed),
-                active_project: active_project.as_deref(),
-                base_prompt_chars: self.prompts.sections.iter().map(|(_, b)| b.len()).sum(),
-            };
-            let response = commands::handle(cmd, &ctx).await;
-
-            if response.trim() == "WHATSAPP_QR" {
-                self.handle_whatsapp_qr(&incoming).await;
+            // --- /branch intercept (summarizes the conversation it leaves) ---
+            if matches!(cmd, commands::Command::Branch) {
+                self.handle_branch_command(&incoming, active_project.as_deref().unwrap_or(""))
+                    .await;
                 return;
             }
 
-            self.send_text(&incoming, &response).await;
-            return;
+            // --- /retry intercept (the previous message is answered again below) ---
+            if matches!(cmd, commands::Command::Retry) {
+                let Some((text, model)) = self
+                    .prepare_retry(
+                        &incoming,
+                        &clean_incoming.text,
+                        active_project.as_deref().unwrap_or(""),
+                    )
+                    .await
+                else {
+                    return;
+                };
+                incoming.text = text.clone();
+                clean_incoming.text = text;
+                retry_model = model;
+            } else {
+                let ctx = commands::CommandContext {
+                    store: &self.memory,
+                    channel: &incoming.channel,
+                    sender_id: &incoming.sender_id,
+                    text: &clean_incoming.text,
+                    uptime: &self.uptime,
+                    provider_name: self.provider.name(),
+                    skills: &skills,
+                    projects,
+                    active_project: active_project.as_deref(),
+                    base_prompt_chars: self.prompts.sections.iter().map(|(_, b)| b.len()).sum(),
+                };
+                let response = commands::handle(cmd, &ctx).await;
+
+                if response.trim() == "WHATSAPP_QR" {
+                    self.handle_whatsapp_qr(&incoming).await;
+                    return;
+                }
+
+                self.send_text(&incoming, &response).await;
+                return;
+            }
         }
 
         // --- 3b. WHATSAPP HELP INTERCEPT ---
@@ -247,6 +376,26 @@ impl Gateway {
             return;
         }
 
+        // --- 3c. ROLE: PROVIDER AND MODEL ---
+        let role = tenant.role_name.as_deref().unwrap_or_default();
+        let provider_name = self.provider.name();
+        let model = tenant
+            .permissions
+            .model_for(retry_model.as_deref().unwrap_or(&self.model_fast));
+        let denial = if !tenant.permissions.allows_provider(provider_name) {
+            Some(format!(
+                "role '{role}' may not use provider {provider_name}"
+            ))
+        } else if model.is_none() {
+            Some(format!("role '{role}' has no allowed model"))
+        } else {
+            None
+        };
+        if let Some(reason) = denial {
+            self.deny_access(&incoming, reason, "role_denied").await;
+            return;
+        }
+
         // --- 4. TYPING INDICATOR ---
         let typing_channel = self.channels.get(&incoming.channel).cloned();
         let typing_target = incoming.reply_target.clone();
@@ -255,7 +404,7 @@ impl Gateway {
                 let ch = ch.clone();
                 let target = target.clone();
                 let _ = ch.send_typing(&target).await;
-                Some(tokio::spawn(async move {
+                Some(self.spawn_scoped(&incoming, async move {
                     loop {
                         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                         if ch.send_typing(&target).await.is_err() {
@@ -312,10 +461,17 @@ impl Gateway {
         // All context sections are always injected — no keyword gating.
         // This eliminates false negatives (missed intent) from fragile keyword
         // matching. The token cost is small; reliability wins.
-        let context_needs = ContextNeeds::default();
+        // The token budget trims them (lowest priority first) for small models.
+        let context_needs = ContextNeeds {
+            token_budget: self
+                .memory
+                .token_budget(model.as_deref().unwrap_or(&self.model_fast)),
+            ..Default::default()
+        };
 
-        let system_prompt =
-            self.build_system_prompt(&incoming, active_project.as_deref(), projects);
+        let system_prompt = self
+            .build_system_prompt(&incoming, active_project.as_deref(), projects, &tenant)
+            .await;
 
         info!(
             "[{}] system prompt: ~{} tokens ({} chars)",
@@ -326,7 +482,7 @@ impl Gateway {
 
         let context = match self
             .memory
-            .build_context(
+            .build_context_with_report(
                 &clean_incoming,
                 &system_prompt,
                 &context_needs,
@@ -334,7 +490,22 @@ impl Gateway {
             )
             .await
         {
-            Ok(ctx) => ctx,
+            Ok((ctx, report)) => {
+                let dropped = report.dropped_tokens();
+                if dropped > 0 {
+                    info!(
+                        "[{}] context: ~{} tokens of {} budget, ~{dropped} tokens left out",
+                        incoming.channel,
+                        report.total_tokens(),
+                        report.budget
+                    );
+                }
+                self.last_context.lock().unwrap().insert(
+                    format!("{}:{}", incoming.channel, incoming.sender_id),
+                    report,
+                );
+                ctx
+            }
             Err(e) => {
                 error!("failed to build context: {e}");
                 if let Some(h) = typing_handle {
@@ -350,12 +521,22 @@ impl Gateway {
         // Claude Code CLI: always activate all MCP servers (cheap — just a config write).
         // HTTP providers: use keyword-based trigger matching (real per-message cost).
         let mcp_servers = if self.provider.name() == "claude-code" {
-            omega_skills::collect_all_mcp_servers(&self.skills)
+            omega_skills::collect_all_mcp_servers(&skills)
         } else {
-            omega_skills::match_skill_triggers(&self.skills, &clean_incoming.text)
+            omega_skills::match_skill_triggers(&skills, &clean_incoming.text)
         };
         let mut context = context;
         context.mcp_servers = mcp_servers;
+        context.workspace = tenant.workspace();
+        context.stable_prefix_len = self.prompt_sections().len();
+
+        // --- 4b-MEDIA. ATTACHMENTS AS CONTENT BLOCKS ---
+        // The Claude Code CLI reads attachments by path; API providers get them inline.
+        if self.provider.name() != "claude-code" {
+            context.current_media = saved_media.iter().map(|(_, b)| b.clone()).collect();
+            let media_dir = DataDir::new(&self.tenant_data_dir(&tenant)).media();
+            attach_history_media(&mut context.history, &media_dir);
+        }
 
         // --- 4c. SESSION-BASED PROMPT PERSISTENCE (Claude Code CLI only) ---
         let project_key = active_project.as_deref().unwrap_or("");
@@ -415,9 +596,33 @@ impl Gateway {
         // Build requests were handled above via early return to handle_build_request().
         info!(
             "[{}] classification: DIRECT → model {}",
-            incoming.channel, self.model_fast
+            incoming.channel,
+            model.as_deref().unwrap_or_default()
         );
-        context.model = Some(self.model_fast.clone());
+        context.model = model;
+        if let Some(ref tools) = tenant.permissions.tools {
+            context.allowed_tools = Some(tools.clone());
+        }
+        context.denied_tools = tenant.denied_tools();
+
+        // --- 5a. TOOL APPROVAL GATE ---
+        // Keyed by the pre-alias sender ID — the key `dispatch_message` sees.
+        let sender_key = format!("{}:{}", incoming.channel, original_sender_id);
+        context.approval = self.approval_gate(&incoming, &sender_key).await;
+
+        // --- 5b. ACTION TOOLS ---
+        // API providers offer gateway actions as native tools; Claude Code keeps markers.
+        context.action_tools = true;
+
+        // --- 5c. ATTACHMENT REFERENCES ---
+        // The stored user message keeps its media so follow-up questions can see it.
+        if !saved_media.is_empty() {
+            let refs: Vec<String> = saved_media
+                .iter()
+                .map(|(path, block)| omega_core::media::reference(path, block))
+                .collect();
+            incoming.text = format!("{}\n{}", refs.join("\n"), incoming.text);
+        }
 
         self.handle_direct_response(
             &incoming,
@@ -4,8 +4,7 @@ use super::keywords::SYSTEM_FACT_KEYS;
 use super::Gateway;
 use crate::markers::*;
 use crate::task_confirmation::{self, MarkerResult};
-use omega_core::{config, config::shellexpand, message::IncomingMessage};
-use std::sync::atomic::Ordering;
+use omega_core::{config::shellexpand, message::IncomingMessage};
 use tracing::{error, info, warn};
 
 impl Gateway {
@@ -49,6 +48,7 @@ impl Gateway {
                     Ok(id) => {
                         info!("scheduled task {id}: {desc} at {due_at}");
                         marker_results.push(MarkerResult::TaskCreated {
+                            id,
                             description: desc,
                             due_at,
                             repeat,
@@ -97,6 +97,7 @@ impl Gateway {
                     Ok(id) => {
                         info!("scheduled action task {id}: {desc} at {due_at}");
                         marker_results.push(MarkerResult::TaskCreated {
+                            id,
                             description: desc,
                             due_at,
                             repeat,
@@ -121,7 +122,8 @@ impl Gateway {
 
         // PROJECT_DEACTIVATE must run BEFORE PROJECT_ACTIVATE so that combined
         // markers (deactivate old + activate new) read the old project name first.
-        let fresh_projects = omega_skills::load_projects(&self.data_dir);
+        let tenant = self.tenant_scope(&incoming.sender_id).await;
+        let fresh_projects = self.tenant_projects(&tenant);
         if has_project_deactivate(text) {
             // Create .disabled marker for current project to stop its heartbeat.
             if let Ok(Some(current)) = self
@@ -266,15 +268,9 @@ impl Gateway {
             *text = strip_purge_marker(text);
         }
 
-        // HEARTBEAT_ADD / HEARTBEAT_REMOVE / HEARTBEAT_INTERVAL
+        // HEARTBEAT_ADD / HEARTBEAT_REMOVE / HEARTBEAT_INTERVAL (sender's own heartbeat)
         let heartbeat_actions = extract_heartbeat_markers(text);
         if !heartbeat_actions.is_empty() {
-            let hb_project = if project.is_empty() {
-                None
-            } else {
-                Some(project)
-            };
-            apply_heartbeat_changes(&heartbeat_actions, hb_project);
             for action in &heartbeat_actions {
                 match action {
                     HeartbeatAction::Add(item) => info!("heartbeat: added '{item}' to checklist"),
@@ -282,13 +278,23 @@ impl Gateway {
                         info!("heartbeat: removed '{item}' from checklist")
                     }
                     HeartbeatAction::SetInterval(mins) => {
-                        self.heartbeat_interval.store(*mins, Ordering::Relaxed);
-                        config::patch_heartbeat_interval(&self.config_path, *mins);
-                        self.heartbeat_notify.notify_one();
-                        info!("heartbeat: interval changed to {mins} minutes");
+                        info!("heartbeat: interval changed to {mins} minutes")
                     }
                 }
             }
+            super::heartbeat_helpers::update_user_heartbeat(
+                &self.memory,
+                &incoming.sender_id,
+                &incoming.channel,
+                incoming
+                    .reply_target
+                    .as_deref()
+                    .unwrap_or(&incoming.sender_id),
+                &heartbeat_actions,
+                project,
+                &self.heartbeat_notify,
+            )
+            .await;
             *text = strip_heartbeat_markers(text);
         }
 
@@ -300,7 +306,12 @@ impl Gateway {
             } else {
                 Some(project)
             };
-            apply_suppress_actions(&suppress_actions, hb_project);
+            apply_suppress_actions(
+                &self.data_dir,
+                &incoming.sender_id,
+                &suppress_actions,
+                hb_project,
+            );
             *text = strip_suppress_section_markers(text);
         }
 
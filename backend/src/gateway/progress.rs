@@ -0,0 +1,87 @@
+//! Live progress for long requests.
+//!
+//! Providers that can observe their own tool use (the Claude Code CLI's
+//! stream-json output) report short status lines through a
+//! [`ChannelProgress`]; the gateway relays them to the user's channel.
+//! Throttling happens in the provider. While real progress is flowing, the
+//! generic "still working" nudges are skipped, so the first status line
+//! carries the stop button instead.
+
+use super::cancel::stop_button;
+use super::Gateway;
+use async_trait::async_trait;
+use omega_core::{
+    context::ProgressSink,
+    message::{IncomingMessage, MessageMetadata, OutgoingMessage},
+    traits::{Channel, ProgressReporter},
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Relays provider status lines to one user's channel.
+pub(super) struct ChannelProgress {
+    channel: Arc<dyn Channel>,
+    reply_target: Option<String>,
+    last_sent: Mutex<Option<Instant>>,
+}
+
+impl ChannelProgress {
+    /// Whether a status line was sent within the last `window`.
+    pub(super) fn reported_within(&self, window: Duration) -> bool {
+        self.last_sent
+            .lock()
+            .ok()
+            .and_then(|last| *last)
+            .is_some_and(|t| t.elapsed() < window)
+    }
+}
+
+#[async_trait]
+impl ProgressReporter for ChannelProgress {
+    async fn report(&self, status: &str) {
+        let first = self.last_sent.lock().map(|l| l.is_none()).unwrap_or(false);
+        let msg = OutgoingMessage {
+            text: format!("{status}…"),
+            metadata: MessageMetadata::default(),
+            reply_target: self.reply_target.clone(),
+            // File paths and commands routinely contain `_` and `*`.
+            plain_text: true,
+            buttons: if first {
+                vec![stop_button()]
+            } else {
+                Vec::new()
+            },
+            ..Default::default()
+        };
+        match self.channel.send(msg).await {
+            Ok(()) => {
+                if let Ok(mut last) = self.last_sent.lock() {
+                    *last = Some(Instant::now());
+                }
+            }
+            Err(e) => debug!("progress: failed to send status: {e}"),
+        }
+    }
+}
+
+impl Gateway {
+    /// Progress reporter for a request from `incoming`, if its channel is known.
+    pub(super) fn channel_progress(
+        &self,
+        incoming: &IncomingMessage,
+    ) -> Option<Arc<ChannelProgress>> {
+        let channel = self.channels.get(&incoming.channel)?.clone();
+        Some(Arc::new(ChannelProgress {
+            channel,
+            reply_target: incoming.reply_target.clone(),
+            last_sent: Mutex::new(None),
+        }))
+    }
+
+    /// [`channel_progress`](Self::channel_progress) as a context sink.
+    pub(super) fn progress_sink(&self, incoming: &IncomingMessage) -> Option<ProgressSink> {
+        self.channel_progress(incoming)
+            .map(|p| ProgressSink(p as Arc<dyn ProgressReporter>))
+    }
+}
@@ -5,29 +5,33 @@
 //! (missed intent) and false positives (irrelevant context). All sections are
 //! now always injected — the token cost is small and reliability wins.
 
-use std::sync::atomic::Ordering;
-
+use omega_core::config::DataDir;
 use omega_core::message::IncomingMessage;
 
+use super::tenants::TenantScope;
 use super::Gateway;
 use crate::markers::*;
 
 impl Gateway {
+    /// All sections from SYSTEM_PROMPT.md in order — the stable prompt prefix.
+    pub(super) fn prompt_sections(&self) -> String {
+        self.prompts
+            .sections
+            .iter()
+            .map(|(_, body)| body.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
     /// Build the system prompt with all context sections always injected.
-    pub(super) fn build_system_prompt(
+    pub(super) async fn build_system_prompt(
         &self,
         incoming: &IncomingMessage,
         active_project: Option<&str>,
         projects: &[omega_skills::Project],
+        tenant: &TenantScope,
     ) -> String {
-        // Concatenate all sections from SYSTEM_PROMPT.md in order.
-        let mut prompt = self
-            .prompts
-            .sections
-            .iter()
-            .map(|(_, body)| body.as_str())
-            .collect::<Vec<_>>()
-            .join("\n\n");
+        let mut prompt = self.prompt_sections();
 
         prompt.push_str(&format!(
             "\n\nYou are running on provider '{}', model '{}'.",
@@ -50,6 +54,10 @@ impl Gateway {
         }
 
         // Always-on project awareness (compact hint, ~40-50 tokens)
+        let projects_dir = DataDir::new(&self.tenant_data_dir(tenant))
+            .projects()
+            .display()
+            .to_string();
         if !projects.is_empty() {
             let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
             let active_note = match active_project {
@@ -57,14 +65,14 @@ impl Gateway {
                 None => String::new(),
             };
             prompt.push_str(&format!(
-                "\n\nAvailable projects: [{}]{}. When conversation aligns with a project domain, activate it. For new recurring domains, suggest creating a project (~/.omega/projects/<name>/ROLE.md). User commands: /projects, /project <name>, /project off.",
+                "\n\nAvailable projects: [{}]{}. When conversation aligns with a project domain, activate it. For new recurring domains, suggest creating a project ({projects_dir}/<name>/ROLE.md). User commands: /projects, /project <name>, /project off.",
                 names.join(", "),
                 active_note,
             ));
         } else {
-            prompt.push_str(
-                "\n\nNo projects yet. When the user works in a recurring domain (trading, real estate, fitness...), suggest creating a project (~/.omega/projects/<name>/ROLE.md). User commands: /projects, /project <name>, /project off."
-            );
+            prompt.push_str(&format!(
+                "\n\nNo projects yet. When the user works in a recurring domain (trading, real estate, fitness...), suggest creating a project ({projects_dir}/<name>/ROLE.md). User commands: /projects, /project <name>, /project off."
+            ));
         }
 
         // Active project ROLE.md — always injected when a project is active
@@ -76,8 +84,8 @@ impl Gateway {
                 ));
                 // Inject project-declared skills
                 if !proj.skills.is_empty() {
-                    let project_skills: Vec<_> = self
-                        .skills
+                    let skills = self.tenant_skills(tenant);
+                    let project_skills: Vec<_> = skills
                         .iter()
                         .filter(|s| proj.skills.contains(&s.name))
                         .collect();
@@ -103,18 +111,35 @@ impl Gateway {
         }
 
         if self.heartbeat_config.enabled {
-            let checklist = match active_project {
-                Some(proj) => read_project_heartbeat_file(proj),
-                None => read_heartbeat_file(),
-            };
-            if let Some(checklist) = checklist {
-                prompt
-                    .push_str("\n\nCurrent heartbeat checklist (items monitored periodically):\n");
-                prompt.push_str(&checklist);
+            let heartbeat = self
+                .memory
+                .get_heartbeat_settings(&incoming.sender_id)
+                .await
+                .ok()
+                .flatten();
+            if let Some(file) =
+                active_project.and_then(|proj| read_project_heartbeat_file(&self.data_dir, proj))
+            {
+                prompt.push_str("\n\nProject heartbeat instructions (HEARTBEAT.md):\n");
+                prompt.push_str(file.trim());
             }
-            let mins = self.heartbeat_interval.load(Ordering::Relaxed);
+            let items = self
+                .memory
+                .get_heartbeat_items(&incoming.sender_id, active_project.unwrap_or(""))
+                .await
+                .unwrap_or_default();
+            if !items.is_empty() {
+                prompt.push_str(
+                    "\n\nCurrent heartbeat checklist (items monitored periodically; reference items by [id]):\n",
+                );
+                prompt.push_str(&render_heartbeat_items(&items));
+            }
+            let pulse = match &heartbeat {
+                Some(h) if h.enabled => format!("every {} minutes", h.interval_minutes),
+                _ => "off".to_string(),
+            };
             prompt.push_str(&format!(
-                "\n\nHeartbeat pulse: every {mins} minutes. You can report this when asked and change it with HEARTBEAT_INTERVAL: <1-1440>."
+                "\n\nHeartbeat pulse for this user: {pulse}. You can report this when asked and change it with HEARTBEAT_INTERVAL: <1-1440>. The user manages their own heartbeat with /heartbeat."
             ));
         }
 
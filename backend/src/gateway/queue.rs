@@ -0,0 +1,333 @@
+//! Messages that arrive while the sender already has a request running.
+//!
+//! `dispatch_message` claims a [`SenderQueue`] per `channel:sender_id` before
+//! handling a message. What happens to messages arriving while it is held
+//! depends on the sender's `/queue` mode ([`QueueMode`]):
+//!
+//! - `merge` (default): they wait, and the run of plain messages is answered
+//!   as one follow-up turn, so a correction lands together with what it corrects.
+//! - `ask`: the same, and the acknowledgement offers `/restart`, which aborts
+//!   the running request and re-runs it merged with the queued messages.
+//! - `parallel`: they are handled right away, concurrently.
+//!
+//! Commands are never merged — each queued command runs as its own turn.
+//! Every queued message is acknowledged with its position, and `/queue`
+//! answers immediately with the live queue length while the sender is busy.
+
+use super::Gateway;
+use crate::commands::{self, QueueMode};
+use crate::i18n;
+use omega_core::message::{IncomingMessage, MessageMetadata, OutgoingMessage, ReplyButton};
+use tracing::{error, info};
+
+/// The request a sender has running and the messages waiting behind it.
+#[derive(Default)]
+pub(crate) struct SenderQueue {
+    /// The message being handled now.
+    pub current: Option<IncomingMessage>,
+    pub queued: Vec<IncomingMessage>,
+}
+
+/// Outcome of [`Gateway::claim_sender`].
+pub(super) enum Claim {
+    /// The sender was idle — the caller handles the message and drains the queue.
+    Owner,
+    /// The message was queued behind the running request.
+    Queued,
+    /// `parallel` mode — the caller handles the message alongside the running one.
+    Parallel,
+}
+
+/// Inline button that sends `/restart`.
+fn restart_button() -> ReplyButton {
+    ReplyButton {
+        label: "🔁 /restart".to_string(),
+        command: "/restart".to_string(),
+    }
+}
+
+impl Gateway {
+    /// Claim `sender_key` for `incoming`, or queue it behind the running request.
+    pub(super) async fn claim_sender(&self, sender_key: &str, incoming: &IncomingMessage) -> Claim {
+        {
+            let mut active = self.active_senders.lock().await;
+            if !active.contains_key(sender_key) {
+                active.insert(
+                    sender_key.to_string(),
+                    SenderQueue {
+                        current: Some(incoming.clone()),
+                        queued: Vec::new(),
+                    },
+                );
+                return Claim::Owner;
+            }
+        }
+
+        let (sender_id, lang) = self.sender_id_and_lang(incoming).await;
+        let mode = QueueMode::load(&self.memory, &sender_id).await;
+
+        let position = {
+            let mut active = self.active_senders.lock().await;
+            match active.get_mut(sender_key) {
+                // The running request finished while the mode was looked up.
+                None => {
+                    active.insert(
+                        sender_key.to_string(),
+                        SenderQueue {
+                            current: Some(incoming.clone()),
+                            queued: Vec::new(),
+                        },
+                    );
+                    return Claim::Owner;
+                }
+                Some(_) if mode == QueueMode::Parallel => {
+                    info!("handling message from {sender_key} in parallel");
+                    return Claim::Parallel;
+                }
+                Some(queue) => {
+                    queue.queued.push(incoming.clone());
+                    queue.queued.len()
+                }
+            }
+        };
+        info!("queued message from {sender_key} at position {position} (active call in progress)");
+
+        // Skip acknowledgment for WhatsApp self-chat: the ack echoes back
+        // as a new incoming message, creating an infinite loop.
+        if incoming.channel == "whatsapp" {
+            return Claim::Queued;
+        }
+        let mut text = i18n::queued_ack(&lang, position);
+        let mut buttons = Vec::new();
+        if mode == QueueMode::Ask && !is_command(&incoming.text) {
+            text.push(' ');
+            text.push_str(i18n::t("queue_restart_offer", &lang));
+            buttons.push(restart_button());
+        }
+        let msg = OutgoingMessage {
+            text,
+            metadata: MessageMetadata::default(),
+            reply_target: incoming.reply_target.clone(),
+            buttons,
+            ..Default::default()
+        };
+        if let Some(channel) = self.channels.get(&incoming.channel) {
+            if let Err(e) = channel.send(msg).await {
+                error!("failed to send message: {e}");
+            }
+        }
+        Claim::Queued
+    }
+
+    /// The next turn queued for `sender_key`, which becomes the current one.
+    /// Releases the sender when the queue is empty.
+    pub(super) async fn next_queued(&self, sender_key: &str) -> Option<IncomingMessage> {
+        let mut active = self.active_senders.lock().await;
+        let queue = active.get_mut(sender_key)?;
+        match take_turn(&mut queue.queued) {
+            Some(next) => {
+                queue.current = Some(next.clone());
+                Some(next)
+            }
+            None => {
+                active.remove(sender_key);
+                None
+            }
+        }
+    }
+
+    /// Answer `/queue` while the sender is busy, with the live queue length.
+    ///
+    /// Called before the busy-sender buffer. Returns `false` (not `/queue`, or
+    /// the sender is idle) to let the command take the normal path.
+    pub(super) async fn intercept_queue(&self, incoming: &IncomingMessage) -> bool {
+        if command_word(&incoming.text) != Some("/queue") {
+            return false;
+        }
+        let sender_key = format!("{}:{}", incoming.channel, incoming.sender_id);
+        let queued = match self.active_senders.lock().await.get(&sender_key) {
+            Some(queue) => queue.queued.len(),
+            None => return false,
+        };
+        let (sender_id, _) = self.sender_id_and_lang(incoming).await;
+        let text = commands::queue_text(&self.memory, &sender_id, &incoming.text, queued).await;
+        self.send_text(incoming, &text).await;
+        true
+    }
+
+    /// Answer `/restart` while the sender is busy: abort the running request
+    /// and re-run it merged with the messages queued behind it.
+    ///
+    /// Called before the busy-sender buffer. Returns `false` when the sender
+    /// is idle, so `/restart` takes the normal path and reports that there is
+    /// nothing to restart.
+    pub(super) async fn intercept_restart(&self, incoming: &IncomingMessage) -> bool {
+        if command_word(&incoming.text) != Some("/restart") {
+            return false;
+        }
+        let sender_key = format!("{}:{}", incoming.channel, incoming.sender_id);
+        let (sender_id, lang) = self.sender_id_and_lang(incoming).await;
+        let canonical_key = format!("{}:{}", incoming.channel, sender_id);
+        // A build is restarted with `/stop` and `/build resume`, not here.
+        let building = self
+            .active_builds
+            .lock()
+            .unwrap()
+            .contains_key(&canonical_key);
+
+        let restarted = {
+            let mut active = self.active_senders.lock().await;
+            let Some(queue) = active.get_mut(&sender_key) else {
+                return false;
+            };
+            let restartable = queue.current.as_ref().is_some_and(|current| {
+                !building
+                    && !is_command(&current.text)
+                    && queue.queued.iter().any(|m| !is_command(&m.text))
+            });
+            match queue.current.clone() {
+                Some(current) if restartable && self.abort_message(current.id) => {
+                    queue.queued.insert(0, current);
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        if restarted {
+            info!("restarting request from {sender_key} with queued messages");
+            self.send_text(incoming, i18n::t("queue_restarting", &lang))
+                .await;
+        } else {
+            self.send_text(incoming, i18n::t("nothing_to_restart", &lang))
+                .await;
+        }
+        true
+    }
+
+    /// Canonical sender id and preferred language for `incoming`.
+    async fn sender_id_and_lang(&self, incoming: &IncomingMessage) -> (String, String) {
+        let sender_id = self
+            .memory
+            .resolve_sender_id(&incoming.sender_id)
+            .await
+            .unwrap_or_else(|_| incoming.sender_id.clone());
+        let lang = self
+            .memory
+            .get_fact(&sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        (sender_id, lang)
+    }
+}
+
+/// First word of `text` without an `@botname` suffix, if it is a command.
+fn command_word(text: &str) -> Option<&str> {
+    let first = text.split_whitespace().next()?;
+    let cmd = first.split('@').next().unwrap_or(first);
+    cmd.starts_with('/').then_some(cmd)
+}
+
+fn is_command(text: &str) -> bool {
+    command_word(text).is_some()
+}
+
+/// Take the next turn off the front of `queue`: a command on its own, or the
+/// run of plain messages to the same chat merged into one.
+fn take_turn(queue: &mut Vec<IncomingMessage>) -> Option<IncomingMessage> {
+    let first = queue.first()?;
+    if is_command(&first.text) {
+        return Some(queue.remove(0));
+    }
+    let len = queue
+        .iter()
+        .take_while(|m| !is_command(&m.text) && m.reply_target == first.reply_target)
+        .count();
+    Some(merge_messages(queue.drain(..len).collect()))
+}
+
+/// Merge plain messages into one. The latest message carries the id, reply
+/// target and timestamp; texts are joined in order and attachments combined.
+fn merge_messages(mut messages: Vec<IncomingMessage>) -> IncomingMessage {
+    let mut merged = messages.pop().expect("merge_messages needs a message");
+    if messages.is_empty() {
+        return merged;
+    }
+    let mut texts: Vec<String> = Vec::new();
+    let mut attachments = Vec::new();
+    for m in messages {
+        texts.push(m.text);
+        attachments.extend(m.attachments);
+    }
+    texts.push(std::mem::take(&mut merged.text));
+    attachments.append(&mut merged.attachments);
+    texts.retain(|t| !t.trim().is_empty());
+    merged.text = texts.join("\n\n");
+    merged.attachments = attachments;
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(text: &str, target: &str) -> IncomingMessage {
+        IncomingMessage {
+            id: uuid::Uuid::new_v4(),
+            channel: "telegram".to_string(),
+            sender_id: "1".to_string(),
+            sender_name: None,
+            text: text.to_string(),
+            timestamp: chrono::Utc::now(),
+            reply_to: None,
+            attachments: Vec::new(),
+            reply_target: Some(target.to_string()),
+            is_group: false,
+            source: None,
+            platform_message_id: None,
+        }
+    }
+
+    #[test]
+    fn test_take_turn_merges_plain_messages() {
+        let mut queue = vec![
+            msg("book a table for Wednesday", "c1"),
+            msg("actually make it Thursday", "c1"),
+        ];
+        let last_id = queue[1].id;
+        let turn = take_turn(&mut queue).unwrap();
+        assert_eq!(
+            turn.text,
+            "book a table for Wednesday\n\nactually make it Thursday"
+        );
+        assert_eq!(turn.id, last_id);
+        assert!(queue.is_empty());
+        assert!(take_turn(&mut queue).is_none());
+    }
+
+    #[test]
+    fn test_take_turn_keeps_commands_and_chats_apart() {
+        let mut queue = vec![
+            msg("first", "c1"),
+            msg("second", "c1"),
+            msg("/status", "c1"),
+            msg("third", "c1"),
+            msg("in the group", "g1"),
+        ];
+        assert_eq!(take_turn(&mut queue).unwrap().text, "first\n\nsecond");
+        assert_eq!(take_turn(&mut queue).unwrap().text, "/status");
+        assert_eq!(take_turn(&mut queue).unwrap().text, "third");
+        assert_eq!(take_turn(&mut queue).unwrap().text, "in the group");
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_command_word() {
+        assert_eq!(command_word("/queue ask"), Some("/queue"));
+        assert_eq!(command_word("/restart@omega_bot"), Some("/restart"));
+        assert_eq!(command_word("restart please"), None);
+        assert_eq!(command_word(""), None);
+    }
+}
@@ -1,14 +1,17 @@
 //! Direct response handling: provider call, session retry, markers, audit, delivery.
 
+use super::cancel::stop_button;
 use super::Gateway;
 use crate::markers::*;
 use omega_core::{
-    config::shellexpand,
-    context::{Context, ContextEntry},
+    config::DataDir,
+    context::{Context, ContextEntry, ProgressSink},
     message::{IncomingMessage, MessageMetadata, OutgoingMessage},
+    traits::ProgressReporter,
 };
 use omega_memory::audit::{AuditEntry, AuditStatus};
 use std::path::PathBuf;
+use std::sync::Arc;
 use tracing::{error, info, warn};
 
 impl Gateway {
@@ -26,13 +29,23 @@ impl Gateway {
         project_key: &str,
     ) {
         // Snapshot workspace images before provider call.
-        let workspace_path = PathBuf::from(shellexpand(&self.data_dir)).join("workspace");
+        let workspace_path = context
+            .workspace
+            .clone()
+            .unwrap_or_else(|| DataDir::new(&self.data_dir).workspace());
         let images_before = snapshot_workspace_images(&workspace_path);
 
+        // Live tool-use progress replaces the generic nudges once it starts.
+        let progress = self.channel_progress(incoming);
+        context.progress = progress
+            .clone()
+            .map(|p| ProgressSink(p as Arc<dyn ProgressReporter>));
+
         // Spawn provider call as background task.
         let provider = self.provider.clone();
         let ctx = context.clone();
-        let provider_task = tokio::spawn(async move { provider.complete(&ctx).await });
+        let provider_task =
+            self.spawn_scoped(incoming, async move { provider.complete(&ctx).await });
 
         // Resolve user language for status messages.
         let user_lang = self
@@ -44,29 +57,43 @@ impl Gateway {
             .unwrap_or_else(|| "English".to_string());
         let nudge_msg = random_nudge_message(&user_lang);
 
-        // Spawn delayed status updater: first nudge after 15s, then every 120s.
+        // Spawn delayed status updater: first nudge after 15s, then every 120s,
+        // each with a stop button.
         let status_channel = self.channels.get(&incoming.channel).cloned();
         let status_target = incoming.reply_target.clone();
         let status_lang = user_lang.clone();
-        let status_handle = tokio::spawn(async move {
+        let status_progress = progress.clone();
+        let status_handle = self.spawn_scoped(incoming, async move {
+            let progressing = || {
+                status_progress
+                    .as_ref()
+                    .is_some_and(|p| p.reported_within(std::time::Duration::from_secs(120)))
+            };
             tokio::time::sleep(std::time::Duration::from_secs(15)).await;
-            if let (Some(ref ch), Some(ref target)) = (&status_channel, &status_target) {
+            if progressing() {
+                // Tool-use progress is already visible.
+            } else if let (Some(ref ch), Some(ref target)) = (&status_channel, &status_target) {
                 let msg = OutgoingMessage {
                     text: nudge_msg.to_string(),
                     metadata: MessageMetadata::default(),
                     reply_target: Some(target.clone()),
+                    buttons: vec![stop_button()],
                     ..Default::default()
                 };
                 let _ = ch.send(msg).await;
             }
             loop {
                 tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+                if progressing() {
+                    continue;
+                }
                 if let (Some(ref ch), Some(ref target)) = (&status_channel, &status_target) {
                     let still_msg = random_still_message(&status_lang);
                     let msg = OutgoingMessage {
                         text: still_msg.to_string(),
                         metadata: MessageMetadata::default(),
                         reply_target: Some(target.clone()),
+                        buttons: vec![stop_button()],
                         ..Default::default()
                     };
                     let _ = ch.send(msg).await;
@@ -187,10 +214,42 @@ impl Gateway {
         }
 
         // --- PROCESS MARKERS ---
+        // Native action calls replace text markers (API providers with function calling).
         let mut response = response;
+        if let Some(ref calls) = response.actions {
+            response.text = apply_action_calls(&response.text, calls);
+        }
+        // A turn that consumed untrusted content (a webhook payload, a fetched
+        // page, an MCP result) must not act on it when `[trust] block_actions`.
+        let blocked_actions = super::shared_markers::gate_untrusted_actions(
+            &mut response.text,
+            &self.trust_config,
+            &response.metadata.untrusted_sources,
+            incoming.source.is_some(),
+            "conversation",
+        );
+        // A scheduled task that dedups into an existing one was not created by
+        // this turn, so `/undo` must leave it alone.
+        let tasks_before: Vec<String> = self
+            .memory
+            .get_tasks_for_sender(&incoming.sender_id)
+            .await
+            .map(|tasks| tasks.into_iter().map(|(id, ..)| id).collect())
+            .unwrap_or_default();
         let marker_results = self
             .process_markers(incoming, &mut response.text, active_project)
             .await;
+        response.metadata.created_tasks = marker_results
+            .iter()
+            .filter_map(|r| match r {
+                crate::task_confirmation::MarkerResult::TaskCreated { id, .. }
+                    if !tasks_before.contains(id) =>
+                {
+                    Some(id.clone())
+                }
+                _ => None,
+            })
+            .collect();
 
         // --- STORE IN MEMORY ---
         if let Err(e) = self
@@ -244,6 +303,12 @@ impl Gateway {
                 self.send_task_confirmation(incoming, &marker_results).await;
             }
 
+            if !blocked_actions.is_empty() {
+                let notice =
+                    crate::i18n::untrusted_actions_blocked(&user_lang, &blocked_actions.join(", "));
+                self.send_text(incoming, &notice).await;
+            }
+
             // Send persona greeting for marker-activated projects.
             // Send build confirmation prompt for build proposals.
             for r in &marker_results {
@@ -1,16 +1,17 @@
 //! Scheduled task delivery — reminders and action tasks.
 
+use super::cancel::{self, ActiveRequests};
 use super::scheduler_action;
+use super::tenants::resolve_tenant_scope;
 use super::Gateway;
 use crate::markers::{is_within_active_hours, next_active_start_utc};
 use omega_core::{
-    config::Prompts,
+    config::{ApprovalConfig, Prompts, RolesConfig, TenantsConfig, TrustConfig},
     message::{MessageMetadata, OutgoingMessage},
     traits::{Channel, Provider},
 };
 use omega_memory::{audit::AuditLogger, Store};
 use std::collections::HashMap;
-use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use tokio::sync::Notify;
 use tracing::{error, info, warn};
@@ -22,6 +23,7 @@ impl Gateway {
     /// with full tool access and process response markers.
     /// During quiet hours (outside active_start..active_end), due tasks are
     /// deferred to the next active_start instead of executing.
+    /// Action tasks are registered in `active_requests`, so `/stop` aborts them.
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn scheduler_loop(
         store: Store,
@@ -31,14 +33,17 @@ impl Gateway {
         skills: Vec<omega_skills::Skill>,
         prompts: Prompts,
         model_complex: String,
-        heartbeat_interval: Arc<AtomicU64>,
         heartbeat_notify: Arc<Notify>,
         audit: AuditLogger,
         provider_name: String,
         data_dir: String,
-        config_path: String,
         active_start: String,
         active_end: String,
+        active_requests: ActiveRequests,
+        trust: TrustConfig,
+        tenants: TenantsConfig,
+        roles: RolesConfig,
+        approval: ApprovalConfig,
     ) {
         loop {
             tokio::time::sleep(std::time::Duration::from_secs(poll_secs)).await;
@@ -70,7 +75,17 @@ impl Gateway {
                 Ok(tasks) => {
                     for task in &tasks {
                         if task.task_type == "action" {
-                            scheduler_action::execute_action_task(
+                            let key = format!("{}:{}", task.channel, task.sender_id);
+                            // Same workspace and tools the sender gets interactively.
+                            let scope = resolve_tenant_scope(
+                                &tenants,
+                                &roles,
+                                &store,
+                                &data_dir,
+                                &task.sender_id,
+                            )
+                            .await;
+                            let run = scheduler_action::execute_action_task(
                                 &task.id,
                                 &task.channel,
                                 &task.sender_id,
@@ -84,14 +99,26 @@ impl Gateway {
                                 &skills,
                                 &prompts,
                                 &model_complex,
-                                &heartbeat_interval,
                                 &heartbeat_notify,
                                 &audit,
                                 &provider_name,
                                 &data_dir,
-                                &config_path,
-                            )
-                            .await;
+                                &trust,
+                                &scope,
+                                &approval,
+                            );
+                            let label = format!("\"{}\"", task.description);
+                            if cancel::run_cancellable(&active_requests, &key, None, label, run)
+                                .await
+                                .is_none()
+                            {
+                                // Stopped by the user: skip this run instead of retrying it.
+                                if let Err(e) =
+                                    store.complete_task(&task.id, task.repeat.as_deref()).await
+                                {
+                                    error!("failed to complete stopped task {}: {e}", task.id);
+                                }
+                            }
                             continue; // Action tasks handle their own completion.
                         }
 
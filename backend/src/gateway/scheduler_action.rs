@@ -2,7 +2,7 @@
 
 use crate::markers::*;
 use omega_core::{
-    config::Prompts,
+    config::{ApprovalConfig, Prompts, TrustConfig},
     context::Context,
     message::{MessageMetadata, OutgoingMessage},
     traits::{Channel, Provider},
@@ -12,13 +12,14 @@ use omega_memory::{
     Store,
 };
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Notify;
 use tracing::{error, info, warn};
 
+use super::approval::background_approval_gate;
 use super::keywords::MAX_ACTION_RETRIES;
+use super::tenants::TenantScope;
 
 /// Execute a single action task with full provider access and project awareness.
 ///
@@ -39,12 +40,13 @@ pub(super) async fn execute_action_task(
     skills: &[omega_skills::Skill],
     prompts: &Prompts,
     model_complex: &str,
-    heartbeat_interval: &Arc<AtomicU64>,
     heartbeat_notify: &Arc<Notify>,
     audit: &AuditLogger,
     provider_name: &str,
     data_dir: &str,
-    config_path: &str,
+    trust: &TrustConfig,
+    scope: &TenantScope,
+    approval: &ApprovalConfig,
 ) {
     info!("scheduler: executing action task {id}: {description}");
     let started = Instant::now();
@@ -170,13 +172,25 @@ pub(super) async fn execute_action_task(
     let mut ctx = Context::new(description);
     ctx.system_prompt = system;
     ctx.model = Some(model_complex.to_string());
+    // The task runs as its owner: their tenant workspace and role's tools.
+    ctx.workspace = scope.workspace();
+    if let Some(ref tools) = scope.permissions.tools {
+        ctx.allowed_tools = Some(tools.clone());
+    }
+    ctx.denied_tools = scope.denied_tools();
+    ctx.approval = background_approval_gate(approval);
+    let skills: Vec<omega_skills::Skill> = skills
+        .iter()
+        .filter(|s| scope.permissions.allows_skill(&s.name))
+        .cloned()
+        .collect();
 
     // Claude Code CLI: always activate all MCP servers (cheap config write).
     // HTTP providers: keyword-based trigger matching (real per-message cost).
     ctx.mcp_servers = if provider_name == "claude-code" {
-        omega_skills::collect_all_mcp_servers(skills)
+        omega_skills::collect_all_mcp_servers(&skills)
     } else {
-        omega_skills::match_skill_triggers(skills, description)
+        omega_skills::match_skill_triggers(&skills, description)
     };
 
     match provider.complete(&ctx).await {
@@ -188,6 +202,15 @@ pub(super) async fn execute_action_task(
             let outcome = extract_action_outcome(&text);
             text = strip_action_outcome(&text);
 
+            // An unattended task that read a page must not schedule or delete on its say-so.
+            let blocked = super::shared_markers::gate_untrusted_actions(
+                &mut text,
+                trust,
+                &resp.metadata.untrusted_sources,
+                false,
+                "action",
+            );
+
             // Process markers from action response (project-tagged).
             process_action_markers(
                 &mut text,
@@ -196,12 +219,14 @@ pub(super) async fn execute_action_task(
                 sender_id,
                 reply_target,
                 project,
-                heartbeat_interval,
                 heartbeat_notify,
                 data_dir,
-                config_path,
             )
             .await;
+            if !blocked.is_empty() {
+                let notice = crate::i18n::untrusted_actions_blocked(language, &blocked.join(", "));
+                text = format!("{}\n\n{notice}", text.trim_end());
+            }
 
             // Determine audit status and handle outcome.
             let (audit_status, action_ok) = match &outcome {
@@ -352,10 +377,8 @@ async fn process_action_markers(
     sender_id: &str,
     reply_target: &str,
     project: &str,
-    heartbeat_interval: &Arc<AtomicU64>,
     heartbeat_notify: &Arc<Notify>,
     data_dir: &str,
-    config_path: &str,
 ) {
     // SCHEDULE markers.
     for sched_line in extract_all_schedule_markers(text) {
@@ -413,18 +436,19 @@ async fn process_action_markers(
     }
     *text = strip_schedule_action_markers(text);
 
-    // HEARTBEAT markers.
+    // HEARTBEAT markers (scoped to the task owner).
     let hb_actions = extract_heartbeat_markers(text);
     if !hb_actions.is_empty() {
-        apply_heartbeat_changes(&hb_actions, None);
-        for action in &hb_actions {
-            if let HeartbeatAction::SetInterval(mins) = action {
-                heartbeat_interval.store(*mins, Ordering::Relaxed);
-                omega_core::config::patch_heartbeat_interval(config_path, *mins);
-                heartbeat_notify.notify_one();
-                info!("heartbeat: interval changed to {mins} minutes (via scheduler)");
-            }
-        }
+        super::heartbeat_helpers::update_user_heartbeat(
+            store,
+            sender_id,
+            channel_name,
+            reply_target,
+            &hb_actions,
+            project,
+            heartbeat_notify,
+        )
+        .await;
         *text = strip_heartbeat_markers(text);
     }
 
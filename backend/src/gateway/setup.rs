@@ -5,7 +5,7 @@
 
 use std::path::PathBuf;
 
-use omega_core::config::shellexpand;
+use omega_core::config::DataDir;
 use omega_core::message::IncomingMessage;
 use omega_memory::audit::{AuditEntry, AuditStatus};
 use tracing::warn;
@@ -16,9 +16,7 @@ use super::Gateway;
 
 /// Path to the setup context file for a given sender.
 pub(super) fn setup_context_path(data_dir: &str, sender_id: &str) -> PathBuf {
-    PathBuf::from(shellexpand(data_dir))
-        .join("setup")
-        .join(format!("{sender_id}.md"))
+    DataDir::new(data_dir).setup_context(sender_id)
 }
 
 /// Parse the current round number from a setup context file's header.
@@ -102,13 +100,13 @@ impl Gateway {
 
         // REQ-BRAIN-005: Load existing projects for collision detection.
         let projects = omega_skills::load_projects(&self.data_dir);
-        let omega_dir = PathBuf::from(shellexpand(&self.data_dir));
+        let data_dir = DataDir::new(&self.data_dir);
 
         // REQ-BRAIN-019: Read existing ROLE.md files for context.
         let mut project_context = String::new();
         for proj in &projects {
             project_context.push_str(&format!("- {}", proj.name));
-            let role_path = omega_dir.join("projects").join(&proj.name).join("ROLE.md");
+            let role_path = data_dir.project(&proj.name).join("ROLE.md");
             if let Ok(content) = tokio::fs::read_to_string(&role_path).await {
                 let first_line = content.lines().next().unwrap_or("");
                 project_context.push_str(&format!(": {first_line}"));
@@ -137,22 +135,27 @@ impl Gateway {
         };
 
         // REQ-BRAIN-003: Write single agent file.
-        let _agent_guard =
-            match AgentFilesGuard::write_single(&omega_dir, "omega-brain", BRAIN_AGENT).await {
-                Ok(guard) => guard,
-                Err(e) => {
-                    warn!("Failed to write Brain agent file: {e}");
-                    if let Some(h) = typing_handle {
-                        h.abort();
-                    }
-                    self.send_text(
-                        incoming,
-                        "Setup failed: could not initialize the Brain agent.",
-                    )
-                    .await;
-                    return;
+        let _agent_guard = match AgentFilesGuard::write_single(
+            data_dir.root(),
+            "omega-brain",
+            BRAIN_AGENT,
+        )
+        .await
+        {
+            Ok(guard) => guard,
+            Err(e) => {
+                warn!("Failed to write Brain agent file: {e}");
+                if let Some(h) = typing_handle {
+                    h.abort();
                 }
-            };
+                self.send_text(
+                    incoming,
+                    "Setup failed: could not initialize the Brain agent.",
+                )
+                .await;
+                return;
+            }
+        };
 
         // REQ-BRAIN-004: Invoke Brain via run_build_phase.
         let result = self
@@ -263,13 +266,14 @@ impl Gateway {
         _incoming: &IncomingMessage,
         proposal_context: &str,
     ) -> Result<String, String> {
-        let omega_dir = PathBuf::from(shellexpand(&self.data_dir));
+        let data_dir = DataDir::new(&self.data_dir);
+        let omega_dir = data_dir.root();
 
         // Phase 1: Brain creates HEARTBEAT.md and emits markers (no ROLE.md).
         let brain_prompt =
             format!("EXECUTE_SETUP. Create HEARTBEAT.md and emit markers. Do NOT write ROLE.md — the role-creator agent handles that.\n\n{proposal_context}");
 
-        let _brain_guard = AgentFilesGuard::write_single(&omega_dir, "omega-brain", BRAIN_AGENT)
+        let _brain_guard = AgentFilesGuard::write_single(omega_dir, "omega-brain", BRAIN_AGENT)
             .await
             .map_err(|e| format!("Failed to write Brain agent: {e}"))?;
 
@@ -287,13 +291,15 @@ impl Gateway {
             .unwrap_or_else(|| "project".to_string());
 
         // Phase 2: Role Creator writes ROLE.md using accumulated context.
+        let role_path = data_dir.project(&project_name).join("ROLE.md");
         let role_prompt = format!(
-            "Create ROLE.md for the project '{project_name}' at ~/.omega/projects/{project_name}/ROLE.md\n\n\
-             Domain context from the setup session:\n{proposal_context}"
+            "Create ROLE.md for the project '{project_name}' at {}\n\n\
+             Domain context from the setup session:\n{proposal_context}",
+            role_path.display()
         );
 
         let _role_guard =
-            AgentFilesGuard::write_single(&omega_dir, "omega-role-creator", ROLE_CREATOR_AGENT)
+            AgentFilesGuard::write_single(omega_dir, "omega-role-creator", ROLE_CREATOR_AGENT)
                 .await
                 .map_err(|e| format!("Failed to write Role Creator agent: {e}"))?;
 
@@ -475,7 +481,7 @@ mod tests {
         // The setup uses data_dir (which is ~/.omega/) as the workspace for Brain.
         // NOT the workspace subdirectory used for builds.
         let data_dir = "~/.omega";
-        let omega_dir = PathBuf::from(shellexpand(data_dir));
+        let omega_dir = DataDir::new(data_dir).root().to_path_buf();
         assert!(
             omega_dir.to_string_lossy().ends_with(".omega")
                 || omega_dir.to_string_lossy().ends_with(".omega/"),
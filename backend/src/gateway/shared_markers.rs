@@ -1,12 +1,42 @@
-//! Shared marker processing for CANCEL_TASK, UPDATE_TASK, REWARD, and LESSON.
+//! Shared marker processing for CANCEL_TASK, UPDATE_TASK, REWARD, and LESSON,
+//! and the `[trust] block_actions` gate that runs before any of them.
 //!
 //! Deduplicated from process_markers.rs, scheduler_action.rs, and heartbeat_helpers.rs.
 
 use crate::markers::*;
 use crate::task_confirmation::MarkerResult;
+use omega_core::config::TrustConfig;
 use omega_memory::Store;
 use tracing::{error, info, warn};
 
+/// Drop side-effecting action markers from a reply that consumed untrusted
+/// content, when `[trust] block_actions` is on.
+///
+/// Shared across the main pipeline, action tasks, and heartbeat processing;
+/// call it before any marker is acted on. `sources` are the response's
+/// `untrusted_sources`; `forced` marks the turn untrusted regardless (a webhook
+/// payload). Returns the dropped markers.
+pub(super) fn gate_untrusted_actions(
+    text: &mut String,
+    trust: &TrustConfig,
+    sources: &[String],
+    forced: bool,
+    source: &str,
+) -> Vec<&'static str> {
+    if !trust.block_actions || (sources.is_empty() && !forced) {
+        return Vec::new();
+    }
+    let (stripped, dropped) = strip_action_markers(text);
+    if !dropped.is_empty() {
+        warn!(
+            "{source}: untrusted turn, dropped {} (sources: {sources:?})",
+            dropped.join(", ")
+        );
+        *text = stripped;
+    }
+    dropped
+}
+
 /// Process CANCEL_TASK, UPDATE_TASK, REWARD, and LESSON markers from response text.
 ///
 /// Shared across the main pipeline, action tasks, and heartbeat processing.
@@ -3,92 +3,157 @@
 use super::keywords::is_valid_fact;
 use super::Gateway;
 use crate::i18n;
-use omega_core::{context::Context, traits::Provider};
+use omega_core::{
+    context::Context,
+    error::OmegaError,
+    structured::{complete_structured, ResponseSchema},
+    traits::Provider,
+};
 use omega_memory::Store;
+use serde::Deserialize;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
-/// Summarize a conversation and extract facts in a single provider call.
-/// Designed for background use — all errors are logged, never surfaced.
-pub(super) async fn summarize_and_extract(
+/// Structured reply of the summarization call.
+#[derive(Debug, Deserialize)]
+struct ConversationDigest {
+    /// 1-2 sentence summary.
+    summary: String,
+    /// Personal facts about the user; empty when there are none.
+    #[serde(default)]
+    facts: Vec<DigestFact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigestFact {
+    key: String,
+    value: String,
+}
+
+impl ConversationDigest {
+    fn schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "conversation_digest",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "summary": {"type": "string"},
+                    "facts": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "key": {"type": "string"},
+                                "value": {"type": "string"}
+                            },
+                            "required": ["key", "value"]
+                        }
+                    }
+                },
+                "required": ["summary", "facts"]
+            }),
+        )
+    }
+
+    /// Facts with normalized keys that pass `is_valid_fact`.
+    fn valid_facts(&self) -> Vec<(String, String)> {
+        self.facts
+            .iter()
+            .filter_map(|f| {
+                let key = f.key.trim().to_lowercase();
+                let value = f.value.trim().to_string();
+                if key.is_empty() || value.is_empty() {
+                    return None;
+                }
+                if is_valid_fact(&key, &value) {
+                    Some((key, value))
+                } else {
+                    debug!("rejected invalid fact: {key}: {value}");
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Load a conversation as a `User: ... / Assistant: ...` transcript, with
+/// its message count. `None` for an empty conversation.
+async fn load_transcript(
     store: &Store,
-    provider: &Arc<dyn Provider>,
     conversation_id: &str,
-    summarize_prompt: &str,
-    facts_prompt: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<Option<(String, usize)>, anyhow::Error> {
     let messages = store.get_conversation_messages(conversation_id).await?;
     if messages.is_empty() {
-        store
-            .close_conversation(conversation_id, "(empty conversation)")
-            .await?;
-        return Ok(());
+        return Ok(None);
     }
-
-    // Build transcript.
     let mut transcript = String::new();
     for (role, content) in &messages {
         let label = if role == "user" { "User" } else { "Assistant" };
         transcript.push_str(&format!("{label}: {content}\n"));
     }
+    Ok(Some((transcript, messages.len())))
+}
 
-    // Single combined prompt for summary + facts.
-    let combined_prompt = format!(
+/// Summarize a transcript and extract facts in a single structured call.
+async fn digest_conversation(
+    provider: &Arc<dyn Provider>,
+    transcript: &str,
+    summarize_prompt: &str,
+    facts_prompt: &str,
+) -> Result<ConversationDigest, OmegaError> {
+    let prompt = format!(
         "{summarize_prompt}\n\n\
          Additionally, extract personal facts about the user from this conversation.\n\
          {facts_prompt}\n\n\
-         Transcript:\n{transcript}\n\n\
-         Respond in this exact format:\n\
-         SUMMARY: <1-2 sentence summary>\n\
-         FACTS:\n\
-         <key: value per line, or \"none\">"
+         Transcript:\n{transcript}"
     );
-    let ctx = Context::new(&combined_prompt);
-
-    match provider.complete(&ctx).await {
-        Ok(resp) => {
-            let text = resp.text.trim();
-            // Parse response: split on FACTS: line.
-            let (summary, facts_section) = if let Some(idx) = text.find("\nFACTS:") {
-                let summary_part = text[..idx].trim();
-                let facts_part = text[idx + 7..].trim(); // skip "\nFACTS:"
-                                                         // Strip "SUMMARY: " prefix if present.
-                let summary_clean = summary_part
-                    .strip_prefix("SUMMARY:")
-                    .unwrap_or(summary_part)
-                    .trim();
-                (summary_clean.to_string(), facts_part.to_string())
-            } else {
-                // No FACTS: section — treat entire response as summary.
-                let summary_clean = text.strip_prefix("SUMMARY:").unwrap_or(text).trim();
-                (summary_clean.to_string(), String::new())
-            };
+    let mut ctx = Context::new(&prompt);
+    ctx.allowed_tools = Some(vec![]);
+    ctx.response_schema = Some(ConversationDigest::schema());
+    complete_structured(provider.as_ref(), &ctx).await
+}
 
-            // Store facts if present.
-            if !facts_section.is_empty() && facts_section.to_lowercase() != "none" {
-                let conv_info: Option<(String,)> =
-                    sqlx::query_as("SELECT sender_id FROM conversations WHERE id = ?")
-                        .bind(conversation_id)
-                        .fetch_optional(store.pool())
-                        .await
-                        .ok()
-                        .flatten();
+/// Store the digest's valid facts for the conversation's sender.
+async fn store_digest_facts(store: &Store, conversation_id: &str, digest: &ConversationDigest) {
+    let facts = digest.valid_facts();
+    if facts.is_empty() {
+        return;
+    }
+    let sender = store
+        .get_conversation_sender(conversation_id)
+        .await
+        .ok()
+        .flatten();
+    if let Some(sender_id) = sender {
+        for (key, value) in &facts {
+            let _ = store.store_fact(&sender_id, key, value).await;
+        }
+    }
+}
 
-                if let Some((sender_id,)) = conv_info {
-                    for line in facts_section.lines() {
-                        if let Some((key, value)) = line.split_once(':') {
-                            let key = key.trim().trim_start_matches("- ").to_lowercase();
-                            let value = value.trim().to_string();
-                            if !key.is_empty() && !value.is_empty() && is_valid_fact(&key, &value) {
-                                let _ = store.store_fact(&sender_id, &key, &value).await;
-                            }
-                        }
-                    }
-                }
-            }
+/// Summarize a conversation and extract facts in a single provider call.
+/// Designed for background use — all errors are logged, never surfaced.
+pub(super) async fn summarize_and_extract(
+    store: &Store,
+    provider: &Arc<dyn Provider>,
+    conversation_id: &str,
+    summarize_prompt: &str,
+    facts_prompt: &str,
+) -> Result<(), anyhow::Error> {
+    let Some((transcript, _)) = load_transcript(store, conversation_id).await? else {
+        store
+            .close_conversation(conversation_id, "(empty conversation)")
+            .await?;
+        return Ok(());
+    };
 
+    match digest_conversation(provider, &transcript, summarize_prompt, facts_prompt).await {
+        Ok(digest) => {
+            store_digest_facts(store, conversation_id, &digest).await;
             // Update the already-closed conversation with the summary.
-            store.close_conversation(conversation_id, &summary).await?;
+            store
+                .close_conversation(conversation_id, digest.summary.trim())
+                .await?;
             info!("Conversation {conversation_id} summarized in background");
         }
         Err(e) => {
@@ -142,73 +207,60 @@ impl Gateway {
         provider: &Arc<dyn Provider>,
         conversation_id: &str,
         summarize_prompt: &str,
-        facts_prompt_template: &str,
+        facts_prompt: &str,
     ) -> Result<(), anyhow::Error> {
-        let messages = store.get_conversation_messages(conversation_id).await?;
-        if messages.is_empty() {
+        let Some((transcript, count)) = load_transcript(store, conversation_id).await? else {
             store
                 .close_conversation(conversation_id, "(empty conversation)")
                 .await?;
             return Ok(());
-        }
-
-        // Build a transcript for summarization.
-        let mut transcript = String::new();
-        for (role, content) in &messages {
-            let label = if role == "user" { "User" } else { "Assistant" };
-            transcript.push_str(&format!("{label}: {content}\n"));
-        }
+        };
 
-        // Ask provider to summarize.
-        let full_summary_prompt = format!("{summarize_prompt}\n\n{transcript}");
-        let summary_ctx = Context::new(&full_summary_prompt);
-        let summary = match provider.complete(&summary_ctx).await {
-            Ok(resp) => resp.text,
+        let summary = match digest_conversation(
+            provider,
+            &transcript,
+            summarize_prompt,
+            facts_prompt,
+        )
+        .await
+        {
+            Ok(digest) => {
+                store_digest_facts(store, conversation_id, &digest).await;
+                digest.summary.trim().to_string()
+            }
             Err(e) => {
                 warn!("summarization failed, using fallback: {e}");
-                format!("({} messages, summary unavailable)", messages.len())
+                format!("({count} messages, summary unavailable)")
             }
         };
 
-        // Ask provider to extract facts.
-        let facts_prompt = format!("{facts_prompt_template}\n\n{transcript}");
-        let facts_ctx = Context::new(&facts_prompt);
-        if let Ok(facts_resp) = provider.complete(&facts_ctx).await {
-            let text = facts_resp.text.trim().to_string();
-            if text.to_lowercase() != "none" {
-                // Find sender_id from the conversation messages context.
-                // We need the sender_id — extract from the conversation row.
-                let conv_info: Option<(String,)> =
-                    sqlx::query_as("SELECT sender_id FROM conversations WHERE id = ?")
-                        .bind(conversation_id)
-                        .fetch_optional(store.pool())
-                        .await
-                        .ok()
-                        .flatten();
-
-                if let Some((sender_id,)) = conv_info {
-                    for line in text.lines() {
-                        if let Some((key, value)) = line.split_once(':') {
-                            let key = key.trim().trim_start_matches("- ").to_lowercase();
-                            let value = value.trim().to_string();
-                            if !key.is_empty() && !value.is_empty() {
-                                if is_valid_fact(&key, &value) {
-                                    let _ = store.store_fact(&sender_id, &key, &value).await;
-                                } else {
-                                    debug!("rejected invalid fact: {key}: {value}");
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
         store.close_conversation(conversation_id, &summary).await?;
         info!("Conversation {conversation_id} summarized and closed");
         Ok(())
     }
 
+    /// Summarize a closed conversation and extract facts in the background.
+    /// `after` names the command that closed it, for the log.
+    pub(super) fn summarize_in_background(&self, conversation_id: String, after: &'static str) {
+        let store = self.memory.clone();
+        let provider = Arc::clone(&self.provider);
+        let summarize_prompt = self.prompts.summarize.clone();
+        let facts_prompt = self.prompts.facts.clone();
+        tokio::spawn(async move {
+            if let Err(e) = summarize_and_extract(
+                &store,
+                &provider,
+                &conversation_id,
+                &summarize_prompt,
+                &facts_prompt,
+            )
+            .await
+            {
+                warn!("background summarization after {after} failed: {e}");
+            }
+        });
+    }
+
     /// Handle /forget: close conversation instantly, summarize in background.
     pub(super) async fn handle_forget(&self, channel: &str, sender_id: &str) -> String {
         let lang = self
@@ -230,21 +282,15 @@ impl Gateway {
         let project_key = active_project.as_str();
 
         // Find the active conversation for this sender + project.
-        let conv: Option<(String,)> = sqlx::query_as(
-            "SELECT id FROM conversations \
-             WHERE channel = ? AND sender_id = ? AND project = ? AND status = 'active' \
-             ORDER BY last_activity DESC LIMIT 1",
-        )
-        .bind(channel)
-        .bind(sender_id)
-        .bind(project_key)
-        .fetch_optional(self.memory.pool())
-        .await
-        .ok()
-        .flatten();
+        let conv = self
+            .memory
+            .get_open_conversation_id(channel, sender_id, project_key)
+            .await
+            .ok()
+            .flatten();
 
         match conv {
-            Some((conversation_id,)) => {
+            Some(conversation_id) => {
                 // Close immediately so new messages start a fresh conversation.
                 let _ = self
                     .memory
@@ -257,24 +303,7 @@ impl Gateway {
                     .clear_session(channel, sender_id, project_key)
                     .await;
 
-                // Summarize + extract facts in the background.
-                let store = self.memory.clone();
-                let provider = Arc::clone(&self.provider);
-                let summarize_prompt = self.prompts.summarize.clone();
-                let facts_prompt = self.prompts.facts.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = summarize_and_extract(
-                        &store,
-                        &provider,
-                        &conversation_id,
-                        &summarize_prompt,
-                        &facts_prompt,
-                    )
-                    .await
-                    {
-                        warn!("background summarization after /forget failed: {e}");
-                    }
-                });
+                self.summarize_in_background(conversation_id, "/forget");
 
                 i18n::t("conversation_cleared", &lang).to_string()
             }
@@ -282,3 +311,34 @@ impl Gateway {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_parses_and_filters_facts() {
+        let digest: ConversationDigest = omega_core::structured::parse(
+            r#"{"summary": "Talked about Rust.",
+                "facts": [
+                    {"key": " Name ", "value": "Ada"},
+                    {"key": "btc_price", "value": "45,678.90"},
+                    {"key": "timezone", "value": ""}
+                ]}"#,
+        )
+        .unwrap();
+        assert_eq!(digest.summary, "Talked about Rust.");
+        assert_eq!(
+            digest.valid_facts(),
+            vec![("name".to_string(), "Ada".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_digest_facts_optional() {
+        let digest: ConversationDigest =
+            omega_core::structured::parse(r#"{"summary": "Hi."}"#).unwrap();
+        assert!(digest.valid_facts().is_empty());
+        assert!(omega_core::structured::parse::<ConversationDigest>(r#"{"facts": []}"#).is_err());
+    }
+}
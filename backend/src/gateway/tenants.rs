@@ -0,0 +1,230 @@
+//! Tenant resolution — each sender's workspace, projects, skills and role.
+//!
+//! With `[tenants]` disabled every sender uses the shared data dir as admin.
+//! Otherwise a tenant member works in `{data_dir}/tenants/<name>/`: its
+//! `workspace/` is the provider's working directory, and its `projects/` and
+//! `skills/` are layered over the shared ones (tenant entries win by name).
+//!
+//! The sender's named role from `[roles]` is resolved here as well and
+//! filters the projects and skills they see.
+
+use std::path::PathBuf;
+
+use omega_core::config::{DataDir, RoleConfig, RolesConfig, TenantRole, TenantsConfig};
+use omega_memory::Store;
+use omega_skills::{Project, Skill};
+use tracing::warn;
+
+use super::Gateway;
+
+/// Where a sender's files live and what they may do.
+#[derive(Debug, Clone)]
+pub(super) struct TenantScope {
+    /// Tenant name (`None` = shared data dir).
+    pub name: Option<String>,
+    pub role: TenantRole,
+    /// Named role from `[roles]` (`None` = unrestricted).
+    pub role_name: Option<String>,
+    /// Permissions of the named role (unrestricted when `role_name` is `None`).
+    pub permissions: RoleConfig,
+    /// The tenant's own data dir (`None` = shared data dir).
+    dir: Option<DataDir>,
+}
+
+impl TenantScope {
+    /// The tenant's workspace — the provider working-directory override.
+    pub fn workspace(&self) -> Option<PathBuf> {
+        self.dir.as_ref().map(DataDir::workspace)
+    }
+
+    /// Tools withheld from this sender (`Context::denied_tools`).
+    ///
+    /// Bash can't be confined to the tenant dir like the file tools, so
+    /// tenant members other than admins don't get it unless their `[roles]`
+    /// tools list names `"Bash"` explicitly.
+    pub fn denied_tools(&self) -> Vec<String> {
+        let granted = self
+            .permissions
+            .tools
+            .as_ref()
+            .is_some_and(|tools| tools.iter().any(|t| t == "Bash"));
+        if self.dir.is_none() || self.role.is_admin() || granted {
+            Vec::new()
+        } else {
+            vec!["Bash".to_string()]
+        }
+    }
+
+    /// The tenant's data dir as a string for `&str`-based loaders.
+    fn dir_str(&self) -> Option<String> {
+        self.dir
+            .as_ref()
+            .map(|d| d.root().to_string_lossy().into_owned())
+    }
+}
+
+/// Merge `overlay` over `base` by name (overlay wins), sorted by name.
+fn overlay_by_name<T>(mut base: Vec<T>, overlay: Vec<T>, name: impl Fn(&T) -> &str) -> Vec<T> {
+    base.retain(|b| !overlay.iter().any(|o| name(o) == name(b)));
+    base.extend(overlay);
+    base.sort_by(|a, b| name(a).cmp(name(b)));
+    base
+}
+
+/// [`Gateway::tenant_scope`] for background loops that don't hold the gateway
+/// (scheduled action tasks).
+pub(super) async fn resolve_tenant_scope(
+    tenants: &TenantsConfig,
+    roles: &RolesConfig,
+    memory: &Store,
+    data_dir: &str,
+    sender_id: &str,
+) -> TenantScope {
+    if !tenants.enabled && roles.role.is_empty() {
+        return TenantScope {
+            name: None,
+            role: TenantRole::Admin,
+            role_name: None,
+            permissions: RoleConfig::default(),
+            dir: None,
+        };
+    }
+    let mut ids = vec![sender_id.to_string()];
+    ids.extend(memory.get_aliases(sender_id).await.unwrap_or_default());
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+    let (role_name, permissions) = match roles.role_for(&ids) {
+        Some((name, role)) => (Some(name.to_string()), role.clone()),
+        None => (None, RoleConfig::default()),
+    };
+    let shared = TenantScope {
+        name: None,
+        role: tenants.role_for(&ids),
+        role_name,
+        permissions,
+        dir: None,
+    };
+    let Some((name, _)) = tenants.tenant_for(&ids) else {
+        return shared;
+    };
+    let dir = DataDir::new(data_dir).tenant(name);
+    if let Err(e) = std::fs::create_dir_all(dir.workspace()) {
+        warn!("tenants: failed to create workspace for '{name}': {e}");
+    }
+    TenantScope {
+        name: Some(name.to_string()),
+        dir: Some(dir),
+        ..shared
+    }
+}
+
+impl Gateway {
+    /// Resolve the tenant and named role of a canonical sender id (its
+    /// aliases count too).
+    ///
+    /// Creates the tenant's workspace on first use.
+    pub(super) async fn tenant_scope(&self, sender_id: &str) -> TenantScope {
+        resolve_tenant_scope(
+            &self.tenants_config,
+            &self.roles_config,
+            &self.memory,
+            &self.data_dir,
+            sender_id,
+        )
+        .await
+    }
+
+    /// Data dir for tenant-scoped files such as the inbox.
+    pub(super) fn tenant_data_dir(&self, scope: &TenantScope) -> String {
+        scope.dir_str().unwrap_or_else(|| self.data_dir.clone())
+    }
+
+    /// Shared projects with the tenant's projects layered on top (hot-reloaded),
+    /// limited to the projects the sender's role allows.
+    pub(super) fn tenant_projects(&self, scope: &TenantScope) -> Vec<Project> {
+        let shared = omega_skills::load_projects(&self.data_dir);
+        let mut projects = match scope.dir_str() {
+            Some(dir) => overlay_by_name(shared, omega_skills::load_projects(&dir), |p| &p.name),
+            None => shared,
+        };
+        projects.retain(|p| scope.permissions.allows_project(&p.name));
+        projects
+    }
+
+    /// Shared skills with the tenant's skills layered on top, limited to the
+    /// skills the sender's role allows.
+    pub(super) fn tenant_skills(&self, scope: &TenantScope) -> Vec<Skill> {
+        let mut skills = match scope.dir_str() {
+            Some(dir) => {
+                overlay_by_name(self.skills.clone(), omega_skills::load_skills(&dir), |s| {
+                    &s.name
+                })
+            }
+            None => self.skills.clone(),
+        };
+        skills.retain(|s| scope.permissions.allows_skill(&s.name));
+        skills
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_by_name_tenant_wins_and_sorts() {
+        let base = vec![("b", 1), ("a", 1)];
+        let overlay = vec![("c", 2), ("a", 2)];
+        let merged = overlay_by_name(base, overlay, |x| x.0);
+        assert_eq!(merged, vec![("a", 2), ("b", 1), ("c", 2)]);
+    }
+
+    #[test]
+    fn test_tenant_scope_workspace() {
+        let scope = TenantScope {
+            name: Some("ops".to_string()),
+            role: TenantRole::User,
+            role_name: None,
+            permissions: RoleConfig::default(),
+            dir: Some(DataDir::new("/srv/omega").tenant("ops")),
+        };
+        assert_eq!(
+            scope.workspace(),
+            Some(PathBuf::from("/srv/omega/tenants/ops/workspace"))
+        );
+        let shared = TenantScope {
+            name: None,
+            role: TenantRole::Admin,
+            role_name: None,
+            permissions: RoleConfig::default(),
+            dir: None,
+        };
+        assert!(shared.workspace().is_none());
+    }
+
+    #[test]
+    fn test_tenant_members_are_denied_bash() {
+        let member = |role: TenantRole, tools: Option<Vec<String>>| TenantScope {
+            name: Some("ops".to_string()),
+            role,
+            role_name: None,
+            permissions: RoleConfig {
+                tools,
+                ..RoleConfig::default()
+            },
+            dir: Some(DataDir::new("/srv/omega").tenant("ops")),
+        };
+        assert_eq!(member(TenantRole::User, None).denied_tools(), vec!["Bash"]);
+        assert_eq!(member(TenantRole::Guest, None).denied_tools(), vec!["Bash"]);
+        assert!(member(TenantRole::Admin, None).denied_tools().is_empty());
+        let granted = Some(vec!["Read".to_string(), "Bash".to_string()]);
+        assert!(member(TenantRole::User, granted).denied_tools().is_empty());
+
+        let shared = TenantScope {
+            dir: None,
+            name: None,
+            ..member(TenantRole::Guest, None)
+        };
+        assert!(shared.denied_tools().is_empty());
+    }
+}
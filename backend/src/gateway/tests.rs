@@ -297,3 +297,33 @@ fn test_kw_match_help_keywords_whatsapp() {
     assert!(kw_match("was kannst du", HELP_KW));
     assert!(!kw_match("hello there", HELP_KW));
 }
+
+#[test]
+fn test_gate_untrusted_actions() {
+    use super::shared_markers::gate_untrusted_actions;
+    use omega_core::config::TrustConfig;
+
+    let reply = "Done.\nSCHEDULE_ACTION: Wire money | 2026-03-01T09:00:00 | once";
+    let on = TrustConfig {
+        block_actions: true,
+    };
+    let sources = vec!["mcp:browser_snapshot".to_string()];
+
+    // Off, or a turn with nothing untrusted: markers are left alone.
+    let mut text = reply.to_string();
+    assert!(
+        gate_untrusted_actions(&mut text, &TrustConfig::default(), &sources, false, "t").is_empty()
+    );
+    assert!(gate_untrusted_actions(&mut text, &on, &[], false, "t").is_empty());
+    assert_eq!(text, reply);
+
+    // Untrusted sources (action task, heartbeat) or a webhook turn: dropped.
+    let dropped = gate_untrusted_actions(&mut text, &on, &sources, false, "action");
+    assert_eq!(dropped, vec!["SCHEDULE_ACTION:"]);
+    assert_eq!(text, "Done.");
+    let mut text = reply.to_string();
+    assert_eq!(
+        gate_untrusted_actions(&mut text, &on, &[], true, "conversation").len(),
+        1
+    );
+}
@@ -0,0 +1,51 @@
+//! `/topologies` command handler — lists installed build topologies.
+//!
+//! Intercepted in pipeline.rs (like `/context`) because topologies live under
+//! the gateway's `data_dir` and are parsed by the gateway-private loader.
+
+use omega_core::message::IncomingMessage;
+
+use super::builds_topology::list_topologies;
+use super::Gateway;
+use crate::i18n;
+
+impl Gateway {
+    /// Handle `/topologies`: one line per installed topology, marking the default
+    /// and the active project's choice.
+    pub(super) async fn handle_topologies_command(&self, incoming: &IncomingMessage) {
+        let lang = self
+            .memory
+            .get_fact(&incoming.sender_id, "preferred_language")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "English".to_string());
+        let project_topology = self.project_topology(&incoming.sender_id).await;
+
+        let mut out = format!("{}\n", i18n::t("topologies_header", &lang));
+        for (name, parsed) in list_topologies(&self.data_dir) {
+            let mut tags = Vec::new();
+            if name == "development" {
+                tags.push("default");
+            }
+            if project_topology.as_deref() == Some(name.as_str()) {
+                tags.push("active project");
+            }
+            let tags = if tags.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", tags.join(", "))
+            };
+            match parsed {
+                Ok(t) => out.push_str(&format!(
+                    "\n- `{name}`{tags} \u{2014} {} [{} phases]",
+                    t.topology.description,
+                    t.phases.len()
+                )),
+                Err(e) => out.push_str(&format!("\n- `{name}`{tags} \u{2014} invalid: {e}")),
+            }
+        }
+        out.push_str(&format!("\n\n{}", i18n::t("topologies_footer", &lang)));
+        self.send_text(incoming, &out).await;
+    }
+}
@@ -63,6 +63,46 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "/forget   \u{2014} \u{041e}\u{0447}\u{0438}\u{0441}\u{0442}\u{0438}\u{0442}\u{044c} \u{0442}\u{0435}\u{043a}\u{0443}\u{0449}\u{0438}\u{0439} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}",
             _ => "/forget   \u{2014} Clear current conversation",
         },
+        "help_retry" => match lang {
+            "Spanish" => "/retry    \u{2014} Volver a responder tu \u{00fa}ltimo mensaje (/retry complex para el modelo m\u{00e1}s potente)",
+            "Portuguese" => "/retry    \u{2014} Responder de novo sua \u{00fa}ltima mensagem (/retry complex para o modelo mais forte)",
+            "French" => "/retry    \u{2014} R\u{00e9}pondre \u{00e0} nouveau \u{00e0} votre dernier message (/retry complex pour le mod\u{00e8}le le plus puissant)",
+            "German" => "/retry    \u{2014} Deine letzte Nachricht neu beantworten (/retry complex f\u{00fc}r das st\u{00e4}rkere Modell)",
+            "Italian" => "/retry    \u{2014} Rispondi di nuovo al tuo ultimo messaggio (/retry complex per il modello pi\u{00f9} potente)",
+            "Dutch" => "/retry    \u{2014} Je laatste bericht opnieuw beantwoorden (/retry complex voor het sterkere model)",
+            "Russian" => "/retry    \u{2014} \u{041e}\u{0442}\u{0432}\u{0435}\u{0442}\u{0438}\u{0442}\u{044c} \u{043d}\u{0430} \u{043f}\u{043e}\u{0441}\u{043b}\u{0435}\u{0434}\u{043d}\u{0435}\u{0435} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0435} \u{0437}\u{0430}\u{043d}\u{043e}\u{0432}\u{043e} (/retry complex \u{2014} \u{0431}\u{043e}\u{043b}\u{0435}\u{0435} \u{0441}\u{0438}\u{043b}\u{044c}\u{043d}\u{0430}\u{044f} \u{043c}\u{043e}\u{0434}\u{0435}\u{043b}\u{044c})",
+            _ => "/retry    \u{2014} Answer your last message again (/retry complex for the stronger model)",
+        },
+        "help_undo" => match lang {
+            "Spanish" => "/undo     \u{2014} Quitar el \u{00fa}ltimo intercambio y las tareas que program\u{00f3}",
+            "Portuguese" => "/undo     \u{2014} Remover a \u{00fa}ltima troca e as tarefas que ela agendou",
+            "French" => "/undo     \u{2014} Retirer le dernier \u{00e9}change et les t\u{00e2}ches qu'il a planifi\u{00e9}es",
+            "German" => "/undo     \u{2014} Letzten Austausch und die dabei geplanten Aufgaben entfernen",
+            "Italian" => "/undo     \u{2014} Rimuovi l'ultimo scambio e le attivit\u{00e0} che ha programmato",
+            "Dutch" => "/undo     \u{2014} Laatste uitwisseling en de daarin geplande taken verwijderen",
+            "Russian" => "/undo     \u{2014} \u{0423}\u{0434}\u{0430}\u{043b}\u{0438}\u{0442}\u{044c} \u{043f}\u{043e}\u{0441}\u{043b}\u{0435}\u{0434}\u{043d}\u{0438}\u{0439} \u{043e}\u{0431}\u{043c}\u{0435}\u{043d} \u{0438} \u{0437}\u{0430}\u{043f}\u{043b}\u{0430}\u{043d}\u{0438}\u{0440}\u{043e}\u{0432}\u{0430}\u{043d}\u{043d}\u{044b}\u{0435} \u{0432} \u{043d}\u{0451}\u{043c} \u{0437}\u{0430}\u{0434}\u{0430}\u{0447}\u{0438}",
+            _ => "/undo     \u{2014} Remove the last exchange and the tasks it scheduled",
+        },
+        "help_snapshot" => match lang {
+            "Spanish" => "/snapshot \u{2014} Guardar esta conversaci\u{00f3}n con un nombre, o ver las instant\u{00e1}neas",
+            "Portuguese" => "/snapshot \u{2014} Salvar esta conversa com um nome, ou listar snapshots",
+            "French" => "/snapshot \u{2014} Enregistrer cette conversation sous un nom, ou lister les instantan\u{00e9}s",
+            "German" => "/snapshot \u{2014} Dieses Gespr\u{00e4}ch unter einem Namen speichern oder Schnappsch\u{00fc}sse auflisten",
+            "Italian" => "/snapshot \u{2014} Salva questa conversazione con un nome, o elenca le istantanee",
+            "Dutch" => "/snapshot \u{2014} Dit gesprek onder een naam bewaren, of momentopnames tonen",
+            "Russian" => "/snapshot \u{2014} \u{0421}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{0438}\u{0442}\u{044c} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440} \u{043f}\u{043e}\u{0434} \u{0438}\u{043c}\u{0435}\u{043d}\u{0435}\u{043c} \u{0438}\u{043b}\u{0438} \u{043f}\u{043e}\u{043a}\u{0430}\u{0437}\u{0430}\u{0442}\u{044c} \u{0441}\u{043d}\u{0438}\u{043c}\u{043a}\u{0438}",
+            _ => "/snapshot \u{2014} Save this conversation under a name, or list snapshots",
+        },
+        "help_branch" => match lang {
+            "Spanish" => "/branch   \u{2014} Continuar desde una instant\u{00e1}nea en una conversaci\u{00f3}n nueva",
+            "Portuguese" => "/branch   \u{2014} Continuar a partir de um snapshot em uma nova conversa",
+            "French" => "/branch   \u{2014} Repartir d'un instantan\u{00e9} dans une nouvelle conversation",
+            "German" => "/branch   \u{2014} Von einem Schnappschuss aus in einem neuen Gespr\u{00e4}ch weitermachen",
+            "Italian" => "/branch   \u{2014} Riparti da un'istantanea in una nuova conversazione",
+            "Dutch" => "/branch   \u{2014} Verdergaan vanaf een momentopname in een nieuw gesprek",
+            "Russian" => "/branch   \u{2014} \u{041f}\u{0440}\u{043e}\u{0434}\u{043e}\u{043b}\u{0436}\u{0438}\u{0442}\u{044c} \u{0441} \u{0441}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{0451}\u{043d}\u{043d}\u{043e}\u{0433}\u{043e} \u{0441}\u{043d}\u{0438}\u{043c}\u{043a}\u{0430} \u{0432} \u{043d}\u{043e}\u{0432}\u{043e}\u{043c} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}\u{0435}",
+            _ => "/branch   \u{2014} Continue from a saved snapshot in a new conversation",
+        },
         "help_tasks" => match lang {
             "Spanish" => "/tasks    \u{2014} Ver tus tareas programadas",
             "Portuguese" => "/tasks    \u{2014} Ver suas tarefas agendadas",
@@ -83,6 +123,26 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "/cancel   \u{2014} \u{041e}\u{0442}\u{043c}\u{0435}\u{043d}\u{0438}\u{0442}\u{044c} \u{0437}\u{0430}\u{0434}\u{0430}\u{0447}\u{0443} \u{043f}\u{043e} ID",
             _ => "/cancel   \u{2014} Cancel a task by ID",
         },
+        "help_stop" => match lang {
+            "Spanish" => "/stop     \u{2014} Detener la solicitud o construcci\u{00f3}n en curso",
+            "Portuguese" => "/stop     \u{2014} Parar a solicita\u{00e7}\u{00e3}o ou constru\u{00e7}\u{00e3}o em andamento",
+            "French" => "/stop     \u{2014} Arr\u{00ea}ter la requ\u{00ea}te ou la construction en cours",
+            "German" => "/stop     \u{2014} Laufende Anfrage oder laufenden Build stoppen",
+            "Italian" => "/stop     \u{2014} Ferma la richiesta o la build in corso",
+            "Dutch" => "/stop     \u{2014} Lopend verzoek of lopende build stoppen",
+            "Russian" => "/stop     \u{2014} \u{041e}\u{0441}\u{0442}\u{0430}\u{043d}\u{043e}\u{0432}\u{0438}\u{0442}\u{044c} \u{0442}\u{0435}\u{043a}\u{0443}\u{0449}\u{0438}\u{0439} \u{0437}\u{0430}\u{043f}\u{0440}\u{043e}\u{0441} \u{0438}\u{043b}\u{0438} \u{0441}\u{0431}\u{043e}\u{0440}\u{043a}\u{0443}",
+            _ => "/stop     \u{2014} Stop the current request or build",
+        },
+        "help_queue" => match lang {
+            "Spanish" => "/queue    \u{2014} Ver o cambiar c\u{00f3}mo trato los mensajes enviados mientras trabajo",
+            "Portuguese" => "/queue    \u{2014} Ver ou alterar como trato mensagens enviadas enquanto trabalho",
+            "French" => "/queue    \u{2014} Voir ou changer le traitement des messages envoy\u{00e9}s pendant que je travaille",
+            "German" => "/queue    \u{2014} Umgang mit Nachrichten w\u{00e4}hrend der Arbeit anzeigen oder \u{00e4}ndern",
+            "Italian" => "/queue    \u{2014} Vedi o cambia come gestisco i messaggi inviati mentre lavoro",
+            "Dutch" => "/queue    \u{2014} Bekijk of wijzig hoe berichten tijdens mijn werk worden afgehandeld",
+            "Russian" => "/queue    \u{2014} \u{041f}\u{043e}\u{043a}\u{0430}\u{0437}\u{0430}\u{0442}\u{044c} \u{0438}\u{043b}\u{0438} \u{0438}\u{0437}\u{043c}\u{0435}\u{043d}\u{0438}\u{0442}\u{044c} \u{043e}\u{0431}\u{0440}\u{0430}\u{0431}\u{043e}\u{0442}\u{043a}\u{0443} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0439}, \u{043f}\u{043e}\u{043a}\u{0430} \u{044f} \u{0437}\u{0430}\u{043d}\u{044f}\u{0442}",
+            _ => "/queue    \u{2014} Show or set how messages sent while I'm busy are handled",
+        },
         "help_language" => match lang {
             "Spanish" => "/language \u{2014} Ver o cambiar tu idioma",
             "Portuguese" => "/language \u{2014} Ver ou alterar seu idioma",
@@ -174,14 +234,44 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             _ => "/learning \u{2014} Show learned rules and recent outcomes",
         },
         "help_heartbeat" => match lang {
-            "Spanish" => "/heartbeat \u{2014} Estado y lista de monitoreo",
-            "Portuguese" => "/heartbeat \u{2014} Estado e lista de monitoramento",
-            "French" => "/heartbeat \u{2014} Statut et liste de surveillance",
-            "German" => "/heartbeat \u{2014} Status und \u{00dc}berwachungsliste",
-            "Italian" => "/heartbeat \u{2014} Stato e lista di monitoraggio",
-            "Dutch" => "/heartbeat \u{2014} Status en bewakingslijst",
-            "Russian" => "/heartbeat \u{2014} \u{0421}\u{0442}\u{0430}\u{0442}\u{0443}\u{0441} \u{0438} \u{0441}\u{043f}\u{0438}\u{0441}\u{043e}\u{043a} \u{043c}\u{043e}\u{043d}\u{0438}\u{0442}\u{043e}\u{0440}\u{0438}\u{043d}\u{0433}\u{0430}",
-            _ => "/heartbeat \u{2014} Heartbeat status and watchlist",
+            "Spanish" => "/heartbeat \u{2014} Tu heartbeat: estado, horario y lista",
+            "Portuguese" => "/heartbeat \u{2014} Seu heartbeat: estado, hor\u{00e1}rio e lista",
+            "French" => "/heartbeat \u{2014} Votre heartbeat : statut, horaires et liste",
+            "German" => "/heartbeat \u{2014} Dein Heartbeat: Status, Zeiten und Liste",
+            "Italian" => "/heartbeat \u{2014} Il tuo heartbeat: stato, orari e lista",
+            "Dutch" => "/heartbeat \u{2014} Jouw heartbeat: status, schema en lijst",
+            "Russian" => "/heartbeat \u{2014} \u{0412}\u{0430}\u{0448} heartbeat: \u{0441}\u{0442}\u{0430}\u{0442}\u{0443}\u{0441}, \u{0440}\u{0430}\u{0441}\u{043f}\u{0438}\u{0441}\u{0430}\u{043d}\u{0438}\u{0435} \u{0438} \u{0441}\u{043f}\u{0438}\u{0441}\u{043e}\u{043a}",
+            _ => "/heartbeat \u{2014} Your heartbeat: status, schedule and watchlist",
+        },
+        "help_link" => match lang {
+            "Spanish" => "/link     \u{2014} Vincular tus cuentas de Telegram y WhatsApp",
+            "Portuguese" => "/link     \u{2014} Vincular suas contas do Telegram e WhatsApp",
+            "French" => "/link     \u{2014} Lier vos comptes Telegram et WhatsApp",
+            "German" => "/link     \u{2014} Telegram- und WhatsApp-Konten verkn\u{00fc}pfen",
+            "Italian" => "/link     \u{2014} Collega i tuoi account Telegram e WhatsApp",
+            "Dutch" => "/link     \u{2014} Je Telegram- en WhatsApp-accounts koppelen",
+            "Russian" => "/link     \u{2014} \u{0421}\u{0432}\u{044f}\u{0437}\u{0430}\u{0442}\u{044c} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{044b} Telegram \u{0438} WhatsApp",
+            _ => "/link     \u{2014} Link your Telegram and WhatsApp accounts",
+        },
+        "help_unlink" => match lang {
+            "Spanish" => "/unlink   \u{2014} Desvincular una cuenta vinculada",
+            "Portuguese" => "/unlink   \u{2014} Desvincular uma conta vinculada",
+            "French" => "/unlink   \u{2014} Dissocier un compte li\u{00e9}",
+            "German" => "/unlink   \u{2014} Verkn\u{00fc}pftes Konto trennen",
+            "Italian" => "/unlink   \u{2014} Scollega un account collegato",
+            "Dutch" => "/unlink   \u{2014} Gekoppelde account ontkoppelen",
+            "Russian" => "/unlink   \u{2014} \u{041e}\u{0442}\u{0432}\u{044f}\u{0437}\u{0430}\u{0442}\u{044c} \u{0441}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}\u{043d}\u{044b}\u{0439} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}",
+            _ => "/unlink   \u{2014} Detach a linked account",
+        },
+        "help_export" => match lang {
+            "Spanish" => "/export   \u{2014} Descargar todo lo que recuerdo de ti",
+            "Portuguese" => "/export   \u{2014} Baixar tudo o que lembro sobre voc\u{00ea}",
+            "French" => "/export   \u{2014} T\u{00e9}l\u{00e9}charger tout ce que je sais de vous",
+            "German" => "/export   \u{2014} Alles herunterladen, was ich \u{00fc}ber dich wei\u{00df}",
+            "Italian" => "/export   \u{2014} Scarica tutto ci\u{00f2} che ricordo di te",
+            "Dutch" => "/export   \u{2014} Alles downloaden wat ik over je weet",
+            "Russian" => "/export   \u{2014} \u{0421}\u{043a}\u{0430}\u{0447}\u{0430}\u{0442}\u{044c} \u{0432}\u{0441}\u{0451}, \u{0447}\u{0442}\u{043e} \u{044f} \u{043f}\u{043e}\u{043c}\u{043d}\u{044e} \u{043e} \u{0432}\u{0430}\u{0441}",
+            _ => "/export   \u{2014} Download everything I remember about you",
         },
         "help_google" => match lang {
             "Spanish" => "/google   \u{2014} Configurar credenciales de cuenta Google",
@@ -203,6 +293,26 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "/context  \u{2014} \u{041f}\u{043e}\u{043a}\u{0430}\u{0437}\u{0430}\u{0442}\u{044c} \u{0441}\u{0435}\u{043a}\u{0446}\u{0438}\u{0438} \u{0441}\u{0438}\u{0441}\u{0442}\u{0435}\u{043c}\u{043d}\u{043e}\u{0433}\u{043e} \u{043f}\u{0440}\u{043e}\u{043c}\u{043f}\u{0442}\u{0430}",
             _ => "/context  \u{2014} Show system prompt sections and token estimate",
         },
+        "help_build" => match lang {
+            "Spanish" => "/build    \u{2014} Estado de la construcci\u{00f3}n o reanudar una interrumpida",
+            "Portuguese" => "/build    \u{2014} Status da constru\u{00e7}\u{00e3}o ou retomar uma interrompida",
+            "French" => "/build    \u{2014} \u{00c9}tat de la construction ou reprise d'une construction interrompue",
+            "German" => "/build    \u{2014} Build-Status oder abgebrochenen Build fortsetzen",
+            "Italian" => "/build    \u{2014} Stato della build o riprendi una interrotta",
+            "Dutch" => "/build    \u{2014} Buildstatus of een onderbroken build hervatten",
+            "Russian" => "/build    \u{2014} \u{0421}\u{0442}\u{0430}\u{0442}\u{0443}\u{0441} \u{0441}\u{0431}\u{043e}\u{0440}\u{043a}\u{0438} \u{0438}\u{043b}\u{0438} \u{0432}\u{043e}\u{0437}\u{043e}\u{0431}\u{043d}\u{043e}\u{0432}\u{0438}\u{0442}\u{044c} \u{043f}\u{0440}\u{0435}\u{0440}\u{0432}\u{0430}\u{043d}\u{043d}\u{0443}\u{044e}",
+            _ => "/build    \u{2014} Build status, or resume an interrupted build",
+        },
+        "help_topologies" => match lang {
+            "Spanish" => "/topologies \u{2014} Listar topolog\u{00ed}as de construcci\u{00f3}n instaladas",
+            "Portuguese" => "/topologies \u{2014} Listar topologias de constru\u{00e7}\u{00e3}o instaladas",
+            "French" => "/topologies \u{2014} Lister les topologies de construction install\u{00e9}es",
+            "German" => "/topologies \u{2014} Installierte Build-Topologien auflisten",
+            "Italian" => "/topologies \u{2014} Elenca le topologie di build installate",
+            "Dutch" => "/topologies \u{2014} Ge\u{00ef}nstalleerde buildtopologie\u{00eb}n tonen",
+            "Russian" => "/topologies \u{2014} \u{0421}\u{043f}\u{0438}\u{0441}\u{043e}\u{043a} \u{0443}\u{0441}\u{0442}\u{0430}\u{043d}\u{043e}\u{0432}\u{043b}\u{0435}\u{043d}\u{043d}\u{044b}\u{0445} \u{0442}\u{043e}\u{043f}\u{043e}\u{043b}\u{043e}\u{0433}\u{0438}\u{0439} \u{0441}\u{0431}\u{043e}\u{0440}\u{043a}\u{0438}",
+            _ => "/topologies \u{2014} List installed build topologies",
+        },
         "help_setup" => match lang {
             "Spanish" => "/setup    \u{2014} Configurar OMEGA \u{03a9} como experto en tu dominio",
             "Portuguese" => "/setup    \u{2014} Configurar OMEGA \u{03a9} como especialista no seu dom\u{00ed}nio",
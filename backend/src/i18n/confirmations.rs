@@ -55,6 +55,126 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             _ => "Task updated.",
         },
         "cancel_usage" => "Usage: /cancel <task-id>",
+        "nothing_running" => match lang {
+            "Spanish" => "No hay nada en curso ahora mismo.",
+            "Portuguese" => "Nada em andamento agora.",
+            "French" => "Rien n'est en cours pour le moment.",
+            "German" => "Gerade l\u{00e4}uft nichts.",
+            "Italian" => "Non c'\u{00e8} nulla in corso al momento.",
+            "Dutch" => "Er loopt nu niets.",
+            "Russian" => "\u{0421}\u{0435}\u{0439}\u{0447}\u{0430}\u{0441} \u{043d}\u{0438}\u{0447}\u{0435}\u{0433}\u{043e} \u{043d}\u{0435} \u{0432}\u{044b}\u{043f}\u{043e}\u{043b}\u{043d}\u{044f}\u{0435}\u{0442}\u{0441}\u{044f}.",
+            _ => "Nothing is running right now.",
+        },
+        "nothing_to_restart" => match lang {
+            "Spanish" => "Nada que reiniciar \u{2014} no hay ninguna respuesta en curso con mensajes en cola.",
+            "Portuguese" => "Nada para reiniciar \u{2014} n\u{00e3}o h\u{00e1} resposta em andamento com mensagens na fila.",
+            "French" => "Rien \u{00e0} relancer \u{2014} aucune r\u{00e9}ponse en cours avec des messages en attente.",
+            "German" => "Nichts neu zu starten \u{2014} keine laufende Antwort mit wartenden Nachrichten.",
+            "Italian" => "Niente da riavviare \u{2014} nessuna risposta in corso con messaggi in coda.",
+            "Dutch" => "Niets om opnieuw te starten \u{2014} geen lopend antwoord met wachtende berichten.",
+            "Russian" => "\u{041d}\u{0435}\u{0447}\u{0435}\u{0433}\u{043e} \u{043f}\u{0435}\u{0440}\u{0435}\u{0437}\u{0430}\u{043f}\u{0443}\u{0441}\u{043a}\u{0430}\u{0442}\u{044c} \u{2014} \u{043d}\u{0435}\u{0442} \u{0442}\u{0435}\u{043a}\u{0443}\u{0449}\u{0435}\u{0433}\u{043e} \u{043e}\u{0442}\u{0432}\u{0435}\u{0442}\u{0430} \u{0441} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{044f}\u{043c}\u{0438} \u{0432} \u{043e}\u{0447}\u{0435}\u{0440}\u{0435}\u{0434}\u{0438}.",
+            _ => "Nothing to restart \u{2014} no reply is running with messages queued behind it.",
+        },
+        "queue_restarting" => match lang {
+            "Spanish" => "Reiniciando con tus \u{00fa}ltimos mensajes incluidos\u{2026}",
+            "Portuguese" => "Reiniciando com suas \u{00fa}ltimas mensagens inclu\u{00ed}das\u{2026}",
+            "French" => "Je recommence en tenant compte de vos derniers messages\u{2026}",
+            "German" => "Starte neu, inklusive deiner neuesten Nachrichten\u{2026}",
+            "Italian" => "Riavvio includendo i tuoi ultimi messaggi\u{2026}",
+            "Dutch" => "Opnieuw beginnen, inclusief je laatste berichten\u{2026}",
+            "Russian" => "\u{041f}\u{0435}\u{0440}\u{0435}\u{0437}\u{0430}\u{043f}\u{0443}\u{0441}\u{043a}\u{0430}\u{044e} \u{0441} \u{0443}\u{0447}\u{0451}\u{0442}\u{043e}\u{043c} \u{0432}\u{0430}\u{0448}\u{0438}\u{0445} \u{043f}\u{043e}\u{0441}\u{043b}\u{0435}\u{0434}\u{043d}\u{0438}\u{0445} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0439}\u{2026}",
+            _ => "Restarting with your latest messages included\u{2026}",
+        },
+        "queue_restart_offer" => match lang {
+            "Spanish" => "Pulsa /restart para rehacer la respuesta actual con esto.",
+            "Portuguese" => "Toque em /restart para refazer a resposta atual com isso.",
+            "French" => "Touchez /restart pour refaire la r\u{00e9}ponse en cours avec ce message.",
+            "German" => "Tippe auf /restart, um die laufende Antwort damit neu zu erstellen.",
+            "Italian" => "Tocca /restart per rifare la risposta in corso con questo.",
+            "Dutch" => "Tik op /restart om het huidige antwoord hiermee opnieuw te maken.",
+            "Russian" => "\u{041d}\u{0430}\u{0436}\u{043c}\u{0438}\u{0442}\u{0435} /restart, \u{0447}\u{0442}\u{043e}\u{0431}\u{044b} \u{043f}\u{0435}\u{0440}\u{0435}\u{0434}\u{0435}\u{043b}\u{0430}\u{0442}\u{044c} \u{0442}\u{0435}\u{043a}\u{0443}\u{0449}\u{0438}\u{0439} \u{043e}\u{0442}\u{0432}\u{0435}\u{0442} \u{0441} \u{0443}\u{0447}\u{0451}\u{0442}\u{043e}\u{043c} \u{044d}\u{0442}\u{043e}\u{0433}\u{043e}.",
+            _ => "Tap /restart to redo the current answer with it.",
+        },
+        "nothing_to_undo" => match lang {
+            "Spanish" => "Nada que deshacer \u{2014} esta conversaci\u{00f3}n no tiene m\u{00e1}s intercambios.",
+            "Portuguese" => "Nada para desfazer \u{2014} esta conversa n\u{00e3}o tem mais trocas.",
+            "French" => "Rien \u{00e0} annuler \u{2014} cette conversation n'a plus d'\u{00e9}change.",
+            "German" => "Nichts r\u{00fc}ckg\u{00e4}ngig zu machen \u{2014} dieses Gespr\u{00e4}ch hat keinen Austausch mehr.",
+            "Italian" => "Niente da annullare \u{2014} questa conversazione non ha pi\u{00f9} scambi.",
+            "Dutch" => "Niets om ongedaan te maken \u{2014} dit gesprek heeft geen uitwisseling meer.",
+            "Russian" => "\u{041d}\u{0435}\u{0447}\u{0435}\u{0433}\u{043e} \u{043e}\u{0442}\u{043c}\u{0435}\u{043d}\u{044f}\u{0442}\u{044c} \u{2014} \u{0432} \u{044d}\u{0442}\u{043e}\u{043c} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}\u{0435} \u{0431}\u{043e}\u{043b}\u{044c}\u{0448}\u{0435} \u{043d}\u{0435}\u{0442} \u{043e}\u{0431}\u{043c}\u{0435}\u{043d}\u{043e}\u{0432}.",
+            _ => "Nothing to undo \u{2014} this conversation has no exchange left.",
+        },
+        "nothing_to_retry" => match lang {
+            "Spanish" => "Nada que reintentar \u{2014} no hay ninguna respuesta anterior en esta conversaci\u{00f3}n.",
+            "Portuguese" => "Nada para tentar de novo \u{2014} n\u{00e3}o h\u{00e1} resposta anterior nesta conversa.",
+            "French" => "Rien \u{00e0} relancer \u{2014} il n'y a pas de r\u{00e9}ponse pr\u{00e9}c\u{00e9}dente dans cette conversation.",
+            "German" => "Nichts zu wiederholen \u{2014} in diesem Gespr\u{00e4}ch gibt es keine vorherige Antwort.",
+            "Italian" => "Niente da riprovare \u{2014} non c'\u{00e8} una risposta precedente in questa conversazione.",
+            "Dutch" => "Niets om opnieuw te proberen \u{2014} er is geen eerder antwoord in dit gesprek.",
+            "Russian" => "\u{041d}\u{0435}\u{0447}\u{0435}\u{0433}\u{043e} \u{043f}\u{043e}\u{0432}\u{0442}\u{043e}\u{0440}\u{044f}\u{0442}\u{044c} \u{2014} \u{0432} \u{044d}\u{0442}\u{043e}\u{043c} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}\u{0435} \u{043d}\u{0435}\u{0442} \u{043f}\u{0440}\u{0435}\u{0434}\u{044b}\u{0434}\u{0443}\u{0449}\u{0435}\u{0433}\u{043e} \u{043e}\u{0442}\u{0432}\u{0435}\u{0442}\u{0430}.",
+            _ => "Nothing to retry \u{2014} there is no previous answer in this conversation.",
+        },
+        "snapshots_header" => match lang {
+            "Spanish" => "Instant\u{00e1}neas:",
+            "Portuguese" => "Snapshots:",
+            "French" => "Instantan\u{00e9}s :",
+            "German" => "Schnappsch\u{00fc}sse:",
+            "Italian" => "Istantanee:",
+            "Dutch" => "Momentopnames:",
+            "Russian" => "\u{0421}\u{043d}\u{0438}\u{043c}\u{043a}\u{0438}:",
+            _ => "Snapshots:",
+        },
+        "no_snapshots" => match lang {
+            "Spanish" => "A\u{00fa}n no hay instant\u{00e1}neas. Guarda una con /snapshot <nombre>.",
+            "Portuguese" => "Nenhum snapshot ainda. Salve um com /snapshot <nome>.",
+            "French" => "Aucun instantan\u{00e9} pour l'instant. Enregistrez-en un avec /snapshot <nom>.",
+            "German" => "Noch keine Schnappsch\u{00fc}sse. Speichere einen mit /snapshot <name>.",
+            "Italian" => "Nessuna istantanea ancora. Salvane una con /snapshot <nome>.",
+            "Dutch" => "Nog geen momentopnames. Sla er een op met /snapshot <naam>.",
+            "Russian" => "\u{0421}\u{043d}\u{0438}\u{043c}\u{043a}\u{043e}\u{0432} \u{043f}\u{043e}\u{043a}\u{0430} \u{043d}\u{0435}\u{0442}. \u{0421}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{0438}\u{0442}\u{0435} \u{0441} \u{043f}\u{043e}\u{043c}\u{043e}\u{0449}\u{044c}\u{044e} /snapshot <\u{0438}\u{043c}\u{044f}>.",
+            _ => "No snapshots yet. Save one with /snapshot <name>.",
+        },
+        "snapshot_usage" => match lang {
+            "Spanish" => "/snapshot <nombre> guarda esta conversaci\u{00f3}n, /snapshot delete <nombre> elimina una instant\u{00e1}nea, /branch <nombre> contin\u{00fa}a desde una.",
+            "Portuguese" => "/snapshot <nome> salva esta conversa, /snapshot delete <nome> remove um snapshot, /branch <nome> continua a partir de um.",
+            "French" => "/snapshot <nom> enregistre cette conversation, /snapshot delete <nom> supprime un instantan\u{00e9}, /branch <nom> repart d'un instantan\u{00e9}.",
+            "German" => "/snapshot <name> speichert dieses Gespr\u{00e4}ch, /snapshot delete <name> l\u{00f6}scht einen Schnappschuss, /branch <name> macht von einem aus weiter.",
+            "Italian" => "/snapshot <nome> salva questa conversazione, /snapshot delete <nome> elimina un'istantanea, /branch <nome> riparte da una.",
+            "Dutch" => "/snapshot <naam> bewaart dit gesprek, /snapshot delete <naam> verwijdert een momentopname, /branch <naam> gaat verder vanaf een.",
+            "Russian" => "/snapshot <\u{0438}\u{043c}\u{044f}> \u{0441}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{044f}\u{0435}\u{0442} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}, /snapshot delete <\u{0438}\u{043c}\u{044f}> \u{0443}\u{0434}\u{0430}\u{043b}\u{044f}\u{0435}\u{0442} \u{0441}\u{043d}\u{0438}\u{043c}\u{043e}\u{043a}, /branch <\u{0438}\u{043c}\u{044f}> \u{043f}\u{0440}\u{043e}\u{0434}\u{043e}\u{043b}\u{0436}\u{0430}\u{0435}\u{0442} \u{0441} \u{043d}\u{0435}\u{0433}\u{043e}.",
+            _ => "/snapshot <name> saves this conversation, /snapshot delete <name> removes a snapshot, /branch <name> continues from one.",
+        },
+        "snapshot_not_found" => match lang {
+            "Spanish" => "No hay ninguna instant\u{00e1}nea con ese nombre. Mira /snapshot.",
+            "Portuguese" => "Nenhum snapshot com esse nome. Veja /snapshot.",
+            "French" => "Aucun instantan\u{00e9} de ce nom. Voir /snapshot.",
+            "German" => "Kein Schnappschuss mit diesem Namen. Siehe /snapshot.",
+            "Italian" => "Nessuna istantanea con quel nome. Vedi /snapshot.",
+            "Dutch" => "Geen momentopname met die naam. Zie /snapshot.",
+            "Russian" => "\u{0421}\u{043d}\u{0438}\u{043c}\u{043a}\u{0430} \u{0441} \u{0442}\u{0430}\u{043a}\u{0438}\u{043c} \u{0438}\u{043c}\u{0435}\u{043d}\u{0435}\u{043c} \u{043d}\u{0435}\u{0442}. \u{0421}\u{043c}. /snapshot.",
+            _ => "No snapshot with that name. See /snapshot.",
+        },
+        "snapshot_deleted" => match lang {
+            "Spanish" => "Instant\u{00e1}nea eliminada.",
+            "Portuguese" => "Snapshot removido.",
+            "French" => "Instantan\u{00e9} supprim\u{00e9}.",
+            "German" => "Schnappschuss gel\u{00f6}scht.",
+            "Italian" => "Istantanea eliminata.",
+            "Dutch" => "Momentopname verwijderd.",
+            "Russian" => "\u{0421}\u{043d}\u{0438}\u{043c}\u{043e}\u{043a} \u{0443}\u{0434}\u{0430}\u{043b}\u{0451}\u{043d}.",
+            _ => "Snapshot deleted.",
+        },
+        "branch_usage" => match lang {
+            "Spanish" => "Uso: /branch <nombre> \u{2014} contin\u{00fa}a desde una instant\u{00e1}nea guardada (mira /snapshot).",
+            "Portuguese" => "Uso: /branch <nome> \u{2014} continua a partir de um snapshot salvo (veja /snapshot).",
+            "French" => "Usage : /branch <nom> \u{2014} repartir d'un instantan\u{00e9} enregistr\u{00e9} (voir /snapshot).",
+            "German" => "Verwendung: /branch <name> \u{2014} von einem gespeicherten Schnappschuss weitermachen (siehe /snapshot).",
+            "Italian" => "Uso: /branch <nome> \u{2014} riparti da un'istantanea salvata (vedi /snapshot).",
+            "Dutch" => "Gebruik: /branch <naam> \u{2014} ga verder vanaf een bewaarde momentopname (zie /snapshot).",
+            "Russian" => "\u{0418}\u{0441}\u{043f}\u{043e}\u{043b}\u{044c}\u{0437}\u{043e}\u{0432}\u{0430}\u{043d}\u{0438}\u{0435}: /branch <\u{0438}\u{043c}\u{044f}> \u{2014} \u{043f}\u{0440}\u{043e}\u{0434}\u{043e}\u{043b}\u{0436}\u{0438}\u{0442}\u{044c} \u{0441} \u{0441}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{0451}\u{043d}\u{043d}\u{043e}\u{0433}\u{043e} \u{0441}\u{043d}\u{0438}\u{043c}\u{043a}\u{0430} (\u{0441}\u{043c}. /snapshot).",
+            _ => "Usage: /branch <name> \u{2014} continue from a saved snapshot (see /snapshot).",
+        },
         "personality_reset" => match lang {
             "Spanish" => "Personalidad restablecida a los valores predeterminados.",
             "Portuguese" => "Personalidade redefinida para o padr\u{00e3}o.",
@@ -348,14 +468,138 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             _ => "Watchlist items:",
         },
         "heartbeat_no_watchlist" => match lang {
-            "Spanish" => "Sin elementos en la lista. Configura ~/.omega/prompts/HEARTBEAT.md",
-            "Portuguese" => "Nenhum item na lista. Configure ~/.omega/prompts/HEARTBEAT.md",
-            "French" => "Aucun \u{00e9}l\u{00e9}ment. Configurez ~/.omega/prompts/HEARTBEAT.md",
-            "German" => "Keine Eintr\u{00e4}ge. Konfiguriere ~/.omega/prompts/HEARTBEAT.md",
-            "Italian" => "Nessun elemento. Configura ~/.omega/prompts/HEARTBEAT.md",
-            "Dutch" => "Geen items. Configureer ~/.omega/prompts/HEARTBEAT.md",
-            "Russian" => "\u{041d}\u{0435}\u{0442} \u{044d}\u{043b}\u{0435}\u{043c}\u{0435}\u{043d}\u{0442}\u{043e}\u{0432}. \u{041d}\u{0430}\u{0441}\u{0442}\u{0440}\u{043e}\u{0439}\u{0442}\u{0435} ~/.omega/prompts/HEARTBEAT.md",
-            _ => "No watchlist items. Configure ~/.omega/prompts/HEARTBEAT.md",
+            "Spanish" => "Sin elementos en la lista. A\u{00f1}ade uno con /heartbeat add <elemento>",
+            "Portuguese" => "Nenhum item na lista. Adicione um com /heartbeat add <item>",
+            "French" => "Aucun \u{00e9}l\u{00e9}ment. Ajoutez-en un avec /heartbeat add <\u{00e9}l\u{00e9}ment>",
+            "German" => "Keine Eintr\u{00e4}ge. F\u{00fc}ge einen mit /heartbeat add <Eintrag> hinzu",
+            "Italian" => "Nessun elemento. Aggiungine uno con /heartbeat add <elemento>",
+            "Dutch" => "Geen items. Voeg er een toe met /heartbeat add <item>",
+            "Russian" => "\u{041d}\u{0435}\u{0442} \u{044d}\u{043b}\u{0435}\u{043c}\u{0435}\u{043d}\u{0442}\u{043e}\u{0432}. \u{0414}\u{043e}\u{0431}\u{0430}\u{0432}\u{044c}\u{0442}\u{0435} \u{0441} \u{043f}\u{043e}\u{043c}\u{043e}\u{0449}\u{044c}\u{044e} /heartbeat add <\u{044d}\u{043b}\u{0435}\u{043c}\u{0435}\u{043d}\u{0442}>",
+            _ => "No watchlist items. Add one with /heartbeat add <item>",
+        },
+        "heartbeat_hours" => match lang {
+            "Spanish" => "Horario activo:",
+            "Portuguese" => "Hor\u{00e1}rio ativo:",
+            "French" => "Heures actives:",
+            "German" => "Aktive Zeiten:",
+            "Italian" => "Orario attivo:",
+            "Dutch" => "Actieve uren:",
+            "Russian" => "\u{0410}\u{043a}\u{0442}\u{0438}\u{0432}\u{043d}\u{044b}\u{0435} \u{0447}\u{0430}\u{0441}\u{044b}:",
+            _ => "Active hours:",
+        },
+        "heartbeat_always" => match lang {
+            "Spanish" => "siempre",
+            "Portuguese" => "sempre",
+            "French" => "toujours",
+            "German" => "immer",
+            "Italian" => "sempre",
+            "Dutch" => "altijd",
+            "Russian" => "\u{0432}\u{0441}\u{0435}\u{0433}\u{0434}\u{0430}",
+            _ => "always",
+        },
+        "heartbeat_usage" => match lang {
+            "Spanish" => "Uso: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <elemento> [| cadencia] [| on_change]|remove <id>|here]",
+            "Portuguese" => "Uso: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <item> [| cad\u{00ea}ncia] [| on_change]|remove <id>|here]",
+            "French" => "Usage : /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <\u{00e9}l\u{00e9}ment> [| cadence] [| on_change]|remove <id>|here]",
+            "German" => "Verwendung: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <Eintrag> [| Rhythmus] [| on_change]|remove <id>|here]",
+            "Italian" => "Uso: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <elemento> [| cadenza] [| on_change]|remove <id>|here]",
+            "Dutch" => "Gebruik: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <item> [| frequentie] [| on_change]|remove <id>|here]",
+            "Russian" => "\u{0418}\u{0441}\u{043f}\u{043e}\u{043b}\u{044c}\u{0437}\u{043e}\u{0432}\u{0430}\u{043d}\u{0438}\u{0435}: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <\u{044d}\u{043b}\u{0435}\u{043c}\u{0435}\u{043d}\u{0442}> [| \u{0447}\u{0430}\u{0441}\u{0442}\u{043e}\u{0442}\u{0430}] [| on_change]|remove <id>|here]",
+            _ => "Usage: /heartbeat [on|off|interval <1-1440>|hours <HH:MM-HH:MM>|hours off|add <item> [| cadence] [| on_change]|remove <id>|here]",
+        },
+
+        // --- Tenants ---
+        "tenant_denied" => match lang {
+            "Spanish" => "Tu rol no permite este comando.",
+            "Portuguese" => "Sua fun\u{00e7}\u{00e3}o n\u{00e3}o permite este comando.",
+            "French" => "Votre r\u{00f4}le ne permet pas cette commande.",
+            "German" => "Deine Rolle erlaubt diesen Befehl nicht.",
+            "Italian" => "Il tuo ruolo non consente questo comando.",
+            "Dutch" => "Je rol staat deze opdracht niet toe.",
+            "Russian" => "\u{0412}\u{0430}\u{0448}\u{0430} \u{0440}\u{043e}\u{043b}\u{044c} \u{043d}\u{0435} \u{043f}\u{043e}\u{0437}\u{0432}\u{043e}\u{043b}\u{044f}\u{0435}\u{0442} \u{044d}\u{0442}\u{0443} \u{043a}\u{043e}\u{043c}\u{0430}\u{043d}\u{0434}\u{0443}.",
+            _ => "Your role does not allow this command.",
+        },
+        "role_denied" => match lang {
+            "Spanish" => "Tu rol no permite esta solicitud.",
+            "Portuguese" => "Sua fun\u{00e7}\u{00e3}o n\u{00e3}o permite esta solicita\u{00e7}\u{00e3}o.",
+            "French" => "Votre r\u{00f4}le ne permet pas cette demande.",
+            "German" => "Deine Rolle erlaubt diese Anfrage nicht.",
+            "Italian" => "Il tuo ruolo non consente questa richiesta.",
+            "Dutch" => "Je rol staat dit verzoek niet toe.",
+            "Russian" => "\u{0412}\u{0430}\u{0448}\u{0430} \u{0440}\u{043e}\u{043b}\u{044c} \u{043d}\u{0435} \u{043f}\u{043e}\u{0437}\u{0432}\u{043e}\u{043b}\u{044f}\u{0435}\u{0442} \u{044d}\u{0442}\u{043e}\u{0442} \u{0437}\u{0430}\u{043f}\u{0440}\u{043e}\u{0441}.",
+            _ => "Your role does not allow this request.",
+        },
+
+        // --- Identity linking ---
+        "link_invalid" => match lang {
+            "Spanish" => "Ese c\u{00f3}digo no es v\u{00e1}lido o ha caducado. Env\u{00ed}a /link en tu otra cuenta para obtener uno nuevo.",
+            "Portuguese" => "Esse c\u{00f3}digo \u{00e9} inv\u{00e1}lido ou expirou. Envie /link na sua outra conta para obter um novo.",
+            "French" => "Ce code est invalide ou a expir\u{00e9}. Envoyez /link depuis votre autre compte pour en obtenir un nouveau.",
+            "German" => "Dieser Code ist ung\u{00fc}ltig oder abgelaufen. Sende /link in deinem anderen Konto, um einen neuen zu erhalten.",
+            "Italian" => "Questo codice non \u{00e8} valido o \u{00e8} scaduto. Invia /link dall'altro account per ottenerne uno nuovo.",
+            "Dutch" => "Deze code is ongeldig of verlopen. Stuur /link vanaf je andere account voor een nieuwe.",
+            "Russian" => "\u{042d}\u{0442}\u{043e}\u{0442} \u{043a}\u{043e}\u{0434} \u{043d}\u{0435}\u{0434}\u{0435}\u{0439}\u{0441}\u{0442}\u{0432}\u{0438}\u{0442}\u{0435}\u{043b}\u{0435}\u{043d} \u{0438}\u{043b}\u{0438} \u{0438}\u{0441}\u{0442}\u{0451}\u{043a}. \u{041e}\u{0442}\u{043f}\u{0440}\u{0430}\u{0432}\u{044c}\u{0442}\u{0435} /link \u{0441} \u{0434}\u{0440}\u{0443}\u{0433}\u{043e}\u{0433}\u{043e} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{0430}, \u{0447}\u{0442}\u{043e}\u{0431}\u{044b} \u{043f}\u{043e}\u{043b}\u{0443}\u{0447}\u{0438}\u{0442}\u{044c} \u{043d}\u{043e}\u{0432}\u{044b}\u{0439}.",
+            _ => "That code is invalid or expired. Send /link from your other account to get a new one.",
+        },
+        "link_locked" => match lang {
+            "Spanish" => "Demasiados c\u{00f3}digos incorrectos. Int\u{00e9}ntalo de nuevo en una hora.",
+            "Portuguese" => "Muitos c\u{00f3}digos errados. Tente novamente daqui a uma hora.",
+            "French" => "Trop de codes erron\u{00e9}s. R\u{00e9}essayez dans une heure.",
+            "German" => "Zu viele falsche Codes. Versuche es in einer Stunde erneut.",
+            "Italian" => "Troppi codici errati. Riprova tra un'ora.",
+            "Dutch" => "Te veel onjuiste codes. Probeer het over een uur opnieuw.",
+            "Russian" => "\u{0421}\u{043b}\u{0438}\u{0448}\u{043a}\u{043e}\u{043c} \u{043c}\u{043d}\u{043e}\u{0433}\u{043e} \u{043d}\u{0435}\u{0432}\u{0435}\u{0440}\u{043d}\u{044b}\u{0445} \u{043a}\u{043e}\u{0434}\u{043e}\u{0432}. \u{041f}\u{043e}\u{043f}\u{0440}\u{043e}\u{0431}\u{0443}\u{0439}\u{0442}\u{0435} \u{0441}\u{043d}\u{043e}\u{0432}\u{0430} \u{0447}\u{0435}\u{0440}\u{0435}\u{0437} \u{0447}\u{0430}\u{0441}.",
+            _ => "Too many wrong codes. Try again in an hour.",
+        },
+        "link_same" => match lang {
+            "Spanish" => "Esta cuenta ya est\u{00e1} vinculada.",
+            "Portuguese" => "Esta conta j\u{00e1} est\u{00e1} vinculada.",
+            "French" => "Ce compte est d\u{00e9}j\u{00e0} li\u{00e9}.",
+            "German" => "Dieses Konto ist bereits verkn\u{00fc}pft.",
+            "Italian" => "Questo account \u{00e8} gi\u{00e0} collegato.",
+            "Dutch" => "Deze account is al gekoppeld.",
+            "Russian" => "\u{042d}\u{0442}\u{043e}\u{0442} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442} \u{0443}\u{0436}\u{0435} \u{0441}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}.",
+            _ => "This account is already linked.",
+        },
+        "link_conflicts" => match lang {
+            "Spanish" => "Datos distintos en ambas cuentas (se conserv\u{00f3} el m\u{00e1}s reciente):",
+            "Portuguese" => "Fatos diferentes nas duas contas (o mais recente foi mantido):",
+            "French" => "Faits diff\u{00e9}rents sur les deux comptes (le plus r\u{00e9}cent est conserv\u{00e9}) :",
+            "German" => "Unterschiedliche Fakten in beiden Konten (der neueste wurde behalten):",
+            "Italian" => "Fatti diversi nei due account (\u{00e8} stato mantenuto il pi\u{00f9} recente):",
+            "Dutch" => "Verschillende feiten in beide accounts (de nieuwste is behouden):",
+            "Russian" => "\u{0420}\u{0430}\u{0437}\u{043b}\u{0438}\u{0447}\u{0430}\u{044e}\u{0449}\u{0438}\u{0435}\u{0441}\u{044f} \u{0444}\u{0430}\u{043a}\u{0442}\u{044b} \u{0432} \u{0434}\u{0432}\u{0443}\u{0445} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{0430}\u{0445} (\u{0441}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{0451}\u{043d} \u{0441}\u{0430}\u{043c}\u{044b}\u{0439} \u{043d}\u{043e}\u{0432}\u{044b}\u{0439}):",
+            _ => "Facts that differed between the accounts (the most recent was kept):",
+        },
+        "unlink_done" => match lang {
+            "Spanish" => "Cuenta desvinculada. Los mensajes nuevos aqu\u{00ed} empiezan un perfil aparte; los datos ya fusionados se quedan en tu cuenta vinculada.",
+            "Portuguese" => "Conta desvinculada. Novas mensagens aqui come\u{00e7}am um perfil separado; os dados j\u{00e1} mesclados ficam na sua conta vinculada.",
+            "French" => "Compte dissoci\u{00e9}. Les nouveaux messages ici d\u{00e9}marrent un profil s\u{00e9}par\u{00e9} ; les donn\u{00e9}es d\u{00e9}j\u{00e0} fusionn\u{00e9}es restent sur votre compte li\u{00e9}.",
+            "German" => "Konto getrennt. Neue Nachrichten hier beginnen ein eigenes Profil; bereits zusammengef\u{00fc}hrte Daten bleiben im verkn\u{00fc}pften Konto.",
+            "Italian" => "Account scollegato. I nuovi messaggi qui iniziano un profilo separato; i dati gi\u{00e0} uniti restano nell'account collegato.",
+            "Dutch" => "Account ontkoppeld. Nieuwe berichten hier starten een apart profiel; al samengevoegde gegevens blijven bij je gekoppelde account.",
+            "Russian" => "\u{0410}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442} \u{043e}\u{0442}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}. \u{041d}\u{043e}\u{0432}\u{044b}\u{0435} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{044f} \u{0437}\u{0434}\u{0435}\u{0441}\u{044c} \u{043d}\u{0430}\u{0447}\u{043d}\u{0443}\u{0442} \u{043e}\u{0442}\u{0434}\u{0435}\u{043b}\u{044c}\u{043d}\u{044b}\u{0439} \u{043f}\u{0440}\u{043e}\u{0444}\u{0438}\u{043b}\u{044c}; \u{0443}\u{0436}\u{0435} \u{043e}\u{0431}\u{044a}\u{0435}\u{0434}\u{0438}\u{043d}\u{0451}\u{043d}\u{043d}\u{044b}\u{0435} \u{0434}\u{0430}\u{043d}\u{043d}\u{044b}\u{0435} \u{043e}\u{0441}\u{0442}\u{0430}\u{044e}\u{0442}\u{0441}\u{044f} \u{0432} \u{0441}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}\u{043d}\u{043e}\u{043c} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{0435}.",
+            _ => "Account unlinked. New messages here start a separate profile; data already merged stays with your linked account.",
+        },
+        "unlink_none" => match lang {
+            "Spanish" => "No tienes cuentas vinculadas.",
+            "Portuguese" => "Voc\u{00ea} n\u{00e3}o tem contas vinculadas.",
+            "French" => "Aucun compte li\u{00e9}.",
+            "German" => "Keine verkn\u{00fc}pften Konten.",
+            "Italian" => "Nessun account collegato.",
+            "Dutch" => "Geen gekoppelde accounts.",
+            "Russian" => "\u{041d}\u{0435}\u{0442} \u{0441}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}\u{043d}\u{044b}\u{0445} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{043e}\u{0432}.",
+            _ => "No linked accounts.",
+        },
+        "unlink_usage" => match lang {
+            "Spanish" => "Cuentas vinculadas. Env\u{00ed}a /unlink <id> para desvincular una:",
+            "Portuguese" => "Contas vinculadas. Envie /unlink <id> para desvincular uma:",
+            "French" => "Comptes li\u{00e9}s. Envoyez /unlink <id> pour en dissocier un :",
+            "German" => "Verkn\u{00fc}pfte Konten. Sende /unlink <id>, um eines zu trennen:",
+            "Italian" => "Account collegati. Invia /unlink <id> per scollegarne uno:",
+            "Dutch" => "Gekoppelde accounts. Stuur /unlink <id> om er een te ontkoppelen:",
+            "Russian" => "\u{0421}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}\u{043d}\u{044b}\u{0435} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{044b}. \u{041e}\u{0442}\u{043f}\u{0440}\u{0430}\u{0432}\u{044c}\u{0442}\u{0435} /unlink <id>, \u{0447}\u{0442}\u{043e}\u{0431}\u{044b} \u{043e}\u{0442}\u{0432}\u{044f}\u{0437}\u{0430}\u{0442}\u{044c}:",
+            _ => "Linked accounts. Send /unlink <id> to detach one:",
         },
 
         // --- Bug report ---
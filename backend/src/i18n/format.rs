@@ -227,3 +227,213 @@ pub fn task_save_failed(lang: &str, n: usize) -> String {
         _ => format!("\u{2717} Failed to save {n} task(s). Please try again."),
     }
 }
+
+/// Format the one-time `/link` code message.
+pub fn link_code(lang: &str, code: &str) -> String {
+    match lang {
+        "Spanish" => format!("Tu c\u{00f3}digo de vinculaci\u{00f3}n: *{code}*. En los pr\u{00f3}ximos 10 minutos, env\u{00ed}a /link {code} desde tu otra cuenta."),
+        "Portuguese" => format!("Seu c\u{00f3}digo de vincula\u{00e7}\u{00e3}o: *{code}*. Nos pr\u{00f3}ximos 10 minutos, envie /link {code} da sua outra conta."),
+        "French" => format!("Votre code de liaison : *{code}*. Dans les 10 prochaines minutes, envoyez /link {code} depuis votre autre compte."),
+        "German" => format!("Dein Verkn\u{00fc}pfungscode: *{code}*. Sende innerhalb von 10 Minuten /link {code} aus deinem anderen Konto."),
+        "Italian" => format!("Il tuo codice di collegamento: *{code}*. Entro 10 minuti, invia /link {code} dall'altro account."),
+        "Dutch" => format!("Je koppelcode: *{code}*. Stuur binnen 10 minuten /link {code} vanaf je andere account."),
+        "Russian" => format!("\u{0412}\u{0430}\u{0448} \u{043a}\u{043e}\u{0434} \u{043f}\u{0440}\u{0438}\u{0432}\u{044f}\u{0437}\u{043a}\u{0438}: *{code}*. \u{0412} \u{0442}\u{0435}\u{0447}\u{0435}\u{043d}\u{0438}\u{0435} 10 \u{043c}\u{0438}\u{043d}\u{0443}\u{0442} \u{043e}\u{0442}\u{043f}\u{0440}\u{0430}\u{0432}\u{044c}\u{0442}\u{0435} /link {code} \u{0441} \u{0434}\u{0440}\u{0443}\u{0433}\u{043e}\u{0433}\u{043e} \u{0430}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{0430}."),
+        _ => format!("Your link code: *{code}*. Within 10 minutes, send /link {code} from your other account."),
+    }
+}
+
+/// Caption for the `/export` archive.
+pub fn export_caption(lang: &str, facts: usize, conversations: usize, tasks: usize) -> String {
+    match lang {
+        "Spanish" => format!("Tu memoria: {facts} datos, {conversations} conversaciones, {tasks} tareas. Imp\u{00f3}rtala con `omega import`."),
+        "Portuguese" => format!("Sua mem\u{00f3}ria: {facts} fatos, {conversations} conversas, {tasks} tarefas. Importe com `omega import`."),
+        "French" => format!("Votre m\u{00e9}moire : {facts} faits, {conversations} conversations, {tasks} t\u{00e2}ches. Importez-la avec `omega import`."),
+        "German" => format!("Dein Ged\u{00e4}chtnis: {facts} Fakten, {conversations} Gespr\u{00e4}che, {tasks} Aufgaben. Importieren mit `omega import`."),
+        "Italian" => format!("La tua memoria: {facts} fatti, {conversations} conversazioni, {tasks} attivit\u{00e0}. Importala con `omega import`."),
+        "Dutch" => format!("Je geheugen: {facts} feiten, {conversations} gesprekken, {tasks} taken. Importeer met `omega import`."),
+        "Russian" => format!("\u{0412}\u{0430}\u{0448}\u{0430} \u{043f}\u{0430}\u{043c}\u{044f}\u{0442}\u{044c}: \u{0444}\u{0430}\u{043a}\u{0442}\u{043e}\u{0432} {facts}, \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}\u{043e}\u{0432} {conversations}, \u{0437}\u{0430}\u{0434}\u{0430}\u{0447} {tasks}. \u{0418}\u{043c}\u{043f}\u{043e}\u{0440}\u{0442}: `omega import`."),
+        _ => format!("Your memory: {facts} facts, {conversations} conversations, {tasks} tasks. Import it with `omega import`."),
+    }
+}
+
+/// Format the `/link` success summary.
+pub fn link_done(lang: &str, facts: u64, conversations: u64, tasks: u64) -> String {
+    match lang {
+        "Spanish" => format!("Cuentas vinculadas. Movidos: {facts} datos, {conversations} conversaciones, {tasks} tareas."),
+        "Portuguese" => format!("Contas vinculadas. Movidos: {facts} fatos, {conversations} conversas, {tasks} tarefas."),
+        "French" => format!("Comptes li\u{00e9}s. D\u{00e9}plac\u{00e9}s : {facts} faits, {conversations} conversations, {tasks} t\u{00e2}ches."),
+        "German" => format!("Konten verkn\u{00fc}pft. \u{00dc}bernommen: {facts} Fakten, {conversations} Gespr\u{00e4}che, {tasks} Aufgaben."),
+        "Italian" => format!("Account collegati. Spostati: {facts} fatti, {conversations} conversazioni, {tasks} attivit\u{00e0}."),
+        "Dutch" => format!("Accounts gekoppeld. Verplaatst: {facts} feiten, {conversations} gesprekken, {tasks} taken."),
+        "Russian" => format!("\u{0410}\u{043a}\u{043a}\u{0430}\u{0443}\u{043d}\u{0442}\u{044b} \u{0441}\u{0432}\u{044f}\u{0437}\u{0430}\u{043d}\u{044b}. \u{041f}\u{0435}\u{0440}\u{0435}\u{043d}\u{0435}\u{0441}\u{0435}\u{043d}\u{043e}: \u{0444}\u{0430}\u{043a}\u{0442}\u{043e}\u{0432} {facts}, \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440}\u{043e}\u{0432} {conversations}, \u{0437}\u{0430}\u{0434}\u{0430}\u{0447} {tasks}."),
+        _ => format!("Accounts linked. Moved {facts} facts, {conversations} conversations and {tasks} tasks."),
+    }
+}
+
+/// Format the /queue mode confirmation.
+pub fn queue_set(lang: &str, mode: &str) -> String {
+    match lang {
+        "Spanish" => format!("Modo de cola: {mode}"),
+        "Portuguese" => format!("Modo de fila: {mode}"),
+        "French" => format!("Mode de file d'attente : {mode}"),
+        "German" => format!("Warteschlangenmodus: {mode}"),
+        "Italian" => format!("Modalit\u{00e0} coda: {mode}"),
+        "Dutch" => format!("Wachtrijmodus: {mode}"),
+        "Russian" => format!("\u{0420}\u{0435}\u{0436}\u{0438}\u{043c} \u{043e}\u{0447}\u{0435}\u{0440}\u{0435}\u{0434}\u{0438}: {mode}"),
+        _ => format!("Queue mode set to: {mode}"),
+    }
+}
+
+/// Format /queue \u{2014} current mode, queued messages, and what each mode does.
+pub fn queue_show(lang: &str, mode: &str, queued: usize) -> String {
+    let (label, waiting, modes) = match lang {
+        "Spanish" => (
+            "Modo de cola:",
+            "Mensajes en espera:",
+            "merge \u{2014} los mensajes enviados mientras trabajo se responden juntos\n\
+             ask \u{2014} igual, y ofrezco /restart para reiniciar la respuesta actual con ellos\n\
+             parallel \u{2014} cada mensaje se responde en paralelo\n\
+             Uso: /queue merge|ask|parallel",
+        ),
+        "Portuguese" => (
+            "Modo de fila:",
+            "Mensagens em espera:",
+            "merge \u{2014} mensagens enviadas enquanto trabalho s\u{00e3}o respondidas juntas\n\
+             ask \u{2014} igual, e ofere\u{00e7}o /restart para reiniciar a resposta atual com elas\n\
+             parallel \u{2014} cada mensagem \u{00e9} respondida em paralelo\n\
+             Uso: /queue merge|ask|parallel",
+        ),
+        "French" => (
+            "Mode de file d'attente :",
+            "Messages en attente :",
+            "merge \u{2014} les messages envoy\u{00e9}s pendant que je travaille re\u{00e7}oivent une seule r\u{00e9}ponse\n\
+             ask \u{2014} pareil, et je propose /restart pour relancer la r\u{00e9}ponse en cours avec eux\n\
+             parallel \u{2014} chaque message est trait\u{00e9} en parall\u{00e8}le\n\
+             Utilisation : /queue merge|ask|parallel",
+        ),
+        "German" => (
+            "Warteschlangenmodus:",
+            "Wartende Nachrichten:",
+            "merge \u{2014} Nachrichten, die w\u{00e4}hrend der Arbeit eintreffen, werden gemeinsam beantwortet\n\
+             ask \u{2014} ebenso, zus\u{00e4}tzlich biete ich /restart an, um die laufende Antwort damit neu zu starten\n\
+             parallel \u{2014} jede Nachricht wird parallel beantwortet\n\
+             Verwendung: /queue merge|ask|parallel",
+        ),
+        "Italian" => (
+            "Modalit\u{00e0} coda:",
+            "Messaggi in attesa:",
+            "merge \u{2014} i messaggi inviati mentre lavoro ricevono una sola risposta\n\
+             ask \u{2014} uguale, e offro /restart per riavviare la risposta in corso con loro\n\
+             parallel \u{2014} ogni messaggio riceve risposta in parallelo\n\
+             Uso: /queue merge|ask|parallel",
+        ),
+        "Dutch" => (
+            "Wachtrijmodus:",
+            "Wachtende berichten:",
+            "merge \u{2014} berichten die binnenkomen terwijl ik bezig ben krijgen samen \u{00e9}\u{00e9}n antwoord\n\
+             ask \u{2014} hetzelfde, en ik bied /restart aan om het lopende antwoord ermee opnieuw te starten\n\
+             parallel \u{2014} elk bericht wordt parallel beantwoord\n\
+             Gebruik: /queue merge|ask|parallel",
+        ),
+        "Russian" => (
+            "\u{0420}\u{0435}\u{0436}\u{0438}\u{043c} \u{043e}\u{0447}\u{0435}\u{0440}\u{0435}\u{0434}\u{0438}:",
+            "\u{0421}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0439} \u{0432} \u{043e}\u{0447}\u{0435}\u{0440}\u{0435}\u{0434}\u{0438}:",
+            "merge \u{2014} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{044f}, \u{043e}\u{0442}\u{043f}\u{0440}\u{0430}\u{0432}\u{043b}\u{0435}\u{043d}\u{043d}\u{044b}\u{0435} \u{043f}\u{043e}\u{043a}\u{0430} \u{044f} \u{0437}\u{0430}\u{043d}\u{044f}\u{0442}, \u{043f}\u{043e}\u{043b}\u{0443}\u{0447}\u{0430}\u{044e}\u{0442} \u{043e}\u{0434}\u{0438}\u{043d} \u{043e}\u{0431}\u{0449}\u{0438}\u{0439} \u{043e}\u{0442}\u{0432}\u{0435}\u{0442}\n\
+             ask \u{2014} \u{0442}\u{043e} \u{0436}\u{0435}, \u{043f}\u{043b}\u{044e}\u{0441} /restart, \u{0447}\u{0442}\u{043e}\u{0431}\u{044b} \u{043f}\u{0435}\u{0440}\u{0435}\u{0437}\u{0430}\u{043f}\u{0443}\u{0441}\u{0442}\u{0438}\u{0442}\u{044c} \u{0442}\u{0435}\u{043a}\u{0443}\u{0449}\u{0438}\u{0439} \u{043e}\u{0442}\u{0432}\u{0435}\u{0442} \u{0441} \u{043d}\u{0438}\u{043c}\u{0438}\n\
+             parallel \u{2014} \u{043a}\u{0430}\u{0436}\u{0434}\u{043e}\u{0435} \u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0435} \u{043e}\u{0431}\u{0440}\u{0430}\u{0431}\u{0430}\u{0442}\u{044b}\u{0432}\u{0430}\u{0435}\u{0442}\u{0441}\u{044f} \u{043f}\u{0430}\u{0440}\u{0430}\u{043b}\u{043b}\u{0435}\u{043b}\u{044c}\u{043d}\u{043e}\n\
+             \u{0418}\u{0441}\u{043f}\u{043e}\u{043b}\u{044c}\u{0437}\u{043e}\u{0432}\u{0430}\u{043d}\u{0438}\u{0435}: /queue merge|ask|parallel",
+        ),
+        _ => (
+            "Queue mode:",
+            "Waiting messages:",
+            "merge \u{2014} messages sent while I'm busy are answered together\n\
+             ask \u{2014} the same, plus /restart to restart the current answer with them\n\
+             parallel \u{2014} every message is answered in parallel\n\
+             Usage: /queue merge|ask|parallel",
+        ),
+    };
+    format!("{label} {mode}\n{waiting} {queued}\n\n{modes}")
+}
+
+/// Format the acknowledgement for a message queued behind a running request.
+pub fn queued_ack(lang: &str, position: usize) -> String {
+    match lang {
+        "Spanish" => format!("Recibido \u{2014} n.\u{00ba} {position} en la cola, lo atiendo a continuaci\u{00f3}n."),
+        "Portuguese" => format!("Recebido \u{2014} n.\u{00ba} {position} na fila, cuido disso em seguida."),
+        "French" => format!("Re\u{00e7}u \u{2014} n\u{00b0} {position} dans la file, je m'en occupe ensuite."),
+        "German" => format!("Verstanden \u{2014} Nr. {position} in der Warteschlange, kommt als N\u{00e4}chstes dran."),
+        "Italian" => format!("Ricevuto \u{2014} n. {position} in coda, me ne occupo subito dopo."),
+        "Dutch" => format!("Ontvangen \u{2014} nr. {position} in de wachtrij, ik pak het hierna op."),
+        "Russian" => format!("\u{041f}\u{0440}\u{0438}\u{043d}\u{044f}\u{0442}\u{043e} \u{2014} \u{2116} {position} \u{0432} \u{043e}\u{0447}\u{0435}\u{0440}\u{0435}\u{0434}\u{0438}, \u{0437}\u{0430}\u{0439}\u{043c}\u{0443}\u{0441}\u{044c} \u{044d}\u{0442}\u{0438}\u{043c} \u{0441}\u{043b}\u{0435}\u{0434}\u{043e}\u{043c}."),
+        _ => format!("Got it \u{2014} #{position} in the queue, I'll get to it next."),
+    }
+}
+
+/// Format the /undo confirmation: the removed message and any cancelled tasks.
+pub fn undo_done(lang: &str, preview: &str, cancelled: usize) -> String {
+    let mut text = match lang {
+        "Spanish" => format!("Deshecho: \u{201c}{preview}\u{201d}"),
+        "Portuguese" => format!("Desfeito: \u{201c}{preview}\u{201d}"),
+        "French" => format!("Annul\u{00e9} : \u{00ab} {preview} \u{00bb}"),
+        "German" => format!("R\u{00fc}ckg\u{00e4}ngig gemacht: \u{201e}{preview}\u{201c}"),
+        "Italian" => format!("Annullato: \u{201c}{preview}\u{201d}"),
+        "Dutch" => format!("Ongedaan gemaakt: \u{201c}{preview}\u{201d}"),
+        "Russian" => format!("\u{041e}\u{0442}\u{043c}\u{0435}\u{043d}\u{0435}\u{043d}\u{043e}: \u{00ab}{preview}\u{00bb}"),
+        _ => format!("Undone: \u{201c}{preview}\u{201d}"),
+    };
+    if cancelled > 0 {
+        text.push_str(&match lang {
+            "Spanish" => format!(" \u{2014} se cancelaron {cancelled} tarea(s) programada(s)."),
+            "Portuguese" => format!(" \u{2014} {cancelled} tarefa(s) agendada(s) cancelada(s)."),
+            "French" => format!(" \u{2014} {cancelled} t\u{00e2}che(s) planifi\u{00e9}e(s) annul\u{00e9}e(s)."),
+            "German" => format!(" \u{2014} {cancelled} geplante Aufgabe(n) storniert."),
+            "Italian" => format!(" \u{2014} annullate {cancelled} attivit\u{00e0} programmate."),
+            "Dutch" => format!(" \u{2014} {cancelled} geplande ta(a)k(en) geannuleerd."),
+            "Russian" => format!(" \u{2014} \u{043e}\u{0442}\u{043c}\u{0435}\u{043d}\u{0435}\u{043d}\u{043e} \u{0437}\u{0430}\u{043f}\u{043b}\u{0430}\u{043d}\u{0438}\u{0440}\u{043e}\u{0432}\u{0430}\u{043d}\u{043d}\u{044b}\u{0445} \u{0437}\u{0430}\u{0434}\u{0430}\u{0447}: {cancelled}."),
+            _ => format!(" \u{2014} cancelled {cancelled} scheduled task(s)."),
+        });
+    }
+    text
+}
+
+/// Format the /snapshot confirmation.
+pub fn snapshot_saved(lang: &str, name: &str, count: usize) -> String {
+    match lang {
+        "Spanish" => format!("Instant\u{00e1}nea \u{201c}{name}\u{201d} guardada ({count} mensajes). Contin\u{00fa}a desde ella cuando quieras con /branch {name}."),
+        "Portuguese" => format!("Snapshot \u{201c}{name}\u{201d} salvo ({count} mensagens). Continue a partir dele quando quiser com /branch {name}."),
+        "French" => format!("Instantan\u{00e9} \u{00ab} {name} \u{00bb} enregistr\u{00e9} ({count} messages). Repartez-en quand vous voulez avec /branch {name}."),
+        "German" => format!("Schnappschuss \u{201e}{name}\u{201c} gespeichert ({count} Nachrichten). Jederzeit mit /branch {name} davon weitermachen."),
+        "Italian" => format!("Istantanea \u{201c}{name}\u{201d} salvata ({count} messaggi). Riparti da qui quando vuoi con /branch {name}."),
+        "Dutch" => format!("Momentopname \u{201c}{name}\u{201d} bewaard ({count} berichten). Ga er altijd mee verder met /branch {name}."),
+        "Russian" => format!("\u{0421}\u{043d}\u{0438}\u{043c}\u{043e}\u{043a} \u{00ab}{name}\u{00bb} \u{0441}\u{043e}\u{0445}\u{0440}\u{0430}\u{043d}\u{0451}\u{043d} (\u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0439}: {count}). \u{041f}\u{0440}\u{043e}\u{0434}\u{043e}\u{043b}\u{0436}\u{0438}\u{0442}\u{044c} \u{0441} \u{043d}\u{0435}\u{0433}\u{043e}: /branch {name}."),
+        _ => format!("Snapshot \u{201c}{name}\u{201d} saved ({count} messages). Continue from it any time with /branch {name}."),
+    }
+}
+
+/// Format the /branch confirmation.
+pub fn branch_done(lang: &str, name: &str, count: usize) -> String {
+    match lang {
+        "Spanish" => format!("Nueva rama desde \u{201c}{name}\u{201d} ({count} mensajes). La conversaci\u{00f3}n anterior se cerr\u{00f3} y resumi\u{00f3}."),
+        "Portuguese" => format!("Nova ramifica\u{00e7}\u{00e3}o a partir de \u{201c}{name}\u{201d} ({count} mensagens). A conversa anterior foi encerrada e resumida."),
+        "French" => format!("Nouvelle branche depuis \u{00ab} {name} \u{00bb} ({count} messages). La conversation pr\u{00e9}c\u{00e9}dente a \u{00e9}t\u{00e9} close et r\u{00e9}sum\u{00e9}e."),
+        "German" => format!("Abzweigung von \u{201e}{name}\u{201c} ({count} Nachrichten). Das vorherige Gespr\u{00e4}ch wurde beendet und zusammengefasst."),
+        "Italian" => format!("Nuovo ramo da \u{201c}{name}\u{201d} ({count} messaggi). La conversazione precedente \u{00e8} stata chiusa e riassunta."),
+        "Dutch" => format!("Vertakt vanaf \u{201c}{name}\u{201d} ({count} berichten). Het vorige gesprek is gesloten en samengevat."),
+        "Russian" => format!("\u{0412}\u{0435}\u{0442}\u{043a}\u{0430} \u{043e}\u{0442} \u{00ab}{name}\u{00bb} (\u{0441}\u{043e}\u{043e}\u{0431}\u{0449}\u{0435}\u{043d}\u{0438}\u{0439}: {count}). \u{041f}\u{0440}\u{0435}\u{0434}\u{044b}\u{0434}\u{0443}\u{0449}\u{0438}\u{0439} \u{0440}\u{0430}\u{0437}\u{0433}\u{043e}\u{0432}\u{043e}\u{0440} \u{0437}\u{0430}\u{043a}\u{0440}\u{044b}\u{0442} \u{0438} \u{043e}\u{0431}\u{043e}\u{0431}\u{0449}\u{0451}\u{043d}."),
+        _ => format!("Branched from \u{201c}{name}\u{201d} ({count} messages). The previous conversation was closed and summarized."),
+    }
+}
+
+/// Format the notice for action markers skipped on an untrusted turn.
+pub fn untrusted_actions_blocked(lang: &str, markers: &str) -> String {
+    match lang {
+        "Spanish" => format!("\u{26a0}\u{fe0f} No ejecut\u{00e9} estas acciones porque esta respuesta ley\u{00f3} contenido no confiable (p\u{00e1}ginas web, herramientas o webhooks): {markers}. P\u{00ed}demelo directamente si las quieres."),
+        "Portuguese" => format!("\u{26a0}\u{fe0f} N\u{00e3}o executei estas a\u{00e7}\u{00f5}es porque esta resposta leu conte\u{00fa}do n\u{00e3}o confi\u{00e1}vel (p\u{00e1}ginas web, ferramentas ou webhooks): {markers}. Pe\u{00e7}a diretamente se as quiser."),
+        "French" => format!("\u{26a0}\u{fe0f} Je n'ai pas ex\u{00e9}cut\u{00e9} ces actions car cette r\u{00e9}ponse a lu du contenu non fiable (pages web, outils ou webhooks) : {markers}. Demandez-les directement si vous les voulez."),
+        "German" => format!("\u{26a0}\u{fe0f} Diese Aktionen habe ich nicht ausgef\u{00fc}hrt, weil diese Antwort nicht vertrauensw\u{00fc}rdige Inhalte gelesen hat (Webseiten, Tools oder Webhooks): {markers}. Bitte mich direkt darum, wenn du sie willst."),
+        "Italian" => format!("\u{26a0}\u{fe0f} Non ho eseguito queste azioni perch\u{00e9} questa risposta ha letto contenuti non attendibili (pagine web, strumenti o webhook): {markers}. Chiedimele direttamente se le vuoi."),
+        "Dutch" => format!("\u{26a0}\u{fe0f} Deze acties heb ik niet uitgevoerd omdat dit antwoord onbetrouwbare inhoud las (webpagina's, tools of webhooks): {markers}. Vraag er rechtstreeks om als je ze wilt."),
+        "Russian" => format!("\u{26a0}\u{fe0f} \u{042d}\u{0442}\u{0438} \u{0434}\u{0435}\u{0439}\u{0441}\u{0442}\u{0432}\u{0438}\u{044f} \u{043d}\u{0435} \u{0432}\u{044b}\u{043f}\u{043e}\u{043b}\u{043d}\u{0435}\u{043d}\u{044b}, \u{043f}\u{043e}\u{0442}\u{043e}\u{043c}\u{0443} \u{0447}\u{0442}\u{043e} \u{043e}\u{0442}\u{0432}\u{0435}\u{0442} \u{043e}\u{043f}\u{0438}\u{0440}\u{0430}\u{043b}\u{0441}\u{044f} \u{043d}\u{0430} \u{043d}\u{0435}\u{043d}\u{0430}\u{0434}\u{0451}\u{0436}\u{043d}\u{044b}\u{0439} \u{043a}\u{043e}\u{043d}\u{0442}\u{0435}\u{043d}\u{0442} (\u{0432}\u{0435}\u{0431}-\u{0441}\u{0442}\u{0440}\u{0430}\u{043d}\u{0438}\u{0446}\u{044b}, \u{0438}\u{043d}\u{0441}\u{0442}\u{0440}\u{0443}\u{043c}\u{0435}\u{043d}\u{0442}\u{044b} \u{0438}\u{043b}\u{0438} \u{0432}\u{0435}\u{0431}\u{0445}\u{0443}\u{043a}\u{0438}): {markers}. \u{041f}\u{043e}\u{043f}\u{0440}\u{043e}\u{0441}\u{0438}\u{0442}\u{0435} \u{043e}\u{0431} \u{044d}\u{0442}\u{043e}\u{043c} \u{043d}\u{0430}\u{043f}\u{0440}\u{044f}\u{043c}\u{0443}\u{044e}, \u{0435}\u{0441}\u{043b}\u{0438} \u{043d}\u{0443}\u{0436}\u{043d}\u{043e}."),
+        _ => format!("\u{26a0}\u{fe0f} I didn't carry out these actions because this reply read untrusted content (web pages, tools or webhooks): {markers}. Ask me directly if you want them."),
+    }
+}
@@ -74,6 +74,16 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "\u{041f}\u{0440}\u{043e}\u{0435}\u{043a}\u{0442}\u{044b}",
             _ => "Projects",
         },
+        "topologies_header" => match lang {
+            "Spanish" => "Topolog\u{00ed}as de construcci\u{00f3}n",
+            "Portuguese" => "Topologias de constru\u{00e7}\u{00e3}o",
+            "French" => "Topologies de construction",
+            "German" => "Build-Topologien",
+            "Italian" => "Topologie di build",
+            "Dutch" => "Buildtopologie\u{00eb}n",
+            "Russian" => "\u{0422}\u{043e}\u{043f}\u{043e}\u{043b}\u{043e}\u{0433}\u{0438}\u{0438} \u{0441}\u{0431}\u{043e}\u{0440}\u{043a}\u{0438}",
+            _ => "Build Topologies",
+        },
         "commands_header" => match lang {
             "Spanish" => "Comandos de *OMEGA \u{03a9}*",
             "Portuguese" => "Comandos do *OMEGA \u{03a9}*",
@@ -144,6 +154,46 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "\u{0411}\u{0430}\u{0437}\u{0430} \u{0434}\u{0430}\u{043d}\u{043d}\u{044b}\u{0445}:",
             _ => "Database:",
         },
+        "maintenance_label" => match lang {
+            "Spanish" => "Mantenimiento:",
+            "Portuguese" => "Manuten\u{00e7}\u{00e3}o:",
+            "French" => "Maintenance :",
+            "German" => "Wartung:",
+            "Italian" => "Manutenzione:",
+            "Dutch" => "Onderhoud:",
+            "Russian" => "\u{041e}\u{0431}\u{0441}\u{043b}\u{0443}\u{0436}\u{0438}\u{0432}\u{0430}\u{043d}\u{0438}\u{0435}:",
+            _ => "Maintenance:",
+        },
+        "maintenance_never" => match lang {
+            "Spanish" => "a\u{00fa}n no se ha ejecutado",
+            "Portuguese" => "ainda n\u{00e3}o executada",
+            "French" => "pas encore ex\u{00e9}cut\u{00e9}e",
+            "German" => "noch nicht gelaufen",
+            "Italian" => "non ancora eseguita",
+            "Dutch" => "nog niet uitgevoerd",
+            "Russian" => "\u{0435}\u{0449}\u{0451} \u{043d}\u{0435} \u{0437}\u{0430}\u{043f}\u{0443}\u{0441}\u{043a}\u{0430}\u{043b}\u{043e}\u{0441}\u{044c}",
+            _ => "not run yet",
+        },
+        "integrity_ok" => match lang {
+            "Spanish" => "integridad correcta",
+            "Portuguese" => "integridade ok",
+            "French" => "int\u{00e9}grit\u{00e9} ok",
+            "German" => "Integrit\u{00e4}t ok",
+            "Italian" => "integrit\u{00e0} ok",
+            "Dutch" => "integriteit ok",
+            "Russian" => "\u{0446}\u{0435}\u{043b}\u{043e}\u{0441}\u{0442}\u{043d}\u{043e}\u{0441}\u{0442}\u{044c} \u{0432} \u{043f}\u{043e}\u{0440}\u{044f}\u{0434}\u{043a}\u{0435}",
+            _ => "integrity ok",
+        },
+        "integrity_failed" => match lang {
+            "Spanish" => "\u{26a0}\u{fe0f} problemas de integridad, ejecuta `omega db check`",
+            "Portuguese" => "\u{26a0}\u{fe0f} problemas de integridade, execute `omega db check`",
+            "French" => "\u{26a0}\u{fe0f} probl\u{00e8}mes d'int\u{00e9}grit\u{00e9}, lancez `omega db check`",
+            "German" => "\u{26a0}\u{fe0f} Integrit\u{00e4}tsprobleme, `omega db check` ausf\u{00fc}hren",
+            "Italian" => "\u{26a0}\u{fe0f} problemi di integrit\u{00e0}, esegui `omega db check`",
+            "Dutch" => "\u{26a0}\u{fe0f} integriteitsproblemen, voer `omega db check` uit",
+            "Russian" => "\u{26a0}\u{fe0f} \u{043f}\u{0440}\u{043e}\u{0431}\u{043b}\u{0435}\u{043c}\u{044b} \u{0446}\u{0435}\u{043b}\u{043e}\u{0441}\u{0442}\u{043d}\u{043e}\u{0441}\u{0442}\u{0438}, \u{0437}\u{0430}\u{043f}\u{0443}\u{0441}\u{0442}\u{0438}\u{0442}\u{0435} `omega db check`",
+            _ => "\u{26a0}\u{fe0f} integrity problems, run `omega db check`",
+        },
         "conversations" => match lang {
             "Spanish" => "Conversaciones:",
             "Portuguese" => "Conversas:",
@@ -301,6 +351,56 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "\u{0420}\u{0430}\u{0437}\u{0434}\u{0435}\u{043b}\u{044b} \u{0430}\u{043a}\u{0442}\u{0438}\u{0432}\u{0438}\u{0440}\u{0443}\u{044e}\u{0442}\u{0441}\u{044f} \u{043f}\u{043e} \u{043a}\u{043b}\u{044e}\u{0447}\u{0435}\u{0432}\u{044b}\u{043c} \u{0441}\u{043b}\u{043e}\u{0432}\u{0430}\u{043c}.\n\u{0418}\u{0441}\u{043f}\u{043e}\u{043b}\u{044c}\u{0437}\u{0443}\u{0439}\u{0442}\u{0435} /context full \u{0434}\u{043b}\u{044f} \u{043f}\u{043e}\u{043b}\u{043d}\u{043e}\u{0433}\u{043e} \u{043f}\u{0440}\u{043e}\u{043c}\u{043f}\u{0442}\u{0430}.",
             _ => "Sections activate based on message keywords.\nUse /context full to see the raw prompt.",
         },
+        "context_last_request" => match lang {
+            "Spanish" => "\u{00da}ltima petici\u{00f3}n:",
+            "Portuguese" => "\u{00da}ltimo pedido:",
+            "French" => "Derni\u{00e8}re requ\u{00ea}te :",
+            "German" => "Letzte Anfrage:",
+            "Italian" => "Ultima richiesta:",
+            "Dutch" => "Laatste verzoek:",
+            "Russian" => "\u{041f}\u{043e}\u{0441}\u{043b}\u{0435}\u{0434}\u{043d}\u{0438}\u{0439} \u{0437}\u{0430}\u{043f}\u{0440}\u{043e}\u{0441}:",
+            _ => "Last request:",
+        },
+        "context_no_request" => match lang {
+            "Spanish" => "A\u{00fa}n no hay ninguna petici\u{00f3}n desde el \u{00fa}ltimo reinicio.",
+            "Portuguese" => "Ainda n\u{00e3}o houve pedidos desde o \u{00fa}ltimo rein\u{00ed}cio.",
+            "French" => "Aucune requ\u{00ea}te depuis le dernier red\u{00e9}marrage.",
+            "German" => "Noch keine Anfrage seit dem letzten Neustart.",
+            "Italian" => "Ancora nessuna richiesta dall'ultimo riavvio.",
+            "Dutch" => "Nog geen verzoek sinds de laatste herstart.",
+            "Russian" => "\u{0417}\u{0430}\u{043f}\u{0440}\u{043e}\u{0441}\u{043e}\u{0432} \u{0441} \u{043f}\u{043e}\u{0441}\u{043b}\u{0435}\u{0434}\u{043d}\u{0435}\u{0433}\u{043e} \u{043f}\u{0435}\u{0440}\u{0435}\u{0437}\u{0430}\u{043f}\u{0443}\u{0441}\u{043a}\u{0430} \u{0435}\u{0449}\u{0451} \u{043d}\u{0435} \u{0431}\u{044b}\u{043b}\u{043e}.",
+            _ => "No request yet since the last restart.",
+        },
+        "context_unlimited" => match lang {
+            "Spanish" => "sin l\u{00ed}mite",
+            "Portuguese" => "sem limite",
+            "French" => "illimit\u{00e9}",
+            "German" => "unbegrenzt",
+            "Italian" => "illimitato",
+            "Dutch" => "onbeperkt",
+            "Russian" => "\u{0431}\u{0435}\u{0437} \u{043b}\u{0438}\u{043c}\u{0438}\u{0442}\u{0430}",
+            _ => "unlimited",
+        },
+        "context_dropped" => match lang {
+            "Spanish" => "omitidos",
+            "Portuguese" => "omitidos",
+            "French" => "omis",
+            "German" => "ausgelassen",
+            "Italian" => "omessi",
+            "Dutch" => "weggelaten",
+            "Russian" => "\u{043f}\u{0440}\u{043e}\u{043f}\u{0443}\u{0449}\u{0435}\u{043d}\u{043e}",
+            _ => "left out",
+        },
+        "context_cut" => match lang {
+            "Spanish" => "recortados",
+            "Portuguese" => "cortados",
+            "French" => "tronqu\u{00e9}s",
+            "German" => "gek\u{00fc}rzt",
+            "Italian" => "troncati",
+            "Dutch" => "ingekort",
+            "Russian" => "\u{043e}\u{0431}\u{0440}\u{0435}\u{0437}\u{0430}\u{043d}\u{043e}",
+            _ => "cut",
+        },
 
         // --- Empty states ---
         "no_pending_tasks" => match lang {
@@ -353,6 +453,16 @@ pub(super) fn lookup(key: &str, lang: &str) -> Option<&'static str> {
             "Russian" => "\u{041d}\u{0430}\u{0432}\u{044b}\u{043a}\u{0438} \u{043d}\u{0435} \u{0443}\u{0441}\u{0442}\u{0430}\u{043d}\u{043e}\u{0432}\u{043b}\u{0435}\u{043d}\u{044b}. \u{0421}\u{043e}\u{0437}\u{0434}\u{0430}\u{0439}\u{0442}\u{0435} \u{043a}\u{0430}\u{0442}\u{0430}\u{043b}\u{043e}\u{0433} \u{0432} ~/.omega/skills/ \u{0441} \u{0444}\u{0430}\u{0439}\u{043b}\u{043e}\u{043c} SKILL.md.",
             _ => "No skills installed. Create a directory in ~/.omega/skills/ with a SKILL.md file.",
         },
+        "topologies_footer" => match lang {
+            "Spanish" => "P\u{00ed}deme construir \"con la topolog\u{00ed}a X\", o a\u{00f1}ade `topology = \"X\"` al ROLE.md de un proyecto.",
+            "Portuguese" => "Pe\u{00e7}a para construir \"com a topologia X\", ou adicione `topology = \"X\"` ao ROLE.md de um projeto.",
+            "French" => "Demandez une construction \u{00ab} avec la topologie X \u{00bb}, ou ajoutez `topology = \"X\"` au ROLE.md d'un projet.",
+            "German" => "Bitte um einen Build \"mit Topologie X\" oder setze `topology = \"X\"` in der ROLE.md eines Projekts.",
+            "Italian" => "Chiedi una build \"con la topologia X\", oppure aggiungi `topology = \"X\"` al ROLE.md di un progetto.",
+            "Dutch" => "Vraag om een build \"met topologie X\", of zet `topology = \"X\"` in de ROLE.md van een project.",
+            "Russian" => "\u{041f}\u{043e}\u{043f}\u{0440}\u{043e}\u{0441}\u{0438}\u{0442}\u{0435} \u{0441}\u{0431}\u{043e}\u{0440}\u{043a}\u{0443} \u{00ab}\u{0441} \u{0442}\u{043e}\u{043f}\u{043e}\u{043b}\u{043e}\u{0433}\u{0438}\u{0435}\u{0439} X\u{00bb} \u{0438}\u{043b}\u{0438} \u{0434}\u{043e}\u{0431}\u{0430}\u{0432}\u{044c}\u{0442}\u{0435} `topology = \"X\"` \u{0432} ROLE.md \u{043f}\u{0440}\u{043e}\u{0435}\u{043a}\u{0442}\u{0430}.",
+            _ => "Ask me to build \"with the X topology\", or set `topology = \"X\"` in a project's ROLE.md.",
+        },
         "no_projects" => match lang {
             "Spanish" => "No se encontraron proyectos. Crea carpetas en ~/.omega/projects/ con ROLE.md",
             "Portuguese" => "Nenhum projeto encontrado. Crie pastas em ~/.omega/projects/ com ROLE.md",
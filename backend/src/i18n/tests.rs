@@ -14,6 +14,10 @@ fn test_all_keys_have_english_fallback() {
         "uptime",
         "provider",
         "database",
+        "maintenance_label",
+        "maintenance_never",
+        "integrity_ok",
+        "integrity_failed",
         "conversations",
         "messages",
         "facts_label",
@@ -30,6 +34,23 @@ fn test_all_keys_have_english_fallback() {
         "no_matching_task",
         "task_updated",
         "cancel_usage",
+        "nothing_running",
+        "nothing_to_restart",
+        "queue_restarting",
+        "queue_restart_offer",
+        "nothing_to_undo",
+        "nothing_to_retry",
+        "snapshots_header",
+        "no_snapshots",
+        "snapshot_usage",
+        "snapshot_not_found",
+        "snapshot_deleted",
+        "branch_usage",
+        "context_last_request",
+        "context_no_request",
+        "context_unlimited",
+        "context_dropped",
+        "context_cut",
         "personality_reset",
         "personality_already_default",
         "personality_default_prompt",
@@ -61,9 +82,28 @@ fn test_all_keys_have_english_fallback() {
         "heartbeat_disabled",
         "heartbeat_watchlist",
         "heartbeat_no_watchlist",
+        "heartbeat_hours",
+        "heartbeat_always",
+        "heartbeat_usage",
+        "tenant_denied",
+        "role_denied",
+        "link_invalid",
+        "link_locked",
+        "link_same",
+        "link_conflicts",
+        "unlink_done",
+        "unlink_none",
+        "unlink_usage",
         "help_heartbeat",
+        "help_link",
+        "help_unlink",
+        "help_export",
         "help_google",
         "help_setup",
+        "help_build",
+        "help_topologies",
+        "topologies_header",
+        "topologies_footer",
         "build_confirm_prompt",
     ];
     for key in keys {
@@ -149,6 +189,30 @@ fn test_format_helpers() {
     // tasks_updated_confirmed
     assert!(tasks_updated_confirmed("English", 3).contains("3 tasks"));
     assert!(tasks_updated_confirmed("Spanish", 2).contains("2 tareas"));
+
+    // link_code / link_done
+    assert!(link_code("English", "042917").contains("/link 042917"));
+    assert!(link_code("German", "042917").contains("/link 042917"));
+    assert!(link_done("English", 3, 2, 1).contains("3 facts"));
+    assert!(export_caption("English", 3, 2, 1).contains("omega import"));
+    assert!(export_caption("Dutch", 3, 2, 1).contains("omega import"));
+
+    // queue_show / queue_set / queued_ack
+    assert!(queue_show("English", "merge", 2).contains("merge"));
+    assert!(queue_show("German", "ask", 2).contains("/queue merge|ask|parallel"));
+    assert!(queue_set("Spanish", "parallel").contains("parallel"));
+    assert!(queued_ack("English", 3).contains("#3"));
+
+    // undo_done / snapshot_saved / branch_done
+    assert!(undo_done("English", "hi", 0).contains("hi"));
+    assert!(!undo_done("English", "hi", 0).contains("task"));
+    assert!(undo_done("English", "hi", 2).contains("2 scheduled"));
+    assert!(snapshot_saved("French", "plan", 4).contains("/branch plan"));
+    assert!(branch_done("German", "plan", 4).contains("plan"));
+
+    // untrusted_actions_blocked
+    assert!(untrusted_actions_blocked("English", "PURGE_FACTS").contains("PURGE_FACTS"));
+    assert!(untrusted_actions_blocked("Russian", "SCHEDULE:").contains("SCHEDULE:"));
 }
 
 #[test]
@@ -159,8 +223,14 @@ fn test_help_commands_all_languages() {
         "help_history",
         "help_facts",
         "help_forget",
+        "help_retry",
+        "help_undo",
+        "help_snapshot",
+        "help_branch",
         "help_tasks",
         "help_cancel",
+        "help_stop",
+        "help_queue",
         "help_language",
         "help_personality",
         "help_purge",
@@ -169,8 +239,13 @@ fn test_help_commands_all_languages() {
         "help_project",
         "help_whatsapp",
         "help_heartbeat",
+        "help_link",
+        "help_unlink",
+        "help_export",
         "help_google",
         "help_setup",
+        "help_build",
+        "help_topologies",
         "help_help",
     ];
     // All help keys should contain the command name (slash prefix)
@@ -7,9 +7,12 @@ mod init;
 mod init_google;
 mod init_style;
 mod init_wizard;
+mod maintenance;
 mod markers;
+mod memory_export;
 mod pair;
 mod provider_builder;
+mod secrets;
 mod selfcheck;
 mod service;
 mod task_confirmation;
@@ -20,6 +23,7 @@ use omega_channels::telegram::TelegramChannel;
 use omega_channels::whatsapp::WhatsAppChannel;
 use omega_core::config::{self, shellexpand, Prompts};
 use omega_core::context::Context;
+use omega_core::crypto::{self, SecretStore};
 use omega_memory::Store;
 use omega_providers::claude_code::ClaudeCodeProvider;
 use std::collections::HashMap;
@@ -93,6 +97,40 @@ enum Commands {
         #[command(subcommand)]
         action: ServiceAction,
     },
+    /// Export one user's memory (facts, conversations, tasks, lessons...) as JSON.
+    Export {
+        /// Sender id to export (aliases resolve to their canonical id).
+        #[arg(long)]
+        sender: String,
+
+        /// Output file (default: omega-export-<sender>-<timestamp>.json).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a memory export, assigning new ids and skipping duplicates.
+    Import {
+        /// Export file written by `omega export` or `/export`.
+        file: PathBuf,
+
+        /// Store the data under this sender id instead of the exported one.
+        #[arg(long)]
+        sender: Option<String>,
+    },
+    /// Back up, maintain and check the memory database.
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+    /// Set up and rotate the encryption key; manage encrypted secrets.
+    Secrets {
+        #[command(subcommand)]
+        action: SecretsAction,
+    },
+    /// Manage build topologies.
+    Topology {
+        #[command(subcommand)]
+        action: TopologyAction,
+    },
 }
 
 #[derive(Subcommand)]
@@ -105,6 +143,53 @@ enum ServiceAction {
     Status,
 }
 
+#[derive(Subcommand)]
+enum DbAction {
+    /// Write an online snapshot of the memory database.
+    Backup {
+        /// Output file (default: a timestamped snapshot in the backup dir).
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Run one maintenance pass now: snapshot, retention, optimize, vacuum, check.
+    Maintain,
+    /// Run a full integrity check.
+    Check,
+}
+
+#[derive(Subcommand)]
+enum SecretsAction {
+    /// Create the master key and encrypt existing memory and Google credentials.
+    Init,
+    /// Re-encrypt memory and secrets under a new master key.
+    Rotate,
+    /// Store a secret (prompted, or read from a file).
+    Set {
+        /// Secret name; reference it in config.toml as "secret:<name>".
+        name: String,
+
+        /// Read the value from this file instead of prompting.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// List stored secret names.
+    List,
+    /// Delete a secret.
+    Remove {
+        /// Secret name.
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TopologyAction {
+    /// Check a topology directory (TOPOLOGY.toml + agents/) before deploying it.
+    Validate {
+        /// Directory containing TOPOLOGY.toml.
+        dir: PathBuf,
+    },
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -163,6 +248,40 @@ async fn main() -> anyhow::Result<()> {
                 ServiceAction::Status => service::status()?,
             }
         }
+        Commands::Export { sender, output } => {
+            init_stdout_tracing("error");
+            memory_export::export(&cli.config, &sender, output).await?;
+        }
+        Commands::Import { file, sender } => {
+            init_stdout_tracing("error");
+            memory_export::import(&cli.config, &file, sender).await?;
+        }
+        Commands::Db { action } => {
+            init_stdout_tracing("error");
+            match action {
+                DbAction::Backup { output } => maintenance::cmd_backup(&cli.config, output).await?,
+                DbAction::Maintain => maintenance::cmd_maintain(&cli.config).await?,
+                DbAction::Check => maintenance::cmd_check(&cli.config).await?,
+            }
+        }
+        Commands::Secrets { action } => {
+            init_stdout_tracing("error");
+            match action {
+                SecretsAction::Init => secrets::cmd_init(&cli.config).await?,
+                SecretsAction::Rotate => secrets::cmd_rotate(&cli.config).await?,
+                SecretsAction::Set { name, file } => {
+                    secrets::cmd_set(&cli.config, &name, file.as_deref()).await?
+                }
+                SecretsAction::List => secrets::cmd_list(&cli.config).await?,
+                SecretsAction::Remove { name } => secrets::cmd_remove(&cli.config, &name).await?,
+            }
+        }
+        Commands::Topology { action } => {
+            init_stdout_tracing("error");
+            match action {
+                TopologyAction::Validate { dir } => cmd_topology_validate(&dir)?,
+            }
+        }
     }
 
     Ok(())
@@ -311,7 +430,9 @@ async fn cmd_start(config_path: &str) -> anyhow::Result<()> {
     }
 
     // Build memory.
-    let memory = Store::new(&cfg.memory).await?;
+    let memory = Store::new(&cfg.memory)
+        .await?
+        .with_cipher(crypto::cipher_for(&cfg)?);
 
     // Self-check before starting.
     if !selfcheck::run(&cfg, &memory).await {
@@ -335,6 +456,12 @@ async fn cmd_start(config_path: &str) -> anyhow::Result<()> {
         heartbeat_config: cfg.heartbeat.clone(),
         scheduler_config: cfg.scheduler.clone(),
         api_config: cfg.api.clone(),
+        approval_config: cfg.approval.clone(),
+        tenants_config: cfg.tenants.clone(),
+        roles_config: cfg.roles.clone(),
+        maintenance_config: cfg.memory.maintenance.clone(),
+        trust_config: cfg.trust.clone(),
+        secrets: SecretStore::for_config(&cfg)?.map(Arc::new),
         prompts,
         data_dir: cfg.omega.data_dir.clone(),
         skills,
@@ -391,6 +518,29 @@ async fn cmd_status(config_path: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Validate a topology directory and report errors and warnings.
+fn cmd_topology_validate(dir: &std::path::Path) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega topology validate").bold().to_string())?;
+    let report = gateway::validate_topology_dir(dir);
+
+    if !report.phases.is_empty() {
+        cliclack::log::info(format!("Phases: {}", report.phases.join(" → ")))?;
+    }
+    for warning in &report.warnings {
+        cliclack::log::warning(warning)?;
+    }
+    for error in &report.errors {
+        cliclack::log::error(error)?;
+    }
+
+    if report.errors.is_empty() {
+        cliclack::outro(format!("{} is valid", dir.display()))?;
+        Ok(())
+    } else {
+        anyhow::bail!("{} has {} error(s)", dir.display(), report.errors.len())
+    }
+}
+
 /// Send a one-shot message to the agent.
 async fn cmd_ask(config_path: &str, message: Vec<String>) -> anyhow::Result<()> {
     init_stdout_tracing("info");
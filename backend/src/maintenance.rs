@@ -0,0 +1,213 @@
+//! Database maintenance — `omega db` commands and the scheduled maintenance loop.
+//!
+//! One run takes a snapshot (rotated to `keep_backups`), applies the retention
+//! windows, optimizes the full-text index, reclaims free pages and runs an
+//! integrity check. Each run is recorded so `/status` can show the last one.
+
+use omega_core::config::{self, shellexpand, DataDir, MaintenanceConfig};
+use omega_core::crypto;
+use omega_memory::{MaintenanceRun, Store};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Where scheduled snapshots go: `backup_dir`, or `{data_dir}/backups`.
+pub fn backup_dir(cfg: &MaintenanceConfig, data_dir: &str) -> PathBuf {
+    if cfg.backup_dir.is_empty() {
+        DataDir::new(data_dir).backups()
+    } else {
+        PathBuf::from(shellexpand(&cfg.backup_dir))
+    }
+}
+
+/// How long to wait before the next run, given when the last one finished
+/// (SQLite `YYYY-MM-DD HH:MM:SS`, UTC). Overdue or never-run means now.
+pub fn next_run_delay(
+    last_finished_at: Option<&str>,
+    interval_hours: u64,
+    now: chrono::NaiveDateTime,
+) -> Duration {
+    let Some(last) = last_finished_at
+        .and_then(|ts| chrono::NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S").ok())
+    else {
+        return Duration::ZERO;
+    };
+    let interval = chrono::Duration::hours(interval_hours as i64);
+    (last + interval - now).to_std().unwrap_or(Duration::ZERO)
+}
+
+/// Run one full maintenance pass and record it. Failing steps are logged and skipped.
+pub async fn run_maintenance(
+    store: &Store,
+    cfg: &MaintenanceConfig,
+    backup_dir: &Path,
+) -> MaintenanceRun {
+    let mut run = MaintenanceRun::default();
+
+    if cfg.keep_backups > 0 {
+        match store.snapshot(backup_dir, cfg.keep_backups).await {
+            Ok(path) => run.snapshot = Some(path.display().to_string()),
+            Err(e) => error!("maintenance: snapshot failed: {e}"),
+        }
+    }
+    match store.prune(cfg).await {
+        Ok(pruned) => run.pruned = pruned,
+        Err(e) => error!("maintenance: {e}"),
+    }
+    if let Err(e) = store.optimize().await {
+        warn!("maintenance: {e}");
+    }
+    match store.compact().await {
+        Ok(bytes) => run.reclaimed_bytes = bytes,
+        Err(e) => warn!("maintenance: {e}"),
+    }
+    match store.integrity_check(false).await {
+        Ok(problems) => run.integrity = problems,
+        Err(e) => run.integrity = vec![e.to_string()],
+    }
+
+    if run.integrity.is_empty() {
+        info!(
+            "maintenance: pruned {} audit entries, {} outcomes, {} messages; reclaimed {} bytes",
+            run.pruned.audit, run.pruned.outcomes, run.pruned.messages, run.reclaimed_bytes
+        );
+    } else {
+        error!(
+            "maintenance: integrity check failed: {}",
+            run.integrity.join("; ")
+        );
+    }
+    if let Err(e) = store.record_maintenance(&run).await {
+        warn!("maintenance: {e}");
+    }
+    run
+}
+
+/// Background loop: run maintenance every `interval_hours`, catching up on
+/// start when the last recorded run is overdue. Runs until aborted on shutdown.
+pub async fn maintenance_loop(store: Store, cfg: MaintenanceConfig, backup_dir: PathBuf) {
+    let interval = Duration::from_secs(cfg.interval_hours.max(1) * 3600);
+    let last = store.last_maintenance().await.ok().flatten();
+    let first = next_run_delay(
+        last.as_ref().map(|r| r.finished_at.as_str()),
+        cfg.interval_hours.max(1),
+        chrono::Utc::now().naive_utc(),
+    );
+    // Let startup traffic settle before vacuuming.
+    tokio::time::sleep(first.max(Duration::from_secs(60))).await;
+    loop {
+        run_maintenance(&store, &cfg, &backup_dir).await;
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn load(config_path: &str) -> anyhow::Result<(config::Config, Store)> {
+    let cp = shellexpand(config_path);
+    let cfg = tokio::task::spawn_blocking(move || config::load(&cp))
+        .await
+        .map_err(|e| anyhow::anyhow!("config load task panicked: {e}"))??;
+    let store = Store::new(&cfg.memory)
+        .await?
+        .with_cipher(crypto::cipher_for(&cfg)?);
+    Ok((cfg, store))
+}
+
+/// `omega db backup` — write an online snapshot of the memory database.
+pub async fn cmd_backup(config_path: &str, output: Option<PathBuf>) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega db backup").bold().to_string())?;
+    let (cfg, store) = load(config_path).await?;
+    let dir = backup_dir(&cfg.memory.maintenance, &cfg.omega.data_dir);
+    let path = match output {
+        Some(path) => {
+            store.backup_to(&path).await?;
+            path
+        }
+        // Manual snapshots never rotate scheduled ones away.
+        None => store.snapshot(&dir, usize::MAX).await?,
+    };
+    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    cliclack::outro(format!("Wrote {} ({size} bytes)", path.display()))?;
+    Ok(())
+}
+
+/// `omega db maintain` — run one maintenance pass now.
+pub async fn cmd_maintain(config_path: &str) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega db maintain").bold().to_string())?;
+    let (cfg, store) = load(config_path).await?;
+    store.sqlite()?;
+    let dir = backup_dir(&cfg.memory.maintenance, &cfg.omega.data_dir);
+    let run = run_maintenance(&store, &cfg.memory.maintenance, &dir).await;
+
+    if let Some(snapshot) = &run.snapshot {
+        cliclack::log::success(format!("Snapshot: {snapshot}"))?;
+    }
+    cliclack::log::info(format!(
+        "Pruned {} audit entries, {} outcomes, {} messages",
+        run.pruned.audit, run.pruned.outcomes, run.pruned.messages
+    ))?;
+    cliclack::log::info(format!("Reclaimed {} bytes", run.reclaimed_bytes))?;
+    report_integrity(&run.integrity)
+}
+
+/// `omega db check` — full integrity check.
+pub async fn cmd_check(config_path: &str) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega db check").bold().to_string())?;
+    let (_, store) = load(config_path).await?;
+    let problems = store.integrity_check(true).await?;
+    report_integrity(&problems)
+}
+
+fn report_integrity(problems: &[String]) -> anyhow::Result<()> {
+    if problems.is_empty() {
+        cliclack::outro("Integrity check passed")?;
+        return Ok(());
+    }
+    for problem in problems {
+        cliclack::log::error(problem)?;
+    }
+    anyhow::bail!(
+        "integrity check found {} problem(s); restore from a snapshot",
+        problems.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ts: &str) -> chrono::NaiveDateTime {
+        chrono::NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_next_run_delay() {
+        let now = at("2026-03-01 12:00:00");
+        assert_eq!(next_run_delay(None, 24, now), Duration::ZERO);
+        assert_eq!(
+            next_run_delay(Some("2026-03-01 06:00:00"), 24, now),
+            Duration::from_secs(18 * 3600)
+        );
+        assert_eq!(
+            next_run_delay(Some("2026-02-20 06:00:00"), 24, now),
+            Duration::ZERO
+        );
+        assert_eq!(next_run_delay(Some("garbage"), 24, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_backup_dir_default_and_override() {
+        let default = MaintenanceConfig::default();
+        assert_eq!(
+            backup_dir(&default, "/srv/omega"),
+            PathBuf::from("/srv/omega/backups")
+        );
+        let custom = MaintenanceConfig {
+            backup_dir: "/mnt/nas/omega".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            backup_dir(&custom, "/srv/omega"),
+            PathBuf::from("/mnt/nas/omega")
+        );
+    }
+}
@@ -85,7 +85,10 @@ pub fn parse_skill_improve_line(line: &str) -> Option<(String, String)> {
 ///
 /// Creates a `## Lessons Learned` section if missing.
 pub fn apply_skill_improve(data_dir: &str, skill_name: &str, lesson: &str) -> Result<(), String> {
-    let skill_path = PathBuf::from(data_dir).join(format!("skills/{skill_name}/SKILL.md"));
+    let skill_path = omega_core::config::DataDir::new(data_dir)
+        .skills()
+        .join(skill_name)
+        .join("SKILL.md");
     if !skill_path.exists() {
         return Err("skill not found".to_string());
     }
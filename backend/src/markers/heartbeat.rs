@@ -1,13 +1,17 @@
 //! Heartbeat markers: HEARTBEAT_ADD, HEARTBEAT_REMOVE, HEARTBEAT_INTERVAL,
 //! HEARTBEAT_SUPPRESS_SECTION, HEARTBEAT_UNSUPPRESS_SECTION,
-//! and heartbeat file operations (global and per-project).
+//! and heartbeat file operations (seed file, project files, suppress lists).
 
+use omega_core::config::DataDir;
+use std::path::Path;
 use tracing::{info, warn};
 
 /// Action extracted from a `HEARTBEAT_ADD:`, `HEARTBEAT_REMOVE:`, or `HEARTBEAT_INTERVAL:` marker.
 #[derive(Debug, Clone, PartialEq)]
 pub enum HeartbeatAction {
+    /// Item spec: `description [| cadence] [| on_change]` (see `parse_item_spec`).
     Add(String),
+    /// Item id (or unique id prefix) to remove.
     Remove(String),
     /// Dynamically change the heartbeat interval (in minutes, 1–1440).
     SetInterval(u64),
@@ -67,22 +71,18 @@ pub fn strip_heartbeat_markers(text: &str) -> String {
         .to_string()
 }
 
-/// Read `~/.omega/prompts/HEARTBEAT.md` if it exists.
-pub fn read_heartbeat_file() -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-    let path = format!("{home}/.omega/prompts/HEARTBEAT.md");
-    let content = std::fs::read_to_string(path).ok()?;
-    if content.trim().is_empty() {
-        None
-    } else {
-        Some(content)
-    }
+/// Read `{data_dir}/prompts/HEARTBEAT.md` if it exists.
+pub fn read_heartbeat_file(data_dir: &str) -> Option<String> {
+    read_non_empty(&DataDir::new(data_dir).heartbeat_file())
+}
+
+/// Read a project-specific heartbeat file at `{data_dir}/projects/<name>/HEARTBEAT.md`.
+pub fn read_project_heartbeat_file(data_dir: &str, project_name: &str) -> Option<String> {
+    read_non_empty(&DataDir::new(data_dir).project_heartbeat_file(project_name))
 }
 
-/// Read a project-specific heartbeat file at `~/.omega/projects/<name>/HEARTBEAT.md`.
-pub fn read_project_heartbeat_file(project_name: &str) -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-    let path = format!("{home}/.omega/projects/{project_name}/HEARTBEAT.md");
+/// Read a file, treating missing and whitespace-only files alike.
+fn read_non_empty(path: &Path) -> Option<String> {
     let content = std::fs::read_to_string(path).ok()?;
     if content.trim().is_empty() {
         None
@@ -91,81 +91,6 @@ pub fn read_project_heartbeat_file(project_name: &str) -> Option<String> {
     }
 }
 
-/// Apply heartbeat add/remove actions to the appropriate heartbeat file.
-///
-/// When `project` is None, writes to `~/.omega/prompts/HEARTBEAT.md` (global).
-/// When `project` is Some, writes to `~/.omega/projects/<name>/HEARTBEAT.md`.
-/// Creates the file if missing. Prevents duplicate adds. Uses case-insensitive
-/// partial matching for removes. Skips comment lines (`#`) during removal.
-pub fn apply_heartbeat_changes(actions: &[HeartbeatAction], project: Option<&str>) {
-    let home = match std::env::var("HOME") {
-        Ok(h) => h,
-        Err(_) => return,
-    };
-
-    let (path, dir) = match project {
-        Some(name) => (
-            format!("{home}/.omega/projects/{name}/HEARTBEAT.md"),
-            format!("{home}/.omega/projects/{name}"),
-        ),
-        None => (
-            format!("{home}/.omega/prompts/HEARTBEAT.md"),
-            format!("{home}/.omega/prompts"),
-        ),
-    };
-
-    // Read existing lines (or start empty).
-    let mut lines: Vec<String> = std::fs::read_to_string(&path)
-        .unwrap_or_default()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
-
-    for action in actions {
-        match action {
-            HeartbeatAction::Add(item) => {
-                // Prevent duplicates (case-insensitive).
-                let already_exists = lines.iter().any(|l| {
-                    let trimmed = l.trim();
-                    !trimmed.starts_with('#')
-                        && trimmed.trim_start_matches("- ").eq_ignore_ascii_case(item)
-                });
-                if !already_exists {
-                    lines.push(format!("- {item}"));
-                }
-            }
-            HeartbeatAction::Remove(item) => {
-                let needle = item.to_lowercase();
-                lines.retain(|l| {
-                    let trimmed = l.trim();
-                    // Never remove comment lines.
-                    if trimmed.starts_with('#') {
-                        return true;
-                    }
-                    let content = trimmed.trim_start_matches("- ").to_lowercase();
-                    // Remove if content contains the needle (partial match).
-                    !content.contains(&needle)
-                });
-            }
-            // SetInterval is handled by process_markers / scheduler_loop, not here.
-            HeartbeatAction::SetInterval(_) => {}
-        }
-    }
-
-    // Write back.
-    let content = lines.join("\n");
-    // Ensure parent directory exists.
-    let _ = std::fs::create_dir_all(&dir);
-    let _ = std::fs::write(
-        &path,
-        if content.is_empty() {
-            content
-        } else {
-            content + "\n"
-        },
-    );
-}
-
 // ---------------------------------------------------------------------------
 // Section suppression — code-level gate for heartbeat sections (REQ-HB-010..014)
 // ---------------------------------------------------------------------------
@@ -215,25 +140,12 @@ pub fn parse_heartbeat_sections(content: &str) -> (String, Vec<(String, String)>
     (preamble, sections)
 }
 
-/// Build the suppress-file path for global or project heartbeat.
-fn suppress_file_path(project: Option<&str>) -> Option<String> {
-    let home = std::env::var("HOME").ok()?;
-    Some(match project {
-        Some(name) if !name.is_empty() => {
-            format!("{home}/.omega/projects/{name}/HEARTBEAT.suppress")
-        }
-        _ => format!("{home}/.omega/prompts/HEARTBEAT.suppress"),
-    })
-}
-
 /// Read suppressed section names from the `.suppress` companion file.
 ///
-/// Returns an empty vec if the file doesn't exist (all sections active).
-pub fn read_suppress_file(project: Option<&str>) -> Vec<String> {
-    let path = match suppress_file_path(project) {
-        Some(p) => p,
-        None => return Vec::new(),
-    };
+/// The personal scope (`None`) is `sender_id`'s own list; a project scope is
+/// shared by the project. Returns an empty vec if the file doesn't exist (all sections active).
+pub fn read_suppress_file(data_dir: &str, sender_id: &str, project: Option<&str>) -> Vec<String> {
+    let path = DataDir::new(data_dir).heartbeat_suppress_file(sender_id, project);
     match std::fs::read_to_string(&path) {
         Ok(content) => content
             .lines()
@@ -245,12 +157,9 @@ pub fn read_suppress_file(project: Option<&str>) -> Vec<String> {
 }
 
 /// Add a section name to the suppress file (no duplicates, case-insensitive).
-pub fn add_suppression(section: &str, project: Option<&str>) {
-    let path = match suppress_file_path(project) {
-        Some(p) => p,
-        None => return,
-    };
-    let mut entries = read_suppress_file(project);
+pub fn add_suppression(data_dir: &str, sender_id: &str, section: &str, project: Option<&str>) {
+    let path = DataDir::new(data_dir).heartbeat_suppress_file(sender_id, project);
+    let mut entries = read_suppress_file(data_dir, sender_id, project);
     let already = entries.iter().any(|e| e.eq_ignore_ascii_case(section));
     if already {
         return;
@@ -258,7 +167,7 @@ pub fn add_suppression(section: &str, project: Option<&str>) {
     entries.push(section.to_string());
 
     // Ensure parent directory exists.
-    if let Some(parent) = std::path::Path::new(&path).parent() {
+    if let Some(parent) = path.parent() {
         let _ = std::fs::create_dir_all(parent);
     }
     if let Err(e) = std::fs::write(&path, entries.join("\n") + "\n") {
@@ -269,12 +178,9 @@ pub fn add_suppression(section: &str, project: Option<&str>) {
 }
 
 /// Remove a section name from the suppress file (case-insensitive).
-pub fn remove_suppression(section: &str, project: Option<&str>) {
-    let path = match suppress_file_path(project) {
-        Some(p) => p,
-        None => return,
-    };
-    let entries = read_suppress_file(project);
+pub fn remove_suppression(data_dir: &str, sender_id: &str, section: &str, project: Option<&str>) {
+    let path = DataDir::new(data_dir).heartbeat_suppress_file(sender_id, project);
+    let entries = read_suppress_file(data_dir, sender_id, project);
     let filtered: Vec<&String> = entries
         .iter()
         .filter(|e| !e.eq_ignore_ascii_case(section))
@@ -292,11 +198,16 @@ pub fn remove_suppression(section: &str, project: Option<&str>) {
 
 /// Filter out suppressed sections from heartbeat content.
 ///
-/// Reads the suppress file for the given project scope, parses the content
+/// Reads the suppress file for the given sender and project scope, parses the content
 /// into sections, and returns only the preamble + non-suppressed sections.
 /// Returns `None` if all sections are suppressed (empty checklist).
-pub fn filter_suppressed_sections(content: &str, project: Option<&str>) -> Option<String> {
-    let suppressed = read_suppress_file(project);
+pub fn filter_suppressed_sections(
+    data_dir: &str,
+    sender_id: &str,
+    content: &str,
+    project: Option<&str>,
+) -> Option<String> {
+    let suppressed = read_suppress_file(data_dir, sender_id, project);
     if suppressed.is_empty() {
         return Some(content.to_string());
     }
@@ -377,11 +288,18 @@ pub fn strip_suppress_section_markers(text: &str) -> String {
 }
 
 /// Apply suppress/unsuppress actions from extracted markers.
-pub fn apply_suppress_actions(actions: &[SuppressAction], project: Option<&str>) {
+pub fn apply_suppress_actions(
+    data_dir: &str,
+    sender_id: &str,
+    actions: &[SuppressAction],
+    project: Option<&str>,
+) {
     for action in actions {
         match action {
-            SuppressAction::Suppress(name) => add_suppression(name, project),
-            SuppressAction::Unsuppress(name) => remove_suppression(name, project),
+            SuppressAction::Suppress(name) => add_suppression(data_dir, sender_id, name, project),
+            SuppressAction::Unsuppress(name) => {
+                remove_suppression(data_dir, sender_id, name, project)
+            }
         }
     }
 }
@@ -0,0 +1,278 @@
+//! Structured heartbeat items: cadence parsing, due checks, rendering,
+//! HEARTBEAT_CHECKED markers, and legacy markdown checklist conversion.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use omega_memory::{HeartbeatItem, NewHeartbeatItem};
+use tracing::warn;
+
+use super::heartbeat::parse_heartbeat_sections;
+
+/// How often a heartbeat item is checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cadence {
+    /// Every heartbeat pulse (the default).
+    EveryPulse,
+    /// At most once an hour.
+    Hourly,
+    /// Once a day, at the first pulse after the given local time.
+    Daily(NaiveTime),
+    /// Once a week, at the first pulse after the given local day and time.
+    Weekly(Weekday, NaiveTime),
+}
+
+fn default_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(9, 0, 0).expect("09:00 is always valid")
+}
+
+impl Cadence {
+    /// Parse a user-facing cadence ("hourly", "daily at 9:00", "weekly on friday").
+    ///
+    /// Empty input, "pulse" and "always" mean every pulse. Returns `None` for
+    /// anything unrecognized.
+    pub fn parse(text: &str) -> Option<Self> {
+        let lower = text.to_lowercase();
+        let tokens: Vec<&str> = lower
+            .split_whitespace()
+            .filter(|t| !matches!(*t, "at" | "on" | "every"))
+            .collect();
+        let Some((kind, rest)) = tokens.split_first() else {
+            return Some(Self::EveryPulse);
+        };
+        let time = |t: &str| NaiveTime::parse_from_str(t, "%H:%M").ok();
+        match (*kind, rest) {
+            ("pulse" | "always", []) => Some(Self::EveryPulse),
+            ("hourly" | "hour", []) => Some(Self::Hourly),
+            ("daily" | "day", []) => Some(Self::Daily(default_time())),
+            ("daily" | "day", [t]) => time(t).map(Self::Daily),
+            ("weekly" | "week", _) if rest.len() <= 2 => {
+                let mut day = Weekday::Mon;
+                let mut at = default_time();
+                for token in rest {
+                    if let Some(t) = time(token) {
+                        at = t;
+                    } else {
+                        day = token.parse().ok()?;
+                    }
+                }
+                Some(Self::Weekly(day, at))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a stored (normalized) cadence, falling back to every pulse.
+    pub fn from_stored(text: &str) -> Self {
+        Self::parse(text).unwrap_or(Self::EveryPulse)
+    }
+
+    /// Normalized form stored in the database ("" for every pulse).
+    pub fn to_stored(self) -> String {
+        match self {
+            Self::EveryPulse => String::new(),
+            Self::Hourly => "hourly".to_string(),
+            Self::Daily(t) => format!("daily {}", t.format("%H:%M")),
+            Self::Weekly(d, t) => format!(
+                "weekly {} {}",
+                d.to_string().to_lowercase(),
+                t.format("%H:%M")
+            ),
+        }
+    }
+
+    /// Human-readable label.
+    pub fn label(self) -> String {
+        match self {
+            Self::EveryPulse => "every pulse".to_string(),
+            other => other.to_stored(),
+        }
+    }
+}
+
+/// Parse a SQLite `datetime('now')` timestamp (UTC) into local time.
+fn parse_db_time(text: &str) -> Option<DateTime<Local>> {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|t| t.and_utc().with_timezone(&Local))
+}
+
+/// Resolve a local date + time, skipping DST gaps.
+fn local_at(date: chrono::NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+/// Most recent scheduled occurrence of a daily/weekly cadence at or before `now`.
+fn latest_occurrence(cadence: Cadence, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let today = now.date_naive();
+    match cadence {
+        Cadence::Daily(t) => {
+            let candidate = local_at(today, t)?;
+            if candidate > now {
+                local_at(today - Duration::days(1), t)
+            } else {
+                Some(candidate)
+            }
+        }
+        Cadence::Weekly(day, t) => {
+            let back = (7 + now.weekday().num_days_from_monday() - day.num_days_from_monday()) % 7;
+            let candidate = local_at(today - Duration::days(i64::from(back)), t)?;
+            if candidate > now {
+                local_at(today - Duration::days(i64::from(back) + 7), t)
+            } else {
+                Some(candidate)
+            }
+        }
+        Cadence::EveryPulse | Cadence::Hourly => None,
+    }
+}
+
+/// Whether an item should run on a pulse at `now`.
+///
+/// Daily and weekly items run once per occurrence; an item added after
+/// today's occurrence waits for the next one.
+pub fn is_item_due(item: &HeartbeatItem, now: DateTime<Local>) -> bool {
+    let last = item.last_checked_at.as_deref().and_then(parse_db_time);
+    match Cadence::from_stored(&item.cadence) {
+        Cadence::EveryPulse => true,
+        // One minute of slack so hourly items are not skipped by pulse jitter.
+        Cadence::Hourly => last.is_none_or(|l| now - l >= Duration::minutes(59)),
+        cadence => {
+            let Some(occurrence) = latest_occurrence(cadence, now) else {
+                return false;
+            };
+            let baseline = last.or_else(|| parse_db_time(&item.created_at));
+            baseline.is_none_or(|b| b < occurrence)
+        }
+    }
+}
+
+/// Parse a `HEARTBEAT_ADD:` / `/heartbeat add` spec: `description [| cadence] [| on_change]`.
+///
+/// Unrecognized cadences are ignored (every pulse) with a warning.
+pub fn parse_item_spec(spec: &str) -> NewHeartbeatItem {
+    let mut parts = spec.split('|').map(str::trim);
+    let mut item = NewHeartbeatItem {
+        description: parts.next().unwrap_or_default().to_string(),
+        ..Default::default()
+    };
+    for part in parts {
+        let flag = part.to_lowercase().replace([' ', '-'], "_");
+        if matches!(flag.as_str(), "on_change" | "alert_on_change" | "change") {
+            item.alert_on_change = true;
+        } else if let Some(cadence) = Cadence::parse(part) {
+            item.cadence = cadence.to_stored();
+        } else {
+            warn!("heartbeat: ignoring unknown cadence '{part}'");
+        }
+    }
+    item
+}
+
+/// Short id shown to users and referenced by markers.
+pub fn short_item_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
+
+/// Render one item as a checklist line: `- [id] description (cadence, state)`.
+///
+/// Multi-line descriptions (converted `## SECTION` blocks) keep their body
+/// below the first line.
+pub fn render_heartbeat_item(item: &HeartbeatItem) -> String {
+    let mut lines = item.description.trim().lines();
+    let first = lines
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('#')
+        .trim();
+    let mut meta = vec![Cadence::from_stored(&item.cadence).label()];
+    if item.alert_on_change {
+        meta.push("alert only on change".to_string());
+    }
+    if let Some(checked) = item.last_checked_at.as_deref().and_then(parse_db_time) {
+        meta.push(format!("last checked {}", checked.format("%Y-%m-%d %H:%M")));
+    }
+    if let Some(result) = item.last_result.as_deref() {
+        meta.push(format!("last result: {result}"));
+    }
+    let mut out = format!(
+        "- [{}] {first} ({})",
+        short_item_id(&item.id),
+        meta.join("; ")
+    );
+    for line in lines {
+        out.push('\n');
+        out.push_str(line);
+    }
+    out
+}
+
+/// Render a list of items, one per line.
+pub fn render_heartbeat_items(items: &[HeartbeatItem]) -> String {
+    items
+        .iter()
+        .map(render_heartbeat_item)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Instructions appended to a heartbeat run that contains structured items.
+pub const HEARTBEAT_ITEM_PROTOCOL: &str = "Items are tagged with [id]. After checking an item, \
+     emit HEARTBEAT_CHECKED: <id> | <short result> on its own line (e.g. \"3 unread\", \"ok\"). \
+     For items marked \"alert only on change\", mention them only when the result differs from \
+     their last result.";
+
+/// Section name of an item converted from a `## SECTION` block, if any.
+pub fn item_section_name(description: &str) -> Option<String> {
+    let (_, sections) = parse_heartbeat_sections(description.trim_start());
+    sections.into_iter().next().map(|(name, _)| name)
+}
+
+/// Split a legacy free-form checklist into item descriptions.
+///
+/// Each bullet or plain line before the first `## ` header becomes one item;
+/// each `## SECTION` block becomes one multi-line item. Comment and title
+/// lines (`#`) are dropped.
+pub fn checklist_to_item_descriptions(checklist: &str) -> Vec<String> {
+    let (preamble, sections) = parse_heartbeat_sections(checklist);
+    let mut items: Vec<String> = preamble
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            l.trim_start_matches("- ")
+                .trim_start_matches("* ")
+                .trim()
+                .to_string()
+        })
+        .filter(|l| !l.is_empty())
+        .collect();
+    items.extend(
+        sections
+            .into_iter()
+            .map(|(_, body)| body.trim().to_string())
+            .filter(|b| !b.is_empty()),
+    );
+    items
+}
+
+/// Extract `HEARTBEAT_CHECKED: <id> | <result>` markers as `(id, result)` pairs.
+pub fn extract_heartbeat_checked_markers(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("HEARTBEAT_CHECKED:")?;
+            let (id, result) = rest.split_once('|')?;
+            let id = id.trim().trim_matches(|c| c == '[' || c == ']');
+            let result = result.trim();
+            (!id.is_empty() && !result.is_empty()).then(|| (id.to_string(), result.to_string()))
+        })
+        .collect()
+}
+
+/// Strip `HEARTBEAT_CHECKED:` lines from response text.
+pub fn strip_heartbeat_checked_markers(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim().starts_with("HEARTBEAT_CHECKED:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
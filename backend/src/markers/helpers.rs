@@ -1,5 +1,5 @@
 //! Miscellaneous helpers: status messages, provider errors, workspace images,
-//! active hours, plan parsing, and inbox operations.
+//! active hours, plan parsing, inbox operations, and attachment media.
 
 use std::path::PathBuf;
 use std::time::SystemTime;
@@ -354,47 +354,13 @@ pub fn next_active_start_utc(start: &str) -> String {
         .to_string()
 }
 
-/// Parse a plan/classification response into numbered steps.
-///
-/// Returns `None` if the response is "DIRECT" (case-insensitive) or has fewer
-/// than 2 steps. Steps are extracted from lines starting with `N.` where N is
-/// a digit.
-pub fn parse_plan_response(text: &str) -> Option<Vec<String>> {
-    let trimmed = text.trim();
-    if trimmed.eq_ignore_ascii_case("DIRECT") {
-        return None;
-    }
-
-    let steps: Vec<String> = trimmed
-        .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            // Match lines starting with "N. " where N is a digit.
-            if line.len() >= 3 && line.as_bytes()[0].is_ascii_digit() && line.as_bytes()[1] == b'.'
-            {
-                Some(line[2..].trim().to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    if steps.len() >= 2 {
-        Some(steps)
-    } else {
-        None
-    }
-}
-
 // ---------------------------------------------------------------------------
 // Inbox helpers
 // ---------------------------------------------------------------------------
 
 /// Ensure the workspace inbox directory exists and return its path.
 pub fn ensure_inbox_dir(data_dir: &str) -> PathBuf {
-    let dir = PathBuf::from(omega_core::config::shellexpand(data_dir))
-        .join("workspace")
-        .join("inbox");
+    let dir = omega_core::config::DataDir::new(data_dir).inbox();
     let _ = std::fs::create_dir_all(&dir);
     dir
 }
@@ -489,3 +455,94 @@ pub fn purge_inbox(data_dir: &str) {
         }
     }
 }
+
+// ---------------------------------------------------------------------------
+// Media helpers
+// ---------------------------------------------------------------------------
+
+/// Most recent history attachments re-sent to the provider.
+const MAX_HISTORY_MEDIA: usize = 4;
+
+/// Normalize image and PDF attachments and keep them in the media directory.
+///
+/// Returns each saved file with its content block. Unsupported or oversized
+/// attachments are skipped with a warning.
+pub fn save_attachments_to_media(
+    media_dir: &std::path::Path,
+    attachments: &[omega_core::message::Attachment],
+) -> Vec<(PathBuf, omega_core::context::ContentBlock)> {
+    use omega_core::message::AttachmentType;
+
+    let mut saved = Vec::new();
+    for attachment in attachments {
+        if !matches!(
+            attachment.file_type,
+            AttachmentType::Image | AttachmentType::Document
+        ) {
+            continue;
+        }
+        let Some(ref data) = attachment.data else {
+            continue;
+        };
+        let name = attachment.filename.as_deref().unwrap_or("attachment");
+        let block = match omega_core::media::content_block(data, name) {
+            Ok(block) => block,
+            Err(reason) => {
+                tracing::warn!("media: skipping attachment {reason}");
+                continue;
+            }
+        };
+        let ext = omega_core::media::sniff_media_type(data)
+            .map(omega_core::media::extension)
+            .unwrap_or("bin");
+        if let Err(e) = std::fs::create_dir_all(media_dir) {
+            tracing::warn!("media: failed to create {}: {e}", media_dir.display());
+            break;
+        }
+        let path = media_dir.join(format!("{}.{ext}", uuid::Uuid::new_v4()));
+        match std::fs::write(&path, data) {
+            Ok(()) => saved.push((path, block)),
+            Err(e) => tracing::warn!("media: failed to write {}: {e}", path.display()),
+        }
+    }
+    saved
+}
+
+/// Reload attachments referenced by history entries.
+///
+/// Only the most recent [`MAX_HISTORY_MEDIA`] files are loaded; missing or
+/// unreadable files are skipped and their text reference stays as-is.
+/// References resolve only inside `media_dir`, the sender's own media dir.
+pub fn attach_history_media(
+    history: &mut [omega_core::context::ContextEntry],
+    media_dir: &std::path::Path,
+) {
+    let mut remaining = MAX_HISTORY_MEDIA;
+    for entry in history.iter_mut().rev() {
+        if remaining == 0 {
+            break;
+        }
+        if entry.role != "user" {
+            continue;
+        }
+        for reference in omega_core::media::references(&entry.content) {
+            if remaining == 0 {
+                break;
+            }
+            let Some(path) = omega_core::media::resolve(media_dir, &reference) else {
+                tracing::warn!(
+                    "media: ignoring reference outside the media dir: {}",
+                    reference.display()
+                );
+                continue;
+            };
+            match omega_core::media::load(&path) {
+                Ok(block) => {
+                    entry.media.push(block);
+                    remaining -= 1;
+                }
+                Err(e) => tracing::debug!("media: history attachment unavailable: {e}"),
+            }
+        }
+    }
+}
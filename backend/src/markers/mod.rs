@@ -4,20 +4,26 @@
 //! - `schedule` — SCHEDULE and SCHEDULE_ACTION markers
 //! - `protocol` — Simple markers (LANG_SWITCH, PERSONALITY, FORGET, CANCEL_TASK, etc.)
 //! - `heartbeat` — Heartbeat markers and file operations
+//! - `heartbeat_items` — Structured heartbeat items (cadence, rendering, HEARTBEAT_CHECKED)
 //! - `actions` — BUG_REPORT, SKILL_IMPROVE, ACTION_OUTCOME
 //! - `helpers` — Status messages, workspace images, inbox, classification
+//! - `tool_calls` — Native action tool calls rendered as marker lines
 
 mod actions;
 mod heartbeat;
+mod heartbeat_items;
 mod helpers;
 mod protocol;
 mod schedule;
+mod tool_calls;
 
 pub use actions::*;
 pub use heartbeat::*;
+pub use heartbeat_items::*;
 pub use helpers::*;
 pub use protocol::*;
 pub use schedule::*;
+pub use tool_calls::*;
 
 // ---------------------------------------------------------------------------
 // Generic inline marker helpers
@@ -98,6 +104,7 @@ pub fn strip_all_remaining_markers(text: &str) -> String {
         "HEARTBEAT_ADD:",
         "HEARTBEAT_REMOVE:",
         "HEARTBEAT_INTERVAL:",
+        "HEARTBEAT_CHECKED:",
         "SKILL_IMPROVE:",
         "BUG_REPORT:",
         "FORGET_CONVERSATION",
@@ -120,5 +127,20 @@ pub fn strip_all_remaining_markers(text: &str) -> String {
     result
 }
 
+/// Strip the side-effecting markers (`sanitize::ACTION_MARKERS`) from a reply
+/// to a turn that consumed untrusted content. Returns the cleaned text and the
+/// markers that were dropped.
+pub fn strip_action_markers(text: &str) -> (String, Vec<&'static str>) {
+    let mut result = text.to_string();
+    let mut dropped = Vec::new();
+    for marker in omega_core::sanitize::ACTION_MARKERS {
+        if result.contains(marker) {
+            result = strip_inline_marker(&result, marker);
+            dropped.push(*marker);
+        }
+    }
+    (result, dropped)
+}
+
 #[cfg(test)]
 mod tests;
@@ -16,8 +16,16 @@ fn test_is_within_active_hours_narrow_miss() {
 
 #[test]
 fn test_read_heartbeat_file_returns_none_when_missing() {
-    let result = read_heartbeat_file();
-    let _ = result;
+    let tmp = tempfile::tempdir().unwrap();
+    let data_dir = tmp.path().to_str().unwrap();
+    assert!(read_heartbeat_file(data_dir).is_none());
+
+    std::fs::create_dir_all(tmp.path().join("prompts")).unwrap();
+    std::fs::write(tmp.path().join("prompts/HEARTBEAT.md"), "  \n").unwrap();
+    assert!(read_heartbeat_file(data_dir).is_none(), "blank file = none");
+
+    std::fs::write(tmp.path().join("prompts/HEARTBEAT.md"), "- Check inbox\n").unwrap();
+    assert_eq!(read_heartbeat_file(data_dir).unwrap(), "- Check inbox\n");
 }
 
 #[test]
@@ -125,87 +133,41 @@ fn test_extract_heartbeat_mixed() {
 }
 
 #[test]
-fn test_apply_heartbeat_add() {
-    // Both add and remove tests share a single HOME env var to avoid
-    // race conditions between parallel tests (HOME is process-global).
-    let fake_home = std::env::temp_dir().join("omega_test_hb_changes_home");
-    let _ = std::fs::remove_dir_all(&fake_home);
-    let _ = std::fs::create_dir_all(fake_home.join(".omega/prompts"));
-    std::fs::write(
-        fake_home.join(".omega/prompts/HEARTBEAT.md"),
-        "# My checklist\n- Existing item\n",
-    )
-    .unwrap();
-    let original_home = std::env::var("HOME").unwrap();
-    std::env::set_var("HOME", &fake_home);
-
-    apply_heartbeat_changes(&[HeartbeatAction::Add("New item".to_string())], None);
-
-    let content = std::fs::read_to_string(fake_home.join(".omega/prompts/HEARTBEAT.md")).unwrap();
-    assert!(content.contains("- Existing item"), "should keep existing");
-    assert!(content.contains("- New item"), "should add new item");
-
-    apply_heartbeat_changes(&[HeartbeatAction::Add("New item".to_string())], None);
-    let content = std::fs::read_to_string(fake_home.join(".omega/prompts/HEARTBEAT.md")).unwrap();
-    assert_eq!(
-        content.matches("New item").count(),
-        1,
-        "should not duplicate"
-    );
-
-    // --- Remove scenario (same HOME, sequential) ---
-    std::fs::write(
-        fake_home.join(".omega/prompts/HEARTBEAT.md"),
-        "# My checklist\n- Check exercise habits\n- Water the plants\n",
-    )
-    .unwrap();
-
-    apply_heartbeat_changes(&[HeartbeatAction::Remove("exercise".to_string())], None);
-
-    let content = std::fs::read_to_string(fake_home.join(".omega/prompts/HEARTBEAT.md")).unwrap();
-    assert!(!content.contains("exercise"), "should remove exercise line");
-    assert!(
-        content.contains("Water the plants"),
-        "should keep other items"
-    );
-    assert!(content.contains("# My checklist"), "should keep comments");
-
-    // --- Section suppression lifecycle (same HOME, sequential) ---
+fn test_heartbeat_suppression_lifecycle() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data_dir = tmp.path().to_str().unwrap();
+    let suppress_path = tmp.path().join("prompts/suppress/u1.suppress");
 
     // Add suppression.
-    add_suppression("TRADING", None);
-    let entries = read_suppress_file(None);
+    add_suppression(data_dir, "u1", "TRADING", None);
+    let entries = read_suppress_file(data_dir, "u1", None);
     assert_eq!(entries, vec!["TRADING"]);
 
     // Duplicate add — no-op (case-insensitive).
-    add_suppression("trading", None);
-    assert_eq!(read_suppress_file(None).len(), 1);
+    add_suppression(data_dir, "u1", "trading", None);
+    assert_eq!(read_suppress_file(data_dir, "u1", None).len(), 1);
 
     // Add another.
-    add_suppression("HEALTH", None);
-    assert_eq!(read_suppress_file(None).len(), 2);
+    add_suppression(data_dir, "u1", "HEALTH", None);
+    assert_eq!(read_suppress_file(data_dir, "u1", None).len(), 2);
 
     // Remove.
-    remove_suppression("TRADING", None);
-    assert_eq!(read_suppress_file(None), vec!["HEALTH"]);
+    remove_suppression(data_dir, "u1", "TRADING", None);
+    assert_eq!(read_suppress_file(data_dir, "u1", None), vec!["HEALTH"]);
 
     // Remove non-existent — no-op.
-    remove_suppression("NONEXISTENT", None);
-    assert_eq!(read_suppress_file(None), vec!["HEALTH"]);
+    remove_suppression(data_dir, "u1", "NONEXISTENT", None);
+    assert_eq!(read_suppress_file(data_dir, "u1", None), vec!["HEALTH"]);
 
     // Clean up for filter tests.
-    remove_suppression("HEALTH", None);
+    remove_suppression(data_dir, "u1", "HEALTH", None);
 
     // --- filter with suppression ---
-    std::fs::write(
-        fake_home.join(".omega/prompts/HEARTBEAT.suppress"),
-        "TRADING\n",
-    )
-    .unwrap();
+    std::fs::write(&suppress_path, "TRADING\n").unwrap();
 
     let hb_content =
         "# Title\n## TRADING — Engine\n400 lines of trading\n## NON-TRADING ITEMS\n- Reminder\n";
-    let result = filter_suppressed_sections(hb_content, None);
+    let result = filter_suppressed_sections(data_dir, "u1", hb_content, None);
     assert!(result.is_some());
     let filtered = result.unwrap();
     assert!(
@@ -224,68 +186,65 @@ fn test_apply_heartbeat_add() {
     assert!(filtered.contains("# Title"), "preamble remains");
 
     // --- all sections suppressed → None ---
-    std::fs::write(
-        fake_home.join(".omega/prompts/HEARTBEAT.suppress"),
-        "TRADING\nNON-TRADING ITEMS\n",
-    )
-    .unwrap();
+    std::fs::write(&suppress_path, "TRADING\nNON-TRADING ITEMS\n").unwrap();
 
     let hb_content = "## TRADING\nStuff\n## NON-TRADING ITEMS\nMore stuff\n";
-    let result = filter_suppressed_sections(hb_content, None);
+    let result = filter_suppressed_sections(data_dir, "u1", hb_content, None);
     assert!(result.is_none(), "all sections suppressed = no checklist");
 
     // --- case-insensitive matching ---
-    std::fs::write(
-        fake_home.join(".omega/prompts/HEARTBEAT.suppress"),
-        "trading\n",
-    )
-    .unwrap();
+    std::fs::write(&suppress_path, "trading\n").unwrap();
 
     let hb_content = "## TRADING — Engine\nStuff\n## OTHER\nKeep\n";
-    let result = filter_suppressed_sections(hb_content, None);
+    let result = filter_suppressed_sections(data_dir, "u1", hb_content, None);
     assert!(result.is_some());
     let filtered = result.unwrap();
     assert!(!filtered.contains("## TRADING"), "case-insensitive filter");
     assert!(!filtered.contains("Stuff"));
     assert!(filtered.contains("OTHER"));
-
-    std::env::set_var("HOME", &original_home);
-    let _ = std::fs::remove_dir_all(&fake_home);
 }
 
 #[test]
-fn test_apply_heartbeat_remove() {
-    // This test verifies remove works standalone, using direct file path
-    // instead of HOME env var to avoid parallel test interference.
-    let dir = std::env::temp_dir().join("omega_test_hb_remove_standalone");
-    let _ = std::fs::remove_dir_all(&dir);
-    std::fs::create_dir_all(&dir).unwrap();
-    let path = dir.join("HEARTBEAT.md");
-    std::fs::write(&path, "# Tasks\n- Monitor BTC\n- Review logs\n").unwrap();
+fn test_heartbeat_files_are_isolated_per_data_dir() {
+    let staging = tempfile::tempdir().unwrap();
+    let prod = tempfile::tempdir().unwrap();
+    let staging_dir = staging.path().to_str().unwrap();
+    let prod_dir = prod.path().to_str().unwrap();
+
+    std::fs::create_dir_all(staging.path().join("projects/garden")).unwrap();
+    std::fs::write(
+        staging.path().join("projects/garden/HEARTBEAT.md"),
+        "- Staging check\n",
+    )
+    .unwrap();
+    add_suppression(staging_dir, "u1", "TRADING", Some("garden"));
 
-    // Verify the remove logic directly on file content (no HOME dependency).
-    let mut lines: Vec<String> = std::fs::read_to_string(&path)
+    assert!(read_project_heartbeat_file(staging_dir, "garden")
         .unwrap()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
-    let needle = "btc".to_lowercase();
-    lines.retain(|l| {
-        let trimmed = l.trim();
-        if trimmed.starts_with('#') {
-            return true;
-        }
-        let content = trimmed.trim_start_matches("- ").to_lowercase();
-        !content.contains(&needle)
-    });
-    std::fs::write(&path, lines.join("\n") + "\n").unwrap();
-
-    let content = std::fs::read_to_string(&path).unwrap();
-    assert!(!content.contains("BTC"), "should remove BTC line");
-    assert!(content.contains("Review logs"), "should keep other items");
-    assert!(content.contains("# Tasks"), "should keep comments");
-
-    let _ = std::fs::remove_dir_all(&dir);
+        .contains("Staging check"));
+    assert!(read_project_heartbeat_file(prod_dir, "garden").is_none());
+    assert_eq!(
+        read_suppress_file(staging_dir, "u1", Some("garden")),
+        vec!["TRADING"]
+    );
+    assert!(read_suppress_file(prod_dir, "u1", Some("garden")).is_empty());
+    assert!(read_suppress_file(staging_dir, "u1", None).is_empty());
+}
+
+#[test]
+fn test_personal_suppression_is_per_sender() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data_dir = tmp.path().to_str().unwrap();
+
+    add_suppression(data_dir, "alice", "TRADING", None);
+
+    assert_eq!(read_suppress_file(data_dir, "alice", None), vec!["TRADING"]);
+    assert!(read_suppress_file(data_dir, "bob", None).is_empty());
+    let content = "## TRADING\nStuff\n## OTHER\nKeep\n";
+    assert_eq!(
+        filter_suppressed_sections(data_dir, "bob", content, None).as_deref(),
+        Some(content)
+    );
 }
 
 // --- Section suppression (REQ-HB-010..014) ---
@@ -332,13 +291,15 @@ fn test_parse_heartbeat_sections_emdash_extraction() {
 #[test]
 fn test_filter_suppressed_sections_no_suppress_file() {
     // No suppress file exists — all sections active.
-    // Uses parse_heartbeat_sections directly to avoid HOME dependency.
+    let tmp = tempfile::tempdir().unwrap();
     let content = "# Title\n## SECTION A\nContent A\n## SECTION B\nContent B\n";
     let (preamble, sections) = parse_heartbeat_sections(content);
     assert!(preamble.contains("# Title"));
     assert_eq!(sections.len(), 2);
-    // With no suppress file, filter_suppressed_sections returns content unchanged.
-    // We test this via the parse path since filter reads HOME.
+    assert_eq!(
+        filter_suppressed_sections(tmp.path().to_str().unwrap(), "u1", content, None).as_deref(),
+        Some(content)
+    );
 }
 
 #[test]
@@ -0,0 +1,229 @@
+use super::super::*;
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc, Weekday};
+use omega_memory::HeartbeatItem;
+
+fn local(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Local> {
+    Local.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+fn db_time(t: DateTime<Local>) -> String {
+    t.with_timezone(&Utc)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+fn item(cadence: &str, created: DateTime<Local>, last: Option<DateTime<Local>>) -> HeartbeatItem {
+    HeartbeatItem {
+        id: "a1b2c3d4-0000-0000-0000-000000000000".to_string(),
+        sender_id: "alice".to_string(),
+        project: String::new(),
+        description: "Check inbox".to_string(),
+        cadence: cadence.to_string(),
+        alert_on_change: false,
+        last_checked_at: last.map(db_time),
+        last_result: None,
+        created_at: db_time(created),
+    }
+}
+
+fn hm(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+// --- Cadence parsing ---
+
+#[test]
+fn test_cadence_parse_variants() {
+    assert_eq!(Cadence::parse(""), Some(Cadence::EveryPulse));
+    assert_eq!(Cadence::parse("every pulse"), Some(Cadence::EveryPulse));
+    assert_eq!(Cadence::parse("Hourly"), Some(Cadence::Hourly));
+    assert_eq!(Cadence::parse("every hour"), Some(Cadence::Hourly));
+    assert_eq!(Cadence::parse("daily"), Some(Cadence::Daily(hm(9, 0))));
+    assert_eq!(
+        Cadence::parse("daily at 7:30"),
+        Some(Cadence::Daily(hm(7, 30)))
+    );
+    assert_eq!(
+        Cadence::parse("weekly"),
+        Some(Cadence::Weekly(Weekday::Mon, hm(9, 0)))
+    );
+    assert_eq!(
+        Cadence::parse("weekly on friday at 18:00"),
+        Some(Cadence::Weekly(Weekday::Fri, hm(18, 0)))
+    );
+    assert_eq!(Cadence::parse("daily at noon"), None);
+    assert_eq!(Cadence::parse("monthly"), None);
+    assert_eq!(Cadence::parse("weekly someday"), None);
+}
+
+#[test]
+fn test_cadence_stored_roundtrip() {
+    for text in ["", "hourly", "daily 07:30", "weekly fri 18:00"] {
+        assert_eq!(Cadence::from_stored(text).to_stored(), text);
+    }
+    assert_eq!(Cadence::from_stored("garbage"), Cadence::EveryPulse);
+    assert_eq!(Cadence::EveryPulse.label(), "every pulse");
+}
+
+// --- Due checks ---
+
+#[test]
+fn test_every_pulse_always_due() {
+    let now = local(2026, 3, 4, 10, 30);
+    assert!(is_item_due(&item("", now, Some(now)), now));
+}
+
+#[test]
+fn test_hourly_due_after_an_hour() {
+    let now = local(2026, 3, 4, 10, 30);
+    let created = local(2026, 3, 1, 8, 0);
+    assert!(is_item_due(&item("hourly", created, None), now));
+    assert!(!is_item_due(
+        &item("hourly", created, Some(local(2026, 3, 4, 10, 0))),
+        now
+    ));
+    assert!(is_item_due(
+        &item("hourly", created, Some(local(2026, 3, 4, 9, 30))),
+        now
+    ));
+}
+
+#[test]
+fn test_daily_runs_once_per_occurrence() {
+    let created = local(2026, 3, 1, 8, 0);
+    let nine = local(2026, 3, 4, 9, 0);
+    // Not checked since yesterday's run → due after 09:00 today.
+    let yesterday = Some(local(2026, 3, 3, 9, 0));
+    assert!(is_item_due(
+        &item("daily 09:00", created, yesterday),
+        local(2026, 3, 4, 9, 30)
+    ));
+    // Before 09:00 today, yesterday's occurrence was already handled.
+    assert!(!is_item_due(
+        &item("daily 09:00", created, yesterday),
+        local(2026, 3, 4, 8, 30)
+    ));
+    // Already checked after today's occurrence.
+    assert!(!is_item_due(
+        &item("daily 09:00", created, Some(nine)),
+        local(2026, 3, 4, 15, 0)
+    ));
+}
+
+#[test]
+fn test_daily_added_after_occurrence_waits_until_tomorrow() {
+    let created = local(2026, 3, 4, 15, 0);
+    assert!(!is_item_due(
+        &item("daily 09:00", created, None),
+        local(2026, 3, 4, 15, 30)
+    ));
+    assert!(is_item_due(
+        &item("daily 09:00", created, None),
+        local(2026, 3, 5, 9, 0)
+    ));
+}
+
+#[test]
+fn test_weekly_due_on_its_day() {
+    // 2026-03-04 is a Wednesday.
+    let created = local(2026, 2, 1, 8, 0);
+    let last_friday = Some(local(2026, 2, 27, 18, 0));
+    assert!(!is_item_due(
+        &item("weekly fri 18:00", created, last_friday),
+        local(2026, 3, 4, 10, 0)
+    ));
+    assert!(is_item_due(
+        &item("weekly fri 18:00", created, last_friday),
+        local(2026, 3, 6, 18, 0)
+    ));
+    // A missed week still runs at the next pulse.
+    assert!(is_item_due(
+        &item("weekly wed 08:00", created, last_friday),
+        local(2026, 3, 4, 10, 0)
+    ));
+}
+
+// --- Specs, rendering, conversion ---
+
+#[test]
+fn test_parse_item_spec() {
+    let plain = parse_item_spec("Check inbox");
+    assert_eq!(plain.description, "Check inbox");
+    assert_eq!(plain.cadence, "");
+    assert!(!plain.alert_on_change);
+
+    let full = parse_item_spec("Disk usage | daily at 8:00 | on change");
+    assert_eq!(full.description, "Disk usage");
+    assert_eq!(full.cadence, "daily 08:00");
+    assert!(full.alert_on_change);
+
+    let flag_only = parse_item_spec("BTC price | on_change");
+    assert_eq!(flag_only.cadence, "");
+    assert!(flag_only.alert_on_change);
+
+    let unknown = parse_item_spec("Backups | fortnightly");
+    assert_eq!(unknown.description, "Backups");
+    assert_eq!(unknown.cadence, "");
+}
+
+#[test]
+fn test_render_heartbeat_item() {
+    let now = local(2026, 3, 4, 10, 30);
+    let mut it = item("hourly", now, None);
+    it.alert_on_change = true;
+    it.last_result = Some("3 unread".to_string());
+    assert_eq!(
+        render_heartbeat_item(&it),
+        "- [a1b2c3d4] Check inbox (hourly; alert only on change; last result: 3 unread)"
+    );
+
+    it.description = "## TRADING — Engine\nCheck positions".to_string();
+    it.alert_on_change = false;
+    it.last_result = None;
+    it.cadence = String::new();
+    assert_eq!(
+        render_heartbeat_item(&it),
+        "- [a1b2c3d4] TRADING — Engine (every pulse)\nCheck positions"
+    );
+}
+
+#[test]
+fn test_checklist_to_item_descriptions() {
+    let legacy = "# My checklist\n- Check inbox\n* Water plants\n\n## TRADING — Engine\nCheck positions\n- Review PnL\n";
+    assert_eq!(
+        checklist_to_item_descriptions(legacy),
+        vec![
+            "Check inbox".to_string(),
+            "Water plants".to_string(),
+            "## TRADING — Engine\nCheck positions\n- Review PnL".to_string(),
+        ]
+    );
+    assert!(checklist_to_item_descriptions("# Only a title\n").is_empty());
+}
+
+#[test]
+fn test_item_section_name() {
+    assert_eq!(
+        item_section_name("## TRADING — Engine\nStuff").as_deref(),
+        Some("TRADING")
+    );
+    assert_eq!(item_section_name("Check inbox"), None);
+}
+
+// --- HEARTBEAT_CHECKED markers ---
+
+#[test]
+fn test_extract_heartbeat_checked_markers() {
+    let text = "All good.\nHEARTBEAT_CHECKED: a1b2c3d4 | 3 unread\nHEARTBEAT_CHECKED: [ffff0000] | ok\nHEARTBEAT_CHECKED: missing-result\nHEARTBEAT_OK";
+    assert_eq!(
+        extract_heartbeat_checked_markers(text),
+        vec![
+            ("a1b2c3d4".to_string(), "3 unread".to_string()),
+            ("ffff0000".to_string(), "ok".to_string()),
+        ]
+    );
+    assert_eq!(
+        strip_heartbeat_checked_markers(text),
+        "All good.\nHEARTBEAT_OK"
+    );
+}
@@ -267,46 +267,99 @@ fn test_inbox_guard_cleans_up_on_drop() {
     let _ = std::fs::remove_dir_all(&tmp);
 }
 
+// --- Media ---
+
 #[test]
-fn test_inbox_guard_empty_is_noop() {
-    // An empty guard should not panic or error on drop.
-    let _guard = InboxGuard::new(Vec::new());
-}
+fn test_save_attachments_to_media() {
+    use omega_core::context::ContentBlock;
+    use omega_core::message::{Attachment, AttachmentType};
 
-// --- Classification ---
+    let tmp = std::env::temp_dir().join("omega_test_save_media");
+    let _ = std::fs::remove_dir_all(&tmp);
 
-#[test]
-fn test_parse_plan_response_direct() {
-    assert!(parse_plan_response("DIRECT").is_none());
-    assert!(parse_plan_response("  DIRECT  ").is_none());
-    assert!(parse_plan_response("direct").is_none());
-}
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+    let attachments = vec![
+        Attachment {
+            file_type: AttachmentType::Image,
+            url: None,
+            data: Some(png.clone()),
+            filename: Some("photo.jpg".to_string()),
+        },
+        Attachment {
+            file_type: AttachmentType::Document,
+            url: None,
+            data: Some(b"%PDF-1.4 body".to_vec()),
+            filename: Some("invoice.pdf".to_string()),
+        },
+        Attachment {
+            file_type: AttachmentType::Image,
+            url: None,
+            data: Some(b"not an image".to_vec()),
+            filename: Some("bogus.jpg".to_string()),
+        },
+    ];
 
-#[test]
-fn test_parse_plan_response_numbered_list() {
-    let text = "1. Set up the database schema\n\
-                2. Create the API endpoint\n\
-                3. Write integration tests";
-    let steps = parse_plan_response(text).unwrap();
-    assert_eq!(steps.len(), 3);
-    assert_eq!(steps[0], "Set up the database schema");
-    assert_eq!(steps[1], "Create the API endpoint");
-    assert_eq!(steps[2], "Write integration tests");
+    let saved = save_attachments_to_media(&tmp, &attachments);
+    assert_eq!(saved.len(), 2, "unsupported formats must be skipped");
+    assert_eq!(saved[0].0.extension().unwrap(), "png");
+    assert_eq!(std::fs::read(&saved[0].0).unwrap(), png);
+    assert!(
+        matches!(saved[0].1, ContentBlock::Image { ref media_type, .. } if media_type == "image/png")
+    );
+    assert!(matches!(saved[1].1, ContentBlock::Document { ref name, .. } if name == "invoice.pdf"));
+
+    let _ = std::fs::remove_dir_all(&tmp);
 }
 
 #[test]
-fn test_parse_plan_response_single_step() {
-    let text = "1. Just do the thing";
-    assert!(parse_plan_response(text).is_none());
+fn test_attach_history_media() {
+    use omega_core::context::ContextEntry;
+
+    let tmp = std::env::temp_dir().join("omega_test_history_media");
+    let _ = std::fs::remove_dir_all(&tmp);
+    let media = tmp.join("media");
+    std::fs::create_dir_all(&media).unwrap();
+    let file = media.join("a.png");
+    std::fs::write(&file, b"\x89PNG\r\n\x1a\n").unwrap();
+    let outside = tmp.join("secret.png");
+    std::fs::write(&outside, b"\x89PNG\r\n\x1a\n").unwrap();
+
+    let mut history = vec![
+        ContextEntry {
+            role: "user".into(),
+            content: format!("[Attached image: {}]\nWhat is this?", file.display()),
+            media: Vec::new(),
+        },
+        ContextEntry {
+            role: "assistant".into(),
+            content: "A cat.".into(),
+            media: Vec::new(),
+        },
+        ContextEntry {
+            role: "user".into(),
+            content: "[Attached image: gone.png]\nAnd this?".into(),
+            media: Vec::new(),
+        },
+        ContextEntry {
+            role: "user".into(),
+            content: format!(
+                "[Attached image: a.png]\n[Attached image: {}]\nAnd these?",
+                outside.display()
+            ),
+            media: Vec::new(),
+        },
+    ];
+    attach_history_media(&mut history, &media);
+    assert_eq!(history[0].media.len(), 1, "legacy absolute path inside");
+    assert!(history[1].media.is_empty());
+    assert!(history[2].media.is_empty(), "missing files are skipped");
+    assert_eq!(history[3].media.len(), 1, "files outside are never loaded");
+
+    let _ = std::fs::remove_dir_all(&tmp);
 }
 
 #[test]
-fn test_parse_plan_response_with_preamble() {
-    let text = "Here are the steps:\n\
-                1. First step\n\
-                2. Second step\n\
-                3. Third step";
-    let steps = parse_plan_response(text).unwrap();
-    assert_eq!(steps.len(), 3);
-    assert_eq!(steps[0], "First step");
+fn test_inbox_guard_empty_is_noop() {
+    // An empty guard should not panic or error on drop.
+    let _guard = InboxGuard::new(Vec::new());
 }
@@ -1,6 +1,8 @@
 mod actions;
 mod heartbeat;
+mod heartbeat_items;
 mod helpers;
 mod mod_tests;
 mod protocol;
 mod schedule;
+mod tool_calls;
@@ -12,6 +12,22 @@ fn test_strip_all_remaining_markers() {
     assert!(result.contains("More text."));
 }
 
+#[test]
+fn test_strip_action_markers() {
+    let text = "Here is the page summary.\nSCHEDULE_ACTION: Delete files | 2026-03-01T09:00:00 | once\nLANG_SWITCH: es\nPURGE_FACTS";
+    let (result, dropped) = strip_action_markers(text);
+    assert_eq!(dropped, vec!["SCHEDULE_ACTION:", "PURGE_FACTS"]);
+    assert!(!result.contains("SCHEDULE_ACTION:"));
+    assert!(!result.contains("PURGE_FACTS"));
+    // Harmless markers are left for process_markers().
+    assert!(result.contains("LANG_SWITCH: es"));
+    assert!(result.contains("Here is the page summary."));
+
+    let (clean, dropped) = strip_action_markers("Just an answer.");
+    assert_eq!(clean, "Just an answer.");
+    assert!(dropped.is_empty());
+}
+
 #[test]
 fn test_strip_all_remaining_markers_includes_bug_report() {
     let text = "Hello. BUG_REPORT: some limitation\nMore text.";
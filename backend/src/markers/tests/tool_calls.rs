@@ -0,0 +1,112 @@
+use super::super::*;
+use omega_core::message::ActionCall;
+use serde_json::json;
+
+fn call(name: &str, args: serde_json::Value) -> ActionCall {
+    ActionCall {
+        name: name.to_string(),
+        args,
+    }
+}
+
+#[test]
+fn test_action_call_marker_round_trips_through_parsers() {
+    let line = action_call_marker(&call(
+        "omega_schedule",
+        json!({"description": "Call mom", "due_at": "2026-03-01T09:00:00", "repeat": "daily"}),
+    ))
+    .unwrap();
+    assert_eq!(
+        parse_schedule_line(&line),
+        Some((
+            "Call mom".to_string(),
+            "2026-03-01T09:00:00".to_string(),
+            "daily".to_string()
+        ))
+    );
+
+    let line = action_call_marker(&call(
+        "omega_schedule_action",
+        json!({"description": "Check BTC", "due_at": "2026-03-01T09:00:00"}),
+    ))
+    .unwrap();
+    assert_eq!(parse_schedule_action_line(&line).unwrap().2, "once");
+
+    let line = action_call_marker(&call(
+        "omega_update_task",
+        json!({"task_id": "abc123", "due_at": "2026-03-02T10:00:00"}),
+    ))
+    .unwrap();
+    assert_eq!(
+        parse_update_task_line(&line),
+        Some((
+            "abc123".to_string(),
+            None,
+            Some("2026-03-02T10:00:00".to_string()),
+            None
+        ))
+    );
+
+    let line = action_call_marker(&call(
+        "omega_reward",
+        json!({"score": -1, "domain": "tone", "lesson": "Too long"}),
+    ))
+    .unwrap();
+    assert_eq!(
+        parse_reward_line(&line),
+        Some((-1, "tone".to_string(), "Too long".to_string()))
+    );
+
+    let line = action_call_marker(&call(
+        "omega_heartbeat_add",
+        json!({"description": "Check inbox", "cadence": "hourly", "alert_on_change": true}),
+    ))
+    .unwrap();
+    assert_eq!(
+        extract_heartbeat_markers(&line),
+        vec![HeartbeatAction::Add(
+            "Check inbox | hourly | on_change".to_string()
+        )]
+    );
+
+    let line = action_call_marker(&call("omega_heartbeat_interval", json!({"minutes": 30})));
+    assert_eq!(
+        extract_heartbeat_markers(&line.unwrap()),
+        vec![HeartbeatAction::SetInterval(30)]
+    );
+    assert_eq!(
+        action_call_marker(&call("omega_purge_facts", json!({}))).as_deref(),
+        Some("PURGE_FACTS")
+    );
+    assert!(action_call_marker(&call("omega_unknown", json!({}))).is_none());
+    assert!(action_call_marker(&call("bash", json!({}))).is_none());
+}
+
+#[test]
+fn test_action_call_marker_keeps_fields_on_one_line() {
+    let line = action_call_marker(&call(
+        "omega_lesson",
+        json!({"domain": "food", "rule": "No dairy |\nnot even cheese"}),
+    ))
+    .unwrap();
+    assert_eq!(line, "LESSON: food|No dairy / not even cheese");
+    assert_eq!(
+        parse_lesson_line(&line),
+        Some(("food".to_string(), "No dairy / not even cheese".to_string()))
+    );
+}
+
+#[test]
+fn test_apply_action_calls_ignores_text_markers() {
+    let text = "You wrote \"SCHEDULE: x | y | once\" earlier.\nDone. CANCEL_TASK: abc";
+    let calls = vec![call("omega_lang_switch", json!({"language": "French"}))];
+    let result = apply_action_calls(text, &calls);
+    assert!(extract_all_schedule_markers(&result).is_empty());
+    assert!(extract_all_cancel_tasks(&result).is_empty());
+    assert_eq!(extract_lang_switch(&result).as_deref(), Some("French"));
+    assert!(result.starts_with("You wrote"));
+
+    // No calls: markers are still dropped, nothing is acted on.
+    let result = apply_action_calls("Sure.\nPURGE_FACTS", &[]);
+    assert_eq!(result, "Sure.");
+}
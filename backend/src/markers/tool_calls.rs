@@ -0,0 +1,104 @@
+//! Native action tool calls (`omega_*`) rendered as canonical marker lines.
+//!
+//! API providers with function calling return the model's action calls on
+//! `OutgoingMessage::actions`. Turning each call into the line its text marker
+//! would have been lets `process_markers()` handle both the same way.
+
+use super::strip_all_remaining_markers;
+use omega_core::message::ActionCall;
+use serde_json::Value;
+use tracing::warn;
+
+/// Replace the reply's text markers with its native action calls.
+///
+/// Markers written in the prose are not acted on: they may sit mid-sentence
+/// or inside quoted user content. They are stripped, and each call is appended
+/// as a line-start marker.
+pub fn apply_action_calls(text: &str, calls: &[ActionCall]) -> String {
+    let mut result = strip_all_remaining_markers(text);
+    for line in calls.iter().filter_map(action_call_marker) {
+        result.push('\n');
+        result.push_str(&line);
+    }
+    result
+}
+
+/// The marker line equivalent to an action call, or `None` for an unknown tool.
+pub fn action_call_marker(call: &ActionCall) -> Option<String> {
+    let arg = |key: &str| field(call.args.get(key));
+    let line = match call.name.strip_prefix("omega_")? {
+        kind @ ("schedule" | "schedule_action") => {
+            let repeat = arg("repeat");
+            format!(
+                "{}: {} | {} | {}",
+                kind.to_uppercase(),
+                arg("description"),
+                arg("due_at"),
+                if repeat.is_empty() { "once" } else { &repeat }
+            )
+        }
+        "cancel_task" => format!("CANCEL_TASK: {}", arg("task_id")),
+        "update_task" => format!(
+            "UPDATE_TASK: {} | {} | {} | {}",
+            arg("task_id"),
+            arg("description"),
+            arg("due_at"),
+            arg("repeat")
+        ),
+        "heartbeat_add" => {
+            let mut spec = arg("description");
+            let cadence = arg("cadence");
+            if !cadence.is_empty() {
+                spec.push_str(&format!(" | {cadence}"));
+            }
+            if call.args.get("alert_on_change").and_then(Value::as_bool) == Some(true) {
+                spec.push_str(" | on_change");
+            }
+            format!("HEARTBEAT_ADD: {spec}")
+        }
+        "heartbeat_remove" => format!("HEARTBEAT_REMOVE: {}", arg("item_id")),
+        "heartbeat_interval" => format!("HEARTBEAT_INTERVAL: {}", arg("minutes")),
+        "heartbeat_suppress_section" => {
+            format!("HEARTBEAT_SUPPRESS_SECTION: {}", arg("section"))
+        }
+        "heartbeat_unsuppress_section" => {
+            format!("HEARTBEAT_UNSUPPRESS_SECTION: {}", arg("section"))
+        }
+        "reward" => format!(
+            "REWARD: {}|{}|{}",
+            arg("score"),
+            arg("domain"),
+            arg("lesson")
+        ),
+        "lesson" => format!("LESSON: {}|{}", arg("domain"), arg("rule")),
+        "lang_switch" => format!("LANG_SWITCH: {}", arg("language")),
+        "personality" => format!("PERSONALITY: {}", arg("style")),
+        "project_activate" => format!("PROJECT_ACTIVATE: {}", arg("name")),
+        "project_deactivate" => "PROJECT_DEACTIVATE".to_string(),
+        "build_proposal" => format!("BUILD_PROPOSAL: {}", arg("description")),
+        "skill_improve" => format!("SKILL_IMPROVE: {} | {}", arg("skill"), arg("lesson")),
+        "bug_report" => format!("BUG_REPORT: {}", arg("description")),
+        "forget_conversation" => "FORGET_CONVERSATION".to_string(),
+        "purge_facts" => "PURGE_FACTS".to_string(),
+        "whatsapp_setup" => "WHATSAPP_QR".to_string(),
+        "google_setup" => "GOOGLE_SETUP".to_string(),
+        other => {
+            warn!("ignoring unknown action tool call: omega_{other}");
+            return None;
+        }
+    };
+    Some(line)
+}
+
+/// One marker field: a single line, with `|` reserved as the separator.
+fn field(value: Option<&Value>) -> String {
+    let raw = match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    raw.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "/")
+}
@@ -0,0 +1,113 @@
+//! `omega export` / `omega import` — move one user's memory in and out.
+
+use omega_core::config::{self, shellexpand};
+use omega_core::crypto;
+use omega_memory::{ImportReport, MemoryExport, Store};
+use std::path::{Path, PathBuf};
+
+/// Default archive name: `omega-export-<sender>-<YYYYMMDD-HHMMSS>.json`.
+pub fn export_file_name(sender_id: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    let safe: String = sender_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("omega-export-{safe}-{}.json", now.format("%Y%m%d-%H%M%S"))
+}
+
+/// One-line summary of what an import added.
+pub fn import_summary(report: &ImportReport) -> String {
+    format!(
+        "{} facts, {} conversations ({} messages), {} tasks, {} outcomes, {} lessons, \
+         {} sessions, {} heartbeat items, {} aliases; {} already present",
+        report.facts,
+        report.conversations,
+        report.messages,
+        report.tasks,
+        report.outcomes,
+        report.lessons,
+        report.sessions,
+        report.heartbeat_items,
+        report.aliases,
+        report.skipped
+    )
+}
+
+async fn open_store(config_path: &str) -> anyhow::Result<Store> {
+    let cp = shellexpand(config_path);
+    let cfg = tokio::task::spawn_blocking(move || config::load(&cp))
+        .await
+        .map_err(|e| anyhow::anyhow!("config load task panicked: {e}"))??;
+    Ok(Store::new(&cfg.memory)
+        .await?
+        .with_cipher(crypto::cipher_for(&cfg)?))
+}
+
+/// Write everything stored for `sender` (or the id it is aliased to) to a JSON archive.
+pub async fn export(
+    config_path: &str,
+    sender: &str,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega export").bold().to_string())?;
+    let store = open_store(config_path).await?;
+    let canonical = store.resolve_sender_id(sender).await?;
+    if canonical != sender {
+        cliclack::log::info(format!("{sender} is an alias of {canonical}"))?;
+    }
+
+    let export = store.export_sender(&canonical).await?;
+    if export.facts.is_empty() && export.conversations.is_empty() && export.tasks.is_empty() {
+        cliclack::log::warning(format!("No memory stored for {canonical}"))?;
+    }
+    let path =
+        output.unwrap_or_else(|| PathBuf::from(export_file_name(&canonical, chrono::Utc::now())));
+    std::fs::write(&path, serde_json::to_vec_pretty(&export)?)?;
+
+    let messages: usize = export.conversations.iter().map(|c| c.messages.len()).sum();
+    cliclack::log::success(format!(
+        "{} facts, {} conversations ({messages} messages), {} tasks, {} lessons",
+        export.facts.len(),
+        export.conversations.len(),
+        export.tasks.len(),
+        export.lessons.len()
+    ))?;
+    cliclack::outro(format!("Wrote {}", path.display()))?;
+    Ok(())
+}
+
+/// Load an archive written by [`export`] into this installation's memory.
+pub async fn import(config_path: &str, file: &Path, sender: Option<String>) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega import").bold().to_string())?;
+    let raw =
+        std::fs::read(file).map_err(|e| anyhow::anyhow!("cannot read {}: {e}", file.display()))?;
+    let export: MemoryExport = serde_json::from_slice(&raw)
+        .map_err(|e| anyhow::anyhow!("{} is not an Omega export: {e}", file.display()))?;
+
+    let store = open_store(config_path).await?;
+    let report = store.import_sender(&export, sender.as_deref()).await?;
+    let target = sender.as_deref().unwrap_or(&export.sender_id);
+    cliclack::log::success(import_summary(&report))?;
+    cliclack::outro(format!("Imported {} into {target}", file.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_export_file_name_is_path_safe() {
+        let now = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 9, 5, 0).unwrap();
+        assert_eq!(
+            export_file_name("+34 600/123", now),
+            "omega-export-_34_600_123-20260301-090500.json"
+        );
+    }
+}
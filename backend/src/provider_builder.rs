@@ -1,14 +1,15 @@
 //! Provider factory — builds the configured AI provider from config.
 
-use omega_core::{config, traits::Provider};
+use omega_core::{config, crypto::SecretStore, traits::Provider};
 use omega_providers::{
     anthropic::AnthropicProvider, claude_code::ClaudeCodeProvider, gemini::GeminiProvider,
     ollama::OllamaProvider, openai::OpenAiProvider, openrouter::OpenRouterProvider,
 };
+use std::sync::Arc;
 
 /// Build the configured provider, returning `(provider, model_fast, model_complex)`.
 ///
-/// For Claude Code, `model_fast` and `model_complex` come from its config.
+/// For Claude Code and Anthropic, `model_fast` and `model_complex` come from their config.
 /// For all other providers, both are set to the provider's single `model` field.
 pub fn build_provider(
     cfg: &config::Config,
@@ -18,6 +19,16 @@ pub fn build_provider(
 
     match cfg.provider.default.as_str() {
         "claude-code" => {
+            // The CLI runs the whole agentic loop itself, so a call can't be
+            // paused for approval; static deny rules would only pretend to gate.
+            if cfg.approval.enabled {
+                anyhow::bail!(
+                    "[approval] is not supported with the claude-code provider: \
+                     it cannot pause tool calls for a decision. Disable [approval] \
+                     or switch to an HTTP provider (anthropic, openai, openrouter, \
+                     gemini, ollama)."
+                );
+            }
             let cc = cfg
                 .provider
                 .claude_code
@@ -27,15 +38,18 @@ pub fn build_provider(
             let model_fast = cc.model.clone();
             let model_complex = cc.model_complex.clone();
             Ok((
-                Box::new(ClaudeCodeProvider::from_config(
-                    cc.max_turns,
-                    cc.allowed_tools,
-                    cc.timeout_secs,
-                    ws,
-                    cc.max_resume_attempts,
-                    cc.model,
-                    cc.oauth_token,
-                )),
+                Box::new(
+                    ClaudeCodeProvider::from_config(
+                        cc.max_turns,
+                        cc.allowed_tools,
+                        cc.timeout_secs,
+                        ws,
+                        cc.max_resume_attempts,
+                        cc.model,
+                        cc.oauth_token,
+                    )
+                    .with_secrets(SecretStore::for_config(cfg)?.map(Arc::new)),
+                ),
                 model_fast,
                 model_complex,
             ))
@@ -80,16 +94,20 @@ pub fn build_provider(
                 cfg.provider.anthropic.as_ref().ok_or_else(|| {
                     anyhow::anyhow!("provider.anthropic section missing in config")
                 })?;
-            let m = ac.model.clone();
+            let model_complex = ac.model_complex().to_string();
             Ok((
-                Box::new(AnthropicProvider::from_config(
-                    ac.api_key.clone(),
-                    ac.model.clone(),
-                    ac.max_tokens,
-                    ws,
-                )?),
-                m.clone(),
-                m,
+                Box::new(
+                    AnthropicProvider::from_config(
+                        ac.api_key.clone(),
+                        ac.model.clone(),
+                        ac.max_tokens,
+                        ws,
+                    )?
+                    .with_prompt_caching(ac.prompt_caching)
+                    .with_thinking(model_complex.clone(), ac.thinking_budget),
+                ),
+                ac.model.clone(),
+                model_complex,
             ))
         }
         "openrouter" => {
@@ -149,6 +167,11 @@ mod tests {
             heartbeat: HeartbeatConfig::default(),
             scheduler: SchedulerConfig::default(),
             api: ApiConfig::default(),
+            approval: ApprovalConfig::default(),
+            tenants: TenantsConfig::default(),
+            roles: RolesConfig::default(),
+            encryption: EncryptionConfig::default(),
+            trust: TrustConfig::default(),
         }
     }
 
@@ -181,6 +204,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_claude_code_rejects_approval() {
+        let mut cfg = test_config("claude-code");
+        cfg.approval.enabled = true;
+        let err = build_provider(&cfg, &PathBuf::from("/tmp"))
+            .err()
+            .expect("approval should be rejected on claude-code");
+        assert!(err.to_string().contains("[approval] is not supported"));
+    }
+
     #[test]
     fn test_claude_code_custom_models() {
         let mut cfg = test_config("claude-code");
@@ -290,11 +323,20 @@ mod tests {
             api_key: "test-key".to_string(),
             model: "claude-sonnet-4-20250514".to_string(),
             max_tokens: 4096,
+            model_complex: String::new(),
+            prompt_caching: true,
+            thinking_budget: 0,
         });
         let ws = PathBuf::from("/tmp");
         let (provider, model_fast, model_complex) = build_provider(&cfg, &ws).unwrap();
         assert_eq!(provider.name(), "anthropic");
         assert_eq!(model_fast, "claude-sonnet-4-20250514");
         assert_eq!(model_complex, "claude-sonnet-4-20250514");
+
+        cfg.provider.anthropic.as_mut().unwrap().model_complex =
+            "claude-opus-4-20250514".to_string();
+        let (_, model_fast, model_complex) = build_provider(&cfg, &ws).unwrap();
+        assert_eq!(model_fast, "claude-sonnet-4-20250514");
+        assert_eq!(model_complex, "claude-opus-4-20250514");
     }
 }
@@ -0,0 +1,164 @@
+//! `omega secrets` — master key setup and rotation, and the encrypted secrets store.
+//!
+//! `init` creates the master key, encrypts existing memory and moves
+//! `stores/google.json` into the secrets store. `rotate` re-encrypts memory
+//! and secrets under a fresh key. Stop the service before either: a running
+//! gateway keeps writing with the key it started with.
+
+use omega_core::config::{self, shellexpand, Config, DataDir};
+use omega_core::crypto::{self, Cipher, SecretStore, GOOGLE_SECRET, KEY_ENV};
+use omega_memory::Store;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+async fn load_config(config_path: &str) -> anyhow::Result<Config> {
+    let cp = shellexpand(config_path);
+    Ok(tokio::task::spawn_blocking(move || config::load(&cp))
+        .await
+        .map_err(|e| anyhow::anyhow!("config load task panicked: {e}"))??)
+}
+
+/// `{data_dir}/master.key.pending` — the new key while a rotation runs.
+fn pending_key_path(cfg: &Config) -> PathBuf {
+    DataDir::new(&cfg.omega.data_dir)
+        .root()
+        .join("master.key.pending")
+}
+
+/// Store `key` in the configured source; `env` keys are shown instead.
+fn save_or_show_key(cfg: &Config, key: &str) -> anyhow::Result<()> {
+    if cfg.encryption.key_source == "env" {
+        cliclack::note(
+            format!("Set {KEY_ENV} for the service"),
+            format!("{KEY_ENV}={key}"),
+        )?;
+        return Ok(());
+    }
+    crypto::save_key(&cfg.encryption, &cfg.omega.data_dir, key)?;
+    cliclack::log::success(format!("Master key saved ({})", cfg.encryption.key_source))?;
+    Ok(())
+}
+
+/// Move a plaintext `stores/google.json` into the secrets store.
+fn migrate_google_json(cfg: &Config, secrets: &SecretStore) -> anyhow::Result<bool> {
+    let path = DataDir::new(&cfg.omega.data_dir)
+        .root()
+        .join("stores")
+        .join("google.json");
+    let Ok(json) = std::fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+    secrets.set(GOOGLE_SECRET, &json)?;
+    std::fs::remove_file(&path)?;
+    Ok(true)
+}
+
+/// `omega secrets init` — create the master key and encrypt what is stored.
+pub async fn cmd_init(config_path: &str) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega secrets init").bold().to_string())?;
+    let cfg = load_config(config_path).await?;
+    if crypto::load_key(&cfg.encryption, &cfg.omega.data_dir).is_ok() {
+        anyhow::bail!("a master key already exists; use `omega secrets rotate` to replace it");
+    }
+
+    let key = crypto::generate_key()?;
+    let cipher = Cipher::new(&key)?;
+    save_or_show_key(&cfg, &key)?;
+
+    let store = Store::new(&cfg.memory).await?;
+    let rows = store.rekey(Some(&cipher)).await?;
+    cliclack::log::success(format!("Encrypted {rows} stored values"))?;
+
+    let secrets = SecretStore::new(
+        DataDir::new(&cfg.omega.data_dir).secrets(),
+        Arc::new(cipher),
+    );
+    if migrate_google_json(&cfg, &secrets)? {
+        cliclack::log::success("Moved stores/google.json into the secrets store")?;
+    }
+    if !cfg.encryption.enabled {
+        cliclack::log::warning("Set `enabled = true` under [encryption] in config.toml")?;
+    }
+    cliclack::log::info(
+        "Existing snapshots in the backup dir are still plaintext; delete them once a new one is taken",
+    )?;
+    cliclack::outro("Encryption at rest is set up")?;
+    Ok(())
+}
+
+/// `omega secrets rotate` — re-encrypt memory and secrets under a new key.
+///
+/// The new key is written to `master.key.pending` first, so an interrupted
+/// rotation never leaves data encrypted under a key that was not saved.
+pub async fn cmd_rotate(config_path: &str) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega secrets rotate").bold().to_string())?;
+    let cfg = load_config(config_path).await?;
+    let old = crypto::cipher_for(&cfg)?
+        .ok_or_else(|| anyhow::anyhow!("encryption is not enabled in config.toml"))?;
+    let old_secrets = SecretStore::new(DataDir::new(&cfg.omega.data_dir).secrets(), old.clone());
+    let secrets = old_secrets.load()?;
+
+    let key = crypto::generate_key()?;
+    let pending = pending_key_path(&cfg);
+    crypto::write_private(&pending, key.as_bytes())?;
+    let new = Arc::new(Cipher::new(&key)?);
+
+    let store = Store::new(&cfg.memory).await?.with_cipher(Some(old));
+    let rows = store.rekey(Some(&new)).await?;
+    SecretStore::new(old_secrets.path().to_path_buf(), new).save(&secrets)?;
+    save_or_show_key(&cfg, &key)?;
+    std::fs::remove_file(&pending)?;
+
+    cliclack::log::success(format!(
+        "Re-encrypted {rows} stored values and {} secrets",
+        secrets.len()
+    ))?;
+    cliclack::outro("Master key rotated")?;
+    Ok(())
+}
+
+async fn open_secrets(config_path: &str) -> anyhow::Result<SecretStore> {
+    let cfg = load_config(config_path).await?;
+    SecretStore::for_config(&cfg)?
+        .ok_or_else(|| anyhow::anyhow!("encryption is not enabled in config.toml"))
+}
+
+/// `omega secrets set` — store a secret from a file or a hidden prompt.
+pub async fn cmd_set(config_path: &str, name: &str, file: Option<&Path>) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega secrets set").bold().to_string())?;
+    let secrets = open_secrets(config_path).await?;
+    let value = match file {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?,
+        None => cliclack::password(format!("Value for {name}")).interact()?,
+    };
+    secrets.set(name, value.trim_end())?;
+    cliclack::outro(format!("Stored {name}; reference it as \"secret:{name}\""))?;
+    Ok(())
+}
+
+/// `omega secrets list` — names only, never values.
+pub async fn cmd_list(config_path: &str) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega secrets list").bold().to_string())?;
+    let names: Vec<String> = open_secrets(config_path)
+        .await?
+        .load()?
+        .into_keys()
+        .collect();
+    if names.is_empty() {
+        cliclack::outro("No secrets stored")?;
+    } else {
+        cliclack::outro(names.join("\n"))?;
+    }
+    Ok(())
+}
+
+/// `omega secrets remove` — delete a secret.
+pub async fn cmd_remove(config_path: &str, name: &str) -> anyhow::Result<()> {
+    cliclack::intro(console::style("omega secrets remove").bold().to_string())?;
+    if !open_secrets(config_path).await?.remove(name)? {
+        anyhow::bail!("no secret named {name}");
+    }
+    cliclack::outro(format!("Removed {name}"))?;
+    Ok(())
+}
@@ -50,6 +50,21 @@ pub async fn run(config: &Config, store: &Store) -> bool {
 
 async fn check_database(store: &Store) -> CheckResult {
     // Try a simple query to verify the database is accessible.
+    if store.sqlite().is_err() {
+        let backend = store.backend().name();
+        return match store.get_all_facts_by_key("welcomed").await {
+            Ok(_) => CheckResult {
+                name: "Database".to_string(),
+                detail: format!("accessible ({backend})"),
+                ok: true,
+            },
+            Err(e) => CheckResult {
+                name: "Database".to_string(),
+                detail: format!("FAILED: {e}"),
+                ok: false,
+            },
+        };
+    }
     let detail = match store.db_size().await {
         Ok(size) => {
             let size_str = if size < 1024 {
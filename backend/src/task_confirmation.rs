@@ -13,6 +13,7 @@ use crate::i18n;
 pub enum MarkerResult {
     /// Task was successfully created in the database.
     TaskCreated {
+        id: String,
         description: String,
         due_at: String,
         repeat: String,
@@ -351,6 +352,7 @@ mod tests {
     #[test]
     fn test_format_task_confirmation_single_created() {
         let results = vec![MarkerResult::TaskCreated {
+            id: "t1".to_string(),
             description: "Call dentist".to_string(),
             due_at: "2026-02-25T10:00:00".to_string(),
             repeat: "once".to_string(),
@@ -365,12 +367,14 @@ mod tests {
     fn test_format_task_confirmation_multiple_created() {
         let results = vec![
             MarkerResult::TaskCreated {
+                id: "t1".to_string(),
                 description: "Task A".to_string(),
                 due_at: "2026-02-22T09:00:00".to_string(),
                 repeat: "daily".to_string(),
                 task_type: "reminder".to_string(),
             },
             MarkerResult::TaskCreated {
+                id: "t2".to_string(),
                 description: "Task B".to_string(),
                 due_at: "2026-02-25T10:00:00".to_string(),
                 repeat: "once".to_string(),
@@ -396,6 +400,7 @@ mod tests {
     #[test]
     fn test_format_task_confirmation_with_similar_warning() {
         let results = vec![MarkerResult::TaskCreated {
+            id: "t1".to_string(),
             description: "Cancel VPS".to_string(),
             due_at: "2026-03-15T09:00:00".to_string(),
             repeat: "once".to_string(),
@@ -468,6 +473,7 @@ mod tests {
         // When creates and cancels happen together, cancels are implicit replacements.
         let results = vec![
             MarkerResult::TaskCreated {
+                id: "t1".to_string(),
                 description: "New task".to_string(),
                 due_at: "2026-03-01T09:00:00".to_string(),
                 repeat: "once".to_string(),